@@ -0,0 +1,62 @@
+//  BUILD.rs
+//    by Lut99
+//
+//  Description:
+//!   Build script for the `macro-toolkit` crate.
+//!
+//!   This finds the version of the compiler for the `rustc_since!()`-macro. Because procedural
+//!   macros are compiled by the same compiler that later runs them, this is also the version of
+//!   the compiler compiling the macro's caller.
+//
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds the version of the compiler.
+///
+/// # Returns
+/// A tuple with the major, minor and patch version of `rustc`, or [`None`] if it could not be
+/// determined.
+fn rustc_version() -> Option<(u64, u64, u64)> {
+    let rustc: String = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Parse `rustc 1.2.3(-nightly) (...)`
+    let output: String = String::from_utf8(output.stdout).ok()?;
+    let version: &str = output.strip_prefix("rustc ")?.split([' ', '-']).next()?;
+    let mut parts = version.split('.').map(str::parse::<u64>);
+    let res = (parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?);
+    if parts.next().is_some() { None } else { Some(res) }
+}
+
+
+
+
+
+/***** ENTRYPOINT *****/
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rerun-if-env-changed=RUSTC");
+    if env::var_os("CARGO_FEATURE_RUSTC_SINCE").is_none() {
+        return;
+    }
+
+    // Write the version as a constant to include in the macro
+    let out: PathBuf = PathBuf::from(env::var_os("OUT_DIR").expect("Cargo should set OUT_DIR for build scripts")).join("rustc_version.rs");
+    let version: String = match rustc_version() {
+        Some((major, minor, patch)) => format!("Some(({major}, {minor}, {patch}))"),
+        None => {
+            println!("cargo::warning=Failed to determine the version of rustc; `rustc_since!()` will error when used");
+            "None".into()
+        },
+    };
+    fs::write(&out, format!("/// The version of the compiler as a (major, minor, patch)-tuple, if known.\nconst RUSTC_VERSION: Option<(u64, u64, u64)> = {version};\n"))
+        .unwrap_or_else(|err| panic!("Failed to write '{}': {err}", out.display()));
+}
@@ -0,0 +1,73 @@
+//  IDENTS.rs
+//    by Lut99
+//
+//  Description:
+//!   Benchmarks the engine of `idents!()` on large inputs, like the items wrapped by derive-style
+//!   macros.
+//
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use macro_toolkit_core::idents::idents;
+use proc_macro2::TokenStream;
+
+
+/***** HELPERS *****/
+/// Generates a large `impl`-block with methods.
+///
+/// # Arguments
+/// - `n`: The number of methods to generate.
+/// - `paste`: Whether the methods paste their names with `[< >]` and their type parameters with
+///   `{< >}`.
+///
+/// # Returns
+/// The generated [`TokenStream`].
+fn methods(n: usize, paste: bool) -> TokenStream {
+    let mut code = String::from("impl Foo {\n");
+    for i in 0..n {
+        let (name, params): (String, String) =
+            if paste { (format!("[<get_ field {i}>]"), "{<...a, b, c>}".into()) } else { (format!("get_field{i}"), "T0, T1, T2".into()) };
+        code.push_str(&format!(
+            "    pub fn {name}<{params}>(&self, values: [u8; {i}]) -> Option<Vec<(u8, u16)>> {{\n        let mut res = Vec::new();\n        for \
+             value in values {{ if value > 0 {{ res.push((value, u16::from(value) * {i})); }} }}\n        Some(res)\n    }}\n"
+        ));
+    }
+    code.push('}');
+    code.parse().unwrap()
+}
+
+/// Generates a deeply nested expression without anything to paste.
+///
+/// # Arguments
+/// - `depth`: The number of nested groups to generate.
+///
+/// # Returns
+/// The generated [`TokenStream`].
+fn nested(depth: usize) -> TokenStream {
+    let mut code = String::new();
+    for i in 0..depth {
+        code.push_str(&format!("{{ let x{i} = [({i}, "));
+    }
+    code.push('0');
+    for _ in 0..depth {
+        code.push_str(")]; }");
+    }
+    code.parse().unwrap()
+}
+
+
+
+
+
+/***** BENCHMARKS *****/
+fn bench_idents(c: &mut Criterion) {
+    let mut group = c.benchmark_group("idents");
+    for (name, input) in [("no_markers", methods(2000, false)), ("markers", methods(2000, true)), ("nested", nested(200))] {
+        group.bench_function(name, |b| b.iter_batched(|| input.clone(), |input| idents(input).unwrap(), BatchSize::LargeInput));
+    }
+    group.finish();
+}
+
+
+
+criterion_group!(benches, bench_idents);
+criterion_main!(benches);
@@ -0,0 +1,261 @@
+//  CURSOR.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines a cursor over tokens that is shared by the parsers of the macros, such that they
+//!   treat lookahead, invisible groups and the end of their input in the same way.
+//
+
+use std::rc::Rc;
+
+use proc_macro2::{Delimiter, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::Diagnostic2;
+
+
+/***** LIBRARY *****/
+/// Looks through the invisible groups around a single token.
+///
+/// Declarative macros wrap the tokens of most fragments (e.g., `$lit:literal`) in groups without
+/// delimiters, which are invisible in source code.
+///
+/// # Arguments
+/// - `tt`: The token to look through.
+///
+/// # Returns
+/// The token wrapped in the invisible groups, or `tt` itself if it isn't an invisible group with
+/// exactly one token.
+pub fn unwrap_invisible(tt: TokenTree) -> TokenTree {
+    match tt {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+            let mut tokens = group.stream().into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(tt), None) => unwrap_invisible(tt),
+                _ => TokenTree::Group(group),
+            }
+        },
+        tt => tt,
+    }
+}
+
+
+
+/// A cursor over a stream of tokens.
+///
+/// On top of iterating over them, it can look ahead, match multi-character punctuation (e.g.,
+/// `=>`) and enter invisible groups as if their tokens were given directly. Errors about missing
+/// tokens point to the next token, or to a given span at the end of the input.
+///
+/// Cursors are cheap to clone. A clone can be used to try parsing something, and only replace the
+/// original if it succeeds.
+#[derive(Clone)]
+pub struct Cursor {
+    /// The lists of tokens being parsed, each with the index of their next token. The last is the
+    /// innermost, and the others are the lists of the invisible groups containing it.
+    frames: Vec<(Rc<[TokenTree]>, usize)>,
+    /// The span to report errors at when there are no tokens left.
+    end:    Span,
+}
+impl Cursor {
+    /// Creates a new Cursor.
+    ///
+    /// # Arguments
+    /// - `tokens`: The [`TokenStream`] to parse.
+    /// - `end`: The [`Span`] to report errors at when there are no tokens left (e.g., the closing
+    ///   bracket of a group, or the call site of the macro).
+    ///
+    /// # Returns
+    /// A new Cursor pointing to the first token.
+    #[inline]
+    pub fn new(tokens: TokenStream, end: Span) -> Self { Self { frames: vec![(tokens.into_iter().collect(), 0)], end } }
+
+    /// Leaves any exhausted invisible groups, such that the next token is in the innermost list.
+    fn settle(&mut self) {
+        while self.frames.len() > 1 && self.frames.last().is_some_and(|(tokens, i)| *i >= tokens.len()) {
+            self.frames.pop();
+        }
+    }
+
+
+
+    /// Looks at a token ahead without consuming it.
+    ///
+    /// This does not enter invisible groups, but does look past the end of entered ones.
+    ///
+    /// # Arguments
+    /// - `n`: The number of tokens to skip (i.e., `0` is the next token).
+    ///
+    /// # Returns
+    /// The token, or [`None`] if there are not enough tokens left.
+    pub fn peek_nth(&self, mut n: usize) -> Option<&TokenTree> {
+        for (tokens, i) in self.frames.iter().rev() {
+            match tokens.get(*i + n) {
+                Some(tt) => return Some(tt),
+                None => n -= tokens.len().saturating_sub(*i),
+            }
+        }
+        None
+    }
+
+    /// Looks at the next token without consuming it.
+    ///
+    /// # Returns
+    /// The next token, or [`None`] if there are no tokens left.
+    #[inline]
+    pub fn peek(&self) -> Option<&TokenTree> { self.peek_nth(0) }
+
+    /// Checks whether there are any tokens left.
+    ///
+    /// # Returns
+    /// True if there are none, or false otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.peek().is_none() }
+
+    /// Finds the span of the next token, to report errors at.
+    ///
+    /// # Returns
+    /// The [`Span`] of the next token, or the end-span given to [`Cursor::new()`] if there are no
+    /// tokens left.
+    #[inline]
+    pub fn span(&self) -> Span { self.peek().map(TokenTree::span).unwrap_or(self.end) }
+
+    /// Creates an error pointing to the next token, or to the end of the input.
+    ///
+    /// # Arguments
+    /// - `message`: The message of the error.
+    ///
+    /// # Returns
+    /// A new [`Diagnostic2`] that can be given an error code or help messages.
+    #[inline]
+    pub fn error(&self, message: impl Into<String>) -> Diagnostic2 { Diagnostic2::error(self.span(), message) }
+
+
+
+    /// Enters any invisible groups at the head of the tokens, such that their contents are parsed
+    /// as if they were given directly.
+    pub fn enter_invisible(&mut self) {
+        while let Some(TokenTree::Group(group)) = self.peek() {
+            if group.delimiter() != Delimiter::None {
+                break;
+            }
+            let tokens: Rc<[TokenTree]> = group.stream().into_iter().collect();
+            self.bump();
+            self.frames.push((tokens, 0));
+            self.settle();
+        }
+    }
+
+    /// Skips the next token.
+    fn bump(&mut self) {
+        self.settle();
+        if let Some((tokens, i)) = self.frames.last_mut() {
+            *i = (*i + 1).min(tokens.len());
+        }
+        self.settle();
+    }
+
+    /// Consumes the next token, entering any invisible groups first.
+    ///
+    /// # Returns
+    /// The next token that isn't an invisible group, or [`None`] if there are no tokens left.
+    #[inline]
+    pub fn next_transparent(&mut self) -> Option<TokenTree> {
+        self.enter_invisible();
+        self.next()
+    }
+
+    /// Consumes the next token if it is the given punctuation.
+    ///
+    /// # Arguments
+    /// - `c`: The character of the punctuation.
+    ///
+    /// # Returns
+    /// The [`Punct`] if it was next, or [`None`] (without consuming anything) otherwise.
+    pub fn eat_punct(&mut self, c: char) -> Option<Punct> {
+        match self.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == c => {
+                let p: Punct = p.clone();
+                self.bump();
+                Some(p)
+            },
+            _ => None,
+        }
+    }
+
+    /// Checks whether the next tokens are the given multi-character punctuation (e.g., `=>`).
+    ///
+    /// # Arguments
+    /// - `op`: The punctuation to check for. All but its last character must be joint to the next.
+    ///
+    /// # Returns
+    /// True if the tokens are next, or false otherwise.
+    pub fn peek_op(&self, op: &str) -> bool {
+        let len: usize = op.chars().count();
+        op.chars()
+            .enumerate()
+            .all(|(i, c)| matches!(self.peek_nth(i), Some(TokenTree::Punct(p)) if p.as_char() == c && (i + 1 == len || p.spacing() == Spacing::Joint)))
+    }
+
+    /// Consumes the next tokens if they are the given multi-character punctuation (e.g., `=>`).
+    ///
+    /// # Arguments
+    /// - `op`: The punctuation to consume. All but its last character must be joint to the next.
+    ///
+    /// # Returns
+    /// True if the tokens were consumed, or false (without consuming anything) otherwise.
+    pub fn eat_op(&mut self, op: &str) -> bool {
+        if !self.peek_op(op) {
+            return false;
+        }
+        for _ in op.chars() {
+            self.bump();
+        }
+        true
+    }
+
+    /// Consumes the next token if it is an identifier.
+    ///
+    /// # Returns
+    /// The [`Ident`] if it was next, or [`None`] (without consuming anything) otherwise.
+    pub fn eat_ident(&mut self) -> Option<Ident> {
+        match self.peek() {
+            Some(TokenTree::Ident(ident)) => {
+                let ident: Ident = ident.clone();
+                self.bump();
+                Some(ident)
+            },
+            _ => None,
+        }
+    }
+
+    /// Consumes tokens up to the given punctuation.
+    ///
+    /// The punctuation itself is consumed too, but not returned. Punctuation nested in groups is
+    /// not considered.
+    ///
+    /// # Arguments
+    /// - `c`: The character of the punctuation to stop at.
+    ///
+    /// # Returns
+    /// The tokens before the punctuation, or all remaining tokens if it does not occur.
+    pub fn until_punct(&mut self, c: char) -> TokenStream {
+        let mut res = TokenStream::new();
+        for tt in self.by_ref() {
+            if matches!(&tt, TokenTree::Punct(p) if p.as_char() == c) {
+                break;
+            }
+            res.extend([tt]);
+        }
+        res
+    }
+}
+impl Iterator for Cursor {
+    type Item = TokenTree;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let tt: TokenTree = self.peek()?.clone();
+        self.bump();
+        Some(tt)
+    }
+}
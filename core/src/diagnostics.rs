@@ -0,0 +1,467 @@
+//  DIAGNOSTICS.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines errors and other diagnostics for procedural macros, from simple
+//!   [`compile_error!()`](::core::compile_error!)s to diagnostics with error codes, help messages
+//!   and notes pointing to other spans (like the compiler's own "first defined here"-notes).
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates an attribute (i.e., `#[...]`) with a single call-like meta (e.g., `allow(...)`).
+///
+/// # Arguments
+/// - `name`: The name of the attribute.
+/// - `args`: The arguments of the attribute.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the attribute.
+#[cfg(not(feature = "nightly_diagnostics"))]
+fn attribute(name: &str, args: TokenStream) -> TokenStream {
+    let span: Span = Span::call_site();
+    let meta = TokenStream::from_iter([TokenTree::Ident(Ident::new(name, span)), TokenTree::Group(Group::new(Delimiter::Parenthesis, args))]);
+    TokenStream::from_iter([TokenTree::Punct(Punct::new('#', Spacing::Alone)), TokenTree::Group(Group::new(Delimiter::Bracket, meta))])
+}
+
+/// Generates a [`TokenStream`] encoding a warning on stable.
+///
+/// Stable Rust has no way for procedural macros to emit warnings, so this generates the use of a
+/// deprecated item of which the deprecation note is the message.
+///
+/// # Arguments
+/// - `span`: Some [`Span`] to have the warning point to.
+/// - `message`: Some message to show with the warning.
+///
+/// # Returns
+/// A [`TokenStream`] encoding an item that produces the warning when compiled.
+#[cfg(not(feature = "nightly_diagnostics"))]
+fn warning2(span: Span, message: &str) -> TokenStream {
+    // Generate `#[deprecated(note = $message)] #[allow(non_camel_case_types)] struct warning;`
+    let call: Span = Span::call_site();
+    let mut body: TokenStream = attribute(
+        "deprecated",
+        TokenStream::from_iter([
+            TokenTree::Ident(Ident::new("note", call)),
+            TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+            TokenTree::Literal(Literal::string(message)),
+        ]),
+    );
+    body.extend(attribute("allow", TokenTree::Ident(Ident::new("non_camel_case_types", call)).into()));
+    body.extend([
+        TokenTree::Ident(Ident::new("struct", call)),
+        TokenTree::Ident(Ident::new("warning", call)),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ]);
+
+    // Use it with the given span, which is where the warning points to: `let _ = warning;`
+    body.extend([
+        TokenTree::Ident(Ident::new("let", call)),
+        TokenTree::Ident(Ident::new("_", call)),
+        TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("warning", span)),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ]);
+
+    // Wrap it in `const _: () = { ... };`
+    TokenStream::from_iter([
+        TokenTree::Ident(Ident::new("const", call)),
+        TokenTree::Ident(Ident::new("_", call)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::new())),
+        TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Brace, body)),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ])
+}
+
+/// Generates a [`TokenStream`] encoding a [`compile_error!()`](::core::compile_error!).
+///
+/// # Arguments
+/// - `span`: Some [`Span`] to have the error point to.
+/// - `message`: Some message to show with the error.
+///
+/// # Returns
+/// A [`TokenStream`] that encodes a [`compile_error!()`](::core::compile_error!) pointing to your
+/// `span` with your `message`.
+fn compile_error2(span: Span, message: &str) -> TokenStream {
+    let mut tts: [TokenTree; 8] = [
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("core", span)),
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenTree::Literal(Literal::string(message)).into())),
+    ];
+    for tt in &mut tts {
+        tt.set_span(span);
+    }
+    TokenStream::from_iter(tts)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Creates an error.
+///
+/// This is a shorthand for a [`Diagnostic2`] without an error code or children, and is what all
+/// macros use to report their errors.
+///
+/// Note that the error is not emitted until [`Diagnostic2::emit()`] is called, which macros should
+/// only do once they are sure to fail. Otherwise, errors that are discarded (e.g., when trying to
+/// parse something in multiple ways) would still end up with the user when using the
+/// `nightly_diagnostics`-feature.
+///
+/// # Arguments
+/// - `span`: Some [`Span`] to have the error point to.
+/// - `message`: Some message to show with the error.
+///
+/// # Returns
+/// A new [`Diagnostic2`] pointing to your `span` with your `message`.
+#[inline]
+pub fn error2(span: Span, message: &str) -> Diagnostic2 { Diagnostic2::error(span, message) }
+
+/// Makes an error valid in item position when emitted, for attribute macros and macros that are
+/// called in item position.
+///
+/// # Arguments
+/// - `err`: The [`Diagnostic2`] to make valid in item position.
+///
+/// # Returns
+/// The same [`Diagnostic2`], which is emitted with a semicolon appended if it doesn't end with one
+/// already.
+#[inline]
+pub fn item_error(mut err: Diagnostic2) -> Diagnostic2 {
+    err.0.item = true;
+    err
+}
+
+
+
+/// Defines the levels of diagnostics.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Level {
+    /// An error, which fails the build.
+    Error,
+    /// A warning, which does not fail the build.
+    Warning,
+    /// A note, which gives additional information.
+    Note,
+    /// A help message, which suggests a fix.
+    Help,
+}
+impl Display for Level {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+            Self::Note => write!(f, "note"),
+            Self::Help => write!(f, "help"),
+        }
+    }
+}
+
+
+
+/// Defines a diagnostic with an optional error code and any number of notes and help messages,
+/// each optionally pointing to their own span.
+///
+/// The error code (e.g., `MTK0003`) is shown in front of the message, as `[MTK0003] ...`. The
+/// codes of the macros are explained in the "Error codes"-section of `macro-toolkit`'s
+/// documentation.
+///
+/// On stable, a diagnostic is emitted as one [`compile_error!()`](::core::compile_error!) (or
+/// deprecation warning) for its own span and one for every child with a span. Children without a
+/// span are appended to the message instead. With the `nightly_diagnostics`-feature, it is emitted
+/// as a native [`proc_macro::Diagnostic`].
+///
+/// Multiple diagnostics can be emitted at once with [`Diagnostic2::and()`].
+#[derive(Clone, Debug)]
+pub struct Diagnostic2(Box<Inner>);
+
+/// Defines the contents of a [`Diagnostic2`], which are boxed to keep errors small.
+#[derive(Clone, Debug)]
+struct Inner {
+    /// The level of the diagnostic.
+    level:    Level,
+    /// The error code of the diagnostic, if any.
+    code:     Option<&'static str>,
+    /// The span the diagnostic points to.
+    span:     Span,
+    /// The message of the diagnostic.
+    message:  String,
+    /// The notes and help messages attached to the diagnostic.
+    children: Vec<(Level, Option<Span>, String)>,
+    /// Other diagnostics to emit after this one.
+    others:   Vec<Diagnostic2>,
+    /// Tokens to emit after the diagnostics.
+    tokens:   Vec<TokenTree>,
+    /// Whether the diagnostic is emitted in item position, and so must end with a semicolon.
+    item:     bool,
+}
+impl Diagnostic2 {
+    /// Creates a new Diagnostic2.
+    ///
+    /// # Arguments
+    /// - `level`: The [`Level`] of the diagnostic.
+    /// - `span`: The [`Span`] to have the diagnostic point to.
+    /// - `message`: The message of the diagnostic.
+    ///
+    /// # Returns
+    /// A new Diagnostic2 without any children.
+    #[inline]
+    pub fn new(level: Level, span: Span, message: impl Into<String>) -> Self {
+        Self(Box::new(Inner { level, code: None, span, message: message.into(), children: Vec::new(), others: Vec::new(), tokens: Vec::new(), item: false }))
+    }
+
+    /// Creates a new error.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] to have the error point to.
+    /// - `message`: The message of the error.
+    ///
+    /// # Returns
+    /// A new Diagnostic2 without any children.
+    #[inline]
+    pub fn error(span: Span, message: impl Into<String>) -> Self { Self::new(Level::Error, span, message) }
+
+    /// Creates a new warning.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] to have the warning point to.
+    /// - `message`: The message of the warning.
+    ///
+    /// # Returns
+    /// A new Diagnostic2 without any children.
+    #[inline]
+    pub fn warning(span: Span, message: impl Into<String>) -> Self { Self::new(Level::Warning, span, message) }
+
+    /// Sets the error code of this diagnostic.
+    ///
+    /// # Arguments
+    /// - `code`: The error code (e.g., `MTK0003`).
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn code(mut self, code: &'static str) -> Self {
+        self.0.code = Some(code);
+        self
+    }
+
+    /// Attaches a note or help message to this diagnostic.
+    ///
+    /// # Arguments
+    /// - `level`: The [`Level`] of the child. Should be [`Level::Note`] or [`Level::Help`].
+    /// - `span`: The [`Span`] to have the child point to, if any.
+    /// - `message`: The message of the child.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn child(mut self, level: Level, span: Option<Span>, message: impl Into<String>) -> Self {
+        self.0.children.push((level, span, message.into()));
+        self
+    }
+
+    /// Attaches a note pointing to another span (e.g., "first defined here") to this diagnostic.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] to have the note point to.
+    /// - `message`: The message of the note.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn span_note(self, span: Span, message: impl Into<String>) -> Self { self.child(Level::Note, Some(span), message) }
+
+    /// Attaches a help message pointing to another span (e.g., to suggest a fix there) to this
+    /// diagnostic.
+    ///
+    /// # Arguments
+    /// - `span`: The [`Span`] to have the help message point to.
+    /// - `message`: The help message.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn span_help(self, span: Span, message: impl Into<String>) -> Self { self.child(Level::Help, Some(span), message) }
+
+    /// Attaches a help message without a span to this diagnostic.
+    ///
+    /// # Arguments
+    /// - `message`: The help message.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn help(self, message: impl Into<String>) -> Self { self.child(Level::Help, None, message) }
+
+    /// Attaches another diagnostic to emit after this one, for reporting multiple errors at once.
+    ///
+    /// # Arguments
+    /// - `other`: The other Diagnostic2.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn and(mut self, mut other: Self) -> Self {
+        self.0.item |= other.0.item;
+        self.0.tokens.extend(std::mem::take(&mut other.0.tokens));
+        self.0.others.push(other);
+        self
+    }
+
+    /// Attaches tokens to emit after this diagnostic (e.g., the item given to an attribute macro,
+    /// to avoid follow-up errors for it missing).
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens to emit.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub fn with_tokens(mut self, tokens: impl IntoIterator<Item = TokenTree>) -> Self {
+        self.0.tokens.extend(tokens);
+        self
+    }
+
+    /// Emits this diagnostic and the others attached to it.
+    ///
+    /// Macros should only call this once they are sure to fail (i.e., when returning the error
+    /// from the macro itself).
+    ///
+    /// # Returns
+    /// A [`TokenStream`] that produces the diagnostics when compiled, followed by the attached
+    /// tokens. A lone error without notes pointing to other spans is a single
+    /// `compile_error!(...)`, which can be used in any position. Otherwise, it consists of items
+    /// (i.e., `compile_error!(...);` or `const _: () = ...;`). With the
+    /// `nightly_diagnostics`-feature, the diagnostics are emitted natively and only the
+    /// `compile_error!(...)`s for errors without children are kept, which the compiler merges with
+    /// the native ones.
+    pub fn emit(mut self) -> TokenStream {
+        let (others, tokens): (Vec<Self>, Vec<TokenTree>) = (std::mem::take(&mut self.0.others), std::mem::take(&mut self.0.tokens));
+
+        // NOTE: Multiple diagnostics can only be given in item position, so separate them with
+        // semicolons
+        let item: bool = self.0.item || !others.is_empty();
+        let mut res = TokenStream::new();
+        for diag in std::iter::once(self).chain(others) {
+            let mut diag: TokenStream = diag.emit_one();
+            if item && !diag.is_empty() && !matches!(diag.clone().into_iter().last(), Some(TokenTree::Punct(p)) if p.as_char() == ';') {
+                diag.extend([TokenTree::Punct(Punct::new(';', Spacing::Alone))]);
+            }
+            res.extend(diag);
+        }
+        res.extend(tokens);
+        res
+    }
+
+    /// Emits a single diagnostic, ignoring the others attached to it and its tokens.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] that produces the diagnostic when compiled.
+    #[cfg(not(feature = "nightly_diagnostics"))]
+    fn emit_one(self) -> TokenStream {
+        let inner: Inner = *self.0;
+
+        // Prefix the code and append the children without a span to the message
+        let mut message: String = match inner.code {
+            Some(code) => format!("[{code}] {}", inner.message),
+            None => inner.message,
+        };
+        for (level, span, child) in &inner.children {
+            if span.is_none() {
+                message.push_str(&format!("\n= {level}: {child}"));
+            }
+        }
+
+        // Then emit the diagnostics themselves
+        // NOTE: A lone error is emitted without a semicolon, such that it can also be used in
+        // expression position like any other `compile_error!()`
+        let lone: bool = inner.level == Level::Error && inner.children.iter().all(|(_, span, _)| span.is_none());
+        let emit = |span: Span, message: &str| -> TokenStream {
+            if inner.level == Level::Error {
+                let mut res: TokenStream = compile_error2(span, message);
+                if !lone {
+                    res.extend([TokenTree::Punct(Punct::new(';', Spacing::Alone))]);
+                }
+                res
+            } else {
+                warning2(span, message)
+            }
+        };
+        let mut res: TokenStream =
+            emit(inner.span, &if matches!(inner.level, Level::Note | Level::Help) { format!("{}: {message}", inner.level) } else { message });
+        for (level, span, child) in &inner.children {
+            if let Some(span) = span {
+                res.extend(emit(*span, &format!("{level}: {child}")));
+            }
+        }
+        res
+    }
+    /// Emits a single diagnostic natively, ignoring the others attached to it and its tokens.
+    ///
+    /// # Returns
+    /// The `compile_error!(...)` equivalent to the diagnostic if it's an error without children,
+    /// or an empty [`TokenStream`] otherwise.
+    #[cfg(feature = "nightly_diagnostics")]
+    fn emit_one(self) -> TokenStream {
+        let inner: Inner = *self.0;
+
+        let level = |level: Level| -> proc_macro::Level {
+            match level {
+                Level::Error => proc_macro::Level::Error,
+                Level::Warning => proc_macro::Level::Warning,
+                Level::Note => proc_macro::Level::Note,
+                Level::Help => proc_macro::Level::Help,
+            }
+        };
+        let message: String = match inner.code {
+            Some(code) => format!("[{code}] {}", inner.message),
+            None => inner.message,
+        };
+        // NOTE: A lone error is also returned as the equivalent `compile_error!()`, which the
+        // compiler merges with the native diagnostic. This keeps the macro's output valid in any
+        // position, instead of e.g. complaining about a missing expression.
+        let res: TokenStream = if inner.level == Level::Error && inner.children.is_empty() { compile_error2(inner.span, &message) } else { TokenStream::new() };
+        let mut diag = proc_macro::Diagnostic::spanned(inner.span.unwrap(), level(inner.level), message);
+        for (child, span, message) in inner.children {
+            diag = match (child, span) {
+                (Level::Help, Some(span)) => diag.span_help(span.unwrap(), message),
+                (Level::Help, None) => diag.help(message),
+                (Level::Warning, Some(span)) => diag.span_warning(span.unwrap(), message),
+                (Level::Warning, None) => diag.warning(message),
+                (Level::Error, Some(span)) => diag.span_error(span.unwrap(), message),
+                (Level::Error, None) => diag.error(message),
+                (Level::Note, Some(span)) => diag.span_note(span.unwrap(), message),
+                (Level::Note, None) => diag.note(message),
+            };
+        }
+        diag.emit();
+        res
+    }
+}
+impl Display for Diagnostic2 {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self.0.code {
+            Some(code) => write!(f, "{}: [{code}] {}", self.0.level, self.0.message),
+            None => write!(f, "{}: {}", self.0.level, self.0.message),
+        }
+    }
+}
+impl From<Diagnostic2> for proc_macro::TokenStream {
+    #[inline]
+    fn from(value: Diagnostic2) -> Self { value.emit().into() }
+}
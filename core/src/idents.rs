@@ -0,0 +1,627 @@
+//  IDENTIFIERS.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines a more powerful alternative for the excellent `paste!()`-macro.
+//
+
+use std::collections::HashMap;
+
+use proc_macro2::{Delimiter, Group, Ident, Spacing, Span, TokenStream, TokenTree};
+
+use crate::cursor::Cursor;
+use crate::diagnostics::{Diagnostic2, item_error};
+
+
+/***** PASTE TOKEN PARSING *****/
+/// Parses the contents of a `[]`.
+///
+/// Either this recognizes nothing, passing [`None`] back, or else it will recognize it as `[<>]`
+/// and paste its contents into a single identifier.
+///
+/// # Arguments
+/// - `group`: The [`Group`] to parse.
+///
+/// # Returns
+/// A [`Result`] encoding a successfully parsed identifier or a reason why it was illegal; or
+/// [`None`] if the inside didn't start with `<` and end with `>` (i.e., it's not a macro).
+fn parse_bracket_contents(group: &Group) -> Option<Result<Ident, Diagnostic2>> {
+    // Check if it is wrapped in `<>`
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    match (tokens.first(), tokens.last()) {
+        (Some(TokenTree::Punct(open)), Some(TokenTree::Punct(close))) if tokens.len() >= 2 && open.as_char() == '<' && close.as_char() == '>' => {},
+        _ => return None,
+    }
+
+    // It does. The remainder are the identifier things
+    Some(paste_ident(tokens[1..tokens.len() - 1].iter().cloned().collect(), group.span()))
+}
+
+/// Pastes the given tokens into a single identifier.
+///
+/// Invisible groups (e.g., from `$name:ident`) are pasted as if their tokens were given directly.
+///
+/// # Arguments
+/// - `cursor`: The [`Cursor`] yielding the tokens to paste.
+/// - `name`: The identifier to paste to.
+/// - `span`: The span of the identifier, which is set to that of the first token if not set yet.
+///
+/// # Errors
+/// This function errors if any of the tokens cannot be pasted.
+fn paste(cursor: &mut Cursor, name: &mut String, span: &mut Option<Span>) -> Result<(), Diagnostic2> {
+    while let Some(token) = cursor.next_transparent() {
+        match token {
+            // Identifiers...
+            TokenTree::Ident(ident) => {
+                let value: String = ident.to_string();
+                name.push_str(value.strip_prefix("r#").unwrap_or(&value));
+                span.get_or_insert(ident.span());
+            },
+            // Literals...
+            TokenTree::Literal(lit) => {
+                let value: String = lit.to_string();
+                let value: &str = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                    Some(value) if !value.contains('\\') => value,
+                    Some(_) => {
+                        return Err(Diagnostic2::error(lit.span(), "Cannot paste string literals with escape sequences")
+                            .code("MTK0001")
+                            .help("write the characters themselves instead of escaping them"));
+                    },
+                    None => &value,
+                };
+                name.push_str(value);
+                span.get_or_insert(lit.span());
+            },
+
+            // Types in parenthesis
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                name.push_str(&flatten_type(group.stream(), "_", "_", group.span())?);
+                span.get_or_insert(group.span());
+            },
+
+            // The rest cannot be pasted
+            token => {
+                return Err(Diagnostic2::error(token.span(), "Expected an identifier, a literal, a type in parenthesis or '>'").code("MTK0002"));
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Checks whether the given tokens contain the given punctuation outside of any angle brackets.
+///
+/// # Arguments
+/// - `tokens`: The tokens to check.
+/// - `c`: The character to look for.
+///
+/// # Returns
+/// True if `c` appears at the top level, or false otherwise.
+fn has_top_level(tokens: &TokenStream, c: char) -> bool {
+    let mut depth: usize = 0;
+    let mut arrow: bool = false;
+    for tt in tokens.clone() {
+        if let TokenTree::Punct(p) = tt {
+            match p.as_char() {
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                p if p == c && depth == 0 => return true,
+                _ => {},
+            }
+            arrow = p.as_char() == '-' && p.spacing() == Spacing::Joint;
+        } else {
+            arrow = false;
+        }
+    }
+    false
+}
+
+/// Flattens a type into a name that can be pasted into an identifier.
+///
+/// The identifiers and literals in the type are kept and joined by separators. Lifetimes are
+/// removed, and references, pointers, slices, arrays, tuples, unit and never are spelled out (e.g.,
+/// `&'a [u8; 4]` becomes `ref_array_u8_4`).
+///
+/// # Arguments
+/// - `tokens`: The tokens of the type.
+/// - `sep`: The separator to put between the parts of the type.
+/// - `path_sep`: The separator to put between the segments of a path (i.e., instead of `::`).
+/// - `span`: The span to report errors at if the type is empty.
+///
+/// # Returns
+/// The flattened type (e.g., `Vec_Option_u8` for `Vec<Option<u8>>`).
+///
+/// # Errors
+/// This function errors if the type is empty or contains string literals with escape sequences.
+pub fn flatten_type(tokens: TokenStream, sep: &str, path_sep: &str, span: Span) -> Result<String, Diagnostic2> {
+    /// Flattens the tokens into a list of words, each with the separator that precedes it.
+    fn flatten<'s>(
+        tokens: TokenStream,
+        sep: &'s str,
+        path_sep: &'s str,
+        words: &mut Vec<(&'s str, String)>,
+        pending: &mut Option<&'s str>,
+    ) -> Result<(), Diagnostic2> {
+        let mut cursor = Cursor::new(tokens, Span::call_site());
+        let mut prev_ident: bool = false;
+        loop {
+            cursor.enter_invisible();
+            let is_ident: bool = matches!(cursor.peek(), Some(TokenTree::Ident(_)));
+            // Path separators
+            if cursor.eat_op("::") {
+                pending.get_or_insert(path_sep);
+                prev_ident = false;
+                continue;
+            }
+            // Function return types
+            if cursor.eat_op("->") {
+                words.push((pending.take().unwrap_or(sep), "ret".into()));
+                prev_ident = false;
+                continue;
+            }
+
+            let Some(tt) = cursor.next() else { break };
+            let word: Option<String> = match tt {
+                TokenTree::Ident(ident) => {
+                    let value: String = ident.to_string();
+                    Some(value.strip_prefix("r#").unwrap_or(&value).into())
+                },
+                TokenTree::Literal(lit) => {
+                    let value: String = lit.to_string();
+                    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                        Some(value) if !value.contains('\\') => Some(value.into()),
+                        Some(_) => {
+                            return Err(Diagnostic2::error(lit.span(), "Cannot paste string literals with escape sequences")
+                                .code("MTK0001")
+                                .help("write the characters themselves instead of escaping them"));
+                        },
+                        None => Some(value),
+                    }
+                },
+
+                // Lifetimes are omitted
+                TokenTree::Punct(p) if p.as_char() == '\'' => {
+                    cursor.next();
+                    None
+                },
+                // Spelled-out punctuation
+                TokenTree::Punct(p) if p.as_char() == '&' => Some("ref".into()),
+                TokenTree::Punct(p) if p.as_char() == '*' => Some("ptr".into()),
+                TokenTree::Punct(p) if p.as_char() == '!' => Some("never".into()),
+                TokenTree::Punct(p) if p.as_char() == '-' => Some("neg".into()),
+                // Any other punctuation only separates
+                TokenTree::Punct(_) => {
+                    *pending = Some(sep);
+                    None
+                },
+
+                // Groups
+                TokenTree::Group(group) => {
+                    let stream: TokenStream = group.stream();
+                    let kind: Option<&str> = match group.delimiter() {
+                        Delimiter::Bracket if has_top_level(&stream, ';') => Some("array"),
+                        Delimiter::Bracket => Some("slice"),
+                        Delimiter::Parenthesis if stream.is_empty() => Some("unit"),
+                        // NOTE: Parenthesis after an identifier are arguments (e.g., `Fn(u8)`)
+                        Delimiter::Parenthesis if !prev_ident && has_top_level(&stream, ',') => Some("tuple"),
+                        _ => None,
+                    };
+                    if let Some(kind) = kind {
+                        words.push((pending.take().unwrap_or(sep), kind.into()));
+                    }
+                    flatten(stream, sep, path_sep, words, pending)?;
+                    None
+                },
+            };
+            if let Some(word) = word {
+                words.push((pending.take().unwrap_or(sep), word));
+            }
+            prev_ident = is_ident;
+        }
+        Ok(())
+    }
+
+    let mut words: Vec<(&str, String)> = Vec::new();
+    flatten(tokens, sep, path_sep, &mut words, &mut None)?;
+    if words.is_empty() {
+        return Err(Diagnostic2::error(span, "Expected a type to flatten").code("MTK0004"));
+    }
+    let mut res = String::new();
+    for (i, (sep, word)) in words.into_iter().enumerate() {
+        if i > 0 {
+            res.push_str(sep);
+        }
+        res.push_str(&word);
+    }
+    Ok(res)
+}
+
+/// Pastes the given tokens into a single, valid identifier.
+///
+/// # Arguments
+/// - `tokens`: The tokens to paste.
+/// - `span`: The span to report errors at if there are no tokens to paste.
+///
+/// # Returns
+/// The pasted identifier, with the span of the first token.
+///
+/// # Errors
+/// This function errors if any of the tokens cannot be pasted, or if the result is not a valid
+/// identifier.
+pub fn paste_ident(tokens: TokenStream, span: Span) -> Result<Ident, Diagnostic2> {
+    let mut name = String::new();
+    let mut first: Option<Span> = None;
+    paste(&mut Cursor::new(tokens, span), &mut name, &mut first)?;
+    new_ident(&name, first, span)
+}
+
+/// Creates an identifier from a pasted name, ensuring it is valid.
+///
+/// # Arguments
+/// - `name`: The pasted name.
+/// - `first`: The span of the first pasted token, if any.
+/// - `span`: The span to report errors at if the name is empty.
+///
+/// # Returns
+/// The identifier, with the span of the first token (or `span` if there is none).
+///
+/// # Errors
+/// This function errors if the name is not a valid identifier.
+pub fn new_ident(name: &str, first: Option<Span>, span: Span) -> Result<Ident, Diagnostic2> {
+    let first: Span = first.unwrap_or(span);
+
+    // Ensure it's a valid identifier
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c == '_' || c.is_alphabetic() => {},
+        Some(_) => {
+            return Err(Diagnostic2::error(first, format!("Pasted identifier `{name}` must start with a letter or '_'"))
+                .code("MTK0003")
+                .help("paste a letter or '_' in front of it (e.g., `[<_ ...>]`)"));
+        },
+        None => return Err(Diagnostic2::error(span, "Expected at least one identifier or literal to paste").code("MTK0004")),
+    }
+    if let Some(c) = chars.find(|c| *c != '_' && !c.is_alphanumeric()) {
+        return Err(Diagnostic2::error(first, format!("Pasted identifier `{name}` cannot contain '{c}'"))
+            .code("MTK0003")
+            .help("identifiers may only contain letters, digits and '_'"));
+    }
+    Ok(Ident::new(name, first))
+}
+
+
+
+
+
+/***** GENERIC TOKEN PARSING *****/
+/// Represents the "pattern" at the start that determines how to serialize identifiers.
+enum Pat {
+    Ident(String),
+    Placeholder,
+}
+
+
+
+/// Parses a pattern (e.g., `T$`) from a stream if it's there.
+///
+/// Always parses `...` at the end. Invisible groups (e.g., from `$prefix:ident`) are parsed as if
+/// their tokens were given directly.
+///
+/// # Arguments
+/// - `cursor`: The [`Cursor`] yielding the remaining tokens.
+///
+/// # Returns
+/// The parsed pattern, which is empty if there was none.
+///
+/// # Errors
+/// This function errors if the pattern is invalid or not followed by three dots.
+fn parse_pattern_and_dots(cursor: &mut Cursor) -> Result<Vec<Pat>, Diagnostic2> {
+    let mut dot_count: usize = 0;
+    let mut pat: Vec<Pat> = Vec::new();
+    while let Some(token) = cursor.next_transparent() {
+        match token {
+            // Parse identifiers and others
+            TokenTree::Ident(ident) => {
+                if dot_count > 0 {
+                    return Err(Diagnostic2::error(ident.span(), "Expected three dots to end pattern").code("MTK0005"));
+                }
+                if let Some(Pat::Ident(name)) = pat.last_mut() {
+                    name.push_str(&ident.to_string());
+                } else {
+                    pat.push(Pat::Ident(ident.to_string()));
+                }
+            },
+            TokenTree::Literal(lit) => {
+                if dot_count > 0 {
+                    return Err(Diagnostic2::error(lit.span(), "Expected three dots to end pattern").code("MTK0005"));
+                }
+                if let Some(Pat::Ident(name)) = pat.last_mut() {
+                    name.push_str(&lit.to_string());
+                } else {
+                    pat.push(Pat::Ident(lit.to_string()));
+                }
+            },
+
+            // Parse the placeholder
+            TokenTree::Punct(punct) if punct.as_char() == '@' => {
+                if dot_count > 0 {
+                    return Err(Diagnostic2::error(punct.span(), "Expected three dots to end pattern").code("MTK0005"));
+                }
+                pat.push(Pat::Placeholder);
+            },
+
+            // Parse the three dots
+            TokenTree::Punct(punct) if punct.as_char() == '.' => {
+                dot_count += 1;
+                if dot_count == 3 {
+                    break;
+                }
+            },
+
+            // The rest is just unexpected
+            token => return Err(Diagnostic2::error(token.span(), "Expected identifier pattern OR three dots before ident list").code("MTK0005")),
+        }
+    }
+
+    // Double check we've had all dots
+    if dot_count != 3 {
+        return Err(cursor.error("Expected three dots before ident list").code("MTK0005"));
+    }
+
+    // Done
+    Ok(pat)
+}
+
+/// Parses the contents of a `{}`.
+///
+/// Either this recognizes nothing, passing [`None`] back, or else it will recognize it as `{<>}`
+/// and do the generic identifier generation.
+///
+/// # Arguments
+/// - `group`: The [`Group`] to parse.
+///
+/// # Returns
+/// A [`Result`] encoding a stream of identifiers or a reason why it was illegal; or
+/// [`None`] if the inside didn't start with `<` (i.e., it's not a macro).
+fn parse_brace_contents(group: &Group) -> Option<Result<TokenStream, Diagnostic2>> {
+    // Check if it begins with `<`
+    let mut cursor = Cursor::new(group.stream(), group.span_close());
+    cursor.eat_punct('<')?;
+
+    // Optionally, parse the pattern
+    let pat: Vec<Pat> = match parse_pattern_and_dots(&mut cursor) {
+        Ok(pat) => pat,
+        Err(err) => return Some(Err(err)),
+    };
+
+    // It does. The remainder of the iterator is our contents, ending with `>`
+    let mut i: usize = 0;
+    let mut output = TokenStream::new();
+    for token in &mut cursor {
+        // Check if we need to stop
+        match token {
+            // Pop commas
+            TokenTree::Punct(punct) if punct.as_char() == ',' => output.extend([TokenTree::Punct(punct)]),
+            // Stop token
+            TokenTree::Punct(punct) if punct.as_char() == '>' => break,
+
+            // The rest maps one-to-one to identifiers
+            // We rely on macro rules to give like, invisible groups here to pass e.g. expressions
+            token => {
+                // Build the identifier first
+                let ident: Ident = if !pat.is_empty() {
+                    let si: String = i.to_string();
+                    let mut name = String::new();
+                    for pat in &pat {
+                        match pat {
+                            Pat::Ident(n) => {
+                                name.push_str(n);
+                            },
+                            Pat::Placeholder => {
+                                name.push_str(&si);
+                            },
+                        }
+                    }
+                    Ident::new(&name, token.span())
+                } else {
+                    Ident::new(&format!("T{i}"), token.span())
+                };
+
+                // Now add it as the replacement
+                output.extend([TokenTree::Ident(ident)]);
+                i += 1
+            },
+        }
+    }
+    if !cursor.is_empty() {
+        return Some(Err(cursor.error("Expected nothing after '>'").code("MTK0002")));
+    }
+
+    // Done
+    Some(Ok(output))
+}
+
+
+
+
+
+/***** ATTRIBUTE PARSING *****/
+/// Parses the placeholders given to the `#[with_idents]`-attribute.
+///
+/// # Arguments
+/// - `attr`: The [`TokenStream`] given to the attribute to parse.
+///
+/// # Returns
+/// A map of placeholder names to the identifiers they should be replaced with.
+///
+/// # Errors
+/// This function can error if the input was invalid, or if any of the values failed to expand.
+fn parse_placeholders(attr: TokenStream) -> Result<HashMap<String, TokenStream>, Diagnostic2> {
+    let mut placeholders: HashMap<String, TokenStream> = HashMap::new();
+    let mut cursor = Cursor::new(attr, Span::call_site());
+    while !cursor.is_empty() {
+        // Parse the name
+        let name: Ident = cursor.eat_ident().ok_or_else(|| cursor.error("Expected the name of a placeholder").code("MTK0006"))?;
+        if placeholders.contains_key(&name.to_string()) {
+            return Err(Diagnostic2::error(name.span(), format!("Placeholder `{name}` is given more than once")).code("MTK0006"));
+        }
+
+        // Parse the `=`
+        if cursor.eat_punct('=').is_none() {
+            return Err(cursor.error("Expected '='").code("MTK0006"));
+        }
+
+        // Parse the value up to the next comma
+        let value: TokenStream = cursor.until_punct(',');
+        if value.is_empty() {
+            return Err(Diagnostic2::error(name.span(), "Expected a value after '='").code("MTK0006"));
+        }
+        placeholders.insert(name.to_string(), idents(value)?);
+    }
+    Ok(placeholders)
+}
+
+/// Replaces all placeholders in the given tokens with their values.
+///
+/// # Arguments
+/// - `tokens`: The [`TokenStream`] to replace the placeholders in.
+/// - `placeholders`: A map from placeholder names to the values to replace them with.
+///
+/// # Returns
+/// The same tokens, but with all placeholders replaced.
+fn substitute(tokens: TokenStream, placeholders: &HashMap<String, TokenStream>) -> TokenStream {
+    let mut output = TokenStream::new();
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => match placeholders.get(&ident.to_string()) {
+                Some(value) => output.extend(value.clone()),
+                None => output.extend([TokenTree::Ident(ident)]),
+            },
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), substitute(group.stream(), placeholders));
+                new.set_span(group.span());
+                output.extend([TokenTree::Group(new)]);
+            },
+            token => output.extend([token]),
+        }
+    }
+    output
+}
+
+
+
+
+
+/***** REWRITING *****/
+/// Rewrites the given tokens in a single pass, pasting and generating identifiers at any depth.
+///
+/// Every token is visited, but the rewritten tokens are pushed to a buffer shared by all depths
+/// that is only turned into a new [`TokenStream`] for groups that actually changed.
+///
+/// # Arguments
+/// - `tokens`: The tokens to rewrite.
+/// - `buf`: The buffer to push the rewritten tokens to.
+///
+/// # Returns
+/// Whether any of the tokens were changed. If not, the pushed tokens are the same as `tokens`.
+///
+/// # Errors
+/// This function may error if the input in between `[<` and `>]` or `{<` and `>}` is invalid.
+fn rewrite(tokens: impl Iterator<Item = TokenTree>, buf: &mut Vec<TokenTree>) -> Result<bool, Diagnostic2> {
+    let mut changed: bool = false;
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => changed |= rewrite_group(group, buf)?,
+            token => buf.push(token),
+        }
+    }
+    Ok(changed)
+}
+
+/// Rewrites a single group, pasting and generating identifiers at any depth.
+///
+/// Only groups starting with `<` are inspected further as `[< >]` or `{< >}`. Other groups without
+/// anything to paste or generate in them are kept as-is instead of being rebuilt.
+///
+/// # Arguments
+/// - `group`: The [`Group`] to rewrite.
+/// - `buf`: The buffer to push the rewritten tokens to.
+///
+/// # Returns
+/// Whether the group was changed. If not, the group itself is pushed.
+///
+/// # Errors
+/// This function may error if the input in between `[<` and `>]` or `{<` and `>}` is invalid.
+fn rewrite_group(group: Group, buf: &mut Vec<TokenTree>) -> Result<bool, Diagnostic2> {
+    let mut tokens = group.stream().into_iter().peekable();
+    if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '<') {
+        match group.delimiter() {
+            // `paste`-like idents
+            Delimiter::Bracket => {
+                if let Some(res) = parse_bracket_contents(&group) {
+                    // We recognized it as ours, but it may be faulty
+                    buf.push(TokenTree::Ident(res?));
+                    return Ok(true);
+                }
+            },
+            // generics generator-idents
+            Delimiter::Brace => {
+                if let Some(res) = parse_brace_contents(&group) {
+                    buf.extend(res?);
+                    return Ok(true);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    // Recurse into other nested areas, only rebuilding them if something changed
+    let start: usize = buf.len();
+    if rewrite(tokens, buf)? {
+        let mut new = Group::new(group.delimiter(), buf.drain(start..).collect());
+        new.set_span(group.span());
+        buf.push(TokenTree::Group(new));
+        Ok(true)
+    } else {
+        buf.truncate(start);
+        buf.push(TokenTree::Group(group));
+        Ok(false)
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the `idents!()`-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to match for input.
+///
+/// # Returns
+/// A new [`TokenStream`] that is the same as in, but with some identifiers replaced.
+///
+/// # Errors
+/// This function may error if the input in between `[<` and `>]` is not valid for this macro.
+pub fn idents(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut buf: Vec<TokenTree> = Vec::new();
+    if rewrite(input.clone().into_iter(), &mut buf)? { Ok(buf.into_iter().collect()) } else { Ok(input) }
+}
+
+/// Defines the implementation of the `#[with_idents]`-attribute.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the placeholders to define.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] that is the same item, but with the placeholders replaced and any
+/// identifiers in nested macro calls pasted or generated like for [`idents()`].
+///
+/// # Errors
+/// This function may error if the placeholders are invalid, or if the input in between `[<` and
+/// `>]` is not valid for this macro.
+pub fn with_idents(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // NOTE: The error ends up in item position, so needs to be a valid item
+    parse_placeholders(attr).and_then(|placeholders| idents(substitute(item, &placeholders))).map_err(item_error)
+}
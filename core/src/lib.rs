@@ -0,0 +1,53 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(feature = "nightly_diagnostics", feature(proc_macro_diagnostic))]
+//  LIB.rs
+//    by Lut99
+//
+//  Description:
+//!   The engines behind the `macro-toolkit` crate, as a normal library for use in other procedural
+//!   macros.
+//!
+//!
+//!   # Usage
+//!   Procedural macro crates can only export macros, so the parsers and generators behind
+//!   `macro-toolkit`'s macros live in this crate instead. Add it to the `[dependencies]` of your
+//!   own proc-macro crate, and call them with the `proc-macro2` tokens of your macro:
+//!   ```toml
+//!   [dependencies]
+//!   macro-toolkit-core = { git = "https://github.com/Lut99/macro-toolkit-rs", features = ["idents", "match_lit"] }
+//!   ```
+//!
+//!   For example, to paste an identifier like `idents!()` does:
+//!   ```rust
+//!   # #[cfg(feature = "idents")]
+//!   # {
+//!   use macro_toolkit_core::idents::paste_ident;
+//!   use proc_macro2::{Span, TokenStream};
+//!
+//!   let tokens: TokenStream = "get_ foo _mut".parse().unwrap();
+//!   let ident = paste_ident(tokens, Span::call_site()).unwrap();
+//!   assert_eq!(ident.to_string(), "get_foo_mut");
+//!   # }
+//!   ```
+//!
+//!
+//!   # Features
+//!   This crate has the following features:
+//!   - `idents`: Enables the compilation of the `idents`-module, with the engine of `idents!()` and `#[with_idents]` for pasting identifiers and flattening types into them.
+//!   - `match_lit`: Enables the compilation of the `match_lit`-module, with the literal classifier of `match_lit!()`.
+//!   - `nightly_diagnostics`: Emits diagnostics as native diagnostics, showing their notes and help messages as part of the diagnostic. Requires a nightly compiler, and that diagnostics are only emitted while expanding a procedural macro.
+//!
+//!   The `cursor`- and `diagnostics`-modules are always available.
+//
+
+extern crate proc_macro;
+
+// Modules
+pub mod cursor;
+pub mod diagnostics;
+#[cfg(feature = "idents")]
+#[cfg_attr(docsrs, doc(cfg(feature = "idents")))]
+pub mod idents;
+#[cfg(feature = "match_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "match_lit")))]
+pub mod match_lit;
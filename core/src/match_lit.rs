@@ -0,0 +1,665 @@
+//  MATCH LITERALS.rs
+//    by Tim Müller
+//
+//  Description:
+//!   Provides a tiny, high-performance macro for switching on literal types.
+//
+
+use proc_macro2::{Group, Ident, Span, TokenStream, TokenTree};
+use syn::{Lit, LitBool, LitInt, LitStr};
+
+use crate::cursor::{Cursor, unwrap_invisible};
+use crate::diagnostics::Diagnostic2;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether a literal has the value given by a pattern.
+///
+/// Literals are compared by their value, not by how they are written (e.g., `0x2A` equals `42`,
+/// and `"\x41"` equals `"A"`). If the pattern has a suffix, the literal must have the same one;
+/// otherwise, its suffix is ignored.
+///
+/// # Arguments
+/// - `value`: The [`Lit`] given as pattern.
+/// - `lit`: The [`Lit`] to match on.
+///
+/// # Returns
+/// True if both are the same kind of literal with the same value, or false otherwise.
+fn lit_eq(value: &Lit, lit: &Lit) -> bool {
+    match (value, lit) {
+        (Lit::Bool(value), Lit::Bool(lit)) => value.value == lit.value,
+        (Lit::Int(value), Lit::Int(lit)) => (value.suffix().is_empty() || value.suffix() == lit.suffix()) && value.base10_digits() == lit.base10_digits(),
+        (Lit::Float(value), Lit::Float(lit)) => {
+            (value.suffix().is_empty() || value.suffix() == lit.suffix())
+                && matches!((value.base10_parse::<f64>(), lit.base10_parse::<f64>()), (Ok(value), Ok(lit)) if value == lit)
+        },
+        (Lit::Byte(value), Lit::Byte(lit)) => value.value() == lit.value(),
+        (Lit::Char(value), Lit::Char(lit)) => value.value() == lit.value(),
+        (Lit::ByteStr(value), Lit::ByteStr(lit)) => value.value() == lit.value(),
+        (Lit::Str(value), Lit::Str(lit)) => value.value() == lit.value(),
+        (Lit::CStr(value), Lit::CStr(lit)) => value.value() == lit.value(),
+        (_, _) => false,
+    }
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines how to treat every possible literal type.
+struct Branches {
+    /// The literal to match on.
+    lit:      Lit,
+    /// The branches to match, in the order they're given.
+    branches: Vec<Branch>,
+    /// The message of the error to emit if none of the branches match, if given by an
+    /// `else`-branch.
+    fallback: Option<LitStr>,
+}
+impl Branches {
+    /// Constructor for the Branches that will initialize it as empty.
+    ///
+    /// # Arguments
+    /// - `lit`: The [`Lit`] that we will use to decide.
+    ///
+    /// # Returns
+    /// A Branches that will decide on the given `lit`eral, but will decide to error for every
+    /// possible outcome.
+    #[inline]
+    const fn new(lit: Lit) -> Self { Self { lit, branches: Vec::new(), fallback: None } }
+}
+impl Branches {
+    /// Parses the input into the literal and the branches to match it with.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// A Branches encoding how to match every arm.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut cursor = Cursor::new(input, Span::call_site());
+
+        // Parse the literal and the group with branches
+        let lit: Lit = Self::parse_lit(&mut cursor)?;
+        let group: Group = match cursor.next() {
+            Some(TokenTree::Group(group)) => group,
+            Some(tt) => return Err(Diagnostic2::error(tt.span(), "Expected match branches wrapped in `{}`").code("MTK0008")),
+            None => return Err(cursor.error("Expected match branches wrapped in `{}`").code("MTK0008")),
+        };
+        if !cursor.is_empty() {
+            return Err(cursor.error("Expected nothing after the match branches").code("MTK0008"));
+        }
+
+        // Then parse the group into branches
+        let mut cursor = Cursor::new(group.stream(), group.span_close());
+        let mut res = Self::new(lit);
+        loop {
+            // The `else`-branch, which must be the last
+            if let Some(TokenTree::Ident(ident)) = cursor.peek()
+                && ident == "else"
+            {
+                cursor.next();
+                res.fallback = Some(Self::parse_fallback(&mut cursor)?);
+                break;
+            }
+
+            match Branch::parse(&mut cursor)? {
+                Some(branch) => res.branches.push(branch),
+                None => break,
+            }
+        }
+        Ok(res)
+    }
+
+    /// Parses the remainder of the `else`-branch after its `else`.
+    ///
+    /// # Arguments
+    /// - `cursor`: The [`Cursor`] yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The [`LitStr`] with the message of the error to emit if none of the branches match.
+    ///
+    /// # Errors
+    /// This function can error if the branch is not followed by `=>` and a string literal, or if
+    /// it is not the last branch.
+    fn parse_fallback(cursor: &mut Cursor) -> Result<LitStr, Diagnostic2> {
+        if !cursor.eat_op("=>") {
+            return Err(cursor.error("Expected '=>'").code("MTK0008"));
+        }
+        let message: LitStr = match cursor.next_transparent() {
+            Some(TokenTree::Literal(lit)) => match Lit::new(lit) {
+                Lit::Str(message) => message,
+                lit => return Err(Diagnostic2::error(lit.span(), "Expected a string literal with the error message").code("MTK0008")),
+            },
+            Some(tt) => return Err(Diagnostic2::error(tt.span(), "Expected a string literal with the error message").code("MTK0008")),
+            None => return Err(cursor.error("Expected a string literal with the error message").code("MTK0008")),
+        };
+        cursor.eat_punct(',');
+        if !cursor.is_empty() {
+            return Err(cursor.error("Expected nothing after the `else`-branch").code("MTK0008").help("the `else`-branch must be the last branch"));
+        }
+        Ok(message)
+    }
+
+    /// Parses the literal to match on.
+    ///
+    /// # Arguments
+    /// - `cursor`: The [`Cursor`] yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed [`Lit`].
+    ///
+    /// # Errors
+    /// This function can error if the next token is not a literal.
+    fn parse_lit(cursor: &mut Cursor) -> Result<Lit, Diagnostic2> {
+        // NOTE: Literals given to declarative macros (e.g., `$lit:literal`) are wrapped in invisible groups
+        match cursor.next().map(unwrap_invisible) {
+            // These are the literals we really match
+            Some(TokenTree::Literal(lit)) => Ok(Lit::new(lit)),
+            Some(TokenTree::Ident(ident)) if ident == "true" || ident == "false" => Ok(Lit::Bool(LitBool { value: ident == "true", span: ident.span() })),
+
+            // Otherwise, it's BAD
+            Some(tt) => Err(Diagnostic2::error(tt.span(), "Expected a literal").code("MTK0007")),
+            None => Err(cursor.error("Expected a literal").code("MTK0007")),
+        }
+    }
+}
+
+
+
+/// Defines the possible branches to parse.
+struct Branch {
+    /// The patterns for this branch, of which any may match.
+    patterns: Vec<Pattern>,
+    /// The stream to compile to when matched.
+    tokens:   TokenStream,
+}
+impl Branch {
+    /// Parses this Branch from an iterator over [`TokenTree`]s.
+    ///
+    /// # Arguments
+    /// - `cursor`: The [`Cursor`] yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// A Branch once we parsed enough to parse a branch. If there was nothing left to parse,
+    /// returns [`None`].
+    ///
+    /// # Errors
+    /// If the input did not have a valid branch at the head, returns an error.
+    fn parse(cursor: &mut Cursor) -> Result<Option<Self>, Diagnostic2> {
+        // Match on the patterns on the head, separated by `|` (and optionally preceded by one)
+        let leading: bool = cursor.eat_punct('|').is_some();
+        let Some(pattern) = Pattern::parse(cursor)? else {
+            if leading {
+                return Err(cursor.error("Expected a match identifier or a literal").code("MTK0008"));
+            }
+            return Ok(None);
+        };
+        let mut patterns: Vec<Pattern> = vec![pattern];
+        while cursor.eat_punct('|').is_some() {
+            match Pattern::parse(cursor)? {
+                Some(pattern) => patterns.push(pattern),
+                None => return Err(cursor.error("Expected a match identifier or a literal").code("MTK0008")),
+            }
+        }
+        // Match the `=>`
+        if !cursor.eat_op("=>") {
+            return Err(cursor.error("Expected '=>'").code("MTK0008"));
+        }
+        // Match until a `,` OR the end
+        let tokens: TokenStream = cursor.until_punct(',');
+        Ok(Some(Self { patterns, tokens }))
+    }
+}
+
+/// Defines the patterns that a branch can match a literal with.
+enum Pattern {
+    /// Matches literals of a certain kind (e.g., `int`).
+    Kind(LitMatcher),
+    /// Matches literals with a certain value (e.g., `42` or `"foo"`).
+    Value(Lit),
+    /// Matches integer literals with a value in a range (e.g., `0..=255`).
+    Range(Range),
+}
+impl Pattern {
+    /// Parses a Pattern from the head of the given tokens.
+    ///
+    /// # Arguments
+    /// - `cursor`: The [`Cursor`] yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Pattern, or [`None`] if there were no tokens left.
+    ///
+    /// # Errors
+    /// This function errors if the next token is not a match identifier, a literal or a range.
+    fn parse(cursor: &mut Cursor) -> Result<Option<Self>, Diagnostic2> {
+        // NOTE: Values given by declarative macros (e.g., `$lit:literal`) are wrapped in invisible groups
+        cursor.enter_invisible();
+
+        // Ranges without a start
+        if cursor.peek_op("..") {
+            return Range::parse(cursor, None).map(|range| Some(Self::Range(range)));
+        }
+        // Negative integers, which can only start ranges
+        if matches!(cursor.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '-') {
+            let span: Span = cursor.span();
+            let start: Bound = Bound::parse(cursor, span)?;
+            if !cursor.peek_op("..") {
+                return Err(Diagnostic2::error(span, "Negative integers can only be used as bounds of ranges")
+                    .code("MTK0008")
+                    .help("a negated literal like `-1` is a `-` followed by a literal, so `match_lit!()` only ever gets the literal itself to match"));
+            }
+            return Range::parse(cursor, Some(start)).map(|range| Some(Self::Range(range)));
+        }
+
+        match cursor.next().map(unwrap_invisible) {
+            Some(TokenTree::Literal(lit)) => match Lit::new(lit) {
+                Lit::Int(lit) if cursor.peek_op("..") => {
+                    let start: Bound = Bound::new(false, lit)?;
+                    Range::parse(cursor, Some(start)).map(|range| Some(Self::Range(range)))
+                },
+                lit if cursor.peek_op("..") => Err(Diagnostic2::error(lit.span(), "Expected an integer literal as bound of the range").code("MTK0008")),
+                lit => Ok(Some(Self::Value(lit))),
+            },
+            Some(TokenTree::Ident(ident)) if ident == "true" || ident == "false" => {
+                Ok(Some(Self::Value(Lit::Bool(LitBool { value: ident == "true", span: ident.span() }))))
+            },
+            Some(TokenTree::Ident(ident)) => Ok(Some(Self::Kind(LitMatcher::parse(ident)?))),
+            Some(tt) => Err(Diagnostic2::error(tt.span(), "Expected a match identifier, a literal or a range").code("MTK0008")),
+            None => Ok(None),
+        }
+    }
+
+    /// Checks whether this pattern matches a literal.
+    ///
+    /// # Arguments
+    /// - `lit`: The [`Lit`] to match on.
+    ///
+    /// # Returns
+    /// True if this pattern would match the given `lit`, or false otherwise.
+    fn match_lit(&self, lit: &Lit) -> bool {
+        match self {
+            Self::Kind(matcher) => matcher.match_lit(lit),
+            Self::Value(value) => lit_eq(value, lit),
+            Self::Range(range) => range.match_lit(lit),
+        }
+    }
+}
+
+/// Defines a range of integers (e.g., `0..=255`), of which either bound may be omitted.
+struct Range {
+    /// The inclusive lower bound, if any.
+    start:     Option<Bound>,
+    /// The upper bound, if any.
+    end:       Option<Bound>,
+    /// Whether the upper bound is inclusive (`..=`) or not (`..`).
+    inclusive: bool,
+}
+impl Range {
+    /// Parses the remainder of a Range after its lower bound.
+    ///
+    /// # Arguments
+    /// - `cursor`: The [`Cursor`] yielding the remaining tokens, starting with `..` or `..=`.
+    /// - `start`: The lower bound that has already been parsed, if any.
+    ///
+    /// # Returns
+    /// The parsed Range.
+    ///
+    /// # Errors
+    /// This function errors if the upper bound is not an integer literal, if an inclusive range has
+    /// no upper bound, or if the range is empty.
+    fn parse(cursor: &mut Cursor, start: Option<Bound>) -> Result<Self, Diagnostic2> {
+        let span: Span = cursor.span();
+        let inclusive: bool = cursor.eat_op("..=");
+        if !inclusive {
+            cursor.eat_op("..");
+        }
+
+        // Parse the upper bound, which can only be omitted for exclusive ranges with a start
+        cursor.enter_invisible();
+        let end: Option<Bound> = if cursor.is_empty() || cursor.peek_op("=>") || matches!(cursor.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '|') {
+            if inclusive || start.is_none() {
+                return Err(Diagnostic2::error(span, "Expected an upper bound for the range").code("MTK0008"));
+            }
+            None
+        } else {
+            Some(Bound::parse(cursor, span)?)
+        };
+
+        // Ensure the range is not empty, like Rust does
+        if let (Some(start), Some(end)) = (&start, &end)
+            && (start.key() > end.key() || (!inclusive && start.key() == end.key()))
+        {
+            let message: &str = if inclusive {
+                "Lower bound of the range must be less than or equal to the upper bound"
+            } else {
+                "Lower bound of the range must be less than the upper bound"
+            };
+            return Err(Diagnostic2::error(span, message).code("MTK0008"));
+        }
+        Ok(Self { start, end, inclusive })
+    }
+
+    /// Checks whether this range matches a literal.
+    ///
+    /// # Arguments
+    /// - `lit`: The [`Lit`] to match on.
+    ///
+    /// # Returns
+    /// True if the literal is an integer literal in the range, or false otherwise. If any of the
+    /// bounds has a suffix, the literal must have the same one.
+    fn match_lit(&self, lit: &Lit) -> bool {
+        let Lit::Int(lit) = lit else { return false };
+        if [&self.start, &self.end].into_iter().flatten().any(|b| !b.suffix.is_empty() && b.suffix != lit.suffix()) {
+            return false;
+        }
+        let Ok(value) = lit.base10_parse::<u128>() else { return false };
+        let key: (bool, u128) = (true, value);
+        self.start.as_ref().is_none_or(|start| start.key() <= key)
+            && self.end.as_ref().is_none_or(|end| if self.inclusive { key <= end.key() } else { key < end.key() })
+    }
+}
+
+/// Defines a bound of a [`Range`], i.e., an integer literal that may be negative.
+struct Bound {
+    /// Whether the bound is negative.
+    neg:    bool,
+    /// The absolute value of the bound.
+    value:  u128,
+    /// The suffix of the bound, which is empty if it has none.
+    suffix: String,
+}
+impl Bound {
+    /// Creates a new Bound from an integer literal.
+    ///
+    /// # Arguments
+    /// - `neg`: Whether the bound is negated.
+    /// - `lit`: The [`LitInt`] with its absolute value.
+    ///
+    /// # Returns
+    /// The new Bound.
+    ///
+    /// # Errors
+    /// This function errors if the value does not fit in a [`u128`].
+    fn new(neg: bool, lit: LitInt) -> Result<Self, Diagnostic2> {
+        match lit.base10_parse::<u128>() {
+            Ok(value) => Ok(Self { neg, value, suffix: lit.suffix().into() }),
+            Err(_) => Err(Diagnostic2::error(lit.span(), "Bound of the range is too large").code("MTK0008")),
+        }
+    }
+
+    /// Parses a Bound from the head of the given tokens.
+    ///
+    /// # Arguments
+    /// - `cursor`: The [`Cursor`] yielding the remaining tokens.
+    /// - `span`: The [`Span`] to report errors at if there are no tokens left.
+    ///
+    /// # Returns
+    /// The parsed Bound.
+    ///
+    /// # Errors
+    /// This function errors if the next tokens are not an (optionally negated) integer literal.
+    fn parse(cursor: &mut Cursor, span: Span) -> Result<Self, Diagnostic2> {
+        cursor.enter_invisible();
+        let neg: bool = cursor.eat_punct('-').is_some();
+        match cursor.next_transparent() {
+            Some(TokenTree::Literal(lit)) => match Lit::new(lit) {
+                Lit::Int(lit) => Self::new(neg, lit),
+                lit => Err(Diagnostic2::error(lit.span(), "Expected an integer literal as bound of the range").code("MTK0008")),
+            },
+            Some(tt) => Err(Diagnostic2::error(tt.span(), "Expected an integer literal as bound of the range").code("MTK0008")),
+            None => Err(Diagnostic2::error(span, "Expected an integer literal as bound of the range").code("MTK0008")),
+        }
+    }
+
+    /// Returns a key by which bounds and non-negative values (as `(true, value)`) can be ordered.
+    ///
+    /// # Returns
+    /// A tuple that orders negative values (with `false`) before non-negative ones (with `true`).
+    fn key(&self) -> (bool, u128) { if self.neg && self.value > 0 { (false, u128::MAX - self.value) } else { (true, self.value) } }
+}
+
+
+
+/// Describes all the possible matchers to specify.
+pub enum LitMatcher {
+    // Any
+    Any,
+
+    // Booleans
+    /// Any boolean literal.
+    Bool,
+
+    // Integers
+    /// Any integer literal.
+    Int,
+    /// Unsized int, specifically.
+    IntUns,
+    /// Any 8-bit wide literal.
+    Int8,
+    /// Any 16-bit wide literal.
+    Int16,
+    /// Any 32-bit wide literal.
+    Int32,
+    /// Any 64-bit wide literal.
+    Int64,
+    /// Any 128-bit wide literal.
+    Int128,
+    /// Any system-address wide literal.
+    IntSize,
+    /// Any signed literal.
+    IntS,
+    /// 8-bit wide, signed literal.
+    IntS8,
+    /// 16-bit wide, signed literal.
+    IntS16,
+    /// 32-bit wide, signed literal.
+    IntS32,
+    /// 64-bit wide, signed literal.
+    IntS64,
+    /// 128-bit wide, signed literal.
+    IntS128,
+    /// System-address wide, signed literal.
+    IntSSize,
+    /// Any unsigned literal.
+    IntU,
+    /// 8-bit wide, unsigned literal.
+    IntU8,
+    /// 16-bit wide, unsigned literal.
+    IntU16,
+    /// 32-bit wide, unsigned literal.
+    IntU32,
+    /// 64-bit wide, unsigned literal.
+    IntU64,
+    /// 128-bit wide, unsigned literal.
+    IntU128,
+    /// System-address wide, unsigned literal.
+    IntUSize,
+
+    // Floats
+    /// Any floating-point.
+    Float,
+    /// Unsized floating-point, specifically.
+    FloatUns,
+    /// 32-bit wide floating-point.
+    Float32,
+    /// 64-bit wide floating-point.
+    Float64,
+
+    // Characters
+    /// Any character-like literal.
+    Char,
+    /// Specifically byte character literal.
+    CharByte,
+    /// Specifically string character literal.
+    CharStr,
+
+    // Strings
+    /// Any string-like literal.
+    String,
+    /// Specifically byte literal.
+    StringByte,
+    /// Any of the two text literals.
+    StringText,
+    /// Specifically string literal.
+    StringStr,
+    /// Specifically c-string literal.
+    StringCStr,
+}
+impl LitMatcher {
+    /// Parses this litmatcher from an identifier.
+    ///
+    /// # Arguments
+    /// - `ident`: The [`Ident`] to parse it from.
+    ///
+    /// # Returns
+    /// A LitMatcher specified by the ident.
+    ///
+    /// # Errors
+    /// This function may error if the ident doesn't match any of the matchers.
+    #[inline]
+    pub fn parse(ident: Ident) -> Result<Self, Diagnostic2> {
+        match ident.to_string().as_str() {
+            // Any
+            "_" => Ok(Self::Any),
+
+            // Boolean
+            "bool" | "boollike" => Ok(Self::Bool),
+
+            // Integer
+            "int" | "intlike" => Ok(Self::Int),
+            "int_" => Ok(Self::IntUns),
+            "int8" => Ok(Self::Int8),
+            "int16" => Ok(Self::Int16),
+            "int32" => Ok(Self::Int32),
+            "int64" => Ok(Self::Int64),
+            "int128" => Ok(Self::Int128),
+            "size" => Ok(Self::IntSize),
+            "sint" => Ok(Self::IntS),
+            "i8" => Ok(Self::IntS8),
+            "i16" => Ok(Self::IntS16),
+            "i32" => Ok(Self::IntS32),
+            "i64" => Ok(Self::IntS64),
+            "i128" => Ok(Self::IntS128),
+            "isize" => Ok(Self::IntSSize),
+            "uint" => Ok(Self::IntU),
+            "u8" => Ok(Self::IntU8),
+            "u16" => Ok(Self::IntU16),
+            "u32" => Ok(Self::IntU32),
+            "u64" => Ok(Self::IntU64),
+            "u128" => Ok(Self::IntU128),
+            "usize" => Ok(Self::IntUSize),
+
+            // Floating-point
+            "float" | "floatlike" => Ok(Self::Float),
+            "float_" => Ok(Self::FloatUns),
+            "f32" => Ok(Self::Float32),
+            "f64" => Ok(Self::Float64),
+
+            // Characters
+            "charlike" => Ok(Self::Char),
+            "char" => Ok(Self::CharStr),
+            "byte" => Ok(Self::CharByte),
+
+            // Strings
+            "stringlike" => Ok(Self::String),
+            "bytes" | "bstring" => Ok(Self::StringByte),
+            "text" => Ok(Self::StringText),
+            "string" => Ok(Self::StringStr),
+            "cstring" => Ok(Self::StringCStr),
+
+            // Any others are invalid
+            _ => Err(Diagnostic2::error(ident.span(), format!("Unknown literal kind `{ident}`"))
+                .code("MTK0009")
+                .help("see the documentation of `match_lit!()` for the supported kinds")),
+        }
+    }
+
+    /// Checks whether this matcher matches a literal.
+    ///
+    /// # Arguments
+    /// - `lit`: The [`Lit`] to match on.
+    ///
+    /// # Returns
+    /// True if this matcher would match the given `lit`, or false otherwise.
+    #[inline]
+    pub fn match_lit(&self, lit: &Lit) -> bool {
+        match (lit, self) {
+            // Booleans
+            (Lit::Bool(_), Self::Any | Self::Bool) => true,
+
+            // Integers
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntUns) if i.suffix().is_empty() => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntS | Self::Int8 | Self::IntS8) if i.suffix() == "i8" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntS | Self::Int16 | Self::IntS16) if i.suffix() == "i16" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntS | Self::Int32 | Self::IntS32) if i.suffix() == "i32" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntS | Self::Int64 | Self::IntS64) if i.suffix() == "i64" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntS | Self::Int128 | Self::IntS128) if i.suffix() == "i128" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntU | Self::Int8 | Self::IntU8) if i.suffix() == "u8" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntU | Self::Int16 | Self::IntU16) if i.suffix() == "u16" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntU | Self::Int32 | Self::IntU32) if i.suffix() == "u32" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntU | Self::Int64 | Self::IntU64) if i.suffix() == "u64" => true,
+            (Lit::Int(i), Self::Any | Self::Int | Self::IntU | Self::Int128 | Self::IntU128) if i.suffix() == "u128" => true,
+
+            // Floats
+            (Lit::Float(f), Self::Any | Self::Float | Self::FloatUns) if f.suffix().is_empty() => true,
+            (Lit::Float(f), Self::Any | Self::Float | Self::Float32) if f.suffix() == "f32" => true,
+            (Lit::Float(f), Self::Any | Self::Float | Self::Float64) if f.suffix() == "f64" => true,
+
+            // Characters
+            (Lit::Byte(_), Self::Any | Self::Char | Self::CharByte) => true,
+            (Lit::Char(_), Self::Any | Self::Char | Self::CharStr) => true,
+
+            // Strings
+            (Lit::ByteStr(_), Self::Any | Self::String | Self::StringByte) => true,
+            (Lit::Str(_), Self::Any | Self::String | Self::StringText | Self::StringStr) => true,
+            (Lit::CStr(_), Self::Any | Self::String | Self::StringText | Self::StringCStr) => true,
+
+            // Done
+            (_, _) => false,
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the `match_lit!()`-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to match for input.
+///
+/// # Returns
+/// A new [`TokenStream`] with the correct output depending on the type (or value) of the literal
+/// in the input.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn match_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Parse the input, first
+    let Branches { lit, branches, fallback } = Branches::parse(input)?;
+
+    // Find the first branch that matches
+    for branch in branches {
+        if !branch.patterns.iter().any(|pattern| pattern.match_lit(&lit)) {
+            continue;
+        }
+
+        // If we match, then serialize the branch
+        return Ok(branch.tokens);
+    }
+
+    // If we failed to match any, then error with the message of the user if they gave one
+    if let Some(message) = fallback {
+        return Err(Diagnostic2::error(lit.span(), message.value()));
+    }
+    Err(Diagnostic2::error(lit.span(), "Unmatched literal type")
+        .code("MTK0010")
+        .help("add a branch for this kind (or value) of literal, or a `_`-branch that matches any literal"))
+}
@@ -0,0 +1,41 @@
+//  IDENTS.rs
+//    by Lut99
+//
+//  Description:
+//!   Tests calling the engine of `idents!()` programmatically.
+//
+
+use macro_toolkit_core::idents::{flatten_type, idents, new_ident, paste_ident};
+use proc_macro2::{Span, TokenStream};
+
+
+/***** TESTS *****/
+#[test]
+fn test_paste_ident() {
+    let tokens: TokenStream = "get_ foo _mut".parse().unwrap();
+    assert_eq!(paste_ident(tokens, Span::call_site()).unwrap().to_string(), "get_foo_mut");
+}
+
+#[test]
+fn test_new_ident() {
+    assert_eq!(new_ident("foo", None, Span::call_site()).unwrap().to_string(), "foo");
+}
+
+#[test]
+#[cfg_attr(feature = "nightly_diagnostics", ignore = "native diagnostics can only be emitted while expanding a macro")]
+fn test_new_ident_invalid() {
+    assert!(new_ident("1foo", None, Span::call_site()).is_err());
+    assert!(new_ident("foo-bar", None, Span::call_site()).is_err());
+}
+
+#[test]
+fn test_flatten_type() {
+    let tokens: TokenStream = "Vec<Option<u8>>".parse().unwrap();
+    assert_eq!(flatten_type(tokens, "_", "_", Span::call_site()).unwrap(), "Vec_Option_u8");
+}
+
+#[test]
+fn test_idents() {
+    let tokens: TokenStream = "fn [<get_ foo>]() {}".parse().unwrap();
+    assert_eq!(idents(tokens).unwrap().to_string(), "fn get_foo () { }");
+}
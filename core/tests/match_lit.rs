@@ -0,0 +1,55 @@
+//  MATCH LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Tests calling the literal classifier of `match_lit!()` programmatically.
+//
+
+use macro_toolkit_core::match_lit::{LitMatcher, match_lit};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use syn::Lit;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether the literal kind given by name matches a literal.
+fn matches(kind: &str, lit: Literal) -> bool { LitMatcher::parse(Ident::new(kind, Span::call_site())).unwrap().match_lit(&Lit::new(lit)) }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_lit_matcher() {
+    assert!(matches("int", Literal::u8_suffixed(42)));
+    assert!(matches("u8", Literal::u8_suffixed(42)));
+    assert!(!matches("i8", Literal::u8_suffixed(42)));
+    assert!(matches("string", Literal::string("foo")));
+    assert!(!matches("bytes", Literal::string("foo")));
+    assert!(matches("_", Literal::f64_unsuffixed(1.5)));
+}
+
+#[test]
+#[cfg_attr(feature = "nightly_diagnostics", ignore = "native diagnostics can only be emitted while expanding a macro")]
+fn test_lit_matcher_unknown() {
+    assert!(LitMatcher::parse(Ident::new("foo", Span::call_site())).is_err());
+}
+
+#[test]
+fn test_match_lit() {
+    let tokens: TokenStream = "42u8 { string => a, int => b, _ => c }".parse().unwrap();
+    assert_eq!(match_lit(tokens).unwrap().to_string(), "b");
+}
+
+#[test]
+fn test_match_lit_value() {
+    let tokens: TokenStream = "0x2a { 41 => a, 42u8 => b, 42 => c, _ => d }".parse().unwrap();
+    assert_eq!(match_lit(tokens).unwrap().to_string(), "c");
+}
+
+#[test]
+#[cfg_attr(feature = "nightly_diagnostics", ignore = "native diagnostics can only be emitted while expanding a macro")]
+fn test_match_lit_else() {
+    let tokens: TokenStream = "42 { string => a, else => \"Expected a string\" }".parse().unwrap();
+    assert!(match_lit(tokens).unwrap_err().to_string().contains("Expected a string"));
+}
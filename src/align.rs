@@ -0,0 +1,166 @@
+//  ALIGN.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for aligning integer literals at compile time.
+//
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::{Lit, LitInt};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, int_lit, parse_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macros.
+struct Input {
+    /// The value to align.
+    value: u128,
+    /// The alignment to align it to.
+    align: u128,
+    /// The type of the result, if any.
+    ty:    Option<IntType>,
+    /// The span of the value.
+    span:  Span,
+}
+impl Input {
+    /// Parses the input to the macros.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter();
+
+        // Parse the value
+        let value: LitInt = Self::parse_int(iter.next())?;
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+            None => return Err(error2(value.span(), "Expected ',' and an alignment")),
+        }
+
+        // Parse the alignment
+        let align: LitInt = Self::parse_int(iter.next())?;
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+            None => {},
+        }
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the alignment"));
+        }
+
+        // Resolve the type from the suffixes
+        let ty: Option<IntType> = match (value.suffix(), align.suffix()) {
+            ("", "") => None,
+            (suffix, "") | ("", suffix) => Some(Self::parse_suffix(suffix, &value, &align)?),
+            (lsuffix, rsuffix) if lsuffix == rsuffix => Some(Self::parse_suffix(lsuffix, &value, &align)?),
+            (_, rsuffix) => return Err(error2(align.span(), &format!("Alignment has suffix `{rsuffix}`, but the value has suffix `{}`", value.suffix()))),
+        };
+
+        // Parse the values
+        let span: Span = value.span();
+        let value: u128 = value.base10_parse().map_err(|_| error2(span, "Integer literal is too large"))?;
+        let align_span: Span = align.span();
+        let align: u128 = align.base10_parse().map_err(|_| error2(align_span, "Integer literal is too large"))?;
+        if !align.is_power_of_two() {
+            return Err(error2(align_span, &format!("Alignment must be a power of two, but got {align}")));
+        }
+        if let Some(ty) = ty {
+            if !ty.fits(false, value) {
+                return Err(error2(span, &format!("Integer literal does not fit in a `{}`", ty.suffix())));
+            }
+            if !ty.fits(false, align) {
+                return Err(error2(align_span, &format!("Integer literal does not fit in a `{}`", ty.suffix())));
+            }
+        }
+        Ok(Self { value, align, ty, span })
+    }
+
+    /// Parses a single integer literal.
+    ///
+    /// # Arguments
+    /// - `tree`: The token to parse.
+    ///
+    /// # Returns
+    /// The parsed [`LitInt`].
+    ///
+    /// # Errors
+    /// This function errors if the token was not an integer literal.
+    fn parse_int(tree: Option<TokenTree>) -> Result<LitInt, Diagnostic2> {
+        match tree.map(parse_lit).transpose()? {
+            Some(Lit::Int(lit)) => Ok(lit),
+            Some(lit) => Err(error2(lit.span(), "Expected a non-negative integer literal")),
+            None => Err(error2(Span::mixed_site(), "Expected a non-negative integer literal")),
+        }
+    }
+
+    /// Parses the suffix of the literals.
+    ///
+    /// # Arguments
+    /// - `suffix`: The suffix to parse.
+    /// - `value`: The value literal, for error reporting.
+    /// - `align`: The alignment literal, for error reporting.
+    ///
+    /// # Returns
+    /// The [`IntType`] of the suffix.
+    ///
+    /// # Errors
+    /// This function errors if the suffix was not that of an integer type.
+    fn parse_suffix(suffix: &str, value: &LitInt, align: &LitInt) -> Result<IntType, Diagnostic2> {
+        let span: Span = if value.suffix().is_empty() { align.span() } else { value.span() };
+        match IntType::from_suffix(suffix) {
+            Some(ty) => Ok(ty),
+            None => Err(error2(span, &format!("Unknown integer suffix `{suffix}`"))),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`align_up()`](super::align_up())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the value and alignment.
+///
+/// # Returns
+/// A new [`TokenStream`] with the smallest multiple of the alignment that is not less than the
+/// value.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the result would
+/// overflow.
+pub fn align_up(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { value, align, ty, span } = Input::parse(input)?;
+    let res: Option<u128> = value.checked_add(align - 1).map(|v| v & !(align - 1));
+    match res {
+        Some(res) if ty.is_none_or(|ty| ty.fits(false, res)) => Ok(int_lit(false, res, ty, span)),
+        _ => Err(error2(span, &format!("Aligning {value} up to {align} overflows{}", ty.map(|ty| format!(" a `{}`", ty.suffix())).unwrap_or_default()))),
+    }
+}
+
+/// Defines the implementation of the [`align_down()`](super::align_down())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the value and alignment.
+///
+/// # Returns
+/// A new [`TokenStream`] with the largest multiple of the alignment that is not greater than the
+/// value.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn align_down(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { value, align, ty, span } = Input::parse(input)?;
+    Ok(int_lit(false, value & !(align - 1), ty, span))
+}
@@ -0,0 +1,36 @@
+//  APPLY.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides an attribute macro for using declarative macros as attributes.
+//
+
+use proc_macro2::{Span, TokenStream};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::utils::Callback;
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`apply()`](super::apply())-macro.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the path to the macro to apply.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] calling the given macro with the item.
+///
+/// # Errors
+/// This function may error if the attribute's arguments are not a path to a macro.
+pub fn apply(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let callback = || -> Result<Callback, Diagnostic2> {
+        if attr.is_empty() {
+            return Err(error2(Span::call_site(), "Expected a path to a macro to apply (e.g., `#[apply(foo!)]`)"));
+        }
+        Callback::parse(&mut attr.into_iter())
+    };
+    // NOTE: The error ends up in item position, so needs a semicolon to be a valid item
+    let callback: Callback = callback().map_err(item_error)?;
+    Ok(callback.call_item(item))
+}
@@ -0,0 +1,236 @@
+//  ASSERT EXPANDS TO.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for testing at compile time that a macro with a callback expands to the
+//!   expected tokens.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, Level, error2};
+
+
+/***** CONSTANTS *****/
+/// The name of the marker with which the macro calls itself as callback of the macro under test.
+const EXPANDED_MARKER: &str = "__mtk_expanded";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Flattens the tokens in groups without delimiters (e.g., from `$ty:ty`), which are invisible in
+/// source code.
+///
+/// # Arguments
+/// - `tokens`: The tokens to flatten.
+/// - `res`: The list to push the flattened tokens to.
+fn flatten(tokens: TokenStream, res: &mut Vec<TokenTree>) {
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => flatten(group.stream(), res),
+            tt => res.push(tt),
+        }
+    }
+}
+
+/// Describes a token for in an error message.
+///
+/// # Arguments
+/// - `tt`: The token to describe, or [`None`] for the end of the tokens.
+///
+/// # Returns
+/// A description like "`foo`" or "nothing".
+fn describe(tt: Option<&TokenTree>) -> String {
+    match tt {
+        Some(TokenTree::Group(group)) => match group.delimiter() {
+            Delimiter::Parenthesis => "`(...)`".into(),
+            Delimiter::Bracket => "`[...]`".into(),
+            Delimiter::Brace => "`{...}`".into(),
+            Delimiter::None => format!("`{}`", group.stream()),
+        },
+        Some(tt) => format!("`{tt}`"),
+        None => "nothing".into(),
+    }
+}
+
+/// Compares two token streams structurally, i.e., ignoring their spans and invisible groups.
+///
+/// Puncts only need the same spacing if they are followed by another punct (e.g., `=>` is
+/// different from `= >`, but `a;` and `a ;` are the same).
+///
+/// # Arguments
+/// - `expected`: The expected tokens.
+/// - `actual`: The actual tokens.
+///
+/// # Returns
+/// [`None`] if the tokens are equal, or else the first expected and actual token that differ
+/// (where [`None`] indicates that one of them ended early).
+fn compare(expected: TokenStream, actual: TokenStream) -> Option<(Option<TokenTree>, Option<TokenTree>)> {
+    let (mut lhs, mut rhs): (Vec<TokenTree>, Vec<TokenTree>) = (Vec::new(), Vec::new());
+    flatten(expected, &mut lhs);
+    flatten(actual, &mut rhs);
+    for i in 0..lhs.len().max(rhs.len()) {
+        let joined: bool = matches!(lhs.get(i + 1), Some(TokenTree::Punct(_)));
+        let equal: bool = match (lhs.get(i), rhs.get(i)) {
+            (Some(TokenTree::Group(l)), Some(TokenTree::Group(r))) if l.delimiter() == r.delimiter() => {
+                if let Some(diff) = compare(l.stream(), r.stream()) {
+                    return Some(diff);
+                }
+                true
+            },
+            (Some(TokenTree::Punct(l)), Some(TokenTree::Punct(r))) => l.as_char() == r.as_char() && (!joined || l.spacing() == r.spacing()),
+            (Some(TokenTree::Ident(l)), Some(TokenTree::Ident(r))) => l == r,
+            (Some(TokenTree::Literal(l)), Some(TokenTree::Literal(r))) => l.to_string() == r.to_string(),
+            _ => false,
+        };
+        if !equal {
+            return Some((lhs.get(i).cloned(), rhs.get(i).cloned()));
+        }
+    }
+    None
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the call to the macro under test.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The path of the macro (including the `!`) and the group with its arguments.
+///
+/// # Errors
+/// This function errors if the tokens do not start with a macro call.
+fn parse_call(iter: &mut impl Iterator<Item = TokenTree>) -> Result<(TokenStream, Group), Diagnostic2> {
+    let mut path = TokenStream::new();
+    loop {
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '!' && !path.is_empty() => {
+                path.extend([TokenTree::Punct(p)]);
+                break;
+            },
+            Some(tt @ (TokenTree::Ident(_) | TokenTree::Punct(_))) => path.extend([tt]),
+            // Allow paths given as `$mac:path`
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => path.extend(group.stream()),
+            Some(tt) => return Err(error2(tt.span(), "Expected a call to a macro (e.g., `foo!(...)`)")),
+            None => return Err(error2(Span::call_site(), "Expected a call to a macro (e.g., `foo!(...)`)")),
+        }
+    }
+    match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() != Delimiter::None => Ok((path, group)),
+        Some(tt) => Err(error2(tt.span(), "Expected the arguments to the macro")),
+        None => Err(error2(Span::call_site(), "Expected the arguments to the macro")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`assert_expands_to()`](super::assert_expands_to())-macro.
+///
+/// The macro works in two stages. First, it calls the macro under test with itself as callback,
+/// passing the expected tokens along. Then, when called as callback, it compares them with the
+/// expansion.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the call to the macro under test and the expected
+///   tokens, or the expected and actual tokens when called as callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the call to the macro under test, or nothing if the tokens match.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro or if the tokens don't match.
+pub fn assert_expands_to(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+
+    // Compare the tokens if we're called back
+    if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '#') {
+        iter.next();
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == EXPANDED_MARKER => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected a call to a macro (e.g., `foo!(...)`)")),
+            None => return Err(error2(Span::call_site(), "Expected a call to a macro (e.g., `foo!(...)`)")),
+        }
+        let expected: Group = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+            _ => return Err(error2(Span::call_site(), "Expected the expected tokens in curly brackets")),
+        };
+        let actual: TokenStream = iter.collect();
+        return match compare(expected.stream(), actual.clone()) {
+            Some((exp, act)) => Err(Diagnostic2::error(
+                exp.as_ref().map(TokenTree::span).unwrap_or_else(|| expected.span_close()),
+                format!("Expansion does not match the expected tokens: expected {}, found {}", describe(exp.as_ref()), describe(act.as_ref())),
+            )
+            .child(Level::Note, None, format!("the macro expanded to `{actual}`"))),
+            None => Ok(TokenStream::new()),
+        };
+    }
+
+    // Otherwise, parse the call and the expected tokens
+    let (path, args): (TokenStream, Group) = parse_call(&mut iter)?;
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+        None => return Err(error2(Span::call_site(), "Expected ',' and the expected tokens in curly brackets")),
+    }
+    let expected: Group = match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(tt) => return Err(error2(tt.span(), "Expected the expected tokens in curly brackets")),
+        None => return Err(error2(Span::call_site(), "Expected the expected tokens in curly brackets")),
+    };
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+        None => {},
+    }
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the expected tokens"));
+    }
+
+    // Call the macro with ourselves as callback: `foo!(... => ::macro_toolkit::assert_expands_to!(#__mtk_expanded { ... }))`
+    // NOTE: A trailing punct is recreated as alone, to prevent the compiler from gluing it to the
+    // `=>` (e.g., `Vec<u8>` followed by `=>` would otherwise become `Vec<u8 >=>`)
+    let mut args_tokens: Vec<TokenTree> = args.stream().into_iter().collect();
+    if let Some(TokenTree::Punct(p)) = args_tokens.last_mut() {
+        let mut alone = Punct::new(p.as_char(), Spacing::Alone);
+        alone.set_span(p.span());
+        *p = alone;
+    }
+    let mut args_stream: TokenStream = args_tokens.into_iter().collect();
+    args_stream.extend([
+        TokenTree::Punct(Punct::new('=', Spacing::Joint)),
+        TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("macro_toolkit", Span::call_site())),
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("assert_expands_to", Span::call_site())),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Parenthesis,
+            TokenStream::from_iter([
+                TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+                TokenTree::Ident(Ident::new(EXPANDED_MARKER, Span::call_site())),
+                TokenTree::Group(expected),
+            ]),
+        )),
+    ]);
+    let mut call = Group::new(args.delimiter(), args_stream);
+    call.set_span(args.span());
+    let mut res: TokenStream = path;
+    res.extend([TokenTree::Group(call)]);
+    if args.delimiter() != Delimiter::Brace {
+        res.extend([TokenTree::Punct(Punct::new(';', Spacing::Alone))]);
+    }
+    Ok(res)
+}
@@ -0,0 +1,285 @@
+//  ATTR ALIAS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for defining named bundles of attributes, and applying them to items.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates a metavariable with a fragment specifier, e.g., `$attr:meta`.
+///
+/// # Arguments
+/// - `name`: The name of the metavariable.
+/// - `kind`: The fragment specifier of the metavariable.
+///
+/// # Returns
+/// A [`TokenStream`] encoding `$<name>:<kind>`.
+fn metavar(name: &str, kind: &str) -> TokenStream {
+    TokenStream::from_iter([
+        TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+        TokenTree::Ident(Ident::new(name, Span::call_site())),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new(kind, Span::call_site())),
+    ])
+}
+
+/// Generates a repetition, e.g., `$( ... )*`.
+///
+/// # Arguments
+/// - `body`: The tokens to repeat.
+///
+/// # Returns
+/// A [`TokenStream`] encoding `$(<body>)*`.
+fn repetition(body: TokenStream) -> TokenStream {
+    TokenStream::from_iter([
+        TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, body)),
+        TokenTree::Punct(Punct::new('*', Spacing::Alone)),
+    ])
+}
+
+/// Generates an attribute with the given contents, e.g., `#[$attr]`.
+///
+/// # Arguments
+/// - `contents`: The tokens in the attribute.
+///
+/// # Returns
+/// A [`TokenStream`] encoding `#[<contents>]`.
+fn attribute(contents: TokenStream) -> TokenStream {
+    TokenStream::from_iter([TokenTree::Punct(Punct::new('#', Spacing::Alone)), TokenTree::Group(Group::new(Delimiter::Bracket, contents))])
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single alias.
+struct Alias {
+    /// Any attributes on the alias itself, e.g., doc comments.
+    attrs:   TokenStream,
+    /// The visibility of the alias, which is empty if it is private.
+    vis:     TokenStream,
+    /// The name of the alias.
+    name:    Ident,
+    /// The attributes the alias stands for.
+    bundled: TokenStream,
+}
+impl Alias {
+    /// Parses an Alias from an iterator over [`TokenTree`]s.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Alias.
+    ///
+    /// # Errors
+    /// This function can error if the head of the input was not a valid alias.
+    fn parse(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Self, Diagnostic2> {
+        // Parse the attributes on the alias itself
+        let attrs: TokenStream = parse_attrs(iter)?;
+
+        // Parse the visibility
+        let mut vis = TokenStream::new();
+        match iter.peek() {
+            Some(TokenTree::Ident(ident)) if ident == "pub" => {
+                vis.extend(iter.next());
+                if let Some(TokenTree::Group(group)) = iter.peek()
+                    && group.delimiter() == Delimiter::Parenthesis
+                {
+                    vis.extend(iter.next());
+                }
+            },
+            // Allow visibilities given as `$vis:vis`
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+                vis.extend(group.stream());
+                iter.next();
+            },
+            _ => {},
+        }
+
+        // Parse the name and the `=`
+        let name: Ident = match iter.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tt) => return Err(error2(tt.span(), "Expected the name of an alias")),
+            None => return Err(error2(Span::call_site(), "Expected the name of an alias")),
+        };
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+            None => return Err(error2(name.span(), "Expected '='")),
+        }
+
+        // Parse the bundled attributes, followed by a semicolon
+        let bundled: TokenStream = parse_attrs(iter)?;
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ';' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either an attribute or ';'")),
+            None => return Err(error2(name.span(), "Expected ';' after the attributes of the alias")),
+        }
+        Ok(Self { attrs, vis, name, bundled })
+    }
+
+    /// Generates the `macro_rules!`-macro implementing this alias.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] encoding
+    /// `<attrs> <vis> macro_rules! <name> { ([$($attrs:tt)*] $($item:tt)*) => { $($attrs)* <bundled> $($item)* }; }`.
+    fn generate(self) -> TokenStream {
+        let Self { attrs, vis, name, bundled } = self;
+
+        // The matcher: `([$($attrs:tt)*] $($item:tt)*)`
+        let mut matcher = TokenStream::from(TokenTree::Group(Group::new(Delimiter::Bracket, repetition(metavar("attrs", "tt")))));
+        matcher.extend(repetition(metavar("item", "tt")));
+
+        // The transcriber: `{ $($attrs)* <bundled> $($item)* }`
+        let var =
+            |name: &str| TokenStream::from_iter([TokenTree::Punct(Punct::new('$', Spacing::Alone)), TokenTree::Ident(Ident::new(name, Span::call_site()))]);
+        let mut transcriber: TokenStream = repetition(var("attrs"));
+        transcriber.extend(bundled);
+        transcriber.extend(repetition(var("item")));
+
+        let mut res: TokenStream = attrs;
+        res.extend(vis);
+        res.extend([
+            TokenTree::Ident(Ident::new("macro_rules", Span::call_site())),
+            TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+            TokenTree::Ident(name),
+            TokenTree::Group(Group::new(
+                Delimiter::Brace,
+                TokenStream::from_iter([
+                    TokenTree::Group(Group::new(Delimiter::Parenthesis, matcher)),
+                    TokenTree::Punct(Punct::new('=', Spacing::Joint)),
+                    TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+                    TokenTree::Group(Group::new(Delimiter::Brace, transcriber)),
+                    TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+                ]),
+            )),
+        ]);
+        res
+    }
+}
+
+/// Parses any number of outer attributes.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The parsed attributes, as-is.
+///
+/// # Errors
+/// This function errors if a `#` is not followed by an attribute in square brackets.
+fn parse_attrs(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<TokenStream, Diagnostic2> {
+    let mut attrs = TokenStream::new();
+    while let Some(pound) = iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '#')) {
+        match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => attrs.extend([pound, TokenTree::Group(group)]),
+            Some(tt) => return Err(error2(tt.span(), "Expected an attribute in square brackets")),
+            None => return Err(error2(pound.span(), "Expected an attribute in square brackets")),
+        }
+    }
+    Ok(attrs)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`attr_alias()`](super::attr_alias())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the aliases to define.
+///
+/// # Returns
+/// A new [`TokenStream`] with a `macro_rules!`-macro for every alias.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn attr_alias(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut res = TokenStream::new();
+    let mut iter = input.into_iter().peekable();
+    while iter.peek().is_some() {
+        match Alias::parse(&mut iter) {
+            // Aliases with a visibility are given path-based scope like with `pub_macro!()`
+            Ok(alias) if !alias.vis.is_empty() => res.extend(crate::pub_macro::pub_macro(alias.generate())?),
+            Ok(alias) => res.extend(alias.generate()),
+            // NOTE: The error ends up in item position, so needs to be a valid item
+            Err(err) => return Err(item_error(err)),
+        }
+    }
+    Ok(res)
+}
+
+/// Defines the implementation of the [`apply_alias()`](super::apply_alias())-macro.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the paths to the aliases to apply.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the first alias, given the item's attributes (including
+/// one applying the remaining aliases) and the rest of the item.
+///
+/// # Errors
+/// This function may error if no aliases are given or if the item's attributes are malformed.
+pub fn apply_alias(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Split the paths on commas
+    let mut paths: Vec<TokenStream> = Vec::new();
+    let mut path = TokenStream::new();
+    for tt in attr {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => paths.push(std::mem::take(&mut path)),
+            tt => path.extend([tt]),
+        }
+    }
+    paths.push(path);
+    paths.retain(|path| !path.is_empty());
+    if paths.is_empty() {
+        return Err(item_error(error2(Span::call_site(), "Expected at least one alias (e.g., `#[apply_alias(common)]`)")).with_tokens(item));
+    }
+
+    // Split the item's own attributes from the rest
+    let mut iter = item.into_iter().peekable();
+    let mut attrs: TokenStream = match parse_attrs(&mut iter) {
+        Ok(attrs) => attrs,
+        Err(err) => return Err(item_error(err)),
+    };
+
+    // Apply the remaining aliases with a new attribute, which comes before the first alias' ones
+    let first: TokenStream = paths.remove(0);
+    if !paths.is_empty() {
+        let mut rest = TokenStream::new();
+        for (i, path) in paths.into_iter().enumerate() {
+            if i > 0 {
+                rest.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+            }
+            rest.extend(path);
+        }
+        attrs.extend(attribute(TokenStream::from_iter([
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(Ident::new("macro_toolkit", Span::call_site())),
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(Ident::new("apply_alias", Span::call_site())),
+            TokenTree::Group(Group::new(Delimiter::Parenthesis, rest)),
+        ])));
+    }
+
+    // Call the first alias with them
+    let mut body = TokenStream::from(TokenTree::Group(Group::new(Delimiter::Bracket, attrs)));
+    body.extend(iter);
+    let mut res: TokenStream = first;
+    res.extend([TokenTree::Punct(Punct::new('!', Spacing::Alone)), TokenTree::Group(Group::new(Delimiter::Brace, body))]);
+    Ok(res)
+}
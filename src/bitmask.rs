@@ -0,0 +1,204 @@
+//  BITMASK.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for building integer literals from bit positions.
+//
+
+use std::iter::Peekable;
+use std::str::FromStr as _;
+
+use proc_macro2::{Literal, Span, TokenStream, TokenTree};
+use syn::{Lit, LitInt};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, int_lit, parse_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Defines a parsed, inclusive range of bits to set.
+struct Bits {
+    /// The first bit to set.
+    start: u32,
+    /// The last bit to set.
+    end:   u32,
+    /// The span of the position(s).
+    span:  Span,
+}
+
+
+
+/// Parses a single bit position.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding remaining tokens.
+///
+/// # Returns
+/// A tuple of the position and the literal encoding it.
+///
+/// # Errors
+/// This function errors if the head of the input was not a valid bit position.
+fn parse_pos(iter: &mut impl Iterator<Item = TokenTree>) -> Result<(u32, LitInt), Diagnostic2> {
+    let lit: LitInt = match iter.next().map(parse_lit).transpose()? {
+        Some(Lit::Int(lit)) => lit,
+        Some(lit) => return Err(error2(lit.span(), "Expected an integer literal")),
+        None => return Err(error2(Span::mixed_site(), "Expected an integer literal")),
+    };
+    match lit.base10_parse::<u32>() {
+        Ok(pos) => Ok((pos, lit)),
+        Err(_) => Err(error2(lit.span(), "Bit position is too large")),
+    }
+}
+
+/// Parses a bit position or a range of them.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding remaining tokens.
+///
+/// # Returns
+/// The parsed [`Bits`].
+///
+/// # Errors
+/// This function errors if the head of the input was not a valid position or range.
+fn parse_bits(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Bits, Diagnostic2> {
+    let (start, start_lit): (u32, LitInt) = parse_pos(iter)?;
+    let span: Span = start_lit.span();
+    if !matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '.') {
+        return Ok(Bits { start, end: start, span });
+    }
+    iter.next();
+
+    // It's a range
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '.' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected either '..' or '..='")),
+        None => return Err(error2(span, "Expected either '..' or '..='")),
+    }
+    let incl: bool = matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=');
+    if incl {
+        iter.next();
+    }
+    let (end, end_lit): (u32, LitInt) = parse_pos(iter)?;
+    let span: Span = span.join(end_lit.span()).unwrap_or(span);
+    match (incl, end.checked_sub(start)) {
+        (true, Some(_)) => Ok(Bits { start, end, span }),
+        (false, Some(n)) if n > 0 => Ok(Bits { start, end: end - 1, span }),
+        _ => Err(error2(span, "Range of bits is empty")),
+    }
+}
+
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the [`IntType`] given, if any, and the [`Bits`] to set.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(Option<IntType>, Vec<Bits>), Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+
+    // Parse the optional type first
+    let mut ty: Option<IntType> = None;
+    if let Some(TokenTree::Ident(ident)) = iter.peek() {
+        match IntType::from_suffix(&ident.to_string()) {
+            Some(IntType::ISize | IntType::USize) => {
+                return Err(error2(ident.span(), &format!("Cannot build a `{ident}` bitmask because its width is platform-dependent")));
+            },
+            Some(t) => ty = Some(t),
+            None => return Err(error2(ident.span(), "Expected either an integer type or a bit position")),
+        }
+        iter.next();
+    }
+
+    // Then parse the comma-separated bits
+    let mut bits: Vec<Bits> = Vec::new();
+    while iter.peek().is_some() {
+        bits.push(parse_bits(&mut iter)?);
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing after a bit position")),
+            None => break,
+        }
+    }
+    Ok((ty, bits))
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Formats an unsigned value as a binary literal, with separators every four bits.
+///
+/// # Arguments
+/// - `value`: The value to format.
+/// - `ty`: The type to suffix the literal with, if any.
+///
+/// # Returns
+/// A [`TokenStream`] with the literal.
+fn bin_lit(value: u128, ty: Option<IntType>) -> TokenStream {
+    let digits: String = format!("{value:b}");
+    let mut lit = String::with_capacity(2 + digits.len() + digits.len() / 4 + 4);
+    lit.push_str("0b");
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(4) {
+            lit.push('_');
+        }
+        lit.push(c);
+    }
+    if let Some(ty) = ty {
+        lit.push_str(ty.suffix());
+    }
+    TokenStream::from(TokenTree::Literal(Literal::from_str(&lit).unwrap()))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`bitmask()`](super::bitmask())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the bit positions to set.
+///
+/// # Returns
+/// A new [`TokenStream`] with an integer literal that has exactly the given bits set.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if any of the bits
+/// is out of range for the type.
+pub fn bitmask(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (ty, bits): (Option<IntType>, Vec<Bits>) = parse_input(input)?;
+    let width: u32 = ty.map(|ty| ty.bits()).unwrap_or(128);
+
+    // Set the bits
+    let mut mask: u128 = 0;
+    for Bits { start, end, span } in bits {
+        if end >= width {
+            return Err(error2(
+                span,
+                &match ty {
+                    Some(ty) => format!("Bit {end} is out of range for `{}` (which has {width} bits)", ty.suffix()),
+                    None => format!("Bit {end} is out of range (at most {width} bits are supported)"),
+                },
+            ));
+        }
+        // Build the range as all bits up to `end` minus all bits up to `start`
+        let upto_end: u128 = if end == 127 { u128::MAX } else { (1 << (end + 1)) - 1 };
+        mask |= upto_end & !((1 << start) - 1);
+    }
+
+    // Emit it. Signed masks with the sign bit set are emitted as negative literals, as binary
+    // literals for them are out of range.
+    match ty {
+        Some(ty) if ty.is_signed() && (mask >> (width - 1)) & 1 == 1 => {
+            let abs: u128 = if width == 128 { mask.wrapping_neg() } else { (1 << width) - mask };
+            Ok(int_lit(true, abs, Some(ty), Span::call_site()))
+        },
+        ty => Ok(bin_lit(mask, ty)),
+    }
+}
@@ -0,0 +1,151 @@
+//  BITPATTERN.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for parsing bit patterns with wildcards into a mask and a value.
+//
+
+use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, int_lit, parse_lit, subspan};
+use crate::utils::Callback;
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The type to emit the mask and value as, if any.
+    ty:       Option<IntType>,
+    /// The literal to parse.
+    lit:      LitStr,
+    /// The callback to pass the mask and value to, if any.
+    callback: Option<Callback>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter();
+
+        // Parse the optional type first
+        let mut ty: Option<IntType> = None;
+        let mut tree: Option<TokenTree> = iter.next();
+        if let Some(TokenTree::Ident(ident)) = &tree {
+            ty = match IntType::from_suffix(&ident.to_string()) {
+                Some(ty) if !ty.is_signed() => Some(ty),
+                _ => return Err(error2(ident.span(), "Expected either an unsigned integer type or a string literal")),
+            };
+            tree = iter.next();
+        }
+
+        // Then the literal
+        let lit: LitStr = match tree.map(parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected a string literal")),
+        };
+
+        // Finally, the optional callback
+        let callback: Option<Callback> = Callback::parse_opt(&mut iter)?;
+        Ok(Self { ty, lit, callback })
+    }
+}
+
+
+
+/// Parses a bit pattern from a string literal.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] to parse.
+///
+/// # Returns
+/// A tuple of the mask, the value and the number of bits in the pattern.
+///
+/// # Errors
+/// This function errors if the literal does not contain a valid bit pattern.
+fn parse_pattern(lit: &LitStr) -> Result<(u128, u128, u32), Diagnostic2> {
+    let value: String = lit.value();
+    let offset: usize = if value.starts_with("0b") { 2 } else { 0 };
+
+    let mut mask: u128 = 0;
+    let mut val: u128 = 0;
+    let mut bits: u32 = 0;
+    for (i, c) in value[offset..].char_indices() {
+        let i: usize = offset + i;
+        let (m, v): (u128, u128) = match c {
+            '0' => (1, 0),
+            '1' => (1, 1),
+            'x' | 'X' => (0, 0),
+            '_' => continue,
+            c => {
+                return Err(error2(
+                    subspan(lit, i..i + c.len_utf8()),
+                    &format!("Invalid character {c:?} at position {i} in bit pattern (expected '0', '1', 'x' or '_')"),
+                ));
+            },
+        };
+        if bits == 128 {
+            return Err(error2(subspan(lit, i..i + c.len_utf8()), &format!("Bit pattern is too long (bit at position {i} exceeds 128 bits)")));
+        }
+        mask = (mask << 1) | m;
+        val = (val << 1) | v;
+        bits += 1;
+    }
+    if bits == 0 {
+        return Err(error2(lit.span(), "Expected a bit pattern with at least one bit (e.g., \"0b10xx\")"));
+    }
+    Ok((mask, val, bits))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`bitpattern()`](super::bitpattern())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the pattern to parse.
+///
+/// # Returns
+/// A new [`TokenStream`] with a `(mask, value)`-tuple of integer literals, or a call to the given
+/// callback with them.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if the literal does not
+/// contain a valid bit pattern or if the pattern does not fit in the given type.
+pub fn bitpattern(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { ty, lit, callback } = Input::parse(input)?;
+    let span: Span = lit.span();
+    let (mask, value, bits) = parse_pattern(&lit)?;
+    if let Some(ty) = ty
+        && bits > ty.bits()
+    {
+        return Err(error2(span, &format!("Bit pattern has {bits} bits, which does not fit in a `{}`", ty.suffix())));
+    }
+
+    // Generate the pair
+    let mut res: TokenStream = int_lit(false, mask, ty, span);
+    let mut comma = Punct::new(',', Spacing::Alone);
+    comma.set_span(span);
+    res.extend([TokenTree::Punct(comma)]);
+    res.extend(int_lit(false, value, ty, span));
+    match callback {
+        Some(callback) => Ok(callback.call(res)),
+        None => {
+            let mut group = Group::new(Delimiter::Parenthesis, res);
+            group.set_span(span);
+            Ok(TokenStream::from(TokenTree::Group(group)))
+        },
+    }
+}
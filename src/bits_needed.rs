@@ -0,0 +1,100 @@
+//  BITS NEEDED.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for computing the number of bits or bytes needed to represent an integer
+//!   literal.
+//
+
+use proc_macro2::{Literal, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{SignedLit, parse_signed_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macro.
+struct Input {
+    /// Whether to count bytes instead of bits.
+    bytes:  bool,
+    /// Whether to count a sign bit.
+    signed: bool,
+    /// Whether the value is negative.
+    neg:    bool,
+    /// The absolute value of the literal.
+    abs:    u128,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the modifiers
+        let mut bytes: bool = false;
+        let mut signed: bool = false;
+        while let Some(TokenTree::Ident(ident)) = iter.peek() {
+            match ident.to_string().as_str() {
+                "bits" if !bytes => {},
+                "bytes" if !bytes => bytes = true,
+                "signed" if !signed => signed = true,
+                _ => return Err(error2(ident.span(), "Expected either `bits`, `bytes`, `signed` or an integer literal")),
+            }
+            iter.next();
+        }
+
+        // Parse the literal
+        let SignedLit { neg, lit } = parse_signed_lit(&mut iter)?;
+        let Lit::Int(lit) = lit else {
+            return Err(error2(lit.span(), "Expected an integer literal"));
+        };
+        let abs: u128 = lit.base10_parse().map_err(|_| error2(lit.span(), "Integer literal is too large"))?;
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the integer literal"));
+        }
+        Ok(Self { bytes, signed, neg: neg && abs != 0, abs })
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`bits_needed()`](super::bits_needed())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to measure.
+///
+/// # Returns
+/// A new [`TokenStream`] with an unsuffixed integer literal encoding the number of bits or bytes.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn bits_needed(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { bytes, signed, neg, abs } = Input::parse(input)?;
+
+    // Count the bits
+    let bits: u32 = if neg {
+        // We need to fit in `-2^(bits - 1)`, so count the bits of `abs - 1` and add the sign bit
+        (128 - (abs - 1).leading_zeros()) + 1
+    } else {
+        // Zero still needs one bit to be stored
+        (128 - abs.leading_zeros()).max(1) + if signed { 1 } else { 0 }
+    };
+    let res: u32 = if bytes { bits.div_ceil(8) } else { bits };
+
+    // Serialize it (not spanned to the input, as it's a different literal that lints would mistake for it)
+    let mut res = Literal::u32_unsuffixed(res);
+    res.set_span(Span::call_site());
+    Ok(TokenStream::from(TokenTree::Literal(res)))
+}
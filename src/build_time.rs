@@ -0,0 +1,164 @@
+//  BUILD TIME.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for emitting the time of the build as a literal.
+//
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use proc_macro2::{Literal, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+
+
+/***** CONSTANTS *****/
+/// The environment variable that may override the build time.
+const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the formats in which the time can be emitted.
+#[derive(Clone, Copy)]
+enum Format {
+    /// Seconds since the Unix epoch, as a `u64` literal.
+    Unix,
+    /// Milliseconds since the Unix epoch, as a `u64` literal.
+    UnixMs,
+    /// An RFC 3339 string literal (e.g., `"2024-01-02T03:04:05Z"`).
+    Rfc3339,
+    /// An ISO 8601 date string literal (e.g., `"2024-01-02"`).
+    Date,
+}
+
+
+
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the [`Format`] to emit the time in and whether to respect `SOURCE_DATE_EPOCH`.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(Format, bool), Diagnostic2> {
+    let mut format: Option<Format> = None;
+    let mut sde: bool = false;
+    let mut iter = input.into_iter();
+    while let Some(tt) = iter.next() {
+        let TokenTree::Ident(ident) = tt else {
+            return Err(error2(tt.span(), "Expected either a format or 'source_date_epoch'"));
+        };
+        let sident: String = ident.to_string();
+        match sident.as_str() {
+            "source_date_epoch" if !sde => sde = true,
+            "source_date_epoch" => return Err(error2(ident.span(), "Duplicate 'source_date_epoch'")),
+            "unix" | "unix_ms" | "rfc3339" | "date" if format.is_some() => {
+                return Err(error2(ident.span(), "Only one format can be given"));
+            },
+            "unix" => format = Some(Format::Unix),
+            "unix_ms" => format = Some(Format::UnixMs),
+            "rfc3339" => format = Some(Format::Rfc3339),
+            "date" => format = Some(Format::Date),
+            _ => return Err(error2(ident.span(), "Expected one of 'unix', 'unix_ms', 'rfc3339', 'date' or 'source_date_epoch'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+            None => break,
+        }
+    }
+    Ok((format.unwrap_or(Format::Rfc3339), sde))
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Converts a number of days since the Unix epoch to a civil date.
+///
+/// This uses the algorithm from <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+///
+/// # Arguments
+/// - `days`: The number of days since 1970-01-01.
+///
+/// # Returns
+/// A tuple of the year, month (1-12) and day (1-31).
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z: u64 = days + 719468;
+    let era: u64 = z / 146097;
+    let doe: u64 = z - era * 146097;
+    let yoe: u64 = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy: u64 = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp: u64 = (5 * doy + 2) / 153;
+    let day: u64 = doy - (153 * mp + 2) / 5 + 1;
+    let month: u64 = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year: u64 = yoe + era * 400 + (month <= 2) as u64;
+    (year, month, day)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`build_time()`](super::build_time())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the format and options.
+///
+/// # Returns
+/// A new [`TokenStream`] with a literal encoding the current time.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if the system clock is
+/// before the Unix epoch or if `SOURCE_DATE_EPOCH` is invalid.
+pub fn build_time(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (format, sde): (Format, bool) = parse_input(input)?;
+    // In deterministic mode, the time must come from `SOURCE_DATE_EPOCH`
+    if cfg!(feature = "deterministic") && std::env::var_os(SOURCE_DATE_EPOCH).is_none() {
+        return Err(error2(
+            Span::call_site(),
+            &format!("Environment variable `{SOURCE_DATE_EPOCH}` must be set to use `build_time!()` when the `deterministic`-feature is enabled"),
+        ));
+    }
+    let sde: bool = sde || cfg!(feature = "deterministic");
+
+    // Find the time
+    let time: Duration = match std::env::var(SOURCE_DATE_EPOCH) {
+        Ok(value) if sde => match value.trim().parse::<u64>() {
+            Ok(secs) => Duration::from_secs(secs),
+            Err(_) => {
+                return Err(error2(
+                    Span::call_site(),
+                    &format!("Environment variable `{SOURCE_DATE_EPOCH}` is set to {value:?}, which is not a non-negative number of seconds"),
+                ));
+            },
+        },
+        _ => SystemTime::now().duration_since(UNIX_EPOCH).map_err(|_| error2(Span::call_site(), "System clock is set before 1970-01-01"))?,
+    };
+
+    // Format it
+    let secs: u64 = time.as_secs();
+    let lit: Literal = match format {
+        Format::Unix => Literal::u64_suffixed(secs),
+        Format::UnixMs => Literal::u64_suffixed(secs.saturating_mul(1000).saturating_add(time.subsec_millis() as u64)),
+        Format::Rfc3339 | Format::Date => {
+            let (year, month, day): (u64, u64, u64) = civil_from_days(secs / 86400);
+            let date: String = format!("{year:04}-{month:02}-{day:02}");
+            if matches!(format, Format::Date) {
+                Literal::string(&date)
+            } else {
+                let rem: u64 = secs % 86400;
+                Literal::string(&format!("{date}T{:02}:{:02}:{:02}Z", rem / 3600, rem % 3600 / 60, rem % 60))
+            }
+        },
+    };
+    Ok(TokenStream::from(TokenTree::Literal(lit)))
+}
@@ -0,0 +1,501 @@
+//  CALC.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for evaluating arithmetic over literals at compile time.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use proc_macro2::{Delimiter, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, int_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Defines the types that the result can be computed in.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Type {
+    /// Any of the integer types.
+    Int(IntType),
+    /// `f32`
+    F32,
+    /// `f64`
+    F64,
+}
+impl Type {
+    /// Parses a Type from the suffix of a literal (or the name of the type).
+    ///
+    /// # Arguments
+    /// - `suffix`: The suffix to parse.
+    ///
+    /// # Returns
+    /// The Type, or [`None`] if the suffix is not that of a numeric type.
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "f32" => Some(Self::F32),
+            "f64" => Some(Self::F64),
+            suffix => IntType::from_suffix(suffix).map(Self::Int),
+        }
+    }
+
+    /// Returns whether this is a floating-point type.
+    ///
+    /// # Returns
+    /// True if it is, false otherwise.
+    fn is_float(&self) -> bool { matches!(self, Self::F32 | Self::F64) }
+}
+impl Display for Type {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Int(ty) => write!(f, "{}", ty.suffix()),
+            Self::F32 => write!(f, "f32"),
+            Self::F64 => write!(f, "f64"),
+        }
+    }
+}
+
+
+
+/// Defines the binary operators that are supported.
+#[derive(Clone, Copy)]
+enum BinOp {
+    /// `+`
+    Add,
+    /// `-`
+    Sub,
+    /// `*`
+    Mul,
+    /// `/`
+    Div,
+    /// `%`
+    Rem,
+    /// `<<`
+    Shl,
+    /// `>>`
+    Shr,
+    /// `&`
+    And,
+    /// `|`
+    Or,
+    /// `^`
+    Xor,
+}
+impl BinOp {
+    /// Returns the precedence of this operator, following Rust's.
+    ///
+    /// # Returns
+    /// The precedence, where higher binds more tightly.
+    fn prec(&self) -> u8 {
+        match self {
+            Self::Mul | Self::Div | Self::Rem => 5,
+            Self::Add | Self::Sub => 4,
+            Self::Shl | Self::Shr => 3,
+            Self::And => 2,
+            Self::Xor => 1,
+            Self::Or => 0,
+        }
+    }
+}
+impl Display for BinOp {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Add => write!(f, "+"),
+            Self::Sub => write!(f, "-"),
+            Self::Mul => write!(f, "*"),
+            Self::Div => write!(f, "/"),
+            Self::Rem => write!(f, "%"),
+            Self::Shl => write!(f, "<<"),
+            Self::Shr => write!(f, ">>"),
+            Self::And => write!(f, "&"),
+            Self::Or => write!(f, "|"),
+            Self::Xor => write!(f, "^"),
+        }
+    }
+}
+
+
+
+/// Defines the value of an expression.
+#[derive(Clone, Copy)]
+pub enum Value {
+    /// An integer value.
+    Int(i128),
+    /// A floating-point value.
+    Float(f64),
+}
+impl Display for Value {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+
+
+/// Evaluates an expression while parsing it.
+pub struct Evaluator {
+    /// The tokens making up the expression.
+    tokens: Vec<TokenTree>,
+    /// The position of the next token to parse.
+    pos:    usize,
+    /// The type to evaluate the expression in, if known.
+    ty:     Option<Type>,
+}
+impl Evaluator {
+    /// Constructor for the Evaluator.
+    ///
+    /// # Arguments
+    /// - `tokens`: The [`TokenStream`] encoding the expression to evaluate.
+    /// - `ty`: The type to evaluate the expression in, if known.
+    ///
+    /// # Returns
+    /// A new Evaluator that is ready to [`evaluate()`](Evaluator::evaluate()).
+    pub fn new(tokens: TokenStream, ty: Option<Type>) -> Self { Self { tokens: tokens.into_iter().collect(), pos: 0, ty } }
+
+    /// Evaluates the full expression.
+    ///
+    /// # Arguments
+    /// - `span`: A [`Span`] to report errors at if the expression is empty.
+    ///
+    /// # Returns
+    /// The [`Value`] of the expression.
+    ///
+    /// # Errors
+    /// This function errors if the expression was invalid or if evaluating it failed.
+    pub fn evaluate(mut self, span: Span) -> Result<Value, Diagnostic2> {
+        let value: Value = self.expr(0, span)?;
+        if let Some(tt) = self.tokens.get(self.pos) {
+            return Err(error2(tt.span(), "Expected an operator"));
+        }
+        Ok(value)
+    }
+
+    /// Evaluates an expression with binary operators of at least the given precedence.
+    ///
+    /// # Arguments
+    /// - `min_prec`: The minimum precedence of operators to consume.
+    /// - `span`: A [`Span`] to report errors at if the expression is missing.
+    ///
+    /// # Returns
+    /// The [`Value`] of the expression.
+    ///
+    /// # Errors
+    /// This function errors if the expression was invalid or if evaluating it failed.
+    fn expr(&mut self, min_prec: u8, span: Span) -> Result<Value, Diagnostic2> {
+        let mut lhs: Value = self.unary(span)?;
+        while let Some((op, op_span, len)) = self.peek_op()? {
+            if op.prec() < min_prec {
+                break;
+            }
+            self.pos += len;
+            let rhs: Value = self.expr(op.prec() + 1, op_span)?;
+            lhs = self.apply(op, lhs, rhs, op_span)?;
+        }
+        Ok(lhs)
+    }
+
+    /// Evaluates a possibly negated operand.
+    ///
+    /// # Arguments
+    /// - `span`: A [`Span`] to report errors at if the operand is missing.
+    ///
+    /// # Returns
+    /// The [`Value`] of the operand.
+    ///
+    /// # Errors
+    /// This function errors if the operand was invalid or if negating it failed.
+    fn unary(&mut self, span: Span) -> Result<Value, Diagnostic2> {
+        match self.tokens.get(self.pos).cloned() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '-' => {
+                self.pos += 1;
+                // Negated literals are only checked after negating them, so `-128i8` works
+                let value: Value = match self.tokens.get(self.pos).cloned() {
+                    Some(TokenTree::Literal(lit)) => {
+                        self.pos += 1;
+                        self.literal(Lit::new(lit))?
+                    },
+                    _ => self.unary(p.span())?,
+                };
+                match value {
+                    Value::Int(value) => match value.checked_neg() {
+                        Some(res) => self.check(Value::Int(res), p.span(), || format!("-{value}")),
+                        None => Err(error2(p.span(), &format!("Arithmetic overflow in `-{value}`"))),
+                    },
+                    Value::Float(value) => Ok(Value::Float(-value)),
+                }
+            },
+            Some(TokenTree::Literal(lit)) => {
+                self.pos += 1;
+                let value: Value = self.literal(Lit::new(lit.clone()))?;
+                self.check(value, lit.span(), || lit.to_string())
+            },
+            Some(TokenTree::Group(group)) if matches!(group.delimiter(), Delimiter::Parenthesis | Delimiter::None) => {
+                self.pos += 1;
+                Self::new(group.stream(), self.ty).evaluate(group.span())
+            },
+            Some(tt) => Err(error2(tt.span(), "Expected a literal, '-' or parenthesis")),
+            None => Err(error2(span, "Expected an expression")),
+        }
+    }
+
+    /// Evaluates a literal.
+    ///
+    /// # Arguments
+    /// - `lit`: The [`Lit`] to evaluate.
+    ///
+    /// Note that this does not check whether the value fits in the type of the expression.
+    ///
+    /// # Returns
+    /// The [`Value`] of the literal.
+    ///
+    /// # Errors
+    /// This function errors if the literal was not numeric or too large.
+    fn literal(&self, lit: Lit) -> Result<Value, Diagnostic2> {
+        match lit {
+            Lit::Int(lit) if self.ty.is_some_and(|ty| ty.is_float()) => {
+                lit.base10_parse::<f64>().map(Value::Float).map_err(|_| error2(lit.span(), "Expected a floating-point literal"))
+            },
+            Lit::Int(lit) => {
+                let value: u128 = lit.base10_parse().map_err(|_| error2(lit.span(), "Integer literal is too large"))?;
+                i128::try_from(value).map(Value::Int).map_err(|_| error2(lit.span(), &format!("Integer literal is too large (maximum is {})", i128::MAX)))
+            },
+            Lit::Float(lit) if self.ty.is_none_or(|ty| ty.is_float()) => {
+                lit.base10_parse::<f64>().map(Value::Float).map_err(|_| error2(lit.span(), "Invalid floating-point literal"))
+            },
+            Lit::Float(lit) => Err(error2(lit.span(), &format!("Expected an integer literal, since the result is a `{}`", self.ty.unwrap()))),
+            lit => Err(error2(lit.span(), "Expected an integer or floating-point literal")),
+        }
+    }
+
+    /// Peeks the next binary operator, if any.
+    ///
+    /// # Returns
+    /// A tuple of the operator, its span and the number of tokens making it up, or [`None`] if
+    /// the expression ends here.
+    ///
+    /// # Errors
+    /// This function errors if the next token is not a supported operator.
+    fn peek_op(&self) -> Result<Option<(BinOp, Span, usize)>, Diagnostic2> {
+        let p: &Punct = match self.tokens.get(self.pos) {
+            Some(TokenTree::Punct(p)) => p,
+            Some(tt) => return Err(error2(tt.span(), "Expected an operator")),
+            None => return Ok(None),
+        };
+        let next: Option<char> = match self.tokens.get(self.pos + 1) {
+            Some(TokenTree::Punct(next)) if p.spacing() == Spacing::Joint => Some(next.as_char()),
+            _ => None,
+        };
+        let (op, len): (BinOp, usize) = match (p.as_char(), next) {
+            ('<', Some('<')) => (BinOp::Shl, 2),
+            ('>', Some('>')) => (BinOp::Shr, 2),
+            ('+', _) => (BinOp::Add, 1),
+            ('-', _) => (BinOp::Sub, 1),
+            ('*', _) => (BinOp::Mul, 1),
+            ('/', _) => (BinOp::Div, 1),
+            ('%', _) => (BinOp::Rem, 1),
+            ('&', Some('&')) | ('|', Some('|')) => return Err(error2(p.span(), "Logical operators are not supported")),
+            ('&', _) => (BinOp::And, 1),
+            ('|', _) => (BinOp::Or, 1),
+            ('^', _) => (BinOp::Xor, 1),
+            _ => return Err(error2(p.span(), "Expected one of `+`, `-`, `*`, `/`, `%`, `<<`, `>>`, `&`, `|` or `^`")),
+        };
+        Ok(Some((op, p.span(), len)))
+    }
+
+    /// Applies a binary operator.
+    ///
+    /// # Arguments
+    /// - `op`: The [`BinOp`] to apply.
+    /// - `lhs`: The left-hand side [`Value`].
+    /// - `rhs`: The right-hand side [`Value`].
+    /// - `span`: The [`Span`] of the operator, for error reporting.
+    ///
+    /// # Returns
+    /// The resulting [`Value`].
+    ///
+    /// # Errors
+    /// This function errors if the operation overflowed or is otherwise invalid.
+    fn apply(&self, op: BinOp, lhs: Value, rhs: Value, span: Span) -> Result<Value, Diagnostic2> {
+        let expr = || format!("{lhs} {op} {rhs}");
+        match (lhs, rhs) {
+            (Value::Int(l), Value::Int(r)) => {
+                let bits: u32 = match self.ty {
+                    Some(Type::Int(ty)) => ty.bits(),
+                    _ => 128,
+                };
+                let res: Option<i128> = match op {
+                    BinOp::Add => l.checked_add(r),
+                    BinOp::Sub => l.checked_sub(r),
+                    BinOp::Mul => l.checked_mul(r),
+                    BinOp::Div | BinOp::Rem if r == 0 => return Err(error2(span, &format!("Division by zero in `{}`", expr()))),
+                    BinOp::Div => l.checked_div(r),
+                    BinOp::Rem => l.checked_rem(r),
+                    BinOp::Shl | BinOp::Shr if r < 0 || r >= bits as i128 => {
+                        return Err(error2(span, &format!("Shift amount in `{}` must be between 0 and {} (inclusive)", expr(), bits - 1)));
+                    },
+                    // Shifting left is checked by shifting back
+                    BinOp::Shl => l.checked_shl(r as u32).filter(|res| res >> r == l),
+                    BinOp::Shr => Some(l >> r),
+                    BinOp::And => Some(l & r),
+                    BinOp::Or => Some(l | r),
+                    BinOp::Xor => Some(l ^ r),
+                };
+                match res {
+                    Some(res) => self.check(Value::Int(res), span, expr),
+                    None => Err(error2(span, &format!("Arithmetic overflow in `{}`", expr()))),
+                }
+            },
+            (Value::Float(l), Value::Float(r)) => {
+                let res: f64 = match op {
+                    BinOp::Add => l + r,
+                    BinOp::Sub => l - r,
+                    BinOp::Mul => l * r,
+                    BinOp::Div => l / r,
+                    BinOp::Rem => l % r,
+                    _ => return Err(error2(span, &format!("Operator `{op}` is not supported for floating-point numbers"))),
+                };
+                if !res.is_finite() {
+                    return Err(error2(span, &format!("Result of `{}` is not a finite number", expr())));
+                }
+                Ok(Value::Float(res))
+            },
+            _ => Err(error2(span, &format!("Cannot mix integers and floating-point numbers in `{}`", expr()))),
+        }
+    }
+
+    /// Checks that a value fits in the type of the expression, if known.
+    ///
+    /// # Arguments
+    /// - `value`: The [`Value`] to check.
+    /// - `span`: The [`Span`] to report errors at.
+    /// - `expr`: A closure producing the expression that resulted in the value, for error reporting.
+    ///
+    /// # Returns
+    /// The given `value`.
+    ///
+    /// # Errors
+    /// This function errors if the value does not fit.
+    fn check(&self, value: Value, span: Span, expr: impl FnOnce() -> String) -> Result<Value, Diagnostic2> {
+        if let (Value::Int(v), Some(Type::Int(ty))) = (value, self.ty)
+            && !ty.fits(v < 0, v.unsigned_abs())
+        {
+            return Err(error2(span, &format!("Arithmetic overflow in `{}` (result {v} does not fit in a `{}`)", expr(), ty.suffix())));
+        }
+        Ok(value)
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds the type of the expression from the suffixes of its literals.
+///
+/// # Arguments
+/// - `tokens`: The [`TokenStream`] to search.
+/// - `ty`: The type found so far, if any, together with the span where it was found.
+///
+/// # Errors
+/// This function errors if literals with different suffixes were found.
+pub fn find_type(tokens: TokenStream, ty: &mut Option<(Type, Span)>) -> Result<(), Diagnostic2> {
+    for tt in tokens {
+        match tt {
+            TokenTree::Literal(lit) => {
+                let suffix: String = match Lit::new(lit.clone()) {
+                    Lit::Int(lit) => lit.suffix().into(),
+                    Lit::Float(lit) => lit.suffix().into(),
+                    _ => continue,
+                };
+                if suffix.is_empty() {
+                    continue;
+                }
+                let Some(found) = Type::from_suffix(&suffix) else {
+                    return Err(error2(lit.span(), &format!("Unknown suffix `{suffix}`")));
+                };
+                match ty {
+                    Some((ty, _)) if *ty != found => {
+                        return Err(error2(lit.span(), &format!("Literal has suffix `{found}`, but the result is a `{ty}`")));
+                    },
+                    Some(_) => {},
+                    None => *ty = Some((found, lit.span())),
+                }
+            },
+            TokenTree::Group(group) => find_type(group.stream(), ty)?,
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`calc()`](super::calc())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the expression to evaluate.
+///
+/// # Returns
+/// A new [`TokenStream`] with a literal encoding the result of the expression.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if evaluating the
+/// expression failed (e.g., because it overflowed).
+pub fn calc(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+
+    // Parse the optional type first
+    let mut ty: Option<(Type, Span)> = None;
+    if let Some(TokenTree::Ident(ident)) = iter.peek() {
+        match Type::from_suffix(&ident.to_string()) {
+            Some(found) => ty = Some((found, ident.span())),
+            None => return Err(error2(ident.span(), "Expected either a numeric type or an expression")),
+        }
+        iter.next();
+    }
+
+    // Find the type of the expression from its literals and evaluate it
+    let tokens: TokenStream = iter.collect();
+    let span: Span = tokens.clone().into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+    find_type(tokens.clone(), &mut ty)?;
+    let ty: Option<Type> = ty.map(|(ty, _)| ty);
+    let value: Value = Evaluator::new(tokens, ty).evaluate(span)?;
+
+    // Serialize the result
+    match value {
+        Value::Int(value) => {
+            let ty: Option<IntType> = match ty {
+                Some(Type::Int(ty)) => Some(ty),
+                _ => None,
+            };
+            Ok(int_lit(value < 0, value.unsigned_abs(), ty, span))
+        },
+        Value::Float(value) => {
+            let mut res = TokenStream::new();
+            if value.is_sign_negative() && value != 0.0 {
+                let mut minus = Punct::new('-', Spacing::Alone);
+                minus.set_span(span);
+                res.extend([TokenTree::Punct(minus)]);
+            }
+            let mut lit: Literal = match ty {
+                Some(Type::F32) => Literal::f32_suffixed(value.abs() as f32),
+                Some(Type::F64) => Literal::f64_suffixed(value.abs()),
+                _ => Literal::f64_unsuffixed(value.abs()),
+            };
+            lit.set_span(span);
+            res.extend([TokenTree::Literal(lit)]);
+            Ok(res)
+        },
+    }
+}
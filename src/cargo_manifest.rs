@@ -0,0 +1,240 @@
+//  CARGO MANIFEST.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for reading fields from the calling crate's `Cargo.toml` and emitting them
+//!   as literals or identifiers.
+//
+
+use std::fs;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use toml::{Table, Value};
+
+use crate::diagnostics::{Diagnostic2, error2, parse_string};
+use crate::utils::{Callback, is_ident};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Reads and parses a manifest.
+///
+/// # Arguments
+/// - `path`: The path of the manifest.
+/// - `span`: The [`Span`] to report errors at.
+///
+/// # Returns
+/// The parsed manifest.
+///
+/// # Errors
+/// This function errors if the manifest could not be read or is not valid TOML.
+fn read_manifest(path: &Path, span: Span) -> Result<Table, Diagnostic2> {
+    let text: String = fs::read_to_string(path).map_err(|err| error2(span, &format!("Failed to read '{}': {err}", path.display())))?;
+    text.parse().map_err(|err| error2(span, &format!("Failed to parse '{}' as TOML: {err}", path.display())))
+}
+
+/// Finds the root manifest of the workspace that the manifest in the given directory belongs to.
+///
+/// # Arguments
+/// - `dir`: The directory of the crate's manifest.
+/// - `span`: The [`Span`] to report errors at.
+///
+/// # Returns
+/// The path of the workspace's manifest and the manifest itself.
+///
+/// # Errors
+/// This function errors if no workspace was found, or if any of the manifests on the way could not
+/// be read.
+fn find_workspace(dir: &Path, span: Span) -> Result<(PathBuf, Table), Diagnostic2> {
+    for dir in dir.ancestors() {
+        let path: PathBuf = dir.join("Cargo.toml");
+        if path.is_file() {
+            let manifest: Table = read_manifest(&path, span)?;
+            if manifest.contains_key("workspace") {
+                return Ok((path, manifest));
+            }
+        }
+    }
+    Err(error2(span, &format!("Failed to find the workspace that '{}' inherits from", dir.join("Cargo.toml").display())))
+}
+
+/// Converts a TOML value to tokens.
+///
+/// # Arguments
+/// - `key`: The key of the value, for use in error messages.
+/// - `value`: The [`Value`] to convert.
+/// - `ident`: Whether to emit strings as identifiers instead of string literals.
+/// - `span`: The [`Span`] to give to the tokens, which is also where errors are reported.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the value.
+///
+/// # Errors
+/// This function errors if the value cannot be represented as tokens, or if `ident` is given and
+/// it is not a string that is a valid identifier.
+fn to_tokens(key: &str, value: &Value, ident: bool, span: Span) -> Result<TokenStream, Diagnostic2> {
+    let mut tt: TokenTree = match value {
+        Value::String(value) if ident => {
+            if !is_ident(value) {
+                return Err(error2(span, &format!("`{key}` is {value:?}, which is not a valid identifier")));
+            }
+            return Ok(TokenTree::Ident(Ident::new(value, span)).into());
+        },
+        _ if ident && !matches!(value, Value::Array(_)) => {
+            return Err(error2(span, &format!("`{key}` is of type {}, which cannot be emitted as an identifier", value.type_str())));
+        },
+        Value::String(value) => TokenTree::Literal(Literal::string(value)),
+        Value::Integer(value) => TokenTree::Literal(Literal::i64_unsuffixed(*value)),
+        Value::Float(value) if value.is_finite() => TokenTree::Literal(Literal::f64_unsuffixed(*value)),
+        Value::Float(value) => return Err(error2(span, &format!("`{key}` is {value}, which cannot be emitted as a literal"))),
+        Value::Boolean(value) => TokenTree::Ident(Ident::new(if *value { "true" } else { "false" }, span)),
+        Value::Datetime(value) => TokenTree::Literal(Literal::string(&value.to_string())),
+        Value::Array(values) => {
+            let mut elems = TokenStream::new();
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    elems.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+                }
+                elems.extend(to_tokens(&format!("{key}.{i}"), value, ident, span)?);
+            }
+            TokenTree::Group(Group::new(Delimiter::Bracket, elems))
+        },
+        Value::Table(table) => {
+            let keys: Vec<&str> = table.keys().map(String::as_str).collect();
+            return Err(Diagnostic2::error(span, format!("`{key}` is a table, which cannot be emitted"))
+                .help(format!("select one of its keys instead ({})", keys.join(", "))));
+        },
+    };
+    tt.set_span(span);
+    Ok(tt.into())
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the key to read, which is a `.`-separated list of identifiers, string literals (for
+/// keys that aren't identifiers) or integer literals (for indexing arrays).
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The segments of the key and a [`Span`] pointing to its first segment.
+///
+/// # Errors
+/// This function errors if the tokens do not start with a key.
+fn parse_key(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<(Vec<String>, Span), Diagnostic2> {
+    let mut key: Vec<String> = Vec::new();
+    let mut span: Option<Span> = None;
+    loop {
+        let tt: TokenTree = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+                let mut tokens = group.stream().into_iter();
+                match (tokens.next(), tokens.next()) {
+                    (Some(tt), None) => tt,
+                    _ => return Err(error2(group.span(), "Expected a key")),
+                }
+            },
+            Some(tt) => tt,
+            None => return Err(error2(span.unwrap_or_else(Span::call_site), "Expected a key")),
+        };
+        span.get_or_insert(tt.span());
+        match &tt {
+            TokenTree::Ident(ident) => key.push(ident.to_string()),
+            TokenTree::Literal(lit) if lit.to_string().bytes().all(|b| b.is_ascii_digit()) => key.push(lit.to_string()),
+            TokenTree::Literal(_) => key.push(parse_string(Some(tt.clone()), tt.span(), "a key")?),
+            _ => return Err(error2(tt.span(), "Expected a key as an identifier or a string literal")),
+        }
+        match iter.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '.' => {
+                iter.next();
+            },
+            _ => return Ok((key, span.unwrap_or_else(Span::call_site))),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`cargo_manifest()`](super::cargo_manifest())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the key to read, an optional `as ident` and an
+///   optional callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the value of the key, or a call to the callback with it.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if the manifest could not be
+/// read, if it does not have the key or if its value cannot be emitted.
+pub fn cargo_manifest(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+
+    // Parse the input
+    let (key, span): (Vec<String>, Span) = parse_key(&mut iter)?;
+    let mut ident: bool = false;
+    if matches!(iter.peek(), Some(TokenTree::Ident(i)) if i == "as") {
+        iter.next();
+        match iter.next() {
+            Some(TokenTree::Ident(i)) if i == "ident" => ident = true,
+            Some(tt) => return Err(error2(tt.span(), "Expected `ident`")),
+            None => return Err(error2(span, "Expected `ident` after `as`")),
+        }
+    }
+    let callback: Option<Callback> = Callback::parse_opt(&mut iter)?;
+
+    // Read the manifest
+    let Some(dir) = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from) else {
+        return Err(error2(span, "Environment variable `CARGO_MANIFEST_DIR` is not set; is this crate being compiled by Cargo?"));
+    };
+    let mut path: PathBuf = dir.join("Cargo.toml");
+    let manifest: Table = read_manifest(&path, span)?;
+
+    // Find the value, following inheritance from the workspace (`foo.workspace = true`)
+    let mut workspace: Option<Table> = None;
+    let root: Value = Value::Table(manifest);
+    let mut value: &Value = &root;
+    for (i, segment) in key.iter().enumerate() {
+        let next: Option<&Value> = match value {
+            Value::Table(table) => table.get(segment),
+            Value::Array(values) => segment.parse::<usize>().ok().and_then(|i| values.get(i)),
+            _ => None,
+        };
+        let Some(next) = next else {
+            let diag = Diagnostic2::error(span, format!("`{}` is not set in '{}'", key.join("."), path.display()));
+            let parent: String = if i > 0 { format!("`{}`", key[..i].join(".")) } else { "the manifest".into() };
+            let diag = match value {
+                Value::Table(table) => diag.help(format!("{parent} only has the keys {}", table.keys().map(String::as_str).collect::<Vec<_>>().join(", "))),
+                Value::Array(values) => diag.help(format!("{parent} is an array of {} element(s)", values.len())),
+                value => diag.help(format!("{parent} is of type {}, which has no keys", value.type_str())),
+            };
+            return Err(diag);
+        };
+        value = next;
+
+        if i == 1
+            && key[0] == "package"
+            && let Value::Table(table) = value
+            && table.get("workspace").and_then(Value::as_bool) == Some(true)
+        {
+            let (root, manifest): (PathBuf, Table) = find_workspace(&dir, span)?;
+            path = root;
+            let inherited: Option<&Value> = workspace.insert(manifest).get("workspace").and_then(|w| w.get("package")).and_then(|p| p.get(segment));
+            value = inherited.ok_or_else(|| error2(span, &format!("`workspace.package.{segment}` is not set in '{}'", path.display())))?;
+        }
+    }
+
+    // Emit it
+    let tokens: TokenStream = to_tokens(&key.join("."), value, ident, span)?;
+    Ok(match callback {
+        Some(callback) => callback.call(tokens),
+        None => tokens,
+    })
+}
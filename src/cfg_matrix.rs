@@ -0,0 +1,166 @@
+//  CFG MATRIX.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides an attribute macro for duplicating an item under several `cfg`-predicates, with
+//!   different substitutions in every copy.
+//
+
+use std::collections::HashMap;
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::duplicate::{parse_values, substitute};
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single row of the matrix, i.e., a copy of the item.
+struct Row {
+    /// The `cfg`-predicate under which this copy is compiled.
+    predicate: TokenStream,
+    /// The placeholders to substitute in this copy, with their values.
+    values:    Vec<(Ident, TokenStream)>,
+}
+impl Row {
+    /// Parses a Row from an iterator over [`TokenTree`]s.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Row.
+    ///
+    /// # Errors
+    /// This function can error if the head of the input was not a valid row.
+    fn parse(iter: &mut impl Iterator<Item = TokenTree>) -> Result<Self, Diagnostic2> {
+        // Parse the predicate up to the `=>` or `,`
+        let mut predicate: Vec<TokenTree> = Vec::new();
+        let mut arrow: bool = false;
+        for tt in iter.by_ref() {
+            match &tt {
+                TokenTree::Punct(p) if p.as_char() == ',' => break,
+                TokenTree::Punct(p)
+                    if p.as_char() == '>' && matches!(predicate.last(), Some(TokenTree::Punct(p)) if p.as_char() == '=' && p.spacing() == Spacing::Joint) =>
+                {
+                    predicate.pop();
+                    arrow = true;
+                    break;
+                },
+                _ => predicate.push(tt),
+            }
+        }
+        let span: Span = predicate.first().map(TokenTree::span).unwrap_or_else(Span::call_site);
+        if predicate.is_empty() {
+            return Err(error2(span, "Expected a `cfg`-predicate"));
+        }
+        if !arrow {
+            return Ok(Self { predicate: predicate.into_iter().collect(), values: Vec::new() });
+        }
+
+        // Parse the values
+        let group: Group = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+            Some(tt) => return Err(error2(tt.span(), "Expected substitutions in curly brackets")),
+            None => return Err(error2(span, "Expected substitutions in curly brackets")),
+        };
+        let mut values: Vec<(Ident, TokenStream)> = Vec::new();
+        for value in parse_values(group.stream())? {
+            let mut value = value.into_iter();
+            let name: Ident = match value.next() {
+                Some(TokenTree::Ident(ident)) => ident,
+                Some(tt) => return Err(error2(tt.span(), "Expected the name of a placeholder")),
+                None => return Err(error2(group.span(), "Expected the name of a placeholder")),
+            };
+            if values.iter().any(|(other, _)| *other == name) {
+                return Err(error2(name.span(), &format!("Placeholder `{name}` is given more than once")));
+            }
+            match value.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '=' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+                None => return Err(error2(name.span(), "Expected '='")),
+            }
+
+            // Unwrap the value if it's in curly brackets
+            let value: Vec<TokenTree> = value.collect();
+            values.push((
+                name.clone(),
+                match value.as_slice() {
+                    [] => return Err(error2(name.span(), "Expected a value after '='")),
+                    [TokenTree::Group(group)] if group.delimiter() == Delimiter::Brace => group.stream(),
+                    _ => value.into_iter().collect(),
+                },
+            ));
+        }
+
+        // Parse the separating comma
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+            None => {},
+        }
+        Ok(Self { predicate: predicate.into_iter().collect(), values })
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`cfg_matrix()`](super::cfg_matrix())-macro.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the `cfg`-predicates and their substitutions.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] with the item repeated once for every predicate, with a `#[cfg]`
+/// attribute and its placeholders replaced by that predicate's values.
+///
+/// # Errors
+/// This function may error if the matrix is invalid.
+pub fn cfg_matrix(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut rows: Vec<Row> = Vec::new();
+    let mut iter = attr.into_iter().peekable();
+    while iter.peek().is_some() {
+        match Row::parse(&mut iter) {
+            Ok(row) => rows.push(row),
+            // NOTE: The error ends up in item position, so needs a semicolon to be a valid item
+            Err(err) => return Err(item_error(err)),
+        }
+    }
+    if rows.is_empty() {
+        return Err(item_error(error2(Span::call_site(), "Expected at least one `cfg`-predicate (e.g., `#[cfg_matrix(feature = \"std\" => { ... })]`)")));
+    }
+
+    // Check that every row substitutes the same placeholders
+    let mut names: Vec<String> = rows[0].values.iter().map(|(name, _)| name.to_string()).collect();
+    names.sort();
+    for row in &rows[1..] {
+        let mut row_names: Vec<String> = row.values.iter().map(|(name, _)| name.to_string()).collect();
+        row_names.sort();
+        if row_names != names {
+            let span: Span = row.predicate.clone().into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+            return Err(item_error(error2(span, &format!("Expected the same placeholders as the first predicate ({})", names.join(", ")))));
+        }
+    }
+
+    // Generate a copy for every row
+    let mut res = TokenStream::new();
+    for Row { predicate, values } in &rows {
+        let values: HashMap<String, &TokenStream> = values.iter().map(|(name, value)| (name.to_string(), value)).collect();
+        res.extend([
+            TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+            TokenTree::Group(Group::new(
+                Delimiter::Bracket,
+                TokenStream::from_iter([
+                    TokenTree::Ident(Ident::new("cfg", Span::call_site())),
+                    TokenTree::Group(Group::new(Delimiter::Parenthesis, predicate.clone())),
+                ]),
+            )),
+        ]);
+        res.extend(substitute(item.clone(), &values));
+    }
+    Ok(res)
+}
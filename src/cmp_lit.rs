@@ -0,0 +1,173 @@
+//  CMP LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for choosing between branches based on how two literals compare.
+//
+
+use std::cmp::Ordering;
+
+use proc_macro2::{Delimiter, Group, Ident, Spacing, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{Number, SignedLit, parse_signed_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The left-hand side literal.
+    lhs:  SignedLit,
+    /// The right-hand side literal.
+    rhs:  SignedLit,
+    /// The arms to choose from, as a list of the orderings they match and their bodies.
+    arms: Vec<(Vec<Ordering>, Group)>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the literals
+        let lhs: SignedLit = parse_signed_lit(&mut iter)?;
+        Self::parse_comma(&mut iter, lhs.lit.span())?;
+        let rhs: SignedLit = parse_signed_lit(&mut iter)?;
+        Self::parse_comma(&mut iter, rhs.lit.span())?;
+
+        // Parse the arms
+        let mut arms: Vec<(Vec<Ordering>, Group)> = Vec::new();
+        while iter.peek().is_some() {
+            // Parse the patterns
+            let mut ords: Vec<Ordering> = Vec::new();
+            loop {
+                let ident: Ident = match iter.next() {
+                    Some(TokenTree::Ident(ident)) => ident,
+                    Some(tt) => return Err(error2(tt.span(), "Expected either `lt`, `le`, `eq`, `ne`, `ge`, `gt` or `_`")),
+                    None => return Err(error2(Span::mixed_site(), "Expected either `lt`, `le`, `eq`, `ne`, `ge`, `gt` or `_`")),
+                };
+                ords.extend(match ident.to_string().as_str() {
+                    "lt" => &[Ordering::Less][..],
+                    "le" => &[Ordering::Less, Ordering::Equal],
+                    "eq" => &[Ordering::Equal],
+                    "ne" => &[Ordering::Less, Ordering::Greater],
+                    "ge" => &[Ordering::Equal, Ordering::Greater],
+                    "gt" => &[Ordering::Greater],
+                    "_" => &[Ordering::Less, Ordering::Equal, Ordering::Greater],
+                    _ => return Err(error2(ident.span(), "Expected either `lt`, `le`, `eq`, `ne`, `ge`, `gt` or `_`")),
+                });
+                match iter.next() {
+                    Some(TokenTree::Punct(p)) if p.as_char() == '|' => continue,
+                    Some(TokenTree::Punct(p)) if p.as_char() == '=' && p.spacing() == Spacing::Joint => {},
+                    Some(tt) => return Err(error2(tt.span(), "Expected either '|' or '=>'")),
+                    None => return Err(error2(ident.span(), "Expected either '|' or '=>'")),
+                }
+                match iter.next() {
+                    Some(TokenTree::Punct(p)) if p.as_char() == '>' => break,
+                    Some(tt) => return Err(error2(tt.span(), "Expected '=>'")),
+                    None => return Err(error2(ident.span(), "Expected '=>'")),
+                }
+            }
+
+            // Parse the body
+            match iter.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => arms.push((ords, group)),
+                Some(tt) => return Err(error2(tt.span(), "Expected a body in curly brackets")),
+                None => return Err(error2(Span::mixed_site(), "Expected a body in curly brackets")),
+            }
+            if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+                iter.next();
+            }
+        }
+        Ok(Self { lhs, rhs, arms })
+    }
+
+    /// Parses a comma separating two arguments.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    /// - `span`: A [`Span`] to report errors at if there is no token.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input was not a comma.
+    fn parse_comma(iter: &mut impl Iterator<Item = TokenTree>, span: Span) -> Result<(), Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => Ok(()),
+            Some(tt) => Err(error2(tt.span(), "Expected ','")),
+            None => Err(error2(span, "Expected ','")),
+        }
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Compares two literals.
+///
+/// # Arguments
+/// - `lhs`: The left-hand side [`SignedLit`].
+/// - `rhs`: The right-hand side [`SignedLit`].
+///
+/// # Returns
+/// The [`Ordering`] of `lhs` with respect to `rhs`.
+///
+/// # Errors
+/// This function errors if the literals cannot be compared.
+pub fn compare(lhs: &SignedLit, rhs: &SignedLit) -> Result<Ordering, Diagnostic2> {
+    let span: Span = rhs.lit.span();
+    if lhs.neg && !matches!(lhs.lit, Lit::Int(_) | Lit::Float(_)) {
+        return Err(error2(lhs.lit.span(), "Only numeric literals can be negated"));
+    }
+    if rhs.neg && !matches!(rhs.lit, Lit::Int(_) | Lit::Float(_)) {
+        return Err(error2(span, "Only numeric literals can be negated"));
+    }
+    match (&lhs.lit, &rhs.lit) {
+        (Lit::Int(_) | Lit::Float(_), Lit::Int(_) | Lit::Float(_)) => {
+            let (l, _) = Number::from_lit(lhs)?;
+            let (r, _) = Number::from_lit(rhs)?;
+            l.compare(&r).ok_or_else(|| error2(span, "Cannot compare integer and floating-point literals"))
+        },
+        (Lit::Str(l), Lit::Str(r)) => Ok(l.value().cmp(&r.value())),
+        (Lit::ByteStr(l), Lit::ByteStr(r)) => Ok(l.value().cmp(&r.value())),
+        (Lit::CStr(l), Lit::CStr(r)) => Ok(l.value().cmp(&r.value())),
+        (Lit::Byte(l), Lit::Byte(r)) => Ok(l.value().cmp(&r.value())),
+        (Lit::Char(l), Lit::Char(r)) => Ok(l.value().cmp(&r.value())),
+        (Lit::Bool(l), Lit::Bool(r)) => Ok(l.value.cmp(&r.value)),
+        _ => Err(error2(span, "Cannot compare literals of different kinds")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`cmp_lit()`](super::cmp_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literals to compare and the arms to choose
+///   from.
+///
+/// # Returns
+/// A new [`TokenStream`] with the body of the first arm matching the comparison, or nothing if
+/// no arm matched.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the literals cannot be
+/// compared.
+pub fn cmp_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { lhs, rhs, arms } = Input::parse(input)?;
+    let ord: Ordering = compare(&lhs, &rhs)?;
+    Ok(arms.into_iter().find(|(ords, _)| ords.contains(&ord)).map(|(_, body)| body.stream()).unwrap_or_default())
+}
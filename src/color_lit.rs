@@ -0,0 +1,202 @@
+//  COLOR LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for parsing hexadecimal colors at compile time.
+//
+
+use std::iter::Peekable;
+use std::str::FromStr as _;
+
+use proc_macro2::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{parse_lit, subspan};
+use crate::utils::Callback;
+
+
+/***** TOKEN PARSING *****/
+/// Defines what to generate for the color.
+enum Repr {
+    /// A tuple of `u8` components, in RGBA-order.
+    Tuple,
+    /// A packed `u32` in `0xRRGGBB`-order.
+    Rgb,
+    /// A packed `u32` in `0xRRGGBBAA`-order.
+    Rgba,
+    /// A packed `u32` in `0xAARRGGBB`-order.
+    Argb,
+}
+
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The representation to generate.
+    repr:     Repr,
+    /// The literal to parse.
+    lit:      LitStr,
+    /// The callback to pass the components to, if any.
+    callback: Option<Callback>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter: Peekable<_> = input.into_iter().peekable();
+
+        // Parse the optional representation first
+        let mut repr = Repr::Tuple;
+        let mut repr_span: Option<Span> = None;
+        if let Some(TokenTree::Ident(ident)) = iter.peek() {
+            repr = match ident.to_string().as_str() {
+                "tuple" => Repr::Tuple,
+                "rgb" => Repr::Rgb,
+                "rgba" => Repr::Rgba,
+                "argb" => Repr::Argb,
+                _ => return Err(error2(ident.span(), "Expected either `tuple`, `rgb`, `rgba`, `argb` or a string literal")),
+            };
+            repr_span = Some(ident.span());
+            iter.next();
+        }
+
+        // Then the literal
+        let lit: LitStr = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected a string literal")),
+        };
+
+        // Finally, the optional callback
+        let callback: Option<Callback> = Callback::parse_opt(&mut iter)?;
+        if let (Some(span), Some(_)) = (repr_span, &callback) {
+            return Err(error2(span, "Cannot give a representation when using a callback"));
+        }
+        Ok(Self { repr, lit, callback })
+    }
+}
+
+
+
+/// Parses a color from a string literal.
+///
+/// Supports the `#RGB`, `#RGBA`, `#RRGGBB` and `#RRGGBBAA` formats.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] to parse.
+///
+/// # Returns
+/// The color as a tuple of its red, green, blue and optional alpha components.
+///
+/// # Errors
+/// This function errors if the literal does not contain a valid color.
+pub fn parse_color(lit: &LitStr) -> Result<([u8; 3], Option<u8>), Diagnostic2> {
+    let value: String = lit.value();
+    let Some(hex) = value.strip_prefix('#') else {
+        return Err(error2(subspan(lit, 0..value.chars().next().map(char::len_utf8).unwrap_or(0)), "Expected a color starting with '#'"));
+    };
+
+    // Parse the digits
+    let mut digits: Vec<u8> = Vec::with_capacity(8);
+    for (i, c) in hex.char_indices() {
+        match c.to_digit(16) {
+            Some(digit) => digits.push(digit as u8),
+            None => {
+                return Err(error2(
+                    subspan(lit, 1 + i..1 + i + c.len_utf8()),
+                    &format!("Invalid character {c:?} at position {} in color (expected a hexadecimal digit)", 1 + i),
+                ));
+            },
+        }
+    }
+
+    // Interpret them based on their count
+    let components: Vec<u8> = match digits.len() {
+        // Short forms repeat every digit
+        3 | 4 => digits.into_iter().map(|d| (d << 4) | d).collect(),
+        6 | 8 => digits.chunks(2).map(|c| (c[0] << 4) | c[1]).collect(),
+        n => return Err(error2(lit.span(), &format!("Expected a color with 3, 4, 6 or 8 hexadecimal digits, got {n}"))),
+    };
+    Ok(([components[0], components[1], components[2]], components.get(3).copied()))
+}
+
+/// Generates a `u8` literal.
+///
+/// # Arguments
+/// - `value`: The value of the literal.
+/// - `span`: The [`Span`] to give the literal.
+///
+/// # Returns
+/// A [`TokenTree`] encoding the literal.
+fn u8_lit(value: u8, span: Span) -> TokenTree {
+    let mut lit = Literal::from_str(&format!("0x{value:02X}u8")).unwrap();
+    lit.set_span(span);
+    TokenTree::Literal(lit)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`color_lit()`](super::color_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to parse.
+///
+/// # Returns
+/// A new [`TokenStream`] with either a tuple of components, a packed `u32` literal, or a call to
+/// the given callback with the components.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the literal does not
+/// contain a valid color.
+pub fn color_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { repr, lit, callback } = Input::parse(input)?;
+    let span: Span = lit.span();
+    let ([r, g, b], a) = parse_color(&lit)?;
+
+    // Generate the packed forms
+    let packed: u32 = match repr {
+        Repr::Tuple => 0,
+        Repr::Rgb => {
+            if a.is_some() {
+                return Err(error2(span, "Cannot pack a color with an alpha component as `rgb`; use `rgba` or `argb` instead"));
+            }
+            u32::from_be_bytes([0, r, g, b])
+        },
+        Repr::Rgba => u32::from_be_bytes([r, g, b, a.unwrap_or(0xFF)]),
+        Repr::Argb => u32::from_be_bytes([a.unwrap_or(0xFF), r, g, b]),
+    };
+    if !matches!(repr, Repr::Tuple) && callback.is_none() {
+        let mut lit = Literal::from_str(&format!("0x{packed:08X}u32")).unwrap();
+        lit.set_span(span);
+        return Ok(TokenStream::from(TokenTree::Literal(lit)));
+    }
+
+    // Otherwise, generate the components
+    let mut components = TokenStream::new();
+    for (i, value) in [r, g, b, a.unwrap_or(0xFF)].into_iter().enumerate() {
+        if i > 0 {
+            let mut comma = Punct::new(',', Spacing::Alone);
+            comma.set_span(span);
+            components.extend([TokenTree::Punct(comma)]);
+        }
+        components.extend([u8_lit(value, span)]);
+    }
+    match callback {
+        Some(callback) => Ok(callback.call(components)),
+        None => {
+            let mut group = Group::new(Delimiter::Parenthesis, components);
+            group.set_span(span);
+            Ok(TokenStream::from(TokenTree::Group(group)))
+        },
+    }
+}
@@ -0,0 +1,101 @@
+//  COMPILE NOTE.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for emitting diagnostics with notes and help messages pointing to multiple
+//!   spans.
+//
+
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, Level, error2, parse_anchor, parse_message};
+use crate::matching::split_punct;
+
+
+/***** TOKEN PARSING *****/
+/// Parses a single entry of the diagnostic.
+///
+/// Entries have the syntax `$level:ident $(($anchor:tt))? : $message:literal`.
+///
+/// # Arguments
+/// - `tokens`: The tokens of the entry, without the separating semicolon.
+/// - `child`: Whether the entry is a child (i.e., not the first entry).
+///
+/// # Returns
+/// The level, span (if any) and message of the entry.
+///
+/// # Errors
+/// This function errors if the tokens are not an entry.
+fn parse_entry(tokens: Vec<TokenTree>, child: bool) -> Result<(Level, Option<Span>, String), Diagnostic2> {
+    let mut iter = tokens.into_iter().peekable();
+
+    // Parse the level
+    let level: Ident = match iter.next() {
+        Some(TokenTree::Ident(level)) => level,
+        Some(tt) => return Err(error2(tt.span(), "Expected a level (e.g., `error` or `note`)")),
+        None => return Err(error2(Span::call_site(), "Expected a level (e.g., `error` or `note`), not an empty entry")),
+    };
+    let parsed: Level = match (level.to_string().as_str(), child) {
+        ("error", false) => Level::Error,
+        ("warning", false) => Level::Warning,
+        ("note", _) => Level::Note,
+        ("help", _) => Level::Help,
+        (_, false) => return Err(error2(level.span(), &format!("Unknown level `{level}`; expected `error`, `warning`, `note` or `help`"))),
+        (_, true) => return Err(error2(level.span(), &format!("Unknown level `{level}`; expected `note` or `help` for anything but the first entry"))),
+    };
+
+    // Parse the optional anchor
+    let span: Option<Span> = match iter.next_if(|tt| matches!(tt, TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis)) {
+        Some(TokenTree::Group(group)) => {
+            let mut tokens = group.stream().into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(tt), None) => Some(parse_anchor(tt)),
+                (Some(_), Some(tt)) => return Err(error2(tt.span(), "Expected a single token to point to")),
+                (None, _) => return Err(error2(group.span(), "Expected a token to point to")),
+            }
+        },
+        _ => None,
+    };
+
+    // Parse the message
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ':' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ':' and a message")),
+        None => return Err(error2(level.span(), "Expected ':' and a message")),
+    }
+    let message: String = parse_message(iter.next(), level.span())?;
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected ';' or nothing after the message"));
+    }
+    Ok((parsed, span, message))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`compile_note()`](super::compile_note())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the entries of the diagnostic.
+///
+/// # Returns
+/// A new [`TokenStream`] that produces the diagnostic when compiled.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn compile_note(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Split the entries on semicolons, then parse them into one diagnostic
+    let mut entries = split_punct(input, ';').into_iter();
+    let Some(first) = entries.next() else {
+        return Err(error2(Span::call_site(), "Expected at least one entry (e.g., `error: \"Something went wrong\";`)"));
+    };
+    let (level, span, message): (Level, Option<Span>, String) = parse_entry(first, false)?;
+    let mut diag = Diagnostic2::new(level, span.unwrap_or_else(Span::call_site), message);
+    for entry in entries {
+        let (level, span, message): (Level, Option<Span>, String) = parse_entry(entry, true)?;
+        diag = diag.child(level, span, message);
+    }
+    Ok(diag.emit())
+}
@@ -0,0 +1,50 @@
+//  COMPILE WARNING.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for emitting warnings at compile time.
+//
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, parse_anchor, parse_message};
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`compile_warning()`](super::compile_warning())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the optional anchor and the message.
+///
+/// # Returns
+/// A new [`TokenStream`] that produces the warning when compiled.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn compile_warning(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    if matches!(tokens.last(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+        tokens.pop();
+    }
+
+    // Parse the anchor, if any, and the message
+    let (span, message): (Span, Option<TokenTree>) = match tokens.len() {
+        0 | 1 => (Span::call_site(), tokens.pop()),
+        _ => {
+            let mut iter = tokens.into_iter();
+            let span: Span = iter.next().map(parse_anchor).unwrap_or_else(Span::call_site);
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+                None => unreachable!(),
+            }
+            let message: Option<TokenTree> = iter.next();
+            if let Some(tt) = iter.next() {
+                return Err(error2(tt.span(), "Expected nothing after the message"));
+            }
+            (span, message)
+        },
+    };
+    let message: String = parse_message(message, Span::call_site())?;
+    Ok(Diagnostic2::warning(span, message).emit())
+}
@@ -0,0 +1,324 @@
+//  CONST ASSERT LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for asserting conditions over literals at compile time.
+//
+
+use std::cmp::Ordering;
+
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::calc::{Evaluator, Type, Value, find_type};
+use crate::cmp_lit::compare;
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::lits::{SignedLit, parse_lit, parse_signed_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Defines the comparison operators that are supported.
+#[derive(Clone, Copy)]
+enum CmpOp {
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+}
+impl CmpOp {
+    /// Checks whether an ordering satisfies this operator.
+    ///
+    /// # Arguments
+    /// - `ord`: The [`Ordering`] of the left-hand side with respect to the right-hand side.
+    ///
+    /// # Returns
+    /// True if it does, false otherwise.
+    fn matches(&self, ord: Ordering) -> bool {
+        match self {
+            Self::Eq => ord.is_eq(),
+            Self::Ne => ord.is_ne(),
+            Self::Lt => ord.is_lt(),
+            Self::Le => ord.is_le(),
+            Self::Gt => ord.is_gt(),
+            Self::Ge => ord.is_ge(),
+        }
+    }
+}
+
+
+
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the tokens making up the condition and the custom message, if any.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(Vec<TokenTree>, Option<LitStr>), Diagnostic2> {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+
+    // The message is the last top-level literal after a comma, if any, optionally followed by
+    // another comma
+    if matches!(tokens.last(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+        tokens.pop();
+    }
+    let mut msg: Option<LitStr> = None;
+    if let Some(i) = tokens.iter().rposition(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ',')) {
+        if i + 2 != tokens.len() {
+            return Err(error2(tokens[i].span(), "Expected either nothing or a single string literal after ','"));
+        }
+        match parse_lit(tokens[i + 1].clone())? {
+            Lit::Str(lit) => msg = Some(lit),
+            lit => return Err(error2(lit.span(), "Expected a string literal with the message to show")),
+        }
+        tokens.truncate(i);
+    }
+    if tokens.is_empty() {
+        return Err(error2(Span::call_site(), "Expected a condition"));
+    }
+    Ok((tokens, msg))
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Splits a list of tokens on a top-level, two-character operator (e.g., `&&`).
+///
+/// # Arguments
+/// - `tokens`: The tokens to split.
+/// - `op`: The character that is repeated to make up the operator.
+///
+/// # Returns
+/// The parts between the operators. If there are none, this is just `tokens`.
+fn split(tokens: &[TokenTree], op: char) -> Vec<&[TokenTree]> {
+    let mut parts: Vec<&[TokenTree]> = Vec::new();
+    let (mut start, mut i): (usize, usize) = (0, 0);
+    while i < tokens.len() {
+        if let (TokenTree::Punct(p), Some(TokenTree::Punct(next))) = (&tokens[i], tokens.get(i + 1))
+            && p.as_char() == op
+            && p.spacing() == Spacing::Joint
+            && next.as_char() == op
+        {
+            parts.push(&tokens[start..i]);
+            start = i + 2;
+            i += 2;
+            continue;
+        }
+        i += 1;
+    }
+    parts.push(&tokens[start..]);
+    parts
+}
+
+/// Finds the top-level comparison operator in a list of tokens.
+///
+/// # Arguments
+/// - `tokens`: The tokens to search.
+///
+/// # Returns
+/// A tuple of the index of the operator, its length in tokens and the operator itself, or
+/// [`None`] if there is none.
+///
+/// # Errors
+/// This function errors if there are multiple comparison operators, as Rust doesn't allow
+/// chaining them either.
+fn find_cmp(tokens: &[TokenTree]) -> Result<Option<(usize, usize, CmpOp)>, Diagnostic2> {
+    let mut found: Option<(usize, usize, CmpOp)> = None;
+    let mut i: usize = 0;
+    while i < tokens.len() {
+        let TokenTree::Punct(p) = &tokens[i] else {
+            i += 1;
+            continue;
+        };
+        let next: Option<char> = match tokens.get(i + 1) {
+            Some(TokenTree::Punct(next)) if p.spacing() == Spacing::Joint => Some(next.as_char()),
+            _ => None,
+        };
+        let (op, len): (CmpOp, usize) = match (p.as_char(), next) {
+            // Shifts are no comparisons
+            ('<', Some('<')) | ('>', Some('>')) => {
+                i += 2;
+                continue;
+            },
+            ('=', Some('=')) => (CmpOp::Eq, 2),
+            ('!', Some('=')) => (CmpOp::Ne, 2),
+            ('<', Some('=')) => (CmpOp::Le, 2),
+            ('>', Some('=')) => (CmpOp::Ge, 2),
+            ('<', _) => (CmpOp::Lt, 1),
+            ('>', _) => (CmpOp::Gt, 1),
+            _ => {
+                i += 1;
+                continue;
+            },
+        };
+        if found.is_some() {
+            return Err(error2(p.span(), "Comparison operators cannot be chained; use parenthesis and `&&` instead"));
+        }
+        found = Some((i, len, op));
+        i += len;
+    }
+    Ok(found)
+}
+
+
+
+/// Checks whether some tokens are a single, non-numeric literal.
+///
+/// # Arguments
+/// - `tokens`: The tokens to check.
+///
+/// # Returns
+/// True if they are, false otherwise.
+fn is_other_lit(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [tt] => parse_lit(tt.clone()).is_ok_and(|lit| !matches!(lit, Lit::Int(_) | Lit::Float(_))),
+        _ => false,
+    }
+}
+
+/// Evaluates a comparison.
+///
+/// # Arguments
+/// - `lhs`: The tokens making up the left-hand side.
+/// - `op`: The [`CmpOp`] to apply.
+/// - `rhs`: The tokens making up the right-hand side.
+/// - `span`: The [`Span`] of the operator, for error reporting.
+///
+/// # Returns
+/// Whether the comparison holds.
+///
+/// # Errors
+/// This function errors if either side was invalid, or if they cannot be compared.
+fn comparison(lhs: &[TokenTree], op: CmpOp, rhs: &[TokenTree], span: Span) -> Result<bool, Diagnostic2> {
+    // Non-numeric literals are compared like `cmp_lit!()` does
+    if is_other_lit(lhs) || is_other_lit(rhs) {
+        let (mut liter, mut riter) = (lhs.iter().cloned(), rhs.iter().cloned());
+        let (l, r): (SignedLit, SignedLit) = (parse_signed_lit(&mut liter)?, parse_signed_lit(&mut riter)?);
+        if let Some(tt) = liter.next().or_else(|| riter.next()) {
+            return Err(error2(tt.span(), "Only a single literal can be compared with a non-numeric literal"));
+        }
+        return Ok(op.matches(compare(&l, &r)?));
+    }
+
+    // Otherwise, the sides are numeric expressions that are evaluated like `calc!()` does
+    let (lhs, rhs): (TokenStream, TokenStream) = (lhs.iter().cloned().collect(), rhs.iter().cloned().collect());
+    let mut ty: Option<(Type, Span)> = None;
+    find_type(lhs.clone(), &mut ty)?;
+    find_type(rhs.clone(), &mut ty)?;
+    let ty: Option<Type> = ty.map(|(ty, _)| ty);
+    let (l, r): (Value, Value) = (Evaluator::new(lhs, ty).evaluate(span)?, Evaluator::new(rhs, ty).evaluate(span)?);
+    let ord: Ordering = match (l, r) {
+        (Value::Int(l), Value::Int(r)) => l.cmp(&r),
+        // NOTE: Values are always finite, so never NaN
+        (Value::Float(l), Value::Float(r)) => l.total_cmp(&r),
+        _ => return Err(error2(span, "Cannot compare integers and floating-point numbers")),
+    };
+    Ok(op.matches(ord))
+}
+
+/// Evaluates a condition.
+///
+/// # Arguments
+/// - `tokens`: The tokens making up the condition.
+/// - `span`: A [`Span`] to report errors at if the condition is empty.
+///
+/// # Returns
+/// A tuple of whether the condition holds and the [`Span`] of the part that decided that, for
+/// error reporting.
+///
+/// # Errors
+/// This function errors if the condition was invalid or if evaluating it failed.
+fn condition(tokens: &[TokenTree], span: Span) -> Result<(bool, Span), Diagnostic2> {
+    let Some(first) = tokens.first() else {
+        return Err(error2(span, "Expected a condition"));
+    };
+
+    // Logical operators go first, as they have the lowest precedence. Note that they
+    // short-circuit like Rust's.
+    for (op, short) in [('|', true), ('&', false)] {
+        let parts: Vec<&[TokenTree]> = split(tokens, op);
+        if parts.len() > 1 {
+            let mut res: Option<(bool, Span)> = None;
+            for part in parts {
+                let (value, span): (bool, Span) = condition(part, span)?;
+                if value == short {
+                    return Ok((value, span));
+                }
+                res = res.or(Some((value, span)));
+            }
+            return Ok(res.unwrap());
+        }
+    }
+
+    // Then comparisons
+    if let Some((i, len, op)) = find_cmp(tokens)? {
+        let (lhs, rhs): (&[TokenTree], &[TokenTree]) = (&tokens[..i], &tokens[i + len..]);
+        if lhs.is_empty() {
+            return Err(error2(tokens[i].span(), "Expected an expression before the comparison"));
+        }
+        return Ok((comparison(lhs, op, rhs, tokens[i].span())?, first.span()));
+    }
+
+    // Finally, atoms
+    match tokens {
+        [TokenTree::Punct(p), rest @ ..] if p.as_char() == '!' => {
+            if !matches!(rest, [_] | [TokenTree::Punct(_), ..]) {
+                return Err(error2(p.span(), "Only `true`, `false` or conditions in parenthesis can be negated"));
+            }
+            let (value, span): (bool, Span) = condition(rest, p.span())?;
+            Ok((!value, span))
+        },
+        [TokenTree::Ident(ident)] if ident == "true" => Ok((true, ident.span())),
+        [TokenTree::Ident(ident)] if ident == "false" => Ok((false, ident.span())),
+        [TokenTree::Group(group)] if matches!(group.delimiter(), Delimiter::Parenthesis | Delimiter::None) => {
+            condition(&group.stream().into_iter().collect::<Vec<TokenTree>>(), group.span())
+        },
+        _ => Err(error2(first.span(), "Expected a comparison, `true`, `false`, `!` or a condition in parenthesis")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`const_assert_lit()`](super::const_assert_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the condition to assert and an optional message.
+///
+/// # Returns
+/// An empty [`TokenStream`] if the condition holds.
+///
+/// # Errors
+/// This function errors with the given message if the condition does not hold. It also errors if
+/// the input is not valid for this macro, or if evaluating the condition failed.
+pub fn const_assert_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // The macro is used in item or statement position, where the `compile_error!()` needs a
+    // semicolon to not trigger another error
+    let assert = || -> Result<TokenStream, Diagnostic2> {
+        let (tokens, msg): (Vec<TokenTree>, Option<LitStr>) = parse_input(input)?;
+        let (value, span): (bool, Span) = condition(&tokens, Span::call_site())?;
+        if value {
+            return Ok(TokenStream::new());
+        }
+        match msg {
+            Some(msg) => Err(error2(span, &msg.value())),
+            None => Err(error2(span, &format!("Assertion failed: `{}`", tokens.into_iter().collect::<TokenStream>()))),
+        }
+    };
+    assert().map_err(item_error)
+}
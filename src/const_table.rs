@@ -0,0 +1,338 @@
+//  CONST TABLE.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for generating a table of values with constants for its indices.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::items::{parse_attrs, parse_vis};
+use crate::matching::split_punct;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether the given tokens are a literal that can be used as a pattern.
+///
+/// # Arguments
+/// - `tokens`: The tokens to check.
+///
+/// # Returns
+/// True if the tokens are a (possibly negated) literal or a boolean, or false otherwise.
+fn is_lit(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [TokenTree::Literal(_)] => true,
+        [TokenTree::Punct(p), TokenTree::Literal(_)] => p.as_char() == '-',
+        [TokenTree::Ident(ident)] => ident == "true" || ident == "false",
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::None => is_lit(&group.stream().into_iter().collect::<Vec<_>>()),
+        _ => false,
+    }
+}
+
+/// Generates a path into `core` (e.g., `::core::option::Option`).
+///
+/// # Arguments
+/// - `segments`: The segments of the path after `core`.
+/// - `span`: The span of the path.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the path.
+fn core_path(segments: &[&str], span: Span) -> TokenStream {
+    let mut res = TokenStream::new();
+    for segment in ["core"].iter().chain(segments) {
+        res.extend([TokenTree::Punct(Punct::new(':', Spacing::Joint)), TokenTree::Punct(Punct::new(':', Spacing::Alone))]);
+        res.extend([TokenTree::Ident(Ident::new(segment, span))]);
+    }
+    res
+}
+
+/// Generates a statement-like item (e.g., `const A: usize = 0;`).
+///
+/// # Arguments
+/// - `attrs`: Any attributes to put on the item.
+/// - `vis`: The visibility of the item.
+/// - `keyword`: The keyword of the item.
+/// - `name`: The name of the item.
+/// - `ty`: The type of the item.
+/// - `value`: The value of the item.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the item.
+fn item(attrs: TokenStream, vis: &TokenStream, keyword: &Ident, name: Ident, ty: TokenStream, value: TokenStream) -> TokenStream {
+    let mut res: TokenStream = attrs;
+    res.extend(vis.clone());
+    res.extend([TokenTree::Ident(keyword.clone()), TokenTree::Ident(name), TokenTree::Punct(Punct::new(':', Spacing::Alone))]);
+    res.extend(ty);
+    res.extend([TokenTree::Punct(Punct::new('=', Spacing::Alone))]);
+    res.extend(value);
+    res.extend([TokenTree::Punct(Punct::new(';', Spacing::Alone))]);
+    res
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single entry in the table.
+struct Entry {
+    /// Any attributes to put on the index constant.
+    attrs: TokenStream,
+    /// The name of the index constant.
+    name:  Ident,
+    /// The value in the table.
+    value: Vec<TokenTree>,
+}
+impl Entry {
+    /// Parses an Entry from its tokens.
+    ///
+    /// Entries have the syntax `$(#[$attr:meta])* $name:ident => $value:expr`.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the entry.
+    /// - `span`: A span to report errors at if the entry is incomplete.
+    ///
+    /// # Returns
+    /// The parsed Entry.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not an entry.
+    fn parse(tokens: Vec<TokenTree>, span: Span) -> Result<Self, Diagnostic2> {
+        let mut iter = tokens.into_iter().peekable();
+        let attrs: TokenStream = parse_attrs(&mut iter)?;
+        let name: Ident = match iter.next() {
+            Some(TokenTree::Ident(name)) => name,
+            Some(tt) => return Err(error2(tt.span(), "Expected the name of an entry")),
+            None => return Err(error2(span, "Expected an entry, not an empty one")),
+        };
+        match (iter.next(), iter.next()) {
+            (Some(TokenTree::Punct(eq)), Some(TokenTree::Punct(gt))) if eq.as_char() == '=' && eq.spacing() == Spacing::Joint && gt.as_char() == '>' => {},
+            (Some(tt), _) => return Err(error2(tt.span(), "Expected '=>'")),
+            (None, _) => return Err(error2(name.span(), "Expected '=>'")),
+        }
+        let value: Vec<TokenTree> = iter.collect();
+        if value.is_empty() {
+            return Err(error2(name.span(), "Expected a value after '=>'"));
+        }
+        Ok(Self { attrs, name, value })
+    }
+}
+
+
+
+/// Defines the table to generate.
+struct Table {
+    /// Any attributes to put on the table.
+    attrs:   TokenStream,
+    /// The lookup function to generate, if any, as its visibility and name.
+    lookup:  Option<(TokenStream, Ident)>,
+    /// The visibility of the table and the index constants.
+    vis:     TokenStream,
+    /// The `static` or `const` keyword.
+    keyword: Ident,
+    /// The name of the table.
+    name:    Ident,
+    /// The type of the elements in the table.
+    elem:    TokenStream,
+    /// The entries in the table.
+    entries: Vec<Entry>,
+}
+impl Table {
+    /// Parses a Table from its tokens.
+    ///
+    /// Tables have the syntax
+    /// `$(#[$attr:meta])* $vis:vis $(static|const) $name:ident : [$elem:ty] = [$($entry),* $(,)?] $(;)?`.
+    ///
+    /// # Arguments
+    /// - `input`: The tokens of the table.
+    ///
+    /// # Returns
+    /// The parsed Table.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a table.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the attributes, taking out `#[lookup(...)]`
+        let mut attrs = TokenStream::new();
+        let mut lookup: Option<(TokenStream, Ident)> = None;
+        let mut all = parse_attrs(&mut iter)?.into_iter();
+        while let (Some(pound), Some(TokenTree::Group(group))) = (all.next(), all.next()) {
+            let mut inner = group.stream().into_iter().peekable();
+            match (inner.next(), inner.next()) {
+                (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(args))) if ident == "lookup" && args.delimiter() == Delimiter::Parenthesis => {
+                    let mut args = args.stream().into_iter().peekable();
+                    let vis: TokenStream = parse_vis(&mut args);
+                    let name: Ident = match (args.next(), args.next()) {
+                        (Some(TokenTree::Ident(kw)), Some(TokenTree::Ident(name))) if kw == "fn" => name,
+                        (Some(tt), _) => return Err(error2(tt.span(), "Expected `fn` and the name of the lookup function")),
+                        (None, _) => return Err(error2(ident.span(), "Expected `fn` and the name of the lookup function")),
+                    };
+                    if let Some(tt) = args.next() {
+                        return Err(error2(tt.span(), "Expected nothing after the name of the lookup function"));
+                    }
+                    if lookup.replace((vis, name)).is_some() {
+                        return Err(error2(ident.span(), "Duplicate `#[lookup(...)]`-attribute"));
+                    }
+                },
+                _ => attrs.extend([pound, TokenTree::Group(group)]),
+            }
+        }
+        let vis: TokenStream = parse_vis(&mut iter);
+
+        // Parse the signature
+        let keyword: Ident = match iter.next() {
+            Some(TokenTree::Ident(kw)) if kw == "static" || kw == "const" => kw,
+            Some(tt) => return Err(error2(tt.span(), "Expected `static` or `const`")),
+            None => return Err(error2(Span::call_site(), "Expected a table (e.g., `static NAMES: [&str] = [...];`)")),
+        };
+        let name: Ident = match iter.next() {
+            Some(TokenTree::Ident(name)) => name,
+            Some(tt) => return Err(error2(tt.span(), "Expected the name of the table")),
+            None => return Err(error2(keyword.span(), "Expected the name of the table")),
+        };
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ':' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ':'")),
+            None => return Err(error2(name.span(), "Expected ':'")),
+        }
+        let elem: TokenStream = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket && !group.stream().is_empty() => group.stream(),
+            Some(tt) => return Err(error2(tt.span(), "Expected the type of the elements in square brackets (e.g., `[&str]`)")),
+            None => return Err(error2(name.span(), "Expected ':' and the type of the elements in square brackets (e.g., `[&str]`)")),
+        };
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+            None => return Err(error2(name.span(), "Expected '='")),
+        }
+
+        // Parse the entries
+        let group: Group = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+            Some(tt) => return Err(error2(tt.span(), "Expected the entries of the table in square brackets")),
+            None => return Err(error2(name.span(), "Expected the entries of the table in square brackets")),
+        };
+        let entries: Vec<Entry> = split_punct(group.stream(), ',').into_iter().map(|entry| Entry::parse(entry, group.span())).collect::<Result<_, _>>()?;
+        iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ';'));
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the table"));
+        }
+        Ok(Self { attrs, lookup, vis, keyword, name, elem, entries })
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`const_table()`](super::const_table())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the table to generate.
+///
+/// # Returns
+/// A new [`TokenStream`] with the table, a constant for the index of every entry and, optionally,
+/// a lookup function.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if an entry is given twice or
+/// if a lookup function is requested for values that are not literals.
+pub fn const_table(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let table: Table = Table::parse(input)?;
+    for (i, entry) in table.entries.iter().enumerate() {
+        if let Some(prev) = table.entries[..i].iter().find(|prev| prev.name == entry.name) {
+            return Err(Diagnostic2::error(entry.name.span(), format!("Duplicate entry `{}`", entry.name)).span_note(prev.name.span(), "first defined here"));
+        }
+    }
+    let span: Span = table.name.span();
+    let usize_ty = TokenStream::from(TokenTree::Ident(Ident::new("usize", span)));
+
+    // Generate the table itself
+    let mut ty = table.elem.clone();
+    ty.extend([TokenTree::Punct(Punct::new(';', Spacing::Alone)), TokenTree::Literal(Literal::usize_unsuffixed(table.entries.len()))]);
+    let mut values = TokenStream::new();
+    for entry in &table.entries {
+        values.extend(entry.value.iter().cloned());
+        values.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+    }
+    let mut res: TokenStream = item(
+        table.attrs,
+        &table.vis,
+        &table.keyword,
+        table.name,
+        TokenTree::Group(Group::new(Delimiter::Bracket, ty)).into(),
+        TokenTree::Group(Group::new(Delimiter::Bracket, values)).into(),
+    );
+
+    // Generate the index constants
+    let kw_const = Ident::new("const", span);
+    for (i, entry) in table.entries.iter().enumerate() {
+        res.extend(item(
+            entry.attrs.clone(),
+            &table.vis,
+            &kw_const,
+            entry.name.clone(),
+            usize_ty.clone(),
+            TokenTree::Literal(Literal::usize_unsuffixed(i)).into(),
+        ));
+    }
+
+    // Generate the lookup function, if any
+    if let Some((vis, name)) = table.lookup {
+        // Build the arms
+        let mut arms = TokenStream::new();
+        for (i, entry) in table.entries.iter().enumerate() {
+            if !is_lit(&entry.value) {
+                return Err(error2(entry.value[0].span(), "Expected a literal, as `#[lookup(...)]` matches on the values"));
+            }
+            let value: String = TokenStream::from_iter(entry.value.iter().cloned()).to_string();
+            if let Some(prev) = table.entries[..i].iter().find(|prev| TokenStream::from_iter(prev.value.iter().cloned()).to_string() == value) {
+                return Err(Diagnostic2::error(entry.value[0].span(), format!("Duplicate value `{value}`, which cannot be looked up unambiguously"))
+                    .span_note(prev.value[0].span(), "first given here")
+                    .help("remove the `#[lookup(...)]`-attribute, or make the values unique"));
+            }
+            arms.extend(entry.value.iter().cloned());
+            arms.extend([TokenTree::Punct(Punct::new('=', Spacing::Joint)), TokenTree::Punct(Punct::new('>', Spacing::Alone))]);
+            arms.extend(core_path(&["option", "Option", "Some"], span));
+            arms.extend([
+                TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenTree::Ident(entry.name.clone()).into())),
+                TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+            ]);
+        }
+        arms.extend([
+            TokenTree::Ident(Ident::new("_", span)),
+            TokenTree::Punct(Punct::new('=', Spacing::Joint)),
+            TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+        ]);
+        arms.extend(core_path(&["option", "Option", "None"], span));
+        arms.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+
+        // Build the function as `$vis fn $name(value: $elem) -> Option<usize> { match value { ... } }`
+        let value = Ident::new("value", span);
+        let mut args = TokenStream::from_iter([TokenTree::Ident(value.clone()), TokenTree::Punct(Punct::new(':', Spacing::Alone))]);
+        args.extend(table.elem);
+        res.extend(vis);
+        res.extend([
+            TokenTree::Ident(Ident::new("fn", span)),
+            TokenTree::Ident(name),
+            TokenTree::Group(Group::new(Delimiter::Parenthesis, args)),
+            TokenTree::Punct(Punct::new('-', Spacing::Joint)),
+            TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+        ]);
+        res.extend(core_path(&["option", "Option"], span));
+        res.extend([TokenTree::Punct(Punct::new('<', Spacing::Alone))]);
+        res.extend(usize_ty);
+        res.extend([TokenTree::Punct(Punct::new('>', Spacing::Alone))]);
+        let body = TokenStream::from_iter([
+            TokenTree::Ident(Ident::new("match", span)),
+            TokenTree::Ident(value),
+            TokenTree::Group(Group::new(Delimiter::Brace, arms)),
+        ]);
+        res.extend([TokenTree::Group(Group::new(Delimiter::Brace, body))]);
+    }
+    Ok(res)
+}
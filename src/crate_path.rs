@@ -0,0 +1,358 @@
+//  CRATE PATH.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for finding the path under which a crate is visible to the calling crate.
+//
+
+use std::path::PathBuf;
+
+use proc_macro2::{Delimiter, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::utils::Callback;
+
+
+/***** CONSTANTS *****/
+/// The names of the tables in a `Cargo.toml` that list dependencies.
+const DEP_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Normalizes the name of a crate such that it can be compared to others.
+///
+/// # Arguments
+/// - `name`: The name to normalize.
+///
+/// # Returns
+/// The name with all dashes replaced by underscores.
+#[inline]
+fn normalize(name: &str) -> String { name.replace('-', "_") }
+
+/// Splits a TOML-file into its statements, i.e., table headers and key/value-pairs.
+///
+/// Comments are removed, and statements spanning multiple lines (e.g., multi-line arrays) are
+/// kept together.
+///
+/// # Arguments
+/// - `toml`: The contents of the file.
+///
+/// # Returns
+/// A list of the statements, trimmed of whitespace.
+fn statements(toml: &str) -> Vec<String> {
+    let mut res: Vec<String> = Vec::new();
+    let mut stmt = String::new();
+    let mut depth: usize = 0;
+    let mut chars = toml.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // Strings, which may contain anything
+            '"' | '\'' => {
+                stmt.push(c);
+                // Find out if it's a multi-line string (`"""`) or not (`"` or `""`)
+                let mut quotes: usize = 1;
+                while quotes < 3 && chars.peek() == Some(&c) {
+                    stmt.extend(chars.next());
+                    quotes += 1;
+                }
+                if quotes == 2 {
+                    continue;
+                }
+
+                // Then skip until the same number of quotes
+                let mut closing: usize = 0;
+                while closing < quotes
+                    && let Some(c2) = chars.next()
+                {
+                    stmt.push(c2);
+                    if c == '"' && c2 == '\\' {
+                        stmt.extend(chars.next());
+                        closing = 0;
+                    } else if c2 == c {
+                        closing += 1;
+                    } else {
+                        closing = 0;
+                    }
+                }
+            },
+            // Comments, which we skip
+            '#' => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            },
+            '[' | '{' => {
+                depth += 1;
+                stmt.push(c);
+            },
+            ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                stmt.push(c);
+            },
+            '\n' if depth == 0 => {
+                if !stmt.trim().is_empty() {
+                    res.push(stmt.trim().into());
+                }
+                stmt.clear();
+            },
+            c => stmt.push(c),
+        }
+    }
+    if !stmt.trim().is_empty() {
+        res.push(stmt.trim().into());
+    }
+    res
+}
+
+/// Parses a TOML string.
+///
+/// # Arguments
+/// - `value`: The text to parse.
+///
+/// # Returns
+/// The contents of the string, or [`None`] if the value was not a (simple) string.
+fn parse_string(value: &str) -> Option<String> {
+    let value: &str = value.trim();
+    if value.len() >= 2 && ((value.starts_with('"') && value.ends_with('"')) || (value.starts_with('\'') && value.ends_with('\''))) {
+        Some(value[1..value.len() - 1].into())
+    } else {
+        None
+    }
+}
+
+/// Parses a (dotted) TOML key.
+///
+/// # Arguments
+/// - `text`: The text to parse the key from.
+/// - `end`: The character that ends the key (e.g., `=` or `]`).
+///
+/// # Returns
+/// The segments of the key and the text after the `end`, or [`None`] if `end` was not found.
+fn parse_key(text: &str, end: char) -> Option<(Vec<String>, &str)> {
+    let mut segments: Vec<String> = Vec::new();
+    let mut segment = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                for (_, c2) in chars.by_ref() {
+                    if c2 == c {
+                        break;
+                    }
+                    segment.push(c2);
+                }
+            },
+            '.' => segments.push(std::mem::take(&mut segment).trim().into()),
+            c if c == end => {
+                segments.push(segment.trim().into());
+                return Some((segments, &text[i + 1..]));
+            },
+            c => segment.push(c),
+        }
+    }
+    None
+}
+
+/// Finds the value of the `package`-key in an inline TOML table.
+///
+/// # Arguments
+/// - `value`: The inline table (e.g., `{ version = "1.0", package = "foo" }`).
+///
+/// # Returns
+/// The value of the `package`-key, or [`None`] if the value was not a table or had no such key.
+fn find_package(value: &str) -> Option<String> {
+    let inner: &str = value.trim().strip_prefix('{')?.strip_suffix('}')?;
+    // NOTE: Commas in strings or arrays don't matter, as the key we're looking for always comes
+    // right after a comma
+    for pair in inner.split(',') {
+        if let Some((key, value)) = parse_key(pair, '=')
+            && key == ["package"]
+        {
+            return parse_string(value);
+        }
+    }
+    None
+}
+
+/// Checks whether a table lists dependencies.
+///
+/// # Arguments
+/// - `table`: The path of the table (e.g., `["target", "cfg(unix)", "dependencies"]`).
+///
+/// # Returns
+/// True if it's one of the [`DEP_TABLES`], possibly for a specific target.
+fn is_dep_table(table: &[String]) -> bool {
+    match table {
+        [name] => DEP_TABLES.contains(&name.as_str()),
+        [target, _, name] => target == "target" && DEP_TABLES.contains(&name.as_str()),
+        _ => false,
+    }
+}
+
+
+
+/// Summarizes the parts of a `Cargo.toml` relevant for finding crate paths.
+#[derive(Default)]
+struct Manifest {
+    /// The name of the package.
+    package: Option<String>,
+    /// The name of the library target, if given explicitly.
+    lib:     Option<String>,
+    /// The dependencies, as the names under which they're visible and the names of their packages.
+    deps:    Vec<(String, String)>,
+}
+impl Manifest {
+    /// Parses a Manifest from the contents of a `Cargo.toml`.
+    ///
+    /// # Arguments
+    /// - `toml`: The contents of the file.
+    ///
+    /// # Returns
+    /// A new Manifest.
+    fn parse(toml: &str) -> Self {
+        let mut res = Self::default();
+        let mut table: Vec<String> = Vec::new();
+        for stmt in statements(toml) {
+            // Parse table headers, e.g., `[dependencies.foo]` or `[[bin]]`
+            if let Some(header) = stmt.strip_prefix('[') {
+                table = parse_key(header.trim_start_matches('['), ']').map(|(key, _)| key).unwrap_or_default();
+                if let [deps @ .., name] = table.as_slice()
+                    && is_dep_table(deps)
+                {
+                    res.add_dep(name);
+                }
+                continue;
+            }
+
+            // Parse key/value-pairs
+            let Some((key, value)) = parse_key(&stmt, '=') else { continue };
+            let path: Vec<String> = table.iter().cloned().chain(key).collect();
+            match path.as_slice() {
+                [package, name] if package == "package" && name == "name" => res.package = parse_string(value),
+                [lib, name] if lib == "lib" && name == "name" => res.lib = parse_string(value),
+                [deps @ .., name] if is_dep_table(deps) => {
+                    res.add_dep(name);
+                    if let Some(package) = find_package(value) {
+                        res.set_package(name, package);
+                    }
+                },
+                [deps @ .., name, package] if is_dep_table(deps) && package == "package" => {
+                    res.add_dep(name);
+                    if let Some(package) = parse_string(value) {
+                        res.set_package(name, package);
+                    }
+                },
+                [deps @ .., name, _] if is_dep_table(deps) => res.add_dep(name),
+                _ => {},
+            }
+        }
+        res
+    }
+
+    /// Adds a dependency if it doesn't exist yet, assuming its package has the same name.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the dependency.
+    fn add_dep(&mut self, name: &str) {
+        if !self.deps.iter().any(|(dep, _)| dep == name) {
+            self.deps.push((name.into(), name.into()));
+        }
+    }
+
+    /// Sets the package of a dependency, i.e., when it is renamed.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the dependency.
+    /// - `package`: The name of its package.
+    fn set_package(&mut self, name: &str, package: String) {
+        if let Some((_, dep_package)) = self.deps.iter_mut().find(|(dep, _)| dep == name) {
+            *dep_package = package;
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`crate_path()`](super::crate_path())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the name of the crate and an optional callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the path to the crate, or a call to the callback with it.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if the calling crate's
+/// `Cargo.toml` could not be read or if the crate is not a dependency of it.
+pub fn crate_path(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Parse the name of the crate
+    let mut iter = input.into_iter();
+    let mut tt: Option<TokenTree> = iter.next();
+    // Look through names given as e.g. `$name:ident`
+    if let Some(TokenTree::Group(group)) = &tt
+        && group.delimiter() == Delimiter::None
+    {
+        let mut tokens = group.stream().into_iter();
+        if let (Some(inner), None) = (tokens.next(), tokens.next()) {
+            tt = Some(inner);
+        }
+    }
+    let (name, span): (String, Span) = match tt {
+        Some(TokenTree::Ident(ident)) => (ident.to_string(), ident.span()),
+        Some(TokenTree::Literal(lit)) => match parse_string(&lit.to_string()) {
+            Some(name) if !name.contains('\\') => (name, lit.span()),
+            _ => return Err(error2(lit.span(), "Expected the name of a crate as an identifier or a string literal")),
+        },
+        Some(tt) => return Err(error2(tt.span(), "Expected the name of a crate as an identifier or a string literal")),
+        None => return Err(error2(Span::call_site(), "Expected the name of a crate")),
+    };
+    let callback: Option<Callback> = Callback::parse_opt(&mut iter)?;
+
+    // Read the manifest of the calling crate
+    let Some(dir) = std::env::var_os("CARGO_MANIFEST_DIR") else {
+        return Err(error2(span, "Cannot find the calling crate's 'Cargo.toml' (CARGO_MANIFEST_DIR is not set)"));
+    };
+    let path: PathBuf = PathBuf::from(dir).join("Cargo.toml");
+    let manifest: Manifest = match std::fs::read_to_string(&path) {
+        Ok(toml) => Manifest::parse(&toml),
+        Err(err) => return Err(error2(span, &format!("Failed to read '{}': {err}", path.display()))),
+    };
+
+    // Find the crate in it
+    let path: TokenStream = if manifest.package.as_deref().is_some_and(|package| normalize(package) == normalize(&name)) {
+        // It's the calling crate itself, which is only `crate` if we're in its library
+        let lib: String = normalize(manifest.lib.as_deref().or(manifest.package.as_deref()).unwrap_or(&name));
+        let in_lib: bool = std::env::var("CARGO_CRATE_NAME").is_ok_and(|name| name == lib)
+            && std::env::var_os("CARGO_BIN_NAME").is_none()
+            && std::env::var_os("UNSTABLE_RUSTDOC_TEST_PATH").is_none();
+        if in_lib {
+            TokenStream::from(TokenTree::Ident(Ident::new("crate", Span::call_site())))
+        } else {
+            TokenStream::from_iter([
+                TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+                TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                TokenTree::Ident(Ident::new(&lib, Span::call_site())),
+            ])
+        }
+    } else if let Some((dep, _)) = manifest.deps.iter().find(|(_, package)| normalize(package) == normalize(&name)) {
+        TokenStream::from_iter([
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(Ident::new(&normalize(dep), Span::call_site())),
+        ])
+    } else {
+        return Err(error2(span, &format!("Crate '{name}' is not a dependency of '{}'", manifest.package.as_deref().unwrap_or("the calling crate"))));
+    };
+
+    // Emit it
+    match callback {
+        Some(callback) => Ok(callback.call(path)),
+        None => Ok(path),
+    }
+}
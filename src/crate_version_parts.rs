@@ -0,0 +1,189 @@
+//  CRATE VERSION PARTS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for emitting the version of the calling crate as separate literals.
+//
+
+use std::iter::Peekable;
+use std::str::FromStr as _;
+
+use proc_macro2::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::utils::Callback;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Reads one of the `CARGO_PKG_VERSION_*` environment variables.
+///
+/// # Arguments
+/// - `part`: The part of the version to read (e.g., `MAJOR`).
+/// - `span`: The [`Span`] to report errors at.
+///
+/// # Returns
+/// The value of the variable.
+///
+/// # Errors
+/// This function errors if the variable is not set, e.g., because the crate is not compiled by
+/// Cargo.
+fn read_part(part: &str, span: Span) -> Result<String, Diagnostic2> {
+    std::env::var(format!("CARGO_PKG_VERSION_{part}"))
+        .map_err(|_| error2(span, &format!("Environment variable `CARGO_PKG_VERSION_{part}` is not set; is this crate being compiled by Cargo?")))
+}
+
+/// Reads one of the numeric parts of the version.
+///
+/// # Arguments
+/// - `part`: The part of the version to read (e.g., `MAJOR`).
+/// - `span`: The [`Span`] to report errors at.
+///
+/// # Returns
+/// The value of the part.
+///
+/// # Errors
+/// This function errors if the variable is not set or is not a number.
+fn read_num(part: &str, span: Span) -> Result<u64, Diagnostic2> {
+    let value: String = read_part(part, span)?;
+    value.parse().map_err(|_| error2(span, &format!("Environment variable `CARGO_PKG_VERSION_{part}` is not a number (it is {value:?})")))
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines what to generate for the version.
+enum Repr {
+    /// A tuple of the major, minor and patch numbers and the pre-release string.
+    Tuple,
+    /// Only the major number.
+    Major,
+    /// Only the minor number.
+    Minor,
+    /// Only the patch number.
+    Patch,
+    /// Only the pre-release string.
+    Pre,
+    /// A packed `u64` in `0xMMMMMMMM_mmmm_pppp`-order.
+    Packed,
+}
+
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The representation to generate.
+    repr:     Repr,
+    /// The callback to pass the parts to, if any.
+    callback: Option<Callback>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter: Peekable<_> = input.into_iter().peekable();
+
+        // Parse the optional representation first
+        let mut repr = Repr::Tuple;
+        let mut repr_span: Option<Span> = None;
+        if let Some(TokenTree::Ident(ident)) = iter.peek() {
+            repr = match ident.to_string().as_str() {
+                "tuple" => Repr::Tuple,
+                "major" => Repr::Major,
+                "minor" => Repr::Minor,
+                "patch" => Repr::Patch,
+                "pre" => Repr::Pre,
+                "packed" => Repr::Packed,
+                _ => return Err(error2(ident.span(), "Expected either `tuple`, `major`, `minor`, `patch`, `pre`, `packed` or '=>'")),
+            };
+            repr_span = Some(ident.span());
+            iter.next();
+        }
+
+        // Then the optional callback
+        let callback: Option<Callback> = Callback::parse_opt(&mut iter)?;
+        if let (Some(span), Some(_)) = (repr_span, &callback) {
+            return Err(error2(span, "Cannot give a representation when using a callback"));
+        }
+        Ok(Self { repr, callback })
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`crate_version_parts()`](super::crate_version_parts())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the optional representation and callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with either a tuple of the parts, a single part, a packed `u64` literal,
+/// or a call to the given callback with the parts.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if the crate is not compiled
+/// by Cargo or if the version does not fit the packed representation.
+pub fn crate_version_parts(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { repr, callback } = Input::parse(input)?;
+    let span: Span = Span::call_site();
+    let num = |value: u64| -> TokenTree {
+        let mut lit = Literal::u64_unsuffixed(value);
+        lit.set_span(span);
+        TokenTree::Literal(lit)
+    };
+    let pre = || -> Result<TokenTree, Diagnostic2> {
+        let mut lit = Literal::string(&read_part("PRE", span)?);
+        lit.set_span(span);
+        Ok(TokenTree::Literal(lit))
+    };
+
+    // Generate the single parts
+    match repr {
+        Repr::Tuple => {},
+        Repr::Major => return Ok(num(read_num("MAJOR", span)?).into()),
+        Repr::Minor => return Ok(num(read_num("MINOR", span)?).into()),
+        Repr::Patch => return Ok(num(read_num("PATCH", span)?).into()),
+        Repr::Pre => return Ok(pre()?.into()),
+        Repr::Packed => {
+            let (major, minor, patch): (u64, u64, u64) = (read_num("MAJOR", span)?, read_num("MINOR", span)?, read_num("PATCH", span)?);
+            if major > u32::MAX as u64 || minor > u16::MAX as u64 || patch > u16::MAX as u64 {
+                return Err(error2(
+                    span,
+                    &format!("Cannot pack version {major}.{minor}.{patch} into a `u64` (the major number must fit in 32 bits, the others in 16)"),
+                ));
+            }
+            let mut lit = Literal::from_str(&format!("0x{major:08X}_{minor:04X}_{patch:04X}u64")).unwrap();
+            lit.set_span(span);
+            return Ok(TokenTree::Literal(lit).into());
+        },
+    }
+
+    // Otherwise, generate all of them
+    let mut parts = TokenStream::new();
+    for (i, part) in [num(read_num("MAJOR", span)?), num(read_num("MINOR", span)?), num(read_num("PATCH", span)?), pre()?].into_iter().enumerate() {
+        if i > 0 {
+            let mut comma = Punct::new(',', Spacing::Alone);
+            comma.set_span(span);
+            parts.extend([TokenTree::Punct(comma)]);
+        }
+        parts.extend([part]);
+    }
+    match callback {
+        Some(callback) => Ok(callback.call_item(parts)),
+        None => {
+            let mut group = Group::new(Delimiter::Parenthesis, parts);
+            group.set_span(span);
+            Ok(TokenStream::from(TokenTree::Group(group)))
+        },
+    }
+}
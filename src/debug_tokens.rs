@@ -0,0 +1,203 @@
+//  DEBUG TOKENS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for printing the tokens it is given while compiling, to debug (declarative)
+//!   macros.
+//
+
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+
+
+/***** CONSTANTS *****/
+/// The keywords after which a group is not a call or an index (e.g., `if (a)` instead of `foo(a)`).
+const KEYWORDS: [&str; 19] =
+    ["as", "box", "break", "dyn", "else", "for", "if", "impl", "in", "let", "match", "move", "mut", "ref", "return", "use", "where", "while", "yield"];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Pushes the indentation of a new line to a string.
+///
+/// # Arguments
+/// - `res`: The string to push to.
+/// - `indent`: The level of indentation.
+fn push_indent(res: &mut String, indent: usize) {
+    for _ in 0..indent {
+        res.push_str("    ");
+    }
+}
+
+/// Ends the current line of a string, if it isn't ended already.
+///
+/// # Arguments
+/// - `res`: The string to end the line of.
+fn end_line(res: &mut String) {
+    if !res.is_empty() && !res.ends_with('\n') {
+        res.push('\n');
+    }
+}
+
+/// Pretty-prints tokens to a string.
+///
+/// # Arguments
+/// - `res`: The string to print to.
+/// - `tokens`: The tokens to print.
+/// - `indent`: The level of indentation of new lines.
+/// - `lines`: Whether the tokens are statements or items, which are printed on separate lines.
+fn write_pretty(res: &mut String, tokens: TokenStream, indent: usize, lines: bool) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut glue: bool = true;
+    for (i, tt) in tokens.iter().enumerate() {
+        let prev: Option<&TokenTree> = i.checked_sub(1).map(|i| &tokens[i]);
+        let next: Option<&TokenTree> = tokens.get(i + 1);
+
+        // Decide whether to put a space in front of the token
+        let operand: bool = matches!(prev, Some(TokenTree::Ident(ident)) if !KEYWORDS.contains(&ident.to_string().as_str()))
+            || matches!(prev, Some(TokenTree::Literal(_) | TokenTree::Group(_)))
+            || matches!(prev, Some(TokenTree::Punct(p)) if matches!(p.as_char(), '?' | '>'));
+        let tight: bool = match tt {
+            TokenTree::Punct(p) => matches!(p.as_char(), ',' | ';' | '.' | '?') || (matches!(p.as_char(), ':' | '!') && operand),
+            TokenTree::Group(group) => group.delimiter() != Delimiter::Brace && operand,
+            _ => false,
+        };
+        if res.is_empty() || res.ends_with('\n') {
+            push_indent(res, indent);
+        } else if !glue && !tight {
+            res.push(' ');
+        }
+
+        // Write the token itself
+        glue = false;
+        match tt {
+            TokenTree::Group(group) => {
+                let (open, close): (&str, &str) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                if group.delimiter() == Delimiter::Brace && !group.stream().is_empty() {
+                    res.push_str("{\n");
+                    write_pretty(res, group.stream(), indent + 1, true);
+                    end_line(res);
+                    push_indent(res, indent);
+                    res.push('}');
+                    if lines
+                        && !matches!(next, Some(TokenTree::Punct(p)) if matches!(p.as_char(), ',' | ';' | '.' | '?'))
+                        && !matches!(next, Some(TokenTree::Ident(ident)) if ident == "else")
+                    {
+                        res.push('\n');
+                    }
+                } else {
+                    res.push_str(open);
+                    write_pretty(res, group.stream(), indent, false);
+                    res.push_str(close);
+                    // Put attributes on their own line
+                    if lines && group.delimiter() == Delimiter::Bracket && matches!(prev, Some(TokenTree::Punct(p)) if matches!(p.as_char(), '#' | '!')) {
+                        res.push('\n');
+                    }
+                }
+            },
+            TokenTree::Punct(p) => {
+                res.push(p.as_char());
+                let unary: bool = matches!(p.as_char(), '&' | '*' | '-' | '!') && !operand;
+                glue = p.spacing() == Spacing::Joint || matches!(p.as_char(), '.' | '#' | '$') || (p.as_char() == '!' && operand) || unary;
+                if lines && matches!(p.as_char(), ';' | ',') {
+                    res.push('\n');
+                }
+            },
+            tt => res.push_str(&tt.to_string()),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Pretty-prints tokens.
+///
+/// This puts statements, items and fields on separate lines and indents the contents of curly
+/// brackets, which is enough to make most expansions readable without parsing them.
+///
+/// # Arguments
+/// - `tokens`: The tokens to print.
+///
+/// # Returns
+/// The printed tokens, without a trailing newline.
+pub fn pretty(tokens: TokenStream) -> String {
+    let mut res = String::new();
+    write_pretty(&mut res, tokens, 0, true);
+    res.truncate(res.trim_end().len());
+    res
+}
+
+/// Finds the location of the call of a macro, for tagging debug output with.
+///
+/// # Returns
+/// The file and line of the call as `file:line`.
+pub fn location() -> String {
+    let span: proc_macro::Span = Span::call_site().unwrap();
+    format!("{}:{}", span.file(), span.line())
+}
+
+/// Parses the input to [`debug_tokens()`](super::debug_tokens()) and
+/// [`dump_expansion()`](super::dump_expansion()) after their required arguments.
+///
+/// This is an optional `pretty`-flag, followed by the tokens in curly brackets.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// Whether `pretty` was given and the tokens.
+///
+/// # Errors
+/// This function errors if the input is not valid.
+pub fn parse_tokens(iter: &mut impl Iterator<Item = TokenTree>) -> Result<(bool, TokenStream), Diagnostic2> {
+    let mut pretty: bool = false;
+    let tokens: TokenStream = loop {
+        match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => break group.stream(),
+            Some(TokenTree::Ident(ident)) if ident == "pretty" && !pretty => {
+                pretty = true;
+                match iter.next() {
+                    Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                    Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+                    None => return Err(error2(Span::call_site(), "Expected ',' and the tokens in curly brackets")),
+                }
+            },
+            Some(tt) => return Err(error2(tt.span(), "Expected either `pretty` or the tokens in curly brackets")),
+            None => return Err(error2(Span::call_site(), "Expected the tokens in curly brackets")),
+        }
+    };
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the tokens"));
+    }
+    Ok((pretty, tokens))
+}
+
+
+
+/// Defines the implementation of the [`debug_tokens()`](super::debug_tokens())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the optional `pretty`-flag and the tokens to print.
+///
+/// # Returns
+/// A new [`TokenStream`] with the printed tokens, unchanged.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn debug_tokens(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (pretty_print, tokens): (bool, TokenStream) = parse_tokens(&mut input.into_iter())?;
+    let text: String = if pretty_print { pretty(tokens.clone()) } else { tokens.to_string() };
+    eprintln!("[debug_tokens] {}:\n    {}", location(), text.replace('\n', "\n    "));
+    Ok(tokens)
+}
@@ -0,0 +1,68 @@
+//  DERIVE ALIAS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for defining named sets of derives, as a shorthand for
+//!   [`attr_alias!{}`](super::attr_alias()).
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::attr_alias::attr_alias;
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`derive_alias()`](super::derive_alias())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the aliases to define.
+///
+/// # Returns
+/// A new [`TokenStream`] with a `macro_rules!`-macro for every alias, as generated by
+/// [`attr_alias()`](super::attr_alias()).
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn derive_alias(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Rewrite every `<head> = A, B, C;` to `<head> = #[derive(A, B, C)];`
+    let mut aliases = TokenStream::new();
+    let mut iter = input.into_iter();
+    while let Some(tt) = iter.next() {
+        let is_eq: bool = matches!(&tt, TokenTree::Punct(p) if p.as_char() == '=');
+        let span: Span = tt.span();
+        aliases.extend([tt]);
+        if !is_eq {
+            continue;
+        }
+
+        // Collect the derives up to the semicolon
+        let mut derives = TokenStream::new();
+        let mut semicolon: Option<TokenTree> = None;
+        for tt in iter.by_ref() {
+            if let TokenTree::Punct(p) = &tt
+                && p.as_char() == ';'
+            {
+                semicolon = Some(tt);
+                break;
+            }
+            derives.extend([tt]);
+        }
+        if derives.is_empty() {
+            return Err(item_error(error2(span, "Expected at least one derive after '='")));
+        }
+        aliases.extend([
+            TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+            TokenTree::Group(Group::new(
+                Delimiter::Bracket,
+                TokenStream::from_iter([
+                    TokenTree::Ident(Ident::new("derive", Span::call_site())),
+                    TokenTree::Group(Group::new(Delimiter::Parenthesis, derives)),
+                ]),
+            )),
+        ]);
+        // NOTE: A missing semicolon is reported by `attr_alias()`
+        aliases.extend(semicolon);
+    }
+    attr_alias(aliases)
+}
@@ -0,0 +1,165 @@
+//  DERIVE VIA.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides an attribute macro for implementing derives with declarative macros.
+//
+
+use std::collections::HashSet;
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::utils::Callback;
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed arguments to the attribute.
+struct Input {
+    /// The macros to call with the item.
+    callbacks: Vec<Callback>,
+    /// The names of the helper attributes to strip from the item.
+    helpers:   HashSet<String>,
+}
+impl Input {
+    /// Parses the arguments to the attribute.
+    ///
+    /// # Arguments
+    /// - `attr`: The [`TokenStream`] given to the attribute to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(attr: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut callbacks: Vec<Callback> = Vec::new();
+        let mut helpers: HashSet<String> = HashSet::new();
+        let mut iter: Peekable<_> = attr.into_iter().peekable();
+        while iter.peek().is_some() {
+            // Collect everything up to the next comma
+            let mut arg: Vec<TokenTree> = Vec::new();
+            for tt in iter.by_ref() {
+                if matches!(&tt, TokenTree::Punct(p) if p.as_char() == ',') {
+                    break;
+                }
+                arg.push(tt);
+            }
+
+            // Then parse it as either a list of helper attributes, or a macro to call
+            match arg.as_slice() {
+                [TokenTree::Ident(ident), TokenTree::Group(group)] if ident == "attributes" && group.delimiter() == Delimiter::Parenthesis => {
+                    helpers.extend(Self::parse_helpers(group.stream())?);
+                },
+                [] => return Err(error2(Span::call_site(), "Expected a path to a macro (e.g., `foo!`) or `attributes(...)` before ','")),
+                _ => callbacks.push(Callback::parse(&mut arg.into_iter())?),
+            }
+        }
+        if callbacks.is_empty() {
+            return Err(error2(Span::call_site(), "Expected at least one path to a macro to derive with (e.g., `#[derive_via(foo!)]`)"));
+        }
+        Ok(Self { callbacks, helpers })
+    }
+
+    /// Parses the names of helper attributes given in `attributes(...)`.
+    ///
+    /// # Arguments
+    /// - `input`: The [`TokenStream`] in the parenthesis to parse.
+    ///
+    /// # Returns
+    /// The names of the helper attributes.
+    ///
+    /// # Errors
+    /// This function can error if the input was not a comma-separated list of identifiers.
+    fn parse_helpers(input: TokenStream) -> Result<Vec<String>, Diagnostic2> {
+        let mut helpers: Vec<String> = Vec::new();
+        let mut iter = input.into_iter();
+        while let Some(tt) = iter.next() {
+            match tt {
+                TokenTree::Ident(ident) => helpers.push(ident.to_string()),
+                tt => return Err(error2(tt.span(), "Expected the name of a helper attribute")),
+            }
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+                None => break,
+            }
+        }
+        Ok(helpers)
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Removes all attributes with the given names from the given tokens, including those in nested
+/// groups (e.g., on fields or variants).
+///
+/// # Arguments
+/// - `tokens`: The [`TokenStream`] to remove the attributes from.
+/// - `names`: The names of the attributes to remove.
+///
+/// # Returns
+/// The same tokens, but without the attributes.
+fn strip_attrs(tokens: TokenStream, names: &HashSet<String>) -> TokenStream {
+    let mut res = TokenStream::new();
+    let mut iter: Peekable<_> = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            // An attribute; see if it has a name we're looking for
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                if let Some(TokenTree::Group(group)) = iter.peek()
+                    && group.delimiter() == Delimiter::Bracket
+                    && matches!(group.stream().into_iter().next(), Some(TokenTree::Ident(ident)) if names.contains(&ident.to_string()))
+                {
+                    iter.next();
+                    continue;
+                }
+                res.extend([TokenTree::Punct(p)]);
+            },
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), strip_attrs(group.stream(), names));
+                new.set_span(group.span());
+                res.extend([TokenTree::Group(new)]);
+            },
+            tt => res.extend([tt]),
+        }
+    }
+    res
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`derive_via()`](super::derive_via())-macro.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the macros to derive with and any helper attributes.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] with the item, without helper attributes, followed by calls to the given
+/// macros with the (full) item.
+///
+/// # Errors
+/// This function may error if the attribute's arguments are invalid. In that case, the error is
+/// still followed by the item.
+pub fn derive_via(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { callbacks, helpers } = Input::parse(attr).map_err(|err| {
+        // NOTE: The error ends up in item position, so needs to be a valid item. We also still
+        // emit the item itself, to avoid confusing errors about it missing.
+        item_error(err).with_tokens(item.clone())
+    })?;
+
+    // Emit the item itself, then one call for every macro
+    let mut res: TokenStream = if helpers.is_empty() { item.clone() } else { strip_attrs(item.clone(), &helpers) };
+    for callback in callbacks {
+        res.extend(callback.call_item(item.clone()));
+    }
+    Ok(res)
+}
@@ -0,0 +1,135 @@
+//  DIAGNOSTICS.rs
+//    by Lut99
+//
+//  Description:
+//!   Parses the arguments of the diagnostic macros, and re-exports the diagnostics of
+//!   `macro-toolkit-core` that all other macros report their errors with.
+//
+
+use proc_macro2::{Delimiter, Span, TokenTree};
+
+pub use macro_toolkit_core::diagnostics::{Diagnostic2, Level, error2, item_error};
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Unescapes the contents of a string literal.
+///
+/// # Arguments
+/// - `value`: The contents of the literal, without the quotes.
+///
+/// # Returns
+/// The unescaped contents, or [`None`] if they contain an invalid escape sequence.
+fn unescape(value: &str) -> Option<String> {
+    let mut res = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => res.push('\n'),
+            'r' => res.push('\r'),
+            't' => res.push('\t'),
+            '0' => res.push('\0'),
+            c @ ('\\' | '\'' | '"') => res.push(c),
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                res.push(char::from(u8::from_str_radix(&hex, 16).ok().filter(|b| b.is_ascii())?));
+            },
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let hex: String = chars.by_ref().take_while(|c| *c != '}').filter(|c| *c != '_').collect();
+                res.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            },
+            // Line continuations skip the newline and any leading whitespace on the next line
+            '\n' => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            _ => return None,
+        }
+    }
+    Some(res)
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the span to have a diagnostic point to.
+///
+/// This is either `call_site`, `mixed_site` or any other token, in which case its span is used.
+///
+/// # Arguments
+/// - `tt`: The token to parse.
+///
+/// # Returns
+/// The parsed [`Span`].
+pub fn parse_anchor(tt: TokenTree) -> Span {
+    match tt {
+        TokenTree::Ident(ident) if ident == "call_site" => Span::call_site(),
+        TokenTree::Ident(ident) if ident == "mixed_site" => Span::mixed_site(),
+        // Look through tokens given as e.g. `$name:ident`
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+            let mut tokens = group.stream().into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(tt), None) => tt.span(),
+                _ => group.span(),
+            }
+        },
+        tt => tt.span(),
+    }
+}
+
+/// Parses a string, given as a string literal.
+///
+/// # Arguments
+/// - `tt`: The token to parse, if any.
+/// - `span`: A span to report errors at if there is no token.
+/// - `what`: What the string is, for in errors (e.g., "a message").
+///
+/// # Returns
+/// The (unescaped) string.
+///
+/// # Errors
+/// This function errors if the token is not a string literal.
+pub fn parse_string(tt: Option<TokenTree>, span: Span, what: &str) -> Result<String, Diagnostic2> {
+    match tt {
+        // Look through literals given as e.g. `$msg:literal`
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+            let mut tokens = group.stream().into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(tt), None) => parse_string(Some(tt), span, what),
+                _ => Err(error2(group.span(), &format!("Expected {what} as a string literal"))),
+            }
+        },
+        Some(TokenTree::Literal(lit)) => {
+            let value: String = lit.to_string();
+            let message: Option<String> = if let Some(raw) = value.strip_prefix('r') {
+                let hashes: usize = raw.len() - raw.trim_start_matches('#').len();
+                raw[hashes..].strip_prefix('"').and_then(|raw| raw.strip_suffix(&format!("\"{}", "#".repeat(hashes)))).map(String::from)
+            } else {
+                value.strip_prefix('"').and_then(|value| value.strip_suffix('"')).and_then(unescape)
+            };
+            message.ok_or_else(|| error2(lit.span(), &format!("Expected {what} as a string literal")))
+        },
+        Some(tt) => Err(error2(tt.span(), &format!("Expected {what} as a string literal"))),
+        None => Err(error2(span, &format!("Expected {what} as a string literal"))),
+    }
+}
+
+/// Parses a message, given as a string literal.
+///
+/// # Arguments
+/// - `tt`: The token to parse, if any.
+/// - `span`: A span to report errors at if there is no token.
+///
+/// # Returns
+/// The message.
+///
+/// # Errors
+/// This function errors if the token is not a string literal.
+#[inline]
+pub fn parse_message(tt: Option<TokenTree>, span: Span) -> Result<String, Diagnostic2> { parse_string(tt, span, "a message") }
@@ -0,0 +1,157 @@
+//  DIGITS OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for splitting integer literals into their digits.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitInt};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::parse_lit;
+use crate::utils::Callback;
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The literal to split.
+    lit:      LitInt,
+    /// The radix to split it in.
+    radix:    u32,
+    /// The callback to pass the digits to, if any.
+    callback: Option<Callback>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the literal first
+        let lit: LitInt = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Int(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected an integer literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected an integer literal")),
+        };
+
+        // Then parse the optional radix
+        let mut radix: u32 = 10;
+        if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+            iter.next();
+            radix = Self::parse_radix(&mut iter)?;
+        }
+
+        // Finally, parse the optional callback
+        let callback: Option<Callback> = match iter.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => Callback::parse_opt(&mut iter)?,
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',', '=>' or nothing after the integer literal")),
+            None => None,
+        };
+
+        // Done
+        Ok(Self { lit, radix, callback })
+    }
+
+    /// Parses a `radix = ...`-pair.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed radix.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input did not encode a valid radix.
+    fn parse_radix(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<u32, Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "radix" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected 'radix'")),
+            None => return Err(error2(Span::mixed_site(), "Expected 'radix'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' && p.spacing() == Spacing::Alone => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+            None => return Err(error2(Span::mixed_site(), "Expected '='")),
+        }
+        let lit: LitInt = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Int(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected an integer literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected an integer literal")),
+        };
+        match lit.base10_parse::<u32>() {
+            Ok(radix) if (2..=36).contains(&radix) => Ok(radix),
+            _ => Err(error2(lit.span(), "Expected a radix between 2 and 36 (inclusive)")),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`digits_of()`](super::digits_of())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to split.
+///
+/// # Returns
+/// A new [`TokenStream`] with an array of digit literals, or a call to the given callback with a
+/// comma-separated list of them.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn digits_of(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { lit, radix, callback } = Input::parse(input)?;
+    let span: Span = lit.span();
+
+    // Parse the literal's value
+    let mut value: u128 = match lit.base10_parse() {
+        Ok(value) => value,
+        Err(_) => return Err(error2(span, "Integer literal is too large")),
+    };
+
+    // Collect the digits, most significant first
+    let mut digits: Vec<u32> = Vec::new();
+    loop {
+        digits.push((value % radix as u128) as u32);
+        value /= radix as u128;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+
+    // Serialize them
+    let mut res = TokenStream::new();
+    for (i, digit) in digits.into_iter().enumerate() {
+        if i > 0 {
+            let mut comma = Punct::new(',', Spacing::Alone);
+            comma.set_span(span);
+            res.extend([TokenTree::Punct(comma)]);
+        }
+        let mut digit = Literal::u32_unsuffixed(digit);
+        digit.set_span(span);
+        res.extend([TokenTree::Literal(digit)]);
+    }
+    match callback {
+        Some(callback) => Ok(callback.call(res)),
+        None => {
+            let mut group = Group::new(Delimiter::Bracket, res);
+            group.set_span(span);
+            Ok(TokenStream::from(TokenTree::Group(group)))
+        },
+    }
+}
@@ -0,0 +1,325 @@
+//  DOC FMT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for generating documentation from a format string at compile time.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::lits::parse_lit;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether the tokens at the given position form a `=>`.
+///
+/// # Arguments
+/// - `tokens`: The list of tokens to check in.
+/// - `i`: The index of the token to check.
+///
+/// # Returns
+/// True if `tokens[i]` and `tokens[i + 1]` form a `=>`, or false otherwise.
+fn is_arrow(tokens: &[TokenTree], i: usize) -> bool {
+    matches!(
+        (tokens.get(i), tokens.get(i + 1)),
+        (Some(TokenTree::Punct(eq)), Some(TokenTree::Punct(gt))) if eq.as_char() == '=' && eq.spacing() == Spacing::Joint && gt.as_char() == '>'
+    )
+}
+
+/// Generates a `#[doc = "..."]`-attribute.
+///
+/// # Arguments
+/// - `line`: The line of documentation to put in it.
+/// - `span`: The [`Span`] to give to the attribute.
+///
+/// # Returns
+/// A [`TokenStream`] with the attribute.
+fn doc_attr(line: &str, span: Span) -> TokenStream {
+    let mut lit = Literal::string(line);
+    lit.set_span(span);
+    let mut eq = Punct::new('=', Spacing::Alone);
+    eq.set_span(span);
+    let mut pound = Punct::new('#', Spacing::Alone);
+    pound.set_span(span);
+    let mut attr =
+        Group::new(Delimiter::Bracket, [TokenTree::Ident(Ident::new("doc", span)), TokenTree::Punct(eq), TokenTree::Literal(lit)].into_iter().collect());
+    attr.set_span(span);
+    [TokenTree::Punct(pound), TokenTree::Group(attr)].into_iter().collect()
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single argument given to the macro.
+struct Arg {
+    /// The name of the argument, if it's a named one.
+    name:    Option<Ident>,
+    /// The value of the argument when formatted with `{}`.
+    display: String,
+    /// The value of the argument when formatted with `{:?}`.
+    debug:   String,
+    /// The span of the argument, for error reporting.
+    span:    Span,
+}
+
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The format string.
+    fmt:    LitStr,
+    /// The arguments to the format string.
+    args:   Vec<Arg>,
+    /// The tokens to document, if any.
+    tokens: Option<TokenStream>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let tokens: Vec<TokenTree> = input.into_iter().collect();
+
+        // Parse the format string first
+        let fmt: LitStr = match tokens.first().cloned().map(parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected a format string literal")),
+        };
+
+        // Then parse the arguments
+        let mut args: Vec<Arg> = Vec::new();
+        let mut i: usize = 1;
+        loop {
+            match tokens.get(i) {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {
+                    // Find the end of the argument
+                    let start: usize = i + 1;
+                    i = start;
+                    while i < tokens.len() && !matches!(&tokens[i], TokenTree::Punct(p) if p.as_char() == ',') && !is_arrow(&tokens, i) {
+                        i += 1;
+                    }
+                    if let Some(arg) = Self::parse_arg(&tokens[start..i])? {
+                        if arg.name.is_none() && args.iter().any(|a| a.name.is_some()) {
+                            return Err(error2(arg.span, "Positional arguments cannot follow named arguments"));
+                        }
+                        if let Some(name) = &arg.name
+                            && args.iter().any(|a| a.name.as_ref() == Some(name))
+                        {
+                            return Err(error2(name.span(), &format!("Duplicate argument named `{name}`")));
+                        }
+                        args.push(arg);
+                    }
+                },
+                Some(_) if is_arrow(&tokens, i) => return Ok(Self { fmt, args, tokens: Some(tokens.into_iter().skip(i + 2).collect()) }),
+                Some(tt) => return Err(error2(tt.span(), "Expected either ',', '=>' or nothing")),
+                None => return Ok(Self { fmt, args, tokens: None }),
+            }
+        }
+    }
+
+    /// Parses a single argument.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens making up the argument.
+    ///
+    /// # Returns
+    /// The parsed [`Arg`], or [`None`] if there was no argument (e.g., after a trailing comma).
+    ///
+    /// # Errors
+    /// This function errors if the argument was invalid.
+    fn parse_arg(tokens: &[TokenTree]) -> Result<Option<Arg>, Diagnostic2> {
+        let Some(first) = tokens.first() else { return Ok(None) };
+        let span: Span = first.span();
+
+        // See if it's named
+        let (name, value): (Option<Ident>, &[TokenTree]) = match (first, tokens.get(1)) {
+            (TokenTree::Ident(name), Some(TokenTree::Punct(p))) if p.as_char() == '=' && p.spacing() == Spacing::Alone => {
+                if tokens.len() < 3 {
+                    return Err(error2(p.span(), "Expected a value after '='"));
+                }
+                (Some(name.clone()), &tokens[2..])
+            },
+            _ => (None, tokens),
+        };
+
+        // Then parse the value
+        let (display, debug): (String, String) = Self::parse_value(value)?;
+        Ok(Some(Arg { name, display, debug, span }))
+    }
+
+    /// Parses the value of a single argument.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens making up the value.
+    ///
+    /// # Returns
+    /// The value when formatted with `{}` and with `{:?}`.
+    ///
+    /// # Errors
+    /// This function errors if the value is not a literal or an identifier.
+    fn parse_value(tokens: &[TokenTree]) -> Result<(String, String), Diagnostic2> {
+        match tokens {
+            // Look through values given as e.g. `$arg:tt`
+            [TokenTree::Group(group)] if group.delimiter() == Delimiter::None => Self::parse_value(&group.stream().into_iter().collect::<Vec<_>>()),
+
+            // Identifiers (except for booleans) are formatted as their name
+            [TokenTree::Ident(ident)] if ident != "true" && ident != "false" => {
+                let name: String = ident.to_string();
+                let name: String = name.strip_prefix("r#").map(String::from).unwrap_or(name);
+                Ok((name.clone(), name))
+            },
+
+            // Literals are formatted as their value, where numbers may be negated
+            [TokenTree::Punct(p), tt] if p.as_char() == '-' => match parse_lit(tt.clone())? {
+                Lit::Int(lit) => Ok((format!("-{}", lit.base10_digits()), format!("-{}", lit.base10_digits()))),
+                Lit::Float(lit) => Ok((format!("-{}", lit.base10_digits()), format!("-{}", lit.base10_digits()))),
+                lit => Err(error2(lit.span(), "Expected a numeric literal after '-'")),
+            },
+            [tt] => match parse_lit(tt.clone())? {
+                Lit::Str(lit) => Ok((lit.value(), format!("{:?}", lit.value()))),
+                Lit::Char(lit) => Ok((lit.value().to_string(), format!("{:?}", lit.value()))),
+                Lit::Int(lit) => Ok((lit.base10_digits().into(), lit.base10_digits().into())),
+                Lit::Float(lit) => Ok((lit.base10_digits().into(), lit.base10_digits().into())),
+                Lit::Bool(lit) => Ok((lit.value().to_string(), lit.value().to_string())),
+                lit => Err(error2(lit.span(), "Expected a string, character, numeric or boolean literal")),
+            },
+            [tt, ..] => Err(error2(tt.span(), "Expected either a literal or an identifier")),
+            [] => unreachable!(),
+        }
+    }
+}
+
+
+
+
+
+/***** FORMATTING *****/
+/// Formats the format string with the given arguments.
+///
+/// # Arguments
+/// - `fmt`: The format string.
+/// - `args`: The arguments to format it with.
+/// - `span`: The [`Span`] of the format string, for error reporting.
+///
+/// # Returns
+/// The formatted string.
+///
+/// # Errors
+/// This function errors if the format string is malformed, uses a format spec other than `?`,
+/// refers to an argument that doesn't exist or if an argument is not used.
+fn format(fmt: &str, args: &[Arg], span: Span) -> Result<String, Diagnostic2> {
+    let mut res = String::with_capacity(fmt.len());
+    let mut used: Vec<bool> = vec![false; args.len()];
+    let mut next: usize = 0;
+    let mut chars = fmt.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.next_if_eq(&'{').is_some() => res.push('{'),
+            '}' if chars.next_if_eq(&'}').is_some() => res.push('}'),
+            '}' => return Err(error2(span, "Invalid format string: unmatched `}` found (use `}}` to write a literal `}`)")),
+            '{' => {
+                // Find the contents of the placeholder
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => return Err(error2(span, "Invalid format string: expected `}` but string was terminated (use `{{` to write a literal `{`)")),
+                    }
+                }
+                let (arg, spec): (&str, &str) = placeholder.split_once(':').unwrap_or((&placeholder, ""));
+                let (arg, spec): (&str, &str) = (arg.trim(), spec.trim());
+
+                // Resolve the argument it refers to
+                let index: usize = if arg.is_empty() {
+                    next += 1;
+                    next - 1
+                } else if let Ok(index) = arg.parse::<usize>() {
+                    index
+                } else {
+                    match args.iter().position(|a| a.name.as_ref().is_some_and(|name| name == arg)) {
+                        Some(index) => index,
+                        None => return Err(error2(span, &format!("There is no argument named `{arg}`"))),
+                    }
+                };
+                let Some(value) = args.get(index) else {
+                    return Err(error2(span, &format!("Invalid reference to positional argument {index} ({} argument(s) given)", args.len())));
+                };
+                used[index] = true;
+
+                // Write it
+                match spec {
+                    "" => res.push_str(&value.display),
+                    "?" => res.push_str(&value.debug),
+                    spec => return Err(error2(span, &format!("Unsupported format spec `{spec}` (only `{{}}` and `{{:?}}` are supported)"))),
+                }
+            },
+            c => res.push(c),
+        }
+    }
+
+    // Check that all arguments have been used
+    if let Some(index) = used.iter().position(|used| !used) {
+        return Err(error2(args[index].span, "Argument never used"));
+    }
+    Ok(res)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`doc_fmt()`](super::doc_fmt())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the format string, the arguments and optionally
+///   the tokens to document.
+///
+/// # Returns
+/// A new [`TokenStream`] with either the formatted string literal, or the tokens with a
+/// `#[doc = "..."]`-attribute for every line of the formatted string.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the format string could
+/// not be formatted with the arguments.
+pub fn doc_fmt(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // When documenting tokens, the macro is used in item position, where the `compile_error!()`
+    // needs a semicolon to not trigger another error
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let is_item: bool = (0..tokens.len()).any(|i| is_arrow(&tokens, i));
+    let generate = || -> Result<TokenStream, Diagnostic2> {
+        let Input { fmt, args, tokens } = Input::parse(input)?;
+        let span: Span = fmt.span();
+        let doc: String = format(&fmt.value(), &args, span)?;
+
+        // Emit either the string or the attributes
+        match tokens {
+            Some(tokens) => {
+                // Lines are prefixed with a space, like `///`-comments are
+                let mut res = TokenStream::new();
+                for line in doc.lines() {
+                    res.extend(doc_attr(&if line.is_empty() { String::new() } else { format!(" {line}") }, span));
+                }
+                res.extend(tokens);
+                Ok(res)
+            },
+            None => {
+                let mut lit = Literal::string(&doc);
+                lit.set_span(span);
+                Ok(TokenStream::from(TokenTree::Literal(lit)))
+            },
+        }
+    };
+    if is_item { generate().map_err(item_error) } else { generate() }
+}
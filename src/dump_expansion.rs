@@ -0,0 +1,91 @@
+//  DUMP EXPANSION.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for writing the tokens it is given to a file while compiling, such that
+//!   (large) expansions can be inspected and diffed between builds.
+//
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::debug_tokens::{location, parse_tokens, pretty};
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::parse_lit;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds the directory to write dumps to.
+///
+/// This is `$OUT_DIR/macro-toolkit` if the calling crate has a build script, or
+/// `macro-toolkit/dumps` in its target directory otherwise. The target directory is taken from
+/// `CARGO_TARGET_DIR` if set, or else the nearest `target`-directory created by Cargo next to
+/// (or above) the calling crate's `Cargo.toml`.
+///
+/// # Returns
+/// The path of the directory, which may not exist yet.
+fn dump_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("OUT_DIR") {
+        return PathBuf::from(dir).join("macro-toolkit");
+    }
+    let manifest_dir: PathBuf = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from).unwrap_or_default();
+    let target_dir: PathBuf = match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        // NOTE: Cargo marks its target directories with a `CACHEDIR.TAG`, which distinguishes it
+        // from e.g. a user's module called `target`
+        None => {
+            manifest_dir.ancestors().map(|dir| dir.join("target")).find(|dir| dir.join("CACHEDIR.TAG").is_file()).unwrap_or_else(|| manifest_dir.join("target"))
+        },
+    };
+    target_dir.join("macro-toolkit").join("dumps")
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`dump_expansion()`](super::dump_expansion())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the name of the dump, the optional `pretty`-flag and
+///   the tokens to dump.
+///
+/// # Returns
+/// A new [`TokenStream`] with the dumped tokens, unchanged.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro or if the dump could not be
+/// written.
+pub fn dump_expansion(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the name of the dump
+    let (name, span): (String, Span) = match iter.next().map(parse_lit).transpose()? {
+        Some(Lit::Str(lit)) => (lit.value(), lit.span()),
+        Some(lit) => return Err(error2(lit.span(), "Expected the name of the dump as a string literal")),
+        None => return Err(error2(Span::call_site(), "Expected the name of the dump as a string literal")),
+    };
+    if name.is_empty() || !Path::new(&name).components().all(|comp| matches!(comp, Component::Normal(_))) {
+        return Err(error2(span, "Expected a relative path without '..' as name of the dump"));
+    }
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+        None => return Err(error2(Span::call_site(), "Expected ',' and the tokens in curly brackets")),
+    }
+    let (pretty_print, tokens): (bool, TokenStream) = parse_tokens(&mut iter)?;
+
+    // Write them, overwriting the dump of any previous build
+    let path: PathBuf = dump_dir().join(format!("{name}.rs"));
+    let text: String =
+        format!("// Dumped by `dump_expansion!()` at {}\n\n{}\n", location(), if pretty_print { pretty(tokens.clone()) } else { tokens.to_string() });
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, text))
+        .map_err(|err| error2(span, &format!("Failed to write to '{}': {err}", path.display())))?;
+    Ok(tokens)
+}
@@ -0,0 +1,194 @@
+//  DUPLICATE.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides an attribute macro for duplicating an item once per row of a substitution table.
+//
+
+use std::collections::HashMap;
+
+use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed arguments to the attribute.
+struct Input {
+    /// The names of the placeholders, in order.
+    names: Vec<Ident>,
+    /// The rows of the table, each of which assigns a value to every placeholder.
+    rows:  Vec<Vec<TokenStream>>,
+}
+impl Input {
+    /// Parses the arguments to the attribute.
+    ///
+    /// # Arguments
+    /// - `attr`: The [`TokenStream`] given to the attribute to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(attr: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut names: Vec<Ident> = Vec::new();
+        let mut columns: Vec<(Span, Vec<TokenStream>)> = Vec::new();
+        let mut iter = attr.into_iter();
+        while let Some(tt) = iter.next() {
+            // Parse the name
+            let name: Ident = match tt {
+                TokenTree::Ident(ident) => ident,
+                tt => return Err(error2(tt.span(), "Expected the name of a placeholder")),
+            };
+            if names.contains(&name) {
+                return Err(error2(name.span(), &format!("Placeholder `{name}` is given more than once")));
+            }
+
+            // Parse the `=`
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '=' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+                None => return Err(error2(name.span(), "Expected '='")),
+            }
+
+            // Parse the values
+            let group: Group = match iter.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+                Some(tt) => return Err(error2(tt.span(), "Expected a list of values in square brackets")),
+                None => return Err(error2(name.span(), "Expected a list of values in square brackets")),
+            };
+            let values: Vec<TokenStream> = parse_values(group.stream())?;
+            if values.is_empty() {
+                return Err(error2(group.span(), "Expected at least one value"));
+            }
+            names.push(name);
+            columns.push((group.span(), values));
+
+            // Parse the separating comma
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+                None => break,
+            }
+        }
+        if names.is_empty() {
+            return Err(error2(Span::call_site(), "Expected at least one placeholder (e.g., `#[duplicate(T = [u8, u16])]`)"));
+        }
+
+        // Transpose the columns to rows
+        let len: usize = columns[0].1.len();
+        if let Some((span, values)) = columns.iter().find(|(_, values)| values.len() != len) {
+            return Err(error2(*span, &format!("Expected {len} values to match the first placeholder, got {}", values.len())));
+        }
+        let mut rows: Vec<Vec<TokenStream>> = vec![Vec::with_capacity(names.len()); len];
+        for (_, values) in columns {
+            for (row, value) in rows.iter_mut().zip(values) {
+                row.push(value);
+            }
+        }
+        Ok(Self { names, rows })
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses a comma-separated list of values.
+///
+/// Every value is a list of tokens up to the next comma. To use a value containing commas, it
+/// can be wrapped in curly brackets, which are removed.
+///
+/// # Arguments
+/// - `input`: The [`TokenStream`] to parse (e.g., the contents of square brackets).
+///
+/// # Returns
+/// The values in the list.
+///
+/// # Errors
+/// This function errors if any of the values is empty.
+pub fn parse_values(input: TokenStream) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let mut values: Vec<TokenStream> = Vec::new();
+    let mut iter = input.into_iter().peekable();
+    while iter.peek().is_some() {
+        // Collect everything up to the next comma
+        let mut value: Vec<TokenTree> = Vec::new();
+        let mut comma: Option<Span> = None;
+        for tt in iter.by_ref() {
+            if let TokenTree::Punct(p) = &tt
+                && p.as_char() == ','
+            {
+                comma = Some(p.span());
+                break;
+            }
+            value.push(tt);
+        }
+
+        // Unwrap it if it's in curly brackets
+        values.push(match value.as_slice() {
+            [] => return Err(error2(comma.unwrap_or_else(Span::call_site), "Expected a value before ','")),
+            [TokenTree::Group(group)] if group.delimiter() == Delimiter::Brace => group.stream(),
+            _ => value.into_iter().collect(),
+        });
+    }
+    Ok(values)
+}
+
+/// Replaces all placeholders in the given tokens with their values.
+///
+/// # Arguments
+/// - `tokens`: The [`TokenStream`] to replace the placeholders in.
+/// - `values`: A map from placeholder names to the values to replace them with.
+///
+/// # Returns
+/// The same tokens, but with all placeholders replaced.
+pub fn substitute(tokens: TokenStream, values: &HashMap<String, &TokenStream>) -> TokenStream {
+    let mut res = TokenStream::new();
+    for tt in tokens {
+        match tt {
+            TokenTree::Ident(ident) => match values.get(&ident.to_string()) {
+                Some(value) => res.extend((*value).clone()),
+                None => res.extend([TokenTree::Ident(ident)]),
+            },
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), substitute(group.stream(), values));
+                new.set_span(group.span());
+                res.extend([TokenTree::Group(new)]);
+            },
+            tt => res.extend([tt]),
+        }
+    }
+    res
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`duplicate()`](super::duplicate())-macro.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the substitution table.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] with the item repeated once for every row of the table, with its
+/// placeholders replaced by the values in that row.
+///
+/// # Errors
+/// This function may error if the substitution table is invalid.
+pub fn duplicate(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // NOTE: The error ends up in item position, so needs a semicolon to be a valid item
+    let Input { names, rows } = Input::parse(attr).map_err(item_error)?;
+
+    // Generate a copy for every row
+    let mut res = TokenStream::new();
+    for row in &rows {
+        let values: HashMap<String, &TokenStream> = names.iter().map(Ident::to_string).zip(row).collect();
+        res.extend(substitute(item.clone(), &values));
+    }
+    Ok(res)
+}
@@ -0,0 +1,192 @@
+//  DURATION LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for parsing human-readable durations at compile time.
+//
+
+use proc_macro2::{Literal, Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{parse_lit, subspan};
+
+
+/***** CONSTANTS *****/
+/// The units that may be used, together with the number of nanoseconds in them.
+///
+/// Longer names come before shorter names that are a prefix of them, so they're matched first.
+const UNITS: [(&str, u128); 10] = [
+    ("ns", 1),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ms", 1_000_000),
+    ("min", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("m", 60_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("d", 86_400_000_000_000),
+    ("w", 604_800_000_000_000),
+];
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the unit to emit the duration in (as a name and the number of nanoseconds in it)
+/// and the [`LitStr`] to parse the duration from.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<((&'static str, u128), LitStr), Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the optional unit first
+    let mut unit: (&'static str, u128) = UNITS[0];
+    let mut tree: Option<TokenTree> = iter.next();
+    if let Some(TokenTree::Ident(ident)) = &tree {
+        let name: String = ident.to_string();
+        unit = match UNITS.iter().find(|(n, _)| *n == name) {
+            Some(unit) => *unit,
+            None => return Err(error2(ident.span(), "Expected either a unit (`ns`, `us`, `ms`, `s`, `m`, `min`, `h`, `d` or `w`) or a string literal")),
+        };
+        tree = iter.next();
+    }
+
+    // Then the literal
+    let lit: LitStr = match tree.map(parse_lit).transpose()? {
+        Some(Lit::Str(lit)) => lit,
+        Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+        None => return Err(error2(Span::mixed_site(), "Expected a string literal")),
+    };
+    if let Some(tree) = iter.next() {
+        return Err(error2(tree.span(), "Expected nothing after the string literal"));
+    }
+    Ok((unit, lit))
+}
+
+
+
+/// Parses a duration from a string literal.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] to parse.
+///
+/// # Returns
+/// The duration, in nanoseconds.
+///
+/// # Errors
+/// This function errors if the literal does not contain a valid duration.
+pub fn parse_duration(lit: &LitStr) -> Result<u128, Diagnostic2> {
+    let value: String = lit.value();
+    let overflow = |start: usize, end: usize| error2(subspan(lit, start..end), "Duration is too large");
+
+    let mut total: u128 = 0;
+    let mut ncomponents: usize = 0;
+    let mut i: usize = 0;
+    while i < value.len() {
+        // Skip whitespace between components
+        let c: char = value[i..].chars().next().unwrap();
+        if c.is_whitespace() {
+            i += c.len_utf8();
+            continue;
+        }
+
+        // Parse the number
+        let start: usize = i;
+        let mut whole: u128 = 0;
+        let mut frac: Vec<u8> = Vec::new();
+        let mut seen_dot: bool = false;
+        while let Some(c) = value[i..].chars().next() {
+            match c {
+                '0'..='9' if !seen_dot => {
+                    let digit: u128 = (c as u8 - b'0') as u128;
+                    whole = whole.checked_mul(10).and_then(|w| w.checked_add(digit)).ok_or_else(|| overflow(start, i + 1))?;
+                },
+                '0'..='9' => frac.push(c as u8 - b'0'),
+                '.' if !seen_dot => seen_dot = true,
+                '_' => {},
+                _ => break,
+            }
+            i += c.len_utf8();
+        }
+        if i == start || value[start..i].chars().all(|c| c == '.' || c == '_') {
+            let len: usize = value[i..].chars().next().map(char::len_utf8).unwrap_or(0);
+            return Err(error2(subspan(lit, i..i + len), &format!("Expected a number at position {i} in duration")));
+        }
+
+        // Parse the unit
+        let ustart: usize = i;
+        let Some((name, ns)) =
+            UNITS.iter().find(|(name, _)| value[i..].starts_with(name) && !value[i + name.len()..].chars().next().is_some_and(char::is_alphabetic))
+        else {
+            let len: usize = match value[i..].chars().take_while(|c| c.is_alphabetic()).map(char::len_utf8).sum() {
+                0 => value[i..].chars().next().map(char::len_utf8).unwrap_or(0),
+                len => len,
+            };
+            return Err(error2(
+                subspan(lit, i..i + len),
+                &format!("Expected a unit (`ns`, `us`, `ms`, `s`, `m`, `min`, `h`, `d` or `w`) at position {i} in duration"),
+            ));
+        };
+        i += name.len();
+
+        // Compute the value of this component
+        let mut component: u128 = whole.checked_mul(*ns).ok_or_else(|| overflow(start, i))?;
+        let mut scale: u128 = *ns;
+        for digit in frac {
+            if !scale.is_multiple_of(10) {
+                return Err(error2(subspan(lit, start..ustart), "Duration is more precise than a nanosecond"));
+            }
+            scale /= 10;
+            component += digit as u128 * scale;
+        }
+        total = total.checked_add(component).ok_or_else(|| overflow(start, i))?;
+        ncomponents += 1;
+    }
+    if ncomponents == 0 {
+        return Err(error2(lit.span(), "Expected a duration (e.g., \"1h30m\"), got an empty string"));
+    }
+    Ok(total)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`duration_lit()`](super::duration_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to parse.
+///
+/// # Returns
+/// A new [`TokenStream`] with a `u64` literal encoding the duration in the chosen unit.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the literal does not
+/// contain a valid duration that can be represented in the chosen unit.
+pub fn duration_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let ((name, ns), lit) = parse_input(input)?;
+    let duration: u128 = parse_duration(&lit)?;
+
+    // Convert it to the chosen unit
+    if !duration.is_multiple_of(ns) {
+        return Err(error2(lit.span(), &format!("Duration is not a whole number of `{name}`")));
+    }
+    let Ok(duration) = u64::try_from(duration / ns) else {
+        return Err(error2(lit.span(), &format!("Duration in `{name}` does not fit in a `u64`")));
+    };
+
+    // Serialize it
+    let mut res = Literal::u64_suffixed(duration);
+    res.set_span(lit.span());
+    Ok(TokenStream::from(TokenTree::Literal(res)))
+}
@@ -0,0 +1,340 @@
+//  ENV LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for reading environment variables at compile time as typed literals,
+//!   instead of as the strings given by [`env!()`](::core::env!).
+//
+
+use proc_macro2::{Delimiter, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2, parse_string};
+use crate::lits::{IntType, SignedLit, int_lit, parse_signed_lit};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses an integer in the same notation as Rust's integer literals.
+///
+/// # Arguments
+/// - `value`: The text to parse, e.g., `-42`, `0xFF` or `1_000`.
+///
+/// # Returns
+/// Whether the integer is negative and its absolute value, or [`None`] if it's not an integer.
+fn parse_int(value: &str) -> Option<(bool, u128)> {
+    let (neg, value): (bool, &str) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (radix, digits): (u32, &str) = match value.get(..2) {
+        Some("0x" | "0X") => (16, &value[2..]),
+        Some("0o" | "0O") => (8, &value[2..]),
+        Some("0b" | "0B") => (2, &value[2..]),
+        _ => (10, value),
+    };
+    let digits: String = digits.chars().filter(|c| *c != '_').collect();
+    if digits.is_empty() || digits.starts_with(['+', '-']) {
+        return None;
+    }
+    u128::from_str_radix(&digits, radix).ok().map(|abs| (neg, abs))
+}
+
+
+
+/// Defines the kinds of literals that environment variables can be emitted as.
+#[derive(Clone, Copy)]
+pub enum Kind {
+    /// An integer literal of the given type, or an unsuffixed one if [`None`].
+    Int(Option<IntType>),
+    /// A 32-bit floating-point literal.
+    F32,
+    /// A 64-bit floating-point literal.
+    F64,
+    /// An unsuffixed floating-point literal.
+    Float,
+    /// A boolean literal.
+    Bool,
+    /// A character literal.
+    Char,
+    /// A string literal.
+    Str,
+}
+impl Kind {
+    /// Parses a Kind from the name of its type.
+    ///
+    /// # Arguments
+    /// - `ident`: The [`Ident`] with the name of the type (e.g., `usize` or `str`).
+    ///
+    /// # Returns
+    /// The parsed Kind.
+    ///
+    /// # Errors
+    /// This function errors if the identifier is not the name of a supported type.
+    pub fn parse(ident: &Ident) -> Result<Self, Diagnostic2> {
+        let name: String = ident.to_string();
+        match name.as_str() {
+            "f32" => Ok(Self::F32),
+            "f64" => Ok(Self::F64),
+            "bool" => Ok(Self::Bool),
+            "char" => Ok(Self::Char),
+            "str" => Ok(Self::Str),
+            name => IntType::from_suffix(name).map(|ty| Self::Int(Some(ty))).ok_or_else(|| {
+                Diagnostic2::error(ident.span(), format!("Unknown literal kind `{name}`"))
+                    .help("expected an integer type (e.g., `usize`), `f32`, `f64`, `bool`, `char` or `str`")
+            }),
+        }
+    }
+
+    /// Finds the Kind of a literal.
+    ///
+    /// # Arguments
+    /// - `lit`: The [`Lit`] to find the kind of.
+    ///
+    /// # Returns
+    /// The Kind of the literal, or [`None`] if it's not of a supported kind (e.g., a byte string).
+    pub fn of(lit: &Lit) -> Option<Self> {
+        match lit {
+            Lit::Int(lit) => match lit.suffix() {
+                "" => Some(Self::Int(None)),
+                suffix => IntType::from_suffix(suffix).map(|ty| Self::Int(Some(ty))),
+            },
+            Lit::Float(lit) => match lit.suffix() {
+                "" => Some(Self::Float),
+                "f32" => Some(Self::F32),
+                "f64" => Some(Self::F64),
+                _ => None,
+            },
+            Lit::Bool(_) => Some(Self::Bool),
+            Lit::Char(_) => Some(Self::Char),
+            Lit::Str(_) => Some(Self::Str),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the type of this Kind.
+    ///
+    /// # Returns
+    /// A string with the name of the type (e.g., `usize`).
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Int(Some(ty)) => ty.suffix(),
+            Self::Int(None) => "integer",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::Float => "float",
+            Self::Bool => "bool",
+            Self::Char => "char",
+            Self::Str => "str",
+        }
+    }
+
+    /// Parses a value as a literal of this Kind.
+    ///
+    /// # Arguments
+    /// - `value`: The value to parse. Leading and trailing whitespace is ignored for all kinds but
+    ///   [`Kind::Str`].
+    /// - `span`: The [`Span`] to give the literal.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] encoding the literal (preceded by a `-` for negative numbers).
+    ///
+    /// # Errors
+    /// This function errors with the reason if the value is not valid for this Kind.
+    pub fn lit(&self, value: &str, span: Span) -> Result<TokenStream, String> {
+        let trimmed: &str = value.trim();
+        let (neg, mut lit): (bool, Literal) = match self {
+            Self::Int(ty) => {
+                let (neg, abs): (bool, u128) = parse_int(trimmed).ok_or("it is not an integer")?;
+                if let Some(ty) = ty
+                    && !ty.fits(neg, abs)
+                {
+                    return Err(format!("it does not fit in a `{}`", ty.suffix()));
+                }
+                return Ok(int_lit(neg, abs, *ty, span));
+            },
+            Self::F32 => match trimmed.parse::<f32>() {
+                Ok(value) if value.is_finite() => (value.is_sign_negative(), Literal::f32_suffixed(value.abs())),
+                _ => return Err("it is not a finite floating-point number".into()),
+            },
+            Self::F64 => match trimmed.parse::<f64>() {
+                Ok(value) if value.is_finite() => (value.is_sign_negative(), Literal::f64_suffixed(value.abs())),
+                _ => return Err("it is not a finite floating-point number".into()),
+            },
+            Self::Float => match trimmed.parse::<f64>() {
+                Ok(value) if value.is_finite() => (value.is_sign_negative(), Literal::f64_unsuffixed(value.abs())),
+                _ => return Err("it is not a finite floating-point number".into()),
+            },
+            Self::Bool => match trimmed {
+                "true" | "false" => return Ok(TokenTree::Ident(Ident::new(trimmed, span)).into()),
+                _ => return Err("it is neither `true` nor `false`".into()),
+            },
+            Self::Char => {
+                let mut chars = trimmed.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => (false, Literal::character(c)),
+                    _ => return Err("it is not a single character".into()),
+                }
+            },
+            Self::Str => (false, Literal::string(value)),
+        };
+
+        // Negative floats are emitted like integers, i.e., as a `-` followed by the literal
+        let mut res = TokenStream::new();
+        if neg {
+            let mut minus = Punct::new('-', Spacing::Alone);
+            minus.set_span(span);
+            res.extend([TokenTree::Punct(minus)]);
+        }
+        lit.set_span(span);
+        res.extend([TokenTree::Literal(lit)]);
+        Ok(res)
+    }
+}
+
+
+
+/// Reads an environment variable and parses it as a literal of the given kind.
+///
+/// # Arguments
+/// - `name`: The name of the variable.
+/// - `span`: The [`Span`] of the name, which is where errors are reported and what the literal
+///   gets as span.
+/// - `kind`: The [`Kind`] of literal to parse it as.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the literal, or [`None`] if the variable is not set.
+///
+/// # Errors
+/// This function errors if the variable is not valid unicode or cannot be parsed as `kind`.
+pub fn read_var(name: &str, span: Span, kind: Kind) -> Result<Option<TokenStream>, Diagnostic2> {
+    let value: String = match std::env::var(name) {
+        Ok(value) => value,
+        Err(std::env::VarError::NotPresent) => return Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => return Err(error2(span, &format!("Environment variable `{name}` is not valid unicode"))),
+    };
+    kind.lit(&value, span).map(Some).map_err(|reason| {
+        Diagnostic2::error(span, format!("Failed to parse environment variable `{name}` as `{}` because {reason}", kind.name()))
+            .help(format!("its value is {value:?}"))
+    })
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the name of an environment variable, given as a string literal.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The name of the variable and its [`Span`].
+///
+/// # Errors
+/// This function errors if the next token is not a string literal.
+pub fn parse_name(iter: &mut impl Iterator<Item = TokenTree>) -> Result<(String, Span), Diagnostic2> {
+    let tt: Option<TokenTree> = iter.next();
+    let span: Span = tt.as_ref().map(TokenTree::span).unwrap_or_else(Span::call_site);
+    Ok((parse_string(tt, span, "the name of an environment variable")?, span))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`env_lit()`](super::env_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the name of the variable and the kind of literal.
+///
+/// # Returns
+/// A new [`TokenStream`] with the value of the variable as a literal.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the variable is not
+/// set or not a valid literal of the given kind.
+#[cfg(feature = "env_lit")]
+pub fn env_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the input
+    let (name, span): (String, Span) = parse_name(&mut iter)?;
+    match iter.next() {
+        Some(TokenTree::Ident(ident)) if ident == "as" => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected `as`")),
+        None => return Err(error2(Span::call_site(), "Expected `as` and the kind of literal (e.g., `usize` or `str`)")),
+    }
+    let kind: Kind = match iter.next() {
+        Some(TokenTree::Ident(ident)) => Kind::parse(&ident)?,
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => match group.stream().into_iter().next() {
+            Some(TokenTree::Ident(ident)) => Kind::parse(&ident)?,
+            _ => return Err(error2(group.span(), "Expected the kind of literal (e.g., `usize` or `str`)")),
+        },
+        Some(tt) => return Err(error2(tt.span(), "Expected the kind of literal (e.g., `usize` or `str`)")),
+        None => return Err(error2(Span::call_site(), "Expected the kind of literal (e.g., `usize` or `str`)")),
+    };
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+        None => {},
+    }
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the kind of literal"));
+    }
+
+    // Read the variable
+    read_var(&name, span, kind)?
+        .ok_or_else(|| Diagnostic2::error(span, format!("Environment variable `{name}` is not set")).help("use `env_or!()` to fall back to a default literal"))
+}
+
+
+
+/// Defines the implementation of the [`env_or()`](super::env_or())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the name of the variable and the default literal.
+///
+/// # Returns
+/// A new [`TokenStream`] with the value of the variable as a literal of the same kind as the
+/// default, or the default if the variable is not set.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the variable is set
+/// but not a valid literal of the kind of the default.
+#[cfg(feature = "env_or")]
+pub fn env_or(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the input
+    let (name, span): (String, Span) = parse_name(&mut iter)?;
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+        None => return Err(error2(Span::call_site(), "Expected ',' and a default literal")),
+    }
+    let default: SignedLit = parse_signed_lit(&mut iter)?;
+    let kind: Kind = match Kind::of(&default.lit) {
+        Some(Kind::Bool | Kind::Char | Kind::Str) if default.neg => return Err(error2(default.lit.span(), "Expected a numeric literal after '-'")),
+        Some(kind) => kind,
+        None => {
+            return Err(Diagnostic2::error(default.lit.span(), "Unsupported kind of default literal")
+                .help("expected an integer, floating-point, boolean, character or string literal"));
+        },
+    };
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+        None => {},
+    }
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the default literal"));
+    }
+
+    // Read the variable, falling back to the default
+    match read_var(&name, span, kind)? {
+        Some(lit) => Ok(lit),
+        None => default.into_tokens(),
+    }
+}
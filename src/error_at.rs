@@ -0,0 +1,51 @@
+//  ERROR AT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for emitting errors pointing to a given token.
+//
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, parse_anchor, parse_message};
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`error_at()`](super::error_at())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the anchor and the message.
+///
+/// # Returns
+/// A new [`TokenStream`] that produces the error when compiled.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn error_at(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the anchor
+    let span: Span = match iter.next() {
+        Some(tt) => parse_anchor(tt),
+        None => return Err(error2(Span::call_site(), "Expected a token to point the error to")),
+    };
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+        None => return Err(error2(Span::call_site(), "Expected ','")),
+    }
+
+    // Parse the message
+    let message: String = parse_message(iter.next(), Span::call_site())?;
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected nothing after the message")),
+        None => {},
+    }
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the message"));
+    }
+
+    // Emit it like `compile_error!()` would, but at the anchor
+    Ok(error2(span, &message).emit())
+}
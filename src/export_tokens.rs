@@ -0,0 +1,293 @@
+//  EXPORT TOKENS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for sharing the tokens of an item with (decl) macros elsewhere, even in
+//!   other crates.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::utils::Callback;
+
+
+/***** CONSTANTS *****/
+/// The prefix of the names of the macros generated by `#[export_tokens]`.
+const PREFIX: &str = "__export_tokens_";
+
+/// The keywords that may appear before an item's keyword.
+const QUALIFIERS: [&str; 6] = ["async", "auto", "const", "default", "extern", "unsafe"];
+/// The keywords of items that have a name.
+const KEYWORDS: [&str; 10] = ["const", "enum", "fn", "mod", "static", "struct", "trait", "type", "union", "use"];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds the name of an item.
+///
+/// # Arguments
+/// - `item`: The item to find the name of.
+///
+/// # Returns
+/// The name of the item, or [`None`] if it has none (e.g., `impl`-blocks or `const _`).
+fn item_name(item: &TokenStream) -> Option<Ident> {
+    let mut iter = item.clone().into_iter().peekable();
+
+    // Skip the attributes and the visibility
+    while let Some(TokenTree::Punct(p)) = iter.peek()
+        && p.as_char() == '#'
+    {
+        iter.next();
+        iter.next();
+    }
+    if matches!(iter.peek(), Some(TokenTree::Ident(ident)) if ident == "pub") {
+        iter.next();
+        if matches!(iter.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis) {
+            iter.next();
+        }
+    }
+
+    // Skip the qualifiers until we find the keyword of the item
+    loop {
+        match iter.next()? {
+            // Note that `const` is both a qualifier and a keyword
+            TokenTree::Ident(ident) if ident == "const" => match iter.peek()? {
+                TokenTree::Ident(next) if *next == "fn" || QUALIFIERS.contains(&next.to_string().as_str()) => continue,
+                _ => break,
+            },
+            TokenTree::Ident(ident) if KEYWORDS.contains(&ident.to_string().as_str()) => break,
+            TokenTree::Ident(ident) if QUALIFIERS.contains(&ident.to_string().as_str()) => continue,
+            // The ABI of `extern "C"`
+            TokenTree::Literal(_) => continue,
+            _ => return None,
+        }
+    }
+
+    // The name comes next, save for the `mut` in `static mut`
+    match iter.next()? {
+        TokenTree::Ident(ident) if ident == "mut" => match iter.next()? {
+            TokenTree::Ident(ident) => Some(ident),
+            _ => None,
+        },
+        TokenTree::Ident(ident) => Some(ident),
+        _ => None,
+    }
+}
+
+/// Generates the name of the macro that holds the tokens exported under the given name.
+///
+/// # Arguments
+/// - `name`: The name under which the tokens are exported.
+///
+/// # Returns
+/// An [`Ident`] with the name of the macro.
+fn macro_name(name: &Ident) -> Ident {
+    let name: String = name.to_string();
+    // NOTE: Always use the call site, as the macro must be visible to callers of `import_tokens!()`
+    Ident::new(&format!("{PREFIX}{}", name.strip_prefix("r#").unwrap_or(&name)), Span::call_site())
+}
+
+/// Parses the name to export the tokens of an item under.
+///
+/// # Arguments
+/// - `attr`: The arguments to the attribute, which may contain an explicit name.
+/// - `item`: The item to derive a name from if none is given explicitly.
+///
+/// # Returns
+/// The name to export the tokens under.
+///
+/// # Errors
+/// This function errors if the arguments were not a single identifier or nothing, or if they were
+/// nothing and the item has no name.
+fn parse_name(attr: TokenStream, item: &TokenStream) -> Result<Ident, Diagnostic2> {
+    let mut iter = attr.into_iter();
+    let name: Ident = match iter.next() {
+        Some(TokenTree::Ident(ident)) => ident,
+        Some(tt) => return Err(error2(tt.span(), "Expected a name to export the tokens under")),
+        None => match item_name(item) {
+            Some(name) => name,
+            None => return Err(error2(Span::call_site(), "Cannot derive a name from this item; give one explicitly (e.g., `#[export_tokens(Name)]`)")),
+        },
+    };
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the name"));
+    }
+    Ok(name)
+}
+
+/// Escapes the `$`-signs in a [`TokenStream`] such that it can be put in the body of a
+/// `macro_rules!`-macro.
+///
+/// # Arguments
+/// - `tokens`: The [`TokenStream`] to escape.
+/// - `dollar`: The name of the metavariable that will contain a `$` when the macro is called.
+///
+/// # Returns
+/// The same tokens, but with every `$` replaced by `$dollar`.
+fn escape_dollars(tokens: TokenStream, dollar: &Ident) -> TokenStream {
+    let mut res = TokenStream::new();
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), escape_dollars(group.stream(), dollar));
+                new_group.set_span(group.span());
+                res.extend([TokenTree::Group(new_group)]);
+            },
+            TokenTree::Punct(p) if p.as_char() == '$' => {
+                res.extend([TokenTree::Punct(Punct::new('$', Spacing::Alone)), TokenTree::Ident(dollar.clone())]);
+            },
+            tt => res.extend([tt]),
+        }
+    }
+    res
+}
+
+/// Generates `$($name:tt)*` for use in a `macro_rules!`-matcher or, if `kind` is [`None`],
+/// `$($name)*` for use in a transcriber.
+///
+/// # Arguments
+/// - `name`: The name of the metavariable.
+/// - `kind`: The fragment specifier of the metavariable, if generating a matcher.
+///
+/// # Returns
+/// A [`TokenStream`] with the repetition.
+fn repetition(name: &Ident, kind: Option<&str>) -> TokenStream {
+    let mut inner = TokenStream::from_iter([TokenTree::Punct(Punct::new('$', Spacing::Alone)), TokenTree::Ident(name.clone())]);
+    if let Some(kind) = kind {
+        inner.extend([TokenTree::Punct(Punct::new(':', Spacing::Alone)), TokenTree::Ident(Ident::new(kind, name.span()))]);
+    }
+    TokenStream::from_iter([
+        TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, inner)),
+        TokenTree::Punct(Punct::new('*', Spacing::Alone)),
+    ])
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`export_tokens()`](super::export_tokens())-attribute.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the optional name to export the tokens under.
+/// - `item`: Some [`TokenStream`] containing the item to export.
+///
+/// # Returns
+/// A new [`TokenStream`] with the item and a `macro_rules!`-macro that passes its tokens to a
+/// callback.
+///
+/// # Errors
+/// This function may error if no name was given and it could not be derived from the item.
+pub fn export_tokens(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Find the name to export the tokens under
+    let name: Ident = match parse_name(attr, &item) {
+        Ok(name) => name,
+        Err(err) => {
+            // NOTE: The error ends up in item position, so needs to be a valid item. We also emit
+            // the item to avoid follow-up errors for it missing.
+            return Err(item_error(err).with_tokens(item));
+        },
+    };
+
+    // Build the matcher, i.e., `$dollar:tt [$($callback:tt)*] [$($prefix:tt)*]`
+    let span: Span = Span::call_site();
+    let dollar = Ident::new("dollar", span);
+    let callback = Ident::new("callback", span);
+    let prefix = Ident::new("prefix", span);
+    let matcher = TokenStream::from_iter([
+        TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+        TokenTree::Ident(dollar.clone()),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("tt", span)),
+        TokenTree::Group(Group::new(Delimiter::Bracket, repetition(&callback, Some("tt")))),
+        TokenTree::Group(Group::new(Delimiter::Bracket, repetition(&prefix, Some("tt")))),
+    ]);
+
+    // Build the transcriber, i.e., `$($callback)* { $($prefix)* <item> }` (the callback includes its `!`)
+    let mut transcriber: TokenStream = repetition(&callback, None);
+    let mut args: TokenStream = repetition(&prefix, None);
+    args.extend(escape_dollars(item.clone(), &dollar));
+    transcriber.extend([TokenTree::Group(Group::new(Delimiter::Brace, args))]);
+
+    // Build the macro
+    let mut res: TokenStream = item;
+    res.extend([
+        TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Bracket,
+            TokenStream::from_iter([
+                TokenTree::Ident(Ident::new("doc", span)),
+                TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::from(TokenTree::Ident(Ident::new("hidden", span))))),
+            ]),
+        )),
+        TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Bracket, TokenStream::from(TokenTree::Ident(Ident::new("macro_export", span))))),
+        TokenTree::Ident(Ident::new("macro_rules", span)),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Ident(macro_name(&name)),
+        TokenTree::Group(Group::new(
+            Delimiter::Brace,
+            TokenStream::from_iter([
+                TokenTree::Group(Group::new(Delimiter::Parenthesis, matcher)),
+                TokenTree::Punct(Punct::new('=', Spacing::Joint)),
+                TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+                TokenTree::Group(Group::new(Delimiter::Brace, transcriber)),
+                TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+            ]),
+        )),
+    ]);
+    Ok(res)
+}
+
+/// Defines the implementation of the [`import_tokens()`](super::import_tokens())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the path to the exported tokens and a callback.
+///
+/// # Returns
+/// A new [`TokenStream`] calling the macro generated by `#[export_tokens]` with the callback.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn import_tokens(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Parse the path up to the comma
+    let mut iter = input.into_iter();
+    let mut path: Vec<TokenTree> = Vec::new();
+    loop {
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => break,
+            Some(tt @ (TokenTree::Ident(_) | TokenTree::Punct(_))) => path.push(tt),
+            // Allow paths given as `$item:path`
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => path.extend(group.stream()),
+            Some(tt) => return Err(error2(tt.span(), "Expected a path to exported tokens")),
+            None => return Err(error2(Span::call_site(), "Expected a path to exported tokens, followed by ',' and a callback")),
+        }
+    }
+    let name: Ident = match path.pop() {
+        Some(TokenTree::Ident(name)) => name,
+        Some(tt) => return Err(error2(tt.span(), "Expected a path ending in the name of exported tokens")),
+        None => return Err(error2(Span::call_site(), "Expected a path to exported tokens before ','")),
+    };
+    let (callback, prefix): (TokenStream, TokenStream) = Callback::parse(&mut iter)?.into_parts();
+
+    // Call the generated macro
+    let mut res = TokenStream::from_iter(path);
+    res.extend([
+        TokenTree::Ident(macro_name(&name)),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Brace,
+            TokenStream::from_iter([
+                TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+                TokenTree::Group(Group::new(Delimiter::Bracket, callback)),
+                TokenTree::Group(Group::new(Delimiter::Bracket, prefix)),
+            ]),
+        )),
+    ]);
+    Ok(res)
+}
@@ -0,0 +1,132 @@
+//  FEATURE GATED.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides an attribute for gating items behind Cargo features, including in the docs.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::generics::split_top_level;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates an attribute (i.e., `#[...]`).
+///
+/// # Arguments
+/// - `span`: The span of the attribute.
+/// - `contents`: The tokens in the attribute.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the attribute.
+fn attribute(span: Span, contents: TokenStream) -> TokenStream {
+    let mut group = Group::new(Delimiter::Bracket, contents);
+    group.set_span(span);
+    TokenStream::from_iter([TokenTree::Punct(Punct::new('#', Spacing::Alone)), TokenTree::Group(group)])
+}
+
+/// Generates a call-like predicate (e.g., `cfg(...)`).
+///
+/// # Arguments
+/// - `name`: The name of the predicate.
+/// - `span`: The span of the predicate.
+/// - `args`: The arguments of the predicate.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the predicate.
+fn call(name: &str, span: Span, args: TokenStream) -> TokenStream {
+    let mut group = Group::new(Delimiter::Parenthesis, args);
+    group.set_span(span);
+    TokenStream::from_iter([TokenTree::Ident(Ident::new(name, span)), TokenTree::Group(group)])
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses a predicate over features into a `cfg`-predicate.
+///
+/// Predicates are either a string literal with the name of a feature, or `all(...)`, `any(...)`
+/// or `not(...)` of other predicates.
+///
+/// # Arguments
+/// - `tokens`: The tokens of the predicate.
+/// - `span`: A span to report errors at if the predicate is empty.
+///
+/// # Returns
+/// The equivalent `cfg`-predicate (e.g., `"foo"` becomes `feature = "foo"`).
+///
+/// # Errors
+/// This function errors if the tokens are not a valid predicate.
+fn parse_pred(tokens: Vec<TokenTree>, span: Span) -> Result<TokenStream, Diagnostic2> {
+    match tokens.as_slice() {
+        // Unwrap predicates given as e.g. `$feature:literal`
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::None => parse_pred(group.stream().into_iter().collect(), span),
+        [TokenTree::Literal(lit)] => {
+            let value: String = lit.to_string();
+            match value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+                Some("") => Err(error2(lit.span(), "Expected a non-empty feature name")),
+                Some(_) => Ok(TokenStream::from_iter([
+                    TokenTree::Ident(Ident::new("feature", lit.span())),
+                    TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+                    TokenTree::Literal(lit.clone()),
+                ])),
+                None => Err(error2(lit.span(), "Expected the name of a feature as a string literal")),
+            }
+        },
+        [TokenTree::Ident(ident), TokenTree::Group(group)] if group.delimiter() == Delimiter::Parenthesis => {
+            let name: String = ident.to_string();
+            if !matches!(name.as_str(), "all" | "any" | "not") {
+                return Err(error2(ident.span(), &format!("Unknown combinator `{name}`; expected `all`, `any` or `not`")));
+            }
+
+            // Parse the nested predicates
+            let preds: Vec<Vec<TokenTree>> = split_top_level(group.stream(), ',').into_iter().filter(|pred| !pred.is_empty()).collect();
+            if name == "not" && preds.len() != 1 {
+                return Err(error2(group.span(), "Expected exactly one predicate in `not(...)`"));
+            }
+            let mut args = TokenStream::new();
+            for (i, pred) in preds.into_iter().enumerate() {
+                if i > 0 {
+                    args.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+                }
+                args.extend(parse_pred(pred, group.span())?);
+            }
+            Ok(call(&name, ident.span(), args))
+        },
+        [tt, ..] => Err(error2(tt.span(), "Expected either the name of a feature as a string literal, or `all(...)`, `any(...)` or `not(...)`")),
+        [] => Err(error2(span, "Expected the name of a feature as a string literal (e.g., `#[feature_gated(\"foo\")]`)")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`feature_gated()`](super::feature_gated())-attribute.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the feature(s) to gate the item behind.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// The `item`, prefixed with a `#[cfg(...)]` and a `#[cfg_attr(docsrs, doc(cfg(...)))]`.
+///
+/// # Errors
+/// This function may error if the attribute's arguments are invalid.
+pub fn feature_gated(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // NOTE: The error ends up in item position, so needs to be a valid item
+    let pred: TokenStream = parse_pred(attr.into_iter().collect(), Span::call_site()).map_err(item_error)?;
+
+    // Generate `#[cfg(...)]` and `#[cfg_attr(docsrs, doc(cfg(...)))]`
+    let span: Span = Span::call_site();
+    let mut res: TokenStream = attribute(span, call("cfg", span, pred.clone()));
+    let mut args = TokenStream::from_iter([TokenTree::Ident(Ident::new("docsrs", span)), TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+    args.extend(call("doc", span, call("cfg", span, pred)));
+    res.extend(attribute(span, call("cfg_attr", span, args)));
+    res.extend(item);
+    Ok(res)
+}
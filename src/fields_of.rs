@@ -0,0 +1,70 @@
+//  FIELDS OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for iterating over the fields of a struct.
+//
+
+use proc_macro2::{Delimiter, Ident, Literal, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::items::{Field, ItemHead, Output, Tail, fill_template, parse_fields, parse_head, part_list, split_output};
+use crate::utils::part;
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`fields_of()`](super::fields_of())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the struct and either a callback or a template.
+///
+/// # Returns
+/// A new [`TokenStream`] with either a call to the callback with the fields of the struct, or the
+/// template with its repetitions expanded once for every field.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn fields_of(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (item, output): (TokenStream, Option<Output>) = split_output(input)?;
+    let span: Span = item.clone().into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+    let Some(output) = output else {
+        return Err(error2(span, "Expected '=>' and either a callback macro or a template in curly brackets after the struct"));
+    };
+    let ItemHead { keyword, name, rest, .. } = parse_head(item)?;
+    if !matches!(keyword.to_string().as_str(), "struct" | "union") {
+        return Err(error2(span, &format!("Expected a struct or a union, found `{keyword}`")));
+    }
+    let Tail { generics, signature, where_, body } = Tail::parse(rest, span)?;
+
+    // Find the fields in either the body or the signature
+    let mut sig = signature.into_iter();
+    let (kind, fields): (&str, Vec<Field>) = match (sig.next(), sig.next(), body.into_iter().next()) {
+        (None, _, Some(TokenTree::Group(group))) => ("named", parse_fields(&group)?),
+        (Some(TokenTree::Group(group)), None, _) if group.delimiter() == Delimiter::Parenthesis => ("tuple", parse_fields(&group)?),
+        (None, _, _) => ("unit", Vec::new()),
+        (Some(tt), _, _) => return Err(error2(tt.span(), "Expected the fields of the struct")),
+    };
+
+    // Generate the parts of every field. Fields of tuple structs are named by their index.
+    let fields: Vec<Vec<(&str, TokenStream)>> = fields
+        .into_iter()
+        .enumerate()
+        .map(|(i, Field { attrs, vis, name, ty })| {
+            let index = TokenStream::from(TokenTree::Literal(Literal::usize_unsuffixed(i)));
+            let name: TokenStream = name.map(|name| TokenStream::from(TokenTree::Ident(name))).unwrap_or_else(|| index.clone());
+            vec![("attrs", attrs), ("vis", vis), ("name", name), ("ty", ty), ("index", index)]
+        })
+        .collect();
+    match output {
+        Output::Callback(callback) => {
+            let mut args = TokenStream::new();
+            args.extend(part("name", name.map(|name| TokenStream::from(TokenTree::Ident(name))).unwrap_or_default()));
+            args.extend(part("generics", generics));
+            args.extend(part("where", where_));
+            args.extend(part("kind", TokenStream::from(TokenTree::Ident(Ident::new(kind, Span::call_site())))));
+            args.extend(part("fields", part_list(fields)));
+            Ok(callback.call_item(args))
+        },
+        Output::Template(template) => fill_template(template, &fields, None),
+    }
+}
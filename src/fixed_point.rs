@@ -0,0 +1,270 @@
+//  FIXED POINT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for converting numeric literals to fixed-point integer literals.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::{Lit, LitInt};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, SignedLit, int_lit, parse_lit, parse_signed_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Defines the supported ways to round the scaled value to an integer.
+#[derive(Clone, Copy)]
+enum Rounding {
+    /// Round to the nearest integer, with ties away from zero.
+    Nearest,
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round towards zero.
+    Trunc,
+}
+impl Rounding {
+    /// Applies the rounding mode to a value.
+    ///
+    /// # Arguments
+    /// - `value`: The value to round.
+    ///
+    /// # Returns
+    /// The rounded value.
+    fn apply(self, value: f64) -> f64 {
+        match self {
+            Self::Nearest => value.round(),
+            Self::Floor => value.floor(),
+            Self::Ceil => value.ceil(),
+            Self::Trunc => value.trunc(),
+        }
+    }
+}
+
+
+
+/// Defines a parsed Q-format.
+struct Format {
+    /// Whether the format is signed.
+    signed: bool,
+    /// The number of fractional bits.
+    frac:   u32,
+    /// The integer type holding the format.
+    ty:     IntType,
+}
+impl Format {
+    /// Parses a Q-format (e.g., `Q15`, `Q1.14` or `UQ8.8`).
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Format.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input was not a valid Q-format.
+    fn parse(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Self, Diagnostic2> {
+        let ident = match iter.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tt) => return Err(error2(tt.span(), "Expected a Q-format (e.g., `Q15` or `Q1.14`)")),
+            None => return Err(error2(Span::mixed_site(), "Expected a Q-format (e.g., `Q15` or `Q1.14`)")),
+        };
+        let sident: String = ident.to_string();
+        let (signed, first): (bool, &str) = if let Some(first) = sident.strip_prefix("UQ") {
+            (false, first)
+        } else if let Some(first) = sident.strip_prefix('Q') {
+            (true, first)
+        } else {
+            return Err(error2(ident.span(), "Expected a Q-format (e.g., `Q15` or `Q1.14`)"));
+        };
+        let first: u32 = first.parse().map_err(|_| error2(ident.span(), "Expected a Q-format (e.g., `Q15` or `Q1.14`)"))?;
+
+        // Parse the optional fractional bits
+        let mut span: Span = ident.span();
+        let dotted: bool = matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '.');
+        let (int, frac): (u32, u32) = if dotted {
+            iter.next();
+            let lit: LitInt = match iter.next().map(parse_lit).transpose()? {
+                Some(Lit::Int(lit)) => lit,
+                Some(lit) => return Err(error2(lit.span(), "Expected the number of fractional bits as an integer literal")),
+                None => return Err(error2(span, "Expected the number of fractional bits as an integer literal")),
+            };
+            span = span.join(lit.span()).unwrap_or(span);
+            (first, lit.base10_parse().map_err(|_| error2(lit.span(), "Number of fractional bits is too large"))?)
+        } else {
+            (0, first)
+        };
+
+        // Find the matching type
+        let bits: u32 = int.saturating_add(frac).saturating_add(signed as u32);
+        let ty: IntType = match (signed, bits) {
+            (true, 8) => IntType::I8,
+            (true, 16) => IntType::I16,
+            (true, 32) => IntType::I32,
+            (true, 64) => IntType::I64,
+            (true, 128) => IntType::I128,
+            (false, 8) => IntType::U8,
+            (false, 16) => IntType::U16,
+            (false, 32) => IntType::U32,
+            (false, 64) => IntType::U64,
+            (false, 128) => IntType::U128,
+            _ => {
+                let name: String = if dotted { format!("{sident}.{frac}") } else { sident };
+                return Err(error2(span, &format!("Q-format `{name}` has {bits} bits, which is not the width of an integer type")));
+            },
+        };
+        Ok(Self { signed, frac, ty })
+    }
+}
+
+
+
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The value to convert.
+    value:  f64,
+    /// The span of the value.
+    span:   Span,
+    /// The format to convert to.
+    format: Format,
+    /// How to round the value.
+    round:  Rounding,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the value
+        let SignedLit { neg, lit } = parse_signed_lit(&mut iter)?;
+        let span: Span = lit.span();
+        let value: f64 = match lit {
+            Lit::Float(lit) => lit.base10_parse().map_err(|_| error2(span, "Invalid floating-point literal"))?,
+            Lit::Int(lit) => lit.base10_parse::<u128>().map_err(|_| error2(span, "Integer literal is too large"))? as f64,
+            _ => return Err(error2(span, "Expected a floating-point or integer literal")),
+        };
+        let value: f64 = if neg { -value } else { value };
+
+        // Parse the format
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+            None => return Err(error2(span, "Expected ',' and then a Q-format")),
+        }
+        let format = Format::parse(&mut iter)?;
+
+        // Parse the optional rounding mode
+        let mut round = Rounding::Nearest;
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing after the Q-format")),
+            None => return Ok(Self { value, span, format, round }),
+        }
+        if iter.peek().is_some() {
+            round = Self::parse_round(&mut iter)?;
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing after the rounding mode")),
+                None => {},
+            }
+        }
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the rounding mode"));
+        }
+        Ok(Self { value, span, format, round })
+    }
+
+    /// Parses a `round = ...`-pair.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed [`Rounding`].
+    ///
+    /// # Errors
+    /// This function errors if the head of the input did not encode a valid rounding mode.
+    fn parse_round(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Rounding, Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "round" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected 'round'")),
+            None => return Err(error2(Span::mixed_site(), "Expected 'round'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+            None => return Err(error2(Span::mixed_site(), "Expected '='")),
+        }
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "nearest" => Ok(Rounding::Nearest),
+            Some(TokenTree::Ident(ident)) if ident == "floor" => Ok(Rounding::Floor),
+            Some(TokenTree::Ident(ident)) if ident == "ceil" => Ok(Rounding::Ceil),
+            Some(TokenTree::Ident(ident)) if ident == "trunc" => Ok(Rounding::Trunc),
+            Some(tt) => Err(error2(tt.span(), "Expected one of 'nearest', 'floor', 'ceil' or 'trunc'")),
+            None => Err(error2(Span::mixed_site(), "Expected one of 'nearest', 'floor', 'ceil' or 'trunc'")),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`fixed_point()`](super::fixed_point())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the value and the format to convert it to.
+///
+/// # Returns
+/// A new [`TokenStream`] with a suffixed integer literal encoding the value in the format.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the value is out of
+/// range for the format.
+pub fn fixed_point(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { value, span, format: Format { signed, frac, ty }, round } = Input::parse(input)?;
+
+    // Scale the value by the fractional bits. This is exact, as it's multiplication by a power of
+    // two (barring overflow to infinity, which the range check catches).
+    let scaled: f64 = round.apply(value * 2f64.powi(frac as i32));
+
+    // Check it fits
+    let bits: i32 = ty.bits() as i32;
+    let (min, max): (f64, f64) = if signed { (-(2f64.powi(bits - 1)), 2f64.powi(bits - 1)) } else { (0.0, 2f64.powi(bits)) };
+    if scaled < min || scaled >= max {
+        let scale: f64 = 2f64.powi(frac as i32);
+
+        // If the value itself is in range, it's been rounded up past the largest value (the minimum
+        // is an integer, so it can't be rounded past that)
+        if value * scale >= min && value * scale < max {
+            return Err(error2(
+                span,
+                &format!(
+                    "Value {value} rounds past the maximum of the Q-format ({}); use `round = floor` or `round = trunc` to round it down instead",
+                    (max - 1.0) / scale
+                ),
+            ));
+        }
+        return Err(error2(
+            span,
+            &format!("Value {value} is out of range for the Q-format (which represents values from {} up to but excluding {})", min / scale, max / scale),
+        ));
+    }
+
+    // Emit it, noting that the value is now an integer within range of a u128
+    Ok(int_lit(scaled < 0.0, scaled.abs() as u128, Some(ty), Span::call_site()))
+}
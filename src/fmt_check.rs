@@ -0,0 +1,341 @@
+//  FMT CHECK.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for validating format strings against their arguments at compile time.
+//
+
+use std::collections::HashMap;
+
+use proc_macro2::{Ident, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::parse_lit;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether the given token is a `=` that is joint with a following `>`.
+///
+/// # Arguments
+/// - `tokens`: The list of tokens to check in.
+/// - `i`: The index of the token to check.
+///
+/// # Returns
+/// True if `tokens[i]` and `tokens[i + 1]` form a `=>`, or false otherwise.
+fn is_arrow(tokens: &[TokenTree], i: usize) -> bool {
+    matches!(
+        (tokens.get(i), tokens.get(i + 1)),
+        (Some(TokenTree::Punct(eq)), Some(TokenTree::Punct(gt))) if eq.as_char() == '=' && eq.spacing() == Spacing::Joint && gt.as_char() == '>'
+    )
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single argument given to the macro.
+struct Arg {
+    /// The name of the argument, if it's a named one.
+    name: Option<Ident>,
+    /// The span of the argument, for error reporting.
+    span: Span,
+}
+
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The format string to check.
+    fmt:    LitStr,
+    /// The arguments to check the format string against.
+    args:   Vec<Arg>,
+    /// The tokens to emit if the check succeeds, if any.
+    tokens: Option<TokenStream>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let tokens: Vec<TokenTree> = input.into_iter().collect();
+
+        // Parse the format string first
+        let fmt: LitStr = match tokens.first().cloned().map(parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected a format string literal")),
+        };
+
+        // Then parse the arguments
+        let mut args: Vec<Arg> = Vec::new();
+        let mut i: usize = 1;
+        loop {
+            match tokens.get(i) {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {
+                    // Find the end of the argument
+                    let start: usize = i + 1;
+                    i = start;
+                    while i < tokens.len() && !matches!(&tokens[i], TokenTree::Punct(p) if p.as_char() == ',') && !is_arrow(&tokens, i) {
+                        i += 1;
+                    }
+                    if let Some(arg) = Self::parse_arg(&tokens[start..i])? {
+                        if arg.name.is_none() && args.iter().any(|a| a.name.is_some()) {
+                            return Err(error2(arg.span, "Positional arguments cannot follow named arguments"));
+                        }
+                        if let Some(name) = &arg.name
+                            && args.iter().any(|a| a.name.as_ref() == Some(name))
+                        {
+                            return Err(error2(name.span(), &format!("Duplicate argument named `{name}`")));
+                        }
+                        args.push(arg);
+                    }
+                },
+                Some(_) if is_arrow(&tokens, i) => return Ok(Self { fmt, args, tokens: Some(tokens.into_iter().skip(i + 2).collect()) }),
+                Some(tt) => return Err(error2(tt.span(), "Expected either ',', '=>' or nothing")),
+                None => return Ok(Self { fmt, args, tokens: None }),
+            }
+        }
+    }
+
+    /// Parses a single argument.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens making up the argument.
+    ///
+    /// # Returns
+    /// The parsed [`Arg`], or [`None`] if there was no argument (e.g., after a trailing comma).
+    ///
+    /// # Errors
+    /// This function errors if the argument was invalid.
+    fn parse_arg(tokens: &[TokenTree]) -> Result<Option<Arg>, Diagnostic2> {
+        let Some(first) = tokens.first() else { return Ok(None) };
+        let span: Span = first.span();
+
+        // See if it's named
+        if let (TokenTree::Ident(name), Some(TokenTree::Punct(p))) = (first, tokens.get(1))
+            && p.as_char() == '='
+            && p.spacing() == Spacing::Alone
+        {
+            if tokens.len() < 3 {
+                return Err(error2(p.span(), "Expected a value after '='"));
+            }
+            return Ok(Some(Arg { name: Some(name.clone()), span }));
+        }
+        Ok(Some(Arg { name: None, span }))
+    }
+}
+
+
+
+/// Defines a reference to an argument from the format string.
+enum ArgRef {
+    /// The next positional argument (i.e., `{}`).
+    Next,
+    /// A specific positional argument (e.g., `{0}`).
+    Index(usize),
+    /// A named argument (e.g., `{foo}`).
+    Name(String),
+}
+
+/// Parses a reference to an argument from the format string.
+///
+/// # Arguments
+/// - `chars`: The characters of the format string.
+/// - `i`: The position in the characters to start parsing. Will be updated to point after the
+///   reference.
+///
+/// # Returns
+/// The parsed [`ArgRef`], or [`None`] if there was no reference at `i`.
+fn parse_arg_ref(chars: &[char], i: &mut usize) -> Option<ArgRef> {
+    let start: usize = *i;
+    if chars.get(*i).is_some_and(char::is_ascii_digit) {
+        while chars.get(*i).is_some_and(char::is_ascii_digit) {
+            *i += 1;
+        }
+        let index: String = chars[start..*i].iter().collect();
+        // Only way this fails is if it's too large, in which case it will never match anyway
+        return Some(ArgRef::Index(index.parse().unwrap_or(usize::MAX)));
+    }
+    if chars.get(*i).is_some_and(|c| c.is_alphabetic() || *c == '_') {
+        while chars.get(*i).is_some_and(|c| c.is_alphanumeric() || *c == '_') {
+            *i += 1;
+        }
+        return Some(ArgRef::Name(chars[start..*i].iter().collect()));
+    }
+    None
+}
+
+/// Parses a format string into the list of arguments it references.
+///
+/// # Arguments
+/// - `fmt`: The format string to parse.
+///
+/// # Returns
+/// A list of [`ArgRef`]s referenced by the string, in order.
+///
+/// # Errors
+/// This function errors with a message if the format string is not valid.
+fn parse_fmt(fmt: &str) -> Result<Vec<ArgRef>, String> {
+    let chars: Vec<char> = fmt.chars().collect();
+    let mut refs: Vec<ArgRef> = Vec::new();
+    let mut i: usize = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '{' if chars.get(i + 1) == Some(&'{') => i += 2,
+            '}' if chars.get(i + 1) == Some(&'}') => i += 2,
+            '}' => return Err(format!("Unmatched '}}' at position {i} in format string (use '}}}}' to escape it)")),
+            '{' => {
+                let open: usize = i;
+                i += 1;
+
+                // Parse the argument
+                let arg: ArgRef = parse_arg_ref(&chars, &mut i).unwrap_or(ArgRef::Next);
+                let mut spec_refs: Vec<ArgRef> = Vec::new();
+
+                // Parse the format spec
+                if chars.get(i) == Some(&':') {
+                    i += 1;
+                    // Fill & alignment
+                    if chars.get(i + 1).is_some_and(|c| "<^>".contains(*c)) && chars.get(i).is_some_and(|c| *c != '}') {
+                        i += 2;
+                    } else if chars.get(i).is_some_and(|c| "<^>".contains(*c)) {
+                        i += 1;
+                    }
+                    // Sign, alternate & zero-padding flags
+                    if chars.get(i).is_some_and(|c| *c == '+' || *c == '-') {
+                        i += 1;
+                    }
+                    if chars.get(i) == Some(&'#') {
+                        i += 1;
+                    }
+                    if chars.get(i) == Some(&'0') && chars.get(i + 1) != Some(&'$') {
+                        i += 1;
+                    }
+                    // Width
+                    let before: usize = i;
+                    match parse_arg_ref(&chars, &mut i) {
+                        Some(arg) if chars.get(i) == Some(&'$') => {
+                            spec_refs.push(arg);
+                            i += 1;
+                        },
+                        Some(ArgRef::Index(_)) => {},
+                        // It's actually the type (e.g., `{:x}`), so rewind
+                        _ => i = before,
+                    }
+                    // Precision
+                    if chars.get(i) == Some(&'.') {
+                        i += 1;
+                        if chars.get(i) == Some(&'*') {
+                            // `.*` takes the precision from the next positional argument, before the value itself
+                            spec_refs.insert(0, ArgRef::Next);
+                            i += 1;
+                        } else {
+                            match parse_arg_ref(&chars, &mut i) {
+                                Some(arg) if chars.get(i) == Some(&'$') => {
+                                    spec_refs.push(arg);
+                                    i += 1;
+                                },
+                                Some(ArgRef::Index(_)) => {},
+                                _ => return Err(format!("Expected a precision after '.' at position {i} in format string")),
+                            }
+                        }
+                    }
+                    // Type
+                    let start: usize = i;
+                    while chars.get(i).is_some_and(|c| *c != '}') {
+                        i += 1;
+                    }
+                    let ty: String = chars[start..i].iter().collect();
+                    if !matches!(ty.as_str(), "" | "?" | "x?" | "X?" | "x" | "X" | "o" | "b" | "e" | "E" | "p") {
+                        return Err(format!("Unknown format trait `{ty}` at position {start} in format string"));
+                    }
+                }
+
+                // Expect the closing bracket
+                if chars.get(i) != Some(&'}') {
+                    return Err(format!("Unmatched '{{' at position {open} in format string (use '{{{{' to escape it)"));
+                }
+                i += 1;
+
+                // Note that the spec references come before the value itself
+                refs.extend(spec_refs);
+                refs.push(arg);
+            },
+            _ => i += 1,
+        }
+    }
+    Ok(refs)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`fmt_check()`](super::fmt_check())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the format string and the arguments to check it
+///   against.
+///
+/// # Returns
+/// The tokens after the `=>` if given, or else the format string itself.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the format string
+/// does not match the arguments.
+pub fn fmt_check(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { fmt, args, tokens } = Input::parse(input)?;
+    let span: Span = fmt.span();
+
+    // Parse the format string
+    let refs: Vec<ArgRef> = parse_fmt(&fmt.value()).map_err(|msg| error2(span, &msg))?;
+
+    // Check every reference
+    let names: HashMap<String, usize> = args.iter().enumerate().filter_map(|(i, arg)| arg.name.as_ref().map(|name| (name.to_string(), i))).collect();
+    let mut used: Vec<bool> = vec![false; args.len()];
+    let mut next: usize = 0;
+    for arg in refs {
+        let i: usize = match arg {
+            ArgRef::Next => {
+                next += 1;
+                next - 1
+            },
+            ArgRef::Index(i) => i,
+            ArgRef::Name(name) => match names.get(&name) {
+                Some(i) => *i,
+                None => return Err(error2(span, &format!("Format string references argument `{name}`, but no such argument was given"))),
+            },
+        };
+        if i >= args.len() {
+            return Err(error2(
+                span,
+                &format!(
+                    "Format string references argument {i}, but only {} argument{} {} given",
+                    args.len(),
+                    if args.len() == 1 { "" } else { "s" },
+                    if args.len() == 1 { "was" } else { "were" }
+                ),
+            ));
+        }
+        used[i] = true;
+    }
+
+    // Check that every argument is used
+    if let Some(i) = used.iter().position(|u| !u) {
+        return Err(error2(args[i].span, "Argument is never used by the format string"));
+    }
+
+    // Pass the tokens through
+    match tokens {
+        Some(tokens) => Ok(tokens),
+        None => Ok(TokenStream::from(TokenTree::Literal(fmt.token()))),
+    }
+}
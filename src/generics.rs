@@ -0,0 +1,346 @@
+//  GENERICS.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines helpers for parsing generic parameters and where-clauses, shared by the macros that
+//!   operate on them.
+//
+
+use proc_macro2::{Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether the given token is a [`Punct`] with the given character.
+///
+/// # Arguments
+/// - `tt`: The token to check.
+/// - `c`: The character to check for.
+///
+/// # Returns
+/// True if `tt` is `c`, or false otherwise.
+#[inline]
+fn is_punct(tt: &TokenTree, c: char) -> bool { matches!(tt, TokenTree::Punct(p) if p.as_char() == c) }
+
+/// Splits the given tokens on the given character, but only where it does not appear between
+/// angle brackets.
+///
+/// The `>` of `->` is not counted as a closing angle bracket.
+///
+/// # Arguments
+/// - `tokens`: The tokens to split.
+/// - `sep`: The character to split on.
+///
+/// # Returns
+/// The tokens in between the separators. Empty chunks (e.g., after a trailing separator) are
+/// omitted.
+pub fn split_top_level(tokens: TokenStream, sep: char) -> Vec<Vec<TokenTree>> {
+    let mut chunks: Vec<Vec<TokenTree>> = Vec::new();
+    let mut chunk: Vec<TokenTree> = Vec::new();
+    let mut depth: usize = 0;
+    let mut prev: Option<Punct> = None;
+    for tt in tokens {
+        if let TokenTree::Punct(p) = &tt {
+            let arrow: bool = prev.as_ref().is_some_and(|prev| prev.as_char() == '-' && prev.spacing() == Spacing::Joint);
+            match p.as_char() {
+                c if c == sep && depth == 0 => {
+                    if !chunk.is_empty() {
+                        chunks.push(std::mem::take(&mut chunk));
+                    }
+                    prev = None;
+                    continue;
+                },
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                _ => {},
+            }
+            prev = Some(p.clone());
+        } else {
+            prev = None;
+        }
+        chunk.push(tt);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the kinds of generic parameters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParamKind {
+    /// A lifetime, e.g., `'a: 'b`.
+    Lifetime,
+    /// A type, e.g., `T: Clone = u8`.
+    Type,
+    /// A constant, e.g., `const N: usize = 42`.
+    Const,
+}
+
+/// Defines a single generic parameter.
+#[derive(Clone)]
+pub struct Param {
+    /// Any attributes on the parameter (e.g., `#[cfg(...)]`).
+    pub attrs:  TokenStream,
+    /// The kind of parameter.
+    pub kind:   ParamKind,
+    /// The name of the parameter, including the `'` of lifetimes.
+    pub name:   TokenStream,
+    /// The bounds of the parameter (or the type of constants), without the `:`.
+    pub bounds: Option<TokenStream>,
+}
+impl Param {
+    /// Parses a Param from its tokens.
+    ///
+    /// The default value of the parameter, if any, is discarded.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the parameter, without any separating commas.
+    ///
+    /// # Returns
+    /// The parsed Param.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a generic parameter.
+    pub fn parse(tokens: Vec<TokenTree>) -> Result<Self, Diagnostic2> {
+        let span: Span = tokens.first().map(TokenTree::span).unwrap_or_else(Span::call_site);
+        let mut iter = tokens.into_iter().peekable();
+
+        // Parse the attributes
+        let mut attrs = TokenStream::new();
+        while let Some(pound) = iter.next_if(|tt| is_punct(tt, '#')) {
+            match iter.next() {
+                Some(TokenTree::Group(group)) => attrs.extend([pound, TokenTree::Group(group)]),
+                Some(tt) => return Err(error2(tt.span(), "Expected an attribute in square brackets")),
+                None => return Err(error2(pound.span(), "Expected an attribute in square brackets")),
+            }
+        }
+
+        // Parse the name
+        let (kind, name): (ParamKind, TokenStream) = match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '\'' => match iter.next() {
+                Some(TokenTree::Ident(ident)) => (ParamKind::Lifetime, TokenStream::from_iter([TokenTree::Punct(p), TokenTree::Ident(ident)])),
+                Some(tt) => return Err(error2(tt.span(), "Expected the name of a lifetime")),
+                None => return Err(error2(p.span(), "Expected the name of a lifetime")),
+            },
+            Some(TokenTree::Ident(ident)) if ident == "const" => match iter.next() {
+                Some(TokenTree::Ident(name)) => (ParamKind::Const, TokenStream::from(TokenTree::Ident(name))),
+                Some(tt) => return Err(error2(tt.span(), "Expected the name of a constant parameter")),
+                None => return Err(error2(ident.span(), "Expected the name of a constant parameter")),
+            },
+            Some(TokenTree::Ident(ident)) => (ParamKind::Type, TokenStream::from(TokenTree::Ident(ident))),
+            Some(tt) => return Err(error2(tt.span(), "Expected a generic parameter")),
+            None => return Err(error2(span, "Expected a generic parameter")),
+        };
+
+        // Parse the bounds, if any (stopping at the default, if any)
+        let mut bounds: Option<TokenStream> = None;
+        if let Some(colon) = iter.next_if(|tt| is_punct(tt, ':')) {
+            let mut tokens = TokenStream::new();
+            let mut depth: usize = 0;
+            let mut prev: Option<Punct> = None;
+            while let Some(tt) = iter.next_if(|tt| depth > 0 || !is_punct(tt, '=')) {
+                if let TokenTree::Punct(p) = &tt {
+                    let arrow: bool = prev.as_ref().is_some_and(|prev| prev.as_char() == '-' && prev.spacing() == Spacing::Joint);
+                    match p.as_char() {
+                        '<' => depth += 1,
+                        '>' if !arrow => depth = depth.saturating_sub(1),
+                        _ => {},
+                    }
+                    prev = Some(p.clone());
+                } else {
+                    prev = None;
+                }
+                tokens.extend([tt]);
+            }
+            if tokens.is_empty() && kind == ParamKind::Const {
+                return Err(error2(colon.span(), "Expected the type of a constant parameter"));
+            }
+            bounds = Some(tokens);
+        } else if kind == ParamKind::Const {
+            return Err(error2(span, "Expected ':' and the type of a constant parameter"));
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' && kind != ParamKind::Lifetime => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ':', '=' or ','")),
+            None => {},
+        }
+        Ok(Self { attrs, kind, name, bounds })
+    }
+
+    /// Generates this parameter as it appears in the generics of an `impl`-block.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] with the parameter and its bounds, but not its default.
+    pub fn to_impl(&self) -> TokenStream {
+        let mut res: TokenStream = self.attrs.clone();
+        if self.kind == ParamKind::Const {
+            res.extend([TokenTree::Ident(Ident::new("const", Span::call_site()))]);
+        }
+        res.extend(self.name.clone());
+        if let Some(bounds) = &self.bounds {
+            res.extend([TokenTree::Punct(Punct::new(':', Spacing::Alone))]);
+            res.extend(bounds.clone());
+        }
+        res
+    }
+
+    /// Generates this parameter as it appears in the generic arguments of a type.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] with only the name of the parameter.
+    #[inline]
+    pub fn to_type(&self) -> TokenStream { self.name.clone() }
+}
+
+
+
+/// Defines the generics of an item, i.e., its generic parameters and its where-clause.
+#[derive(Clone, Default)]
+pub struct Generics {
+    /// The generic parameters.
+    pub params: Vec<Param>,
+    /// The predicates of the where-clause, without the `where`.
+    pub preds:  Vec<TokenStream>,
+}
+impl Generics {
+    /// Parses Generics from a list of generic parameters and a where-clause, as
+    /// `$(<$params>)? $(where $preds)?`.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens to parse.
+    ///
+    /// # Returns
+    /// The parsed Generics.
+    ///
+    /// # Errors
+    /// This function errors if the angle brackets are unterminated, if there are tokens other than
+    /// a where-clause after them, or if any of the parameters are invalid.
+    pub fn parse(tokens: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = tokens.into_iter().peekable();
+
+        // Parse the parameters in angle brackets, if any
+        let mut params: Vec<Param> = Vec::new();
+        if let Some(open) = iter.next_if(|tt| is_punct(tt, '<')) {
+            let mut inner = TokenStream::new();
+            let mut depth: usize = 1;
+            let mut prev: Option<Punct> = None;
+            loop {
+                let tt: TokenTree = match iter.next() {
+                    Some(tt) => tt,
+                    None => return Err(error2(open.span(), "Unterminated generics (expected '>')")),
+                };
+                if let TokenTree::Punct(p) = &tt {
+                    let arrow: bool = prev.as_ref().is_some_and(|prev| prev.as_char() == '-' && prev.spacing() == Spacing::Joint);
+                    match p.as_char() {
+                        '<' => depth += 1,
+                        '>' if !arrow => depth -= 1,
+                        _ => {},
+                    }
+                    prev = Some(p.clone());
+                } else {
+                    prev = None;
+                }
+                if depth == 0 {
+                    break;
+                }
+                inner.extend([tt]);
+            }
+            params = Self::parse_params(inner)?;
+        }
+
+        // Parse the where-clause, if any
+        let preds: Vec<TokenStream> = match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "where" => Self::parse_preds(iter.collect()),
+            Some(tt) => return Err(error2(tt.span(), "Expected either 'where' or nothing")),
+            None => Vec::new(),
+        };
+        Ok(Self { params, preds })
+    }
+
+    /// Parses a comma-separated list of generic parameters, without the angle brackets.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens to parse.
+    ///
+    /// # Returns
+    /// The parsed [`Param`]s.
+    ///
+    /// # Errors
+    /// This function errors if any of the parameters are invalid.
+    #[inline]
+    pub fn parse_params(tokens: TokenStream) -> Result<Vec<Param>, Diagnostic2> { split_top_level(tokens, ',').into_iter().map(Param::parse).collect() }
+
+    /// Parses a comma-separated list of where-predicates, without the `where`.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens to parse.
+    ///
+    /// # Returns
+    /// The predicates, each without separating commas.
+    #[inline]
+    pub fn parse_preds(tokens: TokenStream) -> Vec<TokenStream> { split_top_level(tokens, ',').into_iter().map(TokenStream::from_iter).collect() }
+
+    /// Generates a comma-separated list in angle brackets, or nothing if the list is empty.
+    ///
+    /// # Arguments
+    /// - `elems`: The elements of the list.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] encoding `<elem1, elem2, ...>`, or an empty one.
+    fn angled(elems: impl IntoIterator<Item = TokenStream>) -> TokenStream {
+        let mut inner = TokenStream::new();
+        for (i, elem) in elems.into_iter().enumerate() {
+            if i > 0 {
+                inner.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+            }
+            inner.extend(elem);
+        }
+        if inner.is_empty() {
+            return inner;
+        }
+        let mut res = TokenStream::from(TokenTree::Punct(Punct::new('<', Spacing::Alone)));
+        res.extend(inner);
+        res.extend([TokenTree::Punct(Punct::new('>', Spacing::Alone))]);
+        res
+    }
+
+    /// Generates the generics as they appear after `impl` (i.e., with bounds but without defaults).
+    ///
+    /// # Returns
+    /// A [`TokenStream`] encoding `<'a, T: Clone, const N: usize>`, or an empty one if there are no
+    /// parameters.
+    #[inline]
+    pub fn to_impl(&self) -> TokenStream { Self::angled(self.params.iter().map(Param::to_impl)) }
+
+    /// Generates the generics as they appear after the type in an `impl`-block (i.e., only the
+    /// names).
+    ///
+    /// # Returns
+    /// A [`TokenStream`] encoding `<'a, T, N>`, or an empty one if there are no parameters.
+    #[inline]
+    pub fn to_type(&self) -> TokenStream { Self::angled(self.params.iter().map(Param::to_type)) }
+
+    /// Generates the where-clause.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] encoding `where T: Default, ...`, or an empty one if there are no
+    /// predicates.
+    pub fn to_where(&self) -> TokenStream {
+        if self.preds.is_empty() {
+            return TokenStream::new();
+        }
+        let mut res = TokenStream::from(TokenTree::Ident(Ident::new("where", Span::call_site())));
+        for pred in &self.preds {
+            res.extend(pred.clone());
+            res.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        res
+    }
+}
@@ -0,0 +1,166 @@
+//  GENERICS MERGE.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for merging two sets of generics into one.
+//
+
+use proc_macro2::{Delimiter, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::generics::{Generics, Param, ParamKind, split_top_level};
+use crate::utils::{Callback, part};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Combines two lists of bounds, omitting any that appear in both.
+///
+/// # Arguments
+/// - `lhs`: The bounds to combine into.
+/// - `rhs`: The bounds to add.
+///
+/// # Returns
+/// The bounds of `lhs` followed by those of `rhs` that are not already in `lhs`, separated by `+`.
+fn merge_bounds(lhs: TokenStream, rhs: TokenStream) -> TokenStream {
+    let mut bounds: Vec<Vec<TokenTree>> = split_top_level(lhs, '+');
+    let mut seen: Vec<String> = bounds.iter().map(|bound| TokenStream::from_iter(bound.iter().cloned()).to_string()).collect();
+    for bound in split_top_level(rhs, '+') {
+        let key: String = TokenStream::from_iter(bound.iter().cloned()).to_string();
+        if !seen.contains(&key) {
+            seen.push(key);
+            bounds.push(bound);
+        }
+    }
+
+    // Join them again
+    let mut res = TokenStream::new();
+    for (i, bound) in bounds.into_iter().enumerate() {
+        if i > 0 {
+            res.extend([TokenTree::Punct(Punct::new('+', Spacing::Alone))]);
+        }
+        res.extend(bound);
+    }
+    res
+}
+
+
+
+/// Merges a generic parameter into an existing one with the same name.
+///
+/// # Arguments
+/// - `lhs`: The existing parameter.
+/// - `rhs`: The parameter to merge into it.
+///
+/// # Errors
+/// This function errors if the parameters are of different kinds, or if they are constants of
+/// different types.
+fn merge_param(lhs: &mut Param, rhs: Param) -> Result<(), Diagnostic2> {
+    let span: Span = rhs.name.clone().into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+    if lhs.kind != rhs.kind {
+        return Err(error2(span, &format!("Generic parameter `{}` is given as different kinds of parameters", lhs.name)));
+    }
+    match (lhs.kind, lhs.bounds.take(), rhs.bounds) {
+        (ParamKind::Const, Some(lty), Some(rty)) => {
+            if lty.to_string() != rty.to_string() {
+                return Err(error2(span, &format!("Constant parameter `{}` is given with different types (`{lty}` and `{rty}`)", lhs.name)));
+            }
+            lhs.bounds = Some(lty);
+        },
+        (_, Some(lbounds), Some(rbounds)) => lhs.bounds = Some(merge_bounds(lbounds, rbounds)),
+        (_, lbounds, rbounds) => lhs.bounds = lbounds.or(rbounds),
+    }
+    Ok(())
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses one of the sets of generics to merge.
+///
+/// # Arguments
+/// - `tt`: The token to parse, which should be a group in square brackets.
+/// - `span`: A span to report errors at if the token is missing.
+///
+/// # Returns
+/// The parsed [`Generics`].
+///
+/// # Errors
+/// This function errors if the token is not a group in square brackets, or if its contents are not
+/// valid generics.
+fn parse_generics(tt: Option<TokenTree>, span: Span) -> Result<Generics, Diagnostic2> {
+    match tt {
+        // Unwrap generics given as e.g. `$generics:tt`
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+            let mut iter = group.stream().into_iter();
+            let generics: Generics = parse_generics(iter.next(), group.span())?;
+            if let Some(tt) = iter.next() {
+                return Err(error2(tt.span(), "Expected nothing after the generics"));
+            }
+            Ok(generics)
+        },
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => Generics::parse(group.stream()),
+        Some(tt) => Err(error2(tt.span(), "Expected generics in square brackets (e.g., `[<T: Clone> where T: Default]`)")),
+        None => Err(error2(span, "Expected two sets of generics in square brackets (e.g., `[<T: Clone> where T: Default]`)")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`generics_merge()`](super::generics_merge())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the two sets of generics and the callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the callback with the impl-generics, type-generics and
+/// where-clause of the merged generics.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the generics conflict.
+pub fn generics_merge(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (generics, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let span: Span = generics.clone().into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+    let Some(callback) = callback else {
+        return Err(error2(span, "Expected '=>' and a callback macro after the generics"));
+    };
+    let mut iter = generics.into_iter();
+    let lhs: Generics = parse_generics(iter.next(), span)?;
+    let rhs: Generics = parse_generics(iter.next(), span)?;
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected '=>' and a callback macro after the generics"));
+    }
+
+    // Merge the parameters by name
+    let mut params: Vec<Param> = lhs.params;
+    for param in rhs.params {
+        let name: String = param.name.to_string();
+        match params.iter_mut().find(|existing| existing.name.to_string() == name) {
+            Some(existing) => merge_param(existing, param)?,
+            None => params.push(param),
+        }
+    }
+    // NOTE: Lifetimes must come first, which may no longer be the case if only the second set has them
+    params.sort_by_key(|param| param.kind != ParamKind::Lifetime);
+
+    // Merge the predicates, omitting duplicates
+    let mut preds: Vec<TokenStream> = lhs.preds;
+    for pred in rhs.preds {
+        let key: String = pred.to_string();
+        if !preds.iter().any(|existing| existing.to_string() == key) {
+            preds.push(pred);
+        }
+    }
+
+    // Call the callback with the three forms
+    let generics = Generics { params, preds };
+    let mut args = TokenStream::new();
+    args.extend(part("impl_generics", generics.to_impl()));
+    args.extend(part("type_generics", generics.to_type()));
+    args.extend(part("where_clause", generics.to_where()));
+    Ok(callback.call_item(args))
+}
@@ -0,0 +1,41 @@
+//  GENERICS SPLIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for splitting generics into the forms needed for `impl`-blocks.
+//
+
+use proc_macro2::{Span, TokenStream};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::generics::Generics;
+use crate::utils::{Callback, part};
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`generics_split()`](super::generics_split())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the generics, where-clause and the callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the callback with the impl-generics, type-generics and
+/// where-clause.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn generics_split(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (generics, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let Some(callback) = callback else {
+        let span: Span = generics.into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+        return Err(error2(span, "Expected '=>' and a callback macro after the generics"));
+    };
+    let generics: Generics = Generics::parse(generics)?;
+
+    // Call the callback with the three forms
+    let mut args = TokenStream::new();
+    args.extend(part("impl_generics", generics.to_impl()));
+    args.extend(part("type_generics", generics.to_type()));
+    args.extend(part("where_clause", generics.to_where()));
+    Ok(callback.call_item(args))
+}
@@ -0,0 +1,131 @@
+//  GENSYM.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for generating fresh identifiers.
+//
+
+#[cfg(not(feature = "deterministic"))]
+use std::hash::{BuildHasher as _, Hasher as _, RandomState};
+use std::iter::Peekable;
+#[cfg(not(feature = "deterministic"))]
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use proc_macro2::{Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::utils::Callback;
+
+
+/***** GLOBALS *****/
+/// The number of identifiers generated so far.
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A random tag that is unique to this process, making identifiers unlikely to collide with those
+/// from other compilations (e.g., when they end up in the same binary through `#[no_mangle]`).
+#[cfg(not(feature = "deterministic"))]
+static TAG: OnceLock<u32> = OnceLock::new();
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the labels given and the callback to call with the identifiers, if any.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(Vec<Ident>, Option<Callback>), Diagnostic2> {
+    let mut iter: Peekable<_> = input.into_iter().peekable();
+
+    // Parse the labels
+    let mut labels: Vec<Ident> = Vec::new();
+    while let Some(TokenTree::Ident(ident)) = iter.peek() {
+        labels.push(ident.clone());
+        iter.next();
+        match iter.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {
+                iter.next();
+            },
+            _ => break,
+        }
+    }
+
+    // Parse the optional callback
+    let callback: Option<Callback> = match iter.peek() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '=' => Callback::parse_opt(&mut iter)?,
+        Some(tt) => return Err(error2(tt.span(), "Expected either an identifier, ',', '=>' or nothing")),
+        None => None,
+    };
+    if labels.len() > 1 && callback.is_none() {
+        return Err(error2(labels[1].span(), "Multiple labels can only be given with a callback (e.g., `=> foo!`)"));
+    }
+    Ok((labels, callback))
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates a fresh identifier.
+///
+/// # Arguments
+/// - `label`: Some optional label to use in the identifier instead of `gensym`.
+///
+/// # Returns
+/// A new [`Ident`] that hasn't been generated before.
+pub fn fresh(label: Option<&Ident>) -> Ident {
+    let id: u64 = COUNTER.fetch_add(1, Ordering::Relaxed);
+    #[cfg(not(feature = "deterministic"))]
+    let tag: u32 = *TAG.get_or_init(|| RandomState::new().build_hasher().finish() as u32 & 0xFF_FFFF);
+    // In deterministic mode, the tag is derived from the call instead
+    #[cfg(feature = "deterministic")]
+    let tag: u32 = crate::utils::call_site_seed() as u32 & 0xFF_FFFF;
+    let label: String = label.map(|l| l.to_string().trim_start_matches("r#").to_string()).unwrap_or_else(|| "gensym".into());
+    // Use the call site span, such that the identifier can be referred to by the caller
+    Ident::new(&format!("__mtk_{label}_{id}_{tag:06x}"), Span::call_site())
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`gensym()`](super::gensym())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the optional labels and callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with a fresh identifier, or a call to the given callback with a fresh
+/// identifier per label.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn gensym(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (labels, callback): (Vec<Ident>, Option<Callback>) = parse_input(input)?;
+    match callback {
+        Some(callback) => {
+            let mut idents = TokenStream::new();
+            if labels.is_empty() {
+                idents.extend([TokenTree::Ident(fresh(None))]);
+            }
+            for (i, label) in labels.iter().enumerate() {
+                if i > 0 {
+                    idents.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+                }
+                idents.extend([TokenTree::Ident(fresh(Some(label)))]);
+            }
+            Ok(callback.call(idents))
+        },
+        None => Ok(TokenStream::from(TokenTree::Ident(fresh(labels.first())))),
+    }
+}
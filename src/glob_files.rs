@@ -0,0 +1,193 @@
+//  GLOB FILES.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for expanding a template once for every file that matches a glob, such that
+//!   e.g. a test can be generated for every fixture in a directory.
+//
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+use proc_macro2::{Delimiter, Ident, Literal, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, parse_string};
+use crate::duplicate::substitute;
+use crate::utils::{is_keyword, resolve_path};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Turns the stem of a file into a valid identifier.
+///
+/// Every character that cannot appear in an identifier is replaced by an underscore. If the
+/// result starts with a digit or is empty, an underscore is prepended; if it is a keyword (or just
+/// an underscore), one is appended.
+///
+/// # Arguments
+/// - `stem`: The stem to sanitize.
+///
+/// # Returns
+/// The sanitized identifier.
+fn sanitize(stem: &str) -> String {
+    let mut ident: String = stem.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if ident == "_" || is_keyword(&ident) {
+        ident.push('_');
+    }
+    ident
+}
+
+/// Generates the values of the placeholders for a single file.
+///
+/// # Arguments
+/// - `path`: The path of the file.
+/// - `span`: The [`Span`] to give to the values, which is also where errors are reported.
+///
+/// # Returns
+/// A map from the names of the placeholders to their values.
+///
+/// # Errors
+/// This function errors if the path cannot be made absolute or is not valid unicode.
+fn placeholders(path: &Path, span: Span) -> Result<HashMap<&'static str, TokenStream>, Diagnostic2> {
+    let path: PathBuf = std::path::absolute(path).map_err(|err| error2(span, &format!("Failed to make '{}' absolute: {err}", path.display())))?;
+    let (Some(full), Some(stem)) = (path.to_str(), path.file_stem().and_then(|stem| stem.to_str())) else {
+        return Err(error2(span, &format!("Path '{}' is not valid unicode", path.display())));
+    };
+    let mut full = Literal::string(full);
+    full.set_span(span);
+    let mut lit = Literal::string(stem);
+    lit.set_span(span);
+    Ok(HashMap::from([
+        ("path", TokenTree::Literal(full).into()),
+        ("stem", TokenTree::Literal(lit).into()),
+        ("ident", TokenTree::Ident(Ident::new(&sanitize(stem), span)).into()),
+    ]))
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the closure-like head of the template, i.e., the placeholders in between `|`s.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+/// - `span`: A [`Span`] to report errors at if there are no tokens left.
+///
+/// # Returns
+/// The names of the placeholders used by the template.
+///
+/// # Errors
+/// This function errors if the tokens do not start with placeholders in between `|`s, or if any of
+/// them is unknown or given twice.
+fn parse_params(iter: &mut impl Iterator<Item = TokenTree>, span: Span) -> Result<Vec<Ident>, Diagnostic2> {
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '|' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected '|'")),
+        None => return Err(error2(span, "Expected ',' and the placeholders of the template in between `|`s")),
+    }
+    let mut params: Vec<Ident> = Vec::new();
+    loop {
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '|' => return Ok(params),
+            Some(TokenTree::Ident(ident)) => {
+                if !matches!(ident.to_string().as_str(), "path" | "stem" | "ident") {
+                    return Err(error2(ident.span(), "Expected one of `path`, `stem` or `ident`"));
+                }
+                if params.contains(&ident) {
+                    return Err(error2(ident.span(), &format!("Placeholder `{ident}` is given twice")));
+                }
+                params.push(ident);
+                match iter.next() {
+                    Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                    Some(TokenTree::Punct(p)) if p.as_char() == '|' => return Ok(params),
+                    Some(tt) => return Err(error2(tt.span(), "Expected ',' or '|'")),
+                    None => return Err(error2(span, "Expected ',' or '|'")),
+                }
+            },
+            Some(tt) => return Err(error2(tt.span(), "Expected a placeholder or '|'")),
+            None => return Err(error2(span, "Expected a placeholder or '|'")),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`glob_files()`](super::glob_files())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the glob and the template to expand for every file.
+///
+/// # Returns
+/// A new [`TokenStream`] with the template expanded once for every file that matches the glob.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if the glob is invalid or
+/// matches no files, or if pasting identifiers in any of the expansions fails.
+pub fn glob_files(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the glob
+    let first: Option<TokenTree> = iter.next();
+    let lit_span: Span = first.as_ref().map(TokenTree::span).unwrap_or_else(Span::call_site);
+    let glob: String = parse_string(first, lit_span, "a glob")?;
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+        None => return Err(error2(lit_span, "Expected ',' and the placeholders of the template in between `|`s")),
+    }
+
+    // Parse the template
+    let params: Vec<Ident> = parse_params(&mut iter, lit_span)?;
+    let body: TokenStream = match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group.stream(),
+        Some(tt) => return Err(error2(tt.span(), "Expected the body of the template wrapped in `{}`")),
+        None => return Err(error2(lit_span, "Expected the body of the template wrapped in `{}`")),
+    };
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+        None => {},
+    }
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the body of the template"));
+    }
+
+    // Find the files, relative to the invoking file unless the glob is absolute
+    Pattern::new(&glob).map_err(|err| error2(lit_span, &format!("Invalid glob: {err}")))?;
+    let full: String = if Path::new(&glob).is_absolute() {
+        glob.clone()
+    } else {
+        let dir: PathBuf = resolve_path("");
+        format!("{}{}", Pattern::escape(&dir.to_string_lossy()), glob)
+    };
+    let paths = glob::glob(&full).map_err(|err| error2(lit_span, &format!("Invalid glob: {err}")))?;
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        let path: PathBuf = path.map_err(|err| error2(lit_span, &format!("Failed to read '{}': {}", err.path().display(), err.error())))?;
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    if files.is_empty() {
+        return Err(
+            Diagnostic2::error(lit_span, format!("No files match '{glob}'")).help("relative globs are resolved relative to the directory of the invoking file")
+        );
+    }
+
+    // Expand the template for each of them
+    let mut res = TokenStream::new();
+    for file in files {
+        let values: HashMap<&str, TokenStream> = placeholders(&file, lit_span)?;
+        let values: HashMap<String, &TokenStream> = params.iter().map(|param| (param.to_string(), &values[param.to_string().as_str()])).collect();
+        res.extend(macro_toolkit_core::idents::idents(substitute(body.clone(), &values))?);
+    }
+    Ok(res)
+}
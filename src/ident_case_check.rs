@@ -0,0 +1,255 @@
+//  IDENT CASE CHECK.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for checking that identifiers follow a naming convention at compile time.
+//
+
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Splits an identifier into its words, i.e., on underscores and changes of case.
+///
+/// A run of uppercase letters is seen as one word (e.g., `HTTPServer` is `HTTP` and `Server`).
+///
+/// # Arguments
+/// - `name`: The identifier to split, without leading or trailing underscores.
+///
+/// # Returns
+/// The words in the identifier.
+fn words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, c) in chars.iter().copied().enumerate() {
+            let prev: Option<char> = i.checked_sub(1).map(|i| chars[i]);
+            let next: Option<char> = chars.get(i + 1).copied();
+            let boundary: bool = c.is_uppercase()
+                && (prev.is_some_and(|p| p.is_lowercase() || p.is_numeric()) || (prev.is_some_and(char::is_uppercase) && next.is_some_and(char::is_lowercase)));
+            if boundary && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(c);
+        }
+        words.push(word);
+    }
+    words
+}
+
+/// Capitalizes a word, i.e., makes its first letter uppercase and the rest lowercase.
+///
+/// # Arguments
+/// - `word`: The word to capitalize.
+///
+/// # Returns
+/// The capitalized word.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the naming conventions that can be checked.
+#[derive(Clone, Copy)]
+enum Case {
+    /// `snake_case`, e.g., for functions, variables and modules.
+    Snake,
+    /// `UpperCamelCase`, e.g., for types, traits and enum variants.
+    UpperCamel,
+    /// `SCREAMING_SNAKE_CASE`, e.g., for constants and statics.
+    ScreamingSnake,
+}
+impl Case {
+    /// Parses a case from its name.
+    ///
+    /// # Arguments
+    /// - `ident`: The [`Ident`] with the name of the case.
+    ///
+    /// # Returns
+    /// The parsed Case.
+    ///
+    /// # Errors
+    /// This function errors if the name is not a known case.
+    fn parse(ident: &Ident) -> Result<Self, Diagnostic2> {
+        match ident.to_string().as_str() {
+            "snake_case" => Ok(Self::Snake),
+            "UpperCamelCase" => Ok(Self::UpperCamel),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            _ => Err(Diagnostic2::error(ident.span(), format!("Unknown naming convention `{ident}`"))
+                .help("expected either `snake_case`, `UpperCamelCase` or `SCREAMING_SNAKE_CASE`")),
+        }
+    }
+
+    /// Returns the name of this case.
+    ///
+    /// # Returns
+    /// The name of the case, as it is written in the input.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Snake => "snake_case",
+            Self::UpperCamel => "UpperCamelCase",
+            Self::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        }
+    }
+
+    /// Checks whether the given name follows this case.
+    ///
+    /// # Arguments
+    /// - `name`: The name to check, without leading or trailing underscores.
+    ///
+    /// # Returns
+    /// True if it does, or false otherwise.
+    fn matches(self, name: &str) -> bool {
+        match self {
+            Self::Snake => !name.contains("__") && !name.chars().any(char::is_uppercase),
+            Self::UpperCamel => !name.contains('_') && !name.starts_with(char::is_lowercase),
+            Self::ScreamingSnake => !name.contains("__") && !name.chars().any(char::is_lowercase),
+        }
+    }
+
+    /// Converts the given name to this case.
+    ///
+    /// # Arguments
+    /// - `name`: The name to convert, without leading or trailing underscores.
+    ///
+    /// # Returns
+    /// The converted name.
+    fn convert(self, name: &str) -> String {
+        let words: Vec<String> = words(name);
+        match self {
+            Self::Snake => words.iter().map(|w| w.to_lowercase()).collect::<Vec<_>>().join("_"),
+            Self::UpperCamel => words.iter().map(|w| capitalize(w)).collect(),
+            Self::ScreamingSnake => words.iter().map(|w| w.to_uppercase()).collect::<Vec<_>>().join("_"),
+        }
+    }
+}
+
+
+
+/// Parses a single identifier to check.
+///
+/// # Arguments
+/// - `tt`: The [`TokenTree`] to parse.
+///
+/// # Returns
+/// The parsed [`Ident`].
+///
+/// # Errors
+/// This function errors if the token is not an identifier.
+fn parse_ident(tt: TokenTree) -> Result<Ident, Diagnostic2> {
+    match tt {
+        TokenTree::Ident(ident) => Ok(ident),
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+            let mut tokens = group.stream().into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(tt), None) => parse_ident(tt),
+                _ => Err(error2(group.span(), "Expected an identifier")),
+            }
+        },
+        tt => Err(error2(tt.span(), "Expected an identifier")),
+    }
+}
+
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// The naming convention and the identifiers to check.
+///
+/// # Errors
+/// This function errors if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(Case, Vec<Ident>), Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the case
+    let case: Case = match iter.next() {
+        Some(TokenTree::Ident(ident)) => Case::parse(&ident)?,
+        Some(tt) => return Err(error2(tt.span(), "Expected either `snake_case`, `UpperCamelCase` or `SCREAMING_SNAKE_CASE`")),
+        None => return Err(error2(Span::call_site(), "Expected either `snake_case`, `UpperCamelCase` or `SCREAMING_SNAKE_CASE`")),
+    };
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ':' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ':'")),
+        None => return Err(error2(Span::call_site(), "Expected ':' after the naming convention")),
+    }
+
+    // Parse the identifiers
+    let mut idents: Vec<Ident> = Vec::new();
+    while let Some(tt) = iter.next() {
+        idents.push(parse_ident(tt)?);
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+            None => break,
+        }
+    }
+    if idents.is_empty() {
+        return Err(error2(Span::call_site(), "Expected at least one identifier to check"));
+    }
+    Ok((case, idents))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`ident_case_check()`](super::ident_case_check())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the naming convention and the identifiers to check.
+///
+/// # Returns
+/// An empty [`TokenStream`] if all identifiers follow the convention.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if any of the identifiers
+/// do not follow the convention. In the latter case, an error is emitted for each of them.
+pub fn ident_case_check(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // The macro is used in item or statement position, where the `compile_error!()`s need a
+    // semicolon to not trigger another error
+    let (case, idents): (Case, Vec<Ident>) = parse_input(input).map_err(item_error)?;
+
+    // Check the identifiers
+    let mut errs: Option<Diagnostic2> = None;
+    for ident in idents {
+        let full: String = ident.to_string();
+        let (raw, name): (&str, &str) = match full.strip_prefix("r#") {
+            Some(name) => ("r#", name),
+            None => ("", full.as_str()),
+        };
+        let trimmed: &str = name.trim_matches('_');
+        if case.matches(trimmed) {
+            continue;
+        }
+
+        // Suggest the name in the proper case, keeping the leading and trailing underscores
+        let leading: &str = &name[..name.len() - name.trim_start_matches('_').len()];
+        let trailing: &str = &name[name.trim_end_matches('_').len()..];
+        let err: Diagnostic2 = item_error(
+            Diagnostic2::error(ident.span(), format!("`{name}` is not in {}", case.name()))
+                .help(format!("rename it to `{raw}{leading}{}{trailing}`", case.convert(trimmed))),
+        );
+        errs = Some(match errs {
+            Some(errs) => errs.and(err),
+            None => err,
+        });
+    }
+    match errs {
+        Some(errs) => Err(errs),
+        None => Ok(TokenStream::new()),
+    }
+}
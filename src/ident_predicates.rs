@@ -0,0 +1,232 @@
+//  IDENT PREDICATES.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for checking whether identifiers are keywords and whether strings are valid
+//!   identifiers at compile time.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, parse_string};
+use crate::utils::{RESERVED_KEYWORDS, STRICT_KEYWORDS, WEAK_KEYWORDS, is_ident};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates the output of the macros.
+///
+/// # Arguments
+/// - `res`: Whether the predicate holds.
+/// - `span`: The [`Span`] to give to the generated `true` or `false`.
+/// - `branches`: The branches to choose from, if this is the `if`-form.
+///
+/// # Returns
+/// A [`TokenStream`] with either `true` or `false`, or the contents of the chosen branch in the
+/// `if`-form.
+fn generate(res: bool, span: Span, branches: Option<(Group, Option<Group>)>) -> TokenStream {
+    match branches {
+        Some((then, other)) => {
+            if res {
+                then.stream()
+            } else {
+                other.map(|g| g.stream()).unwrap_or_default()
+            }
+        },
+        None => TokenStream::from(TokenTree::Ident(Ident::new(if res { "true" } else { "false" }, span))),
+    }
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the categories of keywords that can be checked for.
+enum Category {
+    /// Keywords that can only be used as keywords (e.g., `fn`).
+    Strict,
+    /// Keywords that are reserved for future use (e.g., `yield`).
+    Reserved,
+    /// Keywords that only have a special meaning in some contexts (e.g., `union`).
+    Weak,
+    /// Any of the above.
+    Any,
+}
+impl Category {
+    /// Checks whether the given identifier is a keyword in this category.
+    ///
+    /// # Arguments
+    /// - `ident`: The identifier to check.
+    ///
+    /// # Returns
+    /// True if it is, or false otherwise.
+    fn contains(&self, ident: &str) -> bool {
+        match self {
+            Self::Strict => STRICT_KEYWORDS.contains(&ident),
+            Self::Reserved => RESERVED_KEYWORDS.contains(&ident),
+            Self::Weak => WEAK_KEYWORDS.contains(&ident),
+            Self::Any => STRICT_KEYWORDS.contains(&ident) || RESERVED_KEYWORDS.contains(&ident) || WEAK_KEYWORDS.contains(&ident),
+        }
+    }
+}
+
+
+
+/// Parses the optional `if` that starts the `if`-form.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// True if the `if` was there, or false otherwise.
+fn parse_if(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> bool {
+    let is_if: bool = matches!(iter.peek(), Some(TokenTree::Ident(ident)) if ident == "if");
+    if is_if {
+        iter.next();
+    }
+    is_if
+}
+
+/// Parses a branch of the `if`-form.
+///
+/// # Arguments
+/// - `tree`: The token that should be the branch.
+/// - `span`: A [`Span`] to report errors at if there is no token.
+///
+/// # Returns
+/// The braced [`Group`] making up the branch.
+///
+/// # Errors
+/// This function errors if the token was not a braced group.
+fn parse_branch(tree: Option<TokenTree>, span: Span) -> Result<Group, Diagnostic2> {
+    match tree {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => Ok(group),
+        Some(tt) => Err(error2(tt.span(), "Expected a branch in curly brackets")),
+        None => Err(error2(span, "Expected a branch in curly brackets")),
+    }
+}
+
+/// Parses the branches of the `if`-form, if any, and checks that nothing follows them.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+/// - `is_if`: Whether this is the `if`-form.
+/// - `span`: A [`Span`] to report errors at if a branch is missing.
+///
+/// # Returns
+/// The branch to emit if the predicate holds and the optional one to emit if it does not, or
+/// [`None`] if this is not the `if`-form.
+///
+/// # Errors
+/// This function errors if the branches were invalid, or if there are tokens after them.
+fn parse_branches(iter: &mut impl Iterator<Item = TokenTree>, is_if: bool, span: Span) -> Result<Option<(Group, Option<Group>)>, Diagnostic2> {
+    let branches: Option<(Group, Option<Group>)> = if is_if {
+        let then: Group = parse_branch(iter.next(), span)?;
+        let other: Option<Group> = match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "else" => Some(parse_branch(iter.next(), ident.span())?),
+            Some(tt) => return Err(error2(tt.span(), "Expected either `else` or nothing")),
+            None => None,
+        };
+        Some((then, other))
+    } else {
+        None
+    };
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the predicate"));
+    }
+    Ok(branches)
+}
+
+/// Parses the identifier given to [`keyword_check!()`](super::keyword_check!()).
+///
+/// # Arguments
+/// - `group`: The [`Group`] containing the identifier.
+///
+/// # Returns
+/// The identifier.
+///
+/// # Errors
+/// This function errors if the group did not contain a single identifier.
+fn parse_ident(group: Group) -> Result<Ident, Diagnostic2> {
+    let mut iter = group.stream().into_iter();
+    let ident: Ident = match iter.next() {
+        Some(TokenTree::Ident(ident)) => ident,
+        // Look through identifiers given as e.g. `$name:tt`
+        Some(TokenTree::Group(inner)) if inner.delimiter() == Delimiter::None => return parse_ident(inner),
+        Some(tt) => return Err(error2(tt.span(), "Expected an identifier")),
+        None => return Err(error2(group.span(), "Expected an identifier")),
+    };
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the identifier"));
+    }
+    Ok(ident)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`keyword_check()`](super::keyword_check())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the category and identifier to check.
+///
+/// # Returns
+/// A new [`TokenStream`] with either `true` or `false`, or the contents of the chosen branch in
+/// the `if`-form.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn keyword_check(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+    let is_if: bool = parse_if(&mut iter);
+
+    // Parse the category and the identifier
+    let (category, span): (Category, Span) = match iter.next() {
+        Some(TokenTree::Ident(ident)) => match ident.to_string().as_str() {
+            "strict" => (Category::Strict, ident.span()),
+            "reserved" => (Category::Reserved, ident.span()),
+            "weak" => (Category::Weak, ident.span()),
+            "any" => (Category::Any, ident.span()),
+            _ => return Err(error2(ident.span(), "Expected either `strict`, `reserved`, `weak` or `any`")),
+        },
+        Some(tt) => return Err(error2(tt.span(), "Expected either `if`, `strict`, `reserved`, `weak` or `any`")),
+        None => return Err(error2(Span::mixed_site(), "Expected either `if`, `strict`, `reserved`, `weak` or `any`")),
+    };
+    let ident: Ident = match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => parse_ident(group)?,
+        Some(tt) => return Err(error2(tt.span(), "Expected parenthesis with the identifier to check")),
+        None => return Err(error2(span, "Expected parenthesis with the identifier to check")),
+    };
+    let branches: Option<(Group, Option<Group>)> = parse_branches(&mut iter, is_if, span)?;
+
+    // Check it, where raw identifiers are never keywords
+    Ok(generate(category.contains(&ident.to_string()), span, branches))
+}
+
+/// Defines the implementation of the [`is_valid_ident()`](super::is_valid_ident())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the string literal to check.
+///
+/// # Returns
+/// A new [`TokenStream`] with either `true` or `false`, or the contents of the chosen branch in
+/// the `if`-form.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn is_valid_ident(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+    let is_if: bool = parse_if(&mut iter);
+
+    // Parse the string
+    let span: Span = iter.peek().map(TokenTree::span).unwrap_or_else(Span::mixed_site);
+    let value: String = parse_string(iter.next(), span, "the identifier to check")?;
+    let branches: Option<(Group, Option<Group>)> = parse_branches(&mut iter, is_if, span)?;
+
+    // Check it
+    Ok(generate(is_ident(&value), span, branches))
+}
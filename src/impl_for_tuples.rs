@@ -0,0 +1,250 @@
+//  IMPL FOR TUPLES.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for expanding a template once for every arity in a range of tuples.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::parse_lit;
+
+
+/***** CONSTANTS *****/
+/// The largest arity that can be generated, to avoid accidentally generating huge amounts of code.
+const MAX_ARITY: usize = 256;
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The smallest arity to generate.
+    start:    usize,
+    /// The largest arity to generate (inclusive).
+    end:      usize,
+    /// The template to expand for every arity.
+    template: Vec<Part>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the range of arities
+        let (start, span): (usize, Span) = Self::parse_arity(&mut iter)?;
+        let (end, span): (usize, Span) = if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '.') {
+            iter.next();
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '.' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected either '..' or '..='")),
+                None => return Err(error2(span, "Expected either '..' or '..='")),
+            }
+            let incl: bool = matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=');
+            if incl {
+                iter.next();
+            }
+            let (end, span): (usize, Span) = Self::parse_arity(&mut iter)?;
+            match (incl, end.checked_sub(1)) {
+                (true, _) => (end, span),
+                (false, Some(end)) => (end, span),
+                (false, None) => return Err(error2(span, "Range of arities is empty")),
+            }
+        } else {
+            (start, span)
+        };
+        if start > end {
+            return Err(error2(span, "Range of arities is empty"));
+        }
+        if end > MAX_ARITY {
+            return Err(error2(span, &format!("Cannot generate tuples with more than {MAX_ARITY} elements")));
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+            None => return Err(error2(span, "Expected ',' and a template")),
+        }
+
+        // Parse the template
+        let template: Vec<Part> = Part::parse(iter.collect(), false)?;
+        Ok(Self { start, end, template })
+    }
+
+    /// Parses an arity.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// A tuple of the arity and its span.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input was not an unsuffixed integer literal.
+    fn parse_arity(iter: &mut impl Iterator<Item = TokenTree>) -> Result<(usize, Span), Diagnostic2> {
+        let Some(tt) = iter.next() else {
+            return Err(error2(Span::call_site(), "Expected an arity"));
+        };
+        match parse_lit(tt)? {
+            Lit::Int(lit) if lit.suffix().is_empty() => match lit.base10_parse() {
+                Ok(arity) => Ok((arity, lit.span())),
+                Err(_) => Err(error2(lit.span(), "Arity is too large")),
+            },
+            lit => Err(error2(lit.span(), "Expected an unsuffixed integer literal")),
+        }
+    }
+}
+
+
+
+/// Defines the parts of a template.
+enum Part {
+    /// A token that is emitted as-is.
+    Token(TokenTree),
+    /// A group, of which the contents are also a template.
+    Group(Group, Vec<Part>),
+    /// An identifier followed by `@`, which is suffixed with the index.
+    Indexed(Ident),
+    /// A lone `@`, which is replaced by the index.
+    Index(Span),
+    /// A repetition, repeated once for every element in the tuple.
+    Repeat { body: Vec<Part>, sep: Option<TokenTree> },
+}
+impl Part {
+    /// Parses a template.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens making up the template.
+    /// - `in_repeat`: Whether this template is in a repetition.
+    ///
+    /// # Returns
+    /// The parts of the template.
+    ///
+    /// # Errors
+    /// This function errors if the template contained invalid repetitions.
+    fn parse(tokens: Vec<TokenTree>, in_repeat: bool) -> Result<Vec<Self>, Diagnostic2> {
+        let mut parts: Vec<Self> = Vec::with_capacity(tokens.len());
+        let mut iter = tokens.into_iter().peekable();
+        while let Some(tt) = iter.next() {
+            match tt {
+                // Repetitions
+                TokenTree::Punct(p) if p.as_char() == '#' => {
+                    let Some(TokenTree::Group(group)) = iter.next_if(|tt| matches!(tt, TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis)) else {
+                        parts.push(Self::Token(TokenTree::Punct(p)));
+                        continue;
+                    };
+                    if in_repeat {
+                        return Err(error2(group.span(), "Repetitions cannot be nested"));
+                    }
+                    let body: Vec<Self> = Self::parse(group.stream().into_iter().collect(), true)?;
+                    let sep: Option<TokenTree> = match iter.next() {
+                        Some(TokenTree::Punct(p)) if p.as_char() == '*' => None,
+                        Some(TokenTree::Punct(p)) => {
+                            let sep = TokenTree::Punct(p);
+                            match iter.next() {
+                                Some(TokenTree::Punct(p)) if p.as_char() == '*' => {},
+                                Some(tt) => return Err(error2(tt.span(), "Expected '*'")),
+                                None => return Err(error2(sep.span(), "Expected '*'")),
+                            }
+                            Some(sep)
+                        },
+                        Some(tt) => return Err(error2(tt.span(), "Expected either a separator or '*'")),
+                        None => return Err(error2(group.span(), "Expected either a separator or '*'")),
+                    };
+                    parts.push(Self::Repeat { body, sep });
+                },
+
+                // Indices
+                TokenTree::Ident(ident) if in_repeat => match iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '@')) {
+                    Some(_) => parts.push(Self::Indexed(ident)),
+                    None => parts.push(Self::Token(TokenTree::Ident(ident))),
+                },
+                TokenTree::Punct(p) if in_repeat && p.as_char() == '@' => parts.push(Self::Index(p.span())),
+
+                // The rest
+                TokenTree::Group(group) => {
+                    let body: Vec<Self> = Self::parse(group.stream().into_iter().collect(), in_repeat)?;
+                    parts.push(Self::Group(group, body));
+                },
+                tt => parts.push(Self::Token(tt)),
+            }
+        }
+        Ok(parts)
+    }
+
+    /// Expands a template.
+    ///
+    /// # Arguments
+    /// - `parts`: The parts of the template to expand.
+    /// - `arity`: The number of elements in the tuple.
+    /// - `index`: The index of the current element if in a repetition, or else unused.
+    /// - `output`: The [`TokenStream`] to write the expansion to.
+    fn expand(parts: &[Self], arity: usize, index: usize, output: &mut TokenStream) {
+        for part in parts {
+            match part {
+                Self::Token(tt) => output.extend([tt.clone()]),
+                Self::Group(group, body) => {
+                    let mut stream = TokenStream::new();
+                    Self::expand(body, arity, index, &mut stream);
+                    let mut new = Group::new(group.delimiter(), stream);
+                    new.set_span(group.span());
+                    output.extend([TokenTree::Group(new)]);
+                },
+                Self::Indexed(ident) => {
+                    output.extend([TokenTree::Ident(Ident::new(&format!("{ident}{index}"), ident.span()))]);
+                },
+                Self::Index(span) => {
+                    let mut lit = Literal::usize_unsuffixed(index);
+                    lit.set_span(*span);
+                    output.extend([TokenTree::Literal(lit)]);
+                },
+                Self::Repeat { body, sep } => {
+                    for i in 0..arity {
+                        if i > 0
+                            && let Some(sep) = sep
+                        {
+                            output.extend([sep.clone()]);
+                        }
+                        Self::expand(body, arity, i, output);
+                    }
+                },
+            }
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`impl_for_tuples()`](super::impl_for_tuples())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the range of arities and the template to expand.
+///
+/// # Returns
+/// A new [`TokenStream`] with the template expanded once for every arity.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn impl_for_tuples(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { start, end, template } = Input::parse(input)?;
+    let mut output = TokenStream::new();
+    for arity in start..=end {
+        Part::expand(&template, arity, 0, &mut output);
+    }
+    Ok(output)
+}
@@ -0,0 +1,60 @@
+//  INCLUDE TOKENS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for splicing the contents of a file into the token stream, such that e.g.
+//!   large generated tables can live in companion files.
+//
+
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr as _;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, parse_string};
+use crate::utils::{Callback, resolve_path};
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`include_tokens()`](super::include_tokens())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the optional `idents`-flag, the path of the file and
+///   an optional callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the contents of the file, or a call to the callback with them.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if the file could not be read
+/// or if its contents are not valid tokens.
+pub fn include_tokens(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+
+    // Parse the optional flag and the path
+    let mut paste: bool = false;
+    if matches!(iter.peek(), Some(TokenTree::Ident(ident)) if ident == "idents") {
+        paste = true;
+        iter.next();
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+            None => return Err(error2(Span::call_site(), "Expected ',' and the path of the file as a string literal")),
+        }
+    }
+    let span: Span = iter.peek().map(TokenTree::span).unwrap_or_else(Span::call_site);
+    let path: String = parse_string(iter.next(), span, "the path of the file")?;
+    let callback: Option<Callback> = Callback::parse_opt(&mut iter)?;
+
+    // Read the file and lex its contents
+    let path: PathBuf = resolve_path(&path);
+    let text: String = fs::read_to_string(&path).map_err(|err| error2(span, &format!("Failed to read '{}': {err}", path.display())))?;
+    let tokens: TokenStream =
+        TokenStream::from_str(&text).map_err(|err| error2(span, &format!("Failed to lex the contents of '{}' as tokens: {err}", path.display())))?;
+    let tokens: TokenStream = if paste { macro_toolkit_core::idents::idents(tokens)? } else { tokens };
+    Ok(match callback {
+        Some(callback) => callback.call(tokens),
+        None => tokens,
+    })
+}
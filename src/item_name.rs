@@ -0,0 +1,43 @@
+//  ITEM NAME.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for extracting the name of an item.
+//
+
+use proc_macro2::{Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::items::parse_name;
+use crate::utils::Callback;
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`item_name()`](super::item_name())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the item and an optional callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the name of the item, or a call to the callback with the name and
+/// the item.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro or if the item has no name.
+pub fn item_name(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (item, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let span: Span = item.clone().into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+    let Some(name) = parse_name(item.clone())? else {
+        return Err(error2(span, "Expected an item with a name (e.g., not an `impl`-block or `use`-statement)"));
+    };
+
+    // Emit it
+    match callback {
+        Some(callback) => {
+            let mut args = TokenStream::from_iter([TokenTree::Ident(name), TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+            args.extend(item);
+            Ok(callback.call_item(args))
+        },
+        None => Ok(TokenStream::from(TokenTree::Ident(name))),
+    }
+}
@@ -0,0 +1,47 @@
+//  ITEM PARTS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for decomposing an item into labeled parts.
+//
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::items::{ItemHead, Tail, parse_head};
+use crate::utils::{Callback, part};
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`item_parts()`](super::item_parts())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the item and the callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the callback with the labeled parts of the item.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn item_parts(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (item, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let span: Span = item.clone().into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+    let Some(callback) = callback else {
+        return Err(error2(span, "Expected '=>' and a callback macro after the item"));
+    };
+    let ItemHead { attrs, vis, qualifiers, keyword, name, rest } = parse_head(item)?;
+    let Tail { generics, signature, where_, body } = Tail::parse(rest, span)?;
+
+    // Call the callback with the parts
+    let mut args = TokenStream::new();
+    args.extend(part("attrs", attrs));
+    args.extend(part("vis", vis));
+    args.extend(part("qualifiers", qualifiers));
+    args.extend(part("keyword", keyword));
+    args.extend(part("name", name.map(|name| TokenStream::from(TokenTree::Ident(name))).unwrap_or_default()));
+    args.extend(part("generics", generics));
+    args.extend(part("signature", signature));
+    args.extend(part("where", where_));
+    args.extend(part("body", body));
+    Ok(callback.call_item(args))
+}
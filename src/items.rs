@@ -0,0 +1,599 @@
+//  ITEMS.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines helpers for parsing items, shared by the macros that operate on them.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::generics::split_top_level;
+use crate::utils::{Callback, part};
+
+
+/***** CONSTANTS *****/
+/// The keywords that may appear before an item's keyword (besides `const` and `extern`, which
+/// may also be keywords themselves).
+const QUALIFIERS: [&str; 5] = ["async", "auto", "default", "safe", "unsafe"];
+/// The keywords of items, which are followed by the item's name (if any).
+const KEYWORDS: [&str; 11] = ["const", "enum", "fn", "impl", "mod", "static", "struct", "trait", "type", "union", "use"];
+/// The keywords of items that have no name.
+const UNNAMED: [&str; 2] = ["impl", "use"];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Parses any number of outer attributes.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The parsed attributes, as-is.
+///
+/// # Errors
+/// This function errors if a `#` is not followed by an attribute in square brackets.
+pub fn parse_attrs(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<TokenStream, Diagnostic2> {
+    let mut attrs = TokenStream::new();
+    while let Some(pound) = iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '#')) {
+        match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => attrs.extend([pound, TokenTree::Group(group)]),
+            Some(tt) => return Err(error2(tt.span(), "Expected an attribute in square brackets")),
+            None => return Err(error2(pound.span(), "Expected an attribute in square brackets")),
+        }
+    }
+    Ok(attrs)
+}
+
+/// Parses an optional visibility.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The parsed visibility, which is empty if there is none.
+pub fn parse_vis(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> TokenStream {
+    let mut vis = TokenStream::new();
+    match iter.peek() {
+        Some(TokenTree::Ident(ident)) if ident == "pub" => {
+            vis.extend(iter.next());
+            if let Some(TokenTree::Group(group)) = iter.peek()
+                && group.delimiter() == Delimiter::Parenthesis
+            {
+                vis.extend(iter.next());
+            }
+        },
+        // Allow visibilities given as `$vis:vis`
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+            vis.extend(group.stream());
+            iter.next();
+        },
+        _ => {},
+    }
+    vis
+}
+
+/// Generates a comma-separated list of rows of labeled parts, each in curly brackets (e.g.,
+/// `{ name = [x], ty = [u8], }, { name = [y], ty = [u16], }`).
+///
+/// # Arguments
+/// - `rows`: The names of the parts and their values, once for every element in the list.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the list.
+pub fn part_list(rows: Vec<Vec<(&str, TokenStream)>>) -> TokenStream {
+    let mut list = TokenStream::new();
+    for (i, row) in rows.into_iter().enumerate() {
+        if i > 0 {
+            list.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        let mut parts = TokenStream::new();
+        for (label, tokens) in row {
+            parts.extend(part(label, tokens));
+        }
+        list.extend([TokenTree::Group(Group::new(Delimiter::Brace, parts))]);
+    }
+    list
+}
+
+/// Fills in a template by expanding its repetitions (`#( ... )*`) once for every row, replacing
+/// `#name`-placeholders in them with that row's values.
+///
+/// Repetitions may have a separator before the `*` (e.g., `#( ... ),*`), but cannot be nested.
+/// Other uses of `#` (e.g., in `#[derive(...)]`) are kept as-is.
+///
+/// # Arguments
+/// - `template`: The template to fill in.
+/// - `rows`: The names of the placeholders and their values, once for every repetition.
+/// - `row`: The row to fill in placeholders with if we are in a repetition, or else [`None`].
+///
+/// # Returns
+/// The filled-in template.
+///
+/// # Errors
+/// This function errors if the template has nested or malformed repetitions, if it uses a
+/// placeholder that does not exist or if it uses one outside of a repetition.
+pub fn fill_template(template: TokenStream, rows: &[Vec<(&str, TokenStream)>], row: Option<&[(&str, TokenStream)]>) -> Result<TokenStream, Diagnostic2> {
+    let mut res = TokenStream::new();
+    let mut iter = template.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            // Repetitions
+            TokenTree::Punct(p) if p.as_char() == '#' && matches!(iter.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis) => {
+                let Some(TokenTree::Group(group)) = iter.next() else { unreachable!() };
+                if row.is_some() {
+                    return Err(error2(group.span(), "Repetitions cannot be nested"));
+                }
+                let sep: Option<TokenTree> = match iter.next() {
+                    Some(TokenTree::Punct(p)) if p.as_char() == '*' => None,
+                    Some(TokenTree::Punct(p)) => {
+                        let sep = TokenTree::Punct(p);
+                        match iter.next() {
+                            Some(TokenTree::Punct(p)) if p.as_char() == '*' => {},
+                            Some(tt) => return Err(error2(tt.span(), "Expected '*'")),
+                            None => return Err(error2(sep.span(), "Expected '*'")),
+                        }
+                        Some(sep)
+                    },
+                    Some(tt) => return Err(error2(tt.span(), "Expected either a separator or '*'")),
+                    None => return Err(error2(group.span(), "Expected either a separator or '*'")),
+                };
+                for (i, values) in rows.iter().enumerate() {
+                    if i > 0
+                        && let Some(sep) = &sep
+                    {
+                        res.extend([sep.clone()]);
+                    }
+                    res.extend(fill_template(group.stream(), rows, Some(values))?);
+                }
+            },
+
+            // Placeholders
+            TokenTree::Punct(p) if p.as_char() == '#' && matches!(iter.peek(), Some(TokenTree::Ident(_))) => {
+                let Some(TokenTree::Ident(ident)) = iter.next() else { unreachable!() };
+                let Some(values) = row else {
+                    return Err(error2(ident.span(), &format!("Placeholders can only be used in repetitions (e.g., `#( #{ident} )*`)")));
+                };
+                let name: String = ident.to_string();
+                match values.iter().find(|(placeholder, _)| *placeholder == name) {
+                    Some((_, value)) => res.extend(value.clone()),
+                    None => {
+                        let names: Vec<String> = values.iter().map(|(placeholder, _)| format!("`#{placeholder}`")).collect();
+                        return Err(error2(ident.span(), &format!("Unknown placeholder `#{name}` (expected one of {})", names.join(", "))));
+                    },
+                }
+            },
+
+            // The rest
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), fill_template(group.stream(), rows, row)?);
+                new.set_span(group.span());
+                res.extend([TokenTree::Group(new)]);
+            },
+            tt => res.extend([tt]),
+        }
+    }
+    Ok(res)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines what to do with the output of macros that iterate over the parts of an item.
+pub enum Output {
+    /// Pass the output to a callback macro.
+    Callback(Callback),
+    /// Fill in a template with repetitions that are expanded once per part (see
+    /// [`fill_template()`]).
+    Template(TokenStream),
+}
+
+/// Splits the input to a macro into the item and the (optional) [`Output`].
+///
+/// The output is given after the last `=>` outside of any group, as either a callback or a
+/// template in curly brackets.
+///
+/// # Arguments
+/// - `input`: The input to the macro.
+///
+/// # Returns
+/// The tokens before the `=>` and the parsed Output, if any.
+///
+/// # Errors
+/// This function errors if the callback is malformed.
+pub fn split_output(input: TokenStream) -> Result<(TokenStream, Option<Output>), Diagnostic2> {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    if let [.., TokenTree::Punct(eq), TokenTree::Punct(gt), TokenTree::Group(group)] = tokens.as_slice()
+        && eq.as_char() == '='
+        && eq.spacing() == Spacing::Joint
+        && gt.as_char() == '>'
+        && group.delimiter() == Delimiter::Brace
+    {
+        let template: TokenStream = group.stream();
+        tokens.truncate(tokens.len() - 3);
+        return Ok((tokens.into_iter().collect(), Some(Output::Template(template))));
+    }
+    let (item, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(tokens.into_iter().collect())?;
+    Ok((item, callback.map(Output::Callback)))
+}
+
+/// Defines the head of an item, i.e., everything up to and including its name.
+pub struct ItemHead {
+    /// The outer attributes of the item (including doc comments), as-is.
+    pub attrs:      TokenStream,
+    /// The visibility of the item, which is empty if it is private.
+    pub vis:        TokenStream,
+    /// The qualifiers before the item's keyword (e.g., `const unsafe extern "C"`).
+    pub qualifiers: TokenStream,
+    /// The keyword of the item (e.g., `fn` or `struct`).
+    ///
+    /// This may consist of multiple tokens for `static mut`, `extern crate`, `macro_rules!` and
+    /// `extern`-blocks (including their ABI, e.g., `extern "C"`).
+    pub keyword:    TokenStream,
+    /// The name of the item, if it has one (i.e., it is not an `impl`-block, a `use`-statement, an
+    /// `extern`-block or a `const _`).
+    pub name:       Option<Ident>,
+    /// The remaining tokens of the item, after its name (or its keyword if it has no name).
+    pub rest:       Vec<TokenTree>,
+}
+
+/// Parses the head of an item.
+///
+/// Items given as `$item:item`, which end up as a single invisible group, are unwrapped first.
+///
+/// # Arguments
+/// - `item`: The item to parse.
+///
+/// # Returns
+/// The parsed [`ItemHead`].
+///
+/// # Errors
+/// This function errors if the tokens do not start like an item.
+pub fn parse_head(item: TokenStream) -> Result<ItemHead, Diagnostic2> {
+    // Unwrap items given as `$item:item`
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+    while let [TokenTree::Group(group)] = tokens.as_slice()
+        && group.delimiter() == Delimiter::None
+    {
+        tokens = group.stream().into_iter().collect();
+    }
+    let span: Span = tokens.first().map(TokenTree::span).unwrap_or_else(Span::call_site);
+    let mut iter = tokens.into_iter().peekable();
+
+    // Parse the attributes and the visibility
+    let attrs: TokenStream = parse_attrs(&mut iter)?;
+    let vis: TokenStream = parse_vis(&mut iter);
+
+    // Parse the qualifiers until we find the keyword
+    let mut qualifiers = TokenStream::new();
+    let mut keyword = TokenStream::new();
+    let (kw, named): (Ident, bool) = loop {
+        let ident: Ident = match iter.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tt) => return Err(error2(tt.span(), "Expected an item")),
+            None => return Err(error2(span, "Expected an item")),
+        };
+        let next: Option<String> = match iter.peek() {
+            Some(TokenTree::Ident(next)) => Some(next.to_string()),
+            _ => None,
+        };
+        match ident.to_string().as_str() {
+            "extern" if next.as_deref() == Some("crate") => {
+                keyword.extend([TokenTree::Ident(ident.clone())]);
+                keyword.extend(iter.next());
+                break (ident, true);
+            },
+            "extern" => {
+                // Either a qualifier of a function (`extern "C" fn`) or an `extern`-block
+                let mut abi = TokenStream::from(TokenTree::Ident(ident.clone()));
+                abi.extend(iter.next_if(|tt| matches!(tt, TokenTree::Literal(_))));
+                if matches!(iter.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace) {
+                    keyword.extend(abi);
+                    break (ident, false);
+                }
+                qualifiers.extend(abi);
+            },
+            // `const fn` and `const unsafe fn`, but not `const FOO` or `const _`
+            "const" if next.as_deref().is_some_and(|next| next == "fn" || next == "extern" || QUALIFIERS.contains(&next)) => {
+                qualifiers.extend([TokenTree::Ident(ident)]);
+            },
+            "static" => {
+                keyword.extend([TokenTree::Ident(ident.clone())]);
+                keyword.extend(iter.next_if(|tt| matches!(tt, TokenTree::Ident(ident) if ident == "mut")));
+                break (ident, true);
+            },
+            "macro_rules" => {
+                keyword.extend([TokenTree::Ident(ident.clone())]);
+                match iter.next() {
+                    Some(TokenTree::Punct(p)) if p.as_char() == '!' => keyword.extend([TokenTree::Punct(p)]),
+                    Some(tt) => return Err(error2(tt.span(), "Expected '!'")),
+                    None => return Err(error2(ident.span(), "Expected '!'")),
+                }
+                break (ident, true);
+            },
+            name if QUALIFIERS.contains(&name) => qualifiers.extend([TokenTree::Ident(ident)]),
+            name if KEYWORDS.contains(&name) => {
+                keyword.extend([TokenTree::Ident(ident.clone())]);
+                break (ident, !UNNAMED.contains(&name));
+            },
+            _ => return Err(error2(ident.span(), &format!("Expected an item, found `{ident}`"))),
+        }
+    };
+
+    // Then parse the name
+    let name: Option<Ident> = if named {
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "_" => None,
+            Some(TokenTree::Ident(ident)) => Some(ident),
+            Some(tt) => return Err(error2(tt.span(), &format!("Expected the name of the item after `{kw}`"))),
+            None => return Err(error2(kw.span(), &format!("Expected the name of the item after `{kw}`"))),
+        }
+    } else {
+        None
+    };
+    Ok(ItemHead { attrs, vis, qualifiers, keyword, name, rest: iter.collect() })
+}
+
+/// Parses the name of an item.
+///
+/// # Arguments
+/// - `item`: The item to parse.
+///
+/// # Returns
+/// The name of the item, or [`None`] if it has none (i.e., it is an `impl`-block, a
+/// `use`-statement, an `extern`-block or a `const _`). For `extern crate foo as bar`, this is the
+/// alias (`bar`), as that is the name brought into scope.
+///
+/// # Errors
+/// This function errors if the tokens do not start like an item.
+pub fn parse_name(item: TokenStream) -> Result<Option<Ident>, Diagnostic2> {
+    let head: ItemHead = parse_head(item)?;
+    if !head.keyword.into_iter().any(|tt| matches!(tt, TokenTree::Ident(ident) if ident == "crate")) {
+        return Ok(head.name);
+    }
+    match head.rest.as_slice() {
+        [TokenTree::Ident(as_), TokenTree::Ident(alias), ..] if as_ == "as" => Ok(if alias == "_" { None } else { Some(alias.clone()) }),
+        [TokenTree::Ident(as_), rest @ ..] if as_ == "as" => {
+            Err(error2(rest.first().map(TokenTree::span).unwrap_or_else(|| as_.span()), "Expected the alias of the crate after `as`"))
+        },
+        _ => Ok(head.name),
+    }
+}
+
+
+
+/// Defines the parts of an item after its name.
+pub struct Tail {
+    /// The generic parameters, without the angle brackets.
+    pub generics:  TokenStream,
+    /// Anything between the generics and the where-clause (e.g., the parameters and return type of
+    /// a function, or the type of a constant and its value).
+    pub signature: TokenStream,
+    /// The predicates of the where-clause, without the `where`.
+    pub where_:    TokenStream,
+    /// The body of the item, including its curly brackets, or a semicolon.
+    pub body:      TokenStream,
+}
+impl Tail {
+    /// Parses a Tail from the tokens of an item after its name.
+    ///
+    /// # Arguments
+    /// - `rest`: The tokens to parse.
+    /// - `span`: A span to report errors at if `rest` is empty.
+    ///
+    /// # Returns
+    /// The parsed Tail.
+    ///
+    /// # Errors
+    /// This function errors if the generics are unterminated or the item has no body.
+    pub fn parse(mut rest: Vec<TokenTree>, span: Span) -> Result<Self, Diagnostic2> {
+        // The body is the final group in curly brackets or the final semicolon
+        let body: TokenStream = match rest.pop() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => TokenStream::from(TokenTree::Group(group)),
+            Some(TokenTree::Punct(p)) if p.as_char() == ';' => TokenStream::from(TokenTree::Punct(p)),
+            Some(tt) => return Err(error2(tt.span(), "Expected the item to end with either a body in curly brackets or ';'")),
+            None => return Err(error2(span, "Expected the item to end with either a body in curly brackets or ';'")),
+        };
+        let mut iter = rest.into_iter().peekable();
+
+        // Parse the generics, if any
+        let mut generics = TokenStream::new();
+        if let Some(open) = iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '<')) {
+            let mut depth: usize = 1;
+            let mut prev: Option<Punct> = None;
+            loop {
+                let tt: TokenTree = match iter.next() {
+                    Some(tt) => tt,
+                    None => return Err(error2(open.span(), "Unterminated generics (expected '>')")),
+                };
+                if let TokenTree::Punct(p) = &tt {
+                    // Skip the `>` of `->`
+                    let arrow: bool = prev.as_ref().is_some_and(|prev| prev.as_char() == '-' && prev.spacing() == Spacing::Joint);
+                    match p.as_char() {
+                        '<' => depth += 1,
+                        '>' if !arrow => depth -= 1,
+                        _ => {},
+                    }
+                    prev = Some(p.clone());
+                } else {
+                    prev = None;
+                }
+                if depth == 0 {
+                    break;
+                }
+                generics.extend([tt]);
+            }
+        }
+
+        // Then the signature up to the where-clause, and the where-clause itself
+        let mut signature = TokenStream::new();
+        for tt in iter.by_ref() {
+            if matches!(&tt, TokenTree::Ident(ident) if ident == "where") {
+                break;
+            }
+            signature.extend([tt]);
+        }
+
+        // For type aliases, the where-clause may also come before the aliased type (e.g.,
+        // `type A<T> where T: Clone = Vec<T>;`). Associated types in bounds are always nested in
+        // angle brackets, so a top-level `=` ends the where-clause.
+        let mut where_ = TokenStream::new();
+        let mut depth: usize = 0;
+        let mut prev: Option<Punct> = None;
+        while let Some(tt) = iter.next() {
+            if let TokenTree::Punct(p) = &tt {
+                let arrow: bool = prev.as_ref().is_some_and(|prev| prev.as_char() == '-' && prev.spacing() == Spacing::Joint);
+                match p.as_char() {
+                    '<' => depth += 1,
+                    '>' if !arrow => depth = depth.saturating_sub(1),
+                    '=' if depth == 0 => {
+                        signature.extend([tt]);
+                        signature.extend(iter.by_ref());
+                        break;
+                    },
+                    _ => {},
+                }
+                prev = Some(p.clone());
+            } else {
+                prev = None;
+            }
+            where_.extend([tt]);
+        }
+        Ok(Self { generics, signature, where_, body })
+    }
+}
+
+
+
+/// Defines a single field of a struct, union or enum variant.
+pub struct Field {
+    /// The attributes of the field (including doc comments), as-is.
+    pub attrs: TokenStream,
+    /// The visibility of the field, which is empty if it is private.
+    pub vis:   TokenStream,
+    /// The name of the field, or [`None`] if it is a field of a tuple struct.
+    pub name:  Option<Ident>,
+    /// The type of the field.
+    pub ty:    TokenStream,
+}
+
+/// Parses the fields of a struct, union or enum variant.
+///
+/// # Arguments
+/// - `group`: The group containing the fields. If it is in curly brackets, the fields are
+///   parsed as named fields; otherwise, they are parsed as tuple fields.
+///
+/// # Returns
+/// The parsed [`Field`]s.
+///
+/// # Errors
+/// This function errors if any of the fields are malformed.
+pub fn parse_fields(group: &Group) -> Result<Vec<Field>, Diagnostic2> {
+    let named: bool = group.delimiter() == Delimiter::Brace;
+    let mut fields: Vec<Field> = Vec::new();
+    for tokens in split_top_level(group.stream(), ',') {
+        let mut iter = tokens.into_iter().peekable();
+        let attrs: TokenStream = parse_attrs(&mut iter)?;
+        let vis: TokenStream = parse_vis(&mut iter);
+        let name: Option<Ident> = if named {
+            let name: Ident = match iter.next() {
+                Some(TokenTree::Ident(ident)) => ident,
+                Some(tt) => return Err(error2(tt.span(), "Expected the name of a field")),
+                None => return Err(error2(group.span(), "Expected the name of a field")),
+            };
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ':' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected ':'")),
+                None => return Err(error2(name.span(), "Expected ':' and the type of the field")),
+            }
+            Some(name)
+        } else {
+            None
+        };
+        let ty: TokenStream = iter.collect();
+        if ty.is_empty() {
+            return Err(error2(group.span(), "Expected the type of a field"));
+        }
+        fields.push(Field { attrs, vis, name, ty });
+    }
+    Ok(fields)
+}
+
+
+
+/// Defines a single variant of an enum.
+pub struct Variant {
+    /// The attributes of the variant (including doc comments), as-is.
+    pub attrs:        TokenStream,
+    /// The name of the variant.
+    pub name:         Ident,
+    /// The fields of the variant in parenthesis or curly brackets, or [`None`] if it is a unit
+    /// variant.
+    pub fields:       Option<Group>,
+    /// The explicit discriminant of the variant, without the `=`.
+    pub discriminant: Option<TokenStream>,
+}
+
+/// Parses the variants of an enum.
+///
+/// # Arguments
+/// - `group`: The group containing the variants.
+///
+/// # Returns
+/// The parsed [`Variant`]s.
+///
+/// # Errors
+/// This function errors if any of the variants are malformed.
+pub fn parse_variants(group: &Group) -> Result<Vec<Variant>, Diagnostic2> {
+    // Split the variants on commas. Unlike fields, there are no generic types with commas at this
+    // level, and discriminants may contain `<` (e.g., `1 << 2`).
+    let mut chunks: Vec<Vec<TokenTree>> = Vec::new();
+    let mut chunk: Vec<TokenTree> = Vec::new();
+    for tt in group.stream() {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => chunks.push(std::mem::take(&mut chunk)),
+            tt => chunk.push(tt),
+        }
+    }
+    chunks.push(chunk);
+    chunks.retain(|chunk| !chunk.is_empty());
+
+    // Parse every variant
+    let mut variants: Vec<Variant> = Vec::with_capacity(chunks.len());
+    for tokens in chunks {
+        let mut iter = tokens.into_iter().peekable();
+        let attrs: TokenStream = parse_attrs(&mut iter)?;
+        let name: Ident = match iter.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tt) => return Err(error2(tt.span(), "Expected the name of a variant")),
+            None => return Err(error2(group.span(), "Expected the name of a variant")),
+        };
+        let fields: Option<Group> = match iter.next_if(|tt| matches!(tt, TokenTree::Group(g) if g.delimiter() != Delimiter::None)) {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => {
+                return Err(error2(group.span(), "Expected the fields of the variant in parenthesis or curly brackets"));
+            },
+            Some(TokenTree::Group(group)) => Some(group),
+            _ => None,
+        };
+        let discriminant: Option<TokenStream> = match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {
+                let discriminant: TokenStream = iter.collect();
+                if discriminant.is_empty() {
+                    return Err(error2(p.span(), "Expected a discriminant after '='"));
+                }
+                Some(discriminant)
+            },
+            Some(tt) => return Err(error2(tt.span(), "Expected either '=', ',' or nothing")),
+            None => None,
+        };
+        variants.push(Variant { attrs, name, fields, discriminant });
+    }
+    Ok(variants)
+}
@@ -0,0 +1,194 @@
+//  KV ARGS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for normalizing named arguments into positional ones.
+//
+
+use std::collections::HashMap;
+
+use proc_macro2::{Delimiter, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::{Form, Meta, split_commas, unwrap_none};
+use crate::utils::Callback;
+
+
+/***** TOKEN PARSING *****/
+/// Defines the kinds of parameters in a schema.
+pub enum Kind {
+    /// The parameter must be given.
+    Required,
+    /// The parameter may be omitted, in which case it gets the given default value.
+    Default(TokenStream),
+    /// The parameter is a flag, which is either given (`true`) or not (`false`).
+    Flag,
+}
+
+
+
+/// Defines a single parameter in a schema.
+pub struct Param {
+    /// The name of the parameter.
+    pub name: Ident,
+    /// The kind of the parameter.
+    pub kind: Kind,
+}
+impl Param {
+    /// Parses a Param from its tokens.
+    ///
+    /// Parameters have the syntax `$name:ident`, `$name:ident = $($default:tt)*` or
+    /// `flag $name:ident`.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the parameter.
+    ///
+    /// # Returns
+    /// The parsed Param.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a parameter.
+    pub fn parse(tokens: TokenStream) -> Result<Self, Diagnostic2> {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        match tokens.as_slice() {
+            [TokenTree::Ident(name)] => Ok(Self { name: name.clone(), kind: Kind::Required }),
+            [TokenTree::Ident(flag), TokenTree::Ident(name)] if flag == "flag" => Ok(Self { name: name.clone(), kind: Kind::Flag }),
+            [TokenTree::Ident(name), TokenTree::Punct(p), default @ ..] if p.as_char() == '=' => {
+                if default.is_empty() {
+                    return Err(error2(p.span(), "Expected a default value after '='"));
+                }
+                Ok(Self { name: name.clone(), kind: Kind::Default(default.iter().cloned().collect()) })
+            },
+            [TokenTree::Ident(_), tt, ..] => Err(error2(tt.span(), "Expected either nothing or '=' and a default value")),
+            [tt, ..] => Err(error2(tt.span(), "Expected either a parameter name, or `flag` and a parameter name")),
+            [] => Err(error2(Span::call_site(), "Expected a parameter, not an empty one")),
+        }
+    }
+}
+
+
+
+
+
+/***** SCHEMAS *****/
+/// Splits the input to a macro taking a schema into its arguments, schema and callback.
+///
+/// The input has the form `$($args:tt)* { $($params),* } => $callback`.
+///
+/// # Arguments
+/// - `input`: The input to the macro.
+///
+/// # Returns
+/// The tokens of the arguments, the parameters of the schema, the callback and a span to report
+/// errors about missing arguments at.
+///
+/// # Errors
+/// This function errors if the input does not have the above form, or if the schema is invalid.
+pub fn parse_schema(input: TokenStream) -> Result<(TokenStream, Vec<Param>, Callback, Span), Diagnostic2> {
+    // Split the input into the arguments, schema and callback
+    let (head, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let mut head: Vec<TokenTree> = head.into_iter().collect();
+    let span: Span = head.last().map(TokenTree::span).unwrap_or_else(Span::call_site);
+    let Some(callback) = callback else {
+        return Err(error2(span, "Expected '=>' and a callback after the schema"));
+    };
+    let schema = match head.pop() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        _ => return Err(error2(span, "Expected arguments and then a schema wrapped in `{}`")),
+    };
+
+    // Parse the schema
+    let params: Vec<Param> = split_commas(schema.stream()).into_iter().map(Param::parse).collect::<Result<_, _>>()?;
+    for (i, param) in params.iter().enumerate() {
+        if params[..i].iter().any(|prev| prev.name == param.name) {
+            return Err(error2(param.name.span(), &format!("Duplicate parameter `{}`", param.name)));
+        }
+    }
+    Ok((unwrap_none(head.into_iter().collect()), params, callback, span))
+}
+
+/// Generates the error for an argument that is not in the schema.
+///
+/// # Arguments
+/// - `params`: The parameters of the schema.
+/// - `key`: The name of the argument.
+/// - `span`: The span of the argument.
+///
+/// # Returns
+/// A [`Diagnostic2`] with the error.
+pub fn unknown_argument(params: &[Param], key: &str, span: Span) -> Diagnostic2 {
+    let names: Vec<String> = params.iter().map(|param| format!("`{}`", param.name)).collect();
+    let msg: String = if names.is_empty() {
+        format!("Unknown argument `{key}`; expected no arguments")
+    } else {
+        format!("Unknown argument `{key}`; expected one of {}", names.join(", "))
+    };
+    error2(span, &msg)
+}
+
+/// Calls the callback with the values of all parameters in the order of the schema.
+///
+/// # Arguments
+/// - `params`: The parameters of the schema.
+/// - `values`: The values given for the parameters, by name.
+/// - `span`: The span to report missing arguments at.
+/// - `callback`: The callback to call.
+///
+/// # Returns
+/// A [`TokenStream`] with the call to the callback.
+///
+/// # Errors
+/// This function errors if a required parameter has no value.
+pub fn emit(params: Vec<Param>, mut values: HashMap<String, TokenStream>, span: Span, callback: Callback) -> Result<TokenStream, Diagnostic2> {
+    let mut args = TokenStream::new();
+    for (i, param) in params.into_iter().enumerate() {
+        if i > 0 {
+            args.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        match (values.remove(&param.name.to_string()), param.kind) {
+            (Some(value), _) | (None, Kind::Default(value)) => args.extend(value),
+            (None, Kind::Flag) => args.extend([TokenTree::Ident(Ident::new("false", param.name.span()))]),
+            (None, Kind::Required) => return Err(error2(span, &format!("Missing required argument `{}`", param.name))),
+        }
+    }
+    Ok(callback.call_item(args))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`kv_args()`](super::kv_args())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to parse for input.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the callback with the values in the order of the schema.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the arguments do not
+/// adhere to the schema.
+pub fn kv_args(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (args, params, callback, span): (TokenStream, Vec<Param>, Callback, Span) = parse_schema(input)?;
+
+    // Parse the arguments and match them to the schema
+    let mut values: HashMap<String, TokenStream> = HashMap::new();
+    for arg in split_commas(args) {
+        let arg: Meta = Meta::parse(arg)?;
+        let Some(param) = params.iter().find(|param| param.name == arg.key) else {
+            return Err(unknown_argument(&params, &arg.key, arg.span));
+        };
+        let value: TokenStream = match (&param.kind, arg.form) {
+            (Kind::Flag, Form::Flag) => TokenStream::from(TokenTree::Ident(Ident::new("true", arg.span))),
+            (Kind::Flag, _) => return Err(error2(arg.span, &format!("`{}` is a flag, and should be given as {}", arg.key, Form::Flag.show(&arg.key)))),
+            (_, Form::NameValue) => arg.value,
+            (_, _) => return Err(error2(arg.span, &format!("Expected {}", Form::NameValue.show(&arg.key)))),
+        };
+        if values.insert(arg.key.clone(), value).is_some() {
+            return Err(error2(arg.span, &format!("Duplicate argument `{}`", arg.key)));
+        }
+    }
+    emit(params, values, span, callback)
+}
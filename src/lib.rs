@@ -9,6 +9,93 @@
 //!   # Macros
 //!   This crate provides the following macros:
 //!   - `match_lit!()`: A helper macro that can further match `literal` tokens passed to declarative macros.
+//!   - `idents!()`: Pastes identifiers together (e.g., `[<get_ foo>]` becomes `get_foo`) or generates lists of identifiers from other tokens.
+//!   - `#[with_idents]`: Defines placeholders in an item that are replaced by identifiers pasted like in `idents!()`.
+//!   - `lit_len!()`: Emits the length of a string-like literal as a `usize` literal, in either bytes or characters.
+//!   - `digits_of!()`: Splits an integer literal into a list of literals encoding its digits in a chosen radix.
+//!   - `typenum_lit!()`: Converts an integer literal to the equivalent [`typenum`](https://docs.rs/typenum) type.
+//!   - `regex_check!()`: Validates that a string literal is a valid regular expression at compile time.
+//!   - `fmt_check!()`: Checks a format string literal against a list of arguments at compile time.
+//!   - `uuid_lit!()`: Parses a UUID string literal into a `u128` literal or an array of bytes.
+//!   - `duration_lit!()`: Parses a human-readable duration string literal (e.g., `"1h30m"`) into an integer literal.
+//!   - `size_lit!()`: Parses a human-readable size string literal (e.g., `"4KiB"`) into an integer literal.
+//!   - `color_lit!()`: Parses a hexadecimal color string literal (e.g., `"#ff8800"`) into its components or a packed integer literal.
+//!   - `bitpattern!()`: Parses a bit pattern with wildcards (e.g., `"0b10xx_x1x0"`) into a `(mask, value)`-pair of integer literals.
+//!   - `swap_bytes_lit!()`: Byte-swaps an integer literal at its declared width (e.g., `0x1234u16` becomes `0x3412u16`).
+//!   - `parse_lit!()`: Parses a string literal with one of a set of validators (integers, IP addresses, durations, ...) and passes the normalized components to a callback.
+//!   - `str_predicates!()`: Evaluates `contains`, `starts_with` or `ends_with` over string literals, emitting `true`/`false` or choosing between two branches.
+//!   - `calc!()`: Evaluates integer or floating-point arithmetic over literals (e.g., `4 * 1024 + 1`) and emits the result as a literal.
+//!   - `min_lit!()`/`max_lit!()`: Emits the smallest or largest of a list of numeric literals.
+//!   - `cmp_lit!()`: Compares two literals (numbers by value, strings lexicographically) and chooses between `lt`/`eq`/`gt`-branches.
+//!   - `bits_needed!()`: Emits the number of bits (or bytes) needed to represent an integer literal.
+//!   - `align_up!()`/`align_down!()`: Aligns an integer literal up or down to a power-of-two alignment (e.g., `0x1234` up to `0x1000` becomes `0x2000`).
+//!   - `range_expand!()`: Expands an integer range (e.g., `0..8` or `1..=5`, with an optional step) into a list of literals.
+//!   - `bitmask!()`: Builds an integer literal from a list of bit positions or ranges (e.g., `0, 3, 8..=11` becomes `0b1111_0000_1001`).
+//!   - `fixed_point!()`: Converts a numeric literal to a fixed-point integer literal in a given Q-format (e.g., `0.5` in `Q15` becomes `16384i16`).
+//!   - `rand_lit!()`: Generates a random integer, float or byte array literal at compile time, optionally from a fixed seed.
+//!   - `unique_id!()`: Emits an integer literal that is unique across all invocations in the same (optionally named) scope.
+//!   - `gensym!()`: Emits a fresh identifier (e.g., `__mtk_gensym_17_abcdef`) that does not collide with other generated or user-written identifiers.
+//!   - `build_time!()`: Emits the time of the build as a Unix timestamp or an RFC 3339 string literal, optionally respecting `SOURCE_DATE_EPOCH`.
+//!   - `const_assert_lit!()`: Asserts a condition over literals (e.g., `4 * 1024 <= 8192 && "a" < "b"`) at compile time, erroring with a custom message if it does not hold.
+//!   - `#[apply]`: Calls a declarative macro with the item it is placed on, allowing `macro_rules!`-macros to be used as attributes.
+//!   - `#[derive_via]`: Implements a derive with a declarative macro, by emitting the item followed by a call to the given macro with it (e.g., `#[derive_via(foo!)]`).
+//!   - `#[duplicate]`: Duplicates an item once per row of a substitution table (e.g., `#[duplicate(T = [u8, u16], N = [8, 16])]`), replacing placeholders in every copy.
+//!   - `rustc_since!()`/`#[cfg_rustc_since]`: Chooses between tokens (or keeps or removes an item) based on whether the compiler is at least a given version (e.g., `1.77`).
+//!   - `impl_for_tuples!()`: Expands a template (e.g., an `impl`) once for every tuple arity in a range, with `quote!()`-like repetitions over the elements.
+//!   - `template!()`/`expand_template!()`: Defines named token templates with parameters (and `idents!()`-like pasting), and instantiates them later.
+//!   - `#[export_tokens]`/`import_tokens!()`: Exports the tokens of an item such that they can be passed to a (decl) macro elsewhere, even in another crate.
+//!   - `respan!()`/`#[with_span]`: Replaces the span of every token in a block or item with the call site, the mixed site or the span of a given token, e.g., to make macro-generated names resolve at the call site.
+//!   - `crate_path!()`: Emits the path under which a crate is visible to the calling crate (e.g., `::renamed` if it was renamed in its `Cargo.toml`), optionally passing it to a callback.
+//!   - `#[cfg_matrix]`: Duplicates an item once per `cfg`-predicate (e.g., `feature = "std"` and `not(feature = "std")`), with different substitutions in every copy.
+//!   - `pub_macro!()`: Gives `macro_rules!`-macros path-based visibility like other items (e.g., `pub(crate) macro_rules! foo { ... }`), by generating the `#[macro_export]`- and re-export-boilerplate.
+//!   - `macro_v2!{}`: Defines `macro_rules!`-macros in an extended dialect, with named rules, `else`-arms, inline errors and built-in counting and indexing.
+//!   - `#[trace_expansion]`/`trace_tokens!()`: Prints the expansions of a `macro_rules!`-macro (or any tokens) to stderr or a file while emitting them unchanged, to debug macros.
+//!   - `attr_alias!{}`/`#[apply_alias]`: Defines named bundles of attributes (e.g., `common = #[derive(Debug, Clone)];`) and applies them to items.
+//!   - `derive_alias!{}`: Defines named sets of derives (e.g., `Standard = Debug, Clone, PartialEq;`), which are applied to items with `#[apply_alias]`.
+//!   - `item_name!()`: Extracts the name of an item (e.g., given as `$item:item`), optionally passing it and the item to a callback.
+//!   - `item_parts!()`: Decomposes an item into labeled parts (attributes, visibility, keyword, name, generics, where-clause, body, ...) and passes them to a callback.
+//!   - `generics_split!()`: Splits generics into the impl-generics, type-generics and where-clause needed for `impl`-blocks, and passes them to a callback.
+//!   - `fields_of!()`: Iterates over the fields of a struct, either by passing their names, types, indices and attributes to a callback or by expanding a template once per field.
+//!   - `variants_of!()`: Iterates over the variants of an enum, either by passing their names, fields, discriminants and attributes to a callback or by expanding a template once per variant.
+//!   - `#[strip_attrs(...)]`: Removes attributes by path (e.g., `serde::*`) from an item and its fields or variants, optionally forwarding them to a callback.
+//!   - `match_vis!()`: Given a visibility, will conditionally return a given stream of tokens based on its kind (e.g., `pub` or `pub(crate)`).
+//!   - `match_type!()`: Given any type, will conditionally return a given stream of tokens based on its syntactic kind (path, reference, slice, tuple, ...).
+//!   - `match_expr!()`: Given any expression, will conditionally return a given stream of tokens based on its outermost form (literal, path, call, block, closure, ...).
+//!   - `match_path!()`: Given any path, will conditionally return a given stream of tokens based on its shape (single or multi-segment, leading `::`/`crate`/`self`, generic arguments, last segment name).
+//!   - `match_meta!()`: Parses attribute-style arguments (flags, `key = value` pairs and `key(...)` lists) and dispatches each of them to a callback, with errors for unknown and duplicate keys.
+//!   - `kv_args!()`: Validates `name = value` arguments in any order against a schema with required, defaulted and flag parameters, and passes their values to a callback in a fixed order.
+//!   - `where_append!()`: Appends bounds to the where-clause of an item (or of generics passed to a callback), creating one if absent.
+//!   - `turbofish!()`: Converts a type path with generic arguments into its expression form (e.g., `Foo<T>` into `Foo::<T>`).
+//!   - `#[prefix_items]`/`#[rename_items]`: Renames all (or some) items in a module by adding a prefix and/or suffix or by following an explicit map, optionally keeping aliases for the old names.
+//!   - `#[feature_gated]`: Gates an item behind one or more Cargo features, both for compilation and in the docs on docs.rs.
+//!   - `overload!()`: Dispatches to one of several callback macros based on the number and literal kinds of the arguments given.
+//!   - `named_args!()`: Reorders `name: value` arguments given in any order into positional ones for a callback, filling in defaults for omitted ones.
+//!   - `strip_generics!()`: Removes the generic arguments (or only the lifetimes) from a type path (e.g., `HashMap<K, V>` into `HashMap`).
+//!   - `generics_merge!()`: Merges two sets of generics and where-clauses into one, deduplicating parameters by name and combining their bounds, and passes them to a callback.
+//!   - `type_ident!()`: Flattens a type into an identifier (e.g., `Vec<Option<u8>>` into `Vec_Option_u8`), for naming generated items after types.
+//!   - `test_matrix!()`: Generates a `#[test]`-function for every combination of a list of functions under test and a list of cases, from a single body template.
+//!   - `const_table!()`: Generates a static table of values together with a constant for the index of every entry and, optionally, a function for looking up indices by value.
+//!   - `lifetimes_of!()`: Extracts the distinct lifetimes mentioned in a type or item (e.g., `'a, 'b` from `&'a Foo<'b>`), for redeclaring them on generated items.
+//!   - `compile_warning!()`: Emits a warning with a custom message at compile time, optionally pointing to a given token.
+//!   - `compile_note!()`: Emits an error, warning or note at compile time with any number of notes and help messages attached, each optionally pointing to their own token.
+//!   - `error_at!()`: Emits a compile error pointing to a given token, for precise errors in declarative macros.
+//!   - `debug_tokens!()`: Prints the tokens it is given (optionally pretty-printed) to stderr while compiling, tagged with the location of the call, and emits them unchanged.
+//!   - `dump_expansion!()`: Writes the tokens it is given (optionally pretty-printed) to a file in the target directory while compiling, and emits them unchanged, such that expansions can be diffed between builds.
+//!   - `assert_expands_to!()`: Asserts at compile time that a macro taking a callback expands to the expected tokens, erroring at the first token that differs.
+//!   - `include_tokens!()`: Splices the contents of a file (relative to the invoking file) into the token stream, optionally pasting identifiers in it like `idents!()`.
+//!   - `env_lit!()`: Reads an environment variable at compile time and emits it as a literal of a given kind (e.g., `env_lit!("MAX_CONN" as usize)`).
+//!   - `env_or!()`: Like `env_lit!()`, but falls back to a default literal that also determines the kind (e.g., `env_or!("LOG_BUF", 4096usize)`) if the variable is not set.
+//!   - `option_env_match!()`: Given the name of an environment variable, will conditionally return a given stream of tokens based on whether it is set at compile time and on its value.
+//!   - `glob_files!()`: Expands a template once for every file matching a glob relative to the invoking file, with placeholders for the path, stem and a sanitized identifier of each file.
+//!   - `cargo_manifest!()`: Reads a field from the calling crate's `Cargo.toml` (e.g., `package.authors` or a key in `package.metadata`) and emits it as a literal or an identifier.
+//!   - `crate_version_parts!()`: Emits the version of the calling crate as separate major/minor/patch integer literals and a pre-release string literal, or passes them to a callback.
+//!   - `span_location!()`: Emits the file, line and column of a given token (or of the call site) as literals, e.g., to make panics generated by declarative macros point to the code of the user.
+//!   - `meta_count!()`/`meta_index!()`/`meta_ignore!()`: Stable polyfills for `${count($x)}`, `${index()}` and `${ignore($x)}` in `macro_rules!`-repetitions.
+//!   - `ident_case_check!()`: Checks at compile time that identifiers follow a naming convention (`snake_case`, `UpperCamelCase` or `SCREAMING_SNAKE_CASE`), e.g., to enforce the API guidelines on names chosen by users of a declarative macro.
+//!   - `keyword_check!()`/`is_valid_ident!()`: Returns `true` or `false`, or chooses between two branches, based on whether an identifier is a (strict, reserved or weak) keyword or whether a string literal is a valid identifier.
+//!   - `str_switch!()`: Generates an efficient runtime `match` over a string, which dispatches on the length and then on individual bytes instead of comparing the string to every arm in turn.
+//!   - `doc_fmt!()`: Formats a string from a format string with literal and identifier arguments at compile time, and emits it as a literal or as `#[doc = "..."]`-attributes on the given item, e.g., to document items generated by declarative macros.
+//!   - `toolkit!()`: Expands the other macros of this crate and `idents!()`-like pastes anywhere in its input, such that they can be nested in one another.
 //!
 //!
 //!   # Usage
@@ -29,10 +116,145 @@
 //!   cargo doc --no-deps --open
 //!   ```
 //!
+//!   If you are writing procedural macros yourself, you can also call the engines behind some of the macros (e.g., pasting identifiers or classifying literals) from your own code. Because procedural macro crates can only export macros, these live in the separate `macro-toolkit-core`-crate:
+//!   ```toml
+//!   [dependency]
+//!   macro-toolkit-core = { git = "https://github.com/Lut99/macro-toolkit-rs", features = ["idents", "match_lit"] }
+//!   ```
+//!
 //!
 //!   # Features
 //!   This crate has the following features:
-//!   - `macro_lit`: Enables the compilation of the `macro_lit!()`-macro _(default)._
+//!   - `full`: Enables the compilation of all macros below. `all` is an alias of this feature.
+//!   - `match_lit`: Enables the compilation of the `match_lit!()`-macro _(default)._
+//!   - `idents`: Enables the compilation of the `idents!()`-macro and the `#[with_idents]`-attribute _(default)._
+//!   - `lit_len`: Enables the compilation of the `lit_len!()`-macro.
+//!   - `digits_of`: Enables the compilation of the `digits_of!()`-macro.
+//!   - `typenum_lit`: Enables the compilation of the `typenum_lit!()`-macro.
+//!   - `regex_check`: Enables the compilation of the `regex_check!()`-macro.
+//!   - `fmt_check`: Enables the compilation of the `fmt_check!()`-macro.
+//!   - `uuid_lit`: Enables the compilation of the `uuid_lit!()`-macro.
+//!   - `duration_lit`: Enables the compilation of the `duration_lit!()`-macro.
+//!   - `size_lit`: Enables the compilation of the `size_lit!()`-macro.
+//!   - `color_lit`: Enables the compilation of the `color_lit!()`-macro.
+//!   - `bitpattern`: Enables the compilation of the `bitpattern!()`-macro.
+//!   - `swap_bytes_lit`: Enables the compilation of the `swap_bytes_lit!()`-macro.
+//!   - `parse_lit`: Enables the compilation of the `parse_lit!()`-macro.
+//!   - `str_predicates`: Enables the compilation of the `str_predicates!()`-macro.
+//!   - `calc`: Enables the compilation of the `calc!()`-macro.
+//!   - `min_max_lit`: Enables the compilation of the `min_lit!()`- and `max_lit!()`-macros.
+//!   - `cmp_lit`: Enables the compilation of the `cmp_lit!()`-macro.
+//!   - `bits_needed`: Enables the compilation of the `bits_needed!()`-macro.
+//!   - `align`: Enables the compilation of the `align_up!()`- and `align_down!()`-macros.
+//!   - `range_expand`: Enables the compilation of the `range_expand!()`-macro.
+//!   - `bitmask`: Enables the compilation of the `bitmask!()`-macro.
+//!   - `fixed_point`: Enables the compilation of the `fixed_point!()`-macro.
+//!   - `rand_lit`: Enables the compilation of the `rand_lit!()`-macro.
+//!   - `unique_id`: Enables the compilation of the `unique_id!()`-macro.
+//!   - `gensym`: Enables the compilation of the `gensym!()`-macro.
+//!   - `build_time`: Enables the compilation of the `build_time!()`-macro.
+//!   - `const_assert_lit`: Enables the compilation of the `const_assert_lit!()`-macro.
+//!   - `apply`: Enables the compilation of the `#[apply]`-attribute.
+//!   - `derive_via`: Enables the compilation of the `#[derive_via]`-attribute.
+//!   - `duplicate`: Enables the compilation of the `#[duplicate]`-attribute.
+//!   - `rustc_since`: Enables the compilation of the `rustc_since!()`-macro and the `#[cfg_rustc_since]`-attribute.
+//!   - `impl_for_tuples`: Enables the compilation of the `impl_for_tuples!()`-macro.
+//!   - `template`: Enables the compilation of the `template!()`- and `expand_template!()`-macros.
+//!   - `export_tokens`: Enables the compilation of the `#[export_tokens]`-attribute and the `import_tokens!()`-macro.
+//!   - `respan`: Enables the compilation of the `respan!()`-macro and the `#[with_span]`-attribute.
+//!   - `crate_path`: Enables the compilation of the `crate_path!()`-macro.
+//!   - `cfg_matrix`: Enables the compilation of the `#[cfg_matrix]`-attribute.
+//!   - `pub_macro`: Enables the compilation of the `pub_macro!()`-macro.
+//!   - `macro_v2`: Enables the compilation of the `macro_v2!{}`-macro.
+//!   - `trace_expansion`: Enables the compilation of the `#[trace_expansion]`-attribute and the `trace_tokens!()`-macro.
+//!   - `attr_alias`: Enables the compilation of the `attr_alias!{}`-macro and the `#[apply_alias]`-attribute.
+//!   - `derive_alias`: Enables the compilation of the `derive_alias!{}`-macro.
+//!   - `item_name`: Enables the compilation of the `item_name!()`-macro.
+//!   - `item_parts`: Enables the compilation of the `item_parts!()`-macro.
+//!   - `generics_split`: Enables the compilation of the `generics_split!()`-macro.
+//!   - `fields_of`: Enables the compilation of the `fields_of!()`-macro.
+//!   - `variants_of`: Enables the compilation of the `variants_of!()`-macro.
+//!   - `strip_attrs`: Enables the compilation of the `#[strip_attrs(...)]`-attribute.
+//!   - `match_vis`: Enables the compilation of the `match_vis!()`-macro.
+//!   - `match_type`: Enables the compilation of the `match_type!()`-macro.
+//!   - `match_expr`: Enables the compilation of the `match_expr!()`-macro.
+//!   - `match_path`: Enables the compilation of the `match_path!()`-macro.
+//!   - `match_meta`: Enables the compilation of the `match_meta!()`-macro.
+//!   - `kv_args`: Enables the compilation of the `kv_args!()`-macro.
+//!   - `where_append`: Enables the compilation of the `where_append!()`-macro.
+//!   - `turbofish`: Enables the compilation of the `turbofish!()`-macro.
+//!   - `rename_items`: Enables the compilation of the `#[prefix_items]`- and `#[rename_items]`-attributes.
+//!   - `feature_gated`: Enables the compilation of the `#[feature_gated]`-attribute.
+//!   - `overload`: Enables the compilation of the `overload!()`-macro.
+//!   - `named_args`: Enables the compilation of the `named_args!()`-macro.
+//!   - `strip_generics`: Enables the compilation of the `strip_generics!()`-macro.
+//!   - `generics_merge`: Enables the compilation of the `generics_merge!()`-macro.
+//!   - `type_ident`: Enables the compilation of the `type_ident!()`-macro.
+//!   - `test_matrix`: Enables the compilation of the `test_matrix!()`-macro.
+//!   - `const_table`: Enables the compilation of the `const_table!()`-macro.
+//!   - `lifetimes_of`: Enables the compilation of the `lifetimes_of!()`-macro.
+//!   - `compile_warning`: Enables the compilation of the `compile_warning!()`-macro.
+//!   - `compile_note`: Enables the compilation of the `compile_note!()`-macro.
+//!   - `error_at`: Enables the compilation of the `error_at!()`-macro.
+//!   - `debug_tokens`: Enables the compilation of the `debug_tokens!()`-macro.
+//!   - `dump_expansion`: Enables the compilation of the `dump_expansion!()`-macro.
+//!   - `assert_expands_to`: Enables the compilation of the `assert_expands_to!()`-macro.
+//!   - `include_tokens`: Enables the compilation of the `include_tokens!()`-macro.
+//!   - `env_lit`: Enables the compilation of the `env_lit!()`-macro.
+//!   - `env_or`: Enables the compilation of the `env_or!()`-macro.
+//!   - `option_env_match`: Enables the compilation of the `option_env_match!()`-macro.
+//!   - `glob_files`: Enables the compilation of the `glob_files!()`-macro.
+//!   - `cargo_manifest`: Enables the compilation of the `cargo_manifest!()`-macro.
+//!   - `crate_version_parts`: Enables the compilation of the `crate_version_parts!()`-macro.
+//!   - `span_location`: Enables the compilation of the `span_location!()`-macro.
+//!   - `metavar_exprs`: Enables the compilation of the `meta_count!()`-, `meta_index!()`- and `meta_ignore!()`-macros.
+//!   - `ident_case_check`: Enables the compilation of the `ident_case_check!()`-macro.
+//!   - `ident_predicates`: Enables the compilation of the `keyword_check!()`- and `is_valid_ident!()`-macros.
+//!   - `str_switch`: Enables the compilation of the `str_switch!()`-macro.
+//!   - `doc_fmt`: Enables the compilation of the `doc_fmt!()`-macro.
+//!   - `toolkit`: Enables the compilation of the `toolkit!()`-macro. This enables the `idents`- and `match_lit`-features; other macros are only expanded by it if their own features are enabled.
+//!   - `deterministic`: Makes `rand_lit!()`, `gensym!()` and `build_time!()` produce the same output in every compilation, for reproducible builds.
+//!   - `span_locations`: Enables passing a token to `span_location!()` to find its location, instead of only that of the call site. This enables the `span-locations`-feature of `proc-macro2`.
+//!   - `nightly_diagnostics`: Emits all errors of all macros, and the diagnostics of `compile_warning!()` and `compile_note!()`, as native diagnostics instead of as `compile_error!()`s and deprecation warnings. This shows their notes and help messages as part of the diagnostic. Requires a nightly compiler.
+//!
+//!   Every macro is behind its own feature, such that only the macros (and heavyweight dependencies like `syn` and `regex-syntax`) that you use are compiled. By default, only `match_lit!()` and `idents!()` are enabled; enable `full` to get all of them:
+//!   ```toml
+//!   [dependency]
+//!   macro-toolkit = { git = "https://github.com/Lut99/macro-toolkit-rs", features = ["full"] }
+//!   ```
+//!
+//!
+//!   # Tracing
+//!   To find out what a (nested) call to one of the macros expands to, set the `MACRO_TOOLKIT_TRACE` environment variable while compiling. Every macro then logs its input and output tokens, tagged with the location of the call:
+//!   - `MACRO_TOOLKIT_TRACE=1` (or `stderr`) prints them to stderr; and
+//!   - `MACRO_TOOLKIT_TRACE=<path>` appends them to the file at `<path>`, relative to the directory of the `Cargo.toml` of the crate being compiled.
+//!
+//!   For example:
+//!   ```sh
+//!   MACRO_TOOLKIT_TRACE=1 cargo build
+//!   ```
+//!   prints something like:
+//!   ```text
+//!   [MACRO_TOOLKIT_TRACE] idents!() at src/lib.rs:2:1
+//!       input:  fn [<get_ foo>]() {}
+//!       output: fn get_foo() {}
+//!   ```
+//!
+//!   Note that Cargo does not recompile crates when only this variable changes, so you may have to touch the crate you want to trace first (or run `cargo clean -p <crate>`).
+//!
+//!
+//!   # Error codes
+//!   Some errors emitted by the macros come with a code (e.g., `error: [MTK0003] ...`), which identifies the kind of mistake. They are explained below:
+//!   - `MTK0001`: `idents!()` was asked to paste a string literal with escape sequences (e.g., `"a\nb"`), which cannot be part of an identifier.
+//!   - `MTK0002`: `idents!()` was asked to paste something other than an identifier, a literal or a type in parenthesis (e.g., `[<get_ ::foo>]`), or there were tokens after the closing `>`.
+//!   - `MTK0003`: The identifier pasted by `idents!()` is not a valid identifier, because it does not start with a letter or `_` (e.g., `[<1 foo>]`) or contains other characters than letters, digits and `_`.
+//!   - `MTK0004`: `idents!()` was asked to paste nothing (e.g., `[<>]`).
+//!   - `MTK0005`: A pattern for generating a list of identifiers in `idents!()` is malformed (e.g., it is not followed by `...`).
+//!   - `MTK0006`: The placeholders given to `#[with_idents]` are malformed (e.g., missing a `=` or given twice).
+//!   - `MTK0007`: `match_lit!()` was not given a literal to match (e.g., an identifier or an expression).
+//!   - `MTK0008`: The branches given to `match_lit!()` are malformed (e.g., missing a `=>` or not wrapped in curly brackets).
+//!   - `MTK0009`: A branch of `match_lit!()` (or `overload!()`) matches on an unknown kind of literal.
+//!   - `MTK0010`: None of the branches of `match_lit!()` matches the given literal. Add a `_`-branch to handle any literal, or an `else`-branch to emit a custom error instead.
 //!
 //!
 //!   # Contribution
@@ -44,11 +266,195 @@
 //
 
 // Modules
-#[cfg(feature = "idents")]
-mod idents;
-#[cfg(feature = "match_lit")]
-mod match_lit;
+#[cfg(feature = "align")]
+mod align;
+#[cfg(feature = "apply")]
+mod apply;
+#[cfg(feature = "assert_expands_to")]
+mod assert_expands_to;
+#[cfg(feature = "attr_alias")]
+mod attr_alias;
+#[cfg(feature = "bitmask")]
+mod bitmask;
+#[cfg(feature = "bitpattern")]
+mod bitpattern;
+#[cfg(feature = "bits_needed")]
+mod bits_needed;
+#[cfg(feature = "build_time")]
+mod build_time;
+#[cfg(feature = "calc")]
+mod calc;
+#[cfg(feature = "cargo_manifest")]
+mod cargo_manifest;
+#[cfg(feature = "cfg_matrix")]
+mod cfg_matrix;
+#[cfg(feature = "cmp_lit")]
+mod cmp_lit;
+#[cfg(feature = "color_lit")]
+mod color_lit;
+#[cfg(feature = "compile_note")]
+mod compile_note;
+#[cfg(feature = "compile_warning")]
+mod compile_warning;
+#[cfg(feature = "const_assert_lit")]
+mod const_assert_lit;
+#[cfg(feature = "const_table")]
+mod const_table;
+#[cfg(feature = "crate_path")]
+mod crate_path;
+#[cfg(feature = "crate_version_parts")]
+mod crate_version_parts;
+#[cfg(any(feature = "debug_tokens", feature = "dump_expansion"))]
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod debug_tokens;
+#[cfg(feature = "derive_alias")]
+mod derive_alias;
+#[cfg(feature = "derive_via")]
+mod derive_via;
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod diagnostics;
+#[cfg(feature = "digits_of")]
+mod digits_of;
+#[cfg(feature = "doc_fmt")]
+mod doc_fmt;
+#[cfg(feature = "dump_expansion")]
+mod dump_expansion;
+#[cfg(feature = "duplicate")]
+mod duplicate;
+#[cfg(feature = "duration_lit")]
+mod duration_lit;
+#[cfg(any(feature = "env_lit", feature = "env_or"))]
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod env_lit;
+#[cfg(feature = "error_at")]
+mod error_at;
+#[cfg(feature = "export_tokens")]
+mod export_tokens;
+#[cfg(feature = "feature_gated")]
+mod feature_gated;
+#[cfg(feature = "fields_of")]
+mod fields_of;
+#[cfg(feature = "fixed_point")]
+mod fixed_point;
+#[cfg(feature = "fmt_check")]
+mod fmt_check;
+#[cfg(any(feature = "const_table", feature = "feature_gated", feature = "fields_of", feature = "generics_merge", feature = "generics_split", feature = "item_name", feature = "item_parts", feature = "match_type", feature = "rename_items", feature = "strip_attrs", feature = "variants_of", feature = "where_append"))]
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod generics;
+#[cfg(feature = "generics_merge")]
+mod generics_merge;
+#[cfg(feature = "generics_split")]
+mod generics_split;
+#[cfg(feature = "gensym")]
+mod gensym;
+#[cfg(feature = "glob_files")]
+mod glob_files;
+#[cfg(feature = "ident_case_check")]
+mod ident_case_check;
+#[cfg(feature = "ident_predicates")]
+mod ident_predicates;
+#[cfg(feature = "impl_for_tuples")]
+mod impl_for_tuples;
+#[cfg(feature = "include_tokens")]
+mod include_tokens;
+#[cfg(feature = "item_name")]
+mod item_name;
+#[cfg(feature = "item_parts")]
+mod item_parts;
+#[cfg(any(feature = "const_table", feature = "fields_of", feature = "item_name", feature = "item_parts", feature = "rename_items", feature = "strip_attrs", feature = "variants_of", feature = "where_append"))]
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod items;
+#[cfg(any(feature = "kv_args", feature = "named_args"))]
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod kv_args;
+#[cfg(feature = "lifetimes_of")]
+mod lifetimes_of;
+#[cfg(feature = "lit_len")]
+mod lit_len;
+#[cfg(any(feature = "align", feature = "bitmask", feature = "bitpattern", feature = "bits_needed", feature = "calc", feature = "cmp_lit", feature = "color_lit", feature = "const_assert_lit", feature = "digits_of", feature = "doc_fmt", feature = "dump_expansion", feature = "duration_lit", feature = "env_lit", feature = "env_or", feature = "fixed_point", feature = "fmt_check", feature = "impl_for_tuples", feature = "lit_len", feature = "min_max_lit", feature = "parse_lit", feature = "rand_lit", feature = "range_expand", feature = "regex_check", feature = "size_lit", feature = "str_predicates", feature = "swap_bytes_lit", feature = "trace_expansion", feature = "typenum_lit", feature = "unique_id", feature = "uuid_lit"))]
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod lits;
+#[cfg(feature = "macro_v2")]
+mod macro_v2;
+#[cfg(feature = "match_expr")]
+mod match_expr;
+#[cfg(feature = "match_meta")]
+mod match_meta;
+#[cfg(feature = "match_path")]
+mod match_path;
+#[cfg(feature = "match_type")]
+mod match_type;
+#[cfg(feature = "match_vis")]
+mod match_vis;
+#[cfg(any(feature = "compile_note", feature = "const_table", feature = "kv_args", feature = "match_expr", feature = "match_meta", feature = "match_path", feature = "match_type", feature = "match_vis", feature = "named_args", feature = "option_env_match", feature = "overload", feature = "strip_generics", feature = "test_matrix", feature = "toolkit", feature = "turbofish"))]
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod matching;
+#[cfg(feature = "metavar_exprs")]
+mod metavar_exprs;
+#[cfg(feature = "min_max_lit")]
+mod min_max_lit;
+#[cfg(feature = "named_args")]
+mod named_args;
+#[cfg(feature = "option_env_match")]
+mod option_env_match;
+#[cfg(feature = "overload")]
+mod overload;
+#[cfg(feature = "parse_lit")]
+mod parse_lit;
+#[cfg(feature = "pub_macro")]
+mod pub_macro;
+#[cfg(feature = "rand_lit")]
+mod rand_lit;
+#[cfg(feature = "range_expand")]
+mod range_expand;
+#[cfg(feature = "regex_check")]
+mod regex_check;
+#[cfg(feature = "rename_items")]
+mod rename_items;
+#[cfg(feature = "respan")]
+mod respan;
+#[cfg(feature = "rustc_since")]
+mod rustc_since;
+#[cfg(feature = "size_lit")]
+mod size_lit;
+#[cfg(feature = "span_location")]
+mod span_location;
+#[cfg(feature = "str_predicates")]
+mod str_predicates;
+#[cfg(feature = "str_switch")]
+mod str_switch;
+#[cfg(feature = "strip_attrs")]
+mod strip_attrs;
+#[cfg(feature = "strip_generics")]
+mod strip_generics;
+#[cfg(feature = "swap_bytes_lit")]
+mod swap_bytes_lit;
+#[cfg(feature = "template")]
+mod template;
+#[cfg(feature = "test_matrix")]
+mod test_matrix;
+#[cfg(feature = "toolkit")]
+mod toolkit;
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
+mod trace;
+#[cfg(feature = "trace_expansion")]
+mod trace_expansion;
+#[cfg(feature = "turbofish")]
+mod turbofish;
+#[cfg(feature = "type_ident")]
+mod type_ident;
+#[cfg(feature = "typenum_lit")]
+mod typenum_lit;
+#[cfg(feature = "unique_id")]
+mod unique_id;
+#[cfg_attr(not(feature = "full"), allow(dead_code, unused_imports))]
 mod utils;
+#[cfg(feature = "uuid_lit")]
+mod uuid_lit;
+#[cfg(feature = "variants_of")]
+mod variants_of;
+#[cfg(feature = "where_append")]
+mod where_append;
 
 // Imports
 #[allow(unused)]
@@ -62,10 +468,10 @@ use proc_macro::TokenStream;
 #[inline]
 #[proc_macro]
 pub fn match_lit(input: TokenStream) -> TokenStream {
-    match match_lit::match_lit(input.into()) {
+    trace::traced("match_lit!()", input, |input| match macro_toolkit_core::match_lit::match_lit(input.into()) {
         Ok(res) => res.into(),
-        Err(err) => err.into(),
-    }
+        Err(err) => err.emit().into(),
+    })
 }
 
 
@@ -76,8 +482,1352 @@ pub fn match_lit(input: TokenStream) -> TokenStream {
 #[inline]
 #[proc_macro]
 pub fn idents(input: TokenStream) -> TokenStream {
-    match idents::idents(input) {
-        Ok(res) => res,
-        Err(err) => err,
-    }
+    trace::traced("idents!()", input, |input| match macro_toolkit_core::idents::idents(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "idents")]
+#[cfg_attr(docsrs, doc(cfg(feature = "idents")))]
+#[doc = include_str!("../docs/with_idents.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn with_idents(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[with_idents]", attr, item, |attr, item| match macro_toolkit_core::idents::with_idents(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "lit_len")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lit_len")))]
+#[doc = include_str!("../docs/lit_len.md")]
+#[inline]
+#[proc_macro]
+pub fn lit_len(input: TokenStream) -> TokenStream {
+    trace::traced("lit_len!()", input, |input| match lit_len::lit_len(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "digits_of")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digits_of")))]
+#[doc = include_str!("../docs/digits_of.md")]
+#[inline]
+#[proc_macro]
+pub fn digits_of(input: TokenStream) -> TokenStream {
+    trace::traced("digits_of!()", input, |input| match digits_of::digits_of(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "typenum_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "typenum_lit")))]
+#[doc = include_str!("../docs/typenum_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn typenum_lit(input: TokenStream) -> TokenStream {
+    trace::traced("typenum_lit!()", input, |input| match typenum_lit::typenum_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "regex_check")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex_check")))]
+#[doc = include_str!("../docs/regex_check.md")]
+#[inline]
+#[proc_macro]
+pub fn regex_check(input: TokenStream) -> TokenStream {
+    trace::traced("regex_check!()", input, |input| match regex_check::regex_check(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "fmt_check")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fmt_check")))]
+#[doc = include_str!("../docs/fmt_check.md")]
+#[inline]
+#[proc_macro]
+pub fn fmt_check(input: TokenStream) -> TokenStream {
+    trace::traced("fmt_check!()", input, |input| match fmt_check::fmt_check(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "uuid_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid_lit")))]
+#[doc = include_str!("../docs/uuid_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn uuid_lit(input: TokenStream) -> TokenStream {
+    trace::traced("uuid_lit!()", input, |input| match uuid_lit::uuid_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "duration_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "duration_lit")))]
+#[doc = include_str!("../docs/duration_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn duration_lit(input: TokenStream) -> TokenStream {
+    trace::traced("duration_lit!()", input, |input| match duration_lit::duration_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "size_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "size_lit")))]
+#[doc = include_str!("../docs/size_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn size_lit(input: TokenStream) -> TokenStream {
+    trace::traced("size_lit!()", input, |input| match size_lit::size_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "color_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "color_lit")))]
+#[doc = include_str!("../docs/color_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn color_lit(input: TokenStream) -> TokenStream {
+    trace::traced("color_lit!()", input, |input| match color_lit::color_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "bitpattern")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitpattern")))]
+#[doc = include_str!("../docs/bitpattern.md")]
+#[inline]
+#[proc_macro]
+pub fn bitpattern(input: TokenStream) -> TokenStream {
+    trace::traced("bitpattern!()", input, |input| match bitpattern::bitpattern(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "swap_bytes_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "swap_bytes_lit")))]
+#[doc = include_str!("../docs/swap_bytes_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn swap_bytes_lit(input: TokenStream) -> TokenStream {
+    trace::traced("swap_bytes_lit!()", input, |input| match swap_bytes_lit::swap_bytes_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "parse_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parse_lit")))]
+#[doc = include_str!("../docs/parse_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn parse_lit(input: TokenStream) -> TokenStream {
+    trace::traced("parse_lit!()", input, |input| match parse_lit::parse_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "str_predicates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "str_predicates")))]
+#[doc = include_str!("../docs/str_predicates.md")]
+#[inline]
+#[proc_macro]
+pub fn str_predicates(input: TokenStream) -> TokenStream {
+    trace::traced("str_predicates!()", input, |input| match str_predicates::str_predicates(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "calc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "calc")))]
+#[doc = include_str!("../docs/calc.md")]
+#[inline]
+#[proc_macro]
+pub fn calc(input: TokenStream) -> TokenStream {
+    trace::traced("calc!()", input, |input| match calc::calc(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "min_max_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "min_max_lit")))]
+#[doc = include_str!("../docs/min_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn min_lit(input: TokenStream) -> TokenStream {
+    trace::traced("min_lit!()", input, |input| match min_max_lit::min_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "min_max_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "min_max_lit")))]
+#[doc = include_str!("../docs/max_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn max_lit(input: TokenStream) -> TokenStream {
+    trace::traced("max_lit!()", input, |input| match min_max_lit::max_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "cmp_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cmp_lit")))]
+#[doc = include_str!("../docs/cmp_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn cmp_lit(input: TokenStream) -> TokenStream {
+    trace::traced("cmp_lit!()", input, |input| match cmp_lit::cmp_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "bits_needed")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bits_needed")))]
+#[doc = include_str!("../docs/bits_needed.md")]
+#[inline]
+#[proc_macro]
+pub fn bits_needed(input: TokenStream) -> TokenStream {
+    trace::traced("bits_needed!()", input, |input| match bits_needed::bits_needed(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "align")]
+#[cfg_attr(docsrs, doc(cfg(feature = "align")))]
+#[doc = include_str!("../docs/align_up.md")]
+#[inline]
+#[proc_macro]
+pub fn align_up(input: TokenStream) -> TokenStream {
+    trace::traced("align_up!()", input, |input| match align::align_up(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "align")]
+#[cfg_attr(docsrs, doc(cfg(feature = "align")))]
+#[doc = include_str!("../docs/align_down.md")]
+#[inline]
+#[proc_macro]
+pub fn align_down(input: TokenStream) -> TokenStream {
+    trace::traced("align_down!()", input, |input| match align::align_down(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "range_expand")]
+#[cfg_attr(docsrs, doc(cfg(feature = "range_expand")))]
+#[doc = include_str!("../docs/range_expand.md")]
+#[inline]
+#[proc_macro]
+pub fn range_expand(input: TokenStream) -> TokenStream {
+    trace::traced("range_expand!()", input, |input| match range_expand::range_expand(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "bitmask")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitmask")))]
+#[doc = include_str!("../docs/bitmask.md")]
+#[inline]
+#[proc_macro]
+pub fn bitmask(input: TokenStream) -> TokenStream {
+    trace::traced("bitmask!()", input, |input| match bitmask::bitmask(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "fixed_point")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fixed_point")))]
+#[doc = include_str!("../docs/fixed_point.md")]
+#[inline]
+#[proc_macro]
+pub fn fixed_point(input: TokenStream) -> TokenStream {
+    trace::traced("fixed_point!()", input, |input| match fixed_point::fixed_point(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "rand_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_lit")))]
+#[doc = include_str!("../docs/rand_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn rand_lit(input: TokenStream) -> TokenStream {
+    trace::traced("rand_lit!()", input, |input| match rand_lit::rand_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "unique_id")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unique_id")))]
+#[doc = include_str!("../docs/unique_id.md")]
+#[inline]
+#[proc_macro]
+pub fn unique_id(input: TokenStream) -> TokenStream {
+    trace::traced("unique_id!()", input, |input| match unique_id::unique_id(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "gensym")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gensym")))]
+#[doc = include_str!("../docs/gensym.md")]
+#[inline]
+#[proc_macro]
+pub fn gensym(input: TokenStream) -> TokenStream {
+    trace::traced("gensym!()", input, |input| match gensym::gensym(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "build_time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "build_time")))]
+#[doc = include_str!("../docs/build_time.md")]
+#[inline]
+#[proc_macro]
+pub fn build_time(input: TokenStream) -> TokenStream {
+    trace::traced("build_time!()", input, |input| match build_time::build_time(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "const_assert_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const_assert_lit")))]
+#[doc = include_str!("../docs/const_assert_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn const_assert_lit(input: TokenStream) -> TokenStream {
+    trace::traced("const_assert_lit!()", input, |input| match const_assert_lit::const_assert_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "apply")]
+#[cfg_attr(docsrs, doc(cfg(feature = "apply")))]
+#[doc = include_str!("../docs/apply.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn apply(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[apply]", attr, item, |attr, item| match apply::apply(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "derive_via")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive_via")))]
+#[doc = include_str!("../docs/derive_via.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn derive_via(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[derive_via]", attr, item, |attr, item| match derive_via::derive_via(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "duplicate")]
+#[cfg_attr(docsrs, doc(cfg(feature = "duplicate")))]
+#[doc = include_str!("../docs/duplicate.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn duplicate(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[duplicate]", attr, item, |attr, item| match duplicate::duplicate(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "rustc_since")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rustc_since")))]
+#[doc = include_str!("../docs/rustc_since.md")]
+#[inline]
+#[proc_macro]
+pub fn rustc_since(input: TokenStream) -> TokenStream {
+    trace::traced("rustc_since!()", input, |input| match rustc_since::rustc_since(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "rustc_since")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rustc_since")))]
+#[doc = include_str!("../docs/cfg_rustc_since.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn cfg_rustc_since(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[cfg_rustc_since]", attr, item, |attr, item| match rustc_since::cfg_rustc_since(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "impl_for_tuples")]
+#[cfg_attr(docsrs, doc(cfg(feature = "impl_for_tuples")))]
+#[doc = include_str!("../docs/impl_for_tuples.md")]
+#[inline]
+#[proc_macro]
+pub fn impl_for_tuples(input: TokenStream) -> TokenStream {
+    trace::traced("impl_for_tuples!()", input, |input| match impl_for_tuples::impl_for_tuples(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "template")]
+#[cfg_attr(docsrs, doc(cfg(feature = "template")))]
+#[doc = include_str!("../docs/template.md")]
+#[inline]
+#[proc_macro]
+pub fn template(input: TokenStream) -> TokenStream {
+    trace::traced("template!()", input, |input| match template::template(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "template")]
+#[cfg_attr(docsrs, doc(cfg(feature = "template")))]
+#[doc = include_str!("../docs/expand_template.md")]
+#[inline]
+#[proc_macro]
+pub fn expand_template(input: TokenStream) -> TokenStream {
+    trace::traced("expand_template!()", input, |input| match template::expand_template(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "export_tokens")]
+#[cfg_attr(docsrs, doc(cfg(feature = "export_tokens")))]
+#[doc = include_str!("../docs/export_tokens.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn export_tokens(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[export_tokens]", attr, item, |attr, item| match export_tokens::export_tokens(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "export_tokens")]
+#[cfg_attr(docsrs, doc(cfg(feature = "export_tokens")))]
+#[doc = include_str!("../docs/import_tokens.md")]
+#[inline]
+#[proc_macro]
+pub fn import_tokens(input: TokenStream) -> TokenStream {
+    trace::traced("import_tokens!()", input, |input| match export_tokens::import_tokens(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "respan")]
+#[cfg_attr(docsrs, doc(cfg(feature = "respan")))]
+#[doc = include_str!("../docs/respan.md")]
+#[inline]
+#[proc_macro]
+pub fn respan(input: TokenStream) -> TokenStream {
+    trace::traced("respan!()", input, |input| match respan::respan(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "respan")]
+#[cfg_attr(docsrs, doc(cfg(feature = "respan")))]
+#[doc = include_str!("../docs/with_span.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn with_span(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[with_span]", attr, item, |attr, item| match respan::with_span(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "crate_path")]
+#[cfg_attr(docsrs, doc(cfg(feature = "crate_path")))]
+#[doc = include_str!("../docs/crate_path.md")]
+#[inline]
+#[proc_macro]
+pub fn crate_path(input: TokenStream) -> TokenStream {
+    trace::traced("crate_path!()", input, |input| match crate_path::crate_path(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "cfg_matrix")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cfg_matrix")))]
+#[doc = include_str!("../docs/cfg_matrix.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn cfg_matrix(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[cfg_matrix]", attr, item, |attr, item| match cfg_matrix::cfg_matrix(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "pub_macro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pub_macro")))]
+#[doc = include_str!("../docs/pub_macro.md")]
+#[inline]
+#[proc_macro]
+pub fn pub_macro(input: TokenStream) -> TokenStream {
+    trace::traced("pub_macro!()", input, |input| match pub_macro::pub_macro(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "macro_v2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macro_v2")))]
+#[doc = include_str!("../docs/macro_v2.md")]
+#[inline]
+#[proc_macro]
+pub fn macro_v2(input: TokenStream) -> TokenStream {
+    trace::traced("macro_v2!()", input, |input| match macro_v2::macro_v2(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "trace_expansion")]
+#[cfg_attr(docsrs, doc(cfg(feature = "trace_expansion")))]
+#[doc = include_str!("../docs/trace_expansion.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn trace_expansion(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[trace_expansion]", attr, item, |attr, item| match trace_expansion::trace_expansion(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "trace_expansion")]
+#[cfg_attr(docsrs, doc(cfg(feature = "trace_expansion")))]
+#[doc = include_str!("../docs/trace_tokens.md")]
+#[inline]
+#[proc_macro]
+pub fn trace_tokens(input: TokenStream) -> TokenStream {
+    trace::traced("trace_tokens!()", input, |input| match trace_expansion::trace_tokens(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "attr_alias")]
+#[cfg_attr(docsrs, doc(cfg(feature = "attr_alias")))]
+#[doc = include_str!("../docs/attr_alias.md")]
+#[inline]
+#[proc_macro]
+pub fn attr_alias(input: TokenStream) -> TokenStream {
+    trace::traced("attr_alias!()", input, |input| match attr_alias::attr_alias(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "attr_alias")]
+#[cfg_attr(docsrs, doc(cfg(feature = "attr_alias")))]
+#[doc = include_str!("../docs/apply_alias.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn apply_alias(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[apply_alias]", attr, item, |attr, item| match attr_alias::apply_alias(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "derive_alias")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive_alias")))]
+#[doc = include_str!("../docs/derive_alias.md")]
+#[inline]
+#[proc_macro]
+pub fn derive_alias(input: TokenStream) -> TokenStream {
+    trace::traced("derive_alias!()", input, |input| match derive_alias::derive_alias(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "item_name")]
+#[cfg_attr(docsrs, doc(cfg(feature = "item_name")))]
+#[doc = include_str!("../docs/item_name.md")]
+#[inline]
+#[proc_macro]
+pub fn item_name(input: TokenStream) -> TokenStream {
+    trace::traced("item_name!()", input, |input| match item_name::item_name(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "item_parts")]
+#[cfg_attr(docsrs, doc(cfg(feature = "item_parts")))]
+#[doc = include_str!("../docs/item_parts.md")]
+#[inline]
+#[proc_macro]
+pub fn item_parts(input: TokenStream) -> TokenStream {
+    trace::traced("item_parts!()", input, |input| match item_parts::item_parts(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "generics_split")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generics_split")))]
+#[doc = include_str!("../docs/generics_split.md")]
+#[inline]
+#[proc_macro]
+pub fn generics_split(input: TokenStream) -> TokenStream {
+    trace::traced("generics_split!()", input, |input| match generics_split::generics_split(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "fields_of")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fields_of")))]
+#[doc = include_str!("../docs/fields_of.md")]
+#[inline]
+#[proc_macro]
+pub fn fields_of(input: TokenStream) -> TokenStream {
+    trace::traced("fields_of!()", input, |input| match fields_of::fields_of(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "variants_of")]
+#[cfg_attr(docsrs, doc(cfg(feature = "variants_of")))]
+#[doc = include_str!("../docs/variants_of.md")]
+#[inline]
+#[proc_macro]
+pub fn variants_of(input: TokenStream) -> TokenStream {
+    trace::traced("variants_of!()", input, |input| match variants_of::variants_of(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "strip_attrs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "strip_attrs")))]
+#[doc = include_str!("../docs/strip_attrs.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn strip_attrs(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[strip_attrs]", attr, item, |attr, item| match strip_attrs::strip_attrs(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "match_vis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "match_vis")))]
+#[doc = include_str!("../docs/match_vis.md")]
+#[inline]
+#[proc_macro]
+pub fn match_vis(input: TokenStream) -> TokenStream {
+    trace::traced("match_vis!()", input, |input| match match_vis::match_vis(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "match_type")]
+#[cfg_attr(docsrs, doc(cfg(feature = "match_type")))]
+#[doc = include_str!("../docs/match_type.md")]
+#[inline]
+#[proc_macro]
+pub fn match_type(input: TokenStream) -> TokenStream {
+    trace::traced("match_type!()", input, |input| match match_type::match_type(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "match_expr")]
+#[cfg_attr(docsrs, doc(cfg(feature = "match_expr")))]
+#[doc = include_str!("../docs/match_expr.md")]
+#[inline]
+#[proc_macro]
+pub fn match_expr(input: TokenStream) -> TokenStream {
+    trace::traced("match_expr!()", input, |input| match match_expr::match_expr(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "match_path")]
+#[cfg_attr(docsrs, doc(cfg(feature = "match_path")))]
+#[doc = include_str!("../docs/match_path.md")]
+#[inline]
+#[proc_macro]
+pub fn match_path(input: TokenStream) -> TokenStream {
+    trace::traced("match_path!()", input, |input| match match_path::match_path(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "match_meta")]
+#[cfg_attr(docsrs, doc(cfg(feature = "match_meta")))]
+#[doc = include_str!("../docs/match_meta.md")]
+#[inline]
+#[proc_macro]
+pub fn match_meta(input: TokenStream) -> TokenStream {
+    trace::traced("match_meta!()", input, |input| match match_meta::match_meta(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "kv_args")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kv_args")))]
+#[doc = include_str!("../docs/kv_args.md")]
+#[inline]
+#[proc_macro]
+pub fn kv_args(input: TokenStream) -> TokenStream {
+    trace::traced("kv_args!()", input, |input| match kv_args::kv_args(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "where_append")]
+#[cfg_attr(docsrs, doc(cfg(feature = "where_append")))]
+#[doc = include_str!("../docs/where_append.md")]
+#[inline]
+#[proc_macro]
+pub fn where_append(input: TokenStream) -> TokenStream {
+    trace::traced("where_append!()", input, |input| match where_append::where_append(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "turbofish")]
+#[cfg_attr(docsrs, doc(cfg(feature = "turbofish")))]
+#[doc = include_str!("../docs/turbofish.md")]
+#[inline]
+#[proc_macro]
+pub fn turbofish(input: TokenStream) -> TokenStream {
+    trace::traced("turbofish!()", input, |input| match turbofish::turbofish(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "rename_items")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rename_items")))]
+#[doc = include_str!("../docs/prefix_items.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn prefix_items(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[prefix_items]", attr, item, |attr, item| match rename_items::prefix_items(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "rename_items")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rename_items")))]
+#[doc = include_str!("../docs/rename_items.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn rename_items(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[rename_items]", attr, item, |attr, item| match rename_items::rename_items(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "feature_gated")]
+#[cfg_attr(docsrs, doc(cfg(feature = "feature_gated")))]
+#[doc = include_str!("../docs/feature_gated.md")]
+#[inline]
+#[proc_macro_attribute]
+pub fn feature_gated(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trace::traced_attr("#[feature_gated]", attr, item, |attr, item| match feature_gated::feature_gated(attr.into(), item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "overload")]
+#[cfg_attr(docsrs, doc(cfg(feature = "overload")))]
+#[doc = include_str!("../docs/overload.md")]
+#[inline]
+#[proc_macro]
+pub fn overload(input: TokenStream) -> TokenStream {
+    trace::traced("overload!()", input, |input| match overload::overload(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "named_args")]
+#[cfg_attr(docsrs, doc(cfg(feature = "named_args")))]
+#[doc = include_str!("../docs/named_args.md")]
+#[inline]
+#[proc_macro]
+pub fn named_args(input: TokenStream) -> TokenStream {
+    trace::traced("named_args!()", input, |input| match named_args::named_args(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "strip_generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "strip_generics")))]
+#[doc = include_str!("../docs/strip_generics.md")]
+#[inline]
+#[proc_macro]
+pub fn strip_generics(input: TokenStream) -> TokenStream {
+    trace::traced("strip_generics!()", input, |input| match strip_generics::strip_generics(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "generics_merge")]
+#[cfg_attr(docsrs, doc(cfg(feature = "generics_merge")))]
+#[doc = include_str!("../docs/generics_merge.md")]
+#[inline]
+#[proc_macro]
+pub fn generics_merge(input: TokenStream) -> TokenStream {
+    trace::traced("generics_merge!()", input, |input| match generics_merge::generics_merge(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "type_ident")]
+#[cfg_attr(docsrs, doc(cfg(feature = "type_ident")))]
+#[doc = include_str!("../docs/type_ident.md")]
+#[inline]
+#[proc_macro]
+pub fn type_ident(input: TokenStream) -> TokenStream {
+    trace::traced("type_ident!()", input, |input| match type_ident::type_ident(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "test_matrix")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test_matrix")))]
+#[doc = include_str!("../docs/test_matrix.md")]
+#[inline]
+#[proc_macro]
+pub fn test_matrix(input: TokenStream) -> TokenStream {
+    trace::traced("test_matrix!()", input, |input| match test_matrix::test_matrix(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "const_table")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const_table")))]
+#[doc = include_str!("../docs/const_table.md")]
+#[inline]
+#[proc_macro]
+pub fn const_table(input: TokenStream) -> TokenStream {
+    trace::traced("const_table!()", input, |input| match const_table::const_table(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "lifetimes_of")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lifetimes_of")))]
+#[doc = include_str!("../docs/lifetimes_of.md")]
+#[inline]
+#[proc_macro]
+pub fn lifetimes_of(input: TokenStream) -> TokenStream {
+    trace::traced("lifetimes_of!()", input, |input| match lifetimes_of::lifetimes_of(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "compile_warning")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compile_warning")))]
+#[doc = include_str!("../docs/compile_warning.md")]
+#[inline]
+#[proc_macro]
+pub fn compile_warning(input: TokenStream) -> TokenStream {
+    trace::traced("compile_warning!()", input, |input| match compile_warning::compile_warning(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "compile_note")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compile_note")))]
+#[doc = include_str!("../docs/compile_note.md")]
+#[inline]
+#[proc_macro]
+pub fn compile_note(input: TokenStream) -> TokenStream {
+    trace::traced("compile_note!()", input, |input| match compile_note::compile_note(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "error_at")]
+#[cfg_attr(docsrs, doc(cfg(feature = "error_at")))]
+#[doc = include_str!("../docs/error_at.md")]
+#[inline]
+#[proc_macro]
+pub fn error_at(input: TokenStream) -> TokenStream {
+    trace::traced("error_at!()", input, |input| match error_at::error_at(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "debug_tokens")]
+#[cfg_attr(docsrs, doc(cfg(feature = "debug_tokens")))]
+#[doc = include_str!("../docs/debug_tokens.md")]
+#[inline]
+#[proc_macro]
+pub fn debug_tokens(input: TokenStream) -> TokenStream {
+    trace::traced("debug_tokens!()", input, |input| match debug_tokens::debug_tokens(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "dump_expansion")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dump_expansion")))]
+#[doc = include_str!("../docs/dump_expansion.md")]
+#[inline]
+#[proc_macro]
+pub fn dump_expansion(input: TokenStream) -> TokenStream {
+    trace::traced("dump_expansion!()", input, |input| match dump_expansion::dump_expansion(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "assert_expands_to")]
+#[cfg_attr(docsrs, doc(cfg(feature = "assert_expands_to")))]
+#[doc = include_str!("../docs/assert_expands_to.md")]
+#[inline]
+#[proc_macro]
+pub fn assert_expands_to(input: TokenStream) -> TokenStream {
+    trace::traced("assert_expands_to!()", input, |input| match assert_expands_to::assert_expands_to(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "include_tokens")]
+#[cfg_attr(docsrs, doc(cfg(feature = "include_tokens")))]
+#[doc = include_str!("../docs/include_tokens.md")]
+#[inline]
+#[proc_macro]
+pub fn include_tokens(input: TokenStream) -> TokenStream {
+    trace::traced("include_tokens!()", input, |input| match include_tokens::include_tokens(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "env_lit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "env_lit")))]
+#[doc = include_str!("../docs/env_lit.md")]
+#[inline]
+#[proc_macro]
+pub fn env_lit(input: TokenStream) -> TokenStream {
+    trace::traced("env_lit!()", input, |input| match env_lit::env_lit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "env_or")]
+#[cfg_attr(docsrs, doc(cfg(feature = "env_or")))]
+#[doc = include_str!("../docs/env_or.md")]
+#[inline]
+#[proc_macro]
+pub fn env_or(input: TokenStream) -> TokenStream {
+    trace::traced("env_or!()", input, |input| match env_lit::env_or(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "option_env_match")]
+#[cfg_attr(docsrs, doc(cfg(feature = "option_env_match")))]
+#[doc = include_str!("../docs/option_env_match.md")]
+#[inline]
+#[proc_macro]
+pub fn option_env_match(input: TokenStream) -> TokenStream {
+    trace::traced("option_env_match!()", input, |input| match option_env_match::option_env_match(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "glob_files")]
+#[cfg_attr(docsrs, doc(cfg(feature = "glob_files")))]
+#[doc = include_str!("../docs/glob_files.md")]
+#[inline]
+#[proc_macro]
+pub fn glob_files(input: TokenStream) -> TokenStream {
+    trace::traced("glob_files!()", input, |input| match glob_files::glob_files(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "cargo_manifest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cargo_manifest")))]
+#[doc = include_str!("../docs/cargo_manifest.md")]
+#[inline]
+#[proc_macro]
+pub fn cargo_manifest(input: TokenStream) -> TokenStream {
+    trace::traced("cargo_manifest!()", input, |input| match cargo_manifest::cargo_manifest(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "crate_version_parts")]
+#[cfg_attr(docsrs, doc(cfg(feature = "crate_version_parts")))]
+#[doc = include_str!("../docs/crate_version_parts.md")]
+#[inline]
+#[proc_macro]
+pub fn crate_version_parts(input: TokenStream) -> TokenStream {
+    trace::traced("crate_version_parts!()", input, |input| match crate_version_parts::crate_version_parts(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "span_location")]
+#[cfg_attr(docsrs, doc(cfg(feature = "span_location")))]
+#[doc = include_str!("../docs/span_location.md")]
+#[inline]
+#[proc_macro]
+pub fn span_location(input: TokenStream) -> TokenStream {
+    trace::traced("span_location!()", input, |input| match span_location::span_location(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "metavar_exprs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metavar_exprs")))]
+#[doc = include_str!("../docs/meta_count.md")]
+#[inline]
+#[proc_macro]
+pub fn meta_count(input: TokenStream) -> TokenStream {
+    trace::traced("meta_count!()", input, |input| match metavar_exprs::meta_count(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+#[cfg(feature = "metavar_exprs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metavar_exprs")))]
+#[doc = include_str!("../docs/meta_index.md")]
+#[inline]
+#[proc_macro]
+pub fn meta_index(input: TokenStream) -> TokenStream {
+    trace::traced("meta_index!()", input, |input| match metavar_exprs::meta_index(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+#[cfg(feature = "metavar_exprs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metavar_exprs")))]
+#[doc = include_str!("../docs/meta_ignore.md")]
+#[inline]
+#[proc_macro]
+pub fn meta_ignore(input: TokenStream) -> TokenStream {
+    trace::traced("meta_ignore!()", input, |input| match metavar_exprs::meta_ignore(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "ident_case_check")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ident_case_check")))]
+#[doc = include_str!("../docs/ident_case_check.md")]
+#[inline]
+#[proc_macro]
+pub fn ident_case_check(input: TokenStream) -> TokenStream {
+    trace::traced("ident_case_check!()", input, |input| match ident_case_check::ident_case_check(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "ident_predicates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ident_predicates")))]
+#[doc = include_str!("../docs/keyword_check.md")]
+#[inline]
+#[proc_macro]
+pub fn keyword_check(input: TokenStream) -> TokenStream {
+    trace::traced("keyword_check!()", input, |input| match ident_predicates::keyword_check(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+#[cfg(feature = "ident_predicates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ident_predicates")))]
+#[doc = include_str!("../docs/is_valid_ident.md")]
+#[inline]
+#[proc_macro]
+pub fn is_valid_ident(input: TokenStream) -> TokenStream {
+    trace::traced("is_valid_ident!()", input, |input| match ident_predicates::is_valid_ident(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "str_switch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "str_switch")))]
+#[doc = include_str!("../docs/str_switch.md")]
+#[inline]
+#[proc_macro]
+pub fn str_switch(input: TokenStream) -> TokenStream {
+    trace::traced("str_switch!()", input, |input| match str_switch::str_switch(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "doc_fmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "doc_fmt")))]
+#[doc = include_str!("../docs/doc_fmt.md")]
+#[inline]
+#[proc_macro]
+pub fn doc_fmt(input: TokenStream) -> TokenStream {
+    trace::traced("doc_fmt!()", input, |input| match doc_fmt::doc_fmt(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
+}
+
+
+
+#[cfg(feature = "toolkit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toolkit")))]
+#[doc = include_str!("../docs/toolkit.md")]
+#[inline]
+#[proc_macro]
+pub fn toolkit(input: TokenStream) -> TokenStream {
+    trace::traced("toolkit!()", input, |input| match toolkit::toolkit(input.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.emit().into(),
+    })
 }
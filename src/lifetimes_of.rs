@@ -0,0 +1,110 @@
+//  LIFETIMES OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for extracting the lifetimes mentioned in a type or item.
+//
+
+use proc_macro2::{Delimiter, Ident, Punct, Spacing, TokenStream, TokenTree};
+
+use crate::diagnostics::Diagnostic2;
+use crate::utils::Callback;
+
+
+/***** CONSTANTS *****/
+/// The lifetimes that are never declared, and therefore never extracted.
+const BUILTIN: [&str; 2] = ["static", "_"];
+/// The keywords that may follow a label.
+const LABELED: [&str; 3] = ["loop", "while", "for"];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Collects the lifetimes in the given tokens, at any depth.
+///
+/// # Arguments
+/// - `tokens`: The tokens to collect the lifetimes in.
+/// - `lifetimes`: The lifetimes found so far, in order of first use.
+/// - `excluded`: The names of lifetimes bound by `for<...>` and of labels, which are not returned.
+fn collect(tokens: TokenStream, lifetimes: &mut Vec<(Punct, Ident)>, excluded: &mut Vec<String>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut i: usize = 0;
+    while let Some(tt) = tokens.get(i) {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == '\'' => {
+                let Some(TokenTree::Ident(name)) = tokens.get(i + 1) else {
+                    i += 1;
+                    continue;
+                };
+                let key: String = name.to_string();
+
+                // Labels are either followed by `:` and a loop or block, or preceded by `break` or `continue`
+                let label: bool = match (tokens.get(i + 2), tokens.get(i + 3)) {
+                    (Some(TokenTree::Punct(colon)), Some(TokenTree::Ident(kw))) => colon.as_char() == ':' && LABELED.iter().any(|l| kw == l),
+                    (Some(TokenTree::Punct(colon)), Some(TokenTree::Group(group))) => colon.as_char() == ':' && group.delimiter() == Delimiter::Brace,
+                    _ => false,
+                } || matches!(i.checked_sub(1).and_then(|j| tokens.get(j)), Some(TokenTree::Ident(kw)) if kw == "break" || kw == "continue");
+                if label {
+                    excluded.push(key);
+                } else if !BUILTIN.contains(&key.as_str()) && !lifetimes.iter().any(|(_, existing)| *existing == key) {
+                    lifetimes.push((p.clone(), name.clone()));
+                }
+                i += 2;
+            },
+            // Lifetimes bound by `for<...>` are not part of the generics
+            TokenTree::Ident(ident) if ident == "for" && matches!(tokens.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == '<') => {
+                i += 2;
+                while let Some(tt) = tokens.get(i) {
+                    i += 1;
+                    match tt {
+                        TokenTree::Punct(p) if p.as_char() == '>' => break,
+                        TokenTree::Ident(name) => excluded.push(name.to_string()),
+                        _ => {},
+                    }
+                }
+            },
+            TokenTree::Group(group) => {
+                collect(group.stream(), lifetimes, excluded);
+                i += 1;
+            },
+            _ => i += 1,
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`lifetimes_of()`](super::lifetimes_of())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the type or item and an optional callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the comma-separated lifetimes, or a call to the callback with them.
+///
+/// # Errors
+/// This function may error if the callback is invalid.
+pub fn lifetimes_of(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (tokens, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let mut lifetimes: Vec<(Punct, Ident)> = Vec::new();
+    let mut excluded: Vec<String> = Vec::new();
+    collect(tokens, &mut lifetimes, &mut excluded);
+
+    // Serialize them as a list
+    let mut res = TokenStream::new();
+    for (i, (tick, name)) in lifetimes.into_iter().filter(|(_, name)| !excluded.contains(&name.to_string())).enumerate() {
+        if i > 0 {
+            res.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        res.extend([TokenTree::Punct(tick), TokenTree::Ident(name)]);
+    }
+    match callback {
+        Some(callback) => Ok(callback.call_item(res)),
+        None => Ok(res),
+    }
+}
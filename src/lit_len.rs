@@ -0,0 +1,98 @@
+//  LIT LEN.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for computing the length of string-like literals at compile time.
+//
+
+use proc_macro2::{Literal, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::parse_lit;
+
+
+/***** TOKEN PARSING *****/
+/// Defines what to count in the given literal.
+enum Unit {
+    /// Count the bytes in the (UTF-8 encoded) literal.
+    Bytes,
+    /// Count the Unicode scalar values in the literal.
+    Chars,
+}
+
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the [`Unit`] to count and the [`Lit`] to count it in.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(Unit, Lit), Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the optional unit first
+    let mut unit = Unit::Bytes;
+    let lit: Lit = match iter.next() {
+        Some(TokenTree::Ident(ident)) => {
+            match ident.to_string().as_str() {
+                "bytes" => unit = Unit::Bytes,
+                "chars" => unit = Unit::Chars,
+                _ => return Err(error2(ident.span(), "Expected either `bytes`, `chars` or a string literal")),
+            }
+            match iter.next() {
+                Some(tree) => parse_lit(tree)?,
+                None => return Err(error2(Span::mixed_site(), "Expected a string literal after the unit")),
+            }
+        },
+        Some(tree) => parse_lit(tree)?,
+        None => return Err(error2(Span::mixed_site(), "Expected a string literal")),
+    };
+
+    // Assert there's nothing after it
+    if let Some(tree) = iter.next() {
+        return Err(error2(tree.span(), "Expected nothing after the string literal"));
+    }
+    Ok((unit, lit))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`lit_len()`](super::lit_len())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to measure.
+///
+/// # Returns
+/// A new [`TokenStream`] with a single `usize` literal encoding the length of the input literal.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn lit_len(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (unit, lit) = parse_input(input)?;
+
+    // Compute the length
+    let len: usize = match (&lit, unit) {
+        (Lit::Str(s), Unit::Bytes) => s.value().len(),
+        (Lit::Str(s), Unit::Chars) => s.value().chars().count(),
+        (Lit::ByteStr(b), Unit::Bytes) => b.value().len(),
+        (Lit::ByteStr(b), Unit::Chars) => return Err(error2(b.span(), "Cannot count characters in a byte string literal")),
+        (Lit::CStr(c), Unit::Bytes) => c.value().as_bytes().len(),
+        (Lit::CStr(c), Unit::Chars) => match c.value().to_str() {
+            Ok(s) => s.chars().count(),
+            Err(_) => return Err(error2(c.span(), "Cannot count characters in a C-string literal that is not valid UTF-8")),
+        },
+        (lit, _) => return Err(error2(lit.span(), "Expected a string, byte string or C-string literal")),
+    };
+
+    // Serialize it as a literal with the same span
+    let mut res = Literal::usize_suffixed(len);
+    res.set_span(lit.span());
+    Ok(TokenStream::from(TokenTree::Literal(res)))
+}
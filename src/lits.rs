@@ -0,0 +1,357 @@
+//  LITS.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines helpers for parsing and emitting literals, shared by the macros that operate on
+//!   them.
+//
+
+use std::cmp::Ordering;
+use std::ops::Range;
+use std::str::FromStr as _;
+
+use proc_macro2::{Delimiter, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitBool, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2};
+
+
+/***** LIBRARY *****/
+/// Defines the integer types that integer literals can be suffixed with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IntType {
+    /// `i8`
+    I8,
+    /// `i16`
+    I16,
+    /// `i32`
+    I32,
+    /// `i64`
+    I64,
+    /// `i128`
+    I128,
+    /// `isize`
+    ISize,
+    /// `u8`
+    U8,
+    /// `u16`
+    U16,
+    /// `u32`
+    U32,
+    /// `u64`
+    U64,
+    /// `u128`
+    U128,
+    /// `usize`
+    USize,
+}
+impl IntType {
+    /// Parses an IntType from the suffix of a literal (or the name of the type).
+    ///
+    /// # Arguments
+    /// - `suffix`: The suffix to parse.
+    ///
+    /// # Returns
+    /// The IntType, or [`None`] if the suffix is not that of an integer type.
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "i8" => Some(Self::I8),
+            "i16" => Some(Self::I16),
+            "i32" => Some(Self::I32),
+            "i64" => Some(Self::I64),
+            "i128" => Some(Self::I128),
+            "isize" => Some(Self::ISize),
+            "u8" => Some(Self::U8),
+            "u16" => Some(Self::U16),
+            "u32" => Some(Self::U32),
+            "u64" => Some(Self::U64),
+            "u128" => Some(Self::U128),
+            "usize" => Some(Self::USize),
+            _ => None,
+        }
+    }
+
+    /// Returns the suffix (i.e., the name) of this type.
+    ///
+    /// # Returns
+    /// A string with the name of the type.
+    pub const fn suffix(&self) -> &'static str {
+        match self {
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::I128 => "i128",
+            Self::ISize => "isize",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::U128 => "u128",
+            Self::USize => "usize",
+        }
+    }
+
+    /// Returns the width of this type in bits.
+    ///
+    /// Note that `isize` and `usize` are assumed to be 64 bits wide.
+    ///
+    /// # Returns
+    /// The number of bits in this type.
+    pub const fn bits(&self) -> u32 {
+        match self {
+            Self::I8 | Self::U8 => 8,
+            Self::I16 | Self::U16 => 16,
+            Self::I32 | Self::U32 => 32,
+            Self::I64 | Self::U64 | Self::ISize | Self::USize => 64,
+            Self::I128 | Self::U128 => 128,
+        }
+    }
+
+    /// Returns whether this type is signed.
+    ///
+    /// # Returns
+    /// True if it is, false otherwise.
+    pub const fn is_signed(&self) -> bool { matches!(self, Self::I8 | Self::I16 | Self::I32 | Self::I64 | Self::I128 | Self::ISize) }
+
+    /// Checks whether the given value fits in this type.
+    ///
+    /// # Arguments
+    /// - `neg`: Whether the value is negative.
+    /// - `abs`: The absolute value of the value.
+    ///
+    /// # Returns
+    /// True if it fits, false otherwise.
+    pub const fn fits(&self, neg: bool, abs: u128) -> bool {
+        let bits: u32 = self.bits();
+        match (self.is_signed(), neg) {
+            (false, true) => abs == 0,
+            (false, false) => bits == 128 || abs < (1 << bits),
+            (true, true) => abs <= (1 << (bits - 1)),
+            (true, false) => abs < (1 << (bits - 1)),
+        }
+    }
+}
+
+
+
+/// Represents a literal that may be prefixed by a `-`.
+pub struct SignedLit {
+    /// Whether the literal was negated.
+    pub neg: bool,
+    /// The literal itself.
+    pub lit: Lit,
+}
+impl SignedLit {
+    /// Converts this literal back into tokens.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] encoding the literal, preceded by a `-` if it was negated.
+    ///
+    /// # Errors
+    /// This function errors if the literal is of a kind unknown to this crate.
+    pub fn into_tokens(self) -> Result<TokenStream, Diagnostic2> {
+        let span: Span = self.lit.span();
+        let mut res = TokenStream::new();
+        if self.neg {
+            let mut minus = Punct::new('-', Spacing::Alone);
+            minus.set_span(span);
+            res.extend([TokenTree::Punct(minus)]);
+        }
+        res.extend([match self.lit {
+            Lit::Str(lit) => TokenTree::Literal(lit.token()),
+            Lit::ByteStr(lit) => TokenTree::Literal(lit.token()),
+            Lit::CStr(lit) => TokenTree::Literal(lit.token()),
+            Lit::Byte(lit) => TokenTree::Literal(lit.token()),
+            Lit::Char(lit) => TokenTree::Literal(lit.token()),
+            Lit::Int(lit) => TokenTree::Literal(lit.token()),
+            Lit::Float(lit) => TokenTree::Literal(lit.token()),
+            Lit::Bool(lit) => TokenTree::Ident(Ident::new(if lit.value { "true" } else { "false" }, lit.span)),
+            Lit::Verbatim(lit) => TokenTree::Literal(lit),
+            lit => return Err(error2(lit.span(), "Unsupported kind of literal")),
+        }]);
+        Ok(res)
+    }
+}
+
+
+
+/// Represents the value of a numeric literal.
+#[derive(Clone, Copy, Debug)]
+pub enum Number {
+    /// An integer, given as whether it's negative and its absolute value.
+    Int(bool, u128),
+    /// A floating-point number.
+    Float(f64),
+}
+impl Number {
+    /// Parses a Number from a [`SignedLit`].
+    ///
+    /// # Arguments
+    /// - `lit`: The [`SignedLit`] to parse.
+    ///
+    /// # Returns
+    /// A tuple of the parsed Number and the suffix of the literal.
+    ///
+    /// # Errors
+    /// This function errors if the literal was not numeric or too large.
+    pub fn from_lit(lit: &SignedLit) -> Result<(Self, String), Diagnostic2> {
+        match &lit.lit {
+            Lit::Int(int) => {
+                let abs: u128 = int.base10_parse().map_err(|_| error2(int.span(), "Integer literal is too large"))?;
+                Ok((Self::Int(lit.neg, abs), int.suffix().into()))
+            },
+            Lit::Float(float) => {
+                let abs: f64 = float.base10_parse().map_err(|_| error2(float.span(), "Invalid floating-point literal"))?;
+                Ok((Self::Float(if lit.neg { -abs } else { abs }), float.suffix().into()))
+            },
+            other => Err(error2(other.span(), "Expected an integer or floating-point literal")),
+        }
+    }
+
+    /// Compares two numbers by value.
+    ///
+    /// # Arguments
+    /// - `other`: The other Number to compare with.
+    ///
+    /// # Returns
+    /// The [`Ordering`] of `self` with respect to `other`, or [`None`] if they are not of the
+    /// same kind.
+    pub fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Int(lneg, labs), Self::Int(rneg, rabs)) => Some(match (*lneg && *labs != 0, *rneg && *rabs != 0) {
+                (false, false) => labs.cmp(rabs),
+                (true, true) => rabs.cmp(labs),
+                (false, true) => Ordering::Greater,
+                (true, false) => Ordering::Less,
+            }),
+            (Self::Float(l), Self::Float(r)) => l.partial_cmp(r),
+            _ => None,
+        }
+    }
+}
+
+
+
+/// Parses a single literal from the given token.
+///
+/// This transparently unwraps invisible groups, which occur when literals are passed through
+/// `$lit:literal`-captures of declarative macros.
+///
+/// # Arguments
+/// - `tree`: The [`TokenTree`] to parse the literal from.
+///
+/// # Returns
+/// The parsed [`Lit`].
+///
+/// # Errors
+/// This function errors if the given token was not a literal.
+pub fn parse_lit(tree: TokenTree) -> Result<Lit, Diagnostic2> {
+    match tree {
+        TokenTree::Literal(lit) => Ok(Lit::new(lit)),
+        // Booleans are identifiers, but match `$lit:literal` all the same
+        TokenTree::Ident(ident) if ident == "true" || ident == "false" => Ok(Lit::Bool(LitBool::new(ident == "true", ident.span()))),
+
+        // This may occur when given macro input; attempt to recurse into it as single token
+        TokenTree::Group(g) if g.delimiter() == Delimiter::None => {
+            let mut stream = g.stream().into_iter();
+            let tree: TokenTree = stream.next().ok_or_else(|| error2(g.span(), "Expected a literal"))?;
+            if stream.next().is_some() {
+                return Err(error2(g.span(), "Expected a literal"));
+            }
+            parse_lit(tree)
+        },
+
+        // Otherwise, it's BAD
+        _ => Err(error2(tree.span(), "Expected a literal")),
+    }
+}
+
+/// Parses a single, optionally negated literal from the given iterator.
+///
+/// Like [`parse_lit()`], this transparently unwraps invisible groups.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The parsed [`SignedLit`].
+///
+/// # Errors
+/// This function errors if the head of the input was not an optionally negated literal.
+pub fn parse_signed_lit(iter: &mut impl Iterator<Item = TokenTree>) -> Result<SignedLit, Diagnostic2> {
+    match iter.next() {
+        // Negated literals
+        Some(TokenTree::Punct(p)) if p.as_char() == '-' => match iter.next() {
+            Some(tree) => {
+                let SignedLit { neg, lit } = parse_signed_lit(&mut Some(tree).into_iter())?;
+                if neg {
+                    return Err(error2(lit.span(), "Expected a literal"));
+                }
+                Ok(SignedLit { neg: true, lit })
+            },
+            None => Err(error2(p.span(), "Expected a literal after '-'")),
+        },
+
+        // Negated literals passed through macros end up in invisible groups as a whole
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::None => {
+            let mut stream = g.stream().into_iter();
+            let lit = parse_signed_lit(&mut stream)?;
+            if stream.next().is_some() {
+                return Err(error2(g.span(), "Expected a literal"));
+            }
+            Ok(lit)
+        },
+
+        // Non-negated literals
+        Some(tree) => Ok(SignedLit { neg: false, lit: parse_lit(tree)? }),
+        None => Err(error2(Span::mixed_site(), "Expected a literal")),
+    }
+}
+
+/// Attempts to find the span of a part of a string literal.
+///
+/// This is only possible if the compiler supports it (i.e., on nightly) and if the literal
+/// contains no escape sequences. Otherwise, the span of the whole literal is returned.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] to find the span in.
+/// - `range`: The range of bytes in the literal's _value_ to find the span of.
+///
+/// # Returns
+/// A [`Span`] pointing to the given range in the literal, or to the literal as a whole.
+pub fn subspan(lit: &LitStr, range: Range<usize>) -> Span {
+    let token = lit.token();
+    let repr: String = token.to_string();
+    let value: String = lit.value();
+    match repr.find('"') {
+        Some(quote) if repr[quote + 1..].strip_prefix(value.as_str()).is_some_and(|rest| rest.starts_with('"')) => {
+            let start: usize = quote + 1;
+            token.subspan(start + range.start..start + range.end).unwrap_or_else(|| lit.span())
+        },
+        _ => lit.span(),
+    }
+}
+
+/// Generates an integer literal.
+///
+/// # Arguments
+/// - `neg`: Whether the integer is negative. If so, a `-` is generated before the literal.
+/// - `abs`: The absolute value of the integer.
+/// - `ty`: The type to suffix the literal with, if any.
+/// - `span`: The [`Span`] to give the generated tokens.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the literal.
+pub fn int_lit(neg: bool, abs: u128, ty: Option<IntType>, span: Span) -> TokenStream {
+    let mut res = TokenStream::new();
+    if neg && abs != 0 {
+        let mut minus = Punct::new('-', Spacing::Alone);
+        minus.set_span(span);
+        res.extend([TokenTree::Punct(minus)]);
+    }
+    let mut lit = Literal::from_str(&format!("{abs}{}", ty.map(|ty| ty.suffix()).unwrap_or(""))).unwrap();
+    lit.set_span(span);
+    res.extend([TokenTree::Literal(lit)]);
+    res
+}
@@ -0,0 +1,636 @@
+//  MACRO V2.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for defining `macro_rules!`-macros in an extended dialect, with named
+//!   rules, fallback arms, inline errors and built-in counting and indexing.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+
+
+/***** CONSTANTS *****/
+/// The directives that can be used in transcribers, and that hence cannot be used as rule names.
+const DIRECTIVES: [&str; 3] = ["count", "error", "index"];
+
+/// The name of the marker that is emitted for `#count(...)`, and resolved by the helper.
+const COUNT_MARKER: &str = "__mtk_count";
+/// The name of the marker that is emitted for `#index`, and resolved by the helper.
+const INDEX_MARKER: &str = "__mtk_index";
+/// The name of the marker that is emitted for repetitions containing `#index`, and resolved by
+/// the helper.
+const ENUMERATE_MARKER: &str = "__mtk_enumerate";
+/// The name of the mode of the macro that resolves the markers.
+const EXPAND_MODE: &str = "__mtk_expand";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates a path to a macro in `core`, followed by a `!`.
+///
+/// # Arguments
+/// - `name`: The name of the macro (e.g., `compile_error`).
+///
+/// # Returns
+/// A [`TokenStream`] encoding `::core::<name>!`.
+fn core_macro(name: &str) -> TokenStream {
+    TokenStream::from_iter([
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("core", Span::call_site())),
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new(name, Span::call_site())),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+    ])
+}
+
+/// Generates a marker, e.g., `#__mtk_count`.
+///
+/// # Arguments
+/// - `name`: The name of the marker.
+/// - `span`: The span of the directive it replaces.
+///
+/// # Returns
+/// A [`TokenStream`] with the marker.
+fn marker(name: &str, span: Span) -> TokenStream {
+    TokenStream::from_iter([TokenTree::Punct(Punct::new('#', Spacing::Alone)), TokenTree::Ident(Ident::new(name, span))])
+}
+
+/// Wraps a [`TokenStream`] in a [`Group`] with the given delimiter and span.
+///
+/// # Arguments
+/// - `delim`: The delimiter of the group.
+/// - `span`: The span of the group.
+/// - `tokens`: The tokens in the group.
+///
+/// # Returns
+/// A [`TokenTree`] with the group.
+fn group(delim: Delimiter, span: Span, tokens: TokenStream) -> TokenTree {
+    let mut group = Group::new(delim, tokens);
+    group.set_span(span);
+    TokenTree::Group(group)
+}
+
+
+
+
+
+/***** COMPILING *****/
+/// Defines the context needed to compile transcribers.
+struct Context<'m> {
+    /// The path to call the macro itself with, e.g., `foo!` or `$crate::foo!`.
+    path:  &'m TokenStream,
+    /// The names of the named rules of the macro.
+    rules: &'m [Ident],
+}
+
+/// Defines what was found while compiling (part of) a transcriber.
+#[derive(Default)]
+struct Found {
+    /// Whether the transcriber needs to be resolved by the helper.
+    helper: bool,
+    /// Whether `#index` was found outside of any (nested) repetition.
+    index:  Option<Span>,
+}
+
+/// Compiles a transcriber from the extended dialect to an ordinary one.
+///
+/// # Arguments
+/// - `tokens`: The tokens of the transcriber.
+/// - `ctx`: The [`Context`] with information about the macro.
+/// - `found`: A [`Found`] that is updated with the directives found.
+///
+/// # Returns
+/// The compiled transcriber.
+///
+/// # Errors
+/// This function errors if the transcriber used an unknown or malformed directive.
+fn compile_transcriber(tokens: TokenStream, ctx: &Context, found: &mut Found) -> Result<TokenStream, Diagnostic2> {
+    let mut res = TokenStream::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            // Directives
+            TokenTree::Punct(p) if p.as_char() == '#' && matches!(iter.peek(), Some(TokenTree::Ident(_))) => {
+                let Some(TokenTree::Ident(name)) = iter.next() else { unreachable!() };
+                res.extend(compile_directive(&name, &mut iter, ctx, found)?);
+            },
+
+            // Repetitions
+            TokenTree::Punct(p) if p.as_char() == '$' && matches!(iter.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis) => {
+                let Some(TokenTree::Group(body)) = iter.next() else { unreachable!() };
+                res.extend(compile_repetition(p, body, &mut iter, ctx, found)?);
+            },
+
+            // Anything else is kept as-is
+            TokenTree::Group(g) => {
+                let inner: TokenStream = compile_transcriber(g.stream(), ctx, found)?;
+                res.extend([group(g.delimiter(), g.span(), inner)]);
+            },
+            tt => res.extend([tt]),
+        }
+    }
+    Ok(res)
+}
+
+/// Compiles a single directive (e.g., `#count($x)`).
+///
+/// # Arguments
+/// - `name`: The name of the directive, i.e., the identifier after the `#`.
+/// - `iter`: The iterator yielding the tokens after the name.
+/// - `ctx`: The [`Context`] with information about the macro.
+/// - `found`: A [`Found`] that is updated with the directives found.
+///
+/// # Returns
+/// The compiled directive.
+///
+/// # Errors
+/// This function errors if the directive is unknown or malformed.
+fn compile_directive(name: &Ident, iter: &mut Peekable<impl Iterator<Item = TokenTree>>, ctx: &Context, found: &mut Found) -> Result<TokenStream, Diagnostic2> {
+    // Every directive except `#index` has arguments
+    let args: Option<Group> = match iter.peek() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis && name != "index" => match iter.next() {
+            Some(TokenTree::Group(g)) => Some(g),
+            _ => unreachable!(),
+        },
+        _ => None,
+    };
+
+    let name_str: String = name.to_string();
+    match (name_str.as_str(), args) {
+        ("index", _) => {
+            found.helper = true;
+            found.index = Some(found.index.unwrap_or(name.span()));
+            Ok(marker(INDEX_MARKER, name.span()))
+        },
+        ("count", Some(args)) => {
+            // Parse the variable to count
+            let var: Ident = match args.stream().into_iter().collect::<Vec<TokenTree>>().as_slice() {
+                [TokenTree::Punct(p), TokenTree::Ident(var)] if p.as_char() == '$' => var.clone(),
+                _ => return Err(error2(args.span(), "Expected a single metavariable (e.g., `#count($x)`)")),
+            };
+
+            // Emit `#__mtk_count [$({$var})*]`
+            found.helper = true;
+            let mut res: TokenStream = marker(COUNT_MARKER, name.span());
+            let var = TokenStream::from_iter([TokenTree::Punct(Punct::new('$', Spacing::Alone)), TokenTree::Ident(var)]);
+            res.extend([group(
+                Delimiter::Bracket,
+                args.span(),
+                TokenStream::from_iter([
+                    TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+                    group(Delimiter::Parenthesis, args.span(), TokenStream::from(group(Delimiter::Brace, args.span(), var))),
+                    TokenTree::Punct(Punct::new('*', Spacing::Alone)),
+                ]),
+            )]);
+            Ok(res)
+        },
+        ("error", Some(args)) => {
+            // Emit `::core::compile_error! { ::core::concat!(...) }`
+            let args: TokenStream = compile_transcriber(args.stream(), ctx, found)?;
+            let mut concat: TokenStream = core_macro("concat");
+            concat.extend([group(Delimiter::Parenthesis, name.span(), args)]);
+            let mut res: TokenStream = core_macro("compile_error");
+            res.extend([group(Delimiter::Brace, name.span(), concat)]);
+            Ok(res)
+        },
+        (_, Some(args)) if ctx.rules.iter().any(|rule| rule == name) => {
+            // Emit `<path>! { @<rule> ... }`
+            let mut inner = TokenStream::from_iter([TokenTree::Punct(Punct::new('@', Spacing::Alone)), TokenTree::Ident(name.clone())]);
+            inner.extend(compile_transcriber(args.stream(), ctx, found)?);
+            let mut res: TokenStream = ctx.path.clone();
+            res.extend([group(Delimiter::Brace, args.span(), inner)]);
+            Ok(res)
+        },
+        (_, None) if DIRECTIVES.contains(&name_str.as_str()) || ctx.rules.iter().any(|rule| rule == name) => {
+            Err(error2(name.span(), &format!("Expected arguments in parenthesis after `#{name}`")))
+        },
+        _ => Err(error2(name.span(), &format!("Unknown directive `#{name}` (expected `count`, `index`, `error` or the name of a rule)"))),
+    }
+}
+
+/// Compiles a repetition (i.e., `$( ... ) sep? op`).
+///
+/// If the body of the repetition uses `#index` directly, the repetition is wrapped in a marker
+/// such that the helper can resolve it.
+///
+/// # Arguments
+/// - `dollar`: The `$` of the repetition.
+/// - `body`: The body of the repetition.
+/// - `iter`: The iterator yielding the tokens after the body.
+/// - `ctx`: The [`Context`] with information about the macro.
+/// - `found`: A [`Found`] that is updated with the directives found.
+///
+/// # Returns
+/// The compiled repetition.
+///
+/// # Errors
+/// This function errors if the body is invalid or if the repetition has no operator.
+fn compile_repetition(
+    dollar: Punct,
+    body: Group,
+    iter: &mut Peekable<impl Iterator<Item = TokenTree>>,
+    ctx: &Context,
+    found: &mut Found,
+) -> Result<TokenStream, Diagnostic2> {
+    // Compile the body in a fresh context, as `#index` in it refers to this repetition
+    let mut inner = Found::default();
+    let compiled: TokenStream = compile_transcriber(body.stream(), ctx, &mut inner)?;
+    found.helper |= inner.helper;
+
+    // Parse the separator and the operator
+    let mut sep: Option<TokenTree> = None;
+    let op: TokenTree = loop {
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if sep.is_none() && matches!(p.as_char(), '*' | '+' | '?') => break TokenTree::Punct(p),
+            Some(tt) if sep.is_none() => sep = Some(tt),
+            Some(TokenTree::Punct(p)) if matches!(p.as_char(), '*' | '+') => break TokenTree::Punct(p),
+            Some(tt) => return Err(error2(tt.span(), "Expected '*' or '+' after the separator of a repetition")),
+            None => return Err(error2(body.span(), "Expected '*', '+' or '?' after a repetition")),
+        }
+    };
+
+    // Emit it as-is if it doesn't use `#index` directly
+    let mut res = TokenStream::new();
+    if inner.index.is_none() {
+        res.extend([TokenTree::Punct(dollar), group(Delimiter::Parenthesis, body.span(), compiled)]);
+        res.extend(sep);
+        res.extend([op]);
+        return Ok(res);
+    }
+
+    // Otherwise, emit `#__mtk_enumerate (sep) { $({ ... })op }`
+    res.extend(marker(ENUMERATE_MARKER, body.span()));
+    res.extend([group(Delimiter::Parenthesis, body.span(), sep.into_iter().collect())]);
+    res.extend([group(
+        Delimiter::Brace,
+        body.span(),
+        TokenStream::from_iter([
+            TokenTree::Punct(dollar),
+            group(Delimiter::Parenthesis, body.span(), TokenStream::from(group(Delimiter::Brace, body.span(), compiled))),
+            op,
+        ]),
+    )]);
+    Ok(res)
+}
+
+
+
+/// Resolves the markers emitted by the compiled transcribers, after the `macro_rules!`-macro has
+/// been expanded.
+///
+/// # Arguments
+/// - `tokens`: The expanded transcriber.
+/// - `index`: The index of the innermost repetition that is being resolved, if any.
+///
+/// # Returns
+/// The tokens with all markers resolved.
+///
+/// # Errors
+/// This function errors if `#index` was used outside of a repetition.
+fn resolve(tokens: TokenStream, index: Option<usize>) -> Result<TokenStream, Diagnostic2> {
+    let mut res = TokenStream::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        // Find the markers
+        let name: Ident = match (&tt, iter.peek()) {
+            (TokenTree::Punct(p), Some(TokenTree::Ident(name)))
+                if p.as_char() == '#' && (name == INDEX_MARKER || name == COUNT_MARKER || name == ENUMERATE_MARKER) =>
+            {
+                name.clone()
+            },
+            (TokenTree::Group(g), _) => {
+                res.extend([group(g.delimiter(), g.span(), resolve(g.stream(), index)?)]);
+                continue;
+            },
+            _ => {
+                res.extend([tt]);
+                continue;
+            },
+        };
+        iter.next();
+
+        // Resolve them
+        if name == INDEX_MARKER {
+            match index {
+                Some(index) => res.extend([TokenTree::Literal(Literal::usize_unsuffixed(index))]),
+                None => return Err(error2(name.span(), "`#index` can only be used in a repetition")),
+            }
+        } else if name == COUNT_MARKER {
+            let Some(TokenTree::Group(items)) = iter.next() else { return Err(error2(name.span(), "Malformed count marker")) };
+            let mut lit = Literal::usize_unsuffixed(items.stream().into_iter().count());
+            lit.set_span(name.span());
+            res.extend([TokenTree::Literal(lit)]);
+        } else {
+            let (Some(TokenTree::Group(sep)), Some(TokenTree::Group(items))) = (iter.next(), iter.next()) else {
+                return Err(error2(name.span(), "Malformed enumerate marker"));
+            };
+            for (i, item) in items.stream().into_iter().enumerate() {
+                if i > 0 {
+                    res.extend(sep.stream());
+                }
+                match item {
+                    TokenTree::Group(item) => res.extend(resolve(item.stream(), Some(i))?),
+                    item => return Err(error2(item.span(), "Malformed enumerate marker")),
+                }
+            }
+        }
+    }
+    Ok(res)
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single arm of a macro.
+enum Arm {
+    /// An ordinary arm.
+    Unnamed { matcher: Group, transcriber: Group },
+    /// A named arm (e.g., `#name(...) => { ... }`), which is called with `#name(...)`.
+    Named { name: Ident, matcher: Group, transcriber: Group },
+    /// The fallback arm (i.e., `else => { ... }`).
+    Else { span: Span, transcriber: Group },
+}
+
+/// Defines a single macro definition.
+struct Definition {
+    /// Any attributes on the macro.
+    attrs:    TokenStream,
+    /// Whether the macro is exported (i.e., has `#[macro_export]`).
+    exported: bool,
+    /// The name of the macro.
+    name:     Ident,
+    /// The arms of the macro.
+    arms:     Vec<Arm>,
+}
+impl Definition {
+    /// Parses a Definition from an iterator over [`TokenTree`]s.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Definition.
+    ///
+    /// # Errors
+    /// This function can error if the head of the input was not a valid definition.
+    fn parse(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Self, Diagnostic2> {
+        // Parse the attributes
+        let mut attrs = TokenStream::new();
+        let mut exported: bool = false;
+        while let Some(pound) = iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '#')) {
+            let attr: Group = match iter.next() {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => g,
+                Some(tt) => return Err(error2(tt.span(), "Expected an attribute in square brackets")),
+                None => return Err(error2(pound.span(), "Expected an attribute in square brackets")),
+            };
+            exported |= attr.stream().to_string() == "macro_export";
+            attrs.extend([pound, TokenTree::Group(attr)]);
+        }
+
+        // Parse the `macro_rules! name`
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "macro_rules" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected 'macro_rules'")),
+            None => return Err(error2(Span::call_site(), "Expected 'macro_rules'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '!' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '!'")),
+            None => return Err(error2(Span::call_site(), "Expected '!'")),
+        }
+        let name: Ident = match iter.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tt) => return Err(error2(tt.span(), "Expected the name of the macro")),
+            None => return Err(error2(Span::call_site(), "Expected the name of the macro")),
+        };
+        let body: Group = match iter.next() {
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => g,
+            Some(tt) => return Err(error2(tt.span(), "Expected the arms of the macro in curly brackets")),
+            None => return Err(error2(name.span(), "Expected the arms of the macro in curly brackets")),
+        };
+        iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ';'));
+
+        // Parse the arms
+        let mut arms: Vec<Arm> = Vec::new();
+        let mut body = body.stream().into_iter().peekable();
+        while body.peek().is_some() {
+            if let Some(Arm::Else { span, .. }) = arms.last() {
+                return Err(error2(*span, "The `else`-arm must be the last arm of the macro"));
+            }
+            arms.push(Self::parse_arm(&mut body)?);
+        }
+        if arms.is_empty() {
+            return Err(error2(name.span(), "Expected at least one arm"));
+        }
+        Ok(Self { attrs, exported, name, arms })
+    }
+
+    /// Parses a single arm.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the tokens in the body of the macro.
+    ///
+    /// # Returns
+    /// The parsed [`Arm`].
+    ///
+    /// # Errors
+    /// This function can error if the head of the input was not a valid arm.
+    fn parse_arm(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Arm, Diagnostic2> {
+        // Parse the head of the arm
+        enum Head {
+            Unnamed(Group),
+            Named(Ident, Group),
+            Else(Span),
+        }
+        let head: Head = match iter.next() {
+            Some(TokenTree::Group(g)) if g.delimiter() != Delimiter::None => Head::Unnamed(g),
+            Some(TokenTree::Ident(ident)) if ident == "else" => Head::Else(ident.span()),
+            Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                let name: Ident = match iter.next() {
+                    Some(TokenTree::Ident(name)) if DIRECTIVES.contains(&name.to_string().as_str()) => {
+                        return Err(error2(name.span(), &format!("Cannot use `{name}` as the name of a rule, as it is a directive")));
+                    },
+                    Some(TokenTree::Ident(name)) => name,
+                    Some(tt) => return Err(error2(tt.span(), "Expected the name of a rule")),
+                    None => return Err(error2(p.span(), "Expected the name of a rule")),
+                };
+                match iter.next() {
+                    Some(TokenTree::Group(g)) if g.delimiter() != Delimiter::None => Head::Named(name, g),
+                    Some(tt) => return Err(error2(tt.span(), "Expected a matcher")),
+                    None => return Err(error2(name.span(), "Expected a matcher")),
+                }
+            },
+            Some(tt) => return Err(error2(tt.span(), "Expected a matcher, a named rule (e.g., `#name(...)`) or 'else'")),
+            None => return Err(error2(Span::call_site(), "Expected a matcher, a named rule (e.g., `#name(...)`) or 'else'")),
+        };
+
+        // Parse the arrow and the transcriber
+        match (iter.next(), iter.next()) {
+            (Some(TokenTree::Punct(p1)), Some(TokenTree::Punct(p2))) if p1.as_char() == '=' && p2.as_char() == '>' => {},
+            (Some(tt), _) => return Err(error2(tt.span(), "Expected '=>'")),
+            (None, _) => return Err(error2(Span::call_site(), "Expected '=>'")),
+        }
+        let transcriber: Group = match iter.next() {
+            Some(TokenTree::Group(g)) if g.delimiter() != Delimiter::None => g,
+            Some(tt) => return Err(error2(tt.span(), "Expected a transcriber")),
+            None => return Err(error2(Span::call_site(), "Expected a transcriber")),
+        };
+        iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ';'));
+
+        Ok(match head {
+            Head::Unnamed(matcher) => Arm::Unnamed { matcher, transcriber },
+            Head::Named(name, matcher) => Arm::Named { name, matcher, transcriber },
+            Head::Else(span) => Arm::Else { span, transcriber },
+        })
+    }
+
+    /// Compiles this Definition to an ordinary `macro_rules!`-macro.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] defining the macro.
+    ///
+    /// # Errors
+    /// This function errors if any of the transcribers is invalid.
+    fn compile(self) -> Result<TokenStream, Diagnostic2> {
+        let Self { attrs, exported, name, arms } = self;
+
+        // Find how to refer to the macro itself
+        let mut path = TokenStream::new();
+        if exported {
+            path.extend([
+                TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+                TokenTree::Ident(Ident::new("crate", Span::call_site())),
+                TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+                TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            ]);
+        }
+        path.extend([TokenTree::Ident(name.clone()), TokenTree::Punct(Punct::new('!', Spacing::Alone))]);
+        let rules: Vec<Ident> = arms
+            .iter()
+            .filter_map(|arm| match arm {
+                Arm::Named { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        let ctx = Context { path: &path, rules: &rules };
+
+        // Compile the arms, with the named ones first such that they're never shadowed
+        let (named, unnamed): (Vec<Arm>, Vec<Arm>) = arms.into_iter().partition(|arm| matches!(arm, Arm::Named { .. }));
+        let mut body = TokenStream::new();
+        for arm in named.into_iter().chain(unnamed) {
+            let (matcher, transcriber): (TokenTree, Group) = match arm {
+                Arm::Unnamed { matcher, transcriber } => (TokenTree::Group(matcher), transcriber),
+                Arm::Named { name, matcher, transcriber } => {
+                    let mut inner = TokenStream::from_iter([TokenTree::Punct(Punct::new('@', Spacing::Alone)), TokenTree::Ident(name)]);
+                    inner.extend(matcher.stream());
+                    (group(matcher.delimiter(), matcher.span(), inner), transcriber)
+                },
+                Arm::Else { span, transcriber } => {
+                    // `$($rest:tt)*`
+                    let rest = TokenStream::from_iter([
+                        TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+                        TokenTree::Ident(Ident::new("rest", span)),
+                        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                        TokenTree::Ident(Ident::new("tt", span)),
+                    ]);
+                    let matcher = TokenStream::from_iter([
+                        TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+                        group(Delimiter::Parenthesis, span, rest),
+                        TokenTree::Punct(Punct::new('*', Spacing::Alone)),
+                    ]);
+                    (group(Delimiter::Parenthesis, span, matcher), transcriber)
+                },
+            };
+
+            // Compile the transcriber, and wrap it in a call to the helper if needed
+            let mut found = Found::default();
+            let mut compiled: TokenStream = compile_transcriber(transcriber.stream(), &ctx, &mut found)?;
+            if let Some(span) = found.index {
+                return Err(error2(span, "`#index` can only be used in a repetition"));
+            }
+            if found.helper {
+                let mut call = TokenStream::from_iter([
+                    TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+                    TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                    TokenTree::Ident(Ident::new("macro_toolkit", Span::call_site())),
+                    TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+                    TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                    TokenTree::Ident(Ident::new("macro_v2", Span::call_site())),
+                    TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+                ]);
+                let mut args =
+                    TokenStream::from_iter([TokenTree::Punct(Punct::new('@', Spacing::Alone)), TokenTree::Ident(Ident::new(EXPAND_MODE, Span::call_site()))]);
+                args.extend(compiled);
+                call.extend([group(Delimiter::Brace, transcriber.span(), args)]);
+                compiled = call;
+            }
+
+            body.extend([
+                matcher,
+                TokenTree::Punct(Punct::new('=', Spacing::Joint)),
+                TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+                group(Delimiter::Brace, transcriber.span(), compiled),
+                TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+            ]);
+        }
+
+        // Build the macro
+        let mut res: TokenStream = attrs;
+        res.extend([
+            TokenTree::Ident(Ident::new("macro_rules", Span::call_site())),
+            TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+            TokenTree::Ident(name),
+            TokenTree::Group(Group::new(Delimiter::Brace, body)),
+        ]);
+        Ok(res)
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`macro_v2()`](super::macro_v2())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the macro definitions in the extended dialect.
+///
+/// # Returns
+/// A new [`TokenStream`] with ordinary `macro_rules!`-macros.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn macro_v2(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+
+    // Resolve markers if we're called by a compiled macro
+    if let Some(at) = iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '@')) {
+        return match iter.next() {
+            Some(TokenTree::Ident(mode)) if mode == EXPAND_MODE => resolve(iter.collect(), None),
+            Some(tt) => Err(error2(tt.span(), "Unknown mode (this syntax is reserved for macros generated by `macro_v2!`)")),
+            None => Err(error2(at.span(), "Expected a mode (this syntax is reserved for macros generated by `macro_v2!`)")),
+        };
+    }
+
+    // Otherwise, compile the definitions
+    let mut res = TokenStream::new();
+    while iter.peek().is_some() {
+        match Definition::parse(&mut iter).and_then(Definition::compile) {
+            Ok(def) => res.extend(def),
+            // NOTE: The error ends up in item position, so needs a semicolon to be a valid item
+            Err(err) => return Err(item_error(err)),
+        }
+    }
+    Ok(res)
+}
@@ -0,0 +1,709 @@
+//  MATCH EXPR.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for switching on the outermost form of expressions.
+//
+
+use proc_macro2::{Delimiter, Literal, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::{Branch, is_ident, is_punct, join_commas, parse_input, skip_angles, split_commas, unwrap_none};
+
+
+/***** CONSTANTS *****/
+/// The binary operators, with their precedence (higher binds tighter). Longer operators come
+/// first, such that they are matched before their prefixes.
+const OPERATORS: [(&str, u8); 31] = [
+    ("<<=", 1),
+    (">>=", 1),
+    ("..=", 2),
+    ("+=", 1),
+    ("-=", 1),
+    ("*=", 1),
+    ("/=", 1),
+    ("%=", 1),
+    ("^=", 1),
+    ("&=", 1),
+    ("|=", 1),
+    ("..", 2),
+    ("||", 3),
+    ("&&", 4),
+    ("==", 5),
+    ("!=", 5),
+    ("<=", 5),
+    (">=", 5),
+    ("<<", 9),
+    (">>", 9),
+    ("=", 1),
+    ("<", 5),
+    (">", 5),
+    ("|", 6),
+    ("^", 7),
+    ("&", 8),
+    ("+", 10),
+    ("-", 10),
+    ("*", 11),
+    ("/", 11),
+    ("%", 11),
+];
+
+/// The precedence of assignments.
+const PREC_ASSIGN: u8 = 1;
+/// The precedence of ranges.
+const PREC_RANGE: u8 = 2;
+/// The precedence of casts.
+const PREC_CAST: u8 = 12;
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Collects a slice of tokens into a [`TokenStream`].
+///
+/// # Arguments
+/// - `tokens`: The tokens to collect.
+///
+/// # Returns
+/// A [`TokenStream`] with clones of the tokens.
+#[inline]
+fn collect(tokens: &[TokenTree]) -> TokenStream { tokens.iter().cloned().collect() }
+
+/// Attempts to parse a binary operator at the given position.
+///
+/// Operators spanning multiple characters are only recognized if their [`Punct`](proc_macro2::Punct)s
+/// are joint.
+///
+/// # Arguments
+/// - `tokens`: The tokens to parse in.
+/// - `i`: The position of the first token of the operator.
+///
+/// # Returns
+/// The number of tokens in the operator and its precedence, or [`None`] if there is no operator
+/// at `i`.
+fn parse_operator(tokens: &[TokenTree], i: usize) -> Option<(usize, u8)> {
+    // Collect the characters of the joint puncts
+    let mut chars = String::new();
+    for tt in &tokens[i..] {
+        let TokenTree::Punct(p) = tt else { break };
+        chars.push(p.as_char());
+        if p.spacing() == Spacing::Alone || chars.len() == 3 {
+            break;
+        }
+    }
+    OPERATORS.iter().find(|(op, _)| chars.starts_with(op)).map(|(op, prec)| (op.len(), *prec))
+}
+
+/// Finds the position right after the first brace group at or after the given position.
+///
+/// # Arguments
+/// - `tokens`: The tokens to search in.
+/// - `i`: The position to start searching at.
+///
+/// # Returns
+/// The position after the brace group, or the length of `tokens` if there is none.
+fn skip_past_block(tokens: &[TokenTree], i: usize) -> usize {
+    tokens[i..].iter().position(|tt| matches!(tt, TokenTree::Group(g) if g.delimiter() == Delimiter::Brace)).map(|pos| i + pos + 1).unwrap_or(tokens.len())
+}
+
+/// Finds the position right after a path (e.g., `foo`, `::std::vec::Vec::<u8>` or
+/// `<T as Trait>::foo`).
+///
+/// # Arguments
+/// - `tokens`: The tokens to search in.
+/// - `i`: The position of the start of the path.
+///
+/// # Returns
+/// The position after the path.
+fn skip_path(tokens: &[TokenTree], mut i: usize) -> usize {
+    if is_punct(tokens.get(i), '<') {
+        i = skip_angles(tokens, i).unwrap_or(tokens.len());
+    }
+    loop {
+        if matches!(tokens.get(i), Some(TokenTree::Ident(_))) {
+            i += 1;
+        }
+        if !(is_punct(tokens.get(i), ':') && is_punct(tokens.get(i + 1), ':')) {
+            return i;
+        }
+        i += 2;
+        if is_punct(tokens.get(i), '<') {
+            i = skip_angles(tokens, i).unwrap_or(tokens.len());
+        }
+    }
+}
+
+
+
+
+
+/***** SCANNING *****/
+/// Describes the last postfix operation applied to an operand.
+#[derive(Clone, Copy)]
+enum Postfix {
+    /// A function call, with the position of the arguments.
+    Call(usize),
+    /// A method call, with the position of the `.`, the method name and the arguments.
+    Method(usize, usize, usize),
+    /// A field access, with the position of the `.`.
+    Field(usize),
+    /// An index, with the position of the brackets.
+    Index(usize),
+    /// A `?`, with its position.
+    Try(usize),
+    /// An `.await`, with the position of the `.`.
+    Await(usize),
+    /// A macro invocation, with the position of the `!`.
+    Macro(usize),
+    /// A struct expression, with the position of the braces.
+    Struct(usize),
+}
+
+/// Finds the position right after an operand, including any prefix and postfix operators.
+///
+/// # Arguments
+/// - `tokens`: The tokens to search in.
+/// - `i`: The position of the start of the operand.
+///
+/// # Returns
+/// The position after the operand and the last postfix operator applied to it, if any.
+fn skip_operand(tokens: &[TokenTree], mut i: usize) -> (usize, Option<Postfix>) {
+    // Skip prefix operators
+    loop {
+        match tokens.get(i) {
+            Some(TokenTree::Punct(p)) if matches!(p.as_char(), '-' | '!' | '*') => i += 1,
+            Some(TokenTree::Punct(p)) if p.as_char() == '&' => {
+                i += 1;
+                if is_ident(tokens.get(i), "mut") {
+                    i += 1;
+                }
+            },
+            _ => break,
+        }
+    }
+
+    // Skip the primary expression
+    let mut path: bool = false;
+    match tokens.get(i) {
+        // Forms that run until the end of the expression
+        Some(TokenTree::Ident(ident)) if matches!(ident.to_string().as_str(), "return" | "break" | "continue" | "yield" | "move") => {
+            return (tokens.len(), None);
+        },
+        Some(TokenTree::Punct(p)) if p.as_char() == '|' => return (tokens.len(), None),
+        Some(TokenTree::Ident(ident))
+            if ident == "async" && !matches!(tokens.get(i + 1), Some(TokenTree::Group(_))) && !is_ident(tokens.get(i + 1), "move") =>
+        {
+            return (tokens.len(), None);
+        },
+        Some(TokenTree::Ident(ident)) if ident == "async" && is_ident(tokens.get(i + 1), "move") && is_punct(tokens.get(i + 2), '|') => {
+            return (tokens.len(), None);
+        },
+
+        // Forms that end in blocks
+        Some(TokenTree::Punct(p)) if p.as_char() == '\'' => i = skip_past_block(tokens, i),
+        Some(TokenTree::Ident(ident)) if matches!(ident.to_string().as_str(), "while" | "for" | "loop" | "match" | "unsafe" | "async" | "const") => {
+            i = skip_past_block(tokens, i)
+        },
+        Some(TokenTree::Ident(ident)) if ident == "if" => {
+            i = skip_past_block(tokens, i);
+            while is_ident(tokens.get(i), "else") {
+                i = skip_past_block(tokens, i + 1);
+            }
+        },
+
+        // Others
+        Some(TokenTree::Literal(_) | TokenTree::Group(_)) => i += 1,
+        Some(TokenTree::Ident(_)) => {
+            i = skip_path(tokens, i);
+            path = true;
+        },
+        Some(TokenTree::Punct(p)) if p.as_char() == ':' || p.as_char() == '<' => {
+            i = skip_path(tokens, i);
+            path = true;
+        },
+        _ => return (i, None),
+    }
+
+    // Skip the things that can only follow paths
+    let mut last: Option<Postfix> = None;
+    if path {
+        match tokens.get(i) {
+            Some(TokenTree::Punct(p)) if p.as_char() == '!' && matches!(tokens.get(i + 1), Some(TokenTree::Group(_))) => {
+                last = Some(Postfix::Macro(i));
+                i += 2;
+            },
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                last = Some(Postfix::Struct(i));
+                i += 1;
+            },
+            _ => {},
+        }
+    }
+
+    // Skip postfix operators
+    loop {
+        match tokens.get(i) {
+            Some(TokenTree::Punct(p)) if p.as_char() == '?' => {
+                last = Some(Postfix::Try(i));
+                i += 1;
+            },
+            Some(TokenTree::Punct(p)) if p.as_char() == '.' && !is_punct(tokens.get(i + 1), '.') => match tokens.get(i + 1) {
+                Some(TokenTree::Ident(ident)) if ident == "await" => {
+                    last = Some(Postfix::Await(i));
+                    i += 2;
+                },
+                Some(TokenTree::Ident(_)) => {
+                    let dot: usize = i;
+                    i = skip_path(tokens, i + 1);
+                    match tokens.get(i) {
+                        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                            last = Some(Postfix::Method(dot, dot + 1, i));
+                            i += 1;
+                        },
+                        _ => last = Some(Postfix::Field(dot)),
+                    }
+                },
+                Some(TokenTree::Literal(_)) => {
+                    last = Some(Postfix::Field(i));
+                    i += 2;
+                },
+                _ => break,
+            },
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                last = Some(Postfix::Call(i));
+                i += 1;
+            },
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => {
+                last = Some(Postfix::Index(i));
+                i += 1;
+            },
+            _ => break,
+        }
+    }
+    (i, last)
+}
+
+/// Finds the binary operator (or cast) with the lowest precedence outside of any operand.
+///
+/// # Arguments
+/// - `tokens`: The tokens of the expression.
+///
+/// # Returns
+/// The start and end positions of the operator and its precedence, or [`None`] if there is no
+/// binary operator.
+fn find_operator(tokens: &[TokenTree]) -> Option<(usize, usize, u8)> {
+    let mut ops: Vec<(usize, usize, u8)> = Vec::new();
+    let mut i: usize = 0;
+    let mut operand: bool = true;
+    while i < tokens.len() {
+        if operand {
+            // Ranges may omit their start
+            if let Some((len, PREC_RANGE)) = parse_operator(tokens, i) {
+                ops.push((i, i + len, PREC_RANGE));
+                i += len;
+                continue;
+            }
+            let (next, _) = skip_operand(tokens, i);
+            if next == i {
+                break;
+            }
+            i = next;
+            operand = false;
+        } else if is_ident(tokens.get(i), "as") {
+            // Skip the type cast to
+            ops.push((i, i + 1, PREC_CAST));
+            i += 1;
+            if is_punct(tokens.get(i), '*') {
+                i += 2;
+            }
+            i = skip_path(tokens, i);
+            if is_punct(tokens.get(i), '<') {
+                i = skip_angles(tokens, i).unwrap_or(tokens.len());
+            }
+        } else if let Some((len, prec)) = parse_operator(tokens, i) {
+            ops.push((i, i + len, prec));
+            i += len;
+            operand = true;
+        } else {
+            break;
+        }
+    }
+
+    // Assignments are right-associative, the others left-associative
+    let min: u8 = ops.iter().map(|(_, _, prec)| *prec).min()?;
+    if min == PREC_ASSIGN || min == PREC_RANGE {
+        ops.into_iter().find(|(_, _, prec)| *prec == min)
+    } else {
+        ops.into_iter().rev().find(|(_, _, prec)| *prec == min)
+    }
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the possible outermost forms of expressions.
+enum Expr {
+    /// An assignment, with its left-hand side, operator and right-hand side (e.g., `a += 1`).
+    Assign(TokenStream, TokenStream, TokenStream),
+    /// A range, with its (possibly empty) start and end (e.g., `0..n`).
+    Range(TokenStream, TokenStream),
+    /// A binary operation, with its left-hand side, operator and right-hand side (e.g., `a + b`).
+    Binary(TokenStream, TokenStream, TokenStream),
+    /// A cast, with its expression and type (e.g., `a as u8`).
+    Cast(TokenStream, TokenStream),
+    /// A unary operation, with its operator and operand (e.g., `-a`, `!a` or `*a`).
+    Unary(TokenStream, TokenStream),
+    /// A reference (e.g., `&mut a`).
+    Ref { mutable: bool, expr: TokenStream },
+    /// A literal (e.g., `42` or `true`).
+    Lit,
+    /// A path (e.g., `foo` or `Vec::<u8>::new`).
+    Path,
+    /// A function call, with the function and its comma-separated arguments (e.g., `f(a, b)`).
+    Call(TokenStream, TokenStream),
+    /// A method call, with its receiver, method and comma-separated arguments (e.g., `a.f(b)`).
+    Method(TokenStream, TokenStream, TokenStream),
+    /// A field access, with its base and member (e.g., `a.b` or `a.0`).
+    Field(TokenStream, TokenStream),
+    /// An index, with its base and index (e.g., `a[0]`).
+    Index(TokenStream, TokenStream),
+    /// A `?`, with its operand.
+    Try(TokenStream),
+    /// An `.await`, with its operand.
+    Await(TokenStream),
+    /// A macro invocation, with its path (e.g., `vec![]`).
+    Macro(TokenStream),
+    /// A struct expression, with its path (e.g., `Foo { a: 1 }`).
+    Struct(TokenStream),
+    /// A tuple, with its comma-separated elements (e.g., `(a, b)` or `()`).
+    Tuple(TokenStream),
+    /// An array, with its contents (e.g., `[a, b]` or `[a; 4]`).
+    Array(TokenStream),
+    /// A (possibly `unsafe`, `async` or `const`) block.
+    Block,
+    /// An `if`-expression.
+    If,
+    /// A `match`-expression, with its scrutinee.
+    Match(TokenStream),
+    /// A `loop`, `while` or `for`-expression.
+    Loop,
+    /// A closure, with its parameters and body (e.g., `|a| a + 1`).
+    Closure(TokenStream, TokenStream),
+    /// A `return`, `break`, `continue` or `yield`.
+    Jump,
+}
+impl Expr {
+    /// Parses an Expr from its tokens.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the expression.
+    /// - `span`: A span to report errors at.
+    ///
+    /// # Returns
+    /// The parsed Expr.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not recognized as an expression.
+    fn parse(tokens: TokenStream, span: Span) -> Result<Self, Diagnostic2> {
+        let tokens: Vec<TokenTree> = unwrap_none(tokens).into_iter().collect();
+        if tokens.is_empty() {
+            return Err(error2(span, "Expected an expression"));
+        }
+
+        // Binary operators bind the loosest
+        if let Some((start, end, prec)) = find_operator(&tokens) {
+            let (lhs, op, rhs): (TokenStream, TokenStream, TokenStream) = (collect(&tokens[..start]), collect(&tokens[start..end]), collect(&tokens[end..]));
+            return Ok(match prec {
+                PREC_ASSIGN => Self::Assign(lhs, op, rhs),
+                PREC_RANGE => Self::Range(lhs, rhs),
+                PREC_CAST => Self::Cast(lhs, rhs),
+                _ => Self::Binary(lhs, op, rhs),
+            });
+        }
+
+        // Then prefix operators & expressions that run until the end
+        let first: String = tokens[0].to_string();
+        match (&tokens[0], first.as_str()) {
+            (TokenTree::Punct(p), "-" | "!" | "*") => return Ok(Self::Unary(TokenStream::from(TokenTree::Punct(p.clone())), collect(&tokens[1..]))),
+            (TokenTree::Punct(p), "&") => {
+                // `&&a` is given as two joint puncts, so parse it as a reference to `&a`
+                if p.spacing() == Spacing::Joint && is_punct(tokens.get(1), '&') {
+                    return Ok(Self::Ref { mutable: false, expr: collect(&tokens[1..]) });
+                }
+                let mutable: bool = is_ident(tokens.get(1), "mut");
+                return Ok(Self::Ref { mutable, expr: collect(&tokens[if mutable { 2 } else { 1 }..]) });
+            },
+            (TokenTree::Punct(p), "|") => {
+                // Find the closing pipe, if this isn't `||`
+                let end: usize = if p.spacing() == Spacing::Joint && is_punct(tokens.get(1), '|') {
+                    1
+                } else {
+                    tokens.iter().skip(1).position(|tt| is_punct(Some(tt), '|')).map(|pos| pos + 1).unwrap_or(tokens.len() - 1)
+                };
+                let params: TokenStream = join_commas(split_commas(collect(&tokens[1..end])).into_iter().map(|p| p.into_iter().collect()).collect());
+                return Ok(Self::Closure(params, collect(tokens.get(end + 1..).unwrap_or_default())));
+            },
+            (TokenTree::Ident(_), "move" | "async")
+                if tokens.iter().any(|tt| is_punct(Some(tt), '|')) && !matches!(tokens.get(1), Some(TokenTree::Group(_))) =>
+            {
+                let start: usize = tokens.iter().position(|tt| is_punct(Some(tt), '|')).unwrap_or_default();
+                return Self::parse(collect(&tokens[start..]), span);
+            },
+            (TokenTree::Ident(_), "return" | "break" | "continue" | "yield") => return Ok(Self::Jump),
+            _ => {},
+        }
+
+        // Otherwise, it's a single operand with optional postfix operators
+        let (end, last): (usize, Option<Postfix>) = skip_operand(&tokens, 0);
+        if end < tokens.len() {
+            return Err(error2(tokens[end].span(), "Unexpected token in expression"));
+        }
+        if last.is_none() {
+            return match (&tokens[0], first.as_str()) {
+                (TokenTree::Ident(_), "if") => Ok(Self::If),
+                (TokenTree::Ident(_), "match") => Ok(Self::Match(collect(&tokens[1..tokens.len() - 1]))),
+                (TokenTree::Ident(_), "loop" | "while" | "for") | (TokenTree::Punct(_), "'") => Ok(Self::Loop),
+                (TokenTree::Ident(_), "unsafe" | "async" | "const") => Ok(Self::Block),
+                (TokenTree::Literal(_), _) | (TokenTree::Ident(_), "true" | "false") => Ok(Self::Lit),
+                (TokenTree::Group(g), _) => match g.delimiter() {
+                    Delimiter::Brace => Ok(Self::Block),
+                    Delimiter::Bracket => Ok(Self::Array(g.stream())),
+                    Delimiter::Parenthesis | Delimiter::None => {
+                        let trailing: bool = is_punct(g.stream().into_iter().last().as_ref(), ',');
+                        let mut elems: Vec<TokenStream> = split_commas(g.stream());
+                        if elems.len() == 1 && !trailing {
+                            return Self::parse(elems.swap_remove(0), g.span());
+                        }
+                        Ok(Self::Tuple(join_commas(elems.into_iter().map(|e| e.into_iter().collect()).collect())))
+                    },
+                },
+                _ => Ok(Self::Path),
+            };
+        }
+
+        // Take the last postfix operator apart
+        let args = |i: usize| -> TokenStream {
+            let TokenTree::Group(g) = &tokens[i] else { unreachable!() };
+            join_commas(split_commas(g.stream()).into_iter().map(|a| a.into_iter().collect()).collect())
+        };
+        Ok(match last {
+            Some(Postfix::Call(i)) => Self::Call(collect(&tokens[..i]), args(i)),
+            Some(Postfix::Method(dot, name, i)) => Self::Method(collect(&tokens[..dot]), collect(&tokens[name..i]), args(i)),
+            Some(Postfix::Field(dot)) => {
+                // `a.0.1` is given as `a`, `.` and the float `0.1`, so take that apart as `(a.0).1`
+                if let TokenTree::Literal(lit) = &tokens[dot + 1]
+                    && let Some((outer, inner)) = lit.to_string().split_once('.')
+                    && let (Ok(outer), Ok(inner)) = (outer.parse::<usize>(), inner.parse::<usize>())
+                {
+                    let mut outer: Literal = Literal::usize_unsuffixed(outer);
+                    outer.set_span(lit.span());
+                    let mut inner: Literal = Literal::usize_unsuffixed(inner);
+                    inner.set_span(lit.span());
+                    let mut base: TokenStream = collect(&tokens[..dot]);
+                    base.extend([tokens[dot].clone(), TokenTree::Literal(outer)]);
+                    return Ok(Self::Field(base, TokenStream::from(TokenTree::Literal(inner))));
+                }
+                Self::Field(collect(&tokens[..dot]), collect(&tokens[dot + 1..]))
+            },
+            Some(Postfix::Index(i)) => {
+                let TokenTree::Group(g) = &tokens[i] else { unreachable!() };
+                Self::Index(collect(&tokens[..i]), g.stream())
+            },
+            Some(Postfix::Try(i)) => Self::Try(collect(&tokens[..i])),
+            Some(Postfix::Await(dot)) => Self::Await(collect(&tokens[..dot])),
+            Some(Postfix::Macro(i)) => Self::Macro(collect(&tokens[..i])),
+            Some(Postfix::Struct(i)) => Self::Struct(collect(&tokens[..i])),
+            None => unreachable!(),
+        })
+    }
+}
+
+
+
+/// Describes all the possible matchers to specify.
+enum ExprMatcher {
+    /// Any expression.
+    Any,
+    /// Literals.
+    Lit,
+    /// Paths.
+    Path,
+    /// Function calls.
+    Call,
+    /// Method calls.
+    Method,
+    /// Field accesses.
+    Field,
+    /// Indices.
+    Index,
+    /// `?`-expressions.
+    Try,
+    /// `.await`-expressions.
+    Await,
+    /// Macro invocations.
+    Macro,
+    /// Struct expressions.
+    Struct,
+    /// Any tuple, including the unit tuple.
+    Tuple,
+    /// The unit tuple only.
+    Unit,
+    /// Arrays.
+    Array,
+    /// Blocks.
+    Block,
+    /// `if`-expressions.
+    If,
+    /// `match`-expressions.
+    Match,
+    /// Loops.
+    Loop,
+    /// Closures.
+    Closure,
+    /// `return`, `break`, `continue` or `yield`.
+    Jump,
+    /// Any reference.
+    Ref,
+    /// Shared references only.
+    RefShared,
+    /// Mutable references only.
+    RefMut,
+    /// Unary operations.
+    Unary,
+    /// Binary operations.
+    Binary,
+    /// Assignments.
+    Assign,
+    /// Ranges.
+    Range,
+    /// Casts.
+    Cast,
+}
+impl ExprMatcher {
+    /// Parses this ExprMatcher from a branch's matcher.
+    ///
+    /// # Arguments
+    /// - `branch`: The [`Branch`] to parse the matcher of.
+    ///
+    /// # Returns
+    /// An ExprMatcher specified by the branch.
+    ///
+    /// # Errors
+    /// This function may error if the matcher is unknown, or if the branch binds more parts than
+    /// the matcher has.
+    fn parse(branch: &Branch) -> Result<Self, Diagnostic2> {
+        let (matcher, parts): (Self, &[&str]) = match branch.matcher.to_string().as_str() {
+            "_" => (Self::Any, &[]),
+            "lit" => (Self::Lit, &[]),
+            "path" => (Self::Path, &[]),
+            "call" => (Self::Call, &["func", "args"]),
+            "method" => (Self::Method, &["receiver", "method", "args"]),
+            "field" => (Self::Field, &["base", "member"]),
+            "index" => (Self::Index, &["base", "index"]),
+            "try" => (Self::Try, &["expr"]),
+            "await" => (Self::Await, &["expr"]),
+            "macro" => (Self::Macro, &["path"]),
+            "struct" => (Self::Struct, &["path"]),
+            "tuple" => (Self::Tuple, &["elems"]),
+            "unit" => (Self::Unit, &[]),
+            "array" => (Self::Array, &["elems"]),
+            "block" => (Self::Block, &[]),
+            "if" => (Self::If, &[]),
+            "match" => (Self::Match, &["scrutinee"]),
+            "loop" => (Self::Loop, &[]),
+            "closure" => (Self::Closure, &["params", "body"]),
+            "jump" => (Self::Jump, &[]),
+            "ref" => (Self::Ref, &["expr"]),
+            "ref_shared" => (Self::RefShared, &["expr"]),
+            "ref_mut" => (Self::RefMut, &["expr"]),
+            "unary" => (Self::Unary, &["op", "expr"]),
+            "binary" => (Self::Binary, &["lhs", "op", "rhs"]),
+            "assign" => (Self::Assign, &["lhs", "op", "rhs"]),
+            "range" => (Self::Range, &["start", "end"]),
+            "cast" => (Self::Cast, &["expr", "ty"]),
+            _ => {
+                return Err(error2(
+                    branch.matcher.span(),
+                    "Expected one of `_`, `lit`, `path`, `call`, `method`, `field`, `index`, `try`, `await`, `macro`, `struct`, `tuple`, `unit`, \
+                     `array`, `block`, `if`, `match`, `loop`, `closure`, `jump`, `ref`, `ref_shared`, `ref_mut`, `unary`, `binary`, `assign`, \
+                     `range` or `cast`",
+                ));
+            },
+        };
+        branch.check_bindings(parts)?;
+        Ok(matcher)
+    }
+
+    /// Checks whether this matcher matches an expression.
+    ///
+    /// # Arguments
+    /// - `expr`: The [`Expr`] to match on.
+    ///
+    /// # Returns
+    /// The parts to bind if this matcher matches `expr`, or [`None`] otherwise.
+    fn match_expr(&self, expr: &Expr) -> Option<Vec<TokenStream>> {
+        match (self, expr) {
+            (Self::Any, _)
+            | (Self::Lit, Expr::Lit)
+            | (Self::Path, Expr::Path)
+            | (Self::Block, Expr::Block)
+            | (Self::If, Expr::If)
+            | (Self::Loop, Expr::Loop)
+            | (Self::Jump, Expr::Jump) => Some(Vec::new()),
+            (Self::Call, Expr::Call(a, b))
+            | (Self::Field, Expr::Field(a, b))
+            | (Self::Index, Expr::Index(a, b))
+            | (Self::Closure, Expr::Closure(a, b))
+            | (Self::Unary, Expr::Unary(a, b))
+            | (Self::Range, Expr::Range(a, b))
+            | (Self::Cast, Expr::Cast(a, b)) => Some(vec![a.clone(), b.clone()]),
+            (Self::Method, Expr::Method(a, b, c)) | (Self::Binary, Expr::Binary(a, b, c)) | (Self::Assign, Expr::Assign(a, b, c)) => {
+                Some(vec![a.clone(), b.clone(), c.clone()])
+            },
+            (Self::Try, Expr::Try(a))
+            | (Self::Await, Expr::Await(a))
+            | (Self::Macro, Expr::Macro(a))
+            | (Self::Struct, Expr::Struct(a))
+            | (Self::Tuple, Expr::Tuple(a))
+            | (Self::Array, Expr::Array(a))
+            | (Self::Match, Expr::Match(a))
+            | (Self::Ref, Expr::Ref { expr: a, .. })
+            | (Self::RefShared, Expr::Ref { mutable: false, expr: a })
+            | (Self::RefMut, Expr::Ref { mutable: true, expr: a }) => Some(vec![a.clone()]),
+            (Self::Unit, Expr::Tuple(elems)) if elems.is_empty() => Some(Vec::new()),
+            _ => None,
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`match_expr()`](super::match_expr())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to match for input.
+///
+/// # Returns
+/// A new [`TokenStream`] with the tokens of the first branch that matches the expression.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if no branch matches.
+pub fn match_expr(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (expr, span, branches): (TokenStream, Span, Vec<Branch>) = parse_input(input, "an expression")?;
+    let expr: Expr = Expr::parse(expr, span)?;
+    let matchers: Vec<ExprMatcher> = branches.iter().map(ExprMatcher::parse).collect::<Result<_, _>>()?;
+
+    // Find the first branch that matches
+    for (branch, matcher) in branches.into_iter().zip(matchers) {
+        if let Some(parts) = matcher.match_expr(&expr) {
+            return Ok(branch.expand(parts));
+        }
+    }
+    Err(error2(span, "Unmatched expression"))
+}
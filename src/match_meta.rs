@@ -0,0 +1,128 @@
+//  MATCH META.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for parsing attribute-style arguments and dispatching them to callbacks.
+//
+
+use std::collections::HashSet;
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::{Form, Meta, split_commas, split_path, unwrap_none};
+use crate::utils::Callback;
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single branch of the macro.
+struct Branch {
+    /// The key this branch matches, or [`None`] if it's the catch-all.
+    key:      Option<String>,
+    /// The form this branch matches.
+    form:     Form,
+    /// The callback to call with matching items.
+    callback: Callback,
+}
+impl Branch {
+    /// Parses a Branch from its tokens.
+    ///
+    /// Branches have the syntax `$key:path $(= _)? $((..))? => $callback:path ! $(($($args:tt)*))?`,
+    /// or `_ => $callback:path ! $(($($args:tt)*))?`.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the branch.
+    ///
+    /// # Returns
+    /// The parsed Branch.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a valid branch.
+    fn parse(tokens: TokenStream) -> Result<Self, Diagnostic2> {
+        let (head, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(tokens)?;
+        let head: Vec<TokenTree> = head.into_iter().collect();
+        let Some(callback) = callback else {
+            return Err(error2(head.last().map(TokenTree::span).unwrap_or_else(Span::call_site), "Expected '=>' and a callback"));
+        };
+
+        // Parse the head
+        if let [TokenTree::Ident(ident)] = head.as_slice()
+            && ident == "_"
+        {
+            return Ok(Self { key: None, form: Form::Flag, callback });
+        }
+        let (key, rest): (String, &[TokenTree]) = split_path(&head)?;
+        let form: Form = match rest {
+            [] => Form::Flag,
+            [TokenTree::Punct(p), TokenTree::Ident(underscore)] if p.as_char() == '=' && underscore == "_" => Form::NameValue,
+            [TokenTree::Group(g)] if g.delimiter() == Delimiter::Parenthesis && g.stream().to_string().replace(' ', "") == ".." => Form::List,
+            [tt, ..] => return Err(error2(tt.span(), "Expected either nothing, `= _` or `(..)`")),
+        };
+        Ok(Self { key: Some(key), form, callback })
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`match_meta()`](super::match_meta())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to parse for input.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the matching callback for every meta item.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if any of the meta items
+/// are unknown or given more than once.
+pub fn match_meta(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Split the meta items from the branches
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let group = match tokens.pop() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(tt) => return Err(error2(tt.span(), "Expected meta items and then match branches wrapped in `{}`")),
+        None => return Err(error2(Span::call_site(), "Expected meta items and then match branches wrapped in `{}`")),
+    };
+    let metas: Vec<Meta> = split_commas(unwrap_none(tokens.into_iter().collect())).into_iter().map(Meta::parse).collect::<Result<_, _>>()?;
+    let branches: Vec<Branch> = split_commas(group.stream()).into_iter().map(Branch::parse).collect::<Result<_, _>>()?;
+
+    // Dispatch every item to the branch matching it
+    let mut res = TokenStream::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for meta in metas {
+        if !seen.insert(meta.key.clone()) {
+            return Err(error2(meta.span, &format!("Duplicate argument `{}`", meta.key)));
+        }
+
+        // Find the branch for it
+        let forms: Vec<Form> = branches.iter().filter(|b| b.key.as_ref() == Some(&meta.key)).map(|b| b.form).collect();
+        match branches.iter().find(|b| b.key.as_ref() == Some(&meta.key) && b.form == meta.form) {
+            Some(branch) => res.extend(branch.callback.clone().call_item(meta.value)),
+            None if !forms.is_empty() => {
+                let forms: Vec<String> = forms.into_iter().map(|form| form.show(&meta.key)).collect();
+                return Err(error2(meta.span, &format!("Expected {}", forms.join(" or "))));
+            },
+            None => match branches.iter().find(|b| b.key.is_none()) {
+                Some(branch) => res.extend(branch.callback.clone().call_item(meta.tokens)),
+                None => {
+                    let mut keys: Vec<String> = Vec::new();
+                    for key in branches.iter().filter_map(|b| b.key.as_ref().map(|key| format!("`{key}`"))) {
+                        if !keys.contains(&key) {
+                            keys.push(key);
+                        }
+                    }
+                    let msg: String = if keys.is_empty() {
+                        format!("Unknown argument `{}`; expected no arguments", meta.key)
+                    } else {
+                        format!("Unknown argument `{}`; expected one of {}", meta.key, keys.join(", "))
+                    };
+                    return Err(error2(meta.span, &msg));
+                },
+            },
+        }
+    }
+    Ok(res)
+}
@@ -0,0 +1,222 @@
+//  MATCH PATH.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for switching on the shape of paths.
+//
+
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::{Branch, is_punct, parse_input, skip_angles, unwrap_none};
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single segment of a path.
+struct Segment {
+    /// The name of the segment.
+    name: Ident,
+    /// The generic arguments of the segment, without angle brackets, if any.
+    args: Option<TokenStream>,
+}
+
+
+
+/// Defines a parsed path.
+struct Path {
+    /// Whether the path starts with `::`.
+    leading:  bool,
+    /// The segments of the path.
+    segments: Vec<Segment>,
+    /// The tokens of the path before the last segment (including the last `::`).
+    prefix:   TokenStream,
+}
+impl Path {
+    /// Parses a Path from its tokens.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the path.
+    /// - `span`: A span to report errors at.
+    ///
+    /// # Returns
+    /// The parsed Path.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a path.
+    fn parse(tokens: TokenStream, span: Span) -> Result<Self, Diagnostic2> {
+        let tokens: Vec<TokenTree> = unwrap_none(tokens).into_iter().collect();
+        let is_sep = |i: usize| -> bool { is_punct(tokens.get(i), ':') && is_punct(tokens.get(i + 1), ':') };
+
+        // Parse the segments
+        let leading: bool = is_sep(0);
+        let mut i: usize = if leading { 2 } else { 0 };
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut last: usize;
+        loop {
+            last = i;
+            let name: Ident = match tokens.get(i) {
+                Some(TokenTree::Ident(ident)) => ident.clone(),
+                Some(tt) => return Err(error2(tt.span(), "Expected a path segment")),
+                None => return Err(error2(tokens.last().map(TokenTree::span).unwrap_or(span), "Expected a path segment")),
+            };
+            i += 1;
+
+            // Parse the generic arguments, which may be given with a turbofish
+            let mut args: Option<TokenStream> = None;
+            let turbofish: bool = is_sep(i) && is_punct(tokens.get(i + 2), '<');
+            if turbofish || is_punct(tokens.get(i), '<') {
+                let start: usize = if turbofish { i + 2 } else { i };
+                let Some(end) = skip_angles(&tokens, start) else {
+                    return Err(error2(tokens[start].span(), "Unclosed generic arguments"));
+                };
+                i = end;
+                args = Some(tokens[start + 1..i - 1].iter().cloned().collect());
+            } else if let Some(TokenTree::Group(g)) = tokens.get(i)
+                && g.delimiter() == Delimiter::Parenthesis
+            {
+                // Parenthesized arguments (e.g., `Fn(u8) -> u8`) are kept as-is
+                let start: usize = i;
+                i += 1;
+                if is_punct(tokens.get(i), '-') && is_punct(tokens.get(i + 1), '>') {
+                    i = tokens.len();
+                }
+                args = Some(tokens[start..i].iter().cloned().collect());
+            }
+            segments.push(Segment { name, args });
+
+            // Continue if there's another segment
+            if !is_sep(i) {
+                break;
+            }
+            i += 2;
+        }
+        if let Some(tt) = tokens.get(i) {
+            return Err(error2(tt.span(), "Unexpected token after path"));
+        }
+        Ok(Self { leading, segments, prefix: tokens[..last].iter().cloned().collect() })
+    }
+
+    /// Returns the parts of this path to bind.
+    ///
+    /// # Returns
+    /// The name of the last segment, its generic arguments and the path before it.
+    fn parts(&self) -> Vec<TokenStream> {
+        let last: &Segment = self.segments.last().unwrap_or_else(|| unreachable!());
+        vec![TokenStream::from(TokenTree::Ident(last.name.clone())), last.args.clone().unwrap_or_default(), self.prefix.clone()]
+    }
+}
+
+
+
+/// Describes all the possible matchers to specify.
+enum PathMatcher {
+    /// Any path.
+    Any,
+    /// Paths with a single segment.
+    Single,
+    /// Paths with multiple segments or a leading `::`.
+    Multi,
+    /// Paths with a leading `::`.
+    Global,
+    /// Paths starting with `crate` or `$crate`.
+    Crate,
+    /// Paths starting with `self`.
+    SelfPath,
+    /// Paths starting with `super`.
+    Super,
+    /// Paths with generic arguments in any segment.
+    Generic,
+    /// Paths without generic arguments.
+    Plain,
+    /// Paths of which the last segment has the given name.
+    Name(String),
+}
+impl PathMatcher {
+    /// Parses this PathMatcher from a branch's matcher.
+    ///
+    /// # Arguments
+    /// - `branch`: The [`Branch`] to parse the matcher of.
+    ///
+    /// # Returns
+    /// A PathMatcher specified by the branch.
+    ///
+    /// # Errors
+    /// This function may error if the matcher is unknown, or if the branch binds more parts than
+    /// the matcher has.
+    fn parse(branch: &Branch) -> Result<Self, Diagnostic2> {
+        let matcher: String = branch.matcher.to_string();
+        let matcher: Self = match (&branch.matcher, matcher.as_str()) {
+            (TokenTree::Literal(_), lit) if lit.len() >= 2 && lit.starts_with('"') && lit.ends_with('"') => Self::Name(lit[1..lit.len() - 1].into()),
+            (TokenTree::Literal(_), _) => return Err(error2(branch.matcher.span(), "Expected a string literal with the name of the last segment")),
+            (_, "_") => Self::Any,
+            (_, "single") => Self::Single,
+            (_, "multi") => Self::Multi,
+            (_, "global") => Self::Global,
+            (_, "crate") => Self::Crate,
+            (_, "self") => Self::SelfPath,
+            (_, "super") => Self::Super,
+            (_, "generic") => Self::Generic,
+            (_, "plain") => Self::Plain,
+            _ => {
+                return Err(error2(
+                    branch.matcher.span(),
+                    "Expected a string literal or one of `_`, `single`, `multi`, `global`, `crate`, `self`, `super`, `generic` or `plain`",
+                ));
+            },
+        };
+        branch.check_bindings(&["last", "args", "prefix"])?;
+        Ok(matcher)
+    }
+
+    /// Checks whether this matcher matches a path.
+    ///
+    /// # Arguments
+    /// - `path`: The [`Path`] to match on.
+    ///
+    /// # Returns
+    /// True if it matches, or false otherwise.
+    fn matches(&self, path: &Path) -> bool {
+        let first: String = path.segments[0].name.to_string();
+        match self {
+            Self::Any => true,
+            Self::Single => !path.leading && path.segments.len() == 1,
+            Self::Multi => path.leading || path.segments.len() > 1,
+            Self::Global => path.leading,
+            Self::Crate => !path.leading && (first == "crate" || first == "$crate"),
+            Self::SelfPath => !path.leading && first == "self",
+            Self::Super => !path.leading && first == "super",
+            Self::Generic => path.segments.iter().any(|segment| segment.args.is_some()),
+            Self::Plain => path.segments.iter().all(|segment| segment.args.is_none()),
+            Self::Name(name) => path.segments.last().is_some_and(|segment| segment.name == name),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`match_path()`](super::match_path())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to match for input.
+///
+/// # Returns
+/// A new [`TokenStream`] with the tokens of the first branch that matches the path.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if no branch matches.
+pub fn match_path(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (path, span, branches): (TokenStream, Span, Vec<Branch>) = parse_input(input, "a path")?;
+    let path: Path = Path::parse(path, span)?;
+    let matchers: Vec<PathMatcher> = branches.iter().map(PathMatcher::parse).collect::<Result<_, _>>()?;
+
+    // Find the first branch that matches
+    for (branch, matcher) in branches.into_iter().zip(matchers) {
+        if matcher.matches(&path) {
+            return Ok(branch.expand(path.parts()));
+        }
+    }
+    Err(error2(span, "Unmatched path"))
+}
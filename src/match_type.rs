@@ -0,0 +1,377 @@
+//  MATCH TYPE.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for switching on the syntactic kind of types.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::generics::split_top_level;
+use crate::matching::{Branch, is_ident, is_punct, join_commas, parse_input, unwrap_none};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates the elided lifetime `'_`.
+///
+/// # Arguments
+/// - `span`: The span to give the lifetime.
+///
+/// # Returns
+/// A [`TokenStream`] with `'_`.
+fn quote_elided(span: Span) -> TokenStream {
+    let mut tick = Punct::new('\'', Spacing::Joint);
+    tick.set_span(span);
+    TokenStream::from_iter([TokenTree::Punct(tick), TokenTree::Ident(Ident::new("_", span))])
+}
+
+/// Parses the name and generic arguments of the last segment of a path.
+///
+/// # Arguments
+/// - `tokens`: The tokens of the path.
+/// - `span`: A span to report errors at.
+///
+/// # Returns
+/// The name of the last segment and its generic arguments (without angle brackets), which are
+/// empty if it has none.
+///
+/// # Errors
+/// This function errors if the path has no segments.
+fn parse_last_segment(tokens: &[TokenTree], span: Span) -> Result<(Ident, TokenStream), Diagnostic2> {
+    // Find the last identifier outside of any angle brackets
+    let mut depth: usize = 0;
+    let mut last: Option<usize> = None;
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' && !(i > 0 && is_punct(tokens.get(i - 1), '-')) => depth = depth.saturating_sub(1),
+            TokenTree::Ident(_) if depth == 0 => last = Some(i),
+            _ => {},
+        }
+    }
+    let Some(last) = last else {
+        return Err(error2(span, "Expected a path with at least one segment"));
+    };
+    let TokenTree::Ident(name) = &tokens[last] else { unreachable!() };
+
+    // Collect the arguments following it, if any
+    let mut rest = tokens[last + 1..].iter().skip_while(|tt| is_punct(Some(tt), ':')).peekable();
+    let mut args = TokenStream::new();
+    if rest.next_if(|tt| is_punct(Some(tt), '<')).is_some() {
+        let mut depth: usize = 1;
+        let mut prev: Option<&TokenTree> = None;
+        for tt in rest {
+            if is_punct(Some(tt), '<') {
+                depth += 1;
+            } else if is_punct(Some(tt), '>') && !is_punct(prev, '-') {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            args.extend([tt.clone()]);
+            prev = Some(tt);
+        }
+    }
+    Ok((name.clone(), args))
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the possible kinds of types.
+enum Type {
+    /// A path, with the name and generic arguments of its last segment (e.g., `Vec<u8>`).
+    Path { name: Ident, args: TokenStream },
+    /// A reference (e.g., `&'a mut T`). The lifetime is `'_` if it was elided.
+    Ref { mutable: bool, lifetime: TokenStream, elem: TokenStream },
+    /// A slice (e.g., `[T]`).
+    Slice(TokenStream),
+    /// An array, with its element type and length (e.g., `[T; 4]`).
+    Array(TokenStream, TokenStream),
+    /// A tuple with its comma-separated element types (e.g., `(A, B)` or `()`).
+    Tuple(TokenStream),
+    /// A raw pointer (e.g., `*const T`).
+    Ptr { mutable: bool, elem: TokenStream },
+    /// A function pointer, with its comma-separated parameters and return type (e.g., `fn(u8) -> u16`).
+    Fn(TokenStream, TokenStream),
+    /// An `impl Trait`-type, with its bounds.
+    Impl(TokenStream),
+    /// A `dyn Trait`-type, with its bounds.
+    Dyn(TokenStream),
+    /// The never type (`!`).
+    Never,
+    /// The inferred type (`_`).
+    Infer,
+    /// A macro invocation in type position (e.g., `ty!()`).
+    Macro,
+}
+impl Type {
+    /// Parses a Type from its tokens.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the type.
+    /// - `span`: A span to report errors at.
+    ///
+    /// # Returns
+    /// The parsed Type.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a type.
+    fn parse(tokens: TokenStream, span: Span) -> Result<Self, Diagnostic2> {
+        let tokens: Vec<TokenTree> = unwrap_none(tokens).into_iter().collect();
+        match tokens.first() {
+            None => Err(error2(span, "Expected a type")),
+
+            // Never & infer
+            Some(TokenTree::Punct(p)) if p.as_char() == '!' && tokens.len() == 1 => Ok(Self::Never),
+            Some(TokenTree::Ident(i)) if i == "_" && tokens.len() == 1 => Ok(Self::Infer),
+
+            // References
+            Some(TokenTree::Punct(p)) if p.as_char() == '&' => {
+                // `&&T` is given as two joint puncts, so parse it as a reference to `&T`
+                if p.spacing() == Spacing::Joint && is_punct(tokens.get(1), '&') {
+                    return Ok(Self::Ref { mutable: false, lifetime: quote_elided(p.span()), elem: tokens[1..].iter().cloned().collect() });
+                }
+                let mut i: usize = 1;
+                let lifetime: TokenStream = if is_punct(tokens.get(1), '\'') && matches!(tokens.get(2), Some(TokenTree::Ident(_))) {
+                    i = 3;
+                    tokens[1..3].iter().cloned().collect()
+                } else {
+                    quote_elided(p.span())
+                };
+                let mutable: bool = is_ident(tokens.get(i), "mut");
+                if mutable {
+                    i += 1;
+                }
+                if i >= tokens.len() {
+                    return Err(error2(p.span(), "Expected a type after '&'"));
+                }
+                Ok(Self::Ref { mutable, lifetime, elem: tokens[i..].iter().cloned().collect() })
+            },
+
+            // Pointers
+            Some(TokenTree::Punct(p)) if p.as_char() == '*' => {
+                let mutable: bool = match tokens.get(1) {
+                    Some(TokenTree::Ident(i)) if i == "const" => false,
+                    Some(TokenTree::Ident(i)) if i == "mut" => true,
+                    Some(tt) => return Err(error2(tt.span(), "Expected either `const` or `mut`")),
+                    None => return Err(error2(p.span(), "Expected either `const` or `mut` after '*'")),
+                };
+                if tokens.len() < 3 {
+                    return Err(error2(tokens[1].span(), "Expected a type"));
+                }
+                Ok(Self::Ptr { mutable, elem: tokens[2..].iter().cloned().collect() })
+            },
+
+            // Slices & arrays
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket && tokens.len() == 1 => {
+                let mut parts = split_top_level(g.stream(), ';').into_iter();
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(elem), None, _) => Ok(Self::Slice(elem.into_iter().collect())),
+                    (Some(elem), Some(len), None) => Ok(Self::Array(elem.into_iter().collect(), len.into_iter().collect())),
+                    _ => Err(error2(g.span(), "Expected either `[T]` or `[T; N]`")),
+                }
+            },
+
+            // Tuples (and parenthesized types)
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis && tokens.len() == 1 => {
+                let trailing: bool = is_punct(g.stream().into_iter().last().as_ref(), ',');
+                let mut elems: Vec<Vec<TokenTree>> = split_top_level(g.stream(), ',');
+                if elems.len() == 1 && !trailing {
+                    return Self::parse(elems.swap_remove(0).into_iter().collect(), g.span());
+                }
+                Ok(Self::Tuple(join_commas(elems)))
+            },
+
+            // Impl- and dyn-types
+            Some(TokenTree::Ident(i)) if i == "impl" || i == "dyn" => {
+                if tokens.len() < 2 {
+                    return Err(error2(i.span(), &format!("Expected bounds after `{i}`")));
+                }
+                let bounds: TokenStream = tokens[1..].iter().cloned().collect();
+                Ok(if i == "impl" { Self::Impl(bounds) } else { Self::Dyn(bounds) })
+            },
+
+            // Function pointers, which may be prefixed by `for<...>`, `unsafe` or `extern "ABI"`
+            Some(TokenTree::Ident(i)) if (i == "fn" || i == "unsafe" || i == "extern" || i == "for") && tokens.iter().any(|tt| is_ident(Some(tt), "fn")) => {
+                let pos: usize = tokens.iter().position(|tt| is_ident(Some(tt), "fn")).unwrap_or_default();
+                let params: TokenStream = match tokens.get(pos + 1) {
+                    Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => join_commas(split_top_level(g.stream(), ',')),
+                    Some(tt) => return Err(error2(tt.span(), "Expected parameters in parenthesis")),
+                    None => return Err(error2(tokens[pos].span(), "Expected parameters in parenthesis after `fn`")),
+                };
+                let ret: TokenStream = match tokens.get(pos + 2..pos + 4) {
+                    Some([TokenTree::Punct(dash), TokenTree::Punct(arrow)]) if dash.as_char() == '-' && arrow.as_char() == '>' => {
+                        tokens[pos + 4..].iter().cloned().collect()
+                    },
+                    _ if tokens.len() == pos + 2 => TokenStream::from(TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::new()))),
+                    _ => return Err(error2(tokens[pos + 2].span(), "Expected either nothing or '->' and a return type")),
+                };
+                Ok(Self::Fn(params, ret))
+            },
+
+            // Macros & paths
+            Some(TokenTree::Ident(_) | TokenTree::Punct(_)) => {
+                if let [.., TokenTree::Punct(p), TokenTree::Group(_)] = tokens.as_slice()
+                    && p.as_char() == '!'
+                {
+                    return Ok(Self::Macro);
+                }
+                if let Some(TokenTree::Punct(p)) = tokens.first()
+                    && p.as_char() != ':'
+                    && p.as_char() != '<'
+                {
+                    return Err(error2(p.span(), "Expected a type"));
+                }
+                let (name, args): (Ident, TokenStream) = parse_last_segment(&tokens, span)?;
+                Ok(Self::Path { name, args })
+            },
+
+            Some(tt) => Err(error2(tt.span(), "Expected a type")),
+        }
+    }
+}
+
+/// Describes all the possible matchers to specify.
+enum TypeMatcher {
+    /// Any type.
+    Any,
+    /// Any path.
+    Path,
+    /// Any reference.
+    Ref,
+    /// Shared references only.
+    RefShared,
+    /// Mutable references only.
+    RefMut,
+    /// Slices.
+    Slice,
+    /// Arrays.
+    Array,
+    /// Any tuple, including the unit type.
+    Tuple,
+    /// The unit type only.
+    Unit,
+    /// Any raw pointer.
+    Ptr,
+    /// `*const` pointers only.
+    PtrConst,
+    /// `*mut` pointers only.
+    PtrMut,
+    /// Function pointers.
+    Fn,
+    /// `impl Trait`-types.
+    Impl,
+    /// `dyn Trait`-types.
+    Dyn,
+    /// The never type.
+    Never,
+    /// The inferred type.
+    Infer,
+    /// Macros in type position.
+    Macro,
+}
+impl TypeMatcher {
+    /// Parses this TypeMatcher from a branch's matcher.
+    ///
+    /// # Arguments
+    /// - `branch`: The [`Branch`] to parse the matcher of.
+    ///
+    /// # Returns
+    /// A TypeMatcher specified by the branch.
+    ///
+    /// # Errors
+    /// This function may error if the matcher is unknown, or if the branch binds more parts than
+    /// the matcher has.
+    fn parse(branch: &Branch) -> Result<Self, Diagnostic2> {
+        let (matcher, parts): (Self, &[&str]) = match branch.matcher.to_string().as_str() {
+            "_" => (Self::Any, &[]),
+            "path" => (Self::Path, &["name", "args"]),
+            "ref" => (Self::Ref, &["elem", "lifetime"]),
+            "ref_shared" => (Self::RefShared, &["elem", "lifetime"]),
+            "ref_mut" => (Self::RefMut, &["elem", "lifetime"]),
+            "slice" => (Self::Slice, &["elem"]),
+            "array" => (Self::Array, &["elem", "len"]),
+            "tuple" => (Self::Tuple, &["elems"]),
+            "unit" => (Self::Unit, &[]),
+            "ptr" => (Self::Ptr, &["elem"]),
+            "ptr_const" => (Self::PtrConst, &["elem"]),
+            "ptr_mut" => (Self::PtrMut, &["elem"]),
+            "fn" => (Self::Fn, &["params", "ret"]),
+            "impl" => (Self::Impl, &["bounds"]),
+            "dyn" => (Self::Dyn, &["bounds"]),
+            "never" => (Self::Never, &[]),
+            "infer" => (Self::Infer, &[]),
+            "macro" => (Self::Macro, &[]),
+            _ => {
+                return Err(error2(
+                    branch.matcher.span(),
+                    "Expected one of `_`, `path`, `ref`, `ref_shared`, `ref_mut`, `slice`, `array`, `tuple`, `unit`, `ptr`, `ptr_const`, `ptr_mut`, \
+                     `fn`, `impl`, `dyn`, `never`, `infer` or `macro`",
+                ));
+            },
+        };
+        branch.check_bindings(parts)?;
+        Ok(matcher)
+    }
+
+    /// Checks whether this matcher matches a type.
+    ///
+    /// # Arguments
+    /// - `ty`: The [`Type`] to match on.
+    ///
+    /// # Returns
+    /// The parts to bind if this matcher matches `ty`, or [`None`] otherwise.
+    fn match_type(&self, ty: &Type) -> Option<Vec<TokenStream>> {
+        match (self, ty) {
+            (Self::Any, _) | (Self::Never, Type::Never) | (Self::Infer, Type::Infer) | (Self::Macro, Type::Macro) => Some(Vec::new()),
+            (Self::Path, Type::Path { name, args }) => Some(vec![TokenStream::from(TokenTree::Ident(name.clone())), args.clone()]),
+            (Self::Ref, Type::Ref { lifetime, elem, .. })
+            | (Self::RefShared, Type::Ref { mutable: false, lifetime, elem })
+            | (Self::RefMut, Type::Ref { mutable: true, lifetime, elem }) => Some(vec![elem.clone(), lifetime.clone()]),
+            (Self::Slice, Type::Slice(elem)) => Some(vec![elem.clone()]),
+            (Self::Array, Type::Array(elem, len)) => Some(vec![elem.clone(), len.clone()]),
+            (Self::Tuple, Type::Tuple(elems)) => Some(vec![elems.clone()]),
+            (Self::Unit, Type::Tuple(elems)) if elems.is_empty() => Some(Vec::new()),
+            (Self::Ptr, Type::Ptr { elem, .. }) | (Self::PtrConst, Type::Ptr { mutable: false, elem }) | (Self::PtrMut, Type::Ptr { mutable: true, elem }) => {
+                Some(vec![elem.clone()])
+            },
+            (Self::Fn, Type::Fn(params, ret)) => Some(vec![params.clone(), ret.clone()]),
+            (Self::Impl, Type::Impl(bounds)) | (Self::Dyn, Type::Dyn(bounds)) => Some(vec![bounds.clone()]),
+            _ => None,
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`match_type()`](super::match_type())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to match for input.
+///
+/// # Returns
+/// A new [`TokenStream`] with the tokens of the first branch that matches the type.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if no branch matches.
+pub fn match_type(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (ty, span, branches): (TokenStream, Span, Vec<Branch>) = parse_input(input, "a type")?;
+    let ty: Type = Type::parse(ty, span)?;
+    let matchers: Vec<TypeMatcher> = branches.iter().map(TypeMatcher::parse).collect::<Result<_, _>>()?;
+
+    // Find the first branch that matches
+    for (branch, matcher) in branches.into_iter().zip(matchers) {
+        if let Some(parts) = matcher.match_type(&ty) {
+            return Ok(branch.expand(parts));
+        }
+    }
+    Err(error2(span, "Unmatched type"))
+}
@@ -0,0 +1,171 @@
+//  MATCH VIS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for switching on visibilities.
+//
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::{Branch, parse_input, unwrap_none};
+
+
+/***** TOKEN PARSING *****/
+/// Defines the possible visibilities.
+enum Vis {
+    /// No visibility, i.e., private.
+    Private,
+    /// `pub`.
+    Pub,
+    /// `pub(crate)`, `pub(super)`, `pub(self)` or `pub(in path)`, with the path (without `in`).
+    Restricted(TokenStream),
+}
+impl Vis {
+    /// Parses a Vis from its tokens.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the visibility.
+    /// - `span`: A span to report errors at.
+    ///
+    /// # Returns
+    /// The parsed Vis.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a visibility.
+    fn parse(tokens: TokenStream, span: Span) -> Result<Self, Diagnostic2> {
+        let mut iter = tokens.into_iter();
+        match iter.next() {
+            None => return Ok(Self::Private),
+            Some(TokenTree::Ident(ident)) if ident == "pub" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected a visibility")),
+        }
+        let group = match iter.next() {
+            None => return Ok(Self::Pub),
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+            Some(tt) => return Err(error2(tt.span(), "Expected either nothing or a restriction in parenthesis after `pub`")),
+        };
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the visibility"));
+        }
+
+        // Parse the restriction
+        let mut restriction = unwrap_none(group.stream()).into_iter();
+        match restriction.next() {
+            Some(TokenTree::Ident(ident)) if ident == "in" => {
+                let path: TokenStream = restriction.collect();
+                if path.is_empty() {
+                    return Err(error2(ident.span(), "Expected a path after `in`"));
+                }
+                Ok(Self::Restricted(path))
+            },
+            Some(TokenTree::Ident(ident)) if ident == "crate" || ident == "super" || ident == "self" => match restriction.next() {
+                None => Ok(Self::Restricted(TokenStream::from(TokenTree::Ident(ident)))),
+                Some(tt) => Err(error2(tt.span(), "Expected nothing after the restriction (use `pub(in path)` for paths)")),
+            },
+            Some(tt) => Err(error2(tt.span(), "Expected either `crate`, `super`, `self` or `in` and a path")),
+            None => Err(error2(span, "Expected either `crate`, `super`, `self` or `in` and a path")),
+        }
+    }
+}
+
+
+
+/// Describes all the possible matchers to specify.
+enum VisMatcher {
+    /// Any visibility.
+    Any,
+    /// No visibility.
+    Private,
+    /// Exactly `pub`.
+    Pub,
+    /// Any `pub(...)`.
+    Restricted,
+    /// `pub(crate)`.
+    Crate,
+    /// `pub(super)`.
+    Super,
+    /// `pub(self)`.
+    SelfVis,
+    /// `pub(in path)`.
+    In,
+}
+impl VisMatcher {
+    /// Parses this VisMatcher from a branch's matcher.
+    ///
+    /// # Arguments
+    /// - `branch`: The [`Branch`] to parse the matcher of.
+    ///
+    /// # Returns
+    /// A VisMatcher specified by the branch.
+    ///
+    /// # Errors
+    /// This function may error if the matcher is unknown, or if the branch binds more parts than
+    /// the matcher has.
+    fn parse(branch: &Branch) -> Result<Self, Diagnostic2> {
+        let (matcher, parts): (Self, &[&str]) = match branch.matcher.to_string().as_str() {
+            "_" => (Self::Any, &[]),
+            "private" => (Self::Private, &[]),
+            "pub" => (Self::Pub, &[]),
+            "restricted" => (Self::Restricted, &["path"]),
+            "crate" => (Self::Crate, &[]),
+            "super" => (Self::Super, &[]),
+            "self" => (Self::SelfVis, &[]),
+            "in" => (Self::In, &["path"]),
+            _ => {
+                return Err(error2(branch.matcher.span(), "Expected one of `_`, `private`, `pub`, `restricted`, `crate`, `super`, `self` or `in`"));
+            },
+        };
+        branch.check_bindings(parts)?;
+        Ok(matcher)
+    }
+
+    /// Checks whether this matcher matches a visibility.
+    ///
+    /// # Arguments
+    /// - `vis`: The [`Vis`] to match on.
+    ///
+    /// # Returns
+    /// The parts to bind if this matcher matches `vis`, or [`None`] otherwise.
+    fn match_vis(&self, vis: &Vis) -> Option<Vec<TokenStream>> {
+        let path: Option<String> = if let Vis::Restricted(path) = vis { Some(path.to_string()) } else { None };
+        match (self, vis) {
+            (Self::Any, _) | (Self::Private, Vis::Private) | (Self::Pub, Vis::Pub) => Some(Vec::new()),
+            (Self::Restricted, Vis::Restricted(path)) => Some(vec![path.clone()]),
+            (Self::Crate, _) if path.as_deref() == Some("crate") => Some(Vec::new()),
+            (Self::Super, _) if path.as_deref() == Some("super") => Some(Vec::new()),
+            (Self::SelfVis, _) if path.as_deref() == Some("self") => Some(Vec::new()),
+            (Self::In, Vis::Restricted(path)) if !matches!(path.to_string().as_str(), "crate" | "super" | "self") => Some(vec![path.clone()]),
+            _ => None,
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`match_vis()`](super::match_vis())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to match for input.
+///
+/// # Returns
+/// A new [`TokenStream`] with the tokens of the first branch that matches the visibility.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if no branch matches.
+pub fn match_vis(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (vis, span, branches): (TokenStream, Span, Vec<Branch>) = parse_input(input, "a visibility")?;
+    let vis: Vis = Vis::parse(vis, span)?;
+    let matchers: Vec<VisMatcher> = branches.iter().map(VisMatcher::parse).collect::<Result<_, _>>()?;
+
+    // Find the first branch that matches
+    for (branch, matcher) in branches.into_iter().zip(matchers) {
+        if let Some(parts) = matcher.match_vis(&vis) {
+            return Ok(branch.expand(parts));
+        }
+    }
+    Err(error2(span, "Unmatched visibility"))
+}
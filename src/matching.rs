@@ -0,0 +1,396 @@
+//  MATCHING.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines the syntax shared by the macros that match on the shape of some tokens (e.g.,
+//!   [`match_vis!()`](crate::match_vis!()) or [`match_type!()`](crate::match_type!())), or that
+//!   parse attribute-style arguments (e.g., [`match_meta!()`](crate::match_meta!())).
+//
+
+use std::collections::HashMap;
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+#[cfg(feature = "duplicate")]
+use crate::duplicate::substitute;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Unwraps tokens given as a single invisible group (e.g., `$ty:ty`).
+///
+/// # Arguments
+/// - `tokens`: The tokens to unwrap.
+///
+/// # Returns
+/// The tokens in the invisible group(s), or the given tokens if they were not a single invisible
+/// group.
+pub fn unwrap_none(tokens: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    while let [TokenTree::Group(group)] = tokens.as_slice()
+        && group.delimiter() == Delimiter::None
+    {
+        tokens = group.stream().into_iter().collect();
+    }
+    tokens.into_iter().collect()
+}
+
+/// Checks whether the given token is a [`Punct`] with the given character.
+///
+/// # Arguments
+/// - `tt`: The token to check.
+/// - `c`: The character to check for.
+///
+/// # Returns
+/// True if `tt` is `c`, or false otherwise.
+#[inline]
+pub fn is_punct(tt: Option<&TokenTree>, c: char) -> bool { matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == c) }
+
+/// Checks whether the given token is an [`Ident`] with the given name.
+///
+/// # Arguments
+/// - `tt`: The token to check.
+/// - `name`: The name to check for.
+///
+/// # Returns
+/// True if `tt` is `name`, or false otherwise.
+#[inline]
+pub fn is_ident(tt: Option<&TokenTree>, name: &str) -> bool { matches!(tt, Some(TokenTree::Ident(i)) if i == name) }
+
+/// Joins the given chunks of tokens with commas.
+///
+/// # Arguments
+/// - `chunks`: The chunks to join.
+///
+/// # Returns
+/// The chunks, separated by commas (without a trailing one).
+pub fn join_commas(chunks: Vec<Vec<TokenTree>>) -> TokenStream {
+    let mut res = TokenStream::new();
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        if i > 0 {
+            res.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        res.extend(chunk);
+    }
+    res
+}
+
+/// Flattens any invisible groups (e.g., from `$ty:ty`) in the given tokens.
+///
+/// # Arguments
+/// - `tokens`: The tokens to flatten.
+/// - `out`: The list to push the flattened tokens to.
+pub fn flatten_none(tokens: TokenStream, out: &mut Vec<TokenTree>) {
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => flatten_none(group.stream(), out),
+            tt => out.push(tt),
+        }
+    }
+}
+
+/// Finds the position right after a pair of angle brackets.
+///
+/// # Arguments
+/// - `tokens`: The tokens to search in.
+/// - `i`: The position of the opening `<`.
+///
+/// # Returns
+/// The position after the matching `>`, or [`None`] if it is never closed.
+pub fn skip_angles(tokens: &[TokenTree], mut i: usize) -> Option<usize> {
+    let mut depth: usize = 0;
+    while let Some(tt) = tokens.get(i) {
+        if is_punct(Some(tt), '<') {
+            depth += 1;
+        } else if is_punct(Some(tt), '>') && !(i > 0 && is_punct(tokens.get(i - 1), '-')) {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i + 1);
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Splits the given tokens on the given punctuation outside of any group.
+///
+/// # Arguments
+/// - `tokens`: The tokens to split.
+/// - `c`: The character of the punctuation to split on (e.g., `,`).
+///
+/// # Returns
+/// The tokens in between the punctuation. A trailing one does not produce an empty list at the
+/// end.
+pub fn split_punct(tokens: TokenStream, c: char) -> Vec<Vec<TokenTree>> {
+    let mut res: Vec<Vec<TokenTree>> = vec![Vec::new()];
+    for tt in tokens {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == c => res.push(Vec::new()),
+            tt => res.last_mut().unwrap_or_else(|| unreachable!()).push(tt),
+        }
+    }
+    if res.last().is_some_and(Vec::is_empty) {
+        res.pop();
+    }
+    res
+}
+
+/// Splits the given tokens on commas outside of any group.
+///
+/// # Arguments
+/// - `tokens`: The tokens to split.
+///
+/// # Returns
+/// The tokens in between the commas. A trailing comma does not produce an empty list at the end.
+pub fn split_commas(tokens: TokenStream) -> Vec<TokenStream> { split_punct(tokens, ',').into_iter().map(TokenStream::from_iter).collect() }
+
+/// Splits a path off the start of the given tokens.
+///
+/// # Arguments
+/// - `tokens`: The tokens to split.
+///
+/// # Returns
+/// The path (as a string to compare with) and the remaining tokens.
+///
+/// # Errors
+/// This function errors if the tokens do not start with a path.
+pub fn split_path(tokens: &[TokenTree]) -> Result<(String, &[TokenTree]), Diagnostic2> {
+    let mut path = String::new();
+    let mut i: usize = 0;
+    loop {
+        if is_punct(tokens.get(i), ':') && is_punct(tokens.get(i + 1), ':') {
+            path.push_str("::");
+            i += 2;
+        }
+        match tokens.get(i) {
+            Some(TokenTree::Ident(ident)) => path.push_str(&ident.to_string()),
+            Some(tt) => return Err(error2(tt.span(), "Expected an identifier or a path")),
+            None => return Err(error2(tokens.last().map(TokenTree::span).unwrap_or_else(Span::call_site), "Expected an identifier or a path")),
+        }
+        i += 1;
+        if !(is_punct(tokens.get(i), ':') && is_punct(tokens.get(i + 1), ':')) {
+            return Ok((path, &tokens[i..]));
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the forms a meta item can take.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Form {
+    /// A bare flag (e.g., `skip`).
+    Flag,
+    /// A name-value pair (e.g., `rename = "foo"`).
+    NameValue,
+    /// A list (e.g., `bounds(Clone, Debug)`).
+    List,
+}
+impl Form {
+    /// Returns how this form looks for a given key, for use in error messages.
+    ///
+    /// # Arguments
+    /// - `key`: The key to show.
+    ///
+    /// # Returns
+    /// A string showing the form.
+    pub fn show(self, key: &str) -> String {
+        match self {
+            Self::Flag => format!("`{key}`"),
+            Self::NameValue => format!("`{key} = ...`"),
+            Self::List => format!("`{key}(...)`"),
+        }
+    }
+}
+
+
+
+/// Defines a single attribute-style argument (e.g., `skip` or `rename = "foo"`).
+pub struct Meta {
+    /// The tokens of the whole item.
+    pub tokens: TokenStream,
+    /// The span of the key.
+    pub span:   Span,
+    /// The key of the item.
+    pub key:    String,
+    /// The form of the item.
+    pub form:   Form,
+    /// The value of the item (if a name-value pair) or its contents (if a list).
+    pub value:  TokenStream,
+}
+impl Meta {
+    /// Parses a Meta from its tokens.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the item.
+    ///
+    /// # Returns
+    /// The parsed Meta.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a flag, name-value pair or list.
+    pub fn parse(tokens: TokenStream) -> Result<Self, Diagnostic2> {
+        let tokens: TokenStream = unwrap_none(tokens);
+        let trees: Vec<TokenTree> = tokens.clone().into_iter().collect();
+        let Some(span) = trees.first().map(TokenTree::span) else {
+            return Err(error2(Span::call_site(), "Expected a meta item, not an empty one"));
+        };
+        let (key, rest): (String, &[TokenTree]) = split_path(&trees)?;
+        let (form, value): (Form, TokenStream) = match rest {
+            [] => (Form::Flag, TokenStream::new()),
+            [TokenTree::Punct(p), value @ ..] if p.as_char() == '=' => {
+                if value.is_empty() {
+                    return Err(error2(p.span(), "Expected a value after '='"));
+                }
+                (Form::NameValue, value.iter().cloned().collect())
+            },
+            [TokenTree::Group(g)] if g.delimiter() == Delimiter::Parenthesis => (Form::List, g.stream()),
+            [tt, ..] => return Err(error2(tt.span(), "Expected either nothing, '=' and a value, or a list in parenthesis")),
+        };
+        Ok(Self { tokens, span, key, form, value })
+    }
+}
+
+
+
+/// Defines a single branch of a matching macro.
+pub struct Branch {
+    /// The matcher of the branch, which is usually an identifier (e.g., `pub` or `ref`).
+    pub matcher:  TokenTree,
+    /// The identifiers that are bound to the parts of the matched tokens.
+    pub bindings: Vec<Ident>,
+    /// The tokens to generate when this branch is matched.
+    pub tokens:   TokenStream,
+}
+impl Branch {
+    /// Parses a Branch from an iterator over [`TokenTree`]s.
+    ///
+    /// Branches have the syntax `$matcher $(($($binding:ident),*))? => $($tokens:tt)*`, where the
+    /// tokens run up to the next comma (or the end of the input).
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Branch, or [`None`] if there was nothing left to parse.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input was not a valid branch.
+    fn parse(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Option<Self>, Diagnostic2> {
+        // Parse the matcher
+        let matcher: TokenTree = match iter.next() {
+            Some(tt @ (TokenTree::Ident(_) | TokenTree::Literal(_))) => tt,
+            Some(tt) => return Err(error2(tt.span(), "Expected a matcher")),
+            None => return Ok(None),
+        };
+
+        // Parse the bindings, if any
+        let mut bindings: Vec<Ident> = Vec::new();
+        if let Some(TokenTree::Group(group)) = iter.next_if(|tt| matches!(tt, TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis)) {
+            for binding in split_commas(group.stream()) {
+                let mut binding = binding.into_iter();
+                match (binding.next(), binding.next()) {
+                    (Some(TokenTree::Ident(ident)), None) => bindings.push(ident),
+                    (Some(tt), _) => return Err(error2(tt.span(), "Expected an identifier to bind to")),
+                    (None, _) => return Err(error2(group.span(), "Expected an identifier to bind to")),
+                }
+            }
+        }
+
+        // Parse the `=>`
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' && p.spacing() == Spacing::Joint => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '=>'")),
+            None => return Err(error2(matcher.span(), "Expected '=>'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '>' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '=>'")),
+            None => return Err(error2(matcher.span(), "Expected '=>'")),
+        }
+
+        // Parse the tokens until the next comma
+        let mut tokens = TokenStream::new();
+        for tt in iter.by_ref() {
+            if matches!(&tt, TokenTree::Punct(p) if p.as_char() == ',') {
+                break;
+            }
+            tokens.extend([tt]);
+        }
+        Ok(Some(Self { matcher, bindings, tokens }))
+    }
+
+    /// Checks that this branch does not bind more parts than the matcher has.
+    ///
+    /// # Arguments
+    /// - `parts`: The names of the parts the matcher has, in the order they are bound.
+    ///
+    /// # Errors
+    /// This function errors if there are more bindings than `parts`.
+    pub fn check_bindings(&self, parts: &[&str]) -> Result<(), Diagnostic2> {
+        if let Some(binding) = self.bindings.get(parts.len()) {
+            let msg: String = if parts.is_empty() {
+                format!("`{}` does not have any parts to bind", self.matcher)
+            } else {
+                format!("`{}` only has {} part(s) to bind ({})", self.matcher, parts.len(), parts.join(", "))
+            };
+            return Err(error2(binding.span(), &msg));
+        }
+        Ok(())
+    }
+
+    /// Generates the tokens of this branch, with its bindings replaced by the given parts.
+    ///
+    /// Bindings that are `_` are ignored.
+    ///
+    /// # Arguments
+    /// - `parts`: The parts of the matched tokens, in the order they are bound. If there are more
+    ///   than there are bindings, the remaining ones are ignored.
+    ///
+    /// # Returns
+    /// The tokens of this branch.
+    #[cfg(feature = "duplicate")]
+    pub fn expand(self, parts: Vec<TokenStream>) -> TokenStream {
+        let values: HashMap<String, &TokenStream> =
+            self.bindings.iter().zip(parts.iter()).filter(|(binding, _)| *binding != "_").map(|(binding, part)| (binding.to_string(), part)).collect();
+        substitute(self.tokens, &values)
+    }
+}
+
+/// Parses the input to a matching macro, which is the tokens to match followed by the branches
+/// in curly brackets.
+///
+/// # Arguments
+/// - `input`: The input to the macro.
+/// - `what`: A description of what is matched, for use in error messages (e.g., `"a type"`).
+///
+/// # Returns
+/// The tokens to match (with any invisible groups around them removed), a [`Span`] pointing to
+/// them and the parsed [`Branch`]es.
+///
+/// # Errors
+/// This function errors if the input does not end in branches in curly brackets, or if any of the
+/// branches are malformed.
+pub fn parse_input(input: TokenStream, what: &str) -> Result<(TokenStream, Span, Vec<Branch>), Diagnostic2> {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let group = match tokens.pop() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(tt) => return Err(error2(tt.span(), &format!("Expected {what} and then match branches wrapped in `{{}}`"))),
+        None => return Err(error2(Span::call_site(), &format!("Expected {what} and then match branches wrapped in `{{}}`"))),
+    };
+    let span: Span = tokens.first().map(TokenTree::span).unwrap_or_else(|| group.span());
+    let value: TokenStream = unwrap_none(tokens.into_iter().collect());
+
+    // Parse the branches
+    let mut branches: Vec<Branch> = Vec::new();
+    let mut iter = group.stream().into_iter().peekable();
+    while let Some(branch) = Branch::parse(&mut iter)? {
+        branches.push(branch);
+    }
+    Ok((value, span, branches))
+}
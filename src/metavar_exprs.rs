@@ -0,0 +1,149 @@
+//  METAVAR EXPRS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides stable polyfills for the most common uses of the unstable `macro_metavar_expr`,
+//!   i.e., `${count($x)}`, `${index()}` and `${ignore($x)}`.
+//
+
+use proc_macro2::{Delimiter, Group, Literal, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::utils::Callback;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Replaces every `#index` in the given tokens with the given index.
+///
+/// Nested calls to `meta_index!()` are left alone, as `#index` in them refers to their own
+/// iterations.
+///
+/// # Arguments
+/// - `tokens`: The [`TokenStream`] of a single iteration.
+/// - `index`: The index of the iteration.
+///
+/// # Returns
+/// The same tokens, but with every `#index` replaced by an unsuffixed integer literal.
+fn resolve(tokens: TokenStream, index: usize) -> TokenStream {
+    let mut res = TokenStream::new();
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            // Replace `#index`
+            TokenTree::Punct(p) if p.as_char() == '#' && matches!(iter.peek(), Some(TokenTree::Ident(i)) if i == "index") => {
+                let Some(TokenTree::Ident(ident)) = iter.next() else { unreachable!() };
+                let mut lit = Literal::usize_unsuffixed(index);
+                lit.set_span(ident.span());
+                res.extend([TokenTree::Literal(lit)]);
+            },
+
+            // Skip over nested calls, i.e., `meta_index!(...)`
+            TokenTree::Ident(ident) if ident == "meta_index" && matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '!') => {
+                res.extend([TokenTree::Ident(ident)]);
+                res.extend(iter.next());
+                res.extend(iter.next());
+            },
+
+            // Recurse into groups
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), resolve(group.stream(), index));
+                new.set_span(group.span());
+                res.extend([TokenTree::Group(new)]);
+            },
+            tt => res.extend([tt]),
+        }
+    }
+    res
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`meta_count()`](super::meta_count())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] with the comma-separated items to count.
+///
+/// # Returns
+/// A new [`TokenStream`] with the number of items as an unsuffixed integer literal.
+///
+/// # Errors
+/// This function does not error, but returns a [`Result`] for consistency with the other macros.
+pub fn meta_count(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Count the non-empty runs of tokens between commas
+    let mut count: usize = 0;
+    let mut empty: bool = true;
+    for tt in input {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => empty = true,
+            _ if empty => {
+                count += 1;
+                empty = false;
+            },
+            _ => {},
+        }
+    }
+    Ok(TokenTree::Literal(Literal::usize_unsuffixed(count)).into())
+}
+
+/// Defines the implementation of the [`meta_index()`](super::meta_index())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] with the iterations in groups and an optional callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the contents of the iterations, where `#index` is replaced by their
+/// index, or a call to the callback with them.
+///
+/// # Errors
+/// This function may error if the callback is malformed, or if `#index` is used outside of an
+/// iteration.
+pub fn meta_index(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (input, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+
+    // Resolve every iteration
+    let mut res = TokenStream::new();
+    let mut index: usize = 0;
+    let mut iter = input.into_iter().peekable();
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree::Group(group) if group.delimiter() != Delimiter::None => {
+                res.extend(resolve(group.stream(), index));
+                index += 1;
+            },
+            TokenTree::Punct(p) if p.as_char() == '#' && matches!(iter.peek(), Some(TokenTree::Ident(i)) if i == "index") => {
+                let span: Span = iter.next().map(|tt| tt.span()).unwrap_or(p.span());
+                return Err(error2(span, "`#index` can only be used in an iteration (i.e., in brackets)"));
+            },
+            tt => res.extend([tt]),
+        }
+    }
+    Ok(match callback {
+        Some(callback) => callback.call(res),
+        None => res,
+    })
+}
+
+/// Defines the implementation of the [`meta_ignore()`](super::meta_ignore())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] with the tokens to ignore, optionally followed by `=>` and
+///   tokens to emit instead.
+///
+/// # Returns
+/// A new [`TokenStream`] with the tokens after the `=>`, or nothing if there is none.
+///
+/// # Errors
+/// This function does not error, but returns a [`Result`] for consistency with the other macros.
+pub fn meta_ignore(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let arrow: Option<usize> = tokens.windows(2).position(
+        |pair| matches!(pair, [TokenTree::Punct(eq), TokenTree::Punct(gt)] if eq.as_char() == '=' && eq.spacing() == Spacing::Joint && gt.as_char() == '>'),
+    );
+    Ok(match arrow {
+        Some(i) => tokens.into_iter().skip(i + 2).collect(),
+        None => TokenStream::new(),
+    })
+}
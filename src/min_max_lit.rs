@@ -0,0 +1,137 @@
+//  MIN MAX LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for finding the minimum or maximum of a list of numeric literals at compile
+//!   time.
+//
+
+use std::cmp::Ordering;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{Number, SignedLit, parse_signed_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single literal in the list.
+struct Item {
+    /// Its value.
+    value:  Number,
+    /// The suffix of the literal, if any.
+    suffix: String,
+    /// The tokens that make up the literal, including a `-` if it was negated.
+    tokens: TokenStream,
+    /// The span of the literal.
+    span:   Span,
+}
+
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// The list of [`Item`]s to find the minimum or maximum of.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<Vec<Item>, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+    let mut items: Vec<Item> = Vec::new();
+    while iter.peek().is_some() {
+        // Parse the literal
+        let lit: SignedLit = parse_signed_lit(&mut iter)?;
+        let span: Span = lit.lit.span();
+        let (value, suffix): (Number, String) = Number::from_lit(&lit)?;
+        let tokens: TokenStream = lit.into_tokens()?;
+        items.push(Item { value, suffix, tokens, span });
+
+        // Parse the comma
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+            None => break,
+        }
+    }
+    if items.is_empty() {
+        return Err(error2(Span::mixed_site(), "Expected at least one literal"));
+    }
+    Ok(items)
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds the literal in the input that is ordered first by the given ordering.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+/// - `wanted`: The [`Ordering`] that a literal must have with respect to the current best to
+///   replace it.
+///
+/// # Returns
+/// The tokens of the chosen literal.
+///
+/// # Errors
+/// This function can error if the input was invalid, or if the literals were of different types.
+fn find(input: TokenStream, wanted: Ordering) -> Result<TokenStream, Diagnostic2> {
+    let mut items = parse_input(input)?.into_iter();
+    let mut best: Item = items.next().unwrap();
+    let mut suffix: Option<(String, Span)> = if best.suffix.is_empty() { None } else { Some((best.suffix.clone(), best.span)) };
+    for item in items {
+        // Check the literals are compatible
+        if !item.suffix.is_empty() {
+            match &suffix {
+                Some((suffix, _)) if *suffix != item.suffix => {
+                    return Err(error2(item.span, &format!("Literal has suffix `{}`, but a previous literal has suffix `{suffix}`", item.suffix)));
+                },
+                Some(_) => {},
+                None => suffix = Some((item.suffix.clone(), item.span)),
+            }
+        }
+        let Some(ord) = item.value.compare(&best.value) else {
+            return Err(error2(item.span, "Cannot compare integer and floating-point literals"));
+        };
+
+        // Keep the first of equal literals
+        if ord == wanted {
+            best = item;
+        }
+    }
+    Ok(best.tokens)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`min_lit()`](super::min_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the list of literals.
+///
+/// # Returns
+/// A new [`TokenStream`] with the smallest literal.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+#[inline]
+pub fn min_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> { find(input, Ordering::Less) }
+
+/// Defines the implementation of the [`max_lit()`](super::max_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the list of literals.
+///
+/// # Returns
+/// A new [`TokenStream`] with the largest literal.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+#[inline]
+pub fn max_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> { find(input, Ordering::Greater) }
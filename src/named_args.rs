@@ -0,0 +1,83 @@
+//  NAMED ARGS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for reordering `name: value` arguments into positional ones.
+//
+
+use std::collections::HashMap;
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::kv_args::{Kind, Param, emit, parse_schema, unknown_argument};
+use crate::matching::{is_punct, split_commas};
+use crate::utils::Callback;
+
+
+/***** TOKEN PARSING *****/
+/// Parses a single argument.
+///
+/// Arguments have the syntax `$name:ident : $($value:tt)*`, or `$name:ident` as a shorthand for
+/// `$name: $name` (like in struct expressions).
+///
+/// # Arguments
+/// - `tokens`: The tokens of the argument.
+///
+/// # Returns
+/// The name of the argument and its value.
+///
+/// # Errors
+/// This function errors if the tokens are not an argument.
+fn parse_arg(tokens: TokenStream) -> Result<(Ident, TokenStream), Diagnostic2> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    match tokens.as_slice() {
+        [TokenTree::Ident(name)] => Ok((name.clone(), TokenStream::from(TokenTree::Ident(name.clone())))),
+        [TokenTree::Ident(name), TokenTree::Punct(p), value @ ..] if p.as_char() == ':' && !is_punct(value.first(), ':') => {
+            if value.is_empty() {
+                return Err(error2(p.span(), "Expected a value after ':'"));
+            }
+            Ok((name.clone(), value.iter().cloned().collect()))
+        },
+        [TokenTree::Ident(_), tt, ..] => Err(error2(tt.span(), "Expected either ':' and a value, or nothing")),
+        [tt, ..] => Err(error2(tt.span(), "Expected an argument name")),
+        [] => Err(error2(Span::call_site(), "Expected an argument, not an empty one")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`named_args()`](super::named_args())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] to parse for input.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the callback with the values in the order of the schema.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the arguments do not
+/// adhere to the schema.
+pub fn named_args(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (args, params, callback, span): (TokenStream, Vec<Param>, Callback, Span) = parse_schema(input)?;
+    if let Some(param) = params.iter().find(|param| matches!(param.kind, Kind::Flag)) {
+        return Err(error2(param.name.span(), &format!("Flags are not supported; give a default value instead (e.g., `{} = false`)", param.name)));
+    }
+
+    // Parse the arguments and match them to the schema
+    let mut values: HashMap<String, TokenStream> = HashMap::new();
+    for arg in split_commas(args) {
+        let (name, value): (Ident, TokenStream) = parse_arg(arg)?;
+        let key: String = name.to_string();
+        if !params.iter().any(|param| param.name == key) {
+            return Err(unknown_argument(&params, &key, name.span()));
+        }
+        if values.insert(key, value).is_some() {
+            return Err(error2(name.span(), &format!("Duplicate argument `{name}`")));
+        }
+    }
+    emit(params, values, span, callback)
+}
@@ -0,0 +1,118 @@
+//  OPTION ENV MATCH.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for choosing between tokens based on whether an environment variable is set
+//!   at compile time, and on its value.
+//
+
+use proc_macro2::{Literal, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, parse_string};
+use crate::matching::{Branch, parse_input};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Defines the matchers that can be given at the head of a branch.
+enum EnvMatcher {
+    /// Any value, or no value at all.
+    Any,
+    /// The variable is not set.
+    Unset,
+    /// The variable is set to any value.
+    Set,
+    /// The variable is set to exactly this value.
+    Value(String),
+}
+impl EnvMatcher {
+    /// Parses this EnvMatcher from a branch's matcher.
+    ///
+    /// # Arguments
+    /// - `branch`: The [`Branch`] to parse the matcher of.
+    ///
+    /// # Returns
+    /// An EnvMatcher specified by the branch.
+    ///
+    /// # Errors
+    /// This function may error if the matcher is unknown, or if the branch binds more parts than
+    /// the matcher has.
+    fn parse(branch: &Branch) -> Result<Self, Diagnostic2> {
+        let (matcher, parts): (Self, &[&str]) = match &branch.matcher {
+            TokenTree::Literal(_) => (Self::Value(parse_string(Some(branch.matcher.clone()), branch.matcher.span(), "a value")?), &[]),
+            matcher => match matcher.to_string().as_str() {
+                "_" => (Self::Any, &[]),
+                "unset" => (Self::Unset, &[]),
+                "set" => (Self::Set, &["value"]),
+                _ => return Err(error2(matcher.span(), "Expected one of `_`, `unset`, `set` or a string literal")),
+            },
+        };
+        branch.check_bindings(parts)?;
+        Ok(matcher)
+    }
+
+    /// Checks whether this matcher matches the value of a variable.
+    ///
+    /// # Arguments
+    /// - `value`: The value of the variable, or [`None`] if it is not set.
+    /// - `span`: The [`Span`] to give to bound values.
+    ///
+    /// # Returns
+    /// The parts to bind if this matcher matches `value`, or [`None`] otherwise.
+    fn match_value(&self, value: Option<&str>, span: Span) -> Option<Vec<TokenStream>> {
+        match (self, value) {
+            (Self::Any, _) | (Self::Unset, None) => Some(Vec::new()),
+            (Self::Set, Some(value)) => {
+                let mut lit = Literal::string(value);
+                lit.set_span(span);
+                Some(vec![TokenTree::Literal(lit).into()])
+            },
+            (Self::Value(expected), Some(value)) if expected == value => Some(Vec::new()),
+            _ => None,
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`option_env_match()`](super::option_env_match())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] with the name of the variable and the branches to match it
+///   with.
+///
+/// # Returns
+/// A new [`TokenStream`] with the tokens of the first branch that matches the variable.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, if the variable is not valid
+/// unicode or if no branch matches.
+pub fn option_env_match(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (name, span, branches): (TokenStream, Span, Vec<Branch>) = parse_input(input, "the name of an environment variable")?;
+    let mut tokens = name.into_iter();
+    let name: String = parse_string(tokens.next(), span, "the name of an environment variable")?;
+    if let Some(tt) = tokens.next() {
+        return Err(error2(tt.span(), "Expected match branches wrapped in `{}`"));
+    }
+    let matchers: Vec<EnvMatcher> = branches.iter().map(EnvMatcher::parse).collect::<Result<_, _>>()?;
+
+    // Read the variable
+    let value: Option<String> = match std::env::var(&name) {
+        Ok(value) => Some(value),
+        Err(std::env::VarError::NotPresent) => None,
+        Err(std::env::VarError::NotUnicode(_)) => return Err(error2(span, &format!("Environment variable `{name}` is not valid unicode"))),
+    };
+
+    // Find the first branch that matches
+    for (branch, matcher) in branches.into_iter().zip(matchers) {
+        if let Some(parts) = matcher.match_value(value.as_deref(), span) {
+            return Ok(branch.expand(parts));
+        }
+    }
+    Err(Diagnostic2::error(span, format!("Unmatched environment variable `{name}`")).help(match value {
+        Some(value) => format!("its value is {value:?}"),
+        None => "it is not set".into(),
+    }))
+}
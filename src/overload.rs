@@ -0,0 +1,194 @@
+//  OVERLOAD.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for dispatching to callbacks based on the number and kinds of arguments.
+//
+
+use macro_toolkit_core::match_lit::LitMatcher;
+use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitBool};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::split_punct;
+use crate::utils::Callback;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Attempts to interpret an argument as a literal.
+///
+/// # Arguments
+/// - `arg`: The tokens of the argument.
+///
+/// # Returns
+/// The literal if the argument is a single literal (possibly negated, or wrapped in an invisible
+/// group like `$lit:literal`), or [`None`] otherwise.
+fn parse_lit(arg: &[TokenTree]) -> Option<Lit> {
+    match arg {
+        [TokenTree::Literal(lit)] => Some(Lit::new(lit.clone())),
+        [TokenTree::Punct(p), TokenTree::Literal(lit)] if p.as_char() == '-' => match Lit::new(lit.clone()) {
+            lit @ (Lit::Int(_) | Lit::Float(_)) => Some(lit),
+            _ => None,
+        },
+        [TokenTree::Ident(ident)] if ident == "true" || ident == "false" => Some(Lit::Bool(LitBool { value: ident == "true", span: ident.span() })),
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::None => parse_lit(&group.stream().into_iter().collect::<Vec<_>>()),
+        _ => None,
+    }
+}
+
+/// Describes the kind of an argument for error messages.
+///
+/// # Arguments
+/// - `lit`: The argument interpreted as a literal, if it is one.
+///
+/// # Returns
+/// A short description of the argument (e.g., `string` or `non-literal`).
+fn describe(lit: Option<&Lit>) -> &'static str {
+    match lit {
+        Some(Lit::Bool(_)) => "bool",
+        Some(Lit::Int(_)) => "int",
+        Some(Lit::Float(_)) => "float",
+        Some(Lit::Byte(_)) => "byte",
+        Some(Lit::Char(_)) => "char",
+        Some(Lit::ByteStr(_)) => "bytes",
+        Some(Lit::Str(_)) => "string",
+        Some(Lit::CStr(_)) => "cstring",
+        Some(_) => "literal",
+        None => "non-literal",
+    }
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Describes the matcher for a single argument.
+enum ArgMatcher {
+    /// Matches any argument, literal or not.
+    Any,
+    /// Matches literals of a particular kind.
+    Lit(LitMatcher),
+}
+
+
+
+/// Defines a single overload.
+struct Overload {
+    /// The matchers for the arguments, in order.
+    args:     Vec<ArgMatcher>,
+    /// Whether any number of arguments may follow the matched ones (i.e., it ends in `..`).
+    rest:     bool,
+    /// The callback to call when this overload matches.
+    callback: Callback,
+}
+impl Overload {
+    /// Parses an Overload from its tokens.
+    ///
+    /// Overloads have the syntax `($($matcher:ident),* $(, ..)?) => $callback:path ! $(($($args:tt)*))?`.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the overload.
+    ///
+    /// # Returns
+    /// The parsed Overload.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a valid overload.
+    fn parse(tokens: Vec<TokenTree>) -> Result<Self, Diagnostic2> {
+        let mut iter = tokens.into_iter();
+        let group: Group = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+            Some(tt) => return Err(error2(tt.span(), "Expected the matchers of the overload in parenthesis")),
+            None => return Err(error2(Span::call_site(), "Expected an overload, not an empty one")),
+        };
+        let Some(callback) = Callback::parse_opt(&mut iter)? else {
+            return Err(error2(group.span(), "Expected '=>' and a callback after the matchers"));
+        };
+
+        // Parse the matchers
+        let mut args: Vec<ArgMatcher> = Vec::new();
+        let mut rest: bool = false;
+        for matcher in split_punct(group.stream(), ',') {
+            if rest {
+                let span: Span = matcher.first().map(TokenTree::span).unwrap_or_else(|| group.span());
+                return Err(error2(span, "Expected nothing after '..'"));
+            }
+            match matcher.as_slice() {
+                [TokenTree::Ident(ident)] if ident == "any" => args.push(ArgMatcher::Any),
+                [TokenTree::Ident(ident)] => args.push(ArgMatcher::Lit(LitMatcher::parse(ident.clone())?)),
+                [TokenTree::Punct(p1), TokenTree::Punct(p2)] if p1.as_char() == '.' && p2.as_char() == '.' => rest = true,
+                [tt, ..] => return Err(error2(tt.span(), "Expected a match identifier, `any` or '..'")),
+                [] => return Err(error2(group.span(), "Expected a match identifier, `any` or '..', not an empty one")),
+            }
+        }
+        Ok(Self { args, rest, callback })
+    }
+
+    /// Checks whether this overload accepts the given arguments.
+    ///
+    /// # Arguments
+    /// - `args`: The arguments, each with their interpretation as a literal (if any).
+    ///
+    /// # Returns
+    /// True if the number and kinds of arguments match, or false otherwise.
+    fn matches(&self, args: &[(Vec<TokenTree>, Option<Lit>)]) -> bool {
+        if args.len() < self.args.len() || (!self.rest && args.len() > self.args.len()) {
+            return false;
+        }
+        self.args.iter().zip(args).all(|(matcher, (_, lit))| match (matcher, lit) {
+            (ArgMatcher::Any, _) => true,
+            (ArgMatcher::Lit(matcher), Some(lit)) => matcher.match_lit(lit),
+            (ArgMatcher::Lit(_), None) => false,
+        })
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`overload()`](super::overload())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the arguments and the overloads.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the callback of the first overload that matches the
+/// arguments.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if no overload matches.
+pub fn overload(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Split the arguments from the overloads
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let group: Group = match tokens.pop() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(tt) => return Err(error2(tt.span(), "Expected arguments and then overloads wrapped in `{}`")),
+        None => return Err(error2(Span::call_site(), "Expected arguments and then overloads wrapped in `{}`")),
+    };
+    let span: Span = tokens.first().map(TokenTree::span).unwrap_or_else(|| group.span());
+    let args: Vec<(Vec<TokenTree>, Option<Lit>)> = split_punct(tokens.into_iter().collect(), ',')
+        .into_iter()
+        .map(|arg| {
+            let lit: Option<Lit> = parse_lit(&arg);
+            (arg, lit)
+        })
+        .collect();
+    let overloads: Vec<Overload> = split_punct(group.stream(), ',').into_iter().map(Overload::parse).collect::<Result<_, _>>()?;
+
+    // Call the first overload that matches
+    let Some(overload) = overloads.into_iter().find(|overload| overload.matches(&args)) else {
+        let kinds: Vec<&str> = args.iter().map(|(_, lit)| describe(lit.as_ref())).collect();
+        return Err(error2(span, &format!("No overload matches the arguments ({})", kinds.join(", "))));
+    };
+    let mut res = TokenStream::new();
+    for (i, (arg, _)) in args.into_iter().enumerate() {
+        if i > 0 {
+            res.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        res.extend(arg);
+    }
+    Ok(overload.callback.call_item(res))
+}
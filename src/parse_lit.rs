@@ -0,0 +1,425 @@
+//  PARSE LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for parsing string literals with one of a set of validators, passing the
+//!   normalized result to a callback.
+//
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr as _;
+
+use proc_macro2::{Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::color_lit::parse_color;
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::duration_lit::parse_duration;
+use crate::lits::{self, int_lit};
+use crate::size_lit::parse_size;
+use crate::utils::{Callback, is_ident, is_keyword};
+use crate::uuid_lit::parse_uuid;
+
+
+/***** CONSTANTS *****/
+/// Defines the signature of validators.
+///
+/// A validator parses a string literal and returns the normalized components that are passed to
+/// the callback, or a spanned error if the literal was not valid.
+type Validator = fn(&LitStr) -> Result<Vec<TokenStream>, Diagnostic2>;
+
+/// The kinds of literals that can be parsed, together with their validators.
+///
+/// Adding a new kind is a matter of writing a [`Validator`] and listing it here (and in the
+/// macro's documentation).
+const VALIDATORS: [(&str, Validator); 13] = [
+    ("bool", validate_bool),
+    ("char", validate_char),
+    ("color", validate_color),
+    ("duration", validate_duration),
+    ("float", validate_float),
+    ("ident", validate_ident),
+    ("int", validate_int),
+    ("ip", validate_ip),
+    ("ipv4", validate_ipv4),
+    ("ipv6", validate_ipv6),
+    ("path", validate_path),
+    ("size", validate_size),
+    ("uuid", validate_uuid),
+];
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The kind of literal to parse.
+    kind:     Ident,
+    /// The literal to parse.
+    lit:      LitStr,
+    /// The callback to pass the components to.
+    callback: Callback,
+    /// The message to emit instead of the validator's if parsing fails, if any.
+    message:  Option<LitStr>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter();
+
+        // Parse the kind
+        let kind: Ident = match iter.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tt) => return Err(error2(tt.span(), "Expected the kind of literal to parse (e.g., `int`)")),
+            None => return Err(error2(Span::mixed_site(), "Expected the kind of literal to parse (e.g., `int`)")),
+        };
+        Self::parse_comma(&mut iter)?;
+
+        // Parse the literal
+        let lit: LitStr = match iter.next().map(lits::parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected a string literal")),
+        };
+        Self::parse_comma(&mut iter)?;
+
+        // Parse the callback, which runs until the next comma
+        let callback: Vec<TokenTree> = iter.by_ref().take_while(|tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == ',')).collect();
+        let callback: Callback = Callback::parse(&mut callback.into_iter())?;
+
+        // Finally, parse the optional message (and an optional trailing comma)
+        let message: Option<LitStr> = match iter.next().map(lits::parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => Some(lit),
+            Some(lit) => return Err(error2(lit.span(), "Expected a string literal with an error message")),
+            None => None,
+        };
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' && message.is_some() => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected nothing after the error message")),
+            None => {},
+        }
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the error message"));
+        }
+        Ok(Self { kind, lit, callback, message })
+    }
+
+    /// Parses a comma separating two arguments.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input was not a comma.
+    fn parse_comma(iter: &mut impl Iterator<Item = TokenTree>) -> Result<(), Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => Ok(()),
+            Some(tt) => Err(error2(tt.span(), "Expected ','")),
+            None => Err(error2(Span::mixed_site(), "Expected ','")),
+        }
+    }
+}
+
+
+
+
+
+/***** VALIDATORS *****/
+/// Parses a boolean, i.e., `true` or `false`.
+///
+/// Yields the boolean as a single keyword.
+fn validate_bool(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    match lit.value().as_str() {
+        value @ ("true" | "false") => Ok(vec![TokenStream::from(TokenTree::Ident(Ident::new(value, lit.span())))]),
+        _ => Err(error2(lit.span(), "Expected either \"true\" or \"false\"")),
+    }
+}
+
+/// Parses a single character.
+///
+/// Yields the character as a single character literal.
+fn validate_char(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let value: String = lit.value();
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => {
+            let mut res = Literal::character(c);
+            res.set_span(lit.span());
+            Ok(vec![TokenStream::from(TokenTree::Literal(res))])
+        },
+        _ => Err(error2(lit.span(), &format!("Expected exactly one character, got {}", value.chars().count()))),
+    }
+}
+
+/// Parses a hexadecimal color (see the `color_lit!()`-macro).
+///
+/// Yields the red, green, blue and alpha components as `u8` literals.
+fn validate_color(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let ([r, g, b], a) = parse_color(lit)?;
+    Ok([r, g, b, a.unwrap_or(0xFF)]
+        .into_iter()
+        .map(|c| {
+            let mut res = Literal::u8_suffixed(c);
+            res.set_span(lit.span());
+            TokenStream::from(TokenTree::Literal(res))
+        })
+        .collect())
+}
+
+/// Parses a human-readable duration (see the `duration_lit!()`-macro).
+///
+/// Yields the whole seconds as a `u64` literal and the remaining nanoseconds as a `u32` literal,
+/// matching [`Duration::new()`](std::time::Duration::new()).
+fn validate_duration(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let ns: u128 = parse_duration(lit)?;
+    let Ok(secs) = u64::try_from(ns / 1_000_000_000) else {
+        return Err(error2(lit.span(), "Duration in seconds does not fit in a `u64`"));
+    };
+    let mut secs = Literal::u64_suffixed(secs);
+    secs.set_span(lit.span());
+    let mut nanos = Literal::u32_suffixed((ns % 1_000_000_000) as u32);
+    nanos.set_span(lit.span());
+    Ok(vec![TokenStream::from(TokenTree::Literal(secs)), TokenStream::from(TokenTree::Literal(nanos))])
+}
+
+/// Parses a (finite) floating-point number.
+///
+/// Yields the number as a single, unsuffixed float literal, preceded by a `-` if it's negative.
+fn validate_float(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let value: String = lit.value().replace('_', "");
+    let (neg, abs): (bool, &str) = match value.strip_prefix('-') {
+        Some(abs) => (true, abs),
+        None => (false, value.strip_prefix('+').unwrap_or(&value)),
+    };
+    if abs.starts_with(['+', '-']) {
+        return Err(error2(lit.span(), "Expected a finite floating-point number (e.g., \"3.14\")"));
+    }
+    match abs.parse::<f64>() {
+        Ok(abs) if abs.is_finite() => {
+            let mut res = TokenStream::new();
+            if neg {
+                let mut minus = Punct::new('-', Spacing::Alone);
+                minus.set_span(lit.span());
+                res.extend([TokenTree::Punct(minus)]);
+            }
+            let mut abs = Literal::f64_unsuffixed(abs);
+            abs.set_span(lit.span());
+            res.extend([TokenTree::Literal(abs)]);
+            Ok(vec![res])
+        },
+        _ => Err(error2(lit.span(), "Expected a finite floating-point number (e.g., \"3.14\")")),
+    }
+}
+
+/// Parses a Rust identifier.
+///
+/// Yields the identifier itself. Keywords are refused.
+fn validate_ident(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let value: String = lit.value();
+    if is_keyword(&value) {
+        return Err(error2(lit.span(), &format!("{value:?} is a keyword, and cannot be used as an identifier")));
+    }
+    if !is_ident(&value) {
+        return Err(error2(lit.span(), &format!("{value:?} is not a valid identifier")));
+    }
+    Ok(vec![TokenStream::from(TokenTree::Ident(Ident::new(&value, lit.span())))])
+}
+
+/// Parses an integer, which may be negative and use the `0x`, `0o` and `0b` prefixes.
+///
+/// Yields the integer as a single, unsuffixed decimal literal, preceded by a `-` if it's negative.
+fn validate_int(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let value: String = lit.value().replace('_', "");
+    let (neg, abs): (bool, &str) = match value.strip_prefix('-') {
+        Some(abs) => (true, abs),
+        None => (false, value.strip_prefix('+').unwrap_or(&value)),
+    };
+    let (radix, digits): (u32, &str) = match abs.get(..2) {
+        Some("0x" | "0X") => (16, &abs[2..]),
+        Some("0o" | "0O") => (8, &abs[2..]),
+        Some("0b" | "0B") => (2, &abs[2..]),
+        _ => (10, abs),
+    };
+    if digits.is_empty() || digits.starts_with(['+', '-']) {
+        return Err(error2(lit.span(), "Expected an integer (e.g., \"42\" or \"0xFF\")"));
+    }
+    match u128::from_str_radix(digits, radix) {
+        Ok(abs) if !neg || abs <= 1 << 127 => Ok(vec![int_lit(neg, abs, None, lit.span())]),
+        Ok(_) => Err(error2(lit.span(), "Integer is too small to be represented")),
+        Err(err) => Err(error2(lit.span(), &format!("Expected an integer (e.g., \"42\" or \"0xFF\"): {err}"))),
+    }
+}
+
+/// Parses an IPv4 or IPv6 address.
+///
+/// Yields the components as either four `u8` literals or eight `u16` literals, respectively.
+fn validate_ip(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    match IpAddr::from_str(&lit.value()) {
+        Ok(IpAddr::V4(addr)) => Ok(ipv4_components(addr, lit.span())),
+        Ok(IpAddr::V6(addr)) => Ok(ipv6_components(addr, lit.span())),
+        Err(_) => Err(error2(lit.span(), "Expected an IPv4 or IPv6 address")),
+    }
+}
+
+/// Parses an IPv4 address.
+///
+/// Yields the components as four `u8` literals.
+fn validate_ipv4(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    match Ipv4Addr::from_str(&lit.value()) {
+        Ok(addr) => Ok(ipv4_components(addr, lit.span())),
+        Err(_) => Err(error2(lit.span(), "Expected an IPv4 address (e.g., \"127.0.0.1\")")),
+    }
+}
+
+/// Parses an IPv6 address.
+///
+/// Yields the components as eight `u16` literals.
+fn validate_ipv6(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    match Ipv6Addr::from_str(&lit.value()) {
+        Ok(addr) => Ok(ipv6_components(addr, lit.span())),
+        Err(_) => Err(error2(lit.span(), "Expected an IPv6 address (e.g., \"::1\")")),
+    }
+}
+
+/// Parses a `/`-separated path.
+///
+/// Yields every non-empty component other than `.` as a string literal. If the path is absolute,
+/// the first component is `"/"`.
+fn validate_path(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let value: String = lit.value();
+    if value.contains('\0') {
+        return Err(error2(lit.span(), "Paths cannot contain NUL-characters"));
+    }
+    let root: Option<&str> = if value.starts_with('/') { Some("/") } else { None };
+    let components: Vec<TokenStream> = root
+        .into_iter()
+        .chain(value.split('/').filter(|c| !c.is_empty() && *c != "."))
+        .map(|c| {
+            let mut res = Literal::string(c);
+            res.set_span(lit.span());
+            TokenStream::from(TokenTree::Literal(res))
+        })
+        .collect();
+    if components.is_empty() {
+        return Err(error2(lit.span(), "Expected a non-empty path"));
+    }
+    Ok(components)
+}
+
+/// Parses a human-readable size (see the `size_lit!()`-macro).
+///
+/// Yields the size in bytes as a single, unsuffixed integer literal.
+fn validate_size(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> { Ok(vec![int_lit(false, parse_size(lit)?, None, lit.span())]) }
+
+/// Parses a UUID (see the `uuid_lit!()`-macro).
+///
+/// Yields the UUID as a single `u128` literal.
+fn validate_uuid(lit: &LitStr) -> Result<Vec<TokenStream>, Diagnostic2> {
+    let mut res = Literal::from_str(&format!("0x{:032x}u128", parse_uuid(lit)?)).unwrap();
+    res.set_span(lit.span());
+    Ok(vec![TokenStream::from(TokenTree::Literal(res))])
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates the components of an IPv4 address.
+///
+/// # Arguments
+/// - `addr`: The [`Ipv4Addr`] to generate the components of.
+/// - `span`: The [`Span`] to give the generated tokens.
+///
+/// # Returns
+/// A list of four `u8` literals.
+fn ipv4_components(addr: Ipv4Addr, span: Span) -> Vec<TokenStream> {
+    addr.octets()
+        .into_iter()
+        .map(|o| {
+            let mut res = Literal::u8_suffixed(o);
+            res.set_span(span);
+            TokenStream::from(TokenTree::Literal(res))
+        })
+        .collect()
+}
+
+/// Generates the components of an IPv6 address.
+///
+/// # Arguments
+/// - `addr`: The [`Ipv6Addr`] to generate the components of.
+/// - `span`: The [`Span`] to give the generated tokens.
+///
+/// # Returns
+/// A list of eight `u16` literals.
+fn ipv6_components(addr: Ipv6Addr, span: Span) -> Vec<TokenStream> {
+    addr.segments()
+        .into_iter()
+        .map(|s| {
+            let mut res = Literal::from_str(&format!("0x{s:04x}u16")).unwrap();
+            res.set_span(span);
+            TokenStream::from(TokenTree::Literal(res))
+        })
+        .collect()
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`parse_lit()`](super::parse_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the kind of literal, the literal itself, the
+///   callback and an optional error message.
+///
+/// # Returns
+/// A new [`TokenStream`] with a call to the given callback with the normalized components of the
+/// literal.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the literal is not
+/// valid for the given kind.
+pub fn parse_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { kind, lit, callback, message } = Input::parse(input)?;
+
+    // Find the validator
+    let name: String = kind.to_string();
+    let Some((_, validator)) = VALIDATORS.iter().find(|(n, _)| *n == name) else {
+        let kinds: Vec<String> = VALIDATORS.iter().map(|(n, _)| format!("`{n}`")).collect();
+        return Err(error2(kind.span(), &format!("Unknown kind of literal `{name}` (expected one of {})", kinds.join(", "))));
+    };
+
+    // Run it
+    let components: Vec<TokenStream> = match validator(&lit) {
+        Ok(components) => components,
+        Err(err) => match message {
+            Some(message) => return Err(error2(lit.span(), &message.value())),
+            None => return Err(err),
+        },
+    };
+
+    // Pass the result to the callback
+    let mut res = TokenStream::new();
+    for (i, component) in components.into_iter().enumerate() {
+        if i > 0 {
+            let mut comma = Punct::new(',', Spacing::Alone);
+            comma.set_span(lit.span());
+            res.extend([TokenTree::Punct(comma)]);
+        }
+        res.extend(component);
+    }
+    Ok(callback.call(res))
+}
@@ -0,0 +1,238 @@
+//  PUB MACRO.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for giving `macro_rules!`-macros path-based visibility, like other items.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::gensym::fresh;
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single `macro_rules!`-macro with a visibility.
+struct Definition {
+    /// Any attributes on the macro, except for doc comments.
+    attrs: TokenStream,
+    /// Any doc comments (or other `#[doc]`-attributes) on the macro.
+    docs:  TokenStream,
+    /// Any `#[cfg]`- or `#[cfg_attr]`-attributes on the macro, which are also in `attrs`.
+    cfgs:  TokenStream,
+    /// The visibility of the macro, which is empty if it is private.
+    vis:   TokenStream,
+    /// The name of the macro.
+    name:  Ident,
+    /// The rules of the macro, in any kind of delimiter.
+    rules: Group,
+}
+impl Definition {
+    /// Parses a Definition from an iterator over [`TokenTree`]s.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Definition.
+    ///
+    /// # Errors
+    /// This function can error if the head of the input was not a valid definition.
+    fn parse(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Self, Diagnostic2> {
+        // Parse the attributes
+        let (mut attrs, mut docs, mut cfgs) = (TokenStream::new(), TokenStream::new(), TokenStream::new());
+        while let Some(pound) = iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '#')) {
+            let group: Group = match iter.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group,
+                Some(tt) => return Err(error2(tt.span(), "Expected an attribute in square brackets")),
+                None => return Err(error2(pound.span(), "Expected an attribute in square brackets")),
+            };
+            let path: String = group.stream().into_iter().next().map(|tt| tt.to_string()).unwrap_or_default();
+            if path == "doc" {
+                docs.extend([pound, TokenTree::Group(group)]);
+            } else {
+                if path == "cfg" || path == "cfg_attr" {
+                    cfgs.extend([pound.clone(), TokenTree::Group(group.clone())]);
+                }
+                attrs.extend([pound, TokenTree::Group(group)]);
+            }
+        }
+
+        // Parse the visibility
+        let mut vis = TokenStream::new();
+        match iter.peek() {
+            Some(TokenTree::Ident(ident)) if ident == "pub" => {
+                vis.extend(iter.next());
+                if let Some(TokenTree::Group(group)) = iter.peek()
+                    && group.delimiter() == Delimiter::Parenthesis
+                {
+                    vis.extend(iter.next());
+                }
+            },
+            // Allow visibilities given as `$vis:vis`
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+                vis.extend(group.stream());
+                iter.next();
+            },
+            _ => {},
+        }
+
+        // Parse the `macro_rules!`
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "macro_rules" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected 'macro_rules'")),
+            None => return Err(error2(Span::call_site(), "Expected 'macro_rules'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '!' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '!'")),
+            None => return Err(error2(Span::call_site(), "Expected '!'")),
+        }
+        let name: Ident = match iter.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tt) => return Err(error2(tt.span(), "Expected the name of the macro")),
+            None => return Err(error2(Span::call_site(), "Expected the name of the macro")),
+        };
+        let rules: Group = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() != Delimiter::None => group,
+            Some(tt) => return Err(error2(tt.span(), "Expected the rules of the macro")),
+            None => return Err(error2(name.span(), "Expected the rules of the macro")),
+        };
+
+        // Parse the optional semicolon, which is required if the rules aren't in curly brackets
+        match iter.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ';' => {
+                iter.next();
+            },
+            _ if rules.delimiter() != Delimiter::Brace => return Err(error2(rules.span(), "Expected ';' after the rules of the macro")),
+            _ => {},
+        }
+        Ok(Self { attrs, docs, cfgs, vis, name, rules })
+    }
+
+    /// Generates the `macro_rules!`-macro and its re-export.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] defining the macro such that it has the given visibility.
+    fn generate(self) -> TokenStream {
+        let Self { attrs, docs, cfgs, vis, name, rules } = self;
+        let public: bool = vis.clone().into_iter().count() == 1;
+
+        // Public macros must be exported, which we do under a fresh name to avoid collisions
+        let mut res = TokenStream::new();
+        let source: Ident = if public {
+            res.extend(attrs);
+            res.extend(attribute_with("doc", "hidden"));
+            res.extend(attribute(TokenStream::from(TokenTree::Ident(Ident::new("macro_export", Span::call_site())))));
+            fresh(Some(&name))
+        } else {
+            res.extend(attrs);
+            res.extend(docs.clone());
+            name.clone()
+        };
+        res.extend([
+            TokenTree::Ident(Ident::new("macro_rules", Span::call_site())),
+            TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+            TokenTree::Ident(source.clone()),
+        ]);
+        let brace: bool = rules.delimiter() == Delimiter::Brace;
+        res.extend([TokenTree::Group(rules)]);
+        if !brace {
+            res.extend([TokenTree::Punct(Punct::new(';', Spacing::Alone))]);
+        }
+
+        // Then re-export it with the proper visibility
+        if public {
+            res.extend(cfgs);
+            res.extend(docs);
+            res.extend(attribute_with("doc", "inline"));
+            res.extend(reexport(vis, source, Some(name)));
+        } else if !vis.is_empty() {
+            res.extend(cfgs);
+            // NOTE: Unlike `pub`, `pub(crate)` re-exports that are never used do trigger a warning
+            res.extend(attribute_with("allow", "unused_imports"));
+            res.extend(reexport(vis, source, None));
+        }
+        res
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates an attribute.
+///
+/// # Arguments
+/// - `contents`: The tokens in the attribute (e.g., `doc(hidden)`).
+///
+/// # Returns
+/// A [`TokenStream`] encoding `#[<contents>]`.
+fn attribute(contents: TokenStream) -> TokenStream {
+    TokenStream::from_iter([TokenTree::Punct(Punct::new('#', Spacing::Alone)), TokenTree::Group(Group::new(Delimiter::Bracket, contents))])
+}
+
+/// Generates an attribute with a single argument, like `#[doc(hidden)]`.
+///
+/// # Arguments
+/// - `name`: The name of the attribute (e.g., `doc`).
+/// - `arg`: The argument to the attribute (e.g., `hidden`).
+///
+/// # Returns
+/// A [`TokenStream`] encoding `#[<name>(<arg>)]`.
+fn attribute_with(name: &str, arg: &str) -> TokenStream {
+    attribute(TokenStream::from_iter([
+        TokenTree::Ident(Ident::new(name, Span::call_site())),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::from(TokenTree::Ident(Ident::new(arg, Span::call_site()))))),
+    ]))
+}
+
+/// Generates a `use`-statement that re-exports a macro.
+///
+/// # Arguments
+/// - `vis`: The visibility of the re-export.
+/// - `source`: The name of the macro to re-export.
+/// - `name`: The name to re-export it as, if different from `source`.
+///
+/// # Returns
+/// A [`TokenStream`] encoding `<vis> use <source> (as <name>)?;`.
+fn reexport(vis: TokenStream, source: Ident, name: Option<Ident>) -> TokenStream {
+    let mut res: TokenStream = vis;
+    res.extend([TokenTree::Ident(Ident::new("use", Span::call_site())), TokenTree::Ident(source)]);
+    if let Some(name) = name {
+        res.extend([TokenTree::Ident(Ident::new("as", Span::call_site())), TokenTree::Ident(name)]);
+    }
+    res.extend([TokenTree::Punct(Punct::new(';', Spacing::Alone))]);
+    res
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`pub_macro()`](super::pub_macro())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the `macro_rules!`-macros with their visibilities.
+///
+/// # Returns
+/// A new [`TokenStream`] with the macros and their re-exports.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn pub_macro(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut res = TokenStream::new();
+    let mut iter = input.into_iter().peekable();
+    while iter.peek().is_some() {
+        match Definition::parse(&mut iter) {
+            Ok(def) => res.extend(def.generate()),
+            // NOTE: The error ends up in item position, so needs a semicolon to be a valid item
+            Err(err) => return Err(item_error(err)),
+        }
+    }
+    Ok(res)
+}
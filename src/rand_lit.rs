@@ -0,0 +1,440 @@
+//  RAND LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for generating random literals at compile time.
+//
+
+#[cfg(not(feature = "deterministic"))]
+use std::hash::{BuildHasher as _, Hasher as _, RandomState};
+use std::iter::Peekable;
+use std::str::FromStr as _;
+
+use proc_macro2::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitInt};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, SignedLit, int_lit, parse_lit, parse_signed_lit};
+
+
+/***** CONSTANTS *****/
+/// The maximum length of generated byte arrays.
+const MAX_LEN: usize = 65536;
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// A small, seedable pseudo-random number generator (SplitMix64).
+///
+/// This is not cryptographically secure, but it is fast, has good statistical properties and,
+/// crucially, produces the same output for the same seed on every platform.
+struct Rng(u64);
+impl Rng {
+    /// Generates the next 64 random bits.
+    ///
+    /// # Returns
+    /// A random [`u64`].
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z: u64 = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generates the next 128 random bits.
+    ///
+    /// # Returns
+    /// A random [`u128`].
+    fn next_u128(&mut self) -> u128 { ((self.next_u64() as u128) << 64) | self.next_u64() as u128 }
+
+    /// Generates a uniformly random number below some bound.
+    ///
+    /// # Arguments
+    /// - `n`: The (exclusive) upper bound. Must be non-zero.
+    ///
+    /// # Returns
+    /// A random [`u128`] in `[0, n)`.
+    fn below(&mut self, n: u128) -> u128 {
+        // Reject the values in the last, incomplete multiple of `n` to avoid bias
+        let limit: u128 = u128::MAX - u128::MAX % n;
+        loop {
+            let x: u128 = self.next_u128();
+            if x < limit {
+                return x % n;
+            }
+        }
+    }
+
+    /// Generates a uniformly random float in `[0, 1)`.
+    ///
+    /// # Returns
+    /// A random [`f64`].
+    fn unit_f64(&mut self) -> f64 { (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 }
+}
+
+
+
+/// Converts an integer value to its two's complement encoding, offset such that the type's
+/// minimum value is `0`.
+///
+/// # Arguments
+/// - `ty`: The [`IntType`] of the value.
+/// - `neg`: Whether the value is negative.
+/// - `abs`: The absolute value of the value.
+///
+/// # Returns
+/// The offset value.
+fn bias(ty: IntType, neg: bool, abs: u128) -> u128 {
+    let value: u128 = if neg { abs.wrapping_neg() } else { abs };
+    value.wrapping_sub(type_min(ty))
+}
+
+/// Inverse of [`bias()`].
+///
+/// # Arguments
+/// - `ty`: The [`IntType`] of the value.
+/// - `biased`: The offset value.
+///
+/// # Returns
+/// A tuple of whether the value is negative and its absolute value.
+fn unbias(ty: IntType, biased: u128) -> (bool, u128) {
+    let value: u128 = biased.wrapping_add(type_min(ty));
+    if ty.is_signed() && (value as i128) < 0 { (true, (value as i128).unsigned_abs()) } else { (false, value) }
+}
+
+/// Returns the minimum value of an integer type in wrapping two's complement.
+///
+/// # Arguments
+/// - `ty`: The [`IntType`] to find the minimum of.
+///
+/// # Returns
+/// The minimum value as a [`u128`].
+fn type_min(ty: IntType) -> u128 { if ty.is_signed() { (1u128 << (ty.bits() - 1)).wrapping_neg() } else { 0 } }
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a parsed range of literals.
+struct Range {
+    /// The start of the range.
+    start: SignedLit,
+    /// The end of the range.
+    end:   SignedLit,
+    /// Whether the end is inclusive.
+    incl:  bool,
+    /// The span of the start of the range.
+    span:  Span,
+}
+impl Range {
+    /// Parses an optional `in start..end` or `in start..=end`.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Range, or [`None`] if the head of the input was not `in`.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input was `in` but not followed by a valid range.
+    fn parse_opt(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Option<Self>, Diagnostic2> {
+        let in_span: Span = match iter.peek() {
+            Some(TokenTree::Ident(ident)) if ident == "in" => ident.span(),
+            _ => return Ok(None),
+        };
+        iter.next();
+
+        // Parse the range itself
+        let start: SignedLit = parse_signed_lit(iter)?;
+        let span: Span = start.lit.span();
+        for _ in 0..2 {
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '.' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected either '..' or '..='")),
+                None => return Err(error2(in_span, "Expected either '..' or '..='")),
+            }
+        }
+        let incl: bool = matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=');
+        if incl {
+            iter.next();
+        }
+        let end: SignedLit = parse_signed_lit(iter)?;
+        Ok(Some(Self { start, end, incl, span }))
+    }
+}
+
+
+
+/// Defines what kind of literal to generate.
+enum Kind {
+    /// An integer of the given type, between the given biased bounds (inclusive).
+    Int(IntType, u128, u128),
+    /// A float of the given type (`f32` or `f64`), between the given bounds (exclusive end).
+    Float(&'static str, f64, f64),
+    /// An array of bytes with the given length.
+    Bytes(usize),
+}
+
+
+
+/// Defines the parsed input to the macro.
+struct Input {
+    /// What to generate.
+    kind: Kind,
+    /// The seed to use, if any.
+    seed: Option<u64>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the type and its optional range
+        let kind: Kind = match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "f32" || ident == "f64" => {
+                let suffix: &'static str = if ident == "f32" { "f32" } else { "f64" };
+                match Range::parse_opt(&mut iter)? {
+                    Some(Range { incl: true, span, .. }) => return Err(error2(span, "Float ranges must be exclusive (i.e., use '..')")),
+                    Some(Range { start, end, span, .. }) => {
+                        let (start, end): (f64, f64) = (Self::float(start)?, Self::float(end)?);
+                        if start >= end {
+                            return Err(error2(span, "Range is empty"));
+                        }
+                        Kind::Float(suffix, start, end)
+                    },
+                    None => Kind::Float(suffix, 0.0, 1.0),
+                }
+            },
+            Some(TokenTree::Ident(ident)) => {
+                let Some(ty) = IntType::from_suffix(&ident.to_string()) else {
+                    return Err(error2(ident.span(), "Expected an integer type, `f32`, `f64` or `[u8; N]`"));
+                };
+                match Range::parse_opt(&mut iter)? {
+                    Some(Range { start, end, incl, span }) => {
+                        let (start, end): (u128, u128) = (Self::int(ty, start)?, Self::int(ty, end)?);
+                        match (incl, end.checked_sub(start)) {
+                            (true, Some(_)) => Kind::Int(ty, start, end),
+                            (false, Some(n)) if n > 0 => Kind::Int(ty, start, end - 1),
+                            _ => return Err(error2(span, "Range is empty")),
+                        }
+                    },
+                    None if matches!(ty, IntType::ISize | IntType::USize) => {
+                        return Err(error2(
+                            ident.span(),
+                            &format!("Cannot generate any `{ident}` because its width is platform-dependent; give a range using `in` instead"),
+                        ));
+                    },
+                    None => Kind::Int(ty, 0, if ty.bits() == 128 { u128::MAX } else { (1 << ty.bits()) - 1 }),
+                }
+            },
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => Kind::Bytes(Self::parse_array(group)?),
+            Some(tt) => return Err(error2(tt.span(), "Expected an integer type, `f32`, `f64` or `[u8; N]`")),
+            None => return Err(error2(Span::call_site(), "Expected an integer type, `f32`, `f64` or `[u8; N]`")),
+        };
+
+        // Parse the optional seed
+        let mut seed: Option<u64> = None;
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing after the type")),
+            None => return Ok(Self { kind, seed }),
+        }
+        if iter.peek().is_some() {
+            seed = Some(Self::parse_seed(&mut iter)?);
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing after the seed")),
+                None => {},
+            }
+        }
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the seed"));
+        }
+        Ok(Self { kind, seed })
+    }
+
+    /// Parses the contents of a `[u8; N]`-type.
+    ///
+    /// # Arguments
+    /// - `group`: The bracketed [`Group`] to parse.
+    ///
+    /// # Returns
+    /// The length of the array.
+    ///
+    /// # Errors
+    /// This function errors if the group did not contain `u8; N`.
+    fn parse_array(group: Group) -> Result<usize, Diagnostic2> {
+        let mut iter = group.stream().into_iter();
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "u8" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected 'u8' (only byte arrays are supported)")),
+            None => return Err(error2(group.span(), "Expected 'u8'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ';' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ';'")),
+            None => return Err(error2(group.span_close(), "Expected ';'")),
+        }
+        let lit: LitInt = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Int(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected the array length as an integer literal")),
+            None => return Err(error2(group.span_close(), "Expected the array length as an integer literal")),
+        };
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the array length"));
+        }
+        match lit.base10_parse::<usize>() {
+            Ok(len) if len <= MAX_LEN => Ok(len),
+            _ => Err(error2(lit.span(), &format!("Array length may be at most {MAX_LEN}"))),
+        }
+    }
+
+    /// Parses a `seed = ...`-pair.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed seed.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input did not encode a valid seed.
+    fn parse_seed(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<u64, Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "seed" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected 'seed'")),
+            None => return Err(error2(Span::mixed_site(), "Expected 'seed'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+            None => return Err(error2(Span::mixed_site(), "Expected '='")),
+        }
+        let lit: LitInt = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Int(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected the seed as an integer literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected the seed as an integer literal")),
+        };
+        lit.base10_parse().map_err(|_| error2(lit.span(), "Seed must fit in a `u64`"))
+    }
+
+    /// Converts a range bound to a biased integer value.
+    ///
+    /// # Arguments
+    /// - `ty`: The [`IntType`] of the range.
+    /// - `lit`: The [`SignedLit`] to convert.
+    ///
+    /// # Returns
+    /// The value, as given by [`bias()`].
+    ///
+    /// # Errors
+    /// This function errors if the literal was not an integer or doesn't fit in the type.
+    fn int(ty: IntType, SignedLit { neg, lit }: SignedLit) -> Result<u128, Diagnostic2> {
+        let Lit::Int(lit) = lit else {
+            return Err(error2(lit.span(), "Expected an integer literal"));
+        };
+        let abs: u128 = lit.base10_parse().map_err(|_| error2(lit.span(), "Integer literal is too large"))?;
+        if !ty.fits(neg, abs) {
+            return Err(error2(lit.span(), &format!("Integer literal does not fit in a `{}`", ty.suffix())));
+        }
+        Ok(bias(ty, neg, abs))
+    }
+
+    /// Converts a range bound to a float value.
+    ///
+    /// # Arguments
+    /// - `lit`: The [`SignedLit`] to convert.
+    ///
+    /// # Returns
+    /// The value as an [`f64`].
+    ///
+    /// # Errors
+    /// This function errors if the literal was not a float or integer.
+    fn float(SignedLit { neg, lit }: SignedLit) -> Result<f64, Diagnostic2> {
+        let value: f64 = match lit {
+            Lit::Float(lit) => lit.base10_parse().map_err(|_| error2(lit.span(), "Invalid floating-point literal"))?,
+            Lit::Int(lit) => lit.base10_parse::<u128>().map_err(|_| error2(lit.span(), "Integer literal is too large"))? as f64,
+            lit => return Err(error2(lit.span(), "Expected a floating-point or integer literal")),
+        };
+        Ok(if neg { -value } else { value })
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`rand_lit()`](super::rand_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the type of literal to generate.
+///
+/// # Returns
+/// A new [`TokenStream`] with a random literal (or an array of them).
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn rand_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { kind, seed } = Input::parse(input)?;
+    #[cfg(not(feature = "deterministic"))]
+    let mut rng = Rng(seed.unwrap_or_else(|| RandomState::new().build_hasher().finish()));
+    #[cfg(feature = "deterministic")]
+    let mut rng = Rng(seed.unwrap_or_else(crate::utils::call_site_seed));
+
+    // Generate the literal(s). Note we use the call site span, as they don't appear in the input.
+    match kind {
+        Kind::Int(ty, start, end) => {
+            let biased: u128 = if end - start == u128::MAX { rng.next_u128() } else { start + rng.below(end - start + 1) };
+            let (neg, abs): (bool, u128) = unbias(ty, biased);
+            Ok(int_lit(neg, abs, Some(ty), Span::call_site()))
+        },
+        Kind::Float(suffix, start, end) => {
+            // Due to rounding, the result might still hit the end; clamp it to the largest value
+            // below it in that case
+            let mut value: f64 = start + (end - start) * rng.unit_f64();
+            if value >= end {
+                value = end.next_down();
+            }
+            let abs: String = if suffix == "f32" {
+                let mut value: f32 = value as f32;
+                if value as f64 >= end {
+                    value = (end as f32).next_down();
+                }
+                format!("{:?}", value.abs())
+            } else {
+                format!("{:?}", value.abs())
+            };
+
+            let mut res = TokenStream::new();
+            if value.is_sign_negative() {
+                res.extend([TokenTree::Punct(Punct::new('-', Spacing::Alone))]);
+            }
+            res.extend([TokenTree::Literal(Literal::from_str(&format!("{abs}{suffix}")).unwrap())]);
+            Ok(res)
+        },
+        Kind::Bytes(len) => {
+            let mut bytes = TokenStream::new();
+            for i in 0..len {
+                if i > 0 {
+                    bytes.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+                }
+                bytes.extend([TokenTree::Literal(Literal::u8_suffixed(rng.next_u64() as u8))]);
+            }
+            Ok(TokenStream::from(TokenTree::Group(Group::new(Delimiter::Bracket, bytes))))
+        },
+    }
+}
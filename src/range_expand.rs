@@ -0,0 +1,232 @@
+//  RANGE EXPAND.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for expanding integer ranges into lists of literals.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitInt};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, SignedLit, int_lit, parse_lit, parse_signed_lit};
+use crate::utils::Callback;
+
+
+/***** CONSTANTS *****/
+/// The maximum number of literals that the macro will generate.
+const MAX_LEN: u128 = 65536;
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The first value in the range.
+    start:    i128,
+    /// The end of the range.
+    end:      i128,
+    /// Whether the end is inclusive.
+    incl:     bool,
+    /// The step between values.
+    step:     u128,
+    /// The type of the generated literals, if any.
+    ty:       Option<IntType>,
+    /// The span of the range.
+    span:     Span,
+    /// The callback to pass the values to, if any.
+    callback: Option<Callback>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the start, the range operator and the end
+        let (start, start_lit): (i128, LitInt) = Self::parse_int(&mut iter)?;
+        let span: Span = start_lit.span();
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '.' && p.spacing() == Spacing::Joint => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either '..' or '..='")),
+            None => return Err(error2(span, "Expected either '..' or '..='")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '.' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either '..' or '..='")),
+            None => return Err(error2(span, "Expected either '..' or '..='")),
+        }
+        let incl: bool = matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=');
+        if incl {
+            iter.next();
+        }
+        let (end, end_lit): (i128, LitInt) = Self::parse_int(&mut iter)?;
+
+        // Resolve the type from the suffixes
+        let ty: Option<IntType> = match (start_lit.suffix(), end_lit.suffix()) {
+            ("", "") => None,
+            (suffix, "") | ("", suffix) => Some(Self::parse_suffix(suffix, if start_lit.suffix().is_empty() { end_lit.span() } else { span })?),
+            (lsuffix, rsuffix) if lsuffix == rsuffix => Some(Self::parse_suffix(lsuffix, span)?),
+            (lsuffix, rsuffix) => return Err(error2(end_lit.span(), &format!("End has suffix `{rsuffix}`, but the start has suffix `{lsuffix}`"))),
+        };
+        if let Some(ty) = ty {
+            for (value, span) in [(start, span), (end, end_lit.span())] {
+                if !ty.fits(value < 0, value.unsigned_abs()) {
+                    return Err(error2(span, &format!("Integer literal does not fit in a `{}`", ty.suffix())));
+                }
+            }
+        }
+
+        // Parse the optional step
+        let mut step: u128 = 1;
+        if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+            iter.next();
+            step = Self::parse_step(&mut iter)?;
+        }
+
+        // Finally, parse the optional callback
+        let callback: Option<Callback> = match iter.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => Callback::parse_opt(&mut iter)?,
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',', '=>' or nothing after the range")),
+            None => None,
+        };
+        Ok(Self { start, end, incl, step, ty, span, callback })
+    }
+
+    /// Parses an optionally negated integer literal.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// A tuple of the value of the literal and the literal itself.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input was not an integer literal.
+    fn parse_int(iter: &mut impl Iterator<Item = TokenTree>) -> Result<(i128, LitInt), Diagnostic2> {
+        let SignedLit { neg, lit } = parse_signed_lit(iter)?;
+        let Lit::Int(lit) = lit else {
+            return Err(error2(lit.span(), "Expected an integer literal"));
+        };
+        let abs: u128 = lit.base10_parse().map_err(|_| error2(lit.span(), "Integer literal is too large"))?;
+        let value: Option<i128> = if neg { 0i128.checked_sub_unsigned(abs) } else { i128::try_from(abs).ok() };
+        match value {
+            Some(value) => Ok((value, lit)),
+            None => Err(error2(lit.span(), "Integer literal is too large")),
+        }
+    }
+
+    /// Parses the suffix of the bounds.
+    ///
+    /// # Arguments
+    /// - `suffix`: The suffix to parse.
+    /// - `span`: The [`Span`] to report errors at.
+    ///
+    /// # Returns
+    /// The [`IntType`] of the suffix.
+    ///
+    /// # Errors
+    /// This function errors if the suffix was not that of an integer type.
+    fn parse_suffix(suffix: &str, span: Span) -> Result<IntType, Diagnostic2> {
+        IntType::from_suffix(suffix).ok_or_else(|| error2(span, &format!("Unknown integer suffix `{suffix}`")))
+    }
+
+    /// Parses a `step = ...`-pair.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed step.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input did not encode a valid step.
+    fn parse_step(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<u128, Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "step" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected 'step'")),
+            None => return Err(error2(Span::mixed_site(), "Expected 'step'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' && p.spacing() == Spacing::Alone => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+            None => return Err(error2(Span::mixed_site(), "Expected '='")),
+        }
+        let lit: LitInt = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Int(lit)) => lit,
+            Some(lit) => return Err(error2(lit.span(), "Expected an integer literal")),
+            None => return Err(error2(Span::mixed_site(), "Expected an integer literal")),
+        };
+        match lit.base10_parse::<u128>() {
+            Ok(step) if step > 0 => Ok(step),
+            _ => Err(error2(lit.span(), "Expected a positive step")),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`range_expand()`](super::range_expand())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the range to expand.
+///
+/// # Returns
+/// A new [`TokenStream`] with an array of integer literals, or a call to the given callback with
+/// a comma-separated list of them.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the range is too
+/// large.
+pub fn range_expand(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { start, end, incl, step, ty, span, callback } = Input::parse(input)?;
+
+    // Compute the number of values
+    let len: u128 = if start > end || (start == end && !incl) {
+        0
+    } else {
+        // The distance fits in a u128 because both ends fit in an i128
+        let dist: u128 = end.abs_diff(start) - if incl { 0 } else { 1 };
+        dist / step + 1
+    };
+    if len > MAX_LEN {
+        return Err(error2(span, &format!("Range expands to {len} literals, but at most {MAX_LEN} are supported")));
+    }
+
+    // Generate them
+    let mut res = TokenStream::new();
+    for i in 0..len {
+        if i > 0 {
+            let mut comma = Punct::new(',', Spacing::Alone);
+            comma.set_span(span);
+            res.extend([TokenTree::Punct(comma)]);
+        }
+        // Won't overflow, as the value is between `start` and `end`. Also note we use the call
+        // site span, as the literals don't appear in the input as such.
+        let value: i128 = start.wrapping_add_unsigned(i * step);
+        res.extend(int_lit(value < 0, value.unsigned_abs(), ty, Span::call_site()));
+    }
+    match callback {
+        Some(callback) => Ok(callback.call(res)),
+        None => {
+            let mut group = Group::new(Delimiter::Bracket, res);
+            group.set_span(span);
+            Ok(TokenStream::from(TokenTree::Group(group)))
+        },
+    }
+}
@@ -0,0 +1,47 @@
+//  REGEX CHECK.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for validating regular expressions at compile time.
+//
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use regex_syntax::Parser;
+use syn::{Lit, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::parse_lit;
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`regex_check()`](super::regex_check())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to check.
+///
+/// # Returns
+/// The input literal, unchanged.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the literal is not a
+/// valid regular expression.
+pub fn regex_check(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Parse the literal
+    let mut iter = input.into_iter();
+    let tree: TokenTree = iter.next().ok_or_else(|| error2(Span::mixed_site(), "Expected a string literal"))?;
+    if let Some(tree) = iter.next() {
+        return Err(error2(tree.span(), "Expected nothing after the string literal"));
+    }
+    let lit: LitStr = match parse_lit(tree)? {
+        Lit::Str(lit) => lit,
+        lit => return Err(error2(lit.span(), "Expected a string literal")),
+    };
+
+    // Check it
+    if let Err(err) = Parser::new().parse(&lit.value()) {
+        return Err(error2(lit.span(), &format!("Invalid regular expression: {err}")));
+    }
+
+    // Emit it unchanged
+    Ok(TokenStream::from(TokenTree::Literal(lit.token())))
+}
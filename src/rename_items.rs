@@ -0,0 +1,413 @@
+//  RENAME ITEMS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides attributes for renaming all items in a module at once.
+//
+
+use std::collections::HashMap;
+
+use macro_toolkit_core::idents::paste_ident;
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::generics::split_top_level;
+use crate::items::{ItemHead, parse_head};
+
+
+/***** CONSTANTS *****/
+/// The keywords of items that are renamed.
+const RENAMED: [&str; 10] = ["const", "enum", "fn", "mod", "static", "static mut", "struct", "trait", "type", "union"];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Splits the body of a module into its items.
+///
+/// # Arguments
+/// - `body`: The tokens in the module.
+///
+/// # Returns
+/// The tokens of every item in the body.
+fn split_items(body: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut items: Vec<Vec<TokenTree>> = Vec::new();
+    let mut item: Vec<TokenTree> = Vec::new();
+    for tt in body {
+        // Items like `const` and `static` only end at a semicolon, as their value may contain
+        // curly brackets; the rest ends at their body (or a semicolon if they have none)
+        let ends: bool = match &tt {
+            TokenTree::Punct(p) => p.as_char() == ';',
+            TokenTree::Group(group) => group.delimiter() == Delimiter::Brace && !until_semicolon(&item),
+            _ => false,
+        };
+        item.push(tt);
+        if ends {
+            items.push(std::mem::take(&mut item));
+        }
+    }
+    if !item.is_empty() {
+        items.push(item);
+    }
+    items
+}
+
+/// Checks whether an item only ends at a semicolon, based on its first tokens.
+///
+/// # Arguments
+/// - `item`: The tokens of the item seen so far.
+///
+/// # Returns
+/// True if the item is a `const`, `static`, `type`, `use` or `extern crate`, or false otherwise.
+fn until_semicolon(item: &[TokenTree]) -> bool {
+    let mut idents = item.iter().filter_map(|tt| match tt {
+        TokenTree::Ident(ident) => Some(ident.to_string()),
+        _ => None,
+    });
+    while let Some(ident) = idents.next() {
+        match ident.as_str() {
+            "pub" | "unsafe" => continue,
+            "const" => return idents.next().is_some_and(|next| !matches!(next.as_str(), "fn" | "async" | "unsafe" | "extern")),
+            "extern" => return idents.next().is_some_and(|next| next == "crate"),
+            "static" | "type" | "use" => return true,
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// Replaces all references to renamed items in the given tokens.
+///
+/// Identifiers are left alone if they are preceded by `.` (field or method accesses), preceded by
+/// `::` other than in `self::` (items of other modules or types), or followed by a single `:`
+/// (field names).
+///
+/// # Arguments
+/// - `tokens`: The tokens to replace references in.
+/// - `names`: A map from old names to new names.
+///
+/// # Returns
+/// The same tokens, but with the references replaced.
+fn substitute(tokens: TokenStream, names: &HashMap<String, Ident>) -> TokenStream {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let is_punct = |i: usize, c: char| -> bool { matches!(tokens.get(i), Some(TokenTree::Punct(p)) if p.as_char() == c) };
+    let mut res = TokenStream::new();
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Ident(ident) => {
+                let after_dot: bool = i >= 1 && is_punct(i - 1, '.') && !(i >= 2 && is_punct(i - 2, '.'));
+                let after_path: bool =
+                    i >= 2 && is_punct(i - 1, ':') && is_punct(i - 2, ':') && !(i >= 3 && matches!(&tokens[i - 3], TokenTree::Ident(prev) if prev == "self"));
+                let field: bool = is_punct(i + 1, ':') && !is_punct(i + 2, ':');
+                match names.get(&ident.to_string()) {
+                    Some(new) if !after_dot && !after_path && !field => {
+                        res.extend([TokenTree::Ident(Ident::new(&new.to_string(), ident.span()))]);
+                    },
+                    _ => res.extend([tt.clone()]),
+                }
+            },
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), substitute(group.stream(), names));
+                new.set_span(group.span());
+                res.extend([TokenTree::Group(new)]);
+            },
+            tt => res.extend([tt.clone()]),
+        }
+    }
+    res
+}
+
+/// Collects the names of all items in a module that should be renamed.
+///
+/// # Arguments
+/// - `items`: The items in the module.
+///
+/// # Returns
+/// The names of the items, including those in `extern`-blocks, in order of definition.
+fn collect_names(items: &[Vec<TokenTree>]) -> Vec<Ident> {
+    let mut names: Vec<Ident> = Vec::new();
+    for item in items {
+        let Ok(head) = parse_head(item.iter().cloned().collect()) else { continue };
+        if let Some(block) = extern_block(&head) {
+            names.extend(collect_names(&split_items(block.stream())));
+        } else if let Some(name) = head.name
+            && RENAMED.contains(&head.keyword.to_string().as_str())
+        {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Returns the body of an item if it is an `extern`-block.
+///
+/// # Arguments
+/// - `head`: The head of the item.
+///
+/// # Returns
+/// The [`Group`] with the items in the block, or [`None`] if this is not an `extern`-block.
+fn extern_block(head: &ItemHead) -> Option<&Group> {
+    match (head.keyword.clone().into_iter().next(), head.rest.as_slice()) {
+        (Some(TokenTree::Ident(kw)), [TokenTree::Group(group)]) if kw == "extern" && head.name.is_none() && group.delimiter() == Delimiter::Brace => {
+            Some(group)
+        },
+        _ => None,
+    }
+}
+
+/// Renames the items in (the body of) a module.
+///
+/// # Arguments
+/// - `items`: The items in the module.
+/// - `names`: A map from old names to new names.
+/// - `foreign`: Whether these are items of an `extern`-block, which get a `#[link_name]` to keep
+///   linking to the original symbol.
+/// - `aliases`: If given, collects `use`-statements that alias the new names to the old ones.
+///
+/// # Returns
+/// The renamed items.
+fn rename(items: Vec<Vec<TokenTree>>, names: &HashMap<String, Ident>, foreign: bool, aliases: &mut Option<TokenStream>) -> TokenStream {
+    let mut res = TokenStream::new();
+    for item in items {
+        // Items we can't parse (e.g., macro calls) only have their references replaced
+        let Ok(head) = parse_head(item.iter().cloned().collect()) else {
+            res.extend(substitute(item.into_iter().collect(), names));
+            continue;
+        };
+        if let Some(block) = extern_block(&head) {
+            let mut new = Group::new(Delimiter::Brace, rename(split_items(block.stream()), names, true, aliases));
+            new.set_span(block.span());
+            res.extend(substitute(head.attrs, names));
+            res.extend(head.vis);
+            res.extend(head.qualifiers);
+            res.extend(head.keyword);
+            res.extend([TokenTree::Group(new)]);
+            continue;
+        }
+
+        // Find the new name of this item
+        let kind: String = head.keyword.to_string();
+        let new: Option<&Ident> = head.name.as_ref().filter(|_| RENAMED.contains(&kind.as_str())).and_then(|name| names.get(&name.to_string()));
+        let (Some(name), Some(new)) = (&head.name, new) else {
+            res.extend(substitute(item.into_iter().collect(), names));
+            continue;
+        };
+
+        // Keep linking to the original symbol for foreign functions and statics
+        if foreign && (kind == "fn" || kind.starts_with("static")) && !head.attrs.to_string().contains("link_name") {
+            let attr = TokenStream::from_iter([
+                TokenTree::Ident(Ident::new("link_name", name.span())),
+                TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+                TokenTree::Literal(Literal::string(&name.to_string())),
+            ]);
+            res.extend([TokenTree::Punct(Punct::new('#', Spacing::Alone)), TokenTree::Group(Group::new(Delimiter::Bracket, attr))]);
+        }
+
+        // Generate the alias, if requested
+        if let Some(aliases) = aliases {
+            aliases.extend(TokenStream::from_iter([
+                TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+                TokenTree::Group(Group::new(
+                    Delimiter::Bracket,
+                    TokenStream::from_iter([
+                        TokenTree::Ident(Ident::new("allow", Span::call_site())),
+                        TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenTree::Ident(Ident::new("unused_imports", Span::call_site())).into())),
+                    ]),
+                )),
+            ]));
+            aliases.extend(head.vis.clone());
+            aliases.extend([
+                TokenTree::Ident(Ident::new("use", name.span())),
+                TokenTree::Ident(Ident::new("self", name.span())),
+                TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+                TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                TokenTree::Ident(new.clone()),
+                TokenTree::Ident(Ident::new("as", name.span())),
+                TokenTree::Ident(name.clone()),
+                TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+            ]);
+        }
+
+        // Rebuild the item with its new name
+        res.extend(substitute(head.attrs, names));
+        res.extend(head.vis);
+        res.extend(head.qualifiers);
+        res.extend(head.keyword);
+        res.extend([TokenTree::Ident(Ident::new(&new.to_string(), name.span()))]);
+        res.extend(substitute(head.rest.into_iter().collect(), names));
+    }
+    res
+}
+
+
+
+/// Parses the arguments to one of the attributes.
+///
+/// Every argument is either `$key:ident = $($value:tt)*` or `alias`. The first argument may also
+/// be a value without key, in which case it is given the key `default`.
+///
+/// # Arguments
+/// - `attr`: The tokens given to the attribute.
+///
+/// # Returns
+/// A list of keys and values, in order, and whether `alias` was given.
+///
+/// # Errors
+/// This function errors if an argument is empty or `alias` is given more than once.
+fn parse_args(attr: TokenStream) -> Result<(Vec<(Ident, TokenStream)>, bool), Diagnostic2> {
+    let mut args: Vec<(Ident, TokenStream)> = Vec::new();
+    let mut alias: bool = false;
+    let chunks: Vec<Vec<TokenTree>> = split_top_level(attr, ',');
+    let n: usize = chunks.len();
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        match chunk.as_slice() {
+            [] if i + 1 == n && i > 0 => {},
+            [] => return Err(error2(Span::call_site(), "Expected an argument, not an empty one")),
+            [TokenTree::Ident(ident)] if ident == "alias" => {
+                if alias {
+                    return Err(error2(ident.span(), "Duplicate argument `alias`"));
+                }
+                alias = true;
+            },
+            [TokenTree::Ident(key), TokenTree::Punct(p), value @ ..] if p.as_char() == '=' => {
+                if value.is_empty() {
+                    return Err(error2(p.span(), "Expected a value after '='"));
+                }
+                args.push((key.clone(), value.iter().cloned().collect()));
+            },
+            [first, ..] if i == 0 => args.push((Ident::new("default", first.span()), chunk.into_iter().collect())),
+            [first, ..] => return Err(error2(first.span(), "Expected either `alias` or `$key = $value`")),
+        }
+    }
+    Ok((args, alias))
+}
+
+/// Applies a renaming to a module.
+///
+/// # Arguments
+/// - `item`: The module to rename the items of.
+/// - `new_name`: A closure that returns the new name of an item given its old one, or [`None`] if
+///   it should keep its name.
+/// - `alias`: Whether to emit aliases for the old names.
+///
+/// # Returns
+/// The module with its items renamed.
+///
+/// # Errors
+/// This function errors if the item is not an inline module, or if any of the new names are
+/// invalid.
+fn apply(item: TokenStream, mut new_name: impl FnMut(&Ident) -> Result<Option<Ident>, Diagnostic2>, alias: bool) -> Result<TokenStream, Diagnostic2> {
+    let head: ItemHead = parse_head(item)?;
+    let body: Group = match (head.keyword.to_string().as_str(), head.rest.as_slice()) {
+        ("mod", [TokenTree::Group(group)]) if group.delimiter() == Delimiter::Brace => group.clone(),
+        _ => {
+            let span: Span = head.keyword.into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+            return Err(error2(span, "Expected an inline module (e.g., `mod foo { ... }`)"));
+        },
+    };
+
+    // Find the new names of all items
+    let items: Vec<Vec<TokenTree>> = split_items(body.stream());
+    let mut names: HashMap<String, Ident> = HashMap::new();
+    for name in collect_names(&items) {
+        if let Some(new) = new_name(&name)? {
+            names.insert(name.to_string(), new);
+        }
+    }
+
+    // Rename them and rebuild the module
+    let mut aliases: Option<TokenStream> = if alias { Some(TokenStream::new()) } else { None };
+    let mut contents: TokenStream = rename(items, &names, false, &mut aliases);
+    contents.extend(aliases);
+    let mut new = Group::new(Delimiter::Brace, contents);
+    new.set_span(body.span());
+    let mut res: TokenStream = head.attrs;
+    res.extend(head.vis);
+    res.extend(head.keyword);
+    res.extend(head.name.map(TokenTree::Ident));
+    res.extend([TokenTree::Group(new)]);
+    Ok(res)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`prefix_items()`](super::prefix_items())-attribute.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the prefix and/or suffix.
+/// - `item`: Some [`TokenStream`] containing the module to rename the items of.
+///
+/// # Returns
+/// A new [`TokenStream`] with the same module, but with the prefix and suffix added to the names of
+/// all its items.
+///
+/// # Errors
+/// This function may error if the input is not valid for this attribute, or if any of the new
+/// names are not valid identifiers.
+pub fn prefix_items(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (args, alias): (Vec<(Ident, TokenStream)>, bool) = parse_args(attr)?;
+    let mut prefix: Option<TokenStream> = None;
+    let mut suffix: Option<TokenStream> = None;
+    for (key, value) in args {
+        let slot: &mut Option<TokenStream> = match key.to_string().as_str() {
+            "default" | "prefix" => &mut prefix,
+            "suffix" => &mut suffix,
+            _ => return Err(error2(key.span(), &format!("Unknown argument `{key}`; expected one of `prefix`, `suffix` or `alias`"))),
+        };
+        if slot.replace(value).is_some() {
+            return Err(error2(key.span(), &format!("Duplicate argument `{key}`")));
+        }
+    }
+    if prefix.is_none() && suffix.is_none() {
+        return Err(error2(Span::call_site(), "Expected at least a prefix or a suffix (e.g., `#[prefix_items(ffi_)]`)"));
+    }
+
+    // Paste them around every name
+    apply(
+        item,
+        |name: &Ident| {
+            let mut tokens: TokenStream = prefix.clone().unwrap_or_default();
+            tokens.extend([TokenTree::Ident(name.clone())]);
+            tokens.extend(suffix.clone());
+            paste_ident(tokens, name.span()).map(Some)
+        },
+        alias,
+    )
+}
+
+/// Defines the implementation of the [`rename_items()`](super::rename_items())-attribute.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the map of old names to new names.
+/// - `item`: Some [`TokenStream`] containing the module to rename the items of.
+///
+/// # Returns
+/// A new [`TokenStream`] with the same module, but with the given items renamed.
+///
+/// # Errors
+/// This function may error if the input is not valid for this attribute, if any of the new names
+/// are not valid identifiers, or if any of the old names are not items in the module.
+pub fn rename_items(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (args, alias): (Vec<(Ident, TokenStream)>, bool) = parse_args(attr)?;
+    let mut map: HashMap<String, (Ident, Ident)> = HashMap::new();
+    for (key, value) in args {
+        if key == "default" {
+            return Err(error2(key.span(), "Expected `$old = $new`"));
+        }
+        let new: Ident = paste_ident(value, key.span())?;
+        if map.insert(key.to_string(), (key.clone(), new)).is_some() {
+            return Err(error2(key.span(), &format!("Item `{key}` is renamed more than once")));
+        }
+    }
+
+    // Rename the items in the map, then check we've seen all of them
+    let res: TokenStream = apply(item, |name: &Ident| Ok(map.remove(&name.to_string()).map(|(_, new)| new)), alias)?;
+    if let Some((key, _)) = map.into_values().min_by_key(|(key, _)| key.to_string()) {
+        return Err(error2(key.span(), &format!("Unknown item `{key}`; the module does not define it")));
+    }
+    Ok(res)
+}
@@ -0,0 +1,131 @@
+//  RESPAN.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for changing the spans (and thereby the hygiene) of tokens.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error, parse_anchor};
+
+
+/***** TOKEN PARSING *****/
+/// Parses the span to give tokens.
+///
+/// This is either `call_site`, `mixed_site` or any other token, in which case its span is used,
+/// optionally preceded by `to:`.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The parsed [`Span`].
+///
+/// # Errors
+/// This function errors if the input was empty.
+fn parse_span(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Span, Diagnostic2> {
+    let err = || error2(Span::call_site(), "Expected either 'call_site', 'mixed_site' or a token to take the span of");
+    match iter.next() {
+        // Skip the optional `to:`
+        Some(TokenTree::Ident(ident))
+            if ident == "to" && matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ':' && p.spacing() == Spacing::Alone) =>
+        {
+            iter.next();
+            iter.next().map(parse_anchor).ok_or_else(err)
+        },
+        Some(tt) => Ok(parse_anchor(tt)),
+        None => Err(err()),
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Sets the span of every token in a [`TokenStream`], recursing into groups.
+///
+/// # Arguments
+/// - `tokens`: The [`TokenStream`] to respan.
+/// - `span`: The [`Span`] to give all tokens.
+///
+/// # Returns
+/// The same tokens, but with their spans replaced.
+fn set_spans(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), set_spans(group.stream(), span));
+                new_group.set_span(span);
+                TokenTree::Group(new_group)
+            },
+            mut tt => {
+                tt.set_span(span);
+                tt
+            },
+        })
+        .collect()
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`respan()`](super::respan())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the span to use and the tokens to respan.
+///
+/// # Returns
+/// A new [`TokenStream`] with the tokens, but with their spans replaced.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn respan(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+    let span: Span = parse_span(&mut iter)?;
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+        None => return Err(error2(Span::call_site(), "Expected ',' and tokens in curly brackets")),
+    }
+    let tokens: Group = match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(tt) => return Err(error2(tt.span(), "Expected tokens in curly brackets")),
+        None => return Err(error2(Span::call_site(), "Expected tokens in curly brackets")),
+    };
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the tokens"));
+    }
+    Ok(set_spans(tokens.stream(), span))
+}
+
+/// Defines the implementation of the [`with_span()`](super::with_span())-attribute.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the span to use.
+/// - `item`: Some [`TokenStream`] containing the item to respan.
+///
+/// # Returns
+/// A new [`TokenStream`] with the item, but with its spans replaced.
+///
+/// # Errors
+/// This function may error if the attribute's input is not valid.
+pub fn with_span(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = attr.into_iter().peekable();
+    let span: Result<Span, Diagnostic2> = parse_span(&mut iter).and_then(|span| match iter.next() {
+        Some(tt) => Err(error2(tt.span(), "Expected nothing after the span")),
+        None => Ok(span),
+    });
+    match span {
+        Ok(span) => Ok(set_spans(item, span)),
+        // NOTE: The error ends up in item position, so needs to be a valid item. We also emit the
+        // item to avoid follow-up errors for it missing.
+        Err(err) => Err(item_error(err).with_tokens(item)),
+    }
+}
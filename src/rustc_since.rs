@@ -0,0 +1,226 @@
+//  RUSTC SINCE.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for choosing between tokens based on the version of the compiler.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+
+
+/***** CONSTANTS *****/
+// Defines `RUSTC_VERSION`, as found by the build script.
+include!(concat!(env!("OUT_DIR"), "/rustc_version.rs"));
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a parsed version of the compiler.
+struct Version {
+    /// The major, minor and patch version.
+    version: (u64, u64, u64),
+    /// The span of the version, for errors.
+    span:    Span,
+}
+impl Version {
+    /// Parses a Version from an iterator over [`TokenTree`]s.
+    ///
+    /// This accepts either `1.77`, `1.77.0` or `"1.77.0"`.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Version.
+    ///
+    /// # Errors
+    /// This function can error if the head of the input was not a version.
+    fn parse(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Self, Diagnostic2> {
+        let lit = match iter.next() {
+            Some(TokenTree::Literal(lit)) => lit,
+            // Allow versions given as `$version:literal` or `$version:tt`
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => {
+                let mut inner = group.stream().into_iter().peekable();
+                let version: Self = Self::parse(&mut inner)?;
+                if let Some(tt) = inner.next() {
+                    return Err(error2(tt.span(), "Expected nothing after the version"));
+                }
+                return Ok(version);
+            },
+            Some(tt) => return Err(error2(tt.span(), "Expected a version (e.g., `1.77`)")),
+            None => return Err(error2(Span::call_site(), "Expected a version (e.g., `1.77`)")),
+        };
+        let span: Span = lit.span();
+
+        // A version as a string is parsed as-is
+        let text: String = lit.to_string();
+        if let Some(text) = text.strip_prefix('"').and_then(|text| text.strip_suffix('"')) {
+            return Self::parse_parts(text, span);
+        }
+
+        // Otherwise, `1.77.0` is given as `1.77`, `.` and `0`
+        let mut text: String = text;
+        if text.contains('.')
+            && let Some(TokenTree::Punct(p)) = iter.peek()
+            && p.as_char() == '.'
+        {
+            iter.next();
+            match iter.next() {
+                Some(TokenTree::Literal(patch)) => {
+                    text.push('.');
+                    text.push_str(&patch.to_string());
+                },
+                Some(tt) => return Err(error2(tt.span(), "Expected a patch version")),
+                None => return Err(error2(span, "Expected a patch version after '.'")),
+            }
+        }
+        Self::parse_parts(&text, span)
+    }
+
+    /// Parses a version from its textual representation.
+    ///
+    /// # Arguments
+    /// - `text`: The version as `major.minor` or `major.minor.patch`.
+    /// - `span`: The [`Span`] of the version, for errors.
+    ///
+    /// # Returns
+    /// The parsed Version.
+    ///
+    /// # Errors
+    /// This function errors if the text was not a valid version.
+    fn parse_parts(text: &str, span: Span) -> Result<Self, Diagnostic2> {
+        let parts: Vec<u64> = match text.split('.').map(str::parse).collect::<Result<Vec<u64>, _>>() {
+            Ok(parts) => parts,
+            Err(_) => return Err(error2(span, &format!("Invalid version `{text}`; expected `major.minor` or `major.minor.patch`"))),
+        };
+        match parts.as_slice() {
+            [major, minor] => Ok(Self { version: (*major, *minor, 0), span }),
+            [major, minor, patch] => Ok(Self { version: (*major, *minor, *patch), span }),
+            _ => Err(error2(span, &format!("Invalid version `{text}`; expected `major.minor` or `major.minor.patch`"))),
+        }
+    }
+
+    /// Checks whether the current compiler is at least this version.
+    ///
+    /// # Returns
+    /// True if the compiler is this version or newer, or false otherwise.
+    ///
+    /// # Errors
+    /// This function errors if the version of the compiler is unknown.
+    fn is_current(&self) -> Result<bool, Diagnostic2> {
+        match RUSTC_VERSION {
+            Some(current) => Ok(current >= self.version),
+            None => Err(error2(self.span, "The version of rustc could not be determined when building `macro-toolkit`")),
+        }
+    }
+}
+
+
+
+/// Parses a body in curly brackets.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+/// - `span`: A [`Span`] to report errors at if there is no token.
+///
+/// # Returns
+/// The tokens in the body.
+///
+/// # Errors
+/// This function errors if the head of the input was not a body in curly brackets.
+fn parse_body(iter: &mut impl Iterator<Item = TokenTree>, span: Span) -> Result<Group, Diagnostic2> {
+    match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => Ok(group),
+        Some(tt) => Err(error2(tt.span(), "Expected a body in curly brackets")),
+        None => Err(error2(span, "Expected a body in curly brackets")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`rustc_since()`](super::rustc_since())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the version and the bodies to choose from.
+///
+/// # Returns
+/// A new [`TokenStream`] with the first body if the compiler is at least the given version, or
+/// else the second body (if any).
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the version of the
+/// compiler is unknown.
+pub fn rustc_since(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+    let version: Version = Version::parse(&mut iter)?;
+    let then: Group = parse_body(&mut iter, version.span)?;
+    let otherwise: Option<Group> = match iter.next() {
+        Some(TokenTree::Ident(ident)) if ident == "else" => Some(parse_body(&mut iter, ident.span())?),
+        Some(tt) => return Err(error2(tt.span(), "Expected either `else` or nothing")),
+        None => None,
+    };
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the else-body"));
+    }
+
+    // Choose the body
+    if version.is_current()? { Ok(then.stream()) } else { Ok(otherwise.map(|body| body.stream()).unwrap_or_default()) }
+}
+
+/// Defines the implementation of the [`cfg_rustc_since()`](super::cfg_rustc_since())-attribute.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the (optionally negated) version.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// The `item` if the compiler is at least the given version (or not, if negated), or else
+/// nothing.
+///
+/// # Errors
+/// This function may error if the attribute's arguments are invalid, or if the version of the
+/// compiler is unknown.
+pub fn cfg_rustc_since(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let keep = || -> Result<bool, Diagnostic2> {
+        let mut iter = attr.into_iter().peekable();
+        let (negate, version): (bool, Version) = match iter.peek() {
+            Some(TokenTree::Ident(ident)) if ident == "not" => {
+                let span: Span = ident.span();
+                iter.next();
+                match iter.next() {
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                        let mut inner = group.stream().into_iter().peekable();
+                        let version: Version = Version::parse(&mut inner)?;
+                        if let Some(tt) = inner.next() {
+                            return Err(error2(tt.span(), "Expected nothing after the version"));
+                        }
+                        (true, version)
+                    },
+                    Some(tt) => return Err(error2(tt.span(), "Expected a version in parenthesis")),
+                    None => return Err(error2(span, "Expected a version in parenthesis")),
+                }
+            },
+            _ => (false, Version::parse(&mut iter)?),
+        };
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the version"));
+        }
+        Ok(version.is_current()? != negate)
+    };
+
+    match keep() {
+        Ok(true) => Ok(item),
+        Ok(false) => Ok(TokenStream::new()),
+        // NOTE: The error ends up in item position, so needs a semicolon to be a valid item
+        Err(err) => Err(item_error(err)),
+    }
+}
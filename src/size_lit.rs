@@ -0,0 +1,177 @@
+//  SIZE LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for parsing human-readable sizes at compile time.
+//
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, int_lit, parse_lit, subspan};
+
+
+/***** CONSTANTS *****/
+/// The units that may be used, together with the number of bytes in them.
+const UNITS: [(&str, u128); 15] = [
+    ("B", 1),
+    ("kB", 1_000),
+    ("KB", 1_000),
+    ("MB", 1_000_000),
+    ("GB", 1_000_000_000),
+    ("TB", 1_000_000_000_000),
+    ("PB", 1_000_000_000_000_000),
+    ("EB", 1_000_000_000_000_000_000),
+    ("KiB", 1 << 10),
+    ("kiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+    ("TiB", 1 << 40),
+    ("PiB", 1 << 50),
+    ("EiB", 1 << 60),
+];
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the [`IntType`] to emit the size as and the [`LitStr`] to parse the size from.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(IntType, LitStr), Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the optional type first
+    let mut ty: IntType = IntType::USize;
+    let mut tree: Option<TokenTree> = iter.next();
+    if let Some(TokenTree::Ident(ident)) = &tree {
+        ty = match IntType::from_suffix(&ident.to_string()) {
+            Some(ty) if !ty.is_signed() => ty,
+            _ => return Err(error2(ident.span(), "Expected either an unsigned integer type or a string literal")),
+        };
+        tree = iter.next();
+    }
+
+    // Then the literal
+    let lit: LitStr = match tree.map(parse_lit).transpose()? {
+        Some(Lit::Str(lit)) => lit,
+        Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+        None => return Err(error2(Span::mixed_site(), "Expected a string literal")),
+    };
+    if let Some(tree) = iter.next() {
+        return Err(error2(tree.span(), "Expected nothing after the string literal"));
+    }
+    Ok((ty, lit))
+}
+
+
+
+/// Parses a size from a string literal.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] to parse.
+///
+/// # Returns
+/// The size, in bytes.
+///
+/// # Errors
+/// This function errors if the literal does not contain a valid size.
+pub fn parse_size(lit: &LitStr) -> Result<u128, Diagnostic2> {
+    let value: String = lit.value();
+    let trimmed: &str = value.trim_start();
+    let offset: usize = value.len() - trimmed.len();
+    let trimmed: &str = trimmed.trim_end();
+
+    // Parse the number
+    let mut whole: u128 = 0;
+    let mut frac: Vec<u8> = Vec::new();
+    let mut seen_dot: bool = false;
+    let mut i: usize = 0;
+    for c in trimmed.chars() {
+        match c {
+            '0'..='9' if !seen_dot => {
+                let digit: u128 = (c as u8 - b'0') as u128;
+                whole = whole
+                    .checked_mul(10)
+                    .and_then(|w| w.checked_add(digit))
+                    .ok_or_else(|| error2(subspan(lit, offset..offset + i + 1), "Size is too large"))?;
+            },
+            '0'..='9' => frac.push(c as u8 - b'0'),
+            '.' if !seen_dot => seen_dot = true,
+            '_' => {},
+            _ => break,
+        }
+        i += c.len_utf8();
+    }
+    if !trimmed[..i].contains(|c: char| c.is_ascii_digit()) {
+        return Err(error2(lit.span(), "Expected a size (e.g., \"4KiB\")"));
+    }
+
+    // Parse the unit
+    let unit: &str = trimmed[i..].trim_start();
+    let ustart: usize = offset + (trimmed.len() - unit.len());
+    let bytes: u128 = match UNITS.iter().find(|(name, _)| *name == unit) {
+        Some((_, bytes)) => *bytes,
+        None if unit.is_empty() => 1,
+        None if unit.ends_with('b') => {
+            return Err(error2(
+                subspan(lit, ustart..ustart + unit.len()),
+                &format!("Unknown unit `{unit}` (note: sizes are in bytes, use `B` instead of `b`)"),
+            ));
+        },
+        None => return Err(error2(subspan(lit, ustart..ustart + unit.len()), &format!("Unknown unit `{unit}`"))),
+    };
+
+    // Compute the size
+    let too_large = || error2(lit.span(), "Size is too large");
+    let mut size: u128 = whole.checked_mul(bytes).ok_or_else(too_large)?;
+    if !frac.is_empty() {
+        // Compute the fraction as `num / den` bytes
+        let mut num: u128 = 0;
+        let mut den: u128 = 1;
+        for digit in frac {
+            num = num.checked_mul(10).and_then(|n| n.checked_add(digit as u128)).ok_or_else(too_large)?;
+            den = den.checked_mul(10).ok_or_else(too_large)?;
+        }
+        let num: u128 = num.checked_mul(bytes).ok_or_else(too_large)?;
+        if !num.is_multiple_of(den) {
+            return Err(error2(subspan(lit, offset..offset + i), "Size is not a whole number of bytes"));
+        }
+        size = size.checked_add(num / den).ok_or_else(too_large)?;
+    }
+    Ok(size)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`size_lit()`](super::size_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to parse.
+///
+/// # Returns
+/// A new [`TokenStream`] with an integer literal encoding the size in bytes.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the literal does not
+/// contain a valid size that fits in the chosen type.
+pub fn size_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (ty, lit) = parse_input(input)?;
+    let size: u128 = parse_size(&lit)?;
+    if !ty.fits(false, size) {
+        return Err(error2(lit.span(), &format!("Size of {size} bytes does not fit in a `{}`", ty.suffix())));
+    }
+    Ok(int_lit(false, size, Some(ty), lit.span()))
+}
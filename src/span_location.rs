@@ -0,0 +1,123 @@
+//  SPAN LOCATION.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for emitting the location of a token (or of the call site) as literals.
+//
+
+use std::iter::Peekable;
+use std::str::FromStr as _;
+
+use proc_macro2::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::Diagnostic2;
+use crate::utils::Callback;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Finds the location of the given span.
+///
+/// # Arguments
+/// - `span`: The [`Span`] to find the location of.
+///
+/// # Returns
+/// The file, the (one-indexed) line and the (one-indexed) column of the start of the span.
+#[cfg(feature = "span_locations")]
+fn locate(span: Span) -> (String, usize, usize) {
+    let start = span.start();
+    (span.file(), start.line, start.column + 1)
+}
+
+/// Generates a `u32` literal.
+///
+/// # Arguments
+/// - `value`: The value of the literal.
+/// - `span`: The [`Span`] to give the literal.
+///
+/// # Returns
+/// A [`TokenTree`] encoding the literal.
+fn u32_lit(value: usize, span: Span) -> TokenTree {
+    let mut lit = Literal::from_str(&format!("{value}u32")).unwrap();
+    lit.set_span(span);
+    TokenTree::Literal(lit)
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the optional token to find the location of.
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+///
+/// # Returns
+/// The [`Span`] of the token, or [`None`] if none was given (i.e., the input is empty or starts
+/// with the `=>` of a callback).
+fn parse_token(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Option<Span> {
+    match iter.peek()? {
+        TokenTree::Punct(p) if p.as_char() == '=' && p.spacing() == Spacing::Joint => None,
+        _ => iter.next().as_ref().map(TokenTree::span),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`span_location()`](super::span_location())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the optional token and callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with a tuple of the file, line and column, or a call to the given callback
+/// with them.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if a token is given while
+/// the `span_locations`-feature is disabled.
+pub fn span_location(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+    let token: Option<Span> = parse_token(&mut iter);
+    let callback: Option<Callback> = Callback::parse_opt(&mut iter)?;
+
+    // Find the location
+    let span: Span = token.unwrap_or_else(Span::call_site);
+    let (file, line, column): (String, usize, usize) = match token {
+        #[cfg(feature = "span_locations")]
+        Some(span) => locate(span),
+        #[cfg(not(feature = "span_locations"))]
+        Some(span) => {
+            return Err(Diagnostic2::error(span, "Finding the location of a token requires the `span_locations`-feature")
+                .help("enable it in the dependency on `macro-toolkit` in your `Cargo.toml`, or omit the token to use the call site"));
+        },
+        None => {
+            let call_site = proc_macro::Span::call_site();
+            (call_site.file(), call_site.line(), call_site.column())
+        },
+    };
+
+    // Generate the parts
+    let mut file = Literal::string(&file);
+    file.set_span(span);
+    let mut parts = TokenStream::new();
+    for (i, part) in [TokenTree::Literal(file), u32_lit(line, span), u32_lit(column, span)].into_iter().enumerate() {
+        if i > 0 {
+            let mut comma = Punct::new(',', Spacing::Alone);
+            comma.set_span(span);
+            parts.extend([TokenTree::Punct(comma)]);
+        }
+        parts.extend([part]);
+    }
+    match callback {
+        Some(callback) => Ok(callback.call_item(parts)),
+        None => {
+            let mut group = Group::new(Delimiter::Parenthesis, parts);
+            group.set_span(span);
+            Ok(TokenStream::from(TokenTree::Group(group)))
+        },
+    }
+}
@@ -0,0 +1,184 @@
+//  STR PREDICATES.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for evaluating predicates over string literals at compile time.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::parse_lit;
+
+
+/***** TOKEN PARSING *****/
+/// Defines the predicates that can be evaluated.
+enum Predicate {
+    /// The haystack contains the needle.
+    Contains,
+    /// The haystack starts with the needle.
+    StartsWith,
+    /// The haystack ends with the needle.
+    EndsWith,
+}
+
+/// Defines the parsed input to the macro.
+struct Input {
+    /// The predicate to evaluate.
+    pred:     Predicate,
+    /// The string to search in.
+    haystack: String,
+    /// The string to search for.
+    needle:   String,
+    /// The span of the predicate, for the generated tokens.
+    span:     Span,
+    /// The branches to choose from, if this is the `if`-form.
+    branches: Option<(Group, Option<Group>)>,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // See if this is the `if`-form
+        let is_if: bool = matches!(iter.peek(), Some(TokenTree::Ident(ident)) if ident == "if");
+        if is_if {
+            iter.next();
+        }
+
+        // Parse the predicate
+        let (pred, span): (Predicate, Span) = match iter.next() {
+            Some(TokenTree::Ident(ident)) => match ident.to_string().as_str() {
+                "contains" => (Predicate::Contains, ident.span()),
+                "starts_with" => (Predicate::StartsWith, ident.span()),
+                "ends_with" => (Predicate::EndsWith, ident.span()),
+                _ => return Err(error2(ident.span(), "Expected either `contains`, `starts_with` or `ends_with`")),
+            },
+            Some(tt) => return Err(error2(tt.span(), "Expected either `if`, `contains`, `starts_with` or `ends_with`")),
+            None => return Err(error2(Span::mixed_site(), "Expected either `if`, `contains`, `starts_with` or `ends_with`")),
+        };
+        let (haystack, needle): (String, String) = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => Self::parse_args(group)?,
+            Some(tt) => return Err(error2(tt.span(), "Expected parenthesis with the string to search in and the string to search for")),
+            None => return Err(error2(span, "Expected parenthesis with the string to search in and the string to search for")),
+        };
+
+        // Parse the branches, if any
+        let branches: Option<(Group, Option<Group>)> = if is_if {
+            let then: Group = Self::parse_branch(iter.next(), span)?;
+            let other: Option<Group> = match iter.next() {
+                Some(TokenTree::Ident(ident)) if ident == "else" => Some(Self::parse_branch(iter.next(), ident.span())?),
+                Some(tt) => return Err(error2(tt.span(), "Expected either `else` or nothing")),
+                None => None,
+            };
+            Some((then, other))
+        } else {
+            None
+        };
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the predicate"));
+        }
+        Ok(Self { pred, haystack, needle, span, branches })
+    }
+
+    /// Parses the arguments to a predicate.
+    ///
+    /// # Arguments
+    /// - `group`: The [`Group`] containing the arguments.
+    ///
+    /// # Returns
+    /// A tuple of the haystack and the needle.
+    ///
+    /// # Errors
+    /// This function errors if the group did not contain two literals separated by a comma.
+    fn parse_args(group: Group) -> Result<(String, String), Diagnostic2> {
+        let mut iter = group.stream().into_iter();
+        let haystack: String = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => lit.value(),
+            Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+            None => return Err(error2(group.span(), "Expected a string literal")),
+        };
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+            None => return Err(error2(group.span_close(), "Expected ','")),
+        }
+        let needle: String = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => lit.value(),
+            Some(Lit::Char(lit)) => lit.value().to_string(),
+            Some(lit) => return Err(error2(lit.span(), "Expected a string or character literal")),
+            None => return Err(error2(group.span_close(), "Expected a string or character literal")),
+        };
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+            None => {},
+        }
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the string to search for"));
+        }
+        Ok((haystack, needle))
+    }
+
+    /// Parses a branch of the `if`-form.
+    ///
+    /// # Arguments
+    /// - `tree`: The token that should be the branch.
+    /// - `span`: A [`Span`] to report errors at if there is no token.
+    ///
+    /// # Returns
+    /// The braced [`Group`] making up the branch.
+    ///
+    /// # Errors
+    /// This function errors if the token was not a braced group.
+    fn parse_branch(tree: Option<TokenTree>, span: Span) -> Result<Group, Diagnostic2> {
+        match tree {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => Ok(group),
+            Some(tt) => Err(error2(tt.span(), "Expected a branch in curly brackets")),
+            None => Err(error2(span, "Expected a branch in curly brackets")),
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`str_predicates()`](super::str_predicates())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the predicate to evaluate.
+///
+/// # Returns
+/// A new [`TokenStream`] with either `true` or `false`, or the contents of the chosen branch in
+/// the `if`-form.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn str_predicates(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { pred, haystack, needle, span, branches } = Input::parse(input)?;
+
+    // Evaluate the predicate
+    let res: bool = match pred {
+        Predicate::Contains => haystack.contains(&needle),
+        Predicate::StartsWith => haystack.starts_with(&needle),
+        Predicate::EndsWith => haystack.ends_with(&needle),
+    };
+
+    // Generate the output
+    match branches {
+        Some((then, other)) => Ok(if res { then.stream() } else { other.map(|g| g.stream()).unwrap_or_default() }),
+        None => Ok(TokenStream::from(TokenTree::Ident(Ident::new(if res { "true" } else { "false" }, span)))),
+    }
+}
@@ -0,0 +1,344 @@
+//  STR SWITCH.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for generating an efficient runtime `match` over strings.
+//
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, parse_string};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates an identifier that is hygienic to the macro.
+///
+/// # Arguments
+/// - `name`: The name of the identifier.
+///
+/// # Returns
+/// A [`TokenTree`] with the identifier.
+#[inline]
+fn ident(name: &str) -> TokenTree { TokenTree::Ident(Ident::new(name, Span::mixed_site())) }
+
+/// Generates a (possibly multi-character) punctuation.
+///
+/// # Arguments
+/// - `chars`: The characters of the punctuation (e.g., `=>`).
+///
+/// # Returns
+/// A [`TokenStream`] with the punctuation.
+fn punct(chars: &str) -> TokenStream {
+    let count: usize = chars.chars().count();
+    chars
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let mut punct = Punct::new(c, if i + 1 < count { Spacing::Joint } else { Spacing::Alone });
+            punct.set_span(Span::mixed_site());
+            TokenTree::Punct(punct)
+        })
+        .collect()
+}
+
+/// Wraps a [`TokenStream`] in a [`Group`].
+///
+/// # Arguments
+/// - `delim`: The delimiter of the group.
+/// - `tokens`: The tokens in the group.
+///
+/// # Returns
+/// A [`TokenTree`] with the group.
+fn group(delim: Delimiter, tokens: TokenStream) -> TokenTree {
+    let mut group = Group::new(delim, tokens);
+    group.set_span(Span::mixed_site());
+    TokenTree::Group(group)
+}
+
+/// Generates an unsuffixed `usize` literal.
+///
+/// # Arguments
+/// - `value`: The value of the literal.
+///
+/// # Returns
+/// A [`TokenTree`] with the literal.
+fn usize_lit(value: usize) -> TokenTree {
+    let mut lit = Literal::usize_unsuffixed(value);
+    lit.set_span(Span::mixed_site());
+    TokenTree::Literal(lit)
+}
+
+/// Generates the decision tree that finds the arm of a string among strings of the same length.
+///
+/// At every level, the byte position that best tells the remaining strings apart is matched on.
+/// Once a single string remains, it is compared as a whole.
+///
+/// # Arguments
+/// - `strings`: The strings to tell apart with the index of their arm. They must be unique and
+///   have the same length.
+/// - `fallback`: The index to produce if none of the strings match.
+///
+/// # Returns
+/// A [`TokenStream`] with an expression that evaluates to the index of the matching arm.
+fn decision_tree(strings: &[(&[u8], usize)], fallback: usize) -> TokenStream {
+    // Compare the last remaining string as a whole
+    if let [(bytes, arm)] = strings {
+        let mut lit = Literal::byte_string(bytes);
+        lit.set_span(Span::mixed_site());
+        let mut res: TokenStream = [ident("if"), ident("bytes")].into_iter().collect();
+        res.extend(punct("=="));
+        res.extend([
+            TokenTree::Literal(lit),
+            group(Delimiter::Brace, usize_lit(*arm).into()),
+            ident("else"),
+            group(Delimiter::Brace, usize_lit(fallback).into()),
+        ]);
+        return res;
+    }
+
+    // Otherwise, find the position with the most distinct bytes
+    let len: usize = strings[0].0.len();
+    let pos: usize = (0..len).max_by_key(|&i| (strings.iter().map(|(bytes, _)| bytes[i]).collect::<BTreeSet<u8>>().len(), std::cmp::Reverse(i))).unwrap_or(0);
+    let mut children: BTreeMap<u8, Vec<(&[u8], usize)>> = BTreeMap::new();
+    for (bytes, arm) in strings {
+        children.entry(bytes[pos]).or_default().push((bytes, *arm));
+    }
+
+    // Match on it
+    let mut arms = TokenStream::new();
+    for (byte, strings) in children {
+        let mut lit = Literal::u8_unsuffixed(byte);
+        lit.set_span(Span::mixed_site());
+        arms.extend([TokenTree::Literal(lit)]);
+        arms.extend(punct("=>"));
+        arms.extend([group(Delimiter::Brace, decision_tree(&strings, fallback))]);
+    }
+    arms.extend([ident("_")]);
+    arms.extend(punct("=>"));
+    arms.extend([usize_lit(fallback)]);
+    arms.extend(punct(","));
+    let mut res: TokenStream = [ident("match"), ident("bytes"), group(Delimiter::Bracket, usize_lit(pos).into())].into_iter().collect();
+    res.extend([group(Delimiter::Brace, arms)]);
+    res
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single arm of the macro.
+struct Arm {
+    /// The strings matched by this arm, with the spans of their literals.
+    patterns: Vec<(String, Span)>,
+    /// The body of the arm.
+    body:     TokenStream,
+}
+
+/// Defines the catch-all arm of the macro.
+struct CatchAll {
+    /// The identifier that the string is bound to, if it is not `_`.
+    binding: Option<Ident>,
+    /// The body of the arm.
+    body:    TokenStream,
+}
+
+/// Parses the body of an arm.
+///
+/// The body is either a block, optionally followed by a comma, or the tokens up to the next comma
+/// (or the end of the input).
+///
+/// # Arguments
+/// - `iter`: The iterator yielding the remaining tokens.
+/// - `span`: A [`Span`] to report errors at if there is no body.
+///
+/// # Returns
+/// The body as a block.
+///
+/// # Errors
+/// This function errors if there is no body.
+fn parse_body(iter: &mut Peekable<impl Iterator<Item = TokenTree>>, span: Span) -> Result<TokenStream, Diagnostic2> {
+    if let Some(TokenTree::Group(group)) = iter.next_if(|tt| matches!(tt, TokenTree::Group(g) if g.delimiter() == Delimiter::Brace)) {
+        iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ','));
+        return Ok(TokenTree::Group(group).into());
+    }
+    let mut body = TokenStream::new();
+    for tt in iter.by_ref() {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => break,
+            tt => body.extend([tt]),
+        }
+    }
+    if body.is_empty() {
+        return Err(error2(span, "Expected an expression after '=>'"));
+    }
+    Ok(TokenTree::Group(Group::new(Delimiter::Brace, body)).into())
+}
+
+/// Parses the arms of the macro.
+///
+/// # Arguments
+/// - `group`: The [`Group`] containing the arms.
+///
+/// # Returns
+/// The arms that match strings and the catch-all arm.
+///
+/// # Errors
+/// This function errors if any of the arms are malformed, if any string is matched twice or if
+/// there is no catch-all arm.
+fn parse_arms(group: &Group) -> Result<(Vec<Arm>, CatchAll), Diagnostic2> {
+    let mut iter = group.stream().into_iter().peekable();
+    let mut arms: Vec<Arm> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    while let Some(first) = iter.next() {
+        // Parse the patterns, or the catch-all
+        let mut catch_all: Option<Option<Ident>> = None;
+        let mut patterns: Vec<(String, Span)> = Vec::new();
+        let mut next: Option<TokenTree> = Some(first);
+        loop {
+            let span: Span = next.as_ref().map(TokenTree::span).unwrap_or_else(|| group.span_close());
+            match next {
+                Some(TokenTree::Ident(ident)) if patterns.is_empty() => {
+                    catch_all = Some(if ident == "_" { None } else { Some(ident) });
+                    break;
+                },
+                tt => {
+                    let value: String = parse_string(tt, span, "a string to match")?;
+                    if !seen.insert(value.clone()) {
+                        return Err(error2(span, &format!("{value:?} is already matched by an earlier arm")));
+                    }
+                    patterns.push((value, span));
+                },
+            }
+            match iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '|')) {
+                Some(_) => next = iter.next(),
+                None => break,
+            }
+        }
+
+        // Parse the arrow
+        match (iter.next(), iter.next()) {
+            (Some(TokenTree::Punct(eq)), Some(TokenTree::Punct(gt))) if eq.as_char() == '=' && eq.spacing() == Spacing::Joint && gt.as_char() == '>' => {},
+            (Some(tt), _) => return Err(error2(tt.span(), "Expected either '|' or '=>'")),
+            (None, _) => return Err(error2(group.span_close(), "Expected either '|' or '=>'")),
+        }
+        let body: TokenStream = parse_body(&mut iter, group.span_close())?;
+
+        // Stop at the catch-all
+        if let Some(binding) = catch_all {
+            if let Some(tt) = iter.next() {
+                return Err(error2(tt.span(), "Unreachable arm after the catch-all arm"));
+            }
+            return Ok((arms, CatchAll { binding, body }));
+        }
+        arms.push(Arm { patterns, body });
+    }
+    Err(Diagnostic2::error(group.span_close(), "Expected a catch-all arm, as not all strings are matched")
+        .help("add `_ => ...` or `other => ...` as the last arm"))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`str_switch()`](super::str_switch())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the expression to match and the arms in curly
+///   brackets.
+///
+/// # Returns
+/// A new [`TokenStream`] with a block that matches the string and evaluates the body of the
+/// matching arm.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn str_switch(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Parse the input
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let arms_group: Group = match tokens.pop() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace && !tokens.is_empty() => group,
+        Some(tt) => return Err(error2(tt.span(), "Expected an expression and then match arms wrapped in `{}`")),
+        None => return Err(error2(Span::call_site(), "Expected an expression and then match arms wrapped in `{}`")),
+    };
+    let value: TokenStream = tokens.into_iter().collect();
+    let (arms, catch_all): (Vec<Arm>, CatchAll) = parse_arms(&arms_group)?;
+    let fallback: usize = arms.len();
+
+    // Group the strings by length, and generate the decision trees for every length
+    let mut lengths: BTreeMap<usize, Vec<(&[u8], usize)>> = BTreeMap::new();
+    for (i, arm) in arms.iter().enumerate() {
+        for (pattern, _) in &arm.patterns {
+            lengths.entry(pattern.len()).or_default().push((pattern.as_bytes(), i));
+        }
+    }
+    let mut dispatch = TokenStream::new();
+    for (len, strings) in lengths {
+        dispatch.extend([usize_lit(len)]);
+        dispatch.extend(punct("=>"));
+        dispatch.extend([group(Delimiter::Brace, decision_tree(&strings, fallback))]);
+    }
+    dispatch.extend([ident("_")]);
+    dispatch.extend(punct("=>"));
+    dispatch.extend([usize_lit(fallback)]);
+    dispatch.extend(punct(","));
+
+    // Generate the bodies
+    let mut bodies = TokenStream::new();
+    for (i, arm) in arms.into_iter().enumerate() {
+        bodies.extend([usize_lit(i)]);
+        bodies.extend(punct("=>"));
+        bodies.extend(arm.body);
+    }
+    bodies.extend([ident("_")]);
+    bodies.extend(punct("=>"));
+    match catch_all.binding {
+        Some(binding) => {
+            let mut body: TokenStream = [ident("let"), TokenTree::Ident(binding)].into_iter().collect();
+            body.extend(punct("="));
+            body.extend([ident("value")]);
+            body.extend(punct(";"));
+            body.extend(catch_all.body);
+            bodies.extend([group(Delimiter::Brace, body)]);
+        },
+        None => bodies.extend(catch_all.body),
+    }
+
+    // Put it together as:
+    // ```
+    // let value: &str = <value>;
+    // let bytes: &[u8] = value.as_bytes();
+    // let arm: usize = match bytes.len() { <dispatch> };
+    // match arm { <bodies> }
+    // ```
+    let mut res: TokenStream = [ident("let"), ident("value")].into_iter().collect();
+    res.extend(punct(":"));
+    res.extend(punct("&"));
+    res.extend([ident("str")]);
+    res.extend(punct("="));
+    res.extend(value);
+    res.extend(punct(";"));
+    res.extend([ident("let"), ident("bytes")]);
+    res.extend(punct(":"));
+    res.extend(punct("&"));
+    res.extend([group(Delimiter::Bracket, ident("u8").into())]);
+    res.extend(punct("="));
+    res.extend([ident("value")]);
+    res.extend(punct("."));
+    res.extend([ident("as_bytes"), group(Delimiter::Parenthesis, TokenStream::new())]);
+    res.extend(punct(";"));
+    res.extend([ident("let"), ident("arm")]);
+    res.extend(punct(":"));
+    res.extend([ident("usize")]);
+    res.extend(punct("="));
+    res.extend([ident("match"), ident("bytes")]);
+    res.extend(punct("."));
+    res.extend([ident("len"), group(Delimiter::Parenthesis, TokenStream::new()), group(Delimiter::Brace, dispatch)]);
+    res.extend(punct(";"));
+    res.extend([ident("match"), ident("arm"), group(Delimiter::Brace, bodies)]);
+    Ok(group(Delimiter::Brace, res).into())
+}
@@ -0,0 +1,285 @@
+//  STRIP ATTRS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides an attribute for removing attributes from an item and its fields or variants.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::items::{ItemHead, Tail, parse_head};
+use crate::utils::Callback;
+
+
+/***** TOKEN PARSING *****/
+/// Defines a path to strip attributes by.
+struct Pattern {
+    /// The segments of the path, excluding a trailing `*`.
+    segments: Vec<String>,
+    /// Whether the path ended in `*`, i.e., it also matches all paths starting with it.
+    glob:     bool,
+}
+impl Pattern {
+    /// Parses a Pattern from its tokens.
+    ///
+    /// # Arguments
+    /// - `tokens`: The tokens of the pattern, without any separating commas.
+    /// - `span`: A span to report errors at if `tokens` is empty.
+    ///
+    /// # Returns
+    /// The parsed Pattern.
+    ///
+    /// # Errors
+    /// This function errors if the tokens are not a path, optionally ending in `*`.
+    fn parse(tokens: Vec<TokenTree>, span: Span) -> Result<Self, Diagnostic2> {
+        let mut segments: Vec<String> = Vec::new();
+        let mut glob: bool = false;
+        let mut expect_segment: bool = true;
+        for tt in tokens {
+            match tt {
+                TokenTree::Ident(ident) if expect_segment && !glob => {
+                    segments.push(ident.to_string());
+                    expect_segment = false;
+                },
+                TokenTree::Punct(p) if expect_segment && !glob && p.as_char() == '*' => {
+                    glob = true;
+                    expect_segment = false;
+                },
+                // Paths given as `$path:path`
+                TokenTree::Group(group) if expect_segment && !glob && group.delimiter() == Delimiter::None => {
+                    let inner = Self::parse(group.stream().into_iter().collect(), group.span())?;
+                    segments.extend(inner.segments);
+                    glob = inner.glob;
+                    expect_segment = false;
+                },
+                // Both colons of `::` (including a leading one, which is ignored)
+                TokenTree::Punct(p) if !glob && p.as_char() == ':' => expect_segment = true,
+                tt => return Err(error2(tt.span(), "Expected a path to strip attributes by (e.g., `serde` or `serde::*`)")),
+            }
+        }
+        if expect_segment {
+            return Err(error2(span, "Expected a path to strip attributes by (e.g., `serde` or `serde::*`)"));
+        }
+        Ok(Self { segments, glob })
+    }
+
+    /// Checks whether the given attribute path matches this pattern.
+    ///
+    /// # Arguments
+    /// - `path`: The segments of the path of an attribute.
+    ///
+    /// # Returns
+    /// True if an attribute with that path should be stripped, or false otherwise.
+    #[inline]
+    fn matches(&self, path: &[String]) -> bool { if self.glob { path.starts_with(&self.segments) } else { path == self.segments } }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Extracts the path of an attribute.
+///
+/// # Arguments
+/// - `attr`: The contents of the attribute (i.e., the tokens in its square brackets).
+///
+/// # Returns
+/// The segments of the path of the attribute.
+fn attr_path(attr: &Group) -> Vec<String> {
+    let mut path: Vec<String> = Vec::new();
+    for tt in attr.stream() {
+        match tt {
+            TokenTree::Ident(ident) => path.push(ident.to_string()),
+            TokenTree::Punct(p) if p.as_char() == ':' => {},
+            _ => break,
+        }
+    }
+    path
+}
+
+/// Removes the attributes matching any of the given patterns from a list of attributes.
+///
+/// # Arguments
+/// - `attrs`: The attributes to strip, as `#[...]` pairs.
+/// - `patterns`: The [`Pattern`]s to strip by.
+/// - `stripped`: A list of stripped attributes to add to.
+///
+/// # Returns
+/// The attributes that are kept.
+fn strip_list(attrs: Vec<TokenTree>, patterns: &[Pattern], stripped: &mut TokenStream) -> TokenStream {
+    let mut res = TokenStream::new();
+    let mut iter = attrs.into_iter();
+    while let Some(pound) = iter.next() {
+        let Some(tt) = iter.next() else {
+            res.extend([pound]);
+            break;
+        };
+        let strip: bool = matches!(&tt, TokenTree::Group(group) if patterns.iter().any(|pattern| pattern.matches(&attr_path(group))));
+        if strip {
+            stripped.extend([pound, tt]);
+        } else {
+            res.extend([pound, tt]);
+        }
+    }
+    res
+}
+
+/// Removes the attributes matching any of the given patterns from the fields or variants in a
+/// group.
+///
+/// Attributes are removed if they appear at the start of the group or after a top-level comma,
+/// which is where attributes on fields and variants are. If `variants` is true, groups following
+/// an identifier (i.e., the fields of a variant) are stripped as well.
+///
+/// # Arguments
+/// - `group`: The group to strip.
+/// - `variants`: Whether the group contains variants instead of fields.
+/// - `patterns`: The [`Pattern`]s to strip by.
+/// - `stripped`: A list of stripped attributes to add to.
+///
+/// # Returns
+/// The stripped group.
+fn strip_group(group: &Group, variants: bool, patterns: &[Pattern], stripped: &mut TokenStream) -> Group {
+    let mut res = TokenStream::new();
+    let mut iter = group.stream().into_iter().peekable();
+    let mut at_start: bool = true;
+    let mut prev_ident: bool = false;
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree::Punct(p) if at_start && p.as_char() == '#' => {
+                match iter.next_if(|tt| matches!(tt, TokenTree::Group(g) if g.delimiter() == Delimiter::Bracket)) {
+                    Some(attr) => {
+                        res.extend(strip_list(vec![TokenTree::Punct(p), attr], patterns, stripped));
+                        continue;
+                    },
+                    None => {
+                        res.extend([TokenTree::Punct(p)]);
+                        prev_ident = false;
+                    },
+                }
+            },
+            TokenTree::Group(fields) if variants && prev_ident && fields.delimiter() != Delimiter::Bracket => {
+                res.extend([TokenTree::Group(strip_group(&fields, false, patterns, stripped))]);
+                at_start = false;
+                prev_ident = false;
+                continue;
+            },
+            TokenTree::Punct(p) if p.as_char() == ',' => {
+                res.extend([TokenTree::Punct(p)]);
+                at_start = true;
+                prev_ident = false;
+                continue;
+            },
+            TokenTree::Ident(ident) => {
+                res.extend([TokenTree::Ident(ident)]);
+                prev_ident = true;
+            },
+            tt => {
+                res.extend([tt]);
+                prev_ident = false;
+            },
+        }
+        at_start = false;
+    }
+    let mut new = Group::new(group.delimiter(), res);
+    new.set_span(group.span());
+    new
+}
+
+/// Generates the error returned by the attribute, which keeps the item intact.
+///
+/// # Arguments
+/// - `err`: The error to return.
+/// - `item`: The item the attribute was placed on.
+///
+/// # Returns
+/// The error, followed by the item.
+#[inline]
+fn with_item(err: Diagnostic2, item: TokenStream) -> Diagnostic2 { item_error(err).with_tokens(item) }
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`strip_attrs()`](super::strip_attrs())-macro.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the paths to strip and the optional callback.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] with the item without the matching attributes, followed by a call to
+/// the callback with those attributes if one was given.
+///
+/// # Errors
+/// This function may error if the paths or the item are not valid for this macro.
+pub fn strip_attrs(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Parse the patterns and the callback
+    let (paths, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(attr).map_err(|err| with_item(err, item.clone()))?;
+    let mut patterns: Vec<Pattern> = Vec::new();
+    let mut path: Vec<TokenTree> = Vec::new();
+    for tt in paths.into_iter().chain([TokenTree::Punct(Punct::new(',', Spacing::Alone))]) {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => {
+                if !path.is_empty() {
+                    patterns.push(Pattern::parse(std::mem::take(&mut path), p.span()).map_err(|err| with_item(err, item.clone()))?);
+                }
+            },
+            tt => path.push(tt),
+        }
+    }
+    if patterns.is_empty() {
+        return Err(with_item(error2(Span::call_site(), "Expected at least one path to strip attributes by (e.g., `#[strip_attrs(serde::*)]`)"), item));
+    }
+
+    // Parse the item
+    let ItemHead { attrs, vis, qualifiers, keyword, name, rest } = parse_head(item.clone()).map_err(|err| with_item(err, item.clone()))?;
+    let span: Span = name.as_ref().map(Ident::span).unwrap_or_else(Span::call_site);
+
+    // Strip the item's own attributes, and those of its fields and variants
+    let mut stripped = TokenStream::new();
+    let mut res: TokenStream = strip_list(attrs.into_iter().collect(), &patterns, &mut stripped);
+    res.extend(vis);
+    res.extend(qualifiers);
+    let kind: String = keyword.to_string();
+    res.extend(keyword);
+    res.extend(name.map(TokenTree::Ident));
+    match kind.as_str() {
+        "struct" | "union" | "enum" => {
+            let Tail { generics, signature, where_, body } = Tail::parse(rest, span).map_err(|err| with_item(err, item.clone()))?;
+            if !generics.is_empty() {
+                res.extend([TokenTree::Punct(Punct::new('<', Spacing::Alone))]);
+                res.extend(generics);
+                res.extend([TokenTree::Punct(Punct::new('>', Spacing::Alone))]);
+            }
+            for tt in signature {
+                match tt {
+                    TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                        res.extend([TokenTree::Group(strip_group(&group, false, &patterns, &mut stripped))])
+                    },
+                    tt => res.extend([tt]),
+                }
+            }
+            if !where_.is_empty() {
+                res.extend([TokenTree::Ident(Ident::new("where", Span::call_site()))]);
+                res.extend(where_);
+            }
+            for tt in body {
+                match tt {
+                    TokenTree::Group(group) => res.extend([TokenTree::Group(strip_group(&group, kind == "enum", &patterns, &mut stripped))]),
+                    tt => res.extend([tt]),
+                }
+            }
+        },
+        _ => res.extend(rest),
+    }
+
+    // Forward the stripped attributes to the callback, if any
+    if let Some(callback) = callback {
+        res.extend(callback.call_item(stripped));
+    }
+    Ok(res)
+}
@@ -0,0 +1,223 @@
+//  STRIP GENERICS.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for removing the generic arguments from a type path.
+//
+
+use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::{flatten_none, is_punct, skip_angles};
+use crate::utils::Callback;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Splits generic arguments on commas that are not nested in other generic arguments.
+///
+/// # Arguments
+/// - `tokens`: The generic arguments, without angle brackets.
+///
+/// # Returns
+/// The tokens of every argument. Empty arguments (e.g., after a trailing comma) are omitted.
+fn split_args(tokens: &[TokenTree]) -> Vec<&[TokenTree]> {
+    let mut res: Vec<&[TokenTree]> = Vec::new();
+    let (mut start, mut i): (usize, usize) = (0, 0);
+    while i < tokens.len() {
+        if is_punct(tokens.get(i), '<') {
+            i = skip_angles(tokens, i).unwrap_or(tokens.len());
+            continue;
+        }
+        if is_punct(tokens.get(i), ',') {
+            res.push(&tokens[start..i]);
+            start = i + 1;
+        }
+        i += 1;
+    }
+    res.push(&tokens[start..]);
+    res.retain(|arg| !arg.is_empty());
+    res
+}
+
+/// Removes all lifetime arguments from the generic arguments in the given tokens, at any depth.
+///
+/// Lifetimes elsewhere (e.g., in references like `&'a T`) are kept. If all arguments of a
+/// segment are lifetimes, its angle brackets (and turbofish) are removed as well.
+///
+/// # Arguments
+/// - `tokens`: The tokens to remove the lifetimes from.
+///
+/// # Returns
+/// The same tokens, but without lifetime arguments.
+fn strip_lifetimes(tokens: &[TokenTree]) -> Vec<TokenTree> {
+    let mut res: Vec<TokenTree> = Vec::new();
+    let mut i: usize = 0;
+    while let Some(tt) = tokens.get(i) {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == '<' => {
+                let Some(end) = skip_angles(tokens, i) else {
+                    res.extend(tokens[i..].iter().cloned());
+                    break;
+                };
+                // Only generic arguments follow a segment name (and `for<'a>` binds lifetimes instead)
+                let generic: bool = match res.last() {
+                    Some(TokenTree::Ident(ident)) => ident != "for",
+                    Some(TokenTree::Punct(p)) => p.as_char() == ':',
+                    _ => false,
+                };
+                let args: Vec<Vec<TokenTree>> = split_args(&tokens[i + 1..end - 1])
+                    .into_iter()
+                    .filter(|arg| !(generic && matches!(arg, [TokenTree::Punct(p), TokenTree::Ident(_)] if p.as_char() == '\'')))
+                    .map(strip_lifetimes)
+                    .collect();
+                if args.is_empty() && generic {
+                    // Remove the turbofish, if any
+                    if is_punct(res.last(), ':') {
+                        res.truncate(res.len() - 2);
+                    }
+                } else {
+                    res.push(tt.clone());
+                    for (j, arg) in args.into_iter().enumerate() {
+                        if j > 0 {
+                            res.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+                        }
+                        res.extend(arg);
+                    }
+                    res.push(tokens[end - 1].clone());
+                }
+                i = end;
+            },
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), strip_lifetimes(&group.stream().into_iter().collect::<Vec<_>>()).into_iter().collect());
+                new.set_span(group.span());
+                res.push(TokenTree::Group(new));
+                i += 1;
+            },
+            tt => {
+                res.push(tt.clone());
+                i += 1;
+            },
+        }
+    }
+    res
+}
+
+
+
+/// Removes the generic arguments from a type path.
+///
+/// # Arguments
+/// - `tokens`: The (flattened) tokens of the path.
+/// - `span`: A span to report errors at if the path is empty.
+///
+/// # Returns
+/// The path without the generic arguments of any of its segments. Qualified self-types (e.g.,
+/// `<T as Trait>`) are kept as-is.
+///
+/// # Errors
+/// This function errors if the tokens are not a type path.
+fn strip(tokens: &[TokenTree], span: Span) -> Result<Vec<TokenTree>, Diagnostic2> {
+    /// What the previous part of the path was.
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    enum Prev {
+        /// Nothing, we're at the start.
+        Start,
+        /// A `::`, directly following a segment name.
+        SegmentSep,
+        /// A `::`, at the start or following generic arguments.
+        Sep,
+        /// The name of a segment.
+        Ident,
+        /// Generic arguments (or a qualified self-type).
+        Args,
+    }
+
+    let mut res: Vec<TokenTree> = Vec::new();
+    let mut prev: Prev = Prev::Start;
+    let mut i: usize = 0;
+    while let Some(tt) = tokens.get(i) {
+        match tt {
+            TokenTree::Ident(_) if matches!(prev, Prev::Start | Prev::SegmentSep | Prev::Sep) => {
+                res.push(tt.clone());
+                prev = Prev::Ident;
+                i += 1;
+            },
+            TokenTree::Punct(p) if p.as_char() == ':' && p.spacing() == Spacing::Joint && is_punct(tokens.get(i + 1), ':') => {
+                if matches!(prev, Prev::SegmentSep | Prev::Sep) {
+                    return Err(error2(p.span(), "Expected a path segment"));
+                }
+                res.extend(tokens[i..i + 2].iter().cloned());
+                prev = if prev == Prev::Ident { Prev::SegmentSep } else { Prev::Sep };
+                i += 2;
+            },
+            TokenTree::Punct(p) if p.as_char() == '<' && matches!(prev, Prev::Start | Prev::Ident | Prev::SegmentSep) => {
+                let Some(end) = skip_angles(tokens, i) else {
+                    return Err(error2(p.span(), "Unclosed generic arguments"));
+                };
+                match prev {
+                    // Keep qualified self-types
+                    Prev::Start => res.extend(tokens[i..end].iter().cloned()),
+                    // Drop the turbofish with the arguments
+                    Prev::SegmentSep => res.truncate(res.len() - 2),
+                    _ => {},
+                }
+                prev = Prev::Args;
+                i = end;
+            },
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis && prev == Prev::Ident => {
+                // Parenthesized arguments (e.g., `Fn(u8) -> u8`), of which the return type runs until the end
+                i += 1;
+                if is_punct(tokens.get(i), '-') && is_punct(tokens.get(i + 1), '>') {
+                    if i + 2 >= tokens.len() {
+                        return Err(error2(tokens[i + 1].span(), "Expected a return type after '->'"));
+                    }
+                    i = tokens.len();
+                }
+                prev = Prev::Args;
+            },
+            tt => return Err(error2(tt.span(), "Expected a type path (e.g., `Foo<T>` or `std::vec::Vec<u8>`)")),
+        }
+    }
+    match prev {
+        Prev::Ident | Prev::Args => Ok(res),
+        Prev::Start => Err(error2(span, "Expected a type path (e.g., `Foo<T>` or `std::vec::Vec<u8>`)")),
+        Prev::SegmentSep | Prev::Sep => Err(error2(tokens.last().map(TokenTree::span).unwrap_or(span), "Expected a path segment after '::'")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`strip_generics()`](super::strip_generics())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the optional mode, the type path and an optional
+///   callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the path without generic arguments (or only without lifetimes), or
+/// a call to the callback with it.
+///
+/// # Errors
+/// This function may error if the input is not a type path.
+pub fn strip_generics(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (path, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let mut tokens: Vec<TokenTree> = Vec::new();
+    flatten_none(path, &mut tokens);
+
+    // Parse the mode, if any
+    let lifetimes: bool = matches!(tokens.as_slice(), [TokenTree::Ident(ident), TokenTree::Punct(p), ..] if ident == "lifetimes" && p.as_char() == ':' && p.spacing() == Spacing::Alone);
+    if lifetimes {
+        tokens.drain(..2);
+    }
+
+    // Strip the path
+    let path: Vec<TokenTree> = strip(&tokens, Span::call_site())?;
+    let path: TokenStream = if lifetimes { strip_lifetimes(&tokens).into_iter().collect() } else { path.into_iter().collect() };
+    match callback {
+        Some(callback) => Ok(callback.call_item(path)),
+        None => Ok(path),
+    }
+}
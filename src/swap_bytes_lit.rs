@@ -0,0 +1,107 @@
+//  SWAP BYTES LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for byte-swapping integer literals at compile time.
+//
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{IntType, SignedLit, int_lit, parse_signed_lit};
+
+
+/***** TOKEN PARSING *****/
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the [`IntType`] to swap the literal as, whether the literal was negated, its
+/// absolute value and its span.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(IntType, bool, u128, Span), Diagnostic2> {
+    let mut iter = input.into_iter().peekable();
+
+    // Parse the optional type first
+    let mut ty: Option<(IntType, Span)> = None;
+    if let Some(TokenTree::Ident(ident)) = iter.peek() {
+        match IntType::from_suffix(&ident.to_string()) {
+            Some(t) => ty = Some((t, ident.span())),
+            None => return Err(error2(ident.span(), "Expected either an integer type or an integer literal")),
+        }
+        iter.next();
+    }
+
+    // Then the literal
+    let SignedLit { neg, lit } = parse_signed_lit(&mut iter)?;
+    let Lit::Int(lit) = lit else {
+        return Err(error2(lit.span(), "Expected an integer literal"));
+    };
+    let span: Span = lit.span();
+    if let Some(tree) = iter.next() {
+        return Err(error2(tree.span(), "Expected nothing after the integer literal"));
+    }
+
+    // Resolve the type from the literal's suffix and/or the given type
+    let (ty, ty_span): (IntType, Span) = match (ty, lit.suffix()) {
+        (Some((ty, ty_span)), "") => (ty, ty_span),
+        (None, "") => return Err(error2(span, "Expected either a suffixed integer literal (e.g., `0x1234u16`) or a type before it")),
+        (ty, suffix) => match IntType::from_suffix(suffix) {
+            Some(suffixed) if ty.is_none_or(|(ty, _)| ty == suffixed) => (suffixed, span),
+            Some(_) => return Err(error2(span, "Integer literal's suffix does not match the given type")),
+            None => return Err(error2(span, &format!("Unknown integer suffix `{suffix}`"))),
+        },
+    };
+    if matches!(ty, IntType::ISize | IntType::USize) {
+        return Err(error2(ty_span, &format!("Cannot byte-swap a `{}` because its width is platform-dependent", ty.suffix())));
+    }
+
+    // Parse the value and check it fits
+    let abs: u128 = lit.base10_parse().map_err(|_| error2(span, "Integer literal is too large"))?;
+    if !ty.fits(neg, abs) {
+        return Err(error2(span, &format!("Integer literal does not fit in a `{}`", ty.suffix())));
+    }
+    Ok((ty, neg, abs, span))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`swap_bytes_lit()`](super::swap_bytes_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to swap.
+///
+/// # Returns
+/// A new [`TokenStream`] with the byte-swapped literal, suffixed with its type.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the literal does not
+/// fit in its type.
+pub fn swap_bytes_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (ty, neg, abs, span) = parse_input(input)?;
+    let bits: u32 = ty.bits();
+
+    // Find the two's-complement bit pattern of the value in its width
+    let pattern: u128 = if neg { abs.wrapping_neg() } else { abs };
+    let pattern: u128 = if bits == 128 { pattern } else { pattern & ((1 << bits) - 1) };
+
+    // Swap it by swapping the full width and shifting the interesting bytes back down
+    let swapped: u128 = pattern.swap_bytes() >> (128 - bits);
+
+    // Reinterpret the result in the type
+    if ty.is_signed() && swapped >> (bits - 1) == 1 {
+        // It's negative; compute its absolute value
+        let abs: u128 = if bits == 128 { swapped.wrapping_neg() } else { (1 << bits) - swapped };
+        Ok(int_lit(true, abs, Some(ty), span))
+    } else {
+        Ok(int_lit(false, swapped, Some(ty), span))
+    }
+}
@@ -0,0 +1,264 @@
+//  TEMPLATE.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for defining and instantiating reusable token templates.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+
+
+/***** CONSTANTS *****/
+/// The fragment specifiers that parameters may be given.
+const KINDS: [&str; 14] = ["block", "expr", "ident", "item", "lifetime", "literal", "meta", "pat", "pat_param", "path", "stmt", "tt", "ty", "vis"];
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single template definition.
+struct Template {
+    /// Any attributes to put on the generated macro (e.g., `#[macro_export]`).
+    attrs:  TokenStream,
+    /// The name of the template.
+    name:   Ident,
+    /// The parameters of the template, with their fragment specifiers.
+    params: Vec<(Ident, Ident)>,
+    /// The body of the template.
+    body:   Group,
+}
+impl Template {
+    /// Parses a Template from an iterator over [`TokenTree`]s.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed Template.
+    ///
+    /// # Errors
+    /// This function can error if the head of the input was not a valid template.
+    fn parse(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<Self, Diagnostic2> {
+        // Parse the attributes
+        let mut attrs = TokenStream::new();
+        while let Some(TokenTree::Punct(p)) = iter.peek()
+            && p.as_char() == '#'
+        {
+            attrs.extend(iter.next());
+            match iter.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => attrs.extend([TokenTree::Group(group)]),
+                Some(tt) => return Err(error2(tt.span(), "Expected an attribute in square brackets")),
+                None => return Err(error2(Span::call_site(), "Expected an attribute in square brackets")),
+            }
+        }
+
+        // Parse the name and the parameters
+        let name: Ident = match iter.next() {
+            Some(TokenTree::Ident(ident)) => ident,
+            Some(tt) => return Err(error2(tt.span(), "Expected the name of a template")),
+            None => return Err(error2(Span::call_site(), "Expected the name of a template")),
+        };
+        let params: Vec<(Ident, Ident)> = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => Self::parse_params(group.stream())?,
+            Some(tt) => return Err(error2(tt.span(), "Expected parameters in parenthesis")),
+            None => return Err(error2(name.span(), "Expected parameters in parenthesis")),
+        };
+
+        // Parse the arrow and the body
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' && p.spacing() == Spacing::Joint => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '=>'")),
+            None => return Err(error2(name.span(), "Expected '=>'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '>' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '=>'")),
+            None => return Err(error2(name.span(), "Expected '=>'")),
+        }
+        let body: Group = match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+            Some(tt) => return Err(error2(tt.span(), "Expected a body in curly brackets")),
+            None => return Err(error2(name.span(), "Expected a body in curly brackets")),
+        };
+        Ok(Self { attrs, name, params, body })
+    }
+
+    /// Parses the parameters of a template.
+    ///
+    /// # Arguments
+    /// - `input`: The [`TokenStream`] in the parenthesis to parse.
+    ///
+    /// # Returns
+    /// The names of the parameters and their fragment specifiers, which default to `tt`.
+    ///
+    /// # Errors
+    /// This function can error if the input was not a comma-separated list of parameters.
+    fn parse_params(input: TokenStream) -> Result<Vec<(Ident, Ident)>, Diagnostic2> {
+        let mut params: Vec<(Ident, Ident)> = Vec::new();
+        let mut iter = input.into_iter().peekable();
+        while let Some(tt) = iter.next() {
+            let name: Ident = match tt {
+                TokenTree::Ident(ident) => ident,
+                tt => return Err(error2(tt.span(), "Expected the name of a parameter")),
+            };
+            if params.iter().any(|(other, _)| *other == name) {
+                return Err(error2(name.span(), &format!("Parameter `{name}` is given more than once")));
+            }
+
+            // Parse the optional kind
+            let kind: Ident = if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ':') {
+                iter.next();
+                match iter.next() {
+                    Some(TokenTree::Ident(kind)) if KINDS.contains(&kind.to_string().as_str()) => kind,
+                    Some(tt) => {
+                        return Err(error2(tt.span(), &format!("Expected a fragment specifier (i.e., one of {})", KINDS.map(|k| format!("`{k}`")).join(", "))));
+                    },
+                    None => return Err(error2(name.span(), "Expected a fragment specifier after ':'")),
+                }
+            } else {
+                Ident::new("tt", name.span())
+            };
+            params.push((name, kind));
+
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+                None => break,
+            }
+        }
+        Ok(params)
+    }
+
+    /// Generates the `macro_rules!`-macro implementing this template.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] defining the macro.
+    fn generate(self) -> TokenStream {
+        let Self { attrs, name, params, body } = self;
+        let span: Span = name.span();
+
+        // Build the matcher, e.g., `$name:ident, $ty:ty $(,)?`
+        let mut matcher = TokenStream::new();
+        for (i, (param, kind)) in params.into_iter().enumerate() {
+            if i > 0 {
+                matcher.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+            }
+            matcher.extend([
+                TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+                TokenTree::Ident(param),
+                TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                TokenTree::Ident(kind),
+            ]);
+        }
+        matcher.extend([
+            TokenTree::Punct(Punct::new('$', Spacing::Alone)),
+            TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::from(TokenTree::Punct(Punct::new(',', Spacing::Alone))))),
+            TokenTree::Punct(Punct::new('?', Spacing::Alone)),
+        ]);
+
+        // Build the transcriber, i.e., `::macro_toolkit::idents! { ... }`
+        let mut transcriber = TokenStream::new();
+        for segment in ["macro_toolkit", "idents"] {
+            transcriber.extend([TokenTree::Punct(Punct::new(':', Spacing::Joint)), TokenTree::Punct(Punct::new(':', Spacing::Alone))]);
+            transcriber.extend([TokenTree::Ident(Ident::new(segment, span))]);
+        }
+        transcriber.extend([TokenTree::Punct(Punct::new('!', Spacing::Alone)), TokenTree::Group(body)]);
+
+        // Build the macro
+        let mut res: TokenStream = attrs;
+        res.extend([
+            TokenTree::Ident(Ident::new("macro_rules", span)),
+            TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+            TokenTree::Ident(name),
+            TokenTree::Group(Group::new(
+                Delimiter::Brace,
+                TokenStream::from_iter([
+                    TokenTree::Group(Group::new(Delimiter::Parenthesis, matcher)),
+                    TokenTree::Punct(Punct::new('=', Spacing::Joint)),
+                    TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+                    TokenTree::Group(Group::new(Delimiter::Brace, transcriber)),
+                    TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+                ]),
+            )),
+        ]);
+        res
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`template()`](super::template())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the template definitions.
+///
+/// # Returns
+/// A new [`TokenStream`] with a `macro_rules!`-macro for every template.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn template(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut res = TokenStream::new();
+    let mut iter = input.into_iter().peekable();
+    while iter.peek().is_some() {
+        res.extend(Template::parse(&mut iter)?.generate());
+        if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ',' || p.as_char() == ';') {
+            iter.next();
+        }
+    }
+    Ok(res)
+}
+
+/// Defines the implementation of the [`expand_template()`](super::expand_template())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the templates to instantiate and their arguments.
+///
+/// # Returns
+/// A new [`TokenStream`] calling the macro of every template with its arguments.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn expand_template(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut res = TokenStream::new();
+    let mut iter = input.into_iter().peekable();
+    while iter.peek().is_some() {
+        // Parse the path of the template up to its arguments
+        let mut path = TokenStream::new();
+        let args: Group = loop {
+            match iter.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => break group,
+                Some(tt @ (TokenTree::Ident(_) | TokenTree::Punct(_))) => path.extend([tt]),
+                // Allow paths given as `$template:path`
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => path.extend(group.stream()),
+                Some(tt) => return Err(error2(tt.span(), "Expected a path to a template")),
+                None => return Err(error2(Span::call_site(), "Expected a path to a template, followed by arguments in parenthesis")),
+            }
+        };
+        if path.is_empty() {
+            return Err(error2(args.span(), "Expected a path to a template before its arguments"));
+        }
+
+        // Call it
+        let mut group = Group::new(Delimiter::Brace, args.stream());
+        group.set_span(args.span());
+        res.extend(path);
+        res.extend([TokenTree::Punct(Punct::new('!', Spacing::Alone)), TokenTree::Group(group)]);
+
+        // Parse the separator
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' || p.as_char() == ';' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',', ';' or nothing")),
+            None => break,
+        }
+    }
+    Ok(res)
+}
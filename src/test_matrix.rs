@@ -0,0 +1,293 @@
+//  TEST MATRIX.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for generating tests from a matrix of functions and cases.
+//
+
+use std::collections::HashMap;
+
+use macro_toolkit_core::idents::flatten_type;
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::{split_commas, split_punct};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Replaces all parameters in the given tokens with their values.
+///
+/// # Arguments
+/// - `tokens`: The [`TokenStream`] to replace the parameters in.
+/// - `values`: A map from parameter names to the values to replace them with.
+///
+/// # Returns
+/// The same tokens, but with all parameters replaced.
+fn substitute(tokens: TokenStream, values: &HashMap<String, TokenStream>) -> TokenStream {
+    let mut res = TokenStream::new();
+    for tt in tokens {
+        match tt {
+            TokenTree::Ident(ident) => match values.get(&ident.to_string()) {
+                Some(value) => res.extend(value.clone()),
+                None => res.extend([TokenTree::Ident(ident)]),
+            },
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), substitute(group.stream(), values));
+                new.set_span(group.span());
+                res.extend([TokenTree::Group(new)]);
+            },
+            tt => res.extend([tt]),
+        }
+    }
+    res
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines a single case in the matrix.
+struct Case {
+    /// The name of the case, i.e., its given name or its index.
+    name:   String,
+    /// The values of the case, one per parameter.
+    values: Vec<TokenStream>,
+    /// The span of the case, for errors.
+    span:   Span,
+}
+
+/// Defines a function under test in the matrix.
+struct Function {
+    /// The name of the function as used in test names.
+    name: String,
+    /// The path to the function.
+    path: TokenStream,
+}
+
+
+
+/// Parses a labeled list (e.g., `cases = [...]`) from the front of the input.
+///
+/// # Arguments
+/// - `label`: The label of the list.
+/// - `tokens`: The remaining input tokens. The list and a trailing comma are removed on success.
+///
+/// # Returns
+/// The contents of the list, or [`None`] if the input does not start with `label`.
+///
+/// # Errors
+/// This function errors if `label` is not followed by `=` and a list in square brackets.
+fn parse_list(label: &str, tokens: &mut Vec<TokenTree>) -> Result<Option<Group>, Diagnostic2> {
+    let ident: Ident = match tokens.first() {
+        Some(TokenTree::Ident(ident)) if ident == label => ident.clone(),
+        _ => return Ok(None),
+    };
+    match tokens.get(1) {
+        Some(TokenTree::Punct(p)) if p.as_char() == '=' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+        None => return Err(error2(ident.span(), "Expected '='")),
+    }
+    let group: Group = match tokens.get(2) {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group.clone(),
+        Some(tt) => return Err(error2(tt.span(), &format!("Expected a list of {label} in square brackets"))),
+        None => return Err(error2(tokens[1].span(), &format!("Expected a list of {label} in square brackets"))),
+    };
+    match tokens.get(3) {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+        None => return Err(error2(group.span(), "Expected ','")),
+    }
+    tokens.drain(..4);
+    Ok(Some(group))
+}
+
+/// Parses the functions under test.
+///
+/// # Arguments
+/// - `group`: The list of paths to the functions.
+///
+/// # Returns
+/// The parsed [`Function`]s.
+///
+/// # Errors
+/// This function errors if any of the functions is empty or cannot be flattened into a name.
+fn parse_fns(group: Group) -> Result<Vec<Function>, Diagnostic2> {
+    let mut fns: Vec<Function> = Vec::new();
+    for path in split_punct(group.stream(), ',') {
+        let path: TokenStream = path.into_iter().collect();
+        let name: String = flatten_type(path.clone(), "_", "_", group.span())?;
+        fns.push(Function { name, path });
+    }
+    if fns.is_empty() {
+        return Err(error2(group.span(), "Expected at least one function"));
+    }
+    Ok(fns)
+}
+
+/// Parses the cases.
+///
+/// Cases have the syntax `$($name:ident :)? ($($value:expr),*)`.
+///
+/// # Arguments
+/// - `group`: The list of cases.
+///
+/// # Returns
+/// The parsed [`Case`]s.
+///
+/// # Errors
+/// This function errors if any of the cases is invalid.
+fn parse_cases(group: Group) -> Result<Vec<Case>, Diagnostic2> {
+    let mut cases: Vec<Case> = Vec::new();
+    for (i, case) in split_punct(group.stream(), ',').into_iter().enumerate() {
+        let (name, values): (String, &Group) = match case.as_slice() {
+            [TokenTree::Group(values)] if values.delimiter() == Delimiter::Parenthesis => (i.to_string(), values),
+            [TokenTree::Ident(name), TokenTree::Punct(p), TokenTree::Group(values)] if p.as_char() == ':' && values.delimiter() == Delimiter::Parenthesis => {
+                let name: String = name.to_string();
+                (name.strip_prefix("r#").map(String::from).unwrap_or(name), values)
+            },
+            [tt, ..] => return Err(error2(tt.span(), "Expected a case as values in parenthesis, optionally preceded by a name and ':'")),
+            [] => return Err(error2(group.span(), "Expected a case, not an empty one")),
+        };
+        if let Some(prev) = cases.iter().find(|case| case.name == name) {
+            return Err(Diagnostic2::error(values.span(), format!("Duplicate case `{name}`")).span_note(prev.span, "first defined here"));
+        }
+        let span: Span = values.span();
+        let values: Vec<TokenStream> = split_commas(values.stream());
+        cases.push(Case { name, values, span });
+    }
+    if cases.is_empty() {
+        return Err(error2(group.span(), "Expected at least one case"));
+    }
+    Ok(cases)
+}
+
+/// Parses the body template, which looks like a closure.
+///
+/// # Arguments
+/// - `tokens`: The remaining input tokens.
+///
+/// # Returns
+/// The attributes to put on every test, the names of the parameters and the body.
+///
+/// # Errors
+/// This function errors if the tokens are not a closure with a body in curly brackets.
+fn parse_template(tokens: Vec<TokenTree>) -> Result<(TokenStream, Vec<Ident>, Group), Diagnostic2> {
+    let mut iter = tokens.into_iter().peekable();
+
+    // Parse the attributes
+    let mut attrs = TokenStream::new();
+    while let Some(pound) = iter.next_if(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == '#')) {
+        match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => attrs.extend([pound, TokenTree::Group(group)]),
+            Some(tt) => return Err(error2(tt.span(), "Expected an attribute in square brackets")),
+            None => return Err(error2(pound.span(), "Expected an attribute in square brackets")),
+        }
+    }
+
+    // Parse the parameters
+    match iter.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '|' && p.spacing() == Spacing::Alone => {},
+        Some(tt) => return Err(error2(tt.span(), "Expected a template like a closure (e.g., `|f, input, expected| { ... }`)")),
+        None => return Err(error2(Span::call_site(), "Expected a template like a closure (e.g., `|f, input, expected| { ... }`)")),
+    }
+    let mut params: Vec<Ident> = Vec::new();
+    loop {
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) => params.push(ident),
+            Some(tt) => return Err(error2(tt.span(), "Expected the name of a parameter")),
+            None => return Err(error2(Span::call_site(), "Expected the name of a parameter")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(TokenTree::Punct(p)) if p.as_char() == '|' => break,
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or '|'")),
+            None => return Err(error2(Span::call_site(), "Expected either ',' or '|'")),
+        }
+    }
+
+    // Parse the body
+    let body: Group = match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        Some(tt) => return Err(error2(tt.span(), "Expected the body of the template in curly brackets")),
+        None => return Err(error2(Span::call_site(), "Expected the body of the template in curly brackets")),
+    };
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the body of the template"));
+    }
+    Ok((attrs, params, body))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`test_matrix()`](super::test_matrix())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the functions, the cases and the template.
+///
+/// # Returns
+/// A new [`TokenStream`] with one `#[test]`-function for every combination of function and case.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the number of values
+/// in a case does not match the number of parameters.
+pub fn test_matrix(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let fns: Option<Vec<Function>> = parse_list("fns", &mut tokens)?.map(parse_fns).transpose()?;
+    let Some(cases) = parse_list("cases", &mut tokens)? else {
+        let span: Span = tokens.first().map(TokenTree::span).unwrap_or_else(Span::call_site);
+        return Err(error2(span, "Expected `cases = [...],` (optionally preceded by `fns = [...],`)"));
+    };
+    let cases: Vec<Case> = parse_cases(cases)?;
+    let (attrs, mut params, body): (TokenStream, Vec<Ident>, Group) = parse_template(tokens)?;
+
+    // Split off the parameter for the function, if any
+    let fn_param: Option<Ident> = if fns.is_some() {
+        if params.len() < 2 {
+            return Err(error2(body.span(), "Expected a parameter for the function and at least one for the values of the cases"));
+        }
+        Some(params.remove(0))
+    } else {
+        None
+    };
+    if let Some(case) = cases.iter().find(|case| case.values.len() != params.len()) {
+        return Err(error2(case.span, &format!("Expected {} value(s) in every case, one for each parameter, but got {}", params.len(), case.values.len())));
+    }
+
+    // Generate the tests
+    let fns: Vec<Option<Function>> = match fns {
+        Some(fns) => fns.into_iter().map(Some).collect(),
+        None => vec![None],
+    };
+    let mut res = TokenStream::new();
+    for func in &fns {
+        for case in &cases {
+            let mut values: HashMap<String, TokenStream> = params.iter().map(ToString::to_string).zip(case.values.iter().cloned()).collect();
+            let name: String = match (func, &fn_param) {
+                (Some(func), Some(param)) => {
+                    values.insert(param.to_string(), func.path.clone());
+                    format!("test_{}_{}", func.name, case.name)
+                },
+                _ => format!("test_{}", case.name),
+            };
+
+            // Emit `#[test] $attrs fn $name() $body`
+            let mut group = Group::new(Delimiter::Bracket, TokenStream::from(TokenTree::Ident(Ident::new("test", body.span()))));
+            group.set_span(body.span());
+            res.extend([TokenTree::Punct(Punct::new('#', Spacing::Alone)), TokenTree::Group(group)]);
+            res.extend(attrs.clone());
+            res.extend([
+                TokenTree::Ident(Ident::new("fn", body.span())),
+                TokenTree::Ident(Ident::new(&name, case.span)),
+                TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenStream::new())),
+            ]);
+            let mut new = Group::new(Delimiter::Brace, substitute(body.stream(), &values));
+            new.set_span(body.span());
+            res.extend([TokenTree::Group(new)]);
+        }
+    }
+    Ok(res)
+}
@@ -0,0 +1,364 @@
+//  TOOLKIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides an umbrella macro that expands the constructs of all other (function-like) macros in
+//!   this crate anywhere in its input, such that they can be nested in one another.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Spacing, TokenStream, TokenTree};
+
+use crate::diagnostics::Diagnostic2;
+use crate::matching::is_punct;
+
+
+/***** CONSTANTS *****/
+/// The maximum depth of nested expansions, i.e., of macros expanding to macros, like the default
+/// `recursion_limit` of the compiler.
+const RECURSION_LIMIT: usize = 128;
+
+/// The name of this crate, which may prefix the macros (e.g., `macro_toolkit::idents!()`).
+const CRATE_NAME: &str = "macro_toolkit";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// The signature of the implementation of a function-like macro.
+type Macro = fn(TokenStream) -> Result<TokenStream, Diagnostic2>;
+
+/// Finds the implementation of the macro with the given name.
+///
+/// Only the macros that are enabled by their features can be found.
+///
+/// # Arguments
+/// - `name`: The name of the macro (e.g., `idents`).
+///
+/// # Returns
+/// The implementation of the macro, or [`None`] if it isn't one of ours (or isn't enabled).
+fn lookup(name: &str) -> Option<Macro> {
+    let imp: Macro = match name {
+        #[cfg(feature = "match_lit")]
+        "match_lit" => macro_toolkit_core::match_lit::match_lit,
+        #[cfg(feature = "idents")]
+        "idents" => macro_toolkit_core::idents::idents,
+        #[cfg(feature = "lit_len")]
+        "lit_len" => crate::lit_len::lit_len,
+        #[cfg(feature = "digits_of")]
+        "digits_of" => crate::digits_of::digits_of,
+        #[cfg(feature = "typenum_lit")]
+        "typenum_lit" => crate::typenum_lit::typenum_lit,
+        #[cfg(feature = "regex_check")]
+        "regex_check" => crate::regex_check::regex_check,
+        #[cfg(feature = "fmt_check")]
+        "fmt_check" => crate::fmt_check::fmt_check,
+        #[cfg(feature = "uuid_lit")]
+        "uuid_lit" => crate::uuid_lit::uuid_lit,
+        #[cfg(feature = "duration_lit")]
+        "duration_lit" => crate::duration_lit::duration_lit,
+        #[cfg(feature = "size_lit")]
+        "size_lit" => crate::size_lit::size_lit,
+        #[cfg(feature = "color_lit")]
+        "color_lit" => crate::color_lit::color_lit,
+        #[cfg(feature = "bitpattern")]
+        "bitpattern" => crate::bitpattern::bitpattern,
+        #[cfg(feature = "swap_bytes_lit")]
+        "swap_bytes_lit" => crate::swap_bytes_lit::swap_bytes_lit,
+        #[cfg(feature = "parse_lit")]
+        "parse_lit" => crate::parse_lit::parse_lit,
+        #[cfg(feature = "str_predicates")]
+        "str_predicates" => crate::str_predicates::str_predicates,
+        #[cfg(feature = "calc")]
+        "calc" => crate::calc::calc,
+        #[cfg(feature = "min_max_lit")]
+        "min_lit" => crate::min_max_lit::min_lit,
+        #[cfg(feature = "min_max_lit")]
+        "max_lit" => crate::min_max_lit::max_lit,
+        #[cfg(feature = "cmp_lit")]
+        "cmp_lit" => crate::cmp_lit::cmp_lit,
+        #[cfg(feature = "bits_needed")]
+        "bits_needed" => crate::bits_needed::bits_needed,
+        #[cfg(feature = "align")]
+        "align_up" => crate::align::align_up,
+        #[cfg(feature = "align")]
+        "align_down" => crate::align::align_down,
+        #[cfg(feature = "range_expand")]
+        "range_expand" => crate::range_expand::range_expand,
+        #[cfg(feature = "bitmask")]
+        "bitmask" => crate::bitmask::bitmask,
+        #[cfg(feature = "fixed_point")]
+        "fixed_point" => crate::fixed_point::fixed_point,
+        #[cfg(feature = "rand_lit")]
+        "rand_lit" => crate::rand_lit::rand_lit,
+        #[cfg(feature = "unique_id")]
+        "unique_id" => crate::unique_id::unique_id,
+        #[cfg(feature = "gensym")]
+        "gensym" => crate::gensym::gensym,
+        #[cfg(feature = "build_time")]
+        "build_time" => crate::build_time::build_time,
+        #[cfg(feature = "const_assert_lit")]
+        "const_assert_lit" => crate::const_assert_lit::const_assert_lit,
+        #[cfg(feature = "rustc_since")]
+        "rustc_since" => crate::rustc_since::rustc_since,
+        #[cfg(feature = "impl_for_tuples")]
+        "impl_for_tuples" => crate::impl_for_tuples::impl_for_tuples,
+        #[cfg(feature = "template")]
+        "template" => crate::template::template,
+        #[cfg(feature = "template")]
+        "expand_template" => crate::template::expand_template,
+        #[cfg(feature = "export_tokens")]
+        "import_tokens" => crate::export_tokens::import_tokens,
+        #[cfg(feature = "respan")]
+        "respan" => crate::respan::respan,
+        #[cfg(feature = "crate_path")]
+        "crate_path" => crate::crate_path::crate_path,
+        #[cfg(feature = "pub_macro")]
+        "pub_macro" => crate::pub_macro::pub_macro,
+        #[cfg(feature = "macro_v2")]
+        "macro_v2" => crate::macro_v2::macro_v2,
+        #[cfg(feature = "trace_expansion")]
+        "trace_tokens" => crate::trace_expansion::trace_tokens,
+        #[cfg(feature = "attr_alias")]
+        "attr_alias" => crate::attr_alias::attr_alias,
+        #[cfg(feature = "derive_alias")]
+        "derive_alias" => crate::derive_alias::derive_alias,
+        #[cfg(feature = "item_name")]
+        "item_name" => crate::item_name::item_name,
+        #[cfg(feature = "item_parts")]
+        "item_parts" => crate::item_parts::item_parts,
+        #[cfg(feature = "generics_split")]
+        "generics_split" => crate::generics_split::generics_split,
+        #[cfg(feature = "fields_of")]
+        "fields_of" => crate::fields_of::fields_of,
+        #[cfg(feature = "variants_of")]
+        "variants_of" => crate::variants_of::variants_of,
+        #[cfg(feature = "match_vis")]
+        "match_vis" => crate::match_vis::match_vis,
+        #[cfg(feature = "match_type")]
+        "match_type" => crate::match_type::match_type,
+        #[cfg(feature = "match_expr")]
+        "match_expr" => crate::match_expr::match_expr,
+        #[cfg(feature = "match_path")]
+        "match_path" => crate::match_path::match_path,
+        #[cfg(feature = "match_meta")]
+        "match_meta" => crate::match_meta::match_meta,
+        #[cfg(feature = "kv_args")]
+        "kv_args" => crate::kv_args::kv_args,
+        #[cfg(feature = "where_append")]
+        "where_append" => crate::where_append::where_append,
+        #[cfg(feature = "turbofish")]
+        "turbofish" => crate::turbofish::turbofish,
+        #[cfg(feature = "overload")]
+        "overload" => crate::overload::overload,
+        #[cfg(feature = "named_args")]
+        "named_args" => crate::named_args::named_args,
+        #[cfg(feature = "strip_generics")]
+        "strip_generics" => crate::strip_generics::strip_generics,
+        #[cfg(feature = "generics_merge")]
+        "generics_merge" => crate::generics_merge::generics_merge,
+        #[cfg(feature = "type_ident")]
+        "type_ident" => crate::type_ident::type_ident,
+        #[cfg(feature = "test_matrix")]
+        "test_matrix" => crate::test_matrix::test_matrix,
+        #[cfg(feature = "const_table")]
+        "const_table" => crate::const_table::const_table,
+        #[cfg(feature = "lifetimes_of")]
+        "lifetimes_of" => crate::lifetimes_of::lifetimes_of,
+        #[cfg(feature = "compile_warning")]
+        "compile_warning" => crate::compile_warning::compile_warning,
+        #[cfg(feature = "compile_note")]
+        "compile_note" => crate::compile_note::compile_note,
+        #[cfg(feature = "error_at")]
+        "error_at" => crate::error_at::error_at,
+        #[cfg(feature = "debug_tokens")]
+        "debug_tokens" => crate::debug_tokens::debug_tokens,
+        #[cfg(feature = "dump_expansion")]
+        "dump_expansion" => crate::dump_expansion::dump_expansion,
+        #[cfg(feature = "assert_expands_to")]
+        "assert_expands_to" => crate::assert_expands_to::assert_expands_to,
+        #[cfg(feature = "include_tokens")]
+        "include_tokens" => crate::include_tokens::include_tokens,
+        #[cfg(feature = "env_lit")]
+        "env_lit" => crate::env_lit::env_lit,
+        #[cfg(feature = "env_or")]
+        "env_or" => crate::env_lit::env_or,
+        #[cfg(feature = "option_env_match")]
+        "option_env_match" => crate::option_env_match::option_env_match,
+        #[cfg(feature = "glob_files")]
+        "glob_files" => crate::glob_files::glob_files,
+        #[cfg(feature = "cargo_manifest")]
+        "cargo_manifest" => crate::cargo_manifest::cargo_manifest,
+        #[cfg(feature = "crate_version_parts")]
+        "crate_version_parts" => crate::crate_version_parts::crate_version_parts,
+        #[cfg(feature = "span_location")]
+        "span_location" => crate::span_location::span_location,
+        #[cfg(feature = "metavar_exprs")]
+        "meta_count" => crate::metavar_exprs::meta_count,
+        #[cfg(feature = "metavar_exprs")]
+        "meta_index" => crate::metavar_exprs::meta_index,
+        #[cfg(feature = "metavar_exprs")]
+        "meta_ignore" => crate::metavar_exprs::meta_ignore,
+        #[cfg(feature = "ident_case_check")]
+        "ident_case_check" => crate::ident_case_check::ident_case_check,
+        #[cfg(feature = "ident_predicates")]
+        "keyword_check" => crate::ident_predicates::keyword_check,
+        #[cfg(feature = "ident_predicates")]
+        "is_valid_ident" => crate::ident_predicates::is_valid_ident,
+        #[cfg(feature = "str_switch")]
+        "str_switch" => crate::str_switch::str_switch,
+        #[cfg(feature = "doc_fmt")]
+        "doc_fmt" => crate::doc_fmt::doc_fmt,
+        "toolkit" => toolkit,
+        _ => return None,
+    };
+    Some(imp)
+}
+
+/// Checks whether the given tokens start with a path separator (`::`).
+///
+/// # Arguments
+/// - `tokens`: The tokens to check.
+/// - `i`: The index to start checking at.
+///
+/// # Returns
+/// True if they do, or false otherwise.
+fn is_path_sep(tokens: &[TokenTree], i: usize) -> bool {
+    matches!(tokens.get(i), Some(TokenTree::Punct(p)) if p.as_char() == ':' && p.spacing() == Spacing::Joint) && is_punct(tokens.get(i + 1), ':')
+}
+
+/// Checks whether a group should be pasted by [`idents()`](macro_toolkit_core::idents::idents),
+/// i.e., whether it is a `[< >]` or a `{< >}`.
+///
+/// # Arguments
+/// - `group`: The [`Group`] to check.
+///
+/// # Returns
+/// True if it should, or false otherwise.
+fn is_paste(group: &Group) -> bool {
+    let mut tokens = group.stream().into_iter();
+    match group.delimiter() {
+        Delimiter::Bracket => is_punct(tokens.next().as_ref(), '<') && is_punct(tokens.last().as_ref(), '>'),
+        Delimiter::Brace => is_punct(tokens.next().as_ref(), '<'),
+        _ => false,
+    }
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses a call to one of our macros (e.g., `idents! { ... }` or `macro_toolkit::idents!()`).
+///
+/// Calls through other paths (e.g., `foo::idents!()`) are left alone, as they are not ours.
+///
+/// # Arguments
+/// - `tokens`: The tokens to parse the call from.
+/// - `i`: The index of the token to start parsing at.
+///
+/// # Returns
+/// The number of tokens making up the call, the name of the macro, its implementation and the
+/// group with its arguments; or [`None`] if there is no call to our macros at `i`.
+fn parse_call(tokens: &[TokenTree], i: usize) -> Option<(usize, &Ident, Macro, &Group)> {
+    // Paths are only ours if they are (optionally absolutely) in this crate
+    let mut j: usize = i;
+    if is_path_sep(tokens, j) {
+        if !matches!(tokens.get(j + 2), Some(TokenTree::Ident(ident)) if ident == CRATE_NAME) {
+            return None;
+        }
+        j += 2;
+    } else if i >= 1 && is_punct(tokens.get(i - 1), ':') {
+        return None;
+    }
+    if matches!(tokens.get(j), Some(TokenTree::Ident(ident)) if ident == CRATE_NAME) && is_path_sep(tokens, j + 1) {
+        j += 3;
+    }
+
+    // Then the name, the bang and the arguments
+    let Some(TokenTree::Ident(name)) = tokens.get(j) else { return None };
+    let imp: Macro = lookup(&name.to_string())?;
+    if !is_punct(tokens.get(j + 1), '!') {
+        return None;
+    }
+    let Some(TokenTree::Group(args)) = tokens.get(j + 2) else { return None };
+    if args.delimiter() == Delimiter::None {
+        return None;
+    }
+    Some((j + 3 - i, name, imp, args))
+}
+
+
+
+
+
+/***** EXPANSION *****/
+/// Expands all calls to our macros and all pastes in the given tokens, at any depth.
+///
+/// Calls are expanded outside-in, like the compiler does: the macro is given its arguments as
+/// written, after which its output is expanded in turn.
+///
+/// # Arguments
+/// - `input`: The [`TokenStream`] to expand.
+/// - `depth`: The number of expansions that produced `input`.
+/// - `buf`: The buffer to push the expanded tokens to.
+///
+/// # Errors
+/// This function errors if any of the macros or pastes failed, or if the [`RECURSION_LIMIT`] was
+/// reached.
+fn expand(input: TokenStream, depth: usize, buf: &mut Vec<TokenTree>) -> Result<(), Diagnostic2> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut i: usize = 0;
+    while i < tokens.len() {
+        // Calls to our macros
+        if let Some((len, name, imp, args)) = parse_call(&tokens, i) {
+            if depth >= RECURSION_LIMIT {
+                return Err(Diagnostic2::error(name.span(), format!("Recursion limit reached while expanding `{name}!()`"))
+                    .help(format!("`toolkit!()` expands at most {RECURSION_LIMIT} nested macros")));
+            }
+            expand(imp(args.stream())?, depth + 1, buf)?;
+            i += len;
+            continue;
+        }
+
+        match &tokens[i] {
+            // Pastes
+            TokenTree::Group(group) if is_paste(group) => {
+                buf.extend(macro_toolkit_core::idents::idents(TokenStream::from(tokens[i].clone()))?);
+            },
+            // Other groups are expanded recursively
+            TokenTree::Group(group) => {
+                let start: usize = buf.len();
+                expand(group.stream(), depth, buf)?;
+                let mut new = Group::new(group.delimiter(), buf.drain(start..).collect());
+                new.set_span(group.span());
+                buf.push(TokenTree::Group(new));
+            },
+            tt => buf.push(tt.clone()),
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`toolkit()`](super::toolkit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the tokens to expand.
+///
+/// # Returns
+/// A new [`TokenStream`] with the same tokens, but with all calls to the other macros in this crate
+/// and all `[< >]` and `{< >}` expanded.
+///
+/// # Errors
+/// This function errors if any of the expanded macros did.
+pub fn toolkit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let mut buf: Vec<TokenTree> = Vec::new();
+    expand(input, 0, &mut buf)?;
+    Ok(buf.into_iter().collect())
+}
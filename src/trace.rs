@@ -0,0 +1,121 @@
+//  TRACE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements the tracing mode enabled by the `MACRO_TOOLKIT_TRACE` environment variable, in
+//!   which every macro of this crate logs its input and output tokens while compiling.
+//
+
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use proc_macro::{Span, TokenStream};
+
+
+/***** CONSTANTS *****/
+/// The environment variable that enables tracing.
+const TRACE_VAR: &str = "MACRO_TOOLKIT_TRACE";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Defines where traces are written to.
+enum Target {
+    /// Traces are printed to stderr.
+    Stderr,
+    /// Traces are appended to the file at the given path.
+    File(PathBuf),
+}
+
+/// Finds where to write traces to, if tracing is enabled.
+///
+/// This is read from `MACRO_TOOLKIT_TRACE` once per compilation. It is enabled if that variable is
+/// set to anything but nothing or `0`, where `1` and `stderr` print traces to stderr and anything
+/// else is the path of a file to append them to (relative to the calling crate's manifest
+/// directory).
+///
+/// # Returns
+/// The [`Target`] to write traces to, or [`None`] if tracing is disabled.
+fn target() -> Option<&'static Target> {
+    static TARGET: OnceLock<Option<Target>> = OnceLock::new();
+    TARGET
+        .get_or_init(|| {
+            let value: String = std::env::var(TRACE_VAR).ok()?;
+            match value.trim() {
+                "" | "0" => None,
+                "1" | "stderr" => Some(Target::Stderr),
+                path => Some(Target::File(std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from).unwrap_or_default().join(path))),
+            }
+        })
+        .as_ref()
+}
+
+/// Writes a trace of a macro call.
+///
+/// Failing to write the trace is reported on stderr, but does not fail the macro.
+///
+/// # Arguments
+/// - `target`: The [`Target`] to write to.
+/// - `label`: The name of the macro (e.g., `idents!()` or `#[with_idents]`).
+/// - `parts`: The labelled token streams to write (e.g., its input and output).
+fn write(target: &Target, label: &str, parts: &[(&str, &TokenStream)]) {
+    let span: Span = Span::call_site();
+    let mut text: String = format!("[{TRACE_VAR}] {label} at {}:{}:{}\n", span.file(), span.line(), span.column());
+    for (name, tokens) in parts {
+        text.push_str(&format!("    {:<8}{tokens}\n", format!("{name}:")));
+    }
+    match target {
+        Target::Stderr => eprint!("{text}"),
+        Target::File(path) => {
+            if let Err(err) = OpenOptions::new().create(true).append(true).open(path).and_then(|mut handle| handle.write_all(text.as_bytes())) {
+                eprintln!("[{TRACE_VAR}] Failed to write to '{}': {err}", path.display());
+            }
+        },
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Expands a function-like macro, tracing its input and output if tracing is enabled.
+///
+/// # Arguments
+/// - `label`: The name of the macro (e.g., `idents!()`).
+/// - `input`: The input to the macro.
+/// - `expand`: The implementation of the macro.
+///
+/// # Returns
+/// The output of `expand`.
+pub fn traced(label: &str, input: TokenStream, expand: impl FnOnce(TokenStream) -> TokenStream) -> TokenStream {
+    let Some(target) = target() else {
+        return expand(input);
+    };
+    let output: TokenStream = expand(input.clone());
+    write(target, label, &[("input", &input), ("output", &output)]);
+    output
+}
+
+/// Expands an attribute macro, tracing its arguments, item and output if tracing is enabled.
+///
+/// # Arguments
+/// - `label`: The name of the macro (e.g., `#[with_idents]`).
+/// - `attr`: The arguments to the attribute.
+/// - `item`: The item the attribute is placed on.
+/// - `expand`: The implementation of the macro.
+///
+/// # Returns
+/// The output of `expand`.
+pub fn traced_attr(label: &str, attr: TokenStream, item: TokenStream, expand: impl FnOnce(TokenStream, TokenStream) -> TokenStream) -> TokenStream {
+    let Some(target) = target() else {
+        return expand(attr, item);
+    };
+    let output: TokenStream = expand(attr.clone(), item.clone());
+    write(target, label, &[("attr", &attr), ("item", &item), ("output", &output)]);
+    output
+}
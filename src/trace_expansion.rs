@@ -0,0 +1,316 @@
+//  TRACE EXPANSION.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides macros for printing tokens as they are expanded, to debug (declarative) macros.
+//
+
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2, item_error};
+use crate::lits::parse_lit;
+
+
+/***** TOKEN PARSING *****/
+/// Defines the options that can be given to both macros.
+#[derive(Default)]
+struct Options {
+    /// The label to print the tokens with.
+    label: Option<(String, Span)>,
+    /// The file to append the tokens to instead of printing them.
+    file:  Option<(String, Span)>,
+}
+impl Options {
+    /// Parses a single `key = "value"`-option and adds it to these Options.
+    ///
+    /// # Arguments
+    /// - `key`: The already parsed key of the option.
+    /// - `iter`: The iterator yielding the tokens after the key.
+    ///
+    /// # Errors
+    /// This function errors if the option is unknown, given twice or has no string value.
+    fn parse_option(&mut self, key: Ident, iter: &mut impl Iterator<Item = TokenTree>) -> Result<(), Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+            None => return Err(error2(key.span(), "Expected '='")),
+        }
+        let value: (String, Span) = match iter.next().map(parse_lit).transpose()? {
+            Some(Lit::Str(lit)) => (lit.value(), lit.span()),
+            Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+            None => return Err(error2(key.span(), "Expected a string literal")),
+        };
+        let slot: &mut Option<(String, Span)> = if key == "label" {
+            &mut self.label
+        } else if key == "file" {
+            &mut self.file
+        } else {
+            return Err(error2(key.span(), &format!("Unknown option `{key}` (expected `label` or `file`)")));
+        };
+        if slot.is_some() {
+            return Err(error2(key.span(), &format!("Option `{key}` is given more than once")));
+        }
+        *slot = Some(value);
+        Ok(())
+    }
+
+    /// Parses Options from the arguments to the [`trace_expansion()`](super::trace_expansion())-attribute.
+    ///
+    /// # Arguments
+    /// - `attr`: The arguments to the attribute.
+    ///
+    /// # Returns
+    /// The parsed Options.
+    ///
+    /// # Errors
+    /// This function errors if the arguments are invalid.
+    fn parse(attr: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut res = Self::default();
+        let mut iter = attr.into_iter();
+        while let Some(tt) = iter.next() {
+            match tt {
+                TokenTree::Ident(key) => res.parse_option(key, &mut iter)?,
+                tt => return Err(error2(tt.span(), "Expected an option (e.g., `file = \"...\"`)")),
+            }
+            match iter.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+                Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing")),
+                None => break,
+            }
+        }
+        Ok(res)
+    }
+
+    /// Serializes these Options back to tokens, such that they can be passed to
+    /// [`trace_tokens()`](super::trace_tokens()).
+    ///
+    /// # Arguments
+    /// - `label`: The label to use instead of the one in these Options.
+    ///
+    /// # Returns
+    /// A [`TokenStream`] encoding `label = "...", (file = "...",)?`.
+    fn to_tokens(&self, label: &str) -> TokenStream {
+        let mut res = TokenStream::new();
+        for (key, value) in [("label", Some(label)), ("file", self.file.as_ref().map(|(file, _)| file.as_str()))] {
+            if let Some(value) = value {
+                res.extend([
+                    TokenTree::Ident(Ident::new(key, Span::call_site())),
+                    TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+                    TokenTree::Literal(Literal::string(value)),
+                    TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+                ]);
+            }
+        }
+        res
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Prints the given tokens to stderr, or appends them to a file.
+///
+/// # Arguments
+/// - `label`: The label to print the tokens with.
+/// - `tokens`: The tokens to print.
+/// - `file`: The file to append the tokens to instead, relative to the calling crate's manifest
+///   directory.
+///
+/// # Errors
+/// This function errors if the file could not be written.
+fn trace(label: &str, tokens: &TokenStream, file: Option<&(String, Span)>) -> Result<(), Diagnostic2> {
+    let text: String = format!("[trace_expansion] {label}:\n    {tokens}\n");
+    match file {
+        Some((file, span)) => {
+            let path: PathBuf = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from).unwrap_or_default().join(file);
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .and_then(|mut handle| handle.write_all(text.as_bytes()))
+                .map_err(|err| error2(*span, &format!("Failed to write to '{}': {err}", path.display())))
+        },
+        None => {
+            eprint!("{text}");
+            Ok(())
+        },
+    }
+}
+
+/// Finds the name of a `macro_rules!`-macro.
+///
+/// # Arguments
+/// - `item`: The item to find the name of.
+///
+/// # Returns
+/// The name of the macro and the group with its rules, or [`None`] if the item is not a
+/// `macro_rules!`-macro.
+fn macro_rules(item: &[TokenTree]) -> Option<(usize, Ident, Group)> {
+    // Skip the attributes
+    let mut i: usize = 0;
+    while matches!(item.get(i), Some(TokenTree::Punct(p)) if p.as_char() == '#') {
+        i += 2;
+    }
+    match (item.get(i), item.get(i + 1), item.get(i + 2), item.get(i + 3)) {
+        (Some(TokenTree::Ident(kw)), Some(TokenTree::Punct(p)), Some(TokenTree::Ident(name)), Some(TokenTree::Group(rules)))
+            if kw == "macro_rules" && p.as_char() == '!' =>
+        {
+            Some((i + 3, name.clone(), rules.clone()))
+        },
+        _ => None,
+    }
+}
+
+/// Wraps the transcribers of a `macro_rules!`-macro in calls to
+/// [`trace_tokens()`](super::trace_tokens()).
+///
+/// # Arguments
+/// - `name`: The name of the macro.
+/// - `rules`: The group with the rules of the macro.
+/// - `opts`: The [`Options`] to pass to every call.
+///
+/// # Returns
+/// The new group with rules.
+///
+/// # Errors
+/// This function errors if the rules are malformed.
+fn wrap_rules(name: &Ident, rules: &Group, opts: &Options) -> Result<Group, Diagnostic2> {
+    let label: String = opts.label.as_ref().map(|(label, _)| label.clone()).unwrap_or_else(|| format!("{name}!"));
+    let mut res = TokenStream::new();
+    let mut iter = rules.stream().into_iter();
+    let mut arm: usize = 0;
+    while let Some(matcher) = iter.next() {
+        arm += 1;
+        let (matcher, eq, gt, transcriber) = match (matcher, iter.next(), iter.next(), iter.next()) {
+            (TokenTree::Group(matcher), Some(TokenTree::Punct(eq)), Some(TokenTree::Punct(gt)), Some(TokenTree::Group(transcriber)))
+                if eq.as_char() == '=' && gt.as_char() == '>' =>
+            {
+                (matcher, eq, gt, transcriber)
+            },
+            (tt, ..) => return Err(error2(tt.span(), "Expected a rule of a `macro_rules!`-macro")),
+        };
+
+        // Wrap the transcriber as `{ ::macro_toolkit::trace_tokens! { label = "...", { ... } } }`
+        let mut args: TokenStream = opts.to_tokens(&format!("{label} (arm {arm})"));
+        args.extend([TokenTree::Group(Group::new(Delimiter::Brace, transcriber.stream()))]);
+        let call = TokenStream::from_iter([
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(Ident::new("macro_toolkit", Span::call_site())),
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(Ident::new("trace_tokens", Span::call_site())),
+            TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+            TokenTree::Group(Group::new(Delimiter::Brace, args)),
+        ]);
+        let mut wrapped = Group::new(Delimiter::Brace, call);
+        wrapped.set_span(transcriber.span());
+        res.extend([TokenTree::Group(matcher), TokenTree::Punct(eq), TokenTree::Punct(gt), TokenTree::Group(wrapped)]);
+
+        // Keep the separating semicolon
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ';' => res.extend([TokenTree::Punct(p)]),
+            Some(tt) => return Err(error2(tt.span(), "Expected ';'")),
+            None => break,
+        }
+    }
+
+    let mut group = Group::new(rules.delimiter(), res);
+    group.set_span(rules.span());
+    Ok(group)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`trace_expansion()`](super::trace_expansion())-macro.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the options to the attribute.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] with the item, where the transcribers of `macro_rules!`-macros are
+/// wrapped in calls to [`trace_tokens()`](super::trace_tokens()).
+///
+/// # Errors
+/// This function may error if the options are invalid or if the trace could not be written.
+pub fn trace_expansion(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // NOTE: The error ends up in item position, so needs to be a valid item
+    trace_item(attr, item.clone()).map_err(|err| item_error(err).with_tokens(item))
+}
+
+/// Implements [`trace_expansion()`], but without making errors valid items.
+///
+/// # Arguments
+/// - `attr`: Some [`TokenStream`] containing the options to the attribute.
+/// - `item`: Some [`TokenStream`] containing the item the attribute is placed on.
+///
+/// # Returns
+/// A new [`TokenStream`] with the (wrapped) item.
+///
+/// # Errors
+/// This function may error if the options are invalid or if the trace could not be written.
+fn trace_item(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let opts = Options::parse(attr)?;
+    let mut tokens: Vec<TokenTree> = item.clone().into_iter().collect();
+    match macro_rules(&tokens) {
+        // Trace every expansion of the macro
+        Some((i, name, rules)) => {
+            tokens[i] = TokenTree::Group(wrap_rules(&name, &rules, &opts)?);
+            Ok(tokens.into_iter().collect())
+        },
+        // Trace the item itself
+        None => {
+            let label: &str = opts.label.as_ref().map(|(label, _)| label.as_str()).unwrap_or("item");
+            trace(label, &item, opts.file.as_ref())?;
+            Ok(item)
+        },
+    }
+}
+
+
+
+/// Defines the implementation of the [`trace_tokens()`](super::trace_tokens())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the options and the tokens to trace.
+///
+/// # Returns
+/// A new [`TokenStream`] with the traced tokens, unchanged.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro or if the trace could not be
+/// written.
+pub fn trace_tokens(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Parse the options, up to the tokens in curly brackets
+    let mut opts = Options::default();
+    let mut iter = input.into_iter().peekable();
+    let tokens: TokenStream = loop {
+        match iter.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace && iter.peek().is_none() => break group.stream(),
+            Some(TokenTree::Ident(key)) => opts.parse_option(key, &mut iter)?,
+            Some(tt) => return Err(error2(tt.span(), "Expected an option (e.g., `file = \"...\"`) or the tokens to trace in curly brackets")),
+            None => return Err(error2(Span::call_site(), "Expected the tokens to trace in curly brackets")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ','")),
+            None => return Err(error2(Span::call_site(), "Expected the tokens to trace in curly brackets")),
+        }
+    };
+
+    // Trace them
+    let label: &str = opts.label.as_ref().map(|(label, _)| label.as_str()).unwrap_or("tokens");
+    trace(label, &tokens, opts.file.as_ref())?;
+    Ok(tokens)
+}
@@ -0,0 +1,115 @@
+//  TURBOFISH.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for converting type paths into their expression (turbofish) form.
+//
+
+use proc_macro2::{Delimiter, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::matching::{flatten_none, is_punct, skip_angles};
+use crate::utils::Callback;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Converts a type path into its turbofish form.
+///
+/// # Arguments
+/// - `tokens`: The (flattened) tokens of the path.
+/// - `span`: A span to report errors at if the path is empty.
+///
+/// # Returns
+/// The same path, but with `::` inserted before every segment's generic arguments.
+///
+/// # Errors
+/// This function errors if the tokens are not a type path.
+fn convert(tokens: &[TokenTree], span: Span) -> Result<TokenStream, Diagnostic2> {
+    /// What the previous part of the path was.
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    enum Prev {
+        /// Nothing, we're at the start.
+        Start,
+        /// A `::`, directly following a segment name.
+        SegmentSep,
+        /// A `::`, at the start or following generic arguments.
+        Sep,
+        /// The name of a segment.
+        Ident,
+        /// Generic arguments (or a qualified self-type).
+        Args,
+    }
+
+    let mut res = TokenStream::new();
+    let mut prev: Prev = Prev::Start;
+    let mut i: usize = 0;
+    while let Some(tt) = tokens.get(i) {
+        match tt {
+            TokenTree::Ident(_) if matches!(prev, Prev::Start | Prev::SegmentSep | Prev::Sep) => {
+                res.extend([tt.clone()]);
+                prev = Prev::Ident;
+                i += 1;
+            },
+            TokenTree::Punct(p) if p.as_char() == ':' && p.spacing() == Spacing::Joint && is_punct(tokens.get(i + 1), ':') => {
+                if matches!(prev, Prev::SegmentSep | Prev::Sep) {
+                    return Err(error2(p.span(), "Expected a path segment"));
+                }
+                res.extend(tokens[i..i + 2].iter().cloned());
+                prev = if prev == Prev::Ident { Prev::SegmentSep } else { Prev::Sep };
+                i += 2;
+            },
+            TokenTree::Punct(p) if p.as_char() == '<' && matches!(prev, Prev::Start | Prev::Ident | Prev::SegmentSep) => {
+                let Some(end) = skip_angles(tokens, i) else {
+                    return Err(error2(p.span(), "Unclosed generic arguments"));
+                };
+
+                // Only insert the `::` if it isn't there yet (and we're not parsing a qualified self-type)
+                if prev == Prev::Ident {
+                    let mut colon = Punct::new(':', Spacing::Joint);
+                    colon.set_span(p.span());
+                    res.extend([TokenTree::Punct(colon.clone()), TokenTree::Punct(colon)]);
+                }
+                res.extend(tokens[i..end].iter().cloned());
+                prev = Prev::Args;
+                i = end;
+            },
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis && prev == Prev::Ident => {
+                return Err(error2(group.span(), "Parenthesized generic arguments (e.g., `Fn(u8)`) cannot be written in expression position"));
+            },
+            tt => return Err(error2(tt.span(), "Expected a type path (e.g., `Foo<T>` or `std::vec::Vec<u8>`)")),
+        }
+    }
+    match prev {
+        Prev::Ident | Prev::Args => Ok(res),
+        Prev::Start => Err(error2(span, "Expected a type path (e.g., `Foo<T>` or `std::vec::Vec<u8>`)")),
+        Prev::SegmentSep | Prev::Sep => Err(error2(tokens.last().map(TokenTree::span).unwrap_or(span), "Expected a path segment after '::'")),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`turbofish()`](super::turbofish())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the type path and an optional callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the path in turbofish form, or a call to the callback with it.
+///
+/// # Errors
+/// This function may error if the input is not a type path.
+pub fn turbofish(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (path, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let mut tokens: Vec<TokenTree> = Vec::new();
+    flatten_none(path, &mut tokens);
+    let path: TokenStream = convert(&tokens, Span::call_site())?;
+
+    // Emit it
+    match callback {
+        Some(callback) => Ok(callback.call_item(path)),
+        None => Ok(path),
+    }
+}
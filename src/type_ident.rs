@@ -0,0 +1,114 @@
+//  TYPE IDENT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for flattening types into identifiers.
+//
+
+use macro_toolkit_core::idents::{flatten_type, new_ident};
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::utils::Callback;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Flattens a type into an identifier, converting to- and from [`proc_macro`]'s types.
+///
+/// # Arguments
+/// - `tokens`: The tokens of the type.
+/// - `sep`: The separator to put between the parts of the type.
+/// - `path_sep`: The separator to put between the segments of a path.
+/// - `span`: The span to report errors at if the type is empty.
+///
+/// # Returns
+/// The flattened type as an identifier.
+///
+/// # Errors
+/// This function errors if the type cannot be flattened, or if the result is not a valid
+/// identifier.
+fn flatten(tokens: TokenStream, sep: &str, path_sep: &str, span: Span) -> Result<Ident, Diagnostic2> {
+    let first: Option<Span> = tokens.clone().into_iter().next().map(|tt| tt.span());
+    flatten_type(tokens, sep, path_sep, span).and_then(|name| new_ident(&name, first, span))
+}
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the options at the start of the input, if any.
+///
+/// Options have the syntax `$name:ident = $value:literal,`, where `$name` is either `sep` or
+/// `path_sep`.
+///
+/// # Arguments
+/// - `tokens`: The input tokens. Any options are removed from the front.
+///
+/// # Returns
+/// The separator and the path separator, or [`None`] for those that are not given.
+///
+/// # Errors
+/// This function errors if an option is given twice or has an invalid value.
+fn parse_options(tokens: &mut Vec<TokenTree>) -> Result<(Option<String>, Option<String>), Diagnostic2> {
+    let (mut sep, mut path_sep): (Option<String>, Option<String>) = (None, None);
+    loop {
+        let (name, eq) = match tokens.as_slice() {
+            [TokenTree::Ident(name), TokenTree::Punct(eq), ..] if (name == "sep" || name == "path_sep") && eq.as_char() == '=' => (name.clone(), eq.clone()),
+            _ => return Ok((sep, path_sep)),
+        };
+        let value: String = match tokens.get(2) {
+            Some(TokenTree::Literal(lit)) => lit.to_string(),
+            // Unwrap values given as e.g. `$sep:literal`
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None => group.stream().to_string(),
+            Some(tt) => return Err(error2(tt.span(), "Expected a string literal with a separator")),
+            None => return Err(error2(eq.span(), "Expected a string literal with a separator")),
+        };
+        let Some(value) = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) else {
+            return Err(error2(tokens[2].span(), "Expected a string literal with a separator"));
+        };
+        match tokens.get(3) {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected ',' and then the type")),
+            None => return Err(error2(tokens[2].span(), "Expected ',' and then the type")),
+        }
+        let slot: &mut Option<String> = if name == "sep" { &mut sep } else { &mut path_sep };
+        if slot.replace(value.into()).is_some() {
+            return Err(error2(name.span(), &format!("Duplicate option `{name}`")));
+        }
+        tokens.drain(..4);
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`type_ident()`](super::type_ident())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the options, the type and an optional callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with the type flattened into an identifier, or a call to the callback
+/// with it.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the flattened type is
+/// not a valid identifier.
+pub fn type_ident(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (ty, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(input)?;
+    let mut tokens: Vec<TokenTree> = ty.into_iter().collect();
+    let (sep, path_sep): (Option<String>, Option<String>) = parse_options(&mut tokens)?;
+    let sep: String = sep.unwrap_or_else(|| "_".into());
+    let path_sep: String = path_sep.unwrap_or_else(|| sep.clone());
+
+    // Flatten the type
+    let ident: Ident = flatten(tokens.into_iter().collect(), &sep, &path_sep, Span::call_site())?;
+    let ident = TokenStream::from(TokenTree::Ident(ident));
+    match callback {
+        Some(callback) => Ok(callback.call_item(ident)),
+        None => Ok(ident),
+    }
+}
@@ -0,0 +1,230 @@
+//  TYPENUM LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for converting integer literals to `typenum` types.
+//
+
+use std::iter::Peekable;
+
+use proc_macro2::{Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::Lit;
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{SignedLit, parse_signed_lit};
+
+
+/***** CONSTANTS *****/
+/// The largest number for which `typenum` defines aliases.
+const MAX_ALIAS: u128 = 1024;
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Defines the parsed input to the macro.
+struct Input {
+    /// Whether to generate an alias instead of a type tower.
+    alias:  bool,
+    /// Whether to generate a signed integer instead of an unsigned one.
+    signed: bool,
+    /// Whether the value is negative.
+    neg:    bool,
+    /// The value to convert.
+    value:  u128,
+    /// The span of the literal.
+    span:   Span,
+    /// The path to the `typenum` crate.
+    path:   TokenStream,
+}
+impl Input {
+    /// Parses the input to the macro.
+    ///
+    /// # Arguments
+    /// - `input`: The input [`TokenStream`] to parse.
+    ///
+    /// # Returns
+    /// The parsed Input.
+    ///
+    /// # Errors
+    /// This function can error if the input was invalid.
+    fn parse(input: TokenStream) -> Result<Self, Diagnostic2> {
+        let mut iter = input.into_iter().peekable();
+
+        // Parse the modifiers
+        let mut alias: bool = false;
+        let mut signed: Option<(bool, Span)> = None;
+        while let Some(TokenTree::Ident(ident)) = iter.peek() {
+            match ident.to_string().as_str() {
+                "alias" if !alias && signed.is_none() => alias = true,
+                "int" if signed.is_none() => signed = Some((true, ident.span())),
+                "uint" if signed.is_none() => signed = Some((false, ident.span())),
+                _ => return Err(error2(ident.span(), "Expected either `alias`, `int`, `uint` or an integer literal")),
+            }
+            iter.next();
+        }
+
+        // Parse the literal
+        let SignedLit { neg, lit } = parse_signed_lit(&mut iter)?;
+        let Lit::Int(lit) = lit else {
+            return Err(error2(lit.span(), "Expected an integer literal"));
+        };
+        let span: Span = lit.span();
+        let value: u128 = lit.base10_parse().map_err(|_| error2(span, "Integer literal is too large"))?;
+        let signed: bool = match signed {
+            Some((false, span)) if neg && value != 0 => return Err(error2(span, "Cannot represent a negative integer as an unsigned type")),
+            Some((signed, _)) => signed,
+            None => neg && value != 0,
+        };
+
+        // Parse the optional crate path
+        let path: TokenStream = match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => Self::parse_crate(&mut iter)?,
+            Some(tt) => return Err(error2(tt.span(), "Expected either ',' or nothing after the integer literal")),
+            None => {
+                let mut path = TokenStream::new();
+                path.extend([TokenTree::Punct(Punct::new(':', Spacing::Joint)), TokenTree::Punct(Punct::new(':', Spacing::Alone))]);
+                path.extend([TokenTree::Ident(Ident::new("typenum", span))]);
+                path
+            },
+        };
+
+        // Done
+        Ok(Self { alias, signed, neg, value, span, path })
+    }
+
+    /// Parses a `crate = ...`-pair.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding remaining tokens.
+    ///
+    /// # Returns
+    /// The parsed path.
+    ///
+    /// # Errors
+    /// This function errors if the head of the input did not encode a valid crate path.
+    fn parse_crate(iter: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Result<TokenStream, Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree::Ident(ident)) if ident == "crate" => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected 'crate'")),
+            None => return Err(error2(Span::mixed_site(), "Expected 'crate'")),
+        }
+        match iter.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' && p.spacing() == Spacing::Alone => {},
+            Some(tt) => return Err(error2(tt.span(), "Expected '='")),
+            None => return Err(error2(Span::mixed_site(), "Expected '='")),
+        }
+        let path: TokenStream = iter.collect();
+        if path.is_empty() {
+            return Err(error2(Span::mixed_site(), "Expected a path to the `typenum` crate"));
+        }
+        Ok(path)
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates a path to an item in the `typenum` crate.
+///
+/// # Arguments
+/// - `path`: The path to the `typenum` crate.
+/// - `name`: The name of the item in that crate.
+/// - `span`: The [`Span`] to give the generated tokens.
+///
+/// # Returns
+/// A [`TokenStream`] encoding `$path::$name`.
+fn item(path: &TokenStream, name: &str, span: Span) -> TokenStream {
+    let mut res = path.clone();
+    res.extend([
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new(name, span)),
+    ]);
+    res
+}
+
+/// Generates a path to a generic item in the `typenum` crate.
+///
+/// # Arguments
+/// - `path`: The path to the `typenum` crate.
+/// - `name`: The name of the item in that crate.
+/// - `args`: The generic arguments to give it.
+/// - `span`: The [`Span`] to give the generated tokens.
+///
+/// # Returns
+/// A [`TokenStream`] encoding `$path::$name<$($args),*>`.
+fn generic_item(path: &TokenStream, name: &str, args: impl IntoIterator<Item = TokenStream>, span: Span) -> TokenStream {
+    let mut res = item(path, name, span);
+    res.extend([TokenTree::Punct(Punct::new('<', Spacing::Alone))]);
+    for (i, arg) in args.into_iter().enumerate() {
+        if i > 0 {
+            res.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+        }
+        res.extend(arg);
+    }
+    res.extend([TokenTree::Punct(Punct::new('>', Spacing::Alone))]);
+    res
+}
+
+/// Generates the `UInt<...>`-tower for an unsigned number.
+///
+/// # Arguments
+/// - `path`: The path to the `typenum` crate.
+/// - `value`: The value to generate the tower for.
+/// - `span`: The [`Span`] to give the generated tokens.
+///
+/// # Returns
+/// A [`TokenStream`] encoding the type.
+fn tower(path: &TokenStream, value: u128, span: Span) -> TokenStream {
+    if value == 0 {
+        return item(path, "UTerm", span);
+    }
+    let bit: &str = if value & 1 == 1 { "B1" } else { "B0" };
+    generic_item(path, "UInt", [tower(path, value >> 1, span), item(path, bit, span)], span)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`typenum_lit()`](super::typenum_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to convert.
+///
+/// # Returns
+/// A new [`TokenStream`] with the `typenum` type encoding the literal.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn typenum_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let Input { alias, signed, neg, value, span, path } = Input::parse(input)?;
+
+    // Generate either the alias or the full type
+    let res: TokenStream = if alias {
+        if value > MAX_ALIAS {
+            return Err(error2(span, &format!("`typenum` only defines aliases up to {MAX_ALIAS}; omit `alias` to generate the full type instead")));
+        }
+        let name: String = match (signed, neg, value) {
+            (false, _, value) => format!("U{value}"),
+            (true, _, 0) => "Z0".into(),
+            (true, false, value) => format!("P{value}"),
+            (true, true, value) => format!("N{value}"),
+        };
+        item(&path, &name, span)
+    } else {
+        match (signed, neg, value) {
+            (false, _, value) => tower(&path, value, span),
+            (true, _, 0) => item(&path, "Z0", span),
+            (true, false, value) => generic_item(&path, "PInt", [tower(&path, value, span)], span),
+            (true, true, value) => generic_item(&path, "NInt", [tower(&path, value, span)], span),
+        }
+    };
+
+    Ok(res)
+}
@@ -0,0 +1,74 @@
+//  UNIQUE ID.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for generating unique integer literals across invocations.
+//
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::int_lit;
+
+
+/***** GLOBALS *****/
+/// The counters of every scope, by name. The default scope is the empty string.
+static COUNTERS: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+
+
+
+
+
+/***** TOKEN PARSING *****/
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// The name of the scope to draw from. Empty for the default scope.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<String, Diagnostic2> {
+    let mut iter = input.into_iter();
+    let scope: String = match iter.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        Some(tt) => return Err(error2(tt.span(), "Expected either an identifier naming a scope or nothing")),
+        None => return Ok(String::new()),
+    };
+    if let Some(tt) = iter.next() {
+        return Err(error2(tt.span(), "Expected nothing after the scope"));
+    }
+    Ok(scope)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`unique_id()`](super::unique_id())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the optional scope to draw from.
+///
+/// # Returns
+/// A new [`TokenStream`] with an integer literal not yet emitted for the scope.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn unique_id(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let scope: String = parse_input(input)?;
+
+    // Take the next value from the scope's counter. If another invocation panicked while holding
+    // the lock, the counters are still intact, so we just recover them.
+    let mut counters: MutexGuard<HashMap<String, u64>> = COUNTERS.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap_or_else(|err| err.into_inner());
+    let counter: &mut u64 = counters.entry(scope).or_insert(0);
+    let id: u64 = *counter;
+    *counter += 1;
+    Ok(int_lit(false, id as u128, None, Span::call_site()))
+}
@@ -5,103 +5,314 @@
 //!   Defines some utilities used across crates.
 //
 
-use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+#[cfg(feature = "deterministic")]
+use std::collections::HashMap;
+use std::path::PathBuf;
+#[cfg(feature = "deterministic")]
+use std::sync::{Mutex, OnceLock};
+
+#[cfg(feature = "deterministic")]
+use proc_macro::Span;
 use proc_macro2::{
-    Delimiter as Delimiter2, Group as Group2, Ident as Ident2, Literal as Literal2, Punct as Punct2, Spacing as Spacing2, Span as Span2,
-    TokenStream as TokenStream2, TokenTree as TokenTree2,
+    Delimiter as Delimiter2, Group as Group2, Ident as Ident2, Punct as Punct2, Spacing as Spacing2, Span as Span2, TokenStream as TokenStream2,
+    TokenTree as TokenTree2,
 };
 
+use crate::diagnostics::{Diagnostic2, error2};
+
+
+/***** CONSTANTS *****/
+/// The strict keywords, which can only be used as keywords.
+pub const STRICT_KEYWORDS: [&str; 38] = [
+    "Self", "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let",
+    "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while",
+];
+
+/// The reserved keywords, which are not used yet but cannot be used as identifiers either.
+pub const RESERVED_KEYWORDS: [&str; 14] =
+    ["abstract", "become", "box", "do", "final", "gen", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield"];
+
+/// The weak keywords, which only have a special meaning in some contexts and can otherwise be used
+/// as identifiers.
+pub const WEAK_KEYWORDS: [&str; 4] = ["macro_rules", "raw", "safe", "union"];
+
+
+
+
 
 /***** LIBRARY *****/
-/// A generic helper struct that pulls some struct-time specialization for a convenient interface.
-struct With<T>(std::marker::PhantomData<T>);
-impl<const LEN: usize> With<[TokenTree; LEN]> {
-    /// Given a list of [`TokenTree`], sets all of their spans to the given one.
+/// Represents a callback macro that some macros can pass their output to, instead of emitting it
+/// directly.
+///
+/// It is given as `path::to::callback!`, optionally followed by a group of tokens that are passed
+/// to the callback before the generated ones (e.g., `callback!(foo,)`).
+#[derive(Clone)]
+pub struct Callback {
+    /// The path of the macro to call.
+    path:   TokenStream2,
+    /// The `!` separating the path from its arguments.
+    bang:   Punct2,
+    /// Any tokens to give to the callback before the generated ones.
+    prefix: Option<Group2>,
+}
+impl Callback {
+    /// Parses an optional Callback from an iterator over [`TokenTree2`]s.
+    ///
+    /// This expects either the end of the input, or `=>` followed by the callback.
     ///
     /// # Arguments
-    /// - `span`: The span to set it with.
-    /// - `tts`: The list of token trees to set.
+    /// - `iter`: The iterator yielding the remaining tokens.
     ///
     /// # Returns
-    /// The same list, but with all spans updated.
-    pub fn span(span: Span, mut tts: [TokenTree; LEN]) -> [TokenTree; LEN] {
-        for tt in &mut tts {
-            tt.set_span(span);
+    /// A new Callback if one was given, or [`None`] if the input was empty.
+    ///
+    /// # Errors
+    /// This function errors if the input was not empty but also did not contain `=>` and a
+    /// callback.
+    pub fn parse_opt(iter: &mut impl Iterator<Item = TokenTree2>) -> Result<Option<Self>, Diagnostic2> {
+        match iter.next() {
+            Some(TokenTree2::Punct(p)) if p.as_char() == '=' && p.spacing() == Spacing2::Joint => {
+                match iter.next() {
+                    Some(TokenTree2::Punct(p)) if p.as_char() == '>' => {},
+                    Some(tt) => return Err(error2(tt.span(), "Expected '=>'")),
+                    None => return Err(error2(p.span(), "Expected '=>'")),
+                }
+                Ok(Some(Self::parse(iter)?))
+            },
+            Some(tt) => Err(error2(tt.span(), "Expected either '=>' or nothing")),
+            None => Ok(None),
         }
-        tts
     }
-}
-impl<const LEN: usize> With<[TokenTree2; LEN]> {
-    /// Given a list of [`TokenTree2`], sets all of their spans to the given one.
+
+    /// Parses a Callback from an iterator over [`TokenTree2`]s.
+    ///
+    /// This function assumes that the callback is the last thing in the input.
+    ///
+    /// # Arguments
+    /// - `iter`: The iterator yielding the remaining tokens.
+    ///
+    /// # Returns
+    /// A new Callback.
+    ///
+    /// # Errors
+    /// This function errors if the input did not contain a callback or if there were tokens after it.
+    pub fn parse(iter: &mut impl Iterator<Item = TokenTree2>) -> Result<Self, Diagnostic2> {
+        // Parse the path until the `!`
+        let mut path = TokenStream2::new();
+        let bang: Punct2 = loop {
+            match iter.next() {
+                Some(TokenTree2::Punct(punct)) if punct.as_char() == '!' => break punct,
+                Some(tt @ (TokenTree2::Ident(_) | TokenTree2::Punct(_))) => path.extend([tt]),
+                // Allow paths given as `$callback:path`
+                Some(TokenTree2::Group(group)) if group.delimiter() == Delimiter2::None => path.extend(group.stream()),
+                Some(tt) => return Err(error2(tt.span(), "Expected a path to a callback macro")),
+                None => return Err(error2(Span2::mixed_site(), "Expected a path to a callback macro, followed by '!'")),
+            }
+        };
+        if path.is_empty() {
+            return Err(error2(bang.span(), "Expected a path to a callback macro before '!'"));
+        }
+
+        // Then parse the optional prefix
+        let prefix: Option<Group2> = match iter.next() {
+            Some(TokenTree2::Group(group)) if group.delimiter() != Delimiter2::None => Some(group),
+            Some(tt) => return Err(error2(tt.span(), "Expected either nothing or arguments to the callback macro")),
+            None => None,
+        };
+        if let Some(tt) = iter.next() {
+            return Err(error2(tt.span(), "Expected nothing after the callback macro"));
+        }
+        Ok(Self { path, bang, prefix })
+    }
+
+    /// Splits the input to a macro into the tokens before the callback and the (optional)
+    /// callback itself.
+    ///
+    /// The callback is given after the last `=>` outside of any group.
     ///
     /// # Arguments
-    /// - `span`: The span to set it with.
-    /// - `tts`: The list of token trees to set.
+    /// - `input`: The input to the macro.
     ///
     /// # Returns
-    /// The same list, but with all spans updated.
-    pub fn span2(span: Span2, mut tts: [TokenTree2; LEN]) -> [TokenTree2; LEN] {
-        for tt in &mut tts {
-            tt.set_span(span);
+    /// The tokens before the `=>` and the parsed Callback, if any.
+    ///
+    /// # Errors
+    /// This function errors if the callback is malformed.
+    pub fn split_trailing(input: TokenStream2) -> Result<(TokenStream2, Option<Self>), Diagnostic2> {
+        let tokens: Vec<TokenTree2> = input.into_iter().collect();
+        let arrow: Option<usize> = tokens.windows(2).rposition(|pair| {
+            matches!(pair, [TokenTree2::Punct(eq), TokenTree2::Punct(gt)] if eq.as_char() == '=' && eq.spacing() == Spacing2::Joint && gt.as_char() == '>')
+        });
+        match arrow {
+            Some(i) => {
+                let mut iter = tokens.into_iter();
+                let head: TokenStream2 = iter.by_ref().take(i).collect();
+                Ok((head, Self::parse_opt(&mut iter)?))
+            },
+            None => Ok((tokens.into_iter().collect(), None)),
         }
-        tts
+    }
+
+    /// Generates a call to this callback with the given tokens.
+    ///
+    /// # Arguments
+    /// - `tokens`: The [`TokenStream2`] to pass to the callback after its prefix.
+    ///
+    /// # Returns
+    /// A [`TokenStream2`] encoding the call.
+    pub fn call(self, tokens: TokenStream2) -> TokenStream2 {
+        let (delim, span, mut args): (Delimiter2, Span2, TokenStream2) = match self.prefix {
+            Some(group) => (group.delimiter(), group.span(), group.stream()),
+            None => (Delimiter2::Parenthesis, self.bang.span(), TokenStream2::new()),
+        };
+        args.extend(tokens);
+
+        // Build the call
+        let mut group = Group2::new(delim, args);
+        group.set_span(span);
+        let mut res = self.path;
+        res.extend([TokenTree2::Punct(self.bang), TokenTree2::Group(group)]);
+        res
+    }
+
+    /// Generates a call to this callback with the given tokens in item position.
+    ///
+    /// This is like [`Callback::call()`], except that the call is always made with curly brackets
+    /// such that it does not need to be followed by a semicolon.
+    ///
+    /// # Arguments
+    /// - `tokens`: The [`TokenStream2`] to pass to the callback after its prefix.
+    ///
+    /// # Returns
+    /// A [`TokenStream2`] encoding the call.
+    pub fn call_item(mut self, tokens: TokenStream2) -> TokenStream2 {
+        let mut prefix = Group2::new(Delimiter2::Brace, self.prefix.as_ref().map(Group2::stream).unwrap_or_default());
+        prefix.set_span(self.prefix.as_ref().map(Group2::span).unwrap_or_else(|| self.bang.span()));
+        self.prefix = Some(prefix);
+        self.call(tokens)
+    }
+
+    /// Splits this callback into its path and its prefix.
+    ///
+    /// This is useful for generating calls to the callback from another macro, as the delimiter
+    /// of the prefix is dropped.
+    ///
+    /// # Returns
+    /// A tuple with the path of the callback (including the `!`) and the tokens to pass to it
+    /// before the generated ones.
+    pub fn into_parts(self) -> (TokenStream2, TokenStream2) {
+        let mut path = self.path;
+        path.extend([TokenTree2::Punct(self.bang)]);
+        (path, self.prefix.as_ref().map(Group2::stream).unwrap_or_default())
     }
 }
 
 
 
-/// Generates a [`TokenStream`] encoding an error.
+/// Generates a labeled part to pass to a callback, e.g., `name = [Foo],`.
 ///
 /// # Arguments
-/// - `span`: Some [`Span`] to have the error point to.
-/// - `message`: Some message to show with the error.
+/// - `label`: The label of the part.
+/// - `tokens`: The tokens of the part.
 ///
 /// # Returns
-/// A [`TokenStream`] that encodes a [`compile_error!()`](::core::compile_error!) pointing to your
-/// `span` with your `message`.
-pub fn error(span: Span, message: &str) -> TokenStream {
-    let mut res = TokenStream::new();
-    res.extend(With::span(span, [
-        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
-        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
-        TokenTree::Ident(Ident::new("core", span)),
-        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
-        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
-        TokenTree::Ident(Ident::new("compile_error", span)),
-        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
-        TokenTree::Group(Group::new(Delimiter::Parenthesis, {
-            let mut res = TokenStream::new();
-            res.extend([TokenTree::Literal(Literal::string(message))]);
-            res
-        })),
-    ]));
-    res
+/// A [`TokenStream2`] encoding `<label> = [<tokens>],`.
+pub fn part(label: &str, tokens: TokenStream2) -> TokenStream2 {
+    TokenStream2::from_iter([
+        TokenTree2::Ident(Ident2::new(label, Span2::call_site())),
+        TokenTree2::Punct(Punct2::new('=', Spacing2::Alone)),
+        TokenTree2::Group(Group2::new(Delimiter2::Bracket, tokens)),
+        TokenTree2::Punct(Punct2::new(',', Spacing2::Alone)),
+    ])
 }
 
-/// Generates a [`TokenStream2`] encoding an error.
+
+
+/// Derives a stable seed from the location of the current macro call.
+///
+/// The location is the file relative to the crate being compiled (so the seed does not depend on
+/// where the crate is checked out), together with the line and column of the call. Calls at the
+/// same location (e.g., from within a `macro_rules!`-body that is expanded multiple times) are
+/// told apart by the number of times that location has been seen before. This is stable because
+/// the compiler always expands macros in the same order.
+///
+/// # Returns
+/// A seed that is the same for the same call in every compilation.
+#[cfg(feature = "deterministic")]
+pub fn call_site_seed() -> u64 {
+    /// The number of times every location has been seen.
+    static SEEN: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+
+    // Find how often we've seen this location before
+    let span: Span = Span::call_site();
+    let file: String = match (span.local_file(), std::env::var_os("CARGO_MANIFEST_DIR")) {
+        (Some(path), Some(root)) => match path.strip_prefix(root) {
+            // NOTE: Joined with `/` such that the seed is the same on every platform
+            Ok(path) => path.components().map(|comp| comp.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/"),
+            Err(_) => span.file(),
+        },
+        _ => span.file(),
+    };
+    let location: String = format!("{file}:{}:{}", span.line(), span.column());
+    let mut seen = SEEN.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap_or_else(|err| err.into_inner());
+    let count: &mut u64 = seen.entry(location.clone()).or_insert(0);
+    let occurrence: u64 = *count;
+    *count += 1;
+
+    // Hash it all with FNV-1a, which (unlike `std`'s hashers) is guaranteed to be stable
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for byte in location.bytes().chain(occurrence.to_le_bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01B3);
+    }
+    hash
+}
+
+
+
+/// Checks whether the given string is a keyword that cannot be used as an identifier.
+///
+/// # Arguments
+/// - `value`: The string to check.
+///
+/// # Returns
+/// True if `value` is a strict or a reserved keyword, or false otherwise.
+#[inline]
+pub fn is_keyword(value: &str) -> bool { STRICT_KEYWORDS.contains(&value) || RESERVED_KEYWORDS.contains(&value) }
+
+/// Checks whether the given string is a valid, non-keyword identifier.
+///
+/// # Arguments
+/// - `value`: The string to check.
+///
+/// # Returns
+/// True if `value` can be used as an identifier, or false otherwise.
+pub fn is_ident(value: &str) -> bool {
+    let mut chars = value.chars();
+    let valid: bool = match chars.next() {
+        Some('_') => value.len() > 1 && chars.all(|c| c.is_alphanumeric() || c == '_'),
+        Some(c) if c.is_alphabetic() => chars.all(|c| c.is_alphanumeric() || c == '_'),
+        _ => false,
+    };
+    valid && !is_keyword(value)
+}
+
+/// Resolves a path given to a macro that reads files.
+///
+/// Relative paths are resolved relative to the directory of the file that invokes the macro, like
+/// [`include!()`](::core::include!) does. If that file is unknown, they are resolved relative to
+/// the calling crate's `Cargo.toml` instead.
 ///
 /// # Arguments
-/// - `span`: Some [`Span2`] to have the error point to.
-/// - `message`: Some message to show with the error.
+/// - `path`: The path as given to the macro.
 ///
 /// # Returns
-/// A [`TokenStream2`] that encodes a [`compile_error!()`](::core::compile_error!) pointing to your
-/// `span` with your `message`.
-pub fn error2(span: Span2, message: &str) -> TokenStream2 {
-    let mut res = TokenStream2::new();
-    res.extend(With::span2(span, [
-        TokenTree2::Punct(Punct2::new(':', Spacing2::Joint)),
-        TokenTree2::Punct(Punct2::new(':', Spacing2::Alone)),
-        TokenTree2::Ident(Ident2::new("core", span)),
-        TokenTree2::Punct(Punct2::new(':', Spacing2::Joint)),
-        TokenTree2::Punct(Punct2::new(':', Spacing2::Alone)),
-        TokenTree2::Ident(Ident2::new("compile_error", span)),
-        TokenTree2::Punct(Punct2::new('!', Spacing2::Alone)),
-        TokenTree2::Group(Group2::new(Delimiter2::Parenthesis, {
-            let mut res = TokenStream2::new();
-            res.extend([TokenTree2::Literal(Literal2::string(message))]);
-            res
-        })),
-    ]));
-    res
+/// The resolved path.
+pub fn resolve_path(path: &str) -> PathBuf {
+    let dir: PathBuf = match proc_macro::Span::call_site().local_file() {
+        Some(file) => file.parent().map(PathBuf::from).unwrap_or_default(),
+        None => std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from).unwrap_or_default(),
+    };
+    dir.join(path)
 }
@@ -0,0 +1,175 @@
+//  UUID LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for parsing UUIDs at compile time.
+//
+
+use std::str::FromStr as _;
+
+use proc_macro2::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use syn::{Lit, LitStr};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::lits::{parse_lit, subspan};
+
+
+/***** TOKEN PARSING *****/
+/// Defines what to generate for the UUID.
+enum Repr {
+    /// A single `u128` literal.
+    U128,
+    /// An array of 16 `u8` literals.
+    Bytes,
+}
+
+/// Parses the input to the macro.
+///
+/// # Arguments
+/// - `input`: The input [`TokenStream`] to parse.
+///
+/// # Returns
+/// A tuple of the [`Repr`] to generate and the [`LitStr`] to parse the UUID from.
+///
+/// # Errors
+/// This function can error if the input was invalid.
+fn parse_input(input: TokenStream) -> Result<(Repr, LitStr), Diagnostic2> {
+    let mut iter = input.into_iter();
+
+    // Parse the optional representation first
+    let mut repr = Repr::U128;
+    let mut tree: Option<TokenTree> = iter.next();
+    if let Some(TokenTree::Ident(ident)) = &tree {
+        match ident.to_string().as_str() {
+            "u128" => repr = Repr::U128,
+            "bytes" => repr = Repr::Bytes,
+            _ => return Err(error2(ident.span(), "Expected either `u128`, `bytes` or a string literal")),
+        }
+        tree = iter.next();
+    }
+
+    // Then the literal
+    let lit: LitStr = match tree.map(parse_lit).transpose()? {
+        Some(Lit::Str(lit)) => lit,
+        Some(lit) => return Err(error2(lit.span(), "Expected a string literal")),
+        None => return Err(error2(Span::mixed_site(), "Expected a string literal")),
+    };
+    if let Some(tree) = iter.next() {
+        return Err(error2(tree.span(), "Expected nothing after the string literal"));
+    }
+    Ok((repr, lit))
+}
+
+
+
+/// Parses a UUID from a string literal.
+///
+/// Supports the simple (`67e5504410b1426f9247bb680e5fe0c8`), hyphenated
+/// (`67e55044-10b1-426f-9247-bb680e5fe0c8`), braced (`{67e55044-10b1-426f-9247-bb680e5fe0c8}`) and
+/// URN (`urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8`) formats.
+///
+/// # Arguments
+/// - `lit`: The [`LitStr`] to parse.
+///
+/// # Returns
+/// The UUID as a [`u128`].
+///
+/// # Errors
+/// This function errors if the literal does not contain a valid UUID.
+pub fn parse_uuid(lit: &LitStr) -> Result<u128, Diagnostic2> {
+    let value: String = lit.value();
+
+    // Strip the wrapping of the braced and URN formats
+    let (start, end): (usize, usize) = if let Some(rest) = value.strip_prefix("urn:uuid:") {
+        (value.len() - rest.len(), value.len())
+    } else if value.starts_with('{') {
+        if !value.ends_with('}') || value.len() < 2 {
+            return Err(error2(subspan(lit, 0..1), "Unmatched '{' in UUID"));
+        }
+        (1, value.len() - 1)
+    } else {
+        (0, value.len())
+    };
+    let uuid: &str = &value[start..end];
+
+    // Parse the digits, checking the hyphens are at the right places if there are any
+    let hyphenated: bool = uuid.contains('-');
+    let mut res: u128 = 0;
+    let mut ndigits: usize = 0;
+    for (i, c) in uuid.char_indices() {
+        let pos: usize = start + i;
+        if c == '-' {
+            if !hyphenated || ![8, 12, 16, 20].contains(&ndigits) || uuid[..i].ends_with('-') {
+                return Err(error2(subspan(lit, pos..pos + 1), &format!("Unexpected '-' at position {pos} in UUID")));
+            }
+            continue;
+        }
+        if hyphenated && [8, 12, 16, 20].contains(&ndigits) && !uuid[..i].ends_with('-') {
+            return Err(error2(subspan(lit, pos..pos + c.len_utf8()), &format!("Expected '-' at position {pos} in UUID")));
+        }
+        let Some(digit) = c.to_digit(16) else {
+            return Err(error2(
+                subspan(lit, pos..pos + c.len_utf8()),
+                &format!("Invalid character {c:?} at position {pos} in UUID (expected a hexadecimal digit)"),
+            ));
+        };
+        if ndigits == 32 {
+            return Err(error2(subspan(lit, pos..end), "UUID is too long (expected 32 hexadecimal digits)"));
+        }
+        res = (res << 4) | digit as u128;
+        ndigits += 1;
+    }
+    if ndigits < 32 {
+        return Err(error2(lit.span(), &format!("UUID is too short (expected 32 hexadecimal digits, got {ndigits})")));
+    }
+    Ok(res)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`uuid_lit()`](super::uuid_lit())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the literal to parse.
+///
+/// # Returns
+/// A new [`TokenStream`] with either a `u128` literal or an array of `u8` literals encoding the
+/// UUID.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro, or if the literal does not
+/// contain a valid UUID.
+pub fn uuid_lit(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (repr, lit) = parse_input(input)?;
+    let span: Span = lit.span();
+    let uuid: u128 = parse_uuid(&lit)?;
+
+    // Serialize it
+    let res: TokenTree = match repr {
+        Repr::U128 => {
+            let mut res = Literal::from_str(&format!("0x{uuid:032x}u128")).unwrap();
+            res.set_span(span);
+            TokenTree::Literal(res)
+        },
+        Repr::Bytes => {
+            let mut bytes = TokenStream::new();
+            for (i, byte) in uuid.to_be_bytes().into_iter().enumerate() {
+                if i > 0 {
+                    let mut comma = Punct::new(',', Spacing::Alone);
+                    comma.set_span(span);
+                    bytes.extend([TokenTree::Punct(comma)]);
+                }
+                let mut byte = Literal::from_str(&format!("0x{byte:02x}u8")).unwrap();
+                byte.set_span(span);
+                bytes.extend([TokenTree::Literal(byte)]);
+            }
+            let mut group = Group::new(Delimiter::Bracket, bytes);
+            group.set_span(span);
+            TokenTree::Group(group)
+        },
+    };
+    Ok(TokenStream::from(res))
+}
@@ -0,0 +1,96 @@
+//  VARIANTS OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for iterating over the variants of an enum.
+//
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::items::{ItemHead, Output, Tail, Variant, fill_template, parse_head, parse_variants, part_list, split_output};
+use crate::utils::part;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Generates the parts of a single variant.
+///
+/// # Arguments
+/// - `index`: The index of the variant in the enum.
+/// - `variant`: The [`Variant`] to generate the parts of.
+///
+/// # Returns
+/// The names of the parts and their values, in the order they are given to callbacks.
+fn variant_parts(index: usize, variant: Variant) -> Vec<(&'static str, TokenStream)> {
+    let Variant { attrs, name, fields, discriminant } = variant;
+
+    // The kind of the variant and the pattern that matches it regardless of its fields
+    let (kind, pattern): (&str, TokenStream) = match &fields {
+        Some(group) => {
+            let dots = TokenStream::from_iter([TokenTree::Punct(Punct::new('.', Spacing::Joint)), TokenTree::Punct(Punct::new('.', Spacing::Alone))]);
+            let kind: &str = if group.delimiter() == Delimiter::Brace { "named" } else { "tuple" };
+            (kind, TokenStream::from(TokenTree::Group(Group::new(group.delimiter(), dots))))
+        },
+        None => ("unit", TokenStream::new()),
+    };
+
+    vec![
+        ("attrs", attrs),
+        ("name", TokenStream::from(TokenTree::Ident(name))),
+        ("kind", TokenStream::from(TokenTree::Ident(Ident::new(kind, Span::call_site())))),
+        ("fields", fields.map(|group| TokenStream::from(TokenTree::Group(group))).unwrap_or_default()),
+        ("pattern", pattern),
+        ("discriminant", discriminant.unwrap_or_default()),
+        ("index", TokenStream::from(TokenTree::Literal(Literal::usize_unsuffixed(index)))),
+    ]
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`variants_of()`](super::variants_of())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the enum and either a callback or a template.
+///
+/// # Returns
+/// A new [`TokenStream`] with either a call to the callback with the variants of the enum, or
+/// the template with its repetitions expanded once for every variant.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn variants_of(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let (item, output): (TokenStream, Option<Output>) = split_output(input)?;
+    let span: Span = item.clone().into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+    let Some(output) = output else {
+        return Err(error2(span, "Expected '=>' and either a callback macro or a template in curly brackets after the enum"));
+    };
+    let ItemHead { keyword, name, rest, .. } = parse_head(item)?;
+    if keyword.to_string() != "enum" {
+        return Err(error2(span, &format!("Expected an enum, found `{keyword}`")));
+    }
+    let Tail { generics, signature, where_, body } = Tail::parse(rest, span)?;
+    if let Some(tt) = signature.into_iter().next() {
+        return Err(error2(tt.span(), "Expected the variants of the enum in curly brackets"));
+    }
+    let variants: Vec<Variant> = match body.into_iter().next() {
+        Some(TokenTree::Group(group)) => parse_variants(&group)?,
+        Some(tt) => return Err(error2(tt.span(), "Expected the variants of the enum in curly brackets")),
+        None => return Err(error2(span, "Expected the variants of the enum in curly brackets")),
+    };
+    let variants: Vec<Vec<(&str, TokenStream)>> = variants.into_iter().enumerate().map(|(i, variant)| variant_parts(i, variant)).collect();
+
+    match output {
+        Output::Callback(callback) => {
+            let mut args = TokenStream::new();
+            args.extend(part("name", name.map(|name| TokenStream::from(TokenTree::Ident(name))).unwrap_or_default()));
+            args.extend(part("generics", generics));
+            args.extend(part("where", where_));
+            args.extend(part("variants", part_list(variants)));
+            Ok(callback.call_item(args))
+        },
+        Output::Template(template) => fill_template(template, &variants, None),
+    }
+}
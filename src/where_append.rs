@@ -0,0 +1,130 @@
+//  WHERE APPEND.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides a macro for appending bounds to the where-clause of items or generics.
+//
+
+use proc_macro2::{Delimiter, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::diagnostics::{Diagnostic2, error2};
+use crate::items::{ItemHead, Tail, parse_head};
+use crate::utils::{Callback, part};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Merges the given predicates into an existing where-clause.
+///
+/// # Arguments
+/// - `preds`: The predicates of the existing where-clause, without `where` (may be empty).
+/// - `bounds`: The predicates to append.
+///
+/// # Returns
+/// A where-clause with both sets of predicates (including the `where`), or nothing if both are
+/// empty.
+fn merge(preds: TokenStream, bounds: TokenStream) -> TokenStream {
+    let trailing: bool = preds.is_empty() || matches!(preds.clone().into_iter().last(), Some(TokenTree::Punct(p)) if p.as_char() == ',');
+    if preds.is_empty() && bounds.is_empty() {
+        return TokenStream::new();
+    }
+
+    let mut res = TokenStream::from(TokenTree::Ident(Ident::new("where", Span::call_site())));
+    res.extend(preds);
+    if !trailing && !bounds.is_empty() {
+        res.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+    }
+    res.extend(bounds);
+    res
+}
+
+/// Appends the given bounds to the where-clause of an item.
+///
+/// # Arguments
+/// - `item`: The item to append to.
+/// - `bounds`: The predicates to append.
+///
+/// # Returns
+/// The item with the merged where-clause.
+///
+/// # Errors
+/// This function errors if the item is not a function, struct, enum, union, trait or
+/// `impl`-block.
+fn append_item(item: TokenStream, bounds: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    let ItemHead { attrs, vis, qualifiers, keyword, name, rest } = parse_head(item)?;
+    let kind: String = keyword.to_string();
+    if !matches!(kind.as_str(), "fn" | "struct" | "enum" | "union" | "trait" | "impl") {
+        let span: Span = keyword.into_iter().next().map(|tt| tt.span()).unwrap_or_else(Span::call_site);
+        return Err(error2(span, &format!("Expected a function, struct, enum, union, trait or `impl`-block, found `{kind}`")));
+    }
+    let span: Span = name.as_ref().map(Ident::span).unwrap_or_else(Span::call_site);
+    let Tail { generics, signature, where_, body } = Tail::parse(rest, span)?;
+
+    // Rebuild the item with the merged where-clause
+    let mut res: TokenStream = attrs;
+    res.extend(vis);
+    res.extend(qualifiers);
+    res.extend(keyword);
+    res.extend(name.map(TokenTree::Ident));
+    if !generics.is_empty() {
+        res.extend([TokenTree::Punct(Punct::new('<', Spacing::Alone))]);
+        res.extend(generics);
+        res.extend([TokenTree::Punct(Punct::new('>', Spacing::Alone))]);
+    }
+    res.extend(signature);
+    res.extend(merge(where_, bounds));
+    res.extend(body);
+    Ok(res)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines the implementation of the [`where_append()`](super::where_append())-macro.
+///
+/// # Arguments
+/// - `input`: Some [`TokenStream`] containing the bounds and either an item, or generics and a
+///   callback.
+///
+/// # Returns
+/// A new [`TokenStream`] with either the item with the bounds appended to its where-clause, or a
+/// call to the callback with the generics and the merged where-clause.
+///
+/// # Errors
+/// This function may error if the input is not valid for this macro.
+pub fn where_append(input: TokenStream) -> Result<TokenStream, Diagnostic2> {
+    // Parse the bounds
+    let mut iter = input.into_iter();
+    let bounds: TokenStream = match iter.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => group.stream(),
+        Some(tt) => return Err(error2(tt.span(), "Expected the bounds to append in square brackets")),
+        None => return Err(error2(Span::call_site(), "Expected the bounds to append in square brackets")),
+    };
+
+    // Then either parse the generics with a callback, or an item
+    let (rest, callback): (TokenStream, Option<Callback>) = Callback::split_trailing(iter.collect())?;
+    let Some(callback) = callback else {
+        return append_item(rest, bounds);
+    };
+    let mut tokens: Vec<TokenTree> = rest.into_iter().collect();
+    while let [TokenTree::Group(group)] = tokens.as_slice()
+        && group.delimiter() == Delimiter::None
+    {
+        tokens = group.stream().into_iter().collect();
+    }
+    if let Some(tt) = tokens.first()
+        && !matches!(tt, TokenTree::Punct(p) if p.as_char() == '<')
+        && !matches!(tt, TokenTree::Ident(ident) if ident == "where")
+    {
+        return Err(error2(tt.span(), "Expected either generics in angle brackets, `where` or nothing before the callback"));
+    }
+    let pos: usize = tokens.iter().position(|tt| matches!(tt, TokenTree::Ident(ident) if ident == "where")).unwrap_or(tokens.len());
+    let preds: TokenStream = tokens.drain(pos..).skip(1).collect();
+
+    // Call the callback with the generics as-is and the merged where-clause
+    let mut args = TokenStream::new();
+    args.extend(part("generics", tokens.into_iter().collect()));
+    args.extend(part("where_clause", merge(preds, bounds)));
+    Ok(callback.call_item(args))
+}
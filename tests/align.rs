@@ -0,0 +1,42 @@
+//  ALIGN.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `align_up!()`- and `align_down!()`-macros.
+//
+
+use macro_toolkit::{align_down, align_up};
+
+
+/***** TESTS *****/
+#[test]
+fn test_align_up() {
+    assert_eq!(align_up!(0, 8), 0);
+    assert_eq!(align_up!(1, 8), 8);
+    assert_eq!(align_up!(8, 8), 8);
+    assert_eq!(align_up!(9, 1), 9);
+    assert_eq!(align_up!(0x1234, 0x1000,), 0x2000);
+    assert_eq!(align_up!(250u8, 2u8), 250u8);
+    assert_eq!(align_up!(0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFF0u128, 16), u128::MAX - 15);
+}
+
+#[test]
+fn test_align_down() {
+    assert_eq!(align_down!(0, 8), 0);
+    assert_eq!(align_down!(7, 8), 0);
+    assert_eq!(align_down!(15, 8), 8);
+    assert_eq!(align_down!(0x1234, 0x1000), 0x1000);
+    assert_eq!(align_down!(255u8, 128), 128u8);
+}
+
+#[test]
+fn test_align_macro() {
+    macro_rules! region {
+        ($start:literal .. $end:literal, $align:literal) => {
+            (align_down!($start, $align), align_up!($end, $align))
+        };
+    }
+
+    assert_eq!(region!(0x1010..0x2010, 0x1000), (0x1000, 0x3000));
+    assert_eq!(region!(3usize..5usize, 4), (0usize, 8usize));
+}
@@ -0,0 +1,86 @@
+//  APPLY.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[apply]`-macro.
+//
+
+use macro_toolkit::apply;
+
+
+/***** HELPERS *****/
+mod helpers {
+    macro_rules! with_name {
+        ($(#[$attr:meta])* $vis:vis struct $name:ident;) => {
+            $(#[$attr])* $vis struct $name;
+            impl $name {
+                pub const NAME: &'static str = stringify!($name);
+            }
+        };
+    }
+    pub(crate) use with_name;
+}
+
+macro_rules! with_id {
+    ($id:literal, $vis:vis struct $name:ident;) => {
+        $vis struct $name;
+        impl $name {
+            pub const ID: u32 = $id;
+        }
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_apply_struct() {
+    #[apply(helpers::with_name!)]
+    #[derive(Debug)]
+    struct Foo;
+
+    assert_eq!(Foo::NAME, "Foo");
+    assert_eq!(format!("{Foo:?}"), "Foo");
+}
+
+#[test]
+fn test_apply_args() {
+    #[apply(with_id!(42,))]
+    struct Foo;
+    #[apply(with_id![7,])]
+    struct Bar;
+
+    assert_eq!(Foo::ID, 42);
+    assert_eq!(Bar::ID, 7);
+}
+
+#[test]
+fn test_apply_fn() {
+    macro_rules! twice {
+        ($vis:vis fn $name:ident() -> $ret:ty $body:block) => {
+            $vis fn $name() -> $ret { 2 * $body }
+        };
+    }
+
+    #[apply(twice!)]
+    fn answer() -> u32 {
+        21
+    }
+
+    assert_eq!(answer(), 42);
+}
+
+#[test]
+fn test_apply_macro_rules() {
+    macro_rules! wrap {
+        ($cb:path, $name:ident) => {
+            #[apply($cb!)]
+            struct $name;
+        };
+    }
+
+    wrap!(helpers::with_name, Baz);
+    assert_eq!(Baz::NAME, "Baz");
+}
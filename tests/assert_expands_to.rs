@@ -0,0 +1,39 @@
+//  ASSERT EXPANDS TO.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `assert_expands_to!()`-macro.
+//
+
+use macro_toolkit::{assert_expands_to, lifetimes_of, turbofish, type_ident};
+
+
+/***** HELPERS *****/
+/// Generates a getter for a field, passing it to the given callback.
+macro_rules! getter {
+    ($name:ident: $ty:ty => $(::)? $($cb:ident)::+ !($($args:tt)*)) => {
+        $($cb)::+!($($args)* fn $name(&self) -> &$ty { &self.$name });
+    };
+}
+
+assert_expands_to!(turbofish!(Vec<u8>), { Vec::<u8> });
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_assert_expands_to() {
+    assert_expands_to!(type_ident!(Vec<Option<u8>>), { Vec_Option_u8 });
+    assert_expands_to!(lifetimes_of!(&'a Foo<'b, 'a>), { 'a, 'b });
+    assert_expands_to![lifetimes_of![u8], {}];
+}
+
+#[test]
+fn test_assert_expands_to_macro_rules() {
+    assert_expands_to!(getter!(x: i32), {
+        fn x(&self) -> &i32 { &self.x }
+    });
+    assert_expands_to!(getter!(y: Vec<u8>), { fn y (&self) -> & Vec<u8> { & self . y } },);
+}
@@ -0,0 +1,80 @@
+//  ATTR ALIAS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `attr_alias!{}`-macro and the `#[apply_alias]`-attribute.
+//
+
+use macro_toolkit::{apply_alias, attr_alias};
+
+
+/***** HELPERS *****/
+attr_alias! {
+    /// Derives the usual traits.
+    common = #[derive(Clone, Copy, Debug)] #[cfg_attr(test, derive(PartialEq))];
+    ordered = #[derive(PartialOrd)];
+    nothing = ;
+}
+
+mod aliases {
+    use macro_toolkit::attr_alias;
+
+    attr_alias! {
+        pub default = #[derive(Debug, Default)];
+        pub(crate) hash = #[derive(PartialEq, Eq, Hash)];
+    }
+}
+
+#[apply_alias(common)]
+struct Single(u32);
+
+#[apply_alias(common, ordered)]
+/// Attributes after the alias are kept.
+#[allow(dead_code)]
+struct Multiple(u32, u32);
+
+#[apply_alias(aliases::default, crate::aliases::hash, nothing)]
+struct ByPath {
+    value: u32,
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_attr_alias_single() {
+    let single = Single(42);
+    assert_eq!(single.clone(), single);
+    assert_eq!(format!("{single:?}"), "Single(42)");
+}
+
+#[test]
+fn test_attr_alias_multiple() {
+    assert!(Multiple(1, 2) < Multiple(1, 10));
+}
+
+#[test]
+fn test_attr_alias_path() {
+    assert_eq!(ByPath::default(), ByPath { value: 0 });
+    let set: std::collections::HashSet<ByPath> = [ByPath { value: 1 }, ByPath { value: 1 }].into_iter().collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn test_attr_alias_macro_rules() {
+    macro_rules! alias_and_apply {
+        ($alias:ident = $(#[$attr:meta])*; $name:ident) => {
+            attr_alias! {
+                $alias = $(#[$attr])*;
+            }
+
+            #[apply_alias($alias)]
+            struct $name;
+        };
+    }
+    alias_and_apply!(unit = #[derive(Debug, PartialEq)]; Unit);
+
+    assert_eq!(Unit, Unit);
+}
@@ -0,0 +1,51 @@
+//  BITMASK.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `bitmask!()`-macro.
+//
+
+use macro_toolkit::bitmask;
+
+
+/***** TESTS *****/
+#[test]
+fn test_bitmask_positions() {
+    assert_eq!(bitmask!(u8), 0u8);
+    assert_eq!(bitmask!(0), 1);
+    assert_eq!(bitmask!(u16 0, 15), 0x8001u16);
+    assert_eq!(bitmask!(u32 1, 1, 2,), 0b110u32);
+    assert_eq!(bitmask!(u128 127), 1u128 << 127);
+    assert_eq!(bitmask!(100), 1u128 << 100);
+}
+
+#[test]
+fn test_bitmask_ranges() {
+    assert_eq!(bitmask!(0, 3, 8..=11), 0b1111_0000_1001);
+    assert_eq!(bitmask!(u8 0..8), u8::MAX);
+    assert_eq!(bitmask!(u16 4..=4), 0x10u16);
+    assert_eq!(bitmask!(u64 0..4, 2..6), 0x3Fu64);
+    assert_eq!(bitmask!(u128 0..=127), u128::MAX);
+}
+
+#[test]
+fn test_bitmask_signed() {
+    assert_eq!(bitmask!(i8 0..7), i8::MAX);
+    assert_eq!(bitmask!(i8 0..8), -1i8);
+    assert_eq!(bitmask!(i16 15), i16::MIN);
+    assert_eq!(bitmask!(i32 0, 31), i32::MIN + 1);
+    assert_eq!(bitmask!(i128 0..=127), -1i128);
+}
+
+#[test]
+fn test_bitmask_macro() {
+    macro_rules! field {
+        ($name:ident: $start:literal..=$end:literal) => {
+            const $name: u32 = bitmask!(u32 $start..=$end);
+        };
+    }
+    field!(LOW: 0..=7);
+    field!(HIGH: 24..=31);
+
+    assert_eq!([LOW, HIGH], [0xFFu32, 0xFF00_0000]);
+}
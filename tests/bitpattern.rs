@@ -0,0 +1,51 @@
+//  BITPATTERN.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `bitpattern!()`-macro.
+//
+
+use macro_toolkit::bitpattern;
+
+
+/***** TESTS *****/
+#[test]
+fn test_bitpattern_simple() {
+    assert_eq!(bitpattern!("0"), (1, 0));
+    assert_eq!(bitpattern!("x"), (0, 0));
+    assert_eq!(bitpattern!("0b1x0X_1"), (0b10101, 0b10001));
+    assert_eq!(bitpattern!("1111_0000"), (0xFF, 0xF0));
+}
+
+#[test]
+fn test_bitpattern_typed() {
+    let (mask, value): (u8, u8) = bitpattern!(u8 "0bxxxx_xxx1");
+    assert_eq!((mask, value), (1, 1));
+    assert_eq!(bitpattern!(u32 "1x"), (2u32, 2u32));
+    assert_eq!(
+        bitpattern!(u128 "0b1xxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxxx_xxxxxxx0"),
+        (1 << 127 | 1, 1 << 127)
+    );
+}
+
+#[test]
+fn test_bitpattern_callback() {
+    macro_rules! masked {
+        ($n:expr; $mask:literal, $value:literal) => {
+            $n & $mask == $value
+        };
+    }
+    macro_rules! decoder {
+        ($name:ident, $pat:literal) => {
+            fn $name(n: u8) -> bool { bitpattern!(u8 $pat => masked!(n;)) }
+        };
+    }
+    decoder!(is_1x1x, "1x1x");
+    decoder!(is_0x, "0x");
+
+    assert!(is_1x1x(0b1010));
+    assert!(is_1x1x(0b1111));
+    assert!(!is_1x1x(0b1000));
+    assert!(is_0x(1));
+    assert!(!is_0x(2));
+}
@@ -0,0 +1,54 @@
+//  BITS NEEDED.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `bits_needed!()`-macro.
+//
+
+use macro_toolkit::bits_needed;
+
+
+/***** TESTS *****/
+#[test]
+fn test_bits_needed_unsigned() {
+    assert_eq!(bits_needed!(0), 1);
+    assert_eq!(bits_needed!(1), 1);
+    assert_eq!(bits_needed!(2), 2);
+    assert_eq!(bits_needed!(0xFFu8), 8);
+    assert_eq!(bits_needed!(bits 0x100), 9);
+    assert_eq!(bits_needed!(340282366920938463463374607431768211455), 128);
+}
+
+#[test]
+fn test_bits_needed_signed() {
+    assert_eq!(bits_needed!(-1), 1);
+    assert_eq!(bits_needed!(-2), 2);
+    assert_eq!(bits_needed!(-3), 3);
+    assert_eq!(bits_needed!(-128i8), 8);
+    assert_eq!(bits_needed!(-129), 9);
+    assert_eq!(bits_needed!(-0), 1);
+    assert_eq!(bits_needed!(signed 0), 2);
+    assert_eq!(bits_needed!(signed 128), 9);
+    assert_eq!(bits_needed!(signed -128), 8);
+}
+
+#[test]
+fn test_bits_needed_bytes() {
+    assert_eq!(bits_needed!(bytes 0), 1);
+    assert_eq!(bits_needed!(bytes 255), 1);
+    assert_eq!(bits_needed!(bytes 256), 2);
+    assert_eq!(bits_needed!(signed bytes 255), 2);
+    assert_eq!(bits_needed!(bytes -32768), 2);
+}
+
+#[test]
+fn test_bits_needed_macro() {
+    macro_rules! bytes_for {
+        ($lit:literal) => {
+            [0u8; bits_needed!(bytes $lit)]
+        };
+    }
+
+    assert_eq!(bytes_for!(0xFFFFFF).len(), 3);
+    assert_eq!(bytes_for!(-1).len(), 1);
+}
@@ -0,0 +1,45 @@
+//  BUILD TIME.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `build_time!()`-macro.
+//
+
+// Under the `deterministic`-feature, the macro requires `SOURCE_DATE_EPOCH` to be set
+#![cfg(not(feature = "deterministic"))]
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use macro_toolkit::build_time;
+
+
+/***** TESTS *****/
+#[test]
+fn test_build_time_unix() {
+    let now: u64 = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let (secs, millis): (u64, u64) = (build_time!(unix), build_time!(unix_ms,));
+    assert!(secs > 1_700_000_000 && secs <= now);
+    assert!(millis / 1000 >= secs && millis / 1000 <= now);
+}
+
+#[test]
+fn test_build_time_strings() {
+    let (time, date): (&str, &str) = (build_time!(rfc3339), build_time!(date));
+    assert_eq!(time.len(), "2024-01-02T03:04:05Z".len());
+    assert_eq!(date.len(), "2024-01-02".len());
+    assert_eq!(&time[10..11], "T");
+    assert!(time.ends_with('Z'));
+    assert!(time.starts_with("20"));
+    assert_eq!(build_time!().len(), time.len());
+}
+
+#[test]
+fn test_build_time_macro() {
+    macro_rules! version {
+        () => {
+            concat!("v1.0 (", build_time!(date, source_date_epoch), ")")
+        };
+    }
+
+    assert!(version!().starts_with("v1.0 (20"));
+}
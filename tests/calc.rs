@@ -0,0 +1,52 @@
+//  CALC.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `calc!()`-macro.
+//
+
+use macro_toolkit::calc;
+
+
+/***** TESTS *****/
+#[test]
+fn test_calc_int() {
+    assert_eq!(calc!(1 + 2 * 3), 7);
+    assert_eq!(calc!((1 + 2) * 3), 9);
+    assert_eq!(calc!(10 - 4 - 3), 3);
+    assert_eq!(calc!(-7 / 2), -3);
+    assert_eq!(calc!(-7 % 2), -1);
+    assert_eq!(calc!(1 << 2 + 1), 8);
+    assert_eq!(calc!(0xF0 | 0x0F & 0x3C ^ 0x01), 0xF0 | (0x0F & 0x3C) ^ 0x01);
+    assert_eq!(calc!(- -5), 5);
+    assert_eq!(calc!(i128 1 << 126), 1i128 << 126);
+}
+
+#[test]
+fn test_calc_typed() {
+    assert_eq!(calc!(u8 255 - 1), 254u8);
+    assert_eq!(calc!(1u16 << 15), 0x8000u16);
+    assert_eq!(calc!(i8 -128 / 2), -64i8);
+    assert_eq!(calc!(u32 2 * 3u32), 6u32);
+    assert_eq!(calc!(f64 1 / 2), 0.5f64);
+    assert_eq!(calc!(2.5f32 * 2.0), 5.0f32);
+}
+
+#[test]
+fn test_calc_float() {
+    assert_eq!(calc!(1.5 + 2.25), 3.75);
+    assert_eq!(calc!(-(0.5 - 1.5)), 1.0);
+    assert_eq!(calc!(7.5 % 2.0), 1.5);
+}
+
+#[test]
+fn test_calc_macro() {
+    macro_rules! mask {
+        ($width:literal at $offset:expr) => {
+            calc!(u32 ((1 << $width) - 1) << $offset)
+        };
+    }
+
+    assert_eq!(mask!(4 at 8), 0x0F00u32);
+    assert_eq!(mask!(1 at 4 * 2 - 1), 0x80u32);
+}
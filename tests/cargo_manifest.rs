@@ -0,0 +1,61 @@
+//  CARGO MANIFEST.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `cargo_manifest!()`-macro.
+//
+
+use macro_toolkit::cargo_manifest;
+
+
+/***** HELPERS *****/
+/// Collects the tokens given to it as a string.
+macro_rules! stringify_all {
+    ($($t:tt)*) => {
+        stringify!($($t)*)
+    };
+}
+
+/// Describes a key and its value.
+macro_rules! describe {
+    ($key:literal, $value:literal) => {
+        format!("{} = {}", $key, $value)
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_cargo_manifest_strings() {
+    assert_eq!(cargo_manifest!(package.name), "macro-toolkit");
+    assert_eq!(cargo_manifest!(package.license), "Apache-2.0");
+    assert_eq!(cargo_manifest!(package.authors.0), "Lut99");
+}
+
+#[test]
+fn test_cargo_manifest_arrays() {
+    assert_eq!(cargo_manifest!(package.authors), ["Lut99"]);
+    assert_eq!(cargo_manifest!(package.metadata.docs.rs."rustdoc-args"), ["--cfg", "docsrs"]);
+}
+
+#[test]
+fn test_cargo_manifest_bools() {
+    let all: bool = cargo_manifest!(package.metadata.docs.rs."all-features");
+    assert!(all);
+}
+
+#[test]
+fn test_cargo_manifest_idents() {
+    #[allow(non_upper_case_globals)]
+    const Lut99: &str = "the author";
+    assert_eq!(cargo_manifest!(package.authors.0 as ident), "the author");
+    assert_eq!(cargo_manifest!(package.metadata.docs.rs."rustdoc-args".1 as ident => stringify_all!()), "docsrs");
+}
+
+#[test]
+fn test_cargo_manifest_callback() {
+    assert_eq!(cargo_manifest!(package.edition => describe!("edition",)), "edition = 2024");
+}
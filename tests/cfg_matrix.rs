@@ -0,0 +1,61 @@
+//  CFG MATRIX.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[cfg_matrix]`-attribute.
+//
+
+use macro_toolkit::cfg_matrix;
+
+
+/***** HELPERS *****/
+#[cfg_matrix(
+    target_pointer_width = "64" => { Int = u64, BITS = 64 },
+    not(target_pointer_width = "64") => { Int = { Option<usize> }, BITS = 0 },
+)]
+fn pointer_bits() -> (Int, u32) { (<Int>::default(), BITS) }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_cfg_matrix_substitutions() {
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(pointer_bits(), (0, 64));
+    #[cfg(not(target_pointer_width = "64"))]
+    assert_eq!(pointer_bits(), (None, 0));
+}
+
+#[test]
+fn test_cfg_matrix_always() {
+    // `all()` always holds and `any()` never does
+    #[cfg_matrix(all() => { VALUE = 42 }, any() => { VALUE = { compile_error!("Unreachable") } })]
+    const ANSWER: u32 = VALUE;
+
+    assert_eq!(ANSWER, 42);
+}
+
+#[test]
+fn test_cfg_matrix_no_substitutions() {
+    #[cfg_matrix(debug_assertions, not(debug_assertions))]
+    struct Foo(u32);
+
+    assert_eq!(Foo(42).0, 42);
+}
+
+#[test]
+fn test_cfg_matrix_macro_rules() {
+    macro_rules! optional {
+        ($name:ident, $cfg:meta) => {
+            #[cfg_matrix($cfg => { VALUE = Some(stringify!($cfg)) }, not($cfg) => { VALUE = None })]
+            fn $name() -> Option<&'static str> { VALUE }
+        };
+    }
+
+    optional!(always, all());
+    optional!(never, any());
+    assert_eq!(always(), Some("all()"));
+    assert_eq!(never(), None);
+}
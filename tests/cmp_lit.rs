@@ -0,0 +1,57 @@
+//  CMP LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `cmp_lit!()`-macro.
+//
+
+use macro_toolkit::cmp_lit;
+
+
+/***** HELPERS *****/
+/// Shows how two literals compare.
+macro_rules! cmp {
+    ($lhs:literal, $rhs:literal) => {
+        cmp_lit!($lhs, $rhs, lt => { '<' }, eq => { '=' }, gt => { '>' })
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_cmp_lit_numbers() {
+    assert_eq!(cmp!(1, 2), '<');
+    assert_eq!(cmp!(2u8, 2usize), '=');
+    assert_eq!(cmp!(0x10, 15), '>');
+    assert_eq!(cmp_lit!(-3, -4, lt => { '<' }, eq => { '=' }, gt => { '>' }), '>');
+    assert_eq!(cmp_lit!(-0, 0, lt => { '<' }, eq => { '=' }, gt => { '>' }), '=');
+    assert_eq!(cmp!(1.5, 1.25), '>');
+    assert_eq!(cmp!(1e3, 1000.0), '=');
+}
+
+#[test]
+fn test_cmp_lit_others() {
+    assert_eq!(cmp!("abc", "abd"), '<');
+    assert_eq!(cmp!("b", "abc"), '>');
+    assert_eq!(cmp!(b"abc", b"abc"), '=');
+    assert_eq!(cmp!('a', 'b'), '<');
+    assert_eq!(cmp!(b'b', b'a'), '>');
+    assert_eq!(cmp!(true, false), '>');
+}
+
+#[test]
+fn test_cmp_lit_patterns() {
+    assert_eq!(cmp_lit!(1, 2, le => { 1 } _ => { 2 }), 1);
+    assert_eq!(cmp_lit!(2, 2, ne => { 1 } _ => { 2 }), 2);
+    assert_eq!(cmp_lit!(3, 2, lt | eq => { 1 }, ge => { 2 }), 2);
+    assert_eq!(cmp_lit!(3, 2, gt => { 1 }, gt => { 2 }), 1);
+
+    let mut x: u32 = 0;
+    cmp_lit!(1, 2, gt => { x += 1; });
+    cmp_lit!(1, 2, lt => { x += 2; });
+    cmp_lit!(1, 2,);
+    assert_eq!(x, 2);
+}
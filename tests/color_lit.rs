@@ -0,0 +1,45 @@
+//  COLOR LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `color_lit!()`-macro.
+//
+
+use macro_toolkit::color_lit;
+
+
+/***** TESTS *****/
+#[test]
+fn test_color_lit_tuple() {
+    assert_eq!(color_lit!("#000000"), (0, 0, 0, 255));
+    assert_eq!(color_lit!("#12AbEf"), (0x12, 0xAB, 0xEF, 0xFF));
+    assert_eq!(color_lit!("#abc"), (0xAA, 0xBB, 0xCC, 0xFF));
+    assert_eq!(color_lit!("#abcd"), (0xAA, 0xBB, 0xCC, 0xDD));
+    assert_eq!(color_lit!(tuple "#01020304"), (1, 2, 3, 4));
+}
+
+#[test]
+fn test_color_lit_packed() {
+    assert_eq!(color_lit!(rgb "#123456"), 0x00123456u32);
+    assert_eq!(color_lit!(rgba "#123456"), 0x123456FFu32);
+    assert_eq!(color_lit!(rgba "#12345678"), 0x12345678u32);
+    assert_eq!(color_lit!(argb "#12345678"), 0x78123456u32);
+    assert_eq!(color_lit!(argb "#fff"), u32::MAX);
+}
+
+#[test]
+fn test_color_lit_callback() {
+    macro_rules! sum {
+        ($($c:literal),*) => {
+            0u32 $(+ $c as u32)*
+        };
+    }
+    macro_rules! color {
+        ($lit:literal) => {
+            color_lit!($lit => sum!)
+        };
+    }
+
+    assert_eq!(color!("#010203"), 1 + 2 + 3 + 255);
+    assert_eq!(color_lit!("#0000" => sum!), 0);
+}
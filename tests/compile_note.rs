@@ -0,0 +1,71 @@
+//  COMPILE NOTE.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `compile_note!()`-macro.
+//!
+//!   Note that the diagnostics themselves are silenced, as they would otherwise fail the lints.
+//!   This only tests that the macro can be used where it should be.
+//
+
+// NOTE: On stable, the warnings are deprecation warnings
+#![allow(deprecated)]
+
+use macro_toolkit::compile_note;
+
+
+/***** HELPERS *****/
+compile_note!(note: "A note in item position");
+compile_note! {
+    warning(call_site): "A warning at the call site";
+    note(mixed_site): "with a note";
+    help: "and some help";
+}
+
+/// Defines a function, warning if it is asked to be public.
+macro_rules! define {
+    (pub $name:ident) => {
+        compile_note! {
+            warning($name): "Functions defined with `define!()` are never public";
+            help(pub): "remove the `pub`";
+        }
+        define!($name);
+    };
+    ($name:ident) => {
+        fn $name() -> &'static str { stringify!($name) }
+    };
+}
+
+define!(pub exported);
+define!(normal);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_compile_note() {
+    compile_note!(help(mixed_site): "Some help in statement position";);
+    assert_eq!(exported(), "exported");
+    assert_eq!(normal(), "normal");
+}
+
+#[test]
+fn test_compile_note_macro_rules() {
+    macro_rules! checked_div {
+        ($lhs:literal / 0) => {{
+            compile_note! {
+                warning($lhs): "Dividing by zero always returns `None`";
+                note(0): "zero given here";
+            }
+            None::<u32>
+        }};
+        ($lhs:literal / $rhs:literal) => {
+            u32::checked_div($lhs, $rhs)
+        };
+    }
+
+    assert_eq!(checked_div!(42 / 0), None);
+    assert_eq!(checked_div!(42 / 2), Some(21));
+}
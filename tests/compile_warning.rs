@@ -0,0 +1,61 @@
+//  COMPILE WARNING.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `compile_warning!()`-macro.
+//!
+//!   Note that the warnings themselves are silenced, as they would otherwise fail the lints. This
+//!   only tests that the macro can be used where it should be.
+//
+
+// NOTE: On stable, the warnings are deprecation warnings
+#![allow(deprecated)]
+
+use macro_toolkit::compile_warning;
+
+
+/***** HELPERS *****/
+compile_warning!("A warning in item position");
+compile_warning!(call_site, "A warning at the call site",);
+
+/// Defines a function, warning if it is asked to be public.
+macro_rules! define {
+    (pub $name:ident) => {
+        compile_warning!($name, "Functions defined with `define!()` are never public");
+        define!($name);
+    };
+    ($name:ident) => {
+        fn $name() -> &'static str { stringify!($name) }
+    };
+}
+
+define!(pub exported);
+define!(normal);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_compile_warning() {
+    compile_warning!(mixed_site, "A warning in statement position");
+    assert_eq!(exported(), "exported");
+    assert_eq!(normal(), "normal");
+}
+
+#[test]
+fn test_compile_warning_macro_rules() {
+    macro_rules! checked_div {
+        ($lhs:literal / 0) => {{
+            compile_warning!($lhs, "Dividing by zero always returns `None`");
+            None::<u32>
+        }};
+        ($lhs:literal / $rhs:literal) => {
+            u32::checked_div($lhs, $rhs)
+        };
+    }
+
+    assert_eq!(checked_div!(42 / 0), None);
+    assert_eq!(checked_div!(42 / 2), Some(21));
+}
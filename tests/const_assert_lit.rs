@@ -0,0 +1,66 @@
+//  CONST ASSERT LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `const_assert_lit!()`-macro.
+//
+
+use macro_toolkit::const_assert_lit;
+
+
+/***** TESTS *****/
+#[test]
+fn test_const_assert_lit_numeric() {
+    const_assert_lit!(1 == 1);
+    const_assert_lit!(1 + 2 * 3 == 7);
+    const_assert_lit!((1 + 2) * 3 != 7,);
+    const_assert_lit!(1 << 4 >= 16 >> 0);
+    const_assert_lit!(-1 < 0, "Negative numbers are broken");
+    const_assert_lit!(255u8 <= 200 + 55, "Suffixes are shared within a comparison",);
+    const_assert_lit!(0.5 > 0.25);
+    const_assert_lit!(-0.5f32 * 2.0 == -1.0);
+}
+
+#[test]
+fn test_const_assert_lit_literals() {
+    const_assert_lit!("abc" < "abd");
+    const_assert_lit!('a' != 'b');
+    const_assert_lit!(b'a' == b'a');
+    const_assert_lit!(b"abc" > b"ab");
+    const_assert_lit!(true > false);
+}
+
+#[test]
+fn test_const_assert_lit_logic() {
+    const_assert_lit!(true);
+    const_assert_lit!(!false);
+    const_assert_lit!(!!true);
+    const_assert_lit!(1 == 1 && 2 == 2);
+    const_assert_lit!(1 == 2 || 2 == 2);
+    const_assert_lit!(1 == 2 || 2 == 2 && 3 == 3);
+    const_assert_lit!(!(1 == 2 || false) && (true || false));
+    // Short-circuiting skips the division by zero
+    const_assert_lit!(0 == 0 || 1 / 0 == 0);
+    const_assert_lit!(!(0 != 0 && 1 / 0 == 0));
+}
+
+#[test]
+fn test_const_assert_lit_macro() {
+    macro_rules! bounded {
+        ($name:ident: $min:literal ..= $max:literal) => {
+            const_assert_lit!($min <= $max, "Range must not be empty");
+            const $name: (i32, i32) = ($min, $max);
+        };
+    }
+    macro_rules! check {
+        ($cond:expr) => {
+            const_assert_lit!($cond)
+        };
+    }
+    bounded!(PERCENT: 0..=100);
+    bounded!(SINGLE: -5..=-5);
+    check!(1 + 1 == 2);
+    check!((true));
+
+    assert_eq!([PERCENT, SINGLE], [(0, 100), (-5, -5)]);
+}
@@ -0,0 +1,75 @@
+//  CONST TABLE.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `const_table!()`-macro.
+//
+
+use macro_toolkit::const_table;
+
+
+/***** HELPERS *****/
+mod opcodes {
+    use super::const_table;
+
+    const_table! {
+        /// The mnemonics of the opcodes.
+        #[lookup(pub(crate) fn opcode)]
+        pub(crate) static MNEMONICS: [&str] = [
+            NOP => "nop",
+            /// Loads a value.
+            LOAD => "load",
+            STORE => "store",
+        ];
+    }
+}
+
+const_table! {
+    #[lookup(fn prime_index)]
+    const PRIMES: [i64] = [
+        MINUS_TWO => -2,
+        TWO => 2,
+        THREE => 3,
+        FIVE => 5,
+    ]
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_const_table() {
+    assert_eq!(opcodes::MNEMONICS, ["nop", "load", "store"]);
+    assert_eq!((opcodes::NOP, opcodes::LOAD, opcodes::STORE), (0, 1, 2));
+    assert_eq!(opcodes::MNEMONICS[opcodes::STORE], "store");
+}
+
+#[test]
+fn test_const_table_lookup() {
+    assert_eq!(opcodes::opcode("load"), Some(opcodes::LOAD));
+    assert_eq!(opcodes::opcode("jump"), None);
+    assert_eq!(prime_index(-2), Some(MINUS_TWO));
+    assert_eq!(prime_index(5), Some(FIVE));
+    assert_eq!(prime_index(4), None);
+    assert_eq!(PRIMES[THREE], 3);
+    assert_eq!(TWO, 1);
+}
+
+#[test]
+fn test_const_table_macro_rules() {
+    macro_rules! units {
+        ($($name:ident => $value:literal),*) => {
+            const_table! {
+                #[lookup(fn unit)]
+                static UNITS: [&str] = [$($name => $value),*];
+            }
+        };
+    }
+
+    units!(SECONDS => "s", MINUTES => "min", HOURS => "h");
+    assert_eq!(UNITS[MINUTES], "min");
+    assert_eq!(unit("h"), Some(HOURS));
+    assert_eq!(unit("d"), None);
+}
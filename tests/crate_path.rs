@@ -0,0 +1,49 @@
+//  CRATE PATH.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `crate_path!()`-macro.
+//
+
+use macro_toolkit::crate_path;
+
+
+/***** HELPERS *****/
+/// Appends a path to the path given by [`crate_path!()`].
+macro_rules! join {
+    ($($rest:ident)::+, $($krate:tt)*) => {
+        $($krate)*$(::$rest)+
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_crate_path_dependency() {
+    use typenum::Unsigned;
+
+    assert_eq!(<crate_path!(typenum => join!(U42,))>::USIZE, 42);
+    assert_eq!(<crate_path!("typenum" => join!(consts::U7,))>::U8, 7);
+}
+
+#[test]
+fn test_crate_path_self() {
+    // We're in a test, so the crate itself is not `crate`
+    assert_eq!(crate_path!("macro-toolkit" => stringify!).replace(' ', ""), "::macro_toolkit");
+    assert_eq!(crate_path!(macro_toolkit => stringify!).replace(' ', ""), "::macro_toolkit");
+}
+
+#[test]
+fn test_crate_path_macro_rules() {
+    macro_rules! type_of {
+        ($krate:literal, $ty:ident) => {
+            crate_path!($krate => join!($ty,))
+        };
+    }
+
+    type Three = type_of!("typenum", U3);
+    assert_eq!(<Three as typenum::Unsigned>::U32, 3);
+}
@@ -0,0 +1,61 @@
+//  CRATE VERSION PARTS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `crate_version_parts!()`-macro.
+//
+
+use macro_toolkit::crate_version_parts;
+
+
+/***** HELPERS *****/
+/// Formats the parts of a version as a string.
+macro_rules! format_version {
+    ($major:literal, $minor:literal, $patch:literal, $pre:literal) => {
+        if $pre.is_empty() { format!("{}.{}.{}", $major, $minor, $patch) } else { format!("{}.{}.{}-{}", $major, $minor, $patch, $pre) }
+    };
+}
+
+/// Defines constants with the parts of a version.
+macro_rules! version_consts {
+    ($major:literal, $minor:literal, $patch:literal, $pre:literal) => {
+        const MAJOR: u8 = $major;
+        const MINOR: u16 = $minor;
+        const PATCH: u32 = $patch;
+        const PRE: &str = $pre;
+    };
+}
+
+crate_version_parts!(=> version_consts!);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_crate_version_parts_tuple() {
+    assert_eq!(crate_version_parts!(), (0, 1, 0, ""));
+    assert_eq!(crate_version_parts!(tuple), (0, 1, 0, ""));
+}
+
+#[test]
+fn test_crate_version_parts_single() {
+    let major: u8 = crate_version_parts!(major);
+    let minor: i64 = crate_version_parts!(minor);
+    let patch: usize = crate_version_parts!(patch);
+    assert_eq!((major, minor, patch, crate_version_parts!(pre)), (0, 1, 0, ""));
+}
+
+#[test]
+fn test_crate_version_parts_packed() {
+    const PACKED: u64 = crate_version_parts!(packed);
+    assert_eq!(PACKED, 1 << 16);
+    const { assert!(PACKED > 0x0000_0000_0000_FFFF && PACKED < 0x0000_0000_0002_0000) };
+}
+
+#[test]
+fn test_crate_version_parts_callback() {
+    assert_eq!(crate_version_parts!(=> format_version!), env!("CARGO_PKG_VERSION"));
+    assert_eq!((MAJOR, MINOR, PATCH, PRE), (0, 1, 0, ""));
+}
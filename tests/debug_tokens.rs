@@ -0,0 +1,49 @@
+//  DEBUG TOKENS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `debug_tokens!()`-macro.
+//
+
+use macro_toolkit::debug_tokens;
+
+
+/***** HELPERS *****/
+debug_tokens!(pretty, {
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl Point {
+        fn manhattan(&self) -> i32 { self.x.abs() + self.y.abs() }
+    }
+});
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_debug_tokens() {
+    assert_eq!(debug_tokens!({ 21 * 2 }), 42);
+    assert_eq!(debug_tokens!(pretty, { if true { 42 } else { 0 } }), 42);
+}
+
+#[test]
+fn test_debug_tokens_items() {
+    assert_eq!(Point { x: -1, y: 2 }.manhattan(), 3);
+}
+
+#[test]
+fn test_debug_tokens_macro_rules() {
+    macro_rules! sum {
+        () => { 0 };
+        ($head:literal $(, $tail:literal)*) => { debug_tokens!(pretty, { $head + sum!($($tail),*) }) };
+    }
+
+    assert_eq!(sum!(), 0);
+    assert_eq!(sum!(1, 2, 3), 6);
+}
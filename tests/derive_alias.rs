@@ -0,0 +1,76 @@
+//  DERIVE ALIAS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `derive_alias!{}`-macro.
+//
+
+use macro_toolkit::{apply_alias, derive_alias};
+
+
+/***** HELPERS *****/
+derive_alias! {
+    /// The derives of every value type.
+    Standard = Debug, Clone, Copy, PartialEq, Eq, Hash;
+    Ordered = PartialOrd, Ord,;
+}
+
+mod aliases {
+    use macro_toolkit::derive_alias;
+
+    derive_alias! {
+        pub Defaulted = Default, ::core::fmt::Debug;
+    }
+}
+
+#[apply_alias(Standard)]
+struct Id(u64);
+
+#[apply_alias(Standard, Ordered)]
+struct Version(u32, u32);
+
+#[apply_alias(aliases::Defaulted)]
+struct Config {
+    verbose: bool,
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_derive_alias_single() {
+    let id = Id(42);
+    assert_eq!(id, id.clone());
+    assert_eq!(format!("{id:?}"), "Id(42)");
+}
+
+#[test]
+fn test_derive_alias_multiple() {
+    assert!(Version(1, 2) < Version(1, 10));
+    assert_eq!(Version(1, 2).max(Version(2, 0)), Version(2, 0));
+}
+
+#[test]
+fn test_derive_alias_path() {
+    assert!(!Config::default().verbose);
+    assert_eq!(format!("{:?}", Config::default()), "Config { verbose: false }");
+}
+
+#[test]
+fn test_derive_alias_macro_rules() {
+    macro_rules! comparable {
+        ($name:ident) => {
+            derive_alias! {
+                Comparable = PartialEq, Debug;
+            }
+
+            #[apply_alias(Comparable)]
+            struct $name;
+        };
+    }
+    comparable!(Unit);
+
+    assert_eq!(Unit, Unit);
+}
@@ -0,0 +1,99 @@
+//  DERIVE VIA.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[derive_via]`-macro.
+//
+
+use macro_toolkit::derive_via;
+
+
+/***** HELPERS *****/
+trait Named {
+    fn name() -> &'static str;
+}
+macro_rules! Named {
+    ($(#[$attr:meta])* $vis:vis $kw:ident $name:ident $($rest:tt)*) => {
+        impl Named for $name {
+            fn name() -> &'static str { stringify!($name) }
+        }
+    };
+}
+
+trait Fields {
+    fn fields() -> Vec<(&'static str, &'static str)>;
+}
+macro_rules! Fields {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident { $($(#[rename = $rename:literal])? $fvis:vis $field:ident: $ty:ty),* $(,)? }) => {
+        impl Fields for $name {
+            fn fields() -> Vec<(&'static str, &'static str)> {
+                vec![$((stringify!($field), Fields!(@name $field $($rename)?))),*]
+            }
+        }
+    };
+    (@name $field:ident $rename:literal) => { $rename };
+    (@name $field:ident) => { stringify!($field) };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_derive_via_basic() {
+    #[derive_via(Named!)]
+    #[derive(Debug, PartialEq)]
+    struct Foo;
+    #[derive_via(Named!)]
+    #[allow(dead_code)]
+    enum Bar {
+        Baz,
+    }
+
+    assert_eq!(Foo::name(), "Foo");
+    assert_eq!(Foo, Foo);
+    assert_eq!(Bar::name(), "Bar");
+}
+
+#[test]
+fn test_derive_via_helpers() {
+    #[derive_via(Fields!, Named!, attributes(rename))]
+    #[allow(dead_code)]
+    struct Foo {
+        #[rename = "first"]
+        a: u32,
+        b: String,
+    }
+
+    assert_eq!(Foo::name(), "Foo");
+    assert_eq!(Foo::fields(), vec![("a", "first"), ("b", "b")]);
+}
+
+#[test]
+fn test_derive_via_args() {
+    macro_rules! with_id {
+        ($id:literal, struct $name:ident;) => {
+            impl $name {
+                const ID: u32 = $id;
+            }
+        };
+    }
+
+    #[derive_via(with_id!(42,))]
+    struct Foo;
+    assert_eq!(Foo::ID, 42);
+}
+
+#[test]
+fn test_derive_via_macro_rules() {
+    macro_rules! wrap {
+        ($cb:path, $name:ident) => {
+            #[derive_via($cb!)]
+            struct $name;
+        };
+    }
+
+    wrap!(Named, Foo);
+    assert_eq!(Foo::name(), "Foo");
+}
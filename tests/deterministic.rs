@@ -0,0 +1,36 @@
+//  DETERMINISTIC.rs
+//    by Lut99
+//
+//  Description:
+//!   Tests the macros that are affected by the `deterministic`-feature.
+//
+
+use macro_toolkit::{gensym, rand_lit};
+
+
+/***** TESTS *****/
+#[test]
+fn test_deterministic_rand_lit() {
+    macro_rules! twice {
+        () => {
+            [rand_lit!(u64), rand_lit!(u64)]
+        };
+    }
+
+    // Different locations and repeated expansions of the same location still differ
+    let [a, b] = twice!();
+    let [c, d] = twice!();
+    let mut values: [u64; 5] = [a, b, c, d, rand_lit!(u64)];
+    values.sort();
+    for i in 1..values.len() {
+        assert_ne!(values[i - 1], values[i]);
+    }
+}
+
+#[test]
+fn test_deterministic_gensym() {
+    let names: [&str; 3] = [gensym!(=> stringify!), gensym!(=> stringify!), gensym!(a, b => stringify!)];
+    assert_ne!(names[0], names[1]);
+    assert!(names[0].starts_with("__mtk_gensym_"));
+    assert!(names[2].starts_with("__mtk_a_"));
+}
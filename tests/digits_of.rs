@@ -0,0 +1,41 @@
+//  DIGITS OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `digits_of!()`-macro.
+//
+
+use macro_toolkit::digits_of;
+
+
+/***** TESTS *****/
+#[test]
+fn test_digits_of_simple() {
+    assert_eq!(digits_of!(42), [4, 2]);
+    assert_eq!(digits_of!(7u8), [7]);
+    assert_eq!(digits_of!(1_000), [1, 0, 0, 0]);
+}
+
+#[test]
+fn test_digits_of_radix() {
+    assert_eq!(digits_of!(255, radix = 16), [15, 15]);
+    assert_eq!(digits_of!(8, radix = 2), [1, 0, 0, 0]);
+    assert_eq!(digits_of!(35, radix = 36), [35]);
+}
+
+#[test]
+fn test_digits_of_callback() {
+    macro_rules! count {
+        ($($digit:literal),*) => {
+            0 $(+ { let _ = $digit; 1 })*
+        };
+    }
+    macro_rules! digits {
+        ($lit:literal) => {
+            digits_of!($lit => count!)
+        };
+    }
+
+    assert_eq!(digits!(12345), 5);
+    assert_eq!(digits_of!(0xFFFF, radix = 16 => count!), 4);
+}
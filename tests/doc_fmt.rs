@@ -0,0 +1,73 @@
+//  DOC FMT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `doc_fmt!()`-macro.
+//
+
+use macro_toolkit::doc_fmt;
+
+
+/***** HELPERS *****/
+/// Generates a documented struct that can return its own documentation.
+macro_rules! documented {
+    ($name:ident, $value:literal) => {
+        doc_fmt!("The `{}`-struct.\n\nIt has value {:?}.", $name, $value =>
+            #[derive(Debug)]
+            struct $name;
+        );
+    };
+}
+documented!(Foo, "foo");
+
+/// Contains items documented by `doc_fmt!()`, which are checked by `missing_docs`.
+#[deny(missing_docs)]
+pub mod generated {
+    use macro_toolkit::doc_fmt;
+
+    doc_fmt!("The answer to {}.", everything => pub const ANSWER: u32 = 42;);
+    doc_fmt!("A {kind}.\n\nIt has {} field(s).", 1, kind = r#struct =>
+        pub struct Bar {
+            #[doc = doc_fmt!("The {} of the {}.", value, "struct")]
+            pub value: u32,
+        }
+    );
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_doc_fmt() {
+    assert_eq!(doc_fmt!("Hello, world!"), "Hello, world!");
+    assert_eq!(doc_fmt!("Hello, {}!", "world"), "Hello, world!");
+    assert_eq!(doc_fmt!("Hello, {}!", world), "Hello, world!");
+    assert_eq!(doc_fmt!("Hello, {}!", r#type), "Hello, type!");
+    assert_eq!(doc_fmt!("{{}} {{{}}}", 1), "{} {1}");
+}
+
+#[test]
+fn test_doc_fmt_args() {
+    assert_eq!(doc_fmt!("{} {} {}", 'a', "b", c), "a b c");
+    assert_eq!(doc_fmt!("{1} {0} {1}", 'a', "b"), "b a b");
+    assert_eq!(doc_fmt!("{} {name} {}", 1, 2, name = 3), "1 3 2");
+    assert_eq!(doc_fmt!("{a} {b} {a}", a = 1, b = 2,), "1 2 1");
+}
+
+#[test]
+fn test_doc_fmt_values() {
+    assert_eq!(doc_fmt!("{} {} {} {}", 42u8, -42, 0x10, 1_000), "42 -42 16 1000");
+    assert_eq!(doc_fmt!("{} {} {}", 4.5, -1e3, 2.0f32), "4.5 -1e3 2.0");
+    assert_eq!(doc_fmt!("{} {}", true, false), "true false");
+    assert_eq!(doc_fmt!("{} {:?}", "a\"b\n", "a\"b\n"), "a\"b\n \"a\\\"b\\n\"");
+    assert_eq!(doc_fmt!("{} {:?}", '\'', '\''), "' '\\''");
+}
+
+#[test]
+fn test_doc_fmt_attrs() {
+    assert_eq!(format!("{:?}", Foo), "Foo");
+    assert_eq!(generated::ANSWER, 42);
+    assert_eq!(generated::Bar { value: 42 }.value, 42);
+}
@@ -0,0 +1,49 @@
+//  DUMP EXPANSION.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `dump_expansion!()`-macro.
+//
+
+use macro_toolkit::dump_expansion;
+
+
+/***** HELPERS *****/
+dump_expansion!("tests/dump_expansion/items", pretty, {
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl Point {
+        fn manhattan(&self) -> i32 { self.x.abs() + self.y.abs() }
+    }
+});
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_dump_expansion() {
+    assert_eq!(dump_expansion!("tests/dump_expansion/expr", { 21 * 2 }), 42);
+    assert_eq!(dump_expansion!("tests/dump_expansion/pretty", pretty, { if true { 42 } else { 0 } }), 42);
+}
+
+#[test]
+fn test_dump_expansion_items() {
+    assert_eq!(Point { x: -1, y: 2 }.manhattan(), 3);
+}
+
+#[test]
+fn test_dump_expansion_macro_rules() {
+    macro_rules! sum {
+        () => { 0 };
+        ($head:literal $(, $tail:literal)*) => { dump_expansion!("tests/dump_expansion/sum", { $head + sum!($($tail),*) }) };
+    }
+
+    assert_eq!(sum!(), 0);
+    assert_eq!(sum!(1, 2, 3), 6);
+}
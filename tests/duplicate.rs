@@ -0,0 +1,72 @@
+//  DUPLICATE.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[duplicate]`-macro.
+//
+
+use std::collections::HashMap;
+
+use macro_toolkit::duplicate;
+
+
+/***** HELPERS *****/
+trait Bits {
+    const BITS: u32;
+}
+
+#[duplicate(T = [u8, u16, u32, u64], N = [8, 16, 32, 64])]
+impl Bits for T {
+    const BITS: u32 = N;
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_duplicate_impls() {
+    assert_eq!(<u8 as Bits>::BITS, u8::BITS);
+    assert_eq!(<u16 as Bits>::BITS, u16::BITS);
+    assert_eq!(<u32 as Bits>::BITS, u32::BITS);
+    assert_eq!(<u64 as Bits>::BITS, u64::BITS);
+}
+
+#[test]
+fn test_duplicate_values() {
+    #[duplicate(NAME = [list, map, pair,], T = [Vec<u8>, { HashMap<u8, u8> }, { (u8, u8) }])]
+    fn NAME() -> T {
+        <T>::default()
+    }
+
+    assert!(list().is_empty());
+    assert!(map().is_empty());
+    assert_eq!(pair(), (0, 0));
+}
+
+#[test]
+fn test_duplicate_nested() {
+    #[duplicate(NAME = [one, two], V = [2, 3])]
+    fn NAME() -> u32 {
+        #[duplicate(C = [A, B], X = [10, 100])]
+        const C: u32 = V * X;
+        A + B
+    }
+
+    assert_eq!(one(), 220);
+    assert_eq!(two(), 330);
+}
+
+#[test]
+fn test_duplicate_macro_rules() {
+    macro_rules! consts {
+        ($($name:ident = $value:literal),*) => {
+            #[duplicate(NAME = [$($name),*], VALUE = [$($value),*])]
+            const NAME: u32 = VALUE;
+        };
+    }
+
+    consts!(A = 1, B = 2);
+    assert_eq!(A + B, 3);
+}
@@ -0,0 +1,47 @@
+//  DURATION LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `duration_lit!()`-macro.
+//
+
+use macro_toolkit::duration_lit;
+
+
+/***** TESTS *****/
+#[test]
+fn test_duration_lit_simple() {
+    assert_eq!(duration_lit!("5s"), 5_000_000_000);
+    assert_eq!(duration_lit!("1ns"), 1);
+    assert_eq!(duration_lit!("1us 1µs"), 2_000);
+    assert_eq!(duration_lit!("1_000ms"), 1_000_000_000);
+    assert_eq!(duration_lit!(" 1m  1min "), 120_000_000_000);
+}
+
+#[test]
+fn test_duration_lit_units() {
+    assert_eq!(duration_lit!(ns "1s"), 1_000_000_000);
+    assert_eq!(duration_lit!(us "1s"), 1_000_000);
+    assert_eq!(duration_lit!(ms "1s"), 1_000);
+    assert_eq!(duration_lit!(s "1w"), 604_800);
+    assert_eq!(duration_lit!(h "1w1d"), 192);
+    assert_eq!(duration_lit!(d "2w"), 14);
+}
+
+#[test]
+fn test_duration_lit_fractions() {
+    assert_eq!(duration_lit!(ms "0.5s"), 500);
+    assert_eq!(duration_lit!(s ".25h"), 900);
+    assert_eq!(duration_lit!("1.000000001s"), 1_000_000_001);
+}
+
+#[test]
+fn test_duration_lit_macro() {
+    macro_rules! millis {
+        ($lit:literal) => {
+            duration_lit!(ms $lit)
+        };
+    }
+
+    assert_eq!(millis!("1h"), 3_600_000);
+}
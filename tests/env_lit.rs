@@ -0,0 +1,47 @@
+//  ENV LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `env_lit!()`-macro.
+//
+
+use macro_toolkit::env_lit;
+
+
+/***** TESTS *****/
+#[test]
+fn test_env_lit_int() {
+    assert_eq!(env_lit!("CARGO_PKG_VERSION_MAJOR" as u8), 0u8);
+    assert_eq!(env_lit!("CARGO_PKG_VERSION_MINOR" as usize), 1usize);
+    assert_eq!(env_lit!("CARGO_PKG_VERSION_MINOR" as i128), 1i128);
+}
+
+#[test]
+fn test_env_lit_float() {
+    assert_eq!(env_lit!("CARGO_PKG_VERSION_MINOR" as f64), 1.0f64);
+    assert_eq!(env_lit!("CARGO_PKG_VERSION_MINOR" as f32), 1.0f32);
+}
+
+#[test]
+fn test_env_lit_char() {
+    assert_eq!(env_lit!("CARGO_PKG_VERSION_PATCH" as char), '0');
+}
+
+#[test]
+fn test_env_lit_str() {
+    assert_eq!(env_lit!("CARGO_PKG_NAME" as str), "macro-toolkit");
+    assert_eq!(env_lit!("CARGO_PKG_VERSION_PRE" as str), "");
+}
+
+#[test]
+fn test_env_lit_macro_rules() {
+    macro_rules! version_part {
+        ($name:literal) => {
+            env_lit!($name as u32)
+        };
+    }
+
+    // The literal can be used where only literals are accepted, e.g., in `concat!()`
+    assert_eq!(concat!("v", env_lit!("CARGO_PKG_VERSION_MAJOR" as u8)), "v0");
+    assert_eq!(version_part!("CARGO_PKG_VERSION_MINOR"), 1);
+}
@@ -0,0 +1,47 @@
+//  ENV OR.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `env_or!()`-macro.
+//
+
+use macro_toolkit::env_or;
+
+
+/***** TESTS *****/
+#[test]
+fn test_env_or_unset() {
+    assert_eq!(env_or!("MACRO_TOOLKIT_TEST_UNSET", 4096usize), 4096usize);
+    assert_eq!(env_or!("MACRO_TOOLKIT_TEST_UNSET", -5i8), -5i8);
+    assert_eq!(env_or!("MACRO_TOOLKIT_TEST_UNSET", 1.5f32), 1.5f32);
+    assert_eq!((env_or!("MACRO_TOOLKIT_TEST_UNSET", true), env_or!("MACRO_TOOLKIT_TEST_UNSET", 'x')), (true, 'x'));
+    assert_eq!(env_or!("MACRO_TOOLKIT_TEST_UNSET", "default"), "default");
+}
+
+#[test]
+fn test_env_or_set() {
+    assert_eq!(env_or!("CARGO_PKG_VERSION_MINOR", 42usize), 1usize);
+    assert_eq!(env_or!("CARGO_PKG_VERSION_MINOR", -42i64), 1i64);
+    assert_eq!(env_or!("CARGO_PKG_VERSION_MINOR", 0.5f64), 1.0f64);
+    assert_eq!(env_or!("CARGO_PKG_VERSION_PATCH", 'x'), '0');
+    assert_eq!(env_or!("CARGO_PKG_NAME", "default"), "macro-toolkit");
+}
+
+#[test]
+fn test_env_or_unsuffixed() {
+    let size: u16 = env_or!("CARGO_PKG_VERSION_MINOR", 4096);
+    let ratio: f32 = env_or!("MACRO_TOOLKIT_TEST_UNSET", 0.5);
+    assert_eq!((size, ratio), (1, 0.5));
+}
+
+#[test]
+fn test_env_or_macro_rules() {
+    macro_rules! setting {
+        ($var:literal or $default:literal) => {
+            env_or!($var, $default)
+        };
+    }
+
+    assert_eq!(setting!("MACRO_TOOLKIT_TEST_UNSET" or -3i32), -3);
+    assert_eq!(setting!("CARGO_PKG_VERSION_MINOR" or -3i32), 1);
+}
@@ -0,0 +1,49 @@
+//  ERROR AT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `error_at!()`-macro.
+//!
+//!   Note that errors cannot be tested without failing the build. This only tests that the macro
+//!   can be used where it should be when it is not reached.
+//!
+//!   The macro is called by path, as unreached calls do not count as uses of an import.
+//
+
+
+/***** HELPERS *****/
+/// Defines a function, erroring if it is asked to be public.
+macro_rules! define {
+    (pub $name:ident) => {
+        macro_toolkit::error_at!($name, "Functions defined with `define!()` are never public");
+    };
+    ($name:ident) => {
+        fn $name() -> &'static str { stringify!($name) }
+    };
+}
+
+define!(normal);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_error_at() {
+    assert_eq!(normal(), "normal");
+}
+
+#[test]
+fn test_error_at_macro_rules() {
+    macro_rules! color {
+        (red) => { 0xFF0000 };
+        (green) => { 0x00FF00 };
+        ($other:ident) => {
+            macro_toolkit::error_at!($other, "Unknown color; expected `red` or `green`")
+        };
+    }
+
+    assert_eq!(color!(red), 0xFF0000);
+    assert_eq!(color!(green), 0x00FF00);
+}
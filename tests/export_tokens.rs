@@ -0,0 +1,87 @@
+//  EXPORT TOKENS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[export_tokens]`-attribute and the `import_tokens!()`-macro.
+//
+
+use macro_toolkit::{export_tokens, import_tokens};
+
+
+/***** HELPERS *****/
+#[export_tokens]
+#[derive(Debug, Default)]
+pub struct Foo {
+    pub bar: u32,
+    pub baz: String,
+}
+
+#[export_tokens(Dollars)]
+macro_rules! first {
+    ($first:expr $(, $rest:expr)*) => {
+        $first
+    };
+}
+
+#[allow(dead_code)]
+#[export_tokens]
+pub(crate) const unsafe extern "C" fn qux() -> u32 { 42 }
+
+/// Generates a struct with the same fields as the given one, but all wrapped in [`Option`]s.
+macro_rules! partial {
+    ($partial:ident, $(#[$attr:meta])* $vis:vis struct $name:ident { $($fvis:vis $field:ident: $ty:ty),* $(,)? }) => {
+        #[derive(Debug, Default)]
+        struct $partial {
+            $($field: Option<$ty>),*
+        }
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_export_tokens_struct() {
+    import_tokens!(Foo, partial!(PartialFoo,));
+
+    let foo = PartialFoo { bar: Some(42), ..Default::default() };
+    assert_eq!(foo.bar, Some(42));
+    assert_eq!(foo.baz, None);
+    assert_eq!(Foo::default().bar, 0);
+}
+
+#[test]
+fn test_export_tokens_dollars() {
+    macro_rules! copy {
+        ($new:ident, macro_rules! $name:ident { $($body:tt)* }) => {
+            macro_rules! $new { $($body)* }
+        };
+    }
+    import_tokens!(Dollars, copy!(second,));
+
+    assert_eq!(first!(1, 2, 3), 1);
+    assert_eq!(second!(1, 2, 3), 1);
+}
+
+#[test]
+fn test_export_tokens_qualifiers() {
+    assert!(import_tokens!(qux, stringify!).ends_with("fn qux() -> u32 { 42 }"));
+}
+
+#[test]
+fn test_export_tokens_macro_rules() {
+    macro_rules! names {
+        ($($item:ident),*) => {
+            [$(import_tokens!($item, name_of!)),*]
+        };
+    }
+    macro_rules! name_of {
+        ($(#[$attr:meta])* $vis:vis $kind:ident $(!)? $name:ident $($rest:tt)*) => {
+            stringify!($kind $name)
+        };
+    }
+
+    assert_eq!(names!(Foo, Dollars), ["struct Foo", "macro_rules first"]);
+}
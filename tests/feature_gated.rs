@@ -0,0 +1,61 @@
+//  FEATURE GATED.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[feature_gated]`-attribute.
+//
+
+use macro_toolkit::feature_gated;
+
+
+/***** HELPERS *****/
+// NOTE: The tests are only ran with the `feature_gated`-feature enabled, so we use that one
+#[feature_gated("feature_gated")]
+fn enabled() -> bool { true }
+#[feature_gated(not("feature_gated"))]
+fn enabled() -> bool { false }
+
+#[feature_gated(any("feature_gated", "unique_id"))]
+fn any() -> bool { true }
+#[feature_gated(not(any("feature_gated", "unique_id")))]
+fn any() -> bool { false }
+
+#[feature_gated(all("feature_gated", not("feature_gated")))]
+fn all() -> bool { true }
+#[feature_gated(not(all("feature_gated", not("feature_gated"))))]
+fn all() -> bool { false }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_feature_gated() {
+    assert!(enabled());
+    assert!(any());
+    assert!(!all());
+}
+
+#[test]
+fn test_feature_gated_other_attrs() {
+    /// Some documented struct.
+    #[feature_gated(all("feature_gated"))]
+    #[derive(Debug, Default, PartialEq)]
+    struct Foo(u8);
+
+    assert_eq!(Foo::default(), Foo(0));
+}
+
+#[test]
+fn test_feature_gated_macro_rules() {
+    macro_rules! gated_fn {
+        ($feature:literal, $name:ident) => {
+            #[feature_gated($feature)]
+            fn $name() -> bool { true }
+        };
+    }
+
+    gated_fn!("feature_gated", foo);
+    assert!(foo());
+}
@@ -0,0 +1,106 @@
+//  FIELDS OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `fields_of!()`-macro.
+//
+
+use macro_toolkit::fields_of;
+
+
+/***** HELPERS *****/
+/// Removes all whitespace from the given strings, as `stringify!()` does not guarantee it.
+fn strip<const N: usize>(parts: [&str; N]) -> [String; N] { parts.map(|part| part.split_whitespace().collect()) }
+
+/// Stringifies the kind of a struct and every part of its fields.
+macro_rules! fields_str {
+    (
+        name = [$($name:tt)*], generics = [$($generics:tt)*], where = [$($where:tt)*], kind = [$kind:ident],
+        fields = [$({ attrs = [$($attrs:tt)*], vis = [$($vis:tt)*], name = [$field:tt], ty = [$($ty:tt)*], index = [$index:literal], }),*],
+    ) => {
+        (stringify!($($name)*), stringify!($kind), [$([stringify!($($attrs)*), stringify!($($vis)*), stringify!($field), stringify!($($ty)*), stringify!($index)]),*])
+    };
+}
+
+/// Generates a `Debug`-like `describe()`-method listing every field.
+macro_rules! describe {
+    (@impl name = [$name:ident], generics = [$($generics:tt)*], where = [$($where:tt)*], kind = [$kind:ident], fields = [$({ attrs = [$($attrs:tt)*], vis = [$($vis:tt)*], name = [$field:tt], ty = [$ty:ty], index = [$index:literal], }),*],) => {
+        impl<$($generics)*> $name<$($generics)*> where $($where)* {
+            fn describe(&self) -> String {
+                let mut res = String::from(stringify!($name));
+                $(res.push_str(&format!(" {}={:?}", stringify!($field), self.$field));)*
+                res
+            }
+        }
+    };
+    ($item:item) => {
+        $item
+        fields_of!($item => describe!(@impl));
+    };
+}
+
+describe! {
+    #[derive(Clone)]
+    struct Named<T> where T: std::fmt::Debug {
+        id: u32,
+        pub value: T,
+    }
+}
+
+describe! {
+    struct Tuple<T>(u8, T) where T: std::fmt::Debug;
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_fields_of_template() {
+    let names: [&str; 2] = fields_of!(struct Foo { a: u8, #[doc = "B"] pub b: Vec<u8> } => { [#( stringify!(#name) ),*] });
+    assert_eq!(names, ["a", "b"]);
+    let indices: [usize; 3] = fields_of!(struct Foo(u8, u16, u32); => { [#( #index, )*] });
+    assert_eq!(indices, [0, 1, 2]);
+    let sizes: [usize; 2] = fields_of!(struct Foo(u8, u16); => { [#( ::std::mem::size_of::<#ty>() ),*] });
+    assert_eq!(sizes, [1, 2]);
+    let count: usize = fields_of!(struct Foo; => { 0 #( + #index )* });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_fields_of_callback() {
+    let (name, kind, fields) = fields_of!(struct Foo<'a> { #[doc = "A"] pub a: &'a str, b: std::collections::HashMap<u8, u16> } => fields_str!());
+    assert_eq!(name, "Foo");
+    assert_eq!(kind, "named");
+    assert_eq!(fields.map(strip), [
+        strip(["#[doc = \"A\"]", "pub", "a", "&'a str", "0"]),
+        strip(["", "", "b", "std::collections::HashMap<u8, u16>", "1"])
+    ]);
+
+    let (_, kind, fields) = fields_of!(pub struct Bar(pub(crate) u8); => fields_str!());
+    assert_eq!(kind, "tuple");
+    assert_eq!(fields.map(strip), [strip(["", "pub(crate)", "0", "u8", "0"])]);
+    let (_, kind, fields): (&str, &str, [[&str; 5]; 0]) = fields_of!(struct Baz; => fields_str!());
+    assert_eq!(kind, "unit");
+    assert_eq!(fields.len(), 0);
+}
+
+#[test]
+fn test_fields_of_derive() {
+    assert_eq!(Named { id: 1, value: "one" }.describe(), "Named id=1 value=\"one\"");
+    assert_eq!(Named { id: 1, value: "one" }.clone().describe(), "Named id=1 value=\"one\"");
+    assert_eq!(Tuple(42, 'c').describe(), "Tuple 0=42 1='c'");
+}
+
+#[test]
+fn test_fields_of_macro_rules() {
+    macro_rules! field_names {
+        ($item:item) => {
+            fields_of!($item => { [#( stringify!(#name) ),*] })
+        };
+    }
+
+    assert_eq!(field_names!(struct Point { x: f32, y: f32 }), ["x", "y"]);
+    assert_eq!(field_names!(struct Pair(u8, u8);), ["0", "1"]);
+}
@@ -0,0 +1,57 @@
+//  FIXED POINT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `fixed_point!()`-macro.
+//
+
+use macro_toolkit::fixed_point;
+
+
+/***** TESTS *****/
+#[test]
+fn test_fixed_point_formats() {
+    assert_eq!(fixed_point!(0.5, Q7), 64i8);
+    assert_eq!(fixed_point!(0.5, Q15), 16384i16);
+    assert_eq!(fixed_point!(-0.5, Q31), -0x4000_0000i32);
+    assert_eq!(fixed_point!(-1, Q63), i64::MIN);
+    assert_eq!(fixed_point!(3.75, Q3.4), 60i8);
+    assert_eq!(fixed_point!(-2.5, Q15.16), -0x28000i32);
+    assert_eq!(fixed_point!(0.5, UQ8), 128u8);
+    assert_eq!(fixed_point!(255.99609375, UQ8.8), u16::MAX);
+    assert_eq!(fixed_point!(1.0f32, UQ1.31), 0x8000_0000u32);
+}
+
+#[test]
+fn test_fixed_point_rounding() {
+    // 0.3 * 2^4 = 4.8
+    assert_eq!([fixed_point!(0.3, Q3.4), fixed_point!(0.3, Q3.4, round = nearest,)], [5i8, 5]);
+    assert_eq!(fixed_point!(0.3, Q3.4, round = floor), 4i8);
+    assert_eq!(fixed_point!(0.3, Q3.4, round = ceil), 5i8);
+    assert_eq!(fixed_point!(0.3, Q3.4, round = trunc), 4i8);
+    assert_eq!(fixed_point!(-0.3, Q3.4, round = floor), -5i8);
+    assert_eq!(fixed_point!(-0.3, Q3.4, round = ceil), -4i8);
+    assert_eq!(fixed_point!(-0.3, Q3.4, round = trunc), -4i8);
+    // Ties go away from zero
+    assert_eq!([fixed_point!(0.5, Q0.7), fixed_point!(0.00390625, Q7)], [64i8, 1]);
+    assert_eq!(fixed_point!(-0.00390625, Q7), -1i8);
+}
+
+#[test]
+fn test_fixed_point_range() {
+    assert_eq!(fixed_point!(0.9921875, Q7), i8::MAX);
+    assert_eq!(fixed_point!(0.999, Q7, round = floor), 127i8);
+    assert_eq!(fixed_point!(0.99999, Q15, round = trunc), i16::MAX);
+    assert_eq!(fixed_point!(-0.001, UQ8, round = ceil), 0u8);
+}
+
+#[test]
+fn test_fixed_point_macro() {
+    macro_rules! q15 {
+        ($($value:literal),*) => {
+            [$(fixed_point!($value, Q15)),*]
+        };
+    }
+
+    assert_eq!(q15!(0.25, -0.25, 0), [0x2000i16, -0x2000, 0]);
+}
@@ -0,0 +1,45 @@
+//  FMT CHECK.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `fmt_check!()`-macro.
+//
+
+use macro_toolkit::fmt_check;
+
+
+/***** TESTS *****/
+#[test]
+fn test_fmt_check_simple() {
+    assert_eq!(fmt_check!("Hello, world!"), "Hello, world!");
+    assert_eq!(fmt_check!("{{}} {}", a), "{{}} {}");
+    assert_eq!(fmt_check!("{1} {0}", a, b,), "{1} {0}");
+    assert_eq!(fmt_check!("{a} {b:?}", a = 1, b = 2), "{a} {b:?}");
+}
+
+#[test]
+fn test_fmt_check_spec() {
+    assert_eq!(fmt_check!("{:>8.3}", a), "{:>8.3}");
+    assert_eq!(fmt_check!("{:.*}", prec, a), "{:.*}");
+    assert_eq!(fmt_check!("{:0$}", width), "{:0$}");
+    assert_eq!(fmt_check!("{:#010x}", a), "{:#010x}");
+    assert_eq!(fmt_check!("{:-^width$.prec$e}", a, width = w, prec = p), "{:-^width$.prec$e}");
+}
+
+#[test]
+fn test_fmt_check_tokens() {
+    let a: u32 = 42;
+    assert_eq!(fmt_check!("{} == {}", a == 42, 42 => format!("{} == {}", a == 42, 42)), "true == 42");
+    assert_eq!(fmt_check!("{x}", x = a => a + 1), 43);
+}
+
+#[test]
+fn test_fmt_check_macro() {
+    macro_rules! checked_format {
+        ($fmt:literal $(, $arg:expr)*) => {
+            fmt_check!($fmt $(, $arg)* => format!($fmt $(, $arg)*))
+        };
+    }
+
+    assert_eq!(checked_format!("{}-{}", 1, 2), "1-2");
+}
@@ -0,0 +1,63 @@
+//  GENERICS MERGE.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `generics_merge!()`-macro.
+//
+
+use macro_toolkit::generics_merge;
+
+
+/***** HELPERS *****/
+/// Removes all whitespace from the given strings, as `stringify!()` does not guarantee it.
+fn strip<const N: usize>(parts: [&str; N]) -> [String; N] { parts.map(|part| part.split_whitespace().collect()) }
+
+/// Stringifies the merged generics.
+macro_rules! merged_str {
+    (impl_generics = [$($impl:tt)*], type_generics = [$($ty:tt)*], where_clause = [$($where:tt)*],) => {
+        [stringify!($($impl)*), stringify!($($ty)*), stringify!($($where)*)]
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_generics_merge() {
+    let parts = generics_merge!([<T: Clone, const N: usize = 3> where T: Default] [<'a, T: Clone + 'a, U = u8> where T: Default, U: Copy] => merged_str!());
+    assert_eq!(strip(parts), strip(["<'a, T: Clone + 'a, const N: usize, U>", "<'a, T, N, U>", "where T: Default, U: Copy,"]));
+}
+
+#[test]
+fn test_generics_merge_empty() {
+    let parts = generics_merge!([] [] => merged_str!());
+    assert_eq!(strip(parts), strip(["", "", ""]));
+    let parts = generics_merge!([<T>] [where T: Copy] => merged_str!());
+    assert_eq!(strip(parts), strip(["<T>", "<T>", "where T: Copy,"]));
+    let parts = generics_merge!([<F: Fn(u8) -> Vec<u8>>] [<F: Fn(u8) -> Vec<u8> + Send, const N: usize>] => merged_str!());
+    assert_eq!(strip(parts), strip(["<F: Fn(u8) -> Vec<u8> + Send, const N: usize>", "<F, N>", ""]));
+}
+
+#[test]
+fn test_generics_merge_macro_rules() {
+    trait Pick<T> {
+        fn pick(&self) -> T;
+    }
+    macro_rules! impl_pick {
+        (@impl ($name:ident) impl_generics = [$($impl:tt)*], type_generics = [$($ty:tt)*], where_clause = [$($where:tt)*],) => {
+            impl $($impl)* Pick<T> for $name $($ty)* $($where)* {
+                fn pick(&self) -> T { self.0.clone() }
+            }
+        };
+        (struct $name:ident [$($params:tt)*] ($($fields:tt)*);) => {
+            struct $name<$($params)*>($($fields)*);
+            generics_merge!([<$($params)*>] [<T: Clone> where T: Default] => impl_pick!(@impl ($name)));
+        };
+    }
+
+    impl_pick!(struct Pair['a, T: PartialEq](T, &'a str););
+    assert_eq!(Pair(42, "hello").pick(), 42);
+    assert_eq!(Pair(42, "hello").1, "hello");
+}
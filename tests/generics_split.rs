@@ -0,0 +1,88 @@
+//  GENERICS SPLIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `generics_split!()`-macro.
+//
+
+use macro_toolkit::generics_split;
+
+
+/***** HELPERS *****/
+/// Removes all whitespace from the given strings, as `stringify!()` does not guarantee it.
+fn strip<const N: usize>(parts: [&str; N]) -> [String; N] { parts.map(|part| part.split_whitespace().collect()) }
+
+/// Stringifies the split generics.
+macro_rules! split_str {
+    (impl_generics = [$($impl:tt)*], type_generics = [$($ty:tt)*], where_clause = [$($where:tt)*],) => {
+        [stringify!($($impl)*), stringify!($($ty)*), stringify!($($where)*)]
+    };
+}
+
+/// Implements [`Default`] for a wrapper type by using the split generics.
+macro_rules! impl_default {
+    (($name:ident) impl_generics = [$($impl:tt)*], type_generics = [$($ty:tt)*], where_clause = [$($where:tt)*],) => {
+        impl $($impl)* Default for $name $($ty)* $($where)* {
+            fn default() -> Self { Self(Default::default()) }
+        }
+    };
+}
+
+#[derive(Debug, PartialEq)]
+struct Wrapper<T: Clone = u32>(T)
+where
+    T: Default;
+generics_split!(<T: Clone = u32> where T: Default => impl_default!((Wrapper)));
+
+#[derive(Debug, PartialEq)]
+struct Array<const N: usize>([u8; N]);
+generics_split!(<const N: usize> where [u8; N]: Default => impl_default!((Array)));
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_generics_split() {
+    let parts = generics_split!(<'a, 'b: 'a, T: Clone + 'a = u8, const N: usize = 3> where T: Default => split_str!());
+    assert_eq!(strip(parts), strip(["<'a, 'b: 'a, T: Clone + 'a, const N: usize>", "<'a, 'b, T, N>", "where T: Default,"]));
+}
+
+#[test]
+fn test_generics_split_complex_bounds() {
+    let parts = generics_split!(<F: Fn(u8) -> Vec<u8> = fn(u8) -> Vec<u8>, I: Iterator<Item = u8>,> => split_str!());
+    assert_eq!(strip(parts), strip(["<F: Fn(u8) -> Vec<u8>, I: Iterator<Item = u8>>", "<F, I>", ""]));
+    let parts = generics_split!(where Vec<u8>: Clone, u8: Copy => split_str!());
+    assert_eq!(strip(parts), strip(["", "", "where Vec<u8>: Clone, u8: Copy,"]));
+    let parts = generics_split!(=> split_str!());
+    assert_eq!(strip(parts), strip(["", "", ""]));
+}
+
+#[test]
+fn test_generics_split_impl() {
+    assert_eq!(Wrapper::<u32>::default(), Wrapper(0));
+    assert_eq!(Array::<2>::default(), Array([0, 0]));
+}
+
+#[test]
+fn test_generics_split_macro_rules() {
+    trait Name {
+        fn name() -> &'static str;
+    }
+    macro_rules! impl_name {
+        (@impl ($name:ident) impl_generics = [$($impl:tt)*], type_generics = [$($ty:tt)*], where_clause = [$($where:tt)*],) => {
+            impl $($impl)* Name for $name $($ty)* $($where)* {
+                fn name() -> &'static str { stringify!($name) }
+            }
+        };
+        (struct $name:ident [$($params:tt)*] ($($fields:tt)*);) => {
+            #[allow(dead_code)]
+            struct $name<$($params)*>($($fields)*);
+            generics_split!(<$($params)*> => impl_name!(@impl ($name)));
+        };
+    }
+
+    impl_name!(struct Ref['a, T: ?Sized = str](&'a T););
+    assert_eq!(<Ref<'static, str>>::name(), "Ref");
+}
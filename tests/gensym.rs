@@ -0,0 +1,61 @@
+//  GENSYM.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `gensym!()`-macro.
+//
+
+use macro_toolkit::gensym;
+
+
+/***** TESTS *****/
+#[test]
+fn test_gensym_unique() {
+    let names: [&str; 4] = [gensym!(=> stringify!), gensym!(=> stringify!), gensym!(foo => stringify!), gensym!(foo => stringify!)];
+    for (i, lhs) in names.iter().enumerate() {
+        for rhs in &names[i + 1..] {
+            assert_ne!(lhs, rhs);
+        }
+    }
+}
+
+#[test]
+fn test_gensym_labels() {
+    macro_rules! names {
+        ($($name:ident),*) => {
+            [$(stringify!($name)),*]
+        };
+    }
+
+    let [a, b]: [&str; 2] = gensym!(first, r#second => names!);
+    assert!(a.starts_with("__mtk_first_"));
+    assert!(b.starts_with("__mtk_second_"));
+    assert!(gensym!(=> names!)[0].starts_with("__mtk_gensym_"));
+}
+
+#[test]
+fn test_gensym_bare() {
+    let gensym!() = 42;
+    let gensym!(unused) = 42;
+}
+
+#[test]
+fn test_gensym_macro() {
+    macro_rules! constant {
+        ($name:ident = $value:literal) => {
+            gensym!(value, ret => constant!(@impl $name $value;));
+        };
+        (@impl $name:ident $value:literal; $helper:ident, $ret:ident) => {
+            #[allow(non_upper_case_globals)]
+            const $helper: u32 = $value;
+            fn $name() -> u32 {
+                let $ret = $helper;
+                $ret
+            }
+        };
+    }
+    constant!(one = 1);
+    constant!(two = 2);
+
+    assert_eq!([one(), two()], [1, 2]);
+}
@@ -0,0 +1,58 @@
+//  GLOB FILES.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `glob_files!()`-macro.
+//
+
+use macro_toolkit::glob_files;
+
+
+/***** HELPERS *****/
+// Defines a function returning the contents of each file
+glob_files!("glob_files/*.txt", |ident, path| {
+    fn [<ident _contents>]() -> &'static str { include_str!(path).trim() }
+});
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+#[allow(clippy::vec_init_then_push)]
+fn test_glob_files_stems() {
+    let mut stems: Vec<&str> = Vec::new();
+    glob_files!("glob_files/*.txt", |stem| {
+        stems.push(stem);
+    });
+    assert_eq!(stems, ["2nd", "add", "answer-to-everything"]);
+}
+
+#[test]
+fn test_glob_files_idents() {
+    assert_eq!(_2nd_contents(), "10 - 4");
+    assert_eq!(add_contents(), "1 + 2");
+    assert_eq!(answer_to_everything_contents(), "6 * 7");
+}
+
+#[test]
+fn test_glob_files_include() {
+    let mut sum: i32 = 0;
+    glob_files!("glob_files/*.txt", |path| {
+        sum += include!(path);
+    });
+    assert_eq!(sum, 3 + 6 + 42);
+}
+
+#[test]
+#[allow(clippy::vec_init_then_push)]
+fn test_glob_files_paths() {
+    let mut paths: Vec<&str> = Vec::new();
+    glob_files!("glob_files/*", |path| {
+        paths.push(path);
+    });
+    assert_eq!(paths.len(), 4);
+    assert!(paths.iter().all(|path| std::path::Path::new(path).is_absolute()));
+    assert!(paths[3].ends_with("notes.md"));
+}
@@ -0,0 +1,60 @@
+//  IDENT CASE CHECK.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `ident_case_check!()`-macro.
+//
+
+use macro_toolkit::ident_case_check;
+
+
+/***** HELPERS *****/
+/// Defines a constant with the given name, checking that it's a proper constant name.
+macro_rules! constant {
+    ($name:ident = $value:expr) => {
+        ident_case_check!(SCREAMING_SNAKE_CASE: $name);
+        const $name: u32 = $value;
+    };
+}
+
+/// Defines a unit-only enum, checking that its names follow the guidelines.
+macro_rules! unit_enum {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        ident_case_check!(UpperCamelCase: $name, $($variant),*);
+        #[derive(Debug, PartialEq)]
+        enum $name {
+            $($variant),*
+        }
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_ident_case_check_snake_case() {
+    ident_case_check!(snake_case: foo);
+    ident_case_check!(snake_case: foo_bar, foo2, foo_2_bar, _foo, foo_, __foo__, _, r#type,);
+}
+
+#[test]
+fn test_ident_case_check_upper_camel_case() {
+    ident_case_check!(UpperCamelCase: Foo);
+    ident_case_check!(UpperCamelCase: FooBar, Foo2, HTTPServer, _Foo, Foo_, X);
+}
+
+#[test]
+fn test_ident_case_check_screaming_snake_case() {
+    ident_case_check!(SCREAMING_SNAKE_CASE: FOO);
+    ident_case_check!(SCREAMING_SNAKE_CASE: FOO_BAR, FOO2, FOO_2_BAR, _FOO, X);
+}
+
+#[test]
+fn test_ident_case_check_macro() {
+    constant!(ANSWER = 42);
+    unit_enum!(Color { Red, Green, LightBlue });
+    assert_eq!(ANSWER, 42);
+    assert_eq!([Color::Red, Color::Green, Color::LightBlue].len(), 3);
+}
@@ -0,0 +1,76 @@
+//  IDENT PREDICATES.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `keyword_check!()`- and `is_valid_ident!()`-macros.
+//
+
+use macro_toolkit::{is_valid_ident, keyword_check};
+
+
+/***** HELPERS *****/
+/// Returns the categories of keywords the given identifier is in.
+macro_rules! categories {
+    ($ident:tt) => {
+        (keyword_check!(strict($ident)), keyword_check!(reserved($ident)), keyword_check!(weak($ident)), keyword_check!(any($ident)))
+    };
+}
+
+/// Returns the given name if it is a valid identifier, or a fallback otherwise.
+macro_rules! name_or {
+    ($name:literal, $fallback:literal) => {
+        is_valid_ident!(if $name { $name } else { $fallback })
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_keyword_check() {
+    assert_eq!(categories!(fn), (true, false, false, true));
+    assert_eq!(categories!(Self), (true, false, false, true));
+    assert_eq!(categories!(self), (true, false, false, true));
+    assert_eq!(categories!(dyn), (true, false, false, true));
+    assert_eq!(categories!(yield), (false, true, false, true));
+    assert_eq!(categories!(gen), (false, true, false, true));
+    assert_eq!(categories!(union), (false, false, true, true));
+    assert_eq!(categories!(macro_rules), (false, false, true, true));
+    assert_eq!(categories!(foo), (false, false, false, false));
+    assert_eq!(categories!(r#type), (false, false, false, false));
+}
+
+#[test]
+fn test_keyword_check_if() {
+    assert_eq!(keyword_check!(if strict(match) { 1 } else { 2 }), 1);
+    assert_eq!(keyword_check!(if strict(matches) { 1 } else { 2 }), 2);
+
+    keyword_check!(if reserved(foo) {
+        compile_error!("`foo` is not reserved");
+    });
+    keyword_check!(if reserved(abstract) {
+        const ABSTRACT: bool = true;
+    });
+    const { assert!(ABSTRACT) };
+}
+
+#[test]
+fn test_is_valid_ident() {
+    assert_eq!([is_valid_ident!("foo"), is_valid_ident!("Foo2"), is_valid_ident!("_foo"), is_valid_ident!("föö")], [true; 4]);
+    assert_eq!([is_valid_ident!("union"), is_valid_ident!("raw"), is_valid_ident!(r"foo_bar")], [true; 3]);
+    assert_eq!([is_valid_ident!(""), is_valid_ident!("_"), is_valid_ident!("2nd"), is_valid_ident!("foo-bar"), is_valid_ident!("foo bar")], [false; 5]);
+    assert_eq!([is_valid_ident!("type"), is_valid_ident!("Self"), is_valid_ident!("yield"), is_valid_ident!("r#type")], [false; 4]);
+}
+
+#[test]
+fn test_is_valid_ident_if() {
+    assert_eq!(name_or!("foo", "fallback"), "foo");
+    assert_eq!(name_or!("foo-bar", "fallback"), "fallback");
+    assert_eq!(name_or!("async", "fallback"), "fallback");
+
+    is_valid_ident!(if "1" {
+        compile_error!("`1` is not a valid identifier");
+    });
+}
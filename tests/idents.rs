@@ -6,7 +6,10 @@
 //!   additional capabilities.
 //
 
-use macro_toolkit::idents;
+// NOTE: `test_idents_transparent()` deliberately constructs its unit struct through `Default`
+#![allow(clippy::default_constructed_unit_structs)]
+
+use macro_toolkit::{idents, with_idents};
 
 
 /***** TESTS *****/
@@ -56,3 +59,99 @@ fn test_idents_replace_named() {
 
     assert_eq!(format!("{:?}", build_foo!("Test", 42usize)), "Foo(\"Test\", 42)");
 }
+
+#[test]
+fn test_idents_paste() {
+    macro_rules! getter {
+        ($name:ident: $ty:ty) => {
+            idents! {
+                fn [<get_ $name>](&self) -> &$ty { &self.$name }
+                fn [<r#set_ $name _ "value" 2>](&mut self, value: $ty) { self.$name = value; }
+            }
+        };
+    }
+
+    struct Foo {
+        bar: u32,
+    }
+    impl Foo {
+        getter!(bar: u32);
+    }
+
+    let mut foo = Foo { bar: 42 };
+    foo.set_bar_value2(84);
+    assert_eq!(*foo.get_bar(), 84);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_idents_paste_types() {
+    macro_rules! default_fns {
+        ($($ty:ty),*) => {
+            idents! {
+                $(fn [<default_ ($ty)>]() -> $ty { Default::default() })*
+            }
+        };
+    }
+
+    default_fns!(u8, Option<&'static str>, [u16; 2], (), std::string::String);
+    assert_eq!(default_u8(), 0);
+    assert_eq!(default_Option_ref_str(), None);
+    assert_eq!(default_array_u16_2(), [0, 0]);
+    assert_eq!(default_unit(), ());
+    assert_eq!(default_std_string_String(), "");
+}
+
+#[test]
+fn test_idents_nested() {
+    // Brackets that aren't ours should be left alone, but still recursed into
+    trait Foo {
+        const BAR: usize;
+    }
+    impl Foo for () {
+        const BAR: usize = 3;
+    }
+
+    idents! {
+        fn foo() -> [usize; <() as Foo>::BAR] {
+            let [<value_ 1>]: usize = 1;
+            [[<value_ 1>]; <() as Foo>::BAR]
+        }
+    }
+    assert_eq!(foo(), [1, 1, 1]);
+}
+
+#[test]
+fn test_with_idents() {
+    macro_rules! getter {
+        ($name:ident: $ty:ty) => {
+            #[with_idents(GETTER = [<get_ $name>], TY = $ty)]
+            fn GETTER(&self) -> &TY {
+                &self.$name
+            }
+        };
+    }
+
+    struct Foo {
+        bar: u32,
+    }
+    impl Foo {
+        getter!(bar: u32);
+    }
+
+    assert_eq!(*Foo { bar: 42 }.get_bar(), 42);
+}
+
+#[test]
+fn test_with_idents_nested_macro() {
+    #[with_idents]
+    fn foo() -> u32 {
+        macro_rules! id {
+            ($($t:tt)*) => { $($t)* };
+        }
+        id!(let [<value_ 1>]: u32 = 42;);
+        id!([<value_ 1>])
+    }
+
+    assert_eq!(foo(), 42);
+}
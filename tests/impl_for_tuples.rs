@@ -0,0 +1,93 @@
+//  IMPL FOR TUPLES.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `impl_for_tuples!()`-macro.
+//
+
+use macro_toolkit::impl_for_tuples;
+
+
+/***** HELPERS *****/
+trait Names {
+    fn names() -> Vec<&'static str>;
+}
+impl Names for u8 {
+    fn names() -> Vec<&'static str> { vec!["u8"] }
+}
+impl Names for bool {
+    fn names() -> Vec<&'static str> { vec!["bool"] }
+}
+
+impl_for_tuples!(0..=16, impl<#(T@: Names),*> Names for (#(T@,)*) {
+    fn names() -> Vec<&'static str> {
+        #[allow(unused_mut)]
+        let mut names: Vec<&'static str> = Vec::new();
+        #(names.extend(T@::names());)*
+        names
+    }
+});
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_impl_for_tuples_range() {
+    assert_eq!(<()>::names(), Vec::<&str>::new());
+    assert_eq!(<(u8,)>::names(), vec!["u8"]);
+    assert_eq!(<(u8, bool)>::names(), vec!["u8", "bool"]);
+    assert_eq!(<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, bool)>::names().len(), 16);
+}
+
+#[test]
+fn test_impl_for_tuples_index() {
+    trait Sum {
+        fn sum(&self) -> u32;
+    }
+    impl_for_tuples!(1..4, impl Sum for (#(u32,)*) {
+        fn sum(&self) -> u32 {
+            #(self.@)+*
+        }
+    });
+
+    assert_eq!((1,).sum(), 1);
+    assert_eq!((1, 2).sum(), 3);
+    assert_eq!((1, 2, 3).sum(), 6);
+}
+
+#[test]
+fn test_impl_for_tuples_patterns() {
+    // Outside of repetitions, `@` is left alone
+    impl_for_tuples!(2, fn first_small(#(a@: u32),*) -> Option<u32> {
+        match (a0, a1) {
+            (n @ 0..10, _) => Some(n),
+            _ => None,
+        }
+    });
+
+    assert_eq!(first_small(5, 20), Some(5));
+    assert_eq!(first_small(50, 20), None);
+}
+
+#[test]
+fn test_impl_for_tuples_macro_rules() {
+    macro_rules! firsts {
+        ($($arity:literal),*) => {
+            $(impl_for_tuples!($arity, impl<#(T@: Copy),*> First for (#(T@,)*) {
+                type Output = T0;
+                fn first(&self) -> T0 { self.0 }
+            });)*
+        };
+    }
+    trait First {
+        type Output;
+        fn first(&self) -> Self::Output;
+    }
+
+    firsts!(1, 2, 3);
+    assert_eq!((1,).first(), 1);
+    assert_eq!((1, 'a').first(), 1);
+    assert_eq!(("a", 2, 3).first(), "a");
+}
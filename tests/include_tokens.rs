@@ -0,0 +1,52 @@
+//  INCLUDE TOKENS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `include_tokens!()`-macro.
+//
+
+use macro_toolkit::include_tokens;
+
+
+/***** HELPERS *****/
+macro_rules! numbers {
+    ($(($num:literal, $name:literal)),* $(,)?) => {
+        const NUMBERS: &[(u32, &str)] = &[$(($num, $name)),*];
+    };
+}
+include_tokens!("include_tokens/numbers.in" => numbers!{});
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+impl Point {
+    include_tokens!(idents, "include_tokens/getters.in");
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_include_tokens_callback() {
+    assert_eq!(NUMBERS, &[(1, "one"), (2, "two"), (3, "three")]);
+}
+
+#[test]
+fn test_include_tokens_idents() {
+    let point = Point { x: 1, y: 2 };
+    assert_eq!((point.get_x(), point.get_y()), (1, 2));
+}
+
+#[test]
+fn test_include_tokens_expr() {
+    macro_rules! count {
+        ($($tt:tt)*) => { [$(stringify!($tt)),*].len() };
+    }
+
+    // Every line of the file is a group followed by a comma
+    assert_eq!(include_tokens!("include_tokens/numbers.in" => count!()), 6);
+    assert_eq!(include_tokens!("include_tokens/numbers.in" => count!(a b)), 8);
+}
@@ -0,0 +1,94 @@
+//  ITEM NAME.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `item_name!()`-macro.
+//
+
+use macro_toolkit::item_name;
+
+
+/***** HELPERS *****/
+/// Defines a function returning the name of the given item, next to the item itself.
+macro_rules! with_name_fn {
+    // NOTE: This rule must come first, as `$item:item` cannot backtrack
+    (@callback $name:ident, $item:item) => {
+        $item
+
+        pub fn name_of() -> &'static str { stringify!($name) }
+    };
+    ($item:item) => {
+        ::macro_toolkit::item_name!($item => with_name_fn!(@callback));
+    };
+}
+
+mod structs {
+    with_name_fn! {
+        #[derive(Debug)]
+        pub(crate) struct Foo<T>(pub T);
+    }
+}
+
+mod functions {
+    with_name_fn! {
+        pub const unsafe extern "C" fn bar() -> u32 { 42 }
+    }
+}
+
+mod statics {
+    with_name_fn! {
+        pub static BAZ: u32 = 0;
+    }
+}
+
+mod macros {
+    with_name_fn! {
+        #[allow(unused_macros)]
+        macro_rules! quux {
+            () => {};
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_item_name_kinds() {
+    assert_eq!(structs::name_of(), "Foo");
+    assert_eq!(functions::name_of(), "bar");
+    assert_eq!(statics::name_of(), "BAZ");
+    assert_eq!(macros::name_of(), "quux");
+}
+
+#[test]
+fn test_item_name_item_kept() {
+    assert_eq!(format!("{:?}", structs::Foo(42)), "Foo(42)");
+    assert_eq!(unsafe { functions::bar() }, 42);
+    assert_eq!(statics::BAZ, 0);
+}
+
+#[test]
+fn test_item_name_no_callback() {
+    let answer: u32 = 42;
+    assert_eq!(item_name!(const answer: u32 = 0;), 42);
+}
+
+#[test]
+fn test_item_name_macro_rules() {
+    macro_rules! name_str {
+        ($item:item) => {
+            item_name!($item => stringify!())
+        };
+    }
+
+    assert_eq!(name_str!(pub trait Trait {}), "Trait, pub trait Trait {}");
+}
+
+#[test]
+fn test_item_name_extern_crate() {
+    assert_eq!(item_name!(extern crate std; => stringify!()), "std, extern crate std;");
+    assert_eq!(item_name!(extern crate std as foo; => stringify!()), "foo, extern crate std as foo;");
+}
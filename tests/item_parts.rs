@@ -0,0 +1,120 @@
+//  ITEM PARTS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `item_parts!()`-macro.
+//
+
+use macro_toolkit::item_parts;
+
+
+/***** HELPERS *****/
+/// Removes all whitespace from the given strings, as `stringify!()` does not guarantee it.
+fn strip<const N: usize>(parts: [&str; N]) -> [String; N] { parts.map(|part| part.split_whitespace().collect()) }
+
+/// Stringifies every part of an item.
+macro_rules! parts_str {
+    (
+        attrs = [$($attrs:tt)*], vis = [$($vis:tt)*], qualifiers = [$($quals:tt)*], keyword = [$($kw:tt)*], name = [$($name:tt)*],
+        generics = [$($generics:tt)*], signature = [$($sig:tt)*], where = [$($where:tt)*], body = [$($body:tt)*],
+    ) => {
+        [
+            stringify!($($attrs)*),
+            stringify!($($vis)*),
+            stringify!($($quals)*),
+            stringify!($($kw)*),
+            stringify!($($name)*),
+            stringify!($($generics)*),
+            stringify!($($sig)*),
+            stringify!($($where)*),
+            stringify!($($body)*),
+        ]
+    };
+}
+
+/// Reconstructs an item from its parts.
+macro_rules! rebuild {
+    (
+        attrs = [$($attrs:tt)*], vis = [$($vis:tt)*], qualifiers = [$($quals:tt)*], keyword = [$($kw:tt)*], name = [$($name:tt)*],
+        generics = [$($generics:tt)*], signature = [$($sig:tt)*], where = [$($where:tt)*], body = [$($body:tt)*],
+    ) => {
+        $($attrs)* $($vis)* $($quals)* $($kw)* $($name)* <$($generics)*> $($sig)* where $($where)* $($body)*
+    };
+}
+
+item_parts! {
+    #[derive(Debug, PartialEq)]
+    pub struct Wrapper<T>(pub T) where T: Clone;
+    => rebuild!()
+}
+
+item_parts! {
+    const fn add<const N: u32>(x: u32) -> u32 { x + N }
+    => rebuild!()
+}
+
+trait Named {
+    fn name(&self) -> &'static str;
+}
+item_parts! {
+    impl<T: Clone> Named for Wrapper<T> {
+        fn name(&self) -> &'static str { "wrapper" }
+    }
+    => rebuild!()
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_item_parts_fn() {
+    let parts = item_parts!(pub(crate) const unsafe fn foo<'a, F: Fn() -> u8>(f: &'a F) -> u8 where F: Copy { f() } => parts_str!());
+    assert_eq!(strip(parts), strip(["", "pub(crate)", "const unsafe", "fn", "foo", "'a, F: Fn() -> u8", "(f: &'a F) -> u8", "F: Copy", "{ f() }"]));
+}
+
+#[test]
+fn test_item_parts_kinds() {
+    let parts = item_parts!(#[allow(dead_code)] static mut FOO: u32 = 42; => parts_str!());
+    assert_eq!(strip(parts), strip(["#[allow(dead_code)]", "", "", "static mut", "FOO", "", ": u32 = 42", "", ";"]));
+    let parts = item_parts!(impl<T> Foo for Bar<T> {} => parts_str!());
+    assert_eq!(strip(parts), strip(["", "", "", "impl", "", "T", "Foo for Bar<T>", "", "{}"]));
+    let parts = item_parts!(macro_rules! foo { () => {}; } => parts_str!());
+    assert_eq!(strip(parts), strip(["", "", "", "macro_rules!", "foo", "", "", "", "{ () => {}; }"]));
+    let parts = item_parts!(unsafe extern "C" {} => parts_str!());
+    assert_eq!(strip(parts), strip(["", "", "unsafe", "extern \"C\"", "", "", "", "", "{}"]));
+}
+
+#[test]
+fn test_item_parts_type_alias() {
+    let parts = item_parts!(type A<T> where T: Clone = Vec<T>; => parts_str!());
+    assert_eq!(strip(parts), strip(["", "", "", "type", "A", "T", "= Vec<T>", "T: Clone", ";"]));
+    let parts = item_parts!(type A<T> = Vec<T> where T: Clone; => parts_str!());
+    assert_eq!(strip(parts), strip(["", "", "", "type", "A", "T", "= Vec<T>", "T: Clone", ";"]));
+    let parts = item_parts!(type A<I> where I: Iterator<Item = u8>, I::Item: Fn() -> u8 = I; => parts_str!());
+    assert_eq!(strip(parts), strip(["", "", "", "type", "A", "I", "= I", "I: Iterator<Item = u8>, I::Item: Fn() -> u8", ";"]));
+}
+
+#[test]
+fn test_item_parts_rebuild() {
+    assert_eq!(Wrapper(42), Wrapper(42));
+    assert_eq!(add::<2>(40), 42);
+    assert_eq!(Wrapper(42).name(), "wrapper");
+}
+
+#[test]
+fn test_item_parts_macro_rules() {
+    macro_rules! name_and_generics {
+        (@parts attrs = [$($attrs:tt)*], vis = [$($vis:tt)*], qualifiers = [$($quals:tt)*], keyword = [$($kw:tt)*], name = [$name:ident], generics = [$($generics:tt)*], $($rest:tt)*) => {
+            (stringify!($name), stringify!($($generics)*))
+        };
+        ($item:item) => {
+            item_parts!($item => name_and_generics!(@parts))
+        };
+    }
+
+    let (name, generics) = name_and_generics!(enum Either<L, R> { Left(L), Right(R) });
+    assert_eq!(name, "Either");
+    assert_eq!(strip([generics]), strip(["L, R"]));
+}
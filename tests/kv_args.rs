@@ -0,0 +1,67 @@
+//  KV ARGS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `kv_args!()`-macro.
+//
+
+use macro_toolkit::kv_args;
+
+
+/***** HELPERS *****/
+/// Collects the values given to it as strings, without whitespace.
+macro_rules! values {
+    ($($value:expr),*) => {
+        [$(stringify!($value).chars().filter(|c| !c.is_whitespace()).collect::<String>()),*]
+    };
+}
+
+/// Defines a constant with the values given to it.
+macro_rules! config {
+    ($name:expr, $port:expr, $secure:expr) => {
+        const CONFIG: (&str, u16, bool) = ($name, $port, $secure);
+    };
+}
+
+kv_args!(secure, name = "localhost" { name, port = 8080, flag secure } => config!);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_kv_args_order() {
+    assert_eq!(kv_args!(a = 1, b = 2, c = 3 { a, b, c } => values!), ["1", "2", "3"]);
+    assert_eq!(kv_args!(c = 3, a = 1, b = 2, { a, b, c } => values!), ["1", "2", "3"]);
+    assert_eq!(kv_args!(b = vec![1, 2], a = (1, 2) { a, b } => values!), ["(1,2)", "vec![1,2]"]);
+}
+
+#[test]
+fn test_kv_args_defaults() {
+    assert_eq!(kv_args!({ a = 1, b = 2 + 2 } => values!), ["1", "2+2"]);
+    assert_eq!(kv_args!(b = 3 { a = 1, b = 2 } => values!), ["1", "3"]);
+    assert_eq!(kv_args!(x { flag x, flag y } => values!), ["true", "false"]);
+    assert_eq!(kv_args!(a = 0 { a, flag b, c = 5 } => values!), ["0", "false", "5"]);
+}
+
+#[test]
+fn test_kv_args_items() {
+    assert_eq!(CONFIG, ("localhost", 8080, true));
+}
+
+#[test]
+fn test_kv_args_macro_rules() {
+    macro_rules! greet {
+        (@impl $name:expr, $greeting:expr, $shout:expr) => {{
+            let res: String = format!("{}, {}!", $greeting, $name);
+            if $shout { res.to_uppercase() } else { res }
+        }};
+        ($($args:tt)*) => {
+            kv_args!($($args)* { name, greeting = "Hello", flag shout } => greet!(@impl))
+        };
+    }
+
+    assert_eq!(greet!(name = "world"), "Hello, world!");
+    assert_eq!(greet!(shout, name = "world", greeting = "Hi"), "HI, WORLD!");
+}
@@ -0,0 +1,69 @@
+//  LIFETIMES OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `lifetimes_of!()`-macro.
+//
+
+use macro_toolkit::lifetimes_of;
+
+
+/***** HELPERS *****/
+/// Removes all whitespace from the given string, as `stringify!()` does not guarantee it.
+fn strip(value: &str) -> String { value.split_whitespace().collect() }
+
+/// Stringifies the extracted lifetimes.
+macro_rules! lifetimes_str {
+    ($($lifetimes:tt)*) => {
+        stringify!($($lifetimes)*)
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_lifetimes_of() {
+    assert_eq!(strip(lifetimes_of!(u8 => lifetimes_str!())), "");
+    assert_eq!(strip(lifetimes_of!(&'a str => lifetimes_str!())), "'a");
+    assert_eq!(strip(lifetimes_of!(HashMap<&'k str, Vec<&'v mut &'k [u8]>> => lifetimes_str!())), "'k,'v");
+    assert_eq!(strip(lifetimes_of!(&'static dyn Fn(&'_ u8) -> std::borrow::Cow<'a, str> => lifetimes_str!())), "'a");
+    assert_eq!(strip(lifetimes_of!(for<'x> fn(&'x u8, &'y u8) => lifetimes_str!())), "'y");
+}
+
+#[test]
+fn test_lifetimes_of_item() {
+    let lifetimes = lifetimes_of! {
+        fn find<'h, 'n: 'h>(haystack: &'h str, needle: &'n str) -> Option<&'h str> {
+            'outer: loop {
+                'inner: {
+                    break 'inner;
+                }
+                break 'outer;
+            }
+            haystack.find(needle).map(|i| &haystack[i..])
+        }
+        => lifetimes_str!()
+    };
+    assert_eq!(strip(lifetimes), "'h,'n");
+}
+
+#[test]
+fn test_lifetimes_of_macro_rules() {
+    macro_rules! borrowed {
+        (@struct ($name:ident, $ty:ty) $($lifetimes:lifetime),*) => {
+            struct $name<$($lifetimes),*> {
+                value: $ty,
+            }
+        };
+        ($name:ident: $ty:ty) => {
+            lifetimes_of!($ty => borrowed!(@struct ($name, $ty)));
+        };
+    }
+
+    borrowed!(Pair: (&'a str, &'b str, &'static str));
+    let pair = Pair { value: ("a", "b", "c") };
+    assert_eq!(pair.value, ("a", "b", "c"));
+}
@@ -0,0 +1,37 @@
+//  LIT LEN.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `lit_len!()`-macro.
+//
+
+use macro_toolkit::lit_len;
+
+
+/***** TESTS *****/
+#[test]
+fn test_lit_len_simple() {
+    assert_eq!(lit_len!("42"), 2);
+    assert_eq!(lit_len!(b"42"), 2);
+    assert_eq!(lit_len!(c"42"), 2);
+    assert_eq!(lit_len!(""), 0);
+}
+
+#[test]
+fn test_lit_len_units() {
+    assert_eq!(lit_len!(bytes "ünïcödé"), 11);
+    assert_eq!(lit_len!(chars "ünïcödé"), 7);
+    assert_eq!(lit_len!(chars c"ünïcödé"), 7);
+}
+
+#[test]
+fn test_lit_len_macro() {
+    macro_rules! buffer {
+        ($lit:literal) => {{
+            let buf: [u8; lit_len!($lit)] = *$lit;
+            buf
+        }};
+    }
+
+    assert_eq!(buffer!(b"Hello"), *b"Hello");
+}
@@ -0,0 +1,84 @@
+//  MACRO V2.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `macro_v2!{}`-macro.
+//
+
+use macro_toolkit::macro_v2;
+
+
+/***** HELPERS *****/
+macro_v2! {
+    /// Reverses a list of expressions into a tuple.
+    macro_rules! reverse {
+        #rev([$($done:expr),*]) => { ($($done,)*) };
+        #rev([$($done:expr),*] $head:expr $(, $tail:expr)*) => { #rev([$head $(, $done)*] $($tail),*) };
+        ($($e:expr),* $(,)?) => { #rev([] $($e),*) };
+    }
+
+    macro_rules! count {
+        ($($t:tt)*) => { #count($t) };
+    }
+
+    macro_rules! indices {
+        ($($t:tt)*) => { [$((#index, stringify!($t))),*] };
+    }
+
+    macro_rules! nested {
+        ($([$($t:tt)*])*) => { [$([$((#index, stringify!($t))),*]),*] };
+    }
+
+    macro_rules! classify {
+        (0) => { "zero" };
+        ($lit:literal) => { "literal" };
+        else => { "other" };
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_macro_v2_named_rules() {
+    assert_eq!(reverse!(), ());
+    assert_eq!(reverse!(1), (1,));
+    assert_eq!(reverse!(1, "two", 3.0), (3.0, "two", 1));
+}
+
+#[test]
+fn test_macro_v2_count_index() {
+    assert_eq!(count!(), 0);
+    assert_eq!(count!(a b (c d) e), 4);
+    assert_eq!(indices!(a b c), [(0, "a"), (1, "b"), (2, "c")]);
+    let empty: [(usize, &str); 0] = indices!();
+    assert_eq!(empty, []);
+    assert_eq!(nested!([a b] [c d]), [[(0, "a"), (1, "b")], [(0, "c"), (1, "d")]]);
+}
+
+#[test]
+fn test_macro_v2_else() {
+    assert_eq!(classify!(0), "zero");
+    assert_eq!(classify!(42), "literal");
+    assert_eq!(classify!(foo bar), "other");
+}
+
+#[test]
+fn test_macro_v2_macro_rules() {
+    macro_rules! constant {
+        ($name:ident = $value:expr) => {
+            macro_v2! {
+                macro_rules! $name {
+                    #get() => { $value };
+                    () => { #get() };
+                    else => { #error("`", stringify!($name), "!()` takes no arguments") };
+                }
+            }
+        };
+    }
+    constant!(answer = 42);
+
+    assert_eq!(answer!(), 42);
+}
@@ -0,0 +1,145 @@
+//  MATCH EXPR.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `match_expr!()`-macro.
+//
+
+use macro_toolkit::match_expr;
+
+
+/***** HELPERS *****/
+/// Returns the kind of an expression as a string.
+macro_rules! kind {
+    ($($expr:tt)*) => {
+        match_expr!($($expr)* {
+            lit => "lit",
+            path => "path",
+            macro => "macro",
+            struct => "struct",
+            unit => "unit",
+            tuple => "tuple",
+            array => "array",
+            call => "call",
+            method => "method",
+            field => "field",
+            index => "index",
+            try => "try",
+            await => "await",
+            ref_shared => "ref_shared",
+            ref_mut => "ref_mut",
+            unary => "unary",
+            binary => "binary",
+            assign => "assign",
+            range => "range",
+            cast => "cast",
+            block => "block",
+            if => "if",
+            match => "match",
+            loop => "loop",
+            closure => "closure",
+            jump => "jump",
+        })
+    };
+}
+
+/// Removes all whitespace from a string.
+fn strip(s: &str) -> String { s.chars().filter(|c| !c.is_whitespace()).collect() }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_match_expr_kinds() {
+    assert_eq!(kind!(42), "lit");
+    assert_eq!(kind!(true), "lit");
+    assert_eq!(kind!(std::vec::Vec::<u8>::new), "path");
+    assert_eq!(kind!(<u8 as Default>::default), "path");
+    assert_eq!(kind!(vec![1, 2]), "macro");
+    assert_eq!(kind!(Foo { a: 1 }), "struct");
+    assert_eq!(kind!(()), "unit");
+    assert_eq!(kind!((1,)), "tuple");
+    assert_eq!(kind!((1)), "lit");
+    assert_eq!(kind!([0; 4]), "array");
+    assert_eq!(kind!(f(1, 2)), "call");
+    assert_eq!(kind!(a.b::<u8>(1)), "method");
+    assert_eq!(kind!(a.b.0), "field");
+    assert_eq!(kind!(a[0]), "index");
+    assert_eq!(kind!(a()?), "try");
+    assert_eq!(kind!(a.await), "await");
+    assert_eq!(kind!(&&a), "ref_shared");
+    assert_eq!(kind!(&mut a.b), "ref_mut");
+    assert_eq!(kind!(-a.b()), "unary");
+    assert_eq!(kind!(!a), "unary");
+    assert_eq!(kind!(&a + -b * c), "binary");
+    assert_eq!(kind!(a.b() < c), "binary");
+    assert_eq!(kind!(a = b == c), "assign");
+    assert_eq!(kind!(a <<= 1), "assign");
+    assert_eq!(kind!(..=a + 1), "range");
+    assert_eq!(kind!(a as u8), "cast");
+    assert_eq!(kind!(unsafe { a }), "block");
+    assert_eq!(kind!({ a }), "block");
+    assert_eq!(kind!(if a { b } else if c { d } else { e }), "if");
+    assert_eq!(kind!(match a { _ => b }), "match");
+    assert_eq!(kind!('outer: for a in b {}), "loop");
+    assert_eq!(kind!(move |a| a + 1), "closure");
+    assert_eq!(kind!(|| {}), "closure");
+    assert_eq!(kind!(return a + 1), "jump");
+}
+
+#[test]
+fn test_match_expr_bindings() {
+    assert_eq!(strip(match_expr!(a + b * c - d { binary(lhs, op, rhs) => concat!(stringify!(lhs), "|", stringify!(op), "|", stringify!(rhs)) })), "a+b*c|-|d");
+    assert_eq!(strip(match_expr!(a = b = c { assign(lhs, _, rhs) => concat!(stringify!(lhs), "|", stringify!(rhs)) })), "a|b=c");
+    assert_eq!(strip(match_expr!(x.iter().map::<u8, _>(f, g) { method(receiver, method, args) => concat!(stringify!(receiver), "|", stringify!(method), "|", stringify!(args)) })), "x.iter()|map::<u8,_>|f,g");
+    assert_eq!(strip(match_expr!(foo::bar(1, (2, 3)) { call(func, args) => concat!(stringify!(func), "|", stringify!(args)) })), "foo::bar|1,(2,3)");
+    assert_eq!(strip(match_expr!(0..n { range(start, end) => concat!(stringify!(start), "|", stringify!(end)) })), "0|n");
+    assert_eq!(strip(match_expr!(a as *const u8 { cast(_, ty) => stringify!(ty) })), "*constu8");
+    assert_eq!(strip(match_expr!(|a, b: u8| -> u8 { a } { closure(params, body) => concat!(stringify!(params), "|", stringify!(body)) })), "a,b:u8|->u8{a}");
+    assert_eq!(strip(match_expr!(match x { _ => 1 } { match(scrutinee) => stringify!(scrutinee) })), "x");
+    assert_eq!(strip(match_expr!(a.b.0 { field(base, member) => concat!(stringify!(base), "|", stringify!(member)) })), "a.b|0");
+    assert_eq!(strip(match_expr!(a.0.1 { field(base, member) => concat!(stringify!(base), "|", stringify!(member)) })), "a.0|1");
+}
+
+#[test]
+fn test_match_expr_order() {
+    assert_eq!(match_expr!(&mut a { ref => 1, ref_mut => 2 }), 1);
+    assert_eq!(match_expr!(&mut a { ref_shared => 1, ref_mut => 2 }), 2);
+    assert_eq!(match_expr!(() { tuple => 1, unit => 2 }), 1);
+    assert_eq!(match_expr!(a.b { method => 1, _ => 2 }), 2);
+}
+
+#[test]
+fn test_match_expr_macro_rules() {
+    macro_rules! eval {
+        ($expr:expr) => {
+            match_expr!($expr {
+                binary(lhs, op, rhs) => eval!(lhs) op eval!(rhs),
+                lit => $expr,
+                _ => 0,
+            })
+        };
+    }
+
+    let x = 5;
+    let _ = x;
+    assert_eq!(eval!(1 + 2 * 3), 7);
+    assert_eq!(eval!(x), 0);
+    assert_eq!(eval!(x + 2), 2);
+
+    // Nested tuple fields are taken apart one at a time
+    macro_rules! depth {
+        ($expr:expr) => {
+            match_expr!($expr {
+                field(base, _) => 1 + depth!(base),
+                _ => 0,
+            })
+        };
+    }
+    assert_eq!(depth!(a), 0);
+    assert_eq!(depth!(a.0), 1);
+    assert_eq!(depth!(a.0.1), 2);
+    assert_eq!(depth!(a.b.0.1), 3);
+}
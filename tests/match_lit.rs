@@ -50,3 +50,87 @@ fn test_match_lit_nested() {
     assert_eq!(type_lit!(42), 42);
     assert_eq!(type_lit!("42"), "string");
 }
+
+#[test]
+fn test_match_lit_value() {
+    assert_eq!(match_lit!(42 { 0 => "zero", 42 => "answer", _ => "other" }), "answer");
+    assert_eq!(match_lit!(0x2Au8 { 42u16 => "u16", 42 => "answer", _ => "other" }), "answer");
+    assert_eq!(match_lit!(1.50 { 1.5 => "one and a half", _ => "other" }), "one and a half");
+    assert_eq!(match_lit!("\x41" { "A" => "A", string => "string" }), "A");
+    assert_eq!(match_lit!('a' { "a" => "string", 'a' => "char", _ => "other" }), "char");
+    assert_eq!(match_lit!(b"a" { b"a" => "bytes", _ => "other" }), "bytes");
+    assert_eq!(match_lit!(false { true => "true", false => "false" }), "false");
+
+    // Values and kinds can be mixed, where the first match still wins
+    macro_rules! describe {
+        ($lit:literal) => {
+            match_lit!($lit {
+                0 => "zero",
+                int => "int",
+                "" => "empty",
+                _ => "other",
+            })
+        };
+    }
+    assert_eq!(describe!(0), "zero");
+    assert_eq!(describe!(1), "int");
+    assert_eq!(describe!(""), "empty");
+    assert_eq!(describe!(0.0), "other");
+}
+
+#[test]
+fn test_match_lit_or() {
+    assert_eq!(match_lit!(42 { string | bytes => "text", int | float => "number" }), "number");
+    assert_eq!(match_lit!(4.2 { string | bytes => "text", int | float => "number" }), "number");
+    assert_eq!(match_lit!(b"42" { string | bytes => "text", int | float => "number" }), "text");
+    assert_eq!(match_lit!(2 { | 1 | 2 | 3 => "small", _ => "large" }), "small");
+    assert_eq!(match_lit!(4 { | 1 | 2 | 3 => "small", _ => "large" }), "large");
+}
+
+#[test]
+fn test_match_lit_range() {
+    macro_rules! size {
+        ($lit:literal) => {
+            match_lit!($lit {
+                ..10 => "small",
+                10..=99 => "medium",
+                100u8.. => "large byte",
+                100.. => "large",
+                _ => "not an integer",
+            })
+        };
+    }
+    assert_eq!(size!(0), "small");
+    assert_eq!(size!(9), "small");
+    assert_eq!(size!(10), "medium");
+    assert_eq!(size!(0x63), "medium");
+    assert_eq!(size!(100u8), "large byte");
+    assert_eq!(size!(100i32), "large");
+    assert_eq!(size!(340282366920938463463374607431768211455u128), "large");
+    assert_eq!(size!(1.0), "not an integer");
+
+    // Negative bounds and bounds given by declarative macros
+    macro_rules! in_range {
+        ($lit:literal, $start:literal, $end:literal) => {
+            match_lit! { $lit { $start..=$end => true, _ => false } }
+        };
+    }
+    const { assert!(in_range!(0, -128, 127)) };
+    const { assert!(in_range!(127, -128, 127)) };
+    const { assert!(!in_range!(128, -128, 127)) };
+    const { assert!(!in_range!(5, -10, -1)) };
+}
+
+#[test]
+fn test_match_lit_else() {
+    macro_rules! port {
+        ($port:literal) => {
+            match_lit!($port {
+                1024..=65535 => $port as u16,
+                else => "Expected an unprivileged port number",
+            })
+        };
+    }
+    assert_eq!(port!(8080), 8080);
+    assert_eq!(port!(1024u16), 1024);
+}
@@ -0,0 +1,83 @@
+//  MATCH META.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `match_meta!()`-macro.
+//
+
+use macro_toolkit::match_meta;
+
+
+/***** HELPERS *****/
+/// Adds the stringified tokens given to it to a list.
+macro_rules! push {
+    ($list:ident $($tokens:tt)*) => {
+        $list.extend([stringify!($($tokens)*).chars().filter(|c| !c.is_whitespace()).collect::<String>()]);
+    };
+}
+
+/// Defines a constant with the value given to it.
+macro_rules! constant {
+    ($name:ident: $ty:ty, $value:expr) => {
+        const $name: $ty = $value;
+    };
+}
+
+match_meta!(name = "test", retries = 2 + 1 {
+    name = _ => constant!(NAME: &str,),
+    retries = _ => constant!(RETRIES: u32,),
+});
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_match_meta_forms() {
+    let mut list: Vec<String> = Vec::new();
+    match_meta!(skip, rename = "foo", bounds(Clone, Debug), serde::flatten {
+        skip => push!(list),
+        rename = _ => push!(list),
+        bounds(..) => push!(list),
+        serde::flatten => push!(list),
+    });
+    assert_eq!(list, vec!["", "\"foo\"", "Clone,Debug", ""]);
+}
+
+#[test]
+fn test_match_meta_dispatch() {
+    let (mut flags, mut values, mut rest): (Vec<String>, Vec<String>, Vec<String>) = (Vec::new(), Vec::new(), Vec::new());
+    match_meta!(skip = true, other(1, 2), skip2, unknown = 42, {
+        skip = _ => push!(values),
+        skip => push!(flags),
+        skip2 => push!(flags),
+        _ => push!(rest),
+    });
+    assert_eq!(flags, vec![""]);
+    assert_eq!(values, vec!["true"]);
+    assert_eq!(rest, vec!["other(1,2)", "unknown=42"]);
+}
+
+#[test]
+fn test_match_meta_items() {
+    assert_eq!(NAME, "test");
+    assert_eq!(RETRIES, 3);
+}
+
+#[test]
+fn test_match_meta_macro_rules() {
+    macro_rules! options {
+        ($($args:tt)*) => {{
+            let mut options: Vec<String> = Vec::new();
+            match_meta!($($args)* {
+                verbose => push!(options),
+                level = _ => push!(options),
+            });
+            options
+        }};
+    }
+
+    assert_eq!(options!(verbose), vec![""]);
+    assert_eq!(options!(level = 3, verbose,), vec!["3", ""]);
+}
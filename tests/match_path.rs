@@ -0,0 +1,87 @@
+//  MATCH PATH.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `match_path!()`-macro.
+//
+
+use macro_toolkit::match_path;
+
+
+/***** HELPERS *****/
+/// Returns all the matchers that match a path, as a string.
+macro_rules! shapes {
+    ($path:path) => {
+        [
+            match_path!($path { single => "single", _ => "" }),
+            match_path!($path { multi => "multi", _ => "" }),
+            match_path!($path { global => "global", _ => "" }),
+            match_path!($path { crate => "crate", _ => "" }),
+            match_path!($path { self => "self", _ => "" }),
+            match_path!($path { super => "super", _ => "" }),
+            match_path!($path { generic => "generic", _ => "" }),
+            match_path!($path { plain => "plain", _ => "" }),
+        ]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ")
+    };
+}
+
+/// Removes all whitespace from a string.
+fn strip(s: &str) -> String { s.chars().filter(|c| !c.is_whitespace()).collect() }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_match_path_shapes() {
+    assert_eq!(shapes!(foo), "single plain");
+    assert_eq!(shapes!(Vec<u8>), "single generic");
+    assert_eq!(shapes!(::std::vec::Vec), "multi global plain");
+    assert_eq!(shapes!(crate::foo::Bar<'a, T>), "multi crate generic");
+    assert_eq!(shapes!(self::foo), "multi self plain");
+    assert_eq!(shapes!(super::super::foo), "multi super plain");
+    assert_eq!(shapes!(Vec::<u8>::new), "multi generic");
+    assert_eq!(shapes!(Fn(u8) -> u8), "single generic");
+}
+
+#[test]
+fn test_match_path_bindings() {
+    assert_eq!(strip(match_path!(std::collections::HashMap<String, Vec<u8>> { _(last, args, prefix) => concat!(stringify!(last), "|", stringify!(args), "|", stringify!(prefix)) })), "HashMap|String,Vec<u8>|std::collections::");
+    assert_eq!(strip(match_path!(::std::vec::Vec::<u8> { _(_, args, prefix) => concat!(stringify!(args), "|", stringify!(prefix)) })), "u8|::std::vec::");
+    assert_eq!(strip(match_path!(foo { _(last, args, prefix) => concat!(stringify!(last), "|", stringify!(args), "|", stringify!(prefix)) })), "foo||");
+    assert_eq!(strip(match_path!(FnMut(u8) -> u8 { _(_, args) => stringify!(args) })), "(u8)->u8");
+}
+
+#[test]
+fn test_match_path_names() {
+    assert_eq!(match_path!(std::option::Option<u8> { "Vec" => 1, "Option" => 2, _ => 3 }), 2);
+    assert_eq!(match_path!(Option { "Vec" => 1, "Option" => 2, _ => 3 }), 2);
+    assert_eq!(match_path!(option::Some { "Vec" => 1, "Option" => 2, _ => 3 }), 3);
+}
+
+#[test]
+fn test_match_path_macro_rules() {
+    mod inner {
+        pub struct Foo;
+        impl Foo {
+            pub const NAME: &str = "Foo";
+        }
+    }
+
+    macro_rules! qualify {
+        ($path:path) => {
+            match_path!($path {
+                single(last) => inner::last,
+                _ => $path,
+            })
+        };
+    }
+
+    assert_eq!(<qualify!(Foo)>::NAME, "Foo");
+    assert_eq!(<qualify!(inner::Foo)>::NAME, "Foo");
+}
@@ -0,0 +1,104 @@
+//  MATCH TYPE.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `match_type!()`-macro.
+//
+
+use macro_toolkit::match_type;
+
+
+/***** HELPERS *****/
+/// Returns the kind of a type as a string.
+macro_rules! kind {
+    ($ty:ty) => {
+        match_type!($ty {
+            path => "path",
+            ref_shared => "ref_shared",
+            ref_mut => "ref_mut",
+            slice => "slice",
+            array => "array",
+            unit => "unit",
+            tuple => "tuple",
+            ptr_const => "ptr_const",
+            ptr_mut => "ptr_mut",
+            fn => "fn",
+            impl => "impl",
+            dyn => "dyn",
+            never => "never",
+            infer => "infer",
+            macro => "macro",
+        })
+    };
+}
+
+/// Removes all whitespace from a string.
+fn strip(s: &str) -> String { s.chars().filter(|c| !c.is_whitespace()).collect() }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_match_type_kinds() {
+    assert_eq!(kind!(u8), "path");
+    assert_eq!(kind!(std::vec::Vec<u8>), "path");
+    assert_eq!(kind!(<Vec<u8> as IntoIterator>::Item), "path");
+    assert_eq!(kind!(&str), "ref_shared");
+    assert_eq!(kind!(&&str), "ref_shared");
+    assert_eq!(kind!(&'static mut str), "ref_mut");
+    assert_eq!(kind!([u8]), "slice");
+    assert_eq!(kind!([[u8; 2]; 4]), "array");
+    assert_eq!(kind!(()), "unit");
+    assert_eq!(kind!((u8,)), "tuple");
+    assert_eq!(kind!((u8)), "path");
+    assert_eq!(kind!(*const u8), "ptr_const");
+    assert_eq!(kind!(*mut u8), "ptr_mut");
+    assert_eq!(kind!(fn()), "fn");
+    assert_eq!(kind!(for<'a> unsafe extern "C" fn(&'a u8) -> &'a u8), "fn");
+    assert_eq!(kind!(impl Iterator<Item = u8>), "impl");
+    assert_eq!(kind!(dyn Fn() -> u8 + Send), "dyn");
+    assert_eq!(kind!(!), "never");
+    assert_eq!(kind!(_), "infer");
+    assert_eq!(kind!(std::primitive::u8!()), "macro");
+}
+
+#[test]
+fn test_match_type_bindings() {
+    assert_eq!(strip(match_type!(std::collections::HashMap<String, Vec<u8>> { path(name, args) => concat!(stringify!(name), "|", stringify!(args)) })), "HashMap|String,Vec<u8>");
+    assert_eq!(strip(match_type!(Box<dyn Fn() -> u8> { path(_, args) => stringify!(args) })), "dynFn()->u8");
+    assert_eq!(strip(match_type!(&'a mut [u8] { ref(elem, lifetime) => concat!(stringify!(elem), "|", stringify!(lifetime)) })), "[u8]|'a");
+    assert_eq!(strip(match_type!(&u8 { ref(_, lifetime) => stringify!(lifetime) })), "'_");
+    assert_eq!(strip(match_type!([u8; 2 + 2] { array(elem, len) => concat!(stringify!(elem), "|", stringify!(len)) })), "u8|2+2");
+    assert_eq!(strip(match_type!((u8, Vec<(u16, u32)>,) { tuple(elems) => stringify!(elems) })), "u8,Vec<(u16,u32)>");
+    assert_eq!(strip(match_type!(fn(u8, u16) { fn(params, ret) => concat!(stringify!(params), "|", stringify!(ret)) })), "u8,u16|()");
+    assert_eq!(strip(match_type!(impl Clone + Send { impl(bounds) => stringify!(bounds) })), "Clone+Send");
+}
+
+#[test]
+fn test_match_type_order() {
+    assert_eq!(match_type!(&mut u8 { ref => 1, ref_mut => 2 }), 1);
+    assert_eq!(match_type!(&mut u8 { ref_shared => 1, ref_mut => 2 }), 2);
+    assert_eq!(match_type!(() { tuple => 1, unit => 2 }), 1);
+    assert_eq!(match_type!(*mut u8 { ptr_const => 1, _ => 2 }), 2);
+}
+
+#[test]
+fn test_match_type_macro_rules() {
+    macro_rules! element {
+        ($ty:ty) => {
+            match_type!($ty {
+                ref(elem) => element!(elem),
+                slice(elem) => elem,
+                array(elem) => elem,
+                _ => $ty,
+            })
+        };
+    }
+
+    let a: element!(&[u16]) = 1u16;
+    let b: element!([u32; 4]) = 2u32;
+    let c: element!(u64) = 3u64;
+    assert_eq!((a, b, c), (1, 2, 3));
+}
@@ -0,0 +1,89 @@
+//  MATCH VIS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `match_vis!()`-macro.
+//
+
+use macro_toolkit::match_vis;
+
+
+/***** HELPERS *****/
+/// Returns the kind of a visibility as a string.
+macro_rules! kind {
+    ($vis:vis struct) => {
+        match_vis!($vis {
+            private => "private",
+            pub => "pub",
+            crate => "crate",
+            super => "super",
+            self => "self",
+            in => "in",
+        })
+    };
+}
+
+/// Defines a function with the given visibility, and a companion function that is
+/// one level more private.
+macro_rules! companion {
+    ($vis:vis fn $name:ident() -> u32 { $value:literal }) => {
+        $vis fn $name() -> u32 { companion() }
+        match_vis!($vis {
+            pub => pub(crate) fn companion() -> u32 { $value },
+            restricted(path) => pub(in path) fn companion() -> u32 { $value },
+            private => fn companion() -> u32 { $value },
+        });
+    };
+}
+
+mod module {
+    use super::*;
+
+    companion!(pub fn answer() -> u32 { 42 });
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_match_vis_kinds() {
+    assert_eq!(kind!(struct), "private");
+    assert_eq!(kind!(pub struct), "pub");
+    assert_eq!(kind!(pub(crate) struct), "crate");
+    assert_eq!(kind!(pub(super) struct), "super");
+    assert_eq!(kind!(pub(self) struct), "self");
+    assert_eq!(kind!(pub(in crate::module) struct), "in");
+}
+
+#[test]
+fn test_match_vis_bindings() {
+    assert_eq!(match_vis!(pub(in crate::module) { restricted(path) => stringify!(path) }).replace(' ', ""), "crate::module");
+    assert_eq!(match_vis!(pub(crate) { restricted(path) => stringify!(path) }), "crate");
+    assert_eq!(match_vis!(pub(in super::foo) { crate => 1, in(_) => 2 }), 2);
+    assert_eq!(match_vis!(pub { restricted => 1, _ => 2 }), 2);
+}
+
+#[test]
+fn test_match_vis_companion() {
+    assert_eq!(module::answer(), 42);
+    assert_eq!(module::companion(), 42);
+}
+
+#[test]
+fn test_match_vis_macro_rules() {
+    macro_rules! more_private {
+        ($vis:vis struct) => {
+            match_vis!($vis {
+                pub => stringify!(pub(crate)),
+                crate => stringify!(pub(super)),
+                _ => stringify!(),
+            })
+        };
+    }
+
+    assert_eq!(more_private!(pub struct), "pub(crate)");
+    assert_eq!(more_private!(pub(crate) struct), "pub(super)");
+    assert_eq!(more_private!(struct), "");
+}
@@ -0,0 +1,121 @@
+//  METAVAR EXPRS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `meta_count!()`-, `meta_index!()`- and `meta_ignore!()`-macros.
+//
+
+use macro_toolkit::{meta_count, meta_ignore, meta_index};
+
+
+/***** HELPERS *****/
+/// Builds an array of the given expressions, with its length computed by `meta_count!()`.
+macro_rules! counted_array {
+    ($($x:expr),* $(,)?) => {{
+        const LEN: usize = meta_count!($($x),*);
+        let arr: [i32; LEN] = [$($x),*];
+        arr
+    }};
+}
+
+/// Generates a constant for every given name with its index.
+macro_rules! indexed_consts {
+    ($($name:ident),* $(,)?) => {
+        meta_index!($({ const $name: usize = #index; })*);
+    };
+}
+
+/// Emits its input as-is.
+macro_rules! flatten {
+    ($($tokens:tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Wraps its input in parenthesis.
+macro_rules! tuple {
+    ($($tokens:tt)*) => {
+        ($($tokens)*)
+    };
+}
+
+/// Sums the given literals.
+macro_rules! sum {
+    ($($x:literal)*) => {
+        0 $(+ $x)*
+    };
+}
+
+/// Returns a tuple of units, one for each metavariable.
+macro_rules! units {
+    ($($x:expr),*) => {{
+        let units: ($(meta_ignore!($x => ()),)*) = ($(meta_ignore!($x => ()),)*);
+        units
+    }};
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_meta_count() {
+    assert_eq!(meta_count!(), 0);
+    assert_eq!(meta_count!(a), 1);
+    assert_eq!(meta_count!(a, b, c), 3);
+    assert_eq!(meta_count!(a, b, c,), 3);
+    assert_eq!(meta_count!(a b, (c, d), [e]), 3);
+}
+
+#[test]
+fn test_meta_count_macro() {
+    assert_eq!(counted_array![], []);
+    assert_eq!(counted_array![1], [1]);
+    assert_eq!(counted_array![1, std::cmp::max(2, 3), (4, 5).1], [1, 3, 5]);
+}
+
+#[test]
+fn test_meta_index() {
+    meta_index!();
+    assert_eq!(meta_index!((#index)), 0);
+    assert_eq!(meta_index!((#index,) [#index,] {#index,} => tuple!), (0, 1, 2));
+    assert_eq!(meta_index!(((#index, (#index,))) => flatten!), (0, (0,)));
+    assert_eq!(meta_index!((#index) (#index) (#index) (#index) => sum!), 6);
+}
+
+#[test]
+fn test_meta_index_macro() {
+    indexed_consts!(FIRST, SECOND, THIRD);
+    assert_eq!((FIRST, SECOND, THIRD), (0, 1, 2));
+}
+
+#[test]
+fn test_meta_index_nested() {
+    assert_eq!(
+        meta_index!(((#index, meta_index!((#index,) (#index,) => tuple!)),) ((#index, meta_index!((#index,) => tuple!)),) => tuple!),
+        ((0, (0, 1)), (1, (0,)))
+    );
+}
+
+#[test]
+fn test_meta_ignore() {
+    meta_ignore!(these tokens are ignored);
+    assert_eq!(meta_ignore!(these tokens are ignored => 42), 42);
+    assert_eq!(meta_ignore!(=> "empty"), "empty");
+}
+
+#[test]
+fn test_meta_ignore_macro() {
+    assert_eq!(units!(), ());
+    assert_eq!(
+        units!(
+            1,
+            "two",
+            match 3 {
+                _ => 3,
+            }
+        ),
+        ((), (), ())
+    );
+}
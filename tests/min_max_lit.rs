@@ -0,0 +1,47 @@
+//  MIN MAX LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `min_lit!()`- and `max_lit!()`-macros.
+//
+
+use macro_toolkit::{max_lit, min_lit};
+
+
+/***** TESTS *****/
+#[test]
+fn test_min_lit() {
+    assert_eq!(min_lit!(42), 42);
+    assert_eq!(min_lit!(3, 1, 2,), 1);
+    assert_eq!(min_lit!(0, -0, 0x10), 0);
+    assert_eq!(min_lit!(-3, 7i8, 1), -3i8);
+    assert_eq!(min_lit!(-1.5, 2.0, -1.25f32), -1.5f32);
+    assert_eq!(min_lit!(170141183460469231731687303715884105727, 0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF), i128::MAX);
+}
+
+#[test]
+fn test_max_lit() {
+    assert_eq!(max_lit!(42), 42);
+    assert_eq!(max_lit!(3, 1, 2,), 3);
+    assert_eq!(max_lit!(-3, -7, -1i64), -1i64);
+    assert_eq!(max_lit!(0b1000, 0o7, 0x9), 9);
+    assert_eq!(max_lit!(-1.5, 2.0, -1.25f32), 2.0f32);
+}
+
+#[test]
+fn test_min_max_lit_macro() {
+    macro_rules! bounds {
+        ($($size:literal),*) => {
+            (min_lit!($($size),*), max_lit!($($size),*))
+        };
+    }
+    macro_rules! buffer {
+        ($($size:literal),*) => {
+            [0u8; max_lit!($($size),*)]
+        };
+    }
+
+    assert_eq!(bounds!(8, -2, 16, 4), (-2, 16));
+    assert_eq!(bounds!(1.0, 0.5), (0.5, 1.0));
+    assert_eq!(buffer!(2, 8, 4).len(), 8);
+}
@@ -0,0 +1,68 @@
+//  NAMED ARGS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `named_args!()`-macro.
+//
+
+use macro_toolkit::named_args;
+
+
+/***** HELPERS *****/
+/// Collects the values given to it as strings, without whitespace.
+macro_rules! values {
+    ($($value:expr),*) => {
+        [$(stringify!($value).chars().filter(|c| !c.is_whitespace()).collect::<String>()),*]
+    };
+}
+
+/// Defines a constant with the values given to it.
+macro_rules! config {
+    ($name:expr, $port:expr) => {
+        const CONFIG: (&str, u16) = ($name, $port);
+    };
+}
+
+named_args!(name: "localhost" { name, port = 8080 } => config!);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_named_args_order() {
+    assert_eq!(named_args!(a: 1, b: 2, c: 3 { a, b, c } => values!), ["1", "2", "3"]);
+    assert_eq!(named_args!(c: 3, a: 1, b: 2, { a, b, c } => values!), ["1", "2", "3"]);
+    assert_eq!(named_args!(b: std::vec::Vec::<u8>::new(), a: (1, 2) { a, b } => values!), ["(1,2)", "std::vec::Vec::<u8>::new()"]);
+    assert_eq!(named_args!(b, a: 1 { a, b } => values!), ["1", "b"]);
+}
+
+#[test]
+fn test_named_args_defaults() {
+    assert_eq!(named_args!({ a = 1, b = 2 + 2 } => values!), ["1", "2+2"]);
+    assert_eq!(named_args!(b: 3 { a = 1, b = 2 } => values!), ["1", "3"]);
+    assert_eq!(named_args!(a: 0 { a, b = false, c = 5 } => values!), ["0", "false", "5"]);
+}
+
+#[test]
+fn test_named_args_items() {
+    assert_eq!(CONFIG, ("localhost", 8080));
+}
+
+#[test]
+fn test_named_args_macro_rules() {
+    macro_rules! greet {
+        (@impl $name:expr, $greeting:expr) => {
+            format!("{}, {}!", $greeting, $name)
+        };
+        ($($args:tt)*) => {
+            named_args!($($args)* { name, greeting = "Hello" } => greet!(@impl))
+        };
+    }
+
+    let name: &str = "you";
+    assert_eq!(greet!(name: "world"), "Hello, world!");
+    assert_eq!(greet!(greeting: "Hi", name: "world"), "Hi, world!");
+    assert_eq!(greet!(name), "Hello, you!");
+}
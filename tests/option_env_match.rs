@@ -0,0 +1,69 @@
+//  OPTION ENV MATCH.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `option_env_match!()`-macro.
+//
+
+use macro_toolkit::option_env_match;
+
+
+/***** HELPERS *****/
+/// Returns how a variable is set as a string.
+macro_rules! state {
+    ($var:literal) => {
+        option_env_match!($var {
+            unset => "unset",
+            "" => "empty",
+            set(_) => "set",
+        })
+    };
+}
+
+/// Defines a constant with the value of a variable if it is set, or a default otherwise.
+macro_rules! config {
+    ($name:ident = $var:literal or $default:literal) => {
+        option_env_match!($var {
+            set(value) => const $name: &str = value;,
+            unset => const $name: &str = $default;,
+        });
+    };
+}
+
+config!(NAME = "CARGO_PKG_NAME" or "unknown");
+config!(MISSING = "MACRO_TOOLKIT_TEST_UNSET" or "unknown");
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_option_env_match_state() {
+    assert_eq!(state!("CARGO_PKG_NAME"), "set");
+    assert_eq!(state!("CARGO_PKG_VERSION_PRE"), "empty");
+    assert_eq!(state!("MACRO_TOOLKIT_TEST_UNSET"), "unset");
+}
+
+#[test]
+fn test_option_env_match_value() {
+    let minor: u32 = option_env_match!("CARGO_PKG_VERSION_MINOR" {
+        "0" => 0,
+        "1" => 1,
+        _ => u32::MAX,
+    });
+    assert_eq!(minor, 1);
+}
+
+#[test]
+fn test_option_env_match_binding() {
+    assert_eq!(NAME, "macro-toolkit");
+    assert_eq!(MISSING, "unknown");
+    assert_eq!(option_env_match!("CARGO_PKG_VERSION_MAJOR" { set(v) => concat!("v", v) }), "v0");
+}
+
+#[test]
+fn test_option_env_match_catch_all() {
+    assert_eq!(option_env_match!("MACRO_TOOLKIT_TEST_UNSET" { _ => "any" }), "any");
+    assert_eq!(option_env_match!("CARGO_PKG_NAME" { unset => "unset", _ => "any" }), "any");
+}
@@ -0,0 +1,63 @@
+//  OVERLOAD.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `overload!()`-macro.
+//
+
+use macro_toolkit::overload;
+
+
+/***** HELPERS *****/
+/// Stringifies the arguments given to it, without whitespace and prefixed with a label.
+macro_rules! label {
+    ($label:literal, $($args:tt)*) => {
+        format!("{}:{}", $label, strip(stringify!($($args)*)))
+    };
+}
+
+/// Removes all whitespace from a string.
+fn strip(s: &str) -> String { s.chars().filter(|c| !c.is_whitespace()).collect() }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_overload_arity() {
+    assert_eq!(overload!({ () => label!("none",), (any) => label!("one",) }), "none:");
+    assert_eq!(overload!(a { () => label!("none",), (any) => label!("one",) }), "one:a");
+    assert_eq!(overload!(a, b + c { (any) => label!("one",), (any, any) => label!("two",) }), "two:a,b+c");
+    assert_eq!(overload!(a, b, c { (any) => label!("one",), (any, ..) => label!("many",) }), "many:a,b,c");
+    assert_eq!(overload!(a { (any, any, ..) => label!("many",), (..) => label!("any",) }), "any:a");
+}
+
+#[test]
+fn test_overload_kinds() {
+    assert_eq!(overload!(42 { (string) => label!("string",), (int) => label!("int",) }), "int:42");
+    assert_eq!(overload!("42" { (int) => label!("int",), (string) => label!("string",) }), "string:\"42\"");
+    assert_eq!(overload!(-1.5f32 { (int) => label!("int",), (f32) => label!("f32",) }), "f32:-1.5f32");
+    assert_eq!(overload!(true, 'c' { (bool, byte) => label!("byte",), (bool, char) => label!("char",) }), "char:true,'c'");
+    assert_eq!(overload!(x { (_) => label!("lit",), (any) => label!("any",) }), "any:x");
+}
+
+#[test]
+fn test_overload_macro_rules() {
+    macro_rules! area {
+        (@square $size:expr) => {
+            $size * $size
+        };
+        (@rect $w:expr, $h:expr) => {
+            $w * $h
+        };
+        ($($args:expr),*) => {
+            overload!($($args),* { (int) => area!(@square), (int, int) => area!(@rect), (any, any) => area!(@rect) })
+        };
+    }
+
+    let w: u32 = 4;
+    assert_eq!(area!(3), 9);
+    assert_eq!(area!(2, 5), 10);
+    assert_eq!(area!(w, 2 + 1), 12);
+}
@@ -0,0 +1,70 @@
+//  PARSE LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `parse_lit!()`-macro.
+//
+
+use macro_toolkit::parse_lit;
+
+
+/***** HELPERS *****/
+/// Collects its input in a tuple.
+macro_rules! tuple {
+    ($($x:expr),*) => {
+        ($($x,)*)
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_parse_lit_numbers() {
+    assert_eq!(parse_lit!(int, "42", tuple!), (42,));
+    assert_eq!(parse_lit!(int, "-0b1010", tuple!), (-10,));
+    assert_eq!(parse_lit!(int, "0o7_7", tuple!), (63,));
+    assert_eq!(parse_lit!(int, "+0XfF", tuple!), (255,));
+    assert_eq!(parse_lit!(float, "1.5", tuple!), (1.5,));
+    assert_eq!(parse_lit!(float, "-2e3", tuple!), (-2000.0,));
+    assert_eq!(parse_lit!(bool, "true", tuple!), (true,));
+    assert_eq!(parse_lit!(char, "ß", tuple!), ('ß',));
+}
+
+#[test]
+fn test_parse_lit_structured() {
+    assert_eq!(parse_lit!(ipv4, "10.0.0.255", tuple!), (10u8, 0u8, 0u8, 255u8));
+    assert_eq!(parse_lit!(ip, "::1", tuple!), (0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 0u16, 1u16));
+    assert_eq!(parse_lit!(ipv6, "fe80::1:2", tuple!), (0xfe80u16, 0, 0, 0, 0, 0, 1, 2));
+    assert_eq!(parse_lit!(path, "a//b/./c", tuple!), ("a", "b", "c"));
+    assert_eq!(parse_lit!(duration, "1.5s", tuple!), (1u64, 500_000_000u32));
+    assert_eq!(parse_lit!(size, "2KiB", tuple!), (2048,));
+    assert_eq!(parse_lit!(color, "#123", tuple!), (0x11u8, 0x22u8, 0x33u8, 0xFFu8));
+    assert_eq!(parse_lit!(uuid, "00000000-0000-0000-0000-00000000002a", tuple!), (42u128,));
+}
+
+#[test]
+fn test_parse_lit_callback() {
+    macro_rules! constant {
+        ($name:ident: $ty:ty = $kind:ident $lit:literal) => {
+            const $name: $ty = parse_lit!($kind, $lit, constant!(@value), "Invalid constant",);
+        };
+        (@value $x:expr) => {
+            $x
+        };
+    }
+    macro_rules! getter {
+        ($name:ident) => {
+            fn $name() -> &'static str { stringify!($name) }
+        };
+    }
+    constant!(ANSWER: i64 = int "-42");
+    constant!(HALF: f64 = float "0.5");
+    parse_lit!(ident, "hello", getter!);
+
+    assert_eq!(ANSWER, -42);
+    assert_eq!(HALF, 0.5);
+    assert_eq!(hello(), "hello");
+}
@@ -0,0 +1,88 @@
+//  PUB MACRO.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `pub_macro!()`-macro.
+//
+
+
+/***** HELPERS *****/
+mod outer {
+    pub mod inner {
+        use macro_toolkit::pub_macro;
+
+        pub_macro! {
+            /// Doubles an expression.
+            pub macro_rules! double {
+                ($e:expr) => { 2 * $e };
+            }
+            pub(crate) macro_rules! triple (
+                ($e:expr) => { 3 * $e };
+            );
+            pub(super) macro_rules! quadruple [
+                ($e:expr) => { 4 * $e };
+            ];
+            #[allow(unused_macros)]
+            macro_rules! private {
+                () => { 1 };
+            }
+        }
+
+        pub fn use_private() -> u32 { private!() + self::double!(1) }
+    }
+
+    pub fn use_quadruple() -> u32 { inner::quadruple!(10) }
+}
+
+// Public macros with the same name in different modules don't collide
+mod other {
+    use macro_toolkit::pub_macro;
+
+    pub_macro! {
+        pub macro_rules! double {
+            ($e:expr) => { $crate::outer::inner::double!($e) + 1 };
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_pub_macro_pub() {
+    assert_eq!(outer::inner::double!(21), 42);
+    assert_eq!(other::double!(21), 43);
+}
+
+#[test]
+fn test_pub_macro_restricted() {
+    assert_eq!(crate::outer::inner::triple!(14), 42);
+    assert_eq!(outer::use_quadruple(), 40);
+}
+
+#[test]
+fn test_pub_macro_private() {
+    assert_eq!(outer::inner::use_private(), 3);
+}
+
+#[test]
+fn test_pub_macro_macro_rules() {
+    macro_rules! constant {
+        ($vis:vis $name:ident = $value:expr) => {
+            pub_macro! {
+                $vis macro_rules! $name {
+                    () => { $value };
+                }
+            }
+        };
+    }
+    mod constants {
+        use macro_toolkit::pub_macro;
+
+        constant!(pub(crate) answer = 42);
+    }
+
+    assert_eq!(constants::answer!(), 42);
+}
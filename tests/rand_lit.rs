@@ -0,0 +1,57 @@
+//  RAND LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `rand_lit!()`-macro.
+//
+
+use macro_toolkit::rand_lit;
+
+
+/***** TESTS *****/
+#[test]
+fn test_rand_lit_int() {
+    let _: [u8; 2] = [rand_lit!(u8), rand_lit!(u8, seed = 1)];
+    let _: [i128; 2] = [rand_lit!(i128), rand_lit!(i128, seed = 1,)];
+    let _: usize = rand_lit!(usize in 0..1024);
+
+    let xs: [i8; 4] = [rand_lit!(i8 in -5..5), rand_lit!(i8 in -5..5), rand_lit!(i8 in -5..5, seed = 2), rand_lit!(i8 in -5..=-5)];
+    for x in &xs[..3] {
+        assert!((-5..5).contains(x));
+    }
+    assert_eq!(xs[3], -5);
+    assert_eq!(rand_lit!(u128 in 0..=0), 0);
+}
+
+#[test]
+fn test_rand_lit_float() {
+    let xs: [f64; 3] = [rand_lit!(f64), rand_lit!(f64, seed = 3), rand_lit!(f64 in -2..-1)];
+    assert!((0.0..1.0).contains(&xs[0]));
+    assert!((0.0..1.0).contains(&xs[1]));
+    assert!((-2.0..-1.0).contains(&xs[2]));
+    let x: f32 = rand_lit!(f32 in 100.0..101.0);
+    assert!((100.0..101.0).contains(&x));
+}
+
+#[test]
+fn test_rand_lit_bytes() {
+    let empty: [u8; 0] = rand_lit!([u8; 0]);
+    let bytes: [u8; 32] = rand_lit!([u8; 32]);
+    assert_eq!(empty.len() + bytes.len(), 32);
+}
+
+#[test]
+fn test_rand_lit_seed() {
+    macro_rules! seeded {
+        ($ty:tt) => {
+            [rand_lit!($ty, seed = 0xDEAD_BEEF), rand_lit!($ty, seed = 0xDEAD_BEEF)]
+        };
+    }
+
+    let [a, b] = seeded!(u64);
+    assert_eq!(a, b);
+    let [a, b] = seeded!([u8; 8]);
+    assert_eq!(a, b);
+    let [a, b] = seeded!(f32);
+    assert_eq!(a, b);
+}
@@ -0,0 +1,58 @@
+//  RANGE EXPAND.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `range_expand!()`-macro.
+//
+
+use macro_toolkit::range_expand;
+
+
+/***** TESTS *****/
+#[test]
+fn test_range_expand_simple() {
+    assert_eq!(range_expand!(0..3), [0, 1, 2]);
+    assert_eq!(range_expand!(1..=3), [1, 2, 3]);
+    assert_eq!(range_expand!(-3..-1), [-3, -2]);
+    assert_eq!(range_expand!(5..=5), [5]);
+    let empty: [i32; 0] = range_expand!(5..5);
+    assert_eq!(empty, []);
+    let empty: [i32; 0] = range_expand!(5..3);
+    assert_eq!(empty, []);
+    assert_eq!(range_expand!(250u8..=255), [250u8, 251, 252, 253, 254, 255]);
+}
+
+#[test]
+fn test_range_expand_step() {
+    assert_eq!(range_expand!(0..10, step = 3), [0, 3, 6, 9]);
+    assert_eq!(range_expand!(0..=10, step = 5), [0, 5, 10]);
+    assert_eq!(range_expand!(0..10, step = 5), [0, 5]);
+    assert_eq!(range_expand!(-10i8..=10, step = 100), [-10i8]);
+}
+
+#[test]
+fn test_range_expand_callback() {
+    macro_rules! sum {
+        ($($i:literal),*) => {
+            0 $(+ $i)*
+        };
+    }
+    macro_rules! fields {
+        ($name:ident; $($i:literal),*) => {
+            struct $name(
+                $([u8; $i]),*
+            );
+        };
+    }
+    macro_rules! triangle {
+        ($n:literal) => {
+            range_expand!(1..=$n => sum!)
+        };
+    }
+    range_expand!(1..4 => fields!(Foo;));
+
+    assert_eq!(triangle!(4), 10);
+    assert_eq!(range_expand!(0..100, step = 10 => sum!), 450);
+    let Foo(a, b, c) = Foo([0], [0; 2], [0; 3]);
+    assert_eq!((a.len(), b.len(), c.len()), (1, 2, 3));
+}
@@ -0,0 +1,34 @@
+//  REGEX CHECK.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `regex_check!()`-macro.
+//
+
+use macro_toolkit::regex_check;
+
+
+/***** TESTS *****/
+#[test]
+fn test_regex_check_simple() {
+    assert_eq!(regex_check!(""), "");
+    assert_eq!(regex_check!("abc"), "abc");
+    assert_eq!(regex_check!("(?i)^hello, (world|there)!$"), "(?i)^hello, (world|there)!$");
+}
+
+#[test]
+fn test_regex_check_raw() {
+    assert_eq!(regex_check!(r"\bfoo\b"), "\\bfoo\\b");
+    assert_eq!(regex_check!(r#"^"quoted"$"#), "^\"quoted\"$");
+}
+
+#[test]
+fn test_regex_check_macro() {
+    macro_rules! pattern {
+        ($lit:literal) => {
+            regex_check!($lit)
+        };
+    }
+
+    assert_eq!(pattern!("[0-9]{3}"), "[0-9]{3}");
+}
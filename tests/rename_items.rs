@@ -0,0 +1,101 @@
+//  RENAME ITEMS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[prefix_items]`- and `#[rename_items]`-attributes.
+//
+
+use macro_toolkit::{prefix_items, rename_items};
+
+
+/***** HELPERS *****/
+#[prefix_items(shape_, suffix = _t)]
+#[allow(non_camel_case_types, non_upper_case_globals)]
+mod shapes {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Square {
+        pub size: u32,
+    }
+
+    pub const UNIT: Square = Square { size: 1 };
+    pub static mut COUNTER: u32 = 0;
+
+    pub type Size = u32;
+
+    pub trait Area {
+        fn area(&self) -> Size;
+    }
+    impl Area for Square {
+        fn area(&self) -> Size { self.size * self.size }
+    }
+
+    pub mod nested {
+        pub fn double(size: u32) -> u32 { 2 * size }
+    }
+
+    pub fn total(squares: &[Square]) -> Size { squares.iter().map(Area::area).map(self::nested::double).sum::<Size>() / 2 }
+
+    macro_rules! unit {
+        () => {
+            UNIT
+        };
+    }
+    pub fn unit() -> Square { unit!() }
+}
+
+#[rename_items(strlen = c_strlen, abs = c_ "abs", alias)]
+mod libc {
+    unsafe extern "C" {
+        pub fn strlen(s: *const std::ffi::c_char) -> usize;
+        pub safe fn abs(i: i32) -> i32;
+    }
+
+    pub fn len(s: &std::ffi::CStr) -> usize { unsafe { strlen(s.as_ptr()) } }
+}
+
+macro_rules! bindings {
+    ($prefix:ident) => {
+        #[prefix_items($prefix _, alias)]
+        mod generated {
+            pub fn get() -> u32 { value() }
+            fn value() -> u32 { 42 }
+        }
+    };
+}
+bindings!(gen);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_prefix_items() {
+    use shapes::shape_Area_t as _;
+
+    let square = shapes::shape_Square_t { size: 3 };
+    assert_eq!(square.area(), 9);
+    assert_eq!(shapes::shape_UNIT_t, shapes::shape_unit_t());
+    assert_eq!(shapes::shape_total_t(&[square, shapes::shape_UNIT_t]), 10);
+    assert_eq!(shapes::shape_nested_t::double(4), 8);
+    let size: shapes::shape_Size_t = 1;
+    assert_eq!(size, 1);
+    unsafe {
+        shapes::shape_COUNTER_t += 1;
+    }
+}
+
+#[test]
+fn test_rename_items_extern() {
+    assert_eq!(libc::len(c"hello"), 5);
+    assert_eq!(unsafe { libc::c_strlen(c"hi".as_ptr()) }, 2);
+    assert_eq!(unsafe { libc::strlen(c"hey".as_ptr()) }, 3);
+    assert_eq!(libc::c_abs(-4), 4);
+    assert_eq!(libc::abs(-5), 5);
+}
+
+#[test]
+fn test_prefix_items_macro_rules() {
+    assert_eq!(generated::gen_get(), 42);
+    assert_eq!(generated::get(), 42);
+}
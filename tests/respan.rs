@@ -0,0 +1,73 @@
+//  RESPAN.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `respan!()`-macro and the `#[with_span]`-attribute.
+//
+
+use macro_toolkit::{respan, with_span};
+
+
+/***** HELPERS *****/
+#[with_span(call_site)]
+fn answer() -> u32 { 42 }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_respan_call_site() {
+    respan!(call_site, { let value = (answer(), [answer()]); });
+    assert_eq!(value, (42, [42]));
+}
+
+#[test]
+fn test_respan_token() {
+    macro_rules! with_it {
+        ($value:expr, $body:block) => {{
+            respan!($body, { let it = $value; });
+            $body
+        }};
+    }
+
+    assert_eq!(with_it!(21, { it * 2 }), 42);
+    assert_eq!(with_it!("foo", { it.len() }), 3);
+}
+
+#[test]
+fn test_respan_to() {
+    macro_rules! with_it {
+        ($value:expr, $body:block) => {{
+            respan!(to: $body, { let it = $value; });
+            $body
+        }};
+    }
+
+    assert_eq!(with_it!(21, { it * 2 }), 42);
+    respan!(to: call_site, { let to = 42; });
+    respan!(to, { let value = to; });
+    assert_eq!(value, 42);
+}
+
+#[test]
+fn test_respan_mixed_site() {
+    let value = 1;
+    respan!(mixed_site, { let value = 2; });
+    assert_eq!(value, 1);
+}
+
+#[test]
+fn test_with_span_macro_rules() {
+    macro_rules! make_fn {
+        ($name:ident, $body:block) => {
+            #[with_span(to: $body)]
+            fn $name(it: u32, [other]: [u32; 1]) -> u32 { ($body) + other }
+        };
+    }
+
+    make_fn!(double, { it * 2 });
+    assert_eq!(double(20, [2]), 42);
+    assert_eq!(answer(), 42);
+}
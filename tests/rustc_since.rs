@@ -0,0 +1,63 @@
+//  RUSTC SINCE.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `rustc_since!()`-macro and the `#[cfg_rustc_since]`-attribute.
+//
+
+use macro_toolkit::{cfg_rustc_since, rustc_since};
+
+
+/***** TESTS *****/
+#[test]
+fn test_rustc_since_versions() {
+    assert_eq!(rustc_since!(1.0 { "new" } else { "old" }), "new");
+    assert_eq!(rustc_since!(1.85.0 { "new" } else { "old" }), "new");
+    assert_eq!(rustc_since!("1.85.0" { "new" } else { "old" }), "new");
+    assert_eq!(rustc_since!(999.0 { "new" } else { "old" }), "old");
+    assert_eq!(rustc_since!(999.0.0 { "new" } else { "old" }), "old");
+}
+
+#[test]
+fn test_rustc_since_no_else() {
+    let mut value: u32 = 0;
+    rustc_since!(1.0 { value += 1; });
+    rustc_since!(999.0 { value += 2; });
+    assert_eq!(value, 1);
+}
+
+#[test]
+fn test_rustc_since_attr() {
+    #[cfg_rustc_since(1.0)]
+    fn first() -> u32 {
+        1
+    }
+    #[cfg_rustc_since(not(1.0))]
+    fn first() -> u32 {
+        2
+    }
+    #[cfg_rustc_since(999.0)]
+    fn second() -> u32 {
+        1
+    }
+    #[cfg_rustc_since(not("999.0.0"))]
+    fn second() -> u32 {
+        2
+    }
+
+    assert_eq!(first(), 1);
+    assert_eq!(second(), 2);
+}
+
+#[test]
+fn test_rustc_since_macro_rules() {
+    macro_rules! since {
+        ($version:literal) => {
+            rustc_since!($version { "new" } else { "old" })
+        };
+    }
+
+    assert_eq!(since!(1.0), "new");
+    assert_eq!(since!("1.85.0"), "new");
+    assert_eq!(since!(999.0), "old");
+}
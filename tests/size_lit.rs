@@ -0,0 +1,47 @@
+//  SIZE LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `size_lit!()`-macro.
+//
+
+use macro_toolkit::size_lit;
+
+
+/***** TESTS *****/
+#[test]
+fn test_size_lit_decimal() {
+    assert_eq!(size_lit!("42"), 42);
+    assert_eq!(size_lit!("42B"), 42);
+    assert_eq!(size_lit!("1kB"), 1_000);
+    assert_eq!(size_lit!("1KB"), 1_000);
+    assert_eq!(size_lit!("1 MB"), 1_000_000);
+    assert_eq!(size_lit!("1GB"), 1_000_000_000);
+    assert_eq!(size_lit!("1TB"), 1_000_000_000_000);
+    assert_eq!(size_lit!("1PB"), 1_000_000_000_000_000);
+    assert_eq!(size_lit!("1EB"), 1_000_000_000_000_000_000);
+}
+
+#[test]
+fn test_size_lit_binary() {
+    assert_eq!(size_lit!("1KiB"), 1 << 10);
+    assert_eq!(size_lit!("1MiB"), 1 << 20);
+    assert_eq!(size_lit!("1GiB"), 1 << 30);
+    assert_eq!(size_lit!("1TiB"), 1 << 40);
+    assert_eq!(size_lit!("1PiB"), 1 << 50);
+    assert_eq!(size_lit!("1EiB"), 1 << 60);
+}
+
+#[test]
+fn test_size_lit_fractions() {
+    assert_eq!(size_lit!("0.5KiB"), 512);
+    assert_eq!(size_lit!("1.25MB"), 1_250_000);
+    assert_eq!(size_lit!("2.0B"), 2);
+}
+
+#[test]
+fn test_size_lit_types() {
+    assert_eq!(size_lit!(u8 "255B"), 255u8);
+    assert_eq!(size_lit!(u32 "4GB"), 4_000_000_000u32);
+    assert_eq!(size_lit!(u128 "16EiB"), 1u128 << 64);
+}
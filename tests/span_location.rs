@@ -0,0 +1,62 @@
+//  SPAN LOCATION.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `span_location!()`-macro.
+//
+
+use macro_toolkit::span_location;
+
+
+/***** HELPERS *****/
+/// Returns the location of the token given to it.
+#[cfg(feature = "span_locations")]
+macro_rules! location_of {
+    ($token:tt) => {
+        span_location!($token)
+    };
+}
+
+/// Returns the location of itself, i.e., of this definition.
+macro_rules! location_here {
+    () => {
+        span_location!()
+    };
+}
+
+/// Formats the location as a string.
+macro_rules! format_location {
+    ($file:literal, $line:literal, $column:literal) => {
+        format!("{}:{}:{}", $file, $line, $column)
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_span_location_call_site() {
+    let (file, line, column) = span_location!();
+    assert_eq!((file, line, column), (file!(), line!() - 1, 32));
+}
+
+#[test]
+fn test_span_location_in_macro() {
+    let (file, line, column): (&str, u32, u32) = location_here!();
+    assert_eq!((file, line, column), (file!(), 23, 9));
+}
+
+#[test]
+fn test_span_location_callback() {
+    assert_eq!(span_location!(=> format_location!), format!("{}:{}:{}", file!(), line!(), 16));
+}
+
+#[cfg(feature = "span_locations")]
+#[test]
+fn test_span_location_token() {
+    assert_eq!(span_location!(foo), (file!(), line!(), 31));
+    assert_eq!(location_of!(bar), (file!(), line!(), 29));
+    assert_eq!(span_location!({ multiple tokens } => format_location!), format!("{}:{}:{}", file!(), line!(), 31));
+}
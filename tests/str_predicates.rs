@@ -0,0 +1,47 @@
+//  STR PREDICATES.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `str_predicates!()`-macro.
+//
+
+use macro_toolkit::str_predicates;
+
+
+/***** TESTS *****/
+#[test]
+fn test_str_predicates_bool() {
+    let res: [bool; 7] = [
+        str_predicates!(contains("foobar", "oba")),
+        str_predicates!(contains("foobar", "")),
+        str_predicates!(contains("foobar", "baz")),
+        str_predicates!(starts_with("foobar", "foo",)),
+        str_predicates!(starts_with("foobar", 'b')),
+        str_predicates!(ends_with("foobar", 'r')),
+        str_predicates!(ends_with("", "r")),
+    ];
+    assert_eq!(res, [true, true, false, true, false, true, false]);
+}
+
+#[test]
+fn test_str_predicates_if() {
+    assert_eq!(str_predicates!(if contains("héllo", 'é') { 1 } else { 2 }), 1);
+    assert_eq!(str_predicates!(if starts_with("hello", "hi") { 1 } else { 2 }), 2);
+
+    let mut x: u32 = 0;
+    str_predicates!(if ends_with("hello", "lo") { x += 1; });
+    str_predicates!(if ends_with("hello", "he") { x += 2; });
+    assert_eq!(x, 1);
+}
+
+#[test]
+fn test_str_predicates_macro() {
+    macro_rules! prefixed {
+        ($name:literal) => {
+            str_predicates!(if starts_with($name, "__") { $name } else { concat!("__", $name) })
+        };
+    }
+
+    assert_eq!(prefixed!("foo"), "__foo");
+    assert_eq!(prefixed!("__foo"), "__foo");
+}
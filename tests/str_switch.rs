@@ -0,0 +1,117 @@
+//  STR SWITCH.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `str_switch!()`-macro.
+//
+
+use macro_toolkit::str_switch;
+
+
+/***** HELPERS *****/
+/// Finds the index of a keyword, or [`None`] if it isn't one.
+fn keyword(word: &str) -> Option<usize> {
+    str_switch!(word {
+        "as" => Some(0),
+        "async" => Some(1),
+        "await" => Some(2),
+        "break" => Some(3),
+        "const" => Some(4),
+        "continue" => Some(5),
+        "crate" => Some(6),
+        "else" => Some(7),
+        "enum" => Some(8),
+        "extern" => Some(9),
+        "fn" | "for" | "if" | "impl" | "in" => Some(10),
+        _ => None,
+    })
+}
+
+/// Generates a function that looks up a string in a list and returns its index, matching with
+/// `str_switch!()`.
+macro_rules! lookup {
+    ($name:ident, [$($lit:literal),* $(,)?]) => {
+        fn $name(value: &str) -> Option<usize> {
+            const LITS: &[&str] = &[$($lit),*];
+            str_switch!(value {
+                $($lit => LITS.iter().position(|lit| *lit == $lit),)*
+                _ => None,
+            })
+        }
+    };
+}
+lookup!(lookup_similar, ["aaaa", "aaab", "aaba", "abaa", "baaa", "bbbb", "a", "b", ""]);
+lookup!(lookup_unicode, ["héllo", "hello", "wörld", "🦀", "crab"]);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_str_switch() {
+    assert_eq!(keyword("as"), Some(0));
+    assert_eq!(keyword("async"), Some(1));
+    assert_eq!(keyword("await"), Some(2));
+    assert_eq!(keyword("break"), Some(3));
+    assert_eq!(keyword("const"), Some(4));
+    assert_eq!(keyword("continue"), Some(5));
+    assert_eq!(keyword("crate"), Some(6));
+    assert_eq!(keyword("else"), Some(7));
+    assert_eq!(keyword("enum"), Some(8));
+    assert_eq!(keyword("extern"), Some(9));
+    assert_eq!(keyword("fn"), Some(10));
+    assert_eq!(keyword("in"), Some(10));
+    assert_eq!(keyword("impl"), Some(10));
+    assert_eq!(keyword(""), None);
+    assert_eq!(keyword("a"), None);
+    assert_eq!(keyword("asy"), None);
+    assert_eq!(keyword("asyn"), None);
+    assert_eq!(keyword("awaits"), None);
+    assert_eq!(keyword("Async"), None);
+    assert_eq!(keyword("constant"), None);
+}
+
+#[test]
+fn test_str_switch_similar() {
+    for (i, value) in ["aaaa", "aaab", "aaba", "abaa", "baaa", "bbbb", "a", "b", ""].into_iter().enumerate() {
+        assert_eq!(lookup_similar(value), Some(i));
+    }
+    for value in ["aabb", "abab", "bbba", "aaa", "c", "aaaaa"] {
+        assert_eq!(lookup_similar(value), None);
+    }
+}
+
+#[test]
+fn test_str_switch_unicode() {
+    for (i, value) in ["héllo", "hello", "wörld", "🦀", "crab"].into_iter().enumerate() {
+        assert_eq!(lookup_unicode(value), Some(i));
+    }
+    for value in ["hèllo", "world", "🦞"] {
+        assert_eq!(lookup_unicode(value), None);
+    }
+}
+
+#[test]
+fn test_str_switch_catch_all() {
+    let value: String = String::from("foo");
+    assert_eq!(str_switch!(&value { "bar" => 1, _ => 2 }), 2);
+    assert_eq!(str_switch!(&value { other => other.len() }), 3);
+    assert_eq!(str_switch!(value.as_str() { "foo" => "matched".to_string(), other => format!("{other} not matched") }), "matched");
+    assert_eq!(str_switch!("baz" { "foo" => "matched".to_string(), other => format!("{other} not matched") }), "baz not matched");
+}
+
+#[test]
+fn test_str_switch_hygiene() {
+    // The names used by the macro do not clash with those of the caller
+    let (value, bytes, arm): (&str, &str, &str) = ("value", "bytes", "arm");
+    assert_eq!(str_switch!(arm { "value" => value, "bytes" => bytes, other => other }), "arm");
+    assert_eq!(str_switch!(bytes { "value" => value, "bytes" => bytes, other => other }), "bytes");
+}
+
+#[test]
+fn test_str_switch_escapes() {
+    assert_eq!(str_switch!("a\"b" { "a\"b" => 1, r#"a\"b"# => 2, _ => 3 }), 1);
+    assert_eq!(str_switch!(r#"a\"b"# { "a\"b" => 1, r#"a\"b"# => 2, _ => 3 }), 2);
+    assert_eq!(str_switch!("\n" { "\n" => 1, "\\n" => 2, _ => 3 }), 1);
+}
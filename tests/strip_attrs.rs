@@ -0,0 +1,108 @@
+//  STRIP ATTRS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[strip_attrs(...)]`-attribute.
+//
+
+use macro_toolkit::strip_attrs;
+
+
+/***** HELPERS *****/
+/// Stringifies the attributes it is given.
+macro_rules! attrs_str {
+    ($name:ident $(#[$attr:meta])*) => {
+        const $name: &[&str] = &[$(stringify!($attr)),*];
+    };
+}
+
+#[strip_attrs(unknown, unknown::*, doc => attrs_str!(STRUCT_ATTRS))]
+/// A struct with attributes that do not exist.
+#[derive(Clone, Debug, PartialEq)]
+#[unknown(foo)]
+pub struct Named<T> where T: Clone {
+    #[unknown::nested]
+    pub a: T,
+    /// A field.
+    b: Vec<u8>,
+}
+
+#[strip_attrs(unknown)]
+#[derive(Debug, PartialEq)]
+struct Tuple(#[unknown] u8, #[allow(unused)] u16);
+
+#[strip_attrs(*)]
+#[unknown]
+#[derive(Debug, PartialEq)]
+enum Enum {
+    #[unknown(a)]
+    A = 1,
+    #[unknown::b]
+    B = 1 << 2,
+}
+
+#[strip_attrs(unknown => attrs_str!(ENUM_ATTRS))]
+#[derive(Debug, PartialEq)]
+#[repr(u8)]
+enum Data {
+    #[unknown]
+    Unit,
+    Tuple(#[unknown] u8),
+    Struct {
+        #[unknown]
+        #[allow(unused)]
+        field: u16,
+    },
+}
+
+#[strip_attrs(unknown)]
+#[unknown]
+fn function() -> u32 { 42 }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_strip_attrs_struct() {
+    let named = Named { a: 1, b: vec![2] };
+    assert_eq!(named.clone(), named);
+    assert_eq!(named.b, vec![2]);
+    assert_eq!(Tuple(1, 2), Tuple(1, 2));
+}
+
+#[test]
+fn test_strip_attrs_enum() {
+    assert_eq!(Enum::A as u8, 1);
+    assert_eq!(Enum::B as u8, 4);
+    assert_eq!(Data::Tuple(1), Data::Tuple(1));
+    assert_ne!(Data::Unit, Data::Struct { field: 2 });
+}
+
+#[test]
+fn test_strip_attrs_callback() {
+    let strip = |attrs: &[&str]| -> Vec<String> { attrs.iter().map(|attr| attr.split_whitespace().collect()).collect() };
+    assert_eq!(strip(STRUCT_ATTRS), strip(&["doc = \" A struct with attributes that do not exist.\"", "unknown(foo)", "unknown::nested", "doc = \" A field.\""]));
+    assert_eq!(strip(ENUM_ATTRS), strip(&["unknown", "unknown", "unknown"]));
+    assert_eq!(function(), 42);
+}
+
+#[test]
+fn test_strip_attrs_macro_rules() {
+    macro_rules! mirror {
+        ($item:item) => {
+            #[strip_attrs(unknown::*)]
+            $item
+        };
+    }
+
+    mirror! {
+        #[derive(Debug, PartialEq)]
+        struct Mirrored {
+            #[unknown(rename = "y")]
+            x: u8,
+        }
+    }
+    assert_eq!(Mirrored { x: 1 }, Mirrored { x: 1 });
+}
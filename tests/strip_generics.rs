@@ -0,0 +1,79 @@
+//  STRIP GENERICS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `strip_generics!()`-macro.
+//
+
+use std::collections::HashMap;
+
+use macro_toolkit::strip_generics;
+
+
+/***** HELPERS *****/
+/// Stringifies the path given to it, without whitespace.
+macro_rules! path_str {
+    ($($path:tt)*) => {
+        strip(stringify!($($path)*))
+    };
+}
+
+/// Removes all whitespace from a string.
+fn strip(s: &str) -> String { s.chars().filter(|c| !c.is_whitespace()).collect() }
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_strip_generics() {
+    assert_eq!(strip_generics!(Vec<u8> => path_str!), strip("Vec"));
+    assert_eq!(strip_generics!(Plain => path_str!), strip("Plain"));
+    assert_eq!(strip_generics!(::std::vec::Vec<u8> => path_str!), strip("::std::vec::Vec"));
+    assert_eq!(strip_generics!(a::B::<T>::C<U, V> => path_str!), strip("a::B::C"));
+    assert_eq!(strip_generics!(Box<dyn Fn(u8) -> u8> => path_str!), strip("Box"));
+    assert_eq!(strip_generics!(FnMut(u8) -> Vec<u8> => path_str!), strip("FnMut"));
+    assert_eq!(strip_generics!(<T as Trait<U>>::Out<V> => path_str!), strip("<T as Trait<U>>::Out"));
+}
+
+#[test]
+fn test_strip_generics_lifetimes() {
+    assert_eq!(strip_generics!(lifetimes: Foo<'a> => path_str!), strip("Foo"));
+    assert_eq!(strip_generics!(lifetimes: Foo::<'a> => path_str!), strip("Foo"));
+    assert_eq!(strip_generics!(lifetimes: Foo<'a, T> => path_str!), strip("Foo<T>"));
+    assert_eq!(strip_generics!(lifetimes: Foo<'a, Bar<'b, T>, &'c U,> => path_str!), strip("Foo<Bar<T>, &'c U>"));
+    assert_eq!(strip_generics!(lifetimes: a::B<'a>::C<(D<'b>, u8)> => path_str!), strip("a::B::C<(D, u8)>"));
+    assert_eq!(strip_generics!(lifetimes: Box<dyn for<'a> Fn(&'a u8) + 'static> => path_str!), strip("Box<dyn for<'a> Fn(&'a u8) + 'static>"));
+}
+
+#[test]
+fn test_strip_generics_types() {
+    macro_rules! map_of_u8 {
+        ($($path:tt)*) => {
+            $($path)*::<u8, u8>::from([(1, 2)])
+        };
+    }
+
+    let map = strip_generics!(HashMap<String, String> => map_of_u8!);
+    assert_eq!(map[&1], 2);
+
+    struct Wrapper<'a, T>(&'a T);
+    static WRAPPER: strip_generics!(lifetimes: Wrapper<'a, u8>) = Wrapper(&42);
+    assert_eq!(*WRAPPER.0, 42);
+}
+
+#[test]
+fn test_strip_generics_macro_rules() {
+    macro_rules! rewrap {
+        (@impl $value:expr, $($path:tt)*) => {
+            $($path)*::<_>::from([$value])
+        };
+        ($ty:ty, $value:expr) => {
+            strip_generics!($ty => rewrap!(@impl $value,))
+        };
+    }
+
+    let list: Vec<char> = rewrap!(Vec<u8>, 'a');
+    assert_eq!(list, vec!['a']);
+}
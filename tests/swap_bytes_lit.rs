@@ -0,0 +1,41 @@
+//  SWAP BYTES LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `swap_bytes_lit!()`-macro.
+//
+
+use macro_toolkit::swap_bytes_lit;
+
+
+/***** TESTS *****/
+#[test]
+fn test_swap_bytes_lit_unsigned() {
+    assert_eq!(swap_bytes_lit!(0xABu8), 0xABu8);
+    assert_eq!(swap_bytes_lit!(0xAABBu16), 0xBBAAu16);
+    assert_eq!(swap_bytes_lit!(u32 1), 0x0100_0000u32);
+    assert_eq!(swap_bytes_lit!(u64 0x0102030405060708), 0x0807060504030201u64);
+    assert_eq!(swap_bytes_lit!(0x0102030405060708090A0B0C0D0E0F10u128), 0x0102030405060708090A0B0C0D0E0F10u128.swap_bytes());
+}
+
+#[test]
+fn test_swap_bytes_lit_signed() {
+    assert_eq!(swap_bytes_lit!(-1i8), -1i8);
+    assert_eq!(swap_bytes_lit!(0x0080i16), i16::MIN);
+    assert_eq!(swap_bytes_lit!(i32 -2), (-2i32).swap_bytes());
+    assert_eq!(swap_bytes_lit!(0x80i64), 0x80i64.swap_bytes());
+    assert_eq!(swap_bytes_lit!(0x80i128), i128::MIN);
+    assert_eq!(swap_bytes_lit!(i128 -170141183460469231731687303715884105728), 0x80i128);
+}
+
+#[test]
+fn test_swap_bytes_lit_macro() {
+    macro_rules! be {
+        ($ty:ident $lit:literal) => {
+            swap_bytes_lit!($ty $lit)
+        };
+    }
+
+    assert_eq!(be!(u16 0x1234), 0x3412u16);
+    assert_eq!(be!(i16 -0x1234), (-0x1234i16).swap_bytes());
+}
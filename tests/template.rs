@@ -0,0 +1,94 @@
+//  TEMPLATE.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `template!()`- and `expand_template!()`-macros.
+//
+
+use macro_toolkit::{expand_template, template};
+
+
+/***** HELPERS *****/
+#[macro_use]
+mod templates {
+    use macro_toolkit::template;
+
+    template! {
+        double(value: expr) => { 2 * $value }
+        #[allow(unused_macros)]
+        unused() => {}
+    }
+}
+
+template! {
+    getter(name: ident, ty: ty) => {
+        fn [<get_ $name>](&self) -> &$ty { &self.$name }
+    };
+    setter(name: ident, ty: ty) => {
+        fn [<set_ $name>](&mut self, value: $ty) { self.$name = value; }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_template_items() {
+    struct Foo {
+        bar: u32,
+        baz: Vec<u8>,
+    }
+    impl Foo {
+        expand_template!(getter(bar, u32), setter(bar, u32); getter(baz, Vec<u8>));
+    }
+
+    let mut foo = Foo { bar: 42, baz: vec![1, 2, 3] };
+    foo.set_bar(84);
+    assert_eq!(*foo.get_bar(), 84);
+    assert_eq!(foo.get_baz(), &[1, 2, 3]);
+}
+
+#[test]
+fn test_template_untyped() {
+    template! {
+        pair(a, b,) => { ($a, $b) }
+    }
+
+    assert_eq!(expand_template!(pair(1, "two")), (1, "two"));
+    assert_eq!(expand_template!(double(21)), 42);
+}
+
+#[test]
+fn test_template_generics() {
+    template! {
+        tuple_struct(name: ident, a: expr, b: expr) => {
+            #[derive(Debug)]
+            struct $name<{<...$a, $b>}>({<...$a, $b>});
+        }
+    }
+
+    expand_template!(tuple_struct(Foo, 1, "two"));
+    assert_eq!(format!("{:?}", Foo(1, "two")), "Foo(1, \"two\")");
+}
+
+#[test]
+fn test_template_macro_rules() {
+    macro_rules! getters {
+        ($($name:ident: $ty:ty),*) => {
+            expand_template!($(getter($name, $ty)),*);
+        };
+    }
+
+    struct Foo {
+        a: u8,
+        b: char,
+    }
+    impl Foo {
+        getters!(a: u8, b: char);
+    }
+
+    let foo = Foo { a: 1, b: 'b' };
+    assert_eq!((*foo.get_a(), *foo.get_b()), (1, 'b'));
+}
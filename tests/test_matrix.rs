@@ -0,0 +1,94 @@
+//  TEST MATRIX.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `test_matrix!()`-macro.
+//
+
+use macro_toolkit::test_matrix;
+
+
+/***** HELPERS *****/
+/// Computes the sum of the numbers up to and including `n` with a loop.
+fn sum_loop(n: u64) -> u64 { (1..=n).sum() }
+
+/// Computes the sum of the numbers up to and including `n` with Gauss' formula.
+fn sum_gauss(n: u64) -> u64 { n * (n + 1) / 2 }
+
+/// Computes the length of a string by counting its characters.
+fn char_len(value: &str) -> usize { value.chars().count() }
+
+mod recursive {
+    /// Computes the sum of the numbers up to and including `n` recursively.
+    pub fn sum(n: u64) -> u64 { if n == 0 { 0 } else { n + sum(n - 1) } }
+}
+
+
+
+
+
+/***** TESTS *****/
+test_matrix! {
+    fns = [sum_loop, sum_gauss, recursive::sum],
+    cases = [
+        (0, 0),
+        (1, 1),
+        hundred: (100, 5050),
+    ],
+    |f, n, expected| {
+        assert_eq!(f(n), expected);
+    }
+}
+
+/// Generates tests for the length of strings.
+macro_rules! len_tests {
+    ($($name:ident: $value:literal => $len:literal),*) => {
+        test_matrix! {
+            fns = [str::len, char_len],
+            cases = [$($name: ($value, $len)),*],
+            |f, value, len| {
+                assert_eq!(f(value), len);
+            }
+        }
+    };
+}
+
+len_tests!(empty: "" => 0, hello: "hello" => 5);
+
+test_matrix! {
+    cases = [
+        zero: (0u8),
+        max: (u8::MAX),
+    ],
+    #[allow(clippy::identity_op)]
+    |value| {
+        assert_eq!(value.checked_add(0), Some(value));
+    }
+}
+
+test_matrix! {
+    fns = [u8::checked_sub, u8::checked_div],
+    cases = [underflow_or_div_zero: (0, 1, 0)],
+    #[should_panic]
+    |f, lhs, rhs_sub, rhs_div| {
+        // Uses the same values for both functions, but only one of them panics on either
+        let rhs: u8 = if stringify!(f).contains("sub") { rhs_sub } else { rhs_div };
+        f(lhs, rhs).unwrap();
+    }
+}
+
+#[test]
+fn test_test_matrix_names() {
+    // The tests are also normal functions, so we can call them
+    test_sum_loop_0();
+    test_sum_gauss_1();
+    test_recursive_sum_hundred();
+    test_zero();
+    test_max();
+}
+
+#[test]
+fn test_test_matrix_macro_rules() {
+    test_str_len_empty();
+    test_char_len_hello();
+}
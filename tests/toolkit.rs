@@ -0,0 +1,104 @@
+//  TOOLKIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `toolkit!()`-macro.
+//
+
+use macro_toolkit::toolkit;
+
+
+/***** HELPERS *****/
+/// Generates a constant with a name that depends on the kind of the given literal.
+macro_rules! constant {
+    ($name:ident = $value:literal) => {
+        toolkit! {
+            match_lit!($value {
+                string => pub const [<$name _STR>]: &str = $value;,
+                int => pub const [<$name _INT>]: i64 = $value;,
+                bool => pub const [<$name _BOOL>]: bool = $value;,
+            })
+        }
+    };
+}
+constant!(ANSWER = 42);
+constant!(GREETING = "Hello, world!");
+constant!(ENABLED = true);
+
+/// Generates a struct with a tuple that has a generic parameter for every value, wrapped in a
+/// module whose name is pasted.
+macro_rules! tuple_mod {
+    ($name:ident($($value:expr),*)) => {
+        toolkit! {
+            mod [<$name _mod>] {
+                #[derive(Debug)]
+                pub struct Tuple<{<...$($value),*>}>(pub ({<...$($value),*>}));
+            }
+        }
+    };
+}
+tuple_mod!(pair(1, "two"));
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_toolkit() {
+    // Plain tokens are left alone
+    assert_eq!(toolkit!(1 + 2), 3);
+    assert_eq!(toolkit! { [1, 2, 3] }, [1, 2, 3]);
+}
+
+#[test]
+fn test_toolkit_paste() {
+    assert_eq!(ANSWER_INT, 42);
+    assert_eq!(GREETING_STR, "Hello, world!");
+    const { assert!(ENABLED_BOOL) };
+    assert_eq!(format!("{:?}", pair_mod::Tuple((1, "two"))), "Tuple((1, \"two\"))");
+}
+
+#[test]
+fn test_toolkit_nested() {
+    macro_rules! describe {
+        ($lit:literal) => {
+            toolkit! {
+                match_lit!($lit {
+                    int => match_lit!($lit {
+                        sint => "signed integer",
+                        uint => "unsigned integer",
+                        _ => "integer",
+                    }),
+                    _ => "something else",
+                })
+            }
+        };
+    }
+    assert_eq!(describe!(42i8), "signed integer");
+    assert_eq!(describe!(42u8), "unsigned integer");
+    assert_eq!(describe!(42), "integer");
+    assert_eq!(describe!("42"), "something else");
+
+    // Macros expanding to `idents!()` and `toolkit!()` itself
+    let get_foo: u32 = 42;
+    assert_eq!(toolkit!(idents!([<get_ foo>])), 42);
+    assert_eq!(toolkit!(toolkit!(macro_toolkit::idents!([<get_ foo>]))), 42);
+}
+
+#[test]
+fn test_toolkit_unchosen() {
+    // Pastes in branches that aren't chosen are never pasted, so they may be invalid
+    macro_rules! name {
+        ($lit:literal) => {
+            toolkit! {
+                match_lit!($lit {
+                    string => stringify!([<name_ $lit>]),
+                    _ => "not a string",
+                })
+            }
+        };
+    }
+    assert_eq!(name!("foo"), "name_foo");
+    assert_eq!(name!(1.5), "not a string");
+}
@@ -0,0 +1,64 @@
+//  TRACE EXPANSION.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `#[trace_expansion]`-attribute and the `trace_tokens!()`-macro.
+//
+
+use macro_toolkit::{trace_expansion, trace_tokens};
+
+
+/***** HELPERS *****/
+#[trace_expansion]
+macro_rules! sum {
+    () => { 0 };
+    ($head:literal $(, $tail:literal)*) => { $head + sum!($($tail),*) };
+}
+
+#[trace_expansion(label = "define")]
+macro_rules! define {
+    ($name:ident = $value:expr) => {
+        const $name: u32 = $value;
+    };
+}
+define!(ANSWER = 42);
+
+#[trace_expansion]
+#[derive(Debug, PartialEq)]
+struct Traced(u32);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_trace_expansion_macro_rules() {
+    assert_eq!(sum!(), 0);
+    assert_eq!(sum!(1, 2, 3), 6);
+    assert_eq!(ANSWER, 42);
+}
+
+#[test]
+fn test_trace_expansion_item() {
+    assert_eq!(Traced(42), Traced(42));
+}
+
+#[test]
+fn test_trace_tokens() {
+    assert_eq!(trace_tokens!({ 21 * 2 }), 42);
+    assert_eq!(trace_tokens!(label = "answer", { 42 }), 42);
+}
+
+#[test]
+fn test_trace_expansion_macro_rules_generated() {
+    macro_rules! make_fn {
+        ($name:ident, $value:expr) => {
+            #[trace_expansion(label = "make_fn")]
+            fn $name() -> u32 { $value }
+        };
+    }
+    make_fn!(answer, 42);
+
+    assert_eq!(answer(), 42);
+}
@@ -0,0 +1,67 @@
+//  TURBOFISH.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `turbofish!()`-macro.
+//
+
+use std::collections::HashMap;
+
+use macro_toolkit::turbofish;
+
+
+/***** HELPERS *****/
+/// Stringifies the path given to it, without whitespace.
+macro_rules! path_str {
+    ($($path:tt)*) => {
+        strip(stringify!($($path)*))
+    };
+}
+
+/// Removes all whitespace from a string.
+fn strip(s: &str) -> String { s.chars().filter(|c| !c.is_whitespace()).collect() }
+
+/// Some generic type with associated functions.
+#[derive(Debug, PartialEq)]
+struct Pair<T, U>(T, U);
+impl<T: Default, U: Default> Pair<T, U> {
+    fn new() -> Self { Self(T::default(), U::default()) }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_turbofish_expressions() {
+    assert_eq!(turbofish!(Vec<u8>::new)(), Vec::<u8>::new());
+    assert_eq!(turbofish!(Pair<u8, bool>::new)(), Pair(0, false));
+    assert_eq!(turbofish!(Pair<Vec<u8>, Option<char>>)(vec![1], None), Pair(vec![1], None));
+    assert!(turbofish!(HashMap<String, Vec<u8>>::new)().is_empty());
+    assert_eq!(turbofish!(<Vec<u8> as Default>::default)(), vec![]);
+}
+
+#[test]
+fn test_turbofish_callback() {
+    assert_eq!(turbofish!(Vec<u8> => path_str!), strip("Vec::<u8>"));
+    assert_eq!(turbofish!(std::vec::Vec<u8> => path_str!), strip("std::vec::Vec::<u8>"));
+    assert_eq!(turbofish!(::std::vec::Vec::<u8> => path_str!), strip("::std::vec::Vec::<u8>"));
+    assert_eq!(turbofish!(a::B<T>::C<U, V>::d => path_str!), strip("a::B::<T>::C::<U, V>::d"));
+    assert_eq!(turbofish!(Box<dyn Fn(u8) -> u8> => path_str!), strip("Box::<dyn Fn(u8) -> u8>"));
+    assert_eq!(turbofish!(<T as Trait<U>>::Out<V> => path_str!), strip("<T as Trait<U>>::Out::<V>"));
+    assert_eq!(turbofish!(Plain => path_str!), strip("Plain"));
+}
+
+#[test]
+fn test_turbofish_macro_rules() {
+    macro_rules! construct {
+        ($ty:ty) => {
+            turbofish!($ty::new)()
+        };
+    }
+
+    assert_eq!(construct!(Pair<u8, u16>), Pair(0, 0));
+    assert_eq!(construct!(Vec<char>), vec![]);
+    assert_eq!(construct!(String), "");
+}
@@ -0,0 +1,66 @@
+//  TYPE IDENT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `type_ident!()`-macro.
+//
+
+use macro_toolkit::type_ident;
+
+
+/***** HELPERS *****/
+/// Stringifies the generated identifier.
+macro_rules! ident_str {
+    ($ident:ident) => {
+        stringify!($ident)
+    };
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_type_ident() {
+    assert_eq!(type_ident!(u8 => ident_str!()), "u8");
+    assert_eq!(type_ident!(Vec<Option<u8>> => ident_str!()), "Vec_Option_u8");
+    assert_eq!(type_ident!(&'a mut str => ident_str!()), "ref_mut_str");
+    assert_eq!(type_ident!(*const [u8] => ident_str!()), "ptr_const_slice_u8");
+    assert_eq!(type_ident!((u8, (), [bool; 3]) => ident_str!()), "tuple_u8_unit_array_bool_3");
+    assert_eq!(type_ident!(::std::collections::HashMap<String, u8> => ident_str!()), "std_collections_HashMap_String_u8");
+    assert_eq!(type_ident!(<T as Iterator>::Item => ident_str!()), "T_as_Iterator_Item");
+    assert_eq!(type_ident!(Box<dyn Fn(u8, u16) -> ! + Send> => ident_str!()), "Box_dyn_Fn_u8_u16_ret_never_Send");
+    assert_eq!(type_ident!(fn(r#type) => ident_str!()), "fn_type");
+}
+
+#[test]
+fn test_type_ident_separators() {
+    assert_eq!(type_ident!(sep = "__", std::vec::Vec<u8> => ident_str!()), "std__vec__Vec__u8");
+    assert_eq!(type_ident!(path_sep = "", std::vec::Vec<u8> => ident_str!()), "stdvecVec_u8");
+    assert_eq!(type_ident!(path_sep = "_", sep = "", Vec<std::vec::Vec<u8>> => ident_str!()), "Vecstd_vec_Vecu8");
+}
+
+#[test]
+fn test_type_ident_item() {
+    macro_rules! make_fn {
+        ($name:ident) => {
+            #[allow(non_snake_case)]
+            fn $name() -> &'static str { stringify!($name) }
+        };
+    }
+
+    type_ident!(sep = "_of_", Option<Result<u8, u16>> => make_fn!());
+    assert_eq!(Option_of_Result_of_u8_of_u16(), "Option_of_Result_of_u8_of_u16");
+}
+
+#[test]
+fn test_type_ident_macro_rules() {
+    macro_rules! type_names {
+        ($sep:literal; $($ty:ty),*) => {
+            [$(type_ident!(sep = $sep, $ty => ident_str!())),*]
+        };
+    }
+
+    assert_eq!(type_names!(""; u8, Vec<u8>, &'static [u8]), ["u8", "Vecu8", "refsliceu8"]);
+}
@@ -0,0 +1,45 @@
+//  TYPENUM LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `typenum_lit!()`-macro.
+//
+
+use macro_toolkit::typenum_lit;
+use typenum::{Integer, Unsigned};
+
+
+/***** TESTS *****/
+#[test]
+fn test_typenum_lit_unsigned() {
+    assert_eq!(<typenum_lit!(0)>::U64, 0);
+    assert_eq!(<typenum_lit!(1)>::U64, 1);
+    assert_eq!(<typenum_lit!(42u8)>::U64, 42);
+    assert_eq!(<typenum_lit!(0xFFFF_FFFF_FFFF)>::U64, 0xFFFF_FFFF_FFFF);
+}
+
+#[test]
+fn test_typenum_lit_signed() {
+    assert_eq!(<typenum_lit!(-1)>::I64, -1);
+    assert_eq!(<typenum_lit!(int 0)>::I64, 0);
+    assert_eq!(<typenum_lit!(int 1024)>::I64, 1024);
+}
+
+#[test]
+fn test_typenum_lit_alias() {
+    let _: typenum::U1024 = <typenum_lit!(alias 1024)>::default();
+    let _: typenum::P7 = <typenum_lit!(alias int 7)>::default();
+    let _: typenum::Z0 = <typenum_lit!(alias int 0)>::default();
+}
+
+#[test]
+fn test_typenum_lit_macro() {
+    macro_rules! value {
+        ($lit:literal) => {
+            <typenum_lit!($lit)>::I32
+        };
+    }
+
+    assert_eq!(value!(-5), -5);
+    assert_eq!(value!(5), 5);
+}
@@ -0,0 +1,43 @@
+//  UNIQUE ID.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `unique_id!()`-macro.
+//
+
+use macro_toolkit::unique_id;
+
+
+/***** TESTS *****/
+#[test]
+fn test_unique_id_default() {
+    let mut ids: [u64; 4] = [unique_id!(), unique_id!(), unique_id!(), unique_id!()];
+    ids.sort();
+    for i in 1..ids.len() {
+        assert_ne!(ids[i - 1], ids[i]);
+    }
+}
+
+#[test]
+fn test_unique_id_scopes() {
+    // These scopes are only used here, so we know exactly which values they hand out
+    let mut a: [u8; 3] = [unique_id!(test_a), unique_id!(test_a), unique_id!(test_a)];
+    let b: [u8; 1] = [unique_id!(test_b)];
+    a.sort();
+    assert_eq!(a, [0, 1, 2]);
+    assert_eq!(b, [0]);
+}
+
+#[test]
+fn test_unique_id_macro() {
+    macro_rules! slots {
+        ($($name:ident),*) => {
+            $(const $name: usize = unique_id!(test_slots);)*
+        };
+    }
+    slots!(FOO, BAR, BAZ);
+
+    let mut slots: [usize; 3] = [FOO, BAR, BAZ];
+    slots.sort();
+    assert_eq!(slots, [0, 1, 2]);
+}
@@ -0,0 +1,40 @@
+//  UUID LIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `uuid_lit!()`-macro.
+//
+
+use macro_toolkit::uuid_lit;
+
+
+/***** TESTS *****/
+#[test]
+fn test_uuid_lit_formats() {
+    const UUID: u128 = 0x67e5504410b1426f9247bb680e5fe0c8;
+    assert_eq!(uuid_lit!("67e55044-10b1-426f-9247-bb680e5fe0c8"), UUID);
+    assert_eq!(uuid_lit!("67e5504410b1426f9247bb680e5fe0c8"), UUID);
+    assert_eq!(uuid_lit!("{67e55044-10b1-426f-9247-bb680e5fe0c8}"), UUID);
+    assert_eq!(uuid_lit!("urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"), UUID);
+    assert_eq!(uuid_lit!(u128 "67E55044-10B1-426F-9247-BB680E5FE0C8"), UUID);
+}
+
+#[test]
+fn test_uuid_lit_bytes() {
+    assert_eq!(uuid_lit!(bytes "00112233-4455-6677-8899-aabbccddeeff"), [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff
+    ]);
+    assert_eq!(uuid_lit!(bytes "00000000-0000-0000-0000-000000000000"), [0; 16]);
+}
+
+#[test]
+fn test_uuid_lit_macro() {
+    macro_rules! service {
+        ($name:ident = $uuid:literal) => {
+            const $name: u128 = uuid_lit!($uuid);
+        };
+    }
+
+    service!(BATTERY = "0000180f-0000-1000-8000-00805f9b34fb");
+    assert_eq!(BATTERY, 0x0000180f_0000_1000_8000_00805f9b34fb);
+}
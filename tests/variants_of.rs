@@ -0,0 +1,98 @@
+//  VARIANTS OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `variants_of!()`-macro.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use macro_toolkit::variants_of;
+
+
+/***** HELPERS *****/
+/// Removes all whitespace from the given strings, as `stringify!()` does not guarantee it.
+fn strip<const N: usize>(parts: [&str; N]) -> [String; N] { parts.map(|part| part.split_whitespace().collect()) }
+
+/// Stringifies the name of an enum and every part of its variants.
+macro_rules! variants_str {
+    (
+        name = [$($name:tt)*], generics = [$($generics:tt)*], where = [$($where:tt)*],
+        variants = [$({ attrs = [$($attrs:tt)*], name = [$variant:ident], kind = [$kind:ident], fields = [$($fields:tt)*], pattern = [$($pattern:tt)*], discriminant = [$($disc:tt)*], index = [$index:literal], }),*],
+    ) => {
+        (stringify!($($name)*), [$([stringify!($($attrs)*), stringify!($variant), stringify!($kind), stringify!($($fields)*), stringify!($($pattern)*), stringify!($($disc)*), stringify!($index)]),*])
+    };
+}
+
+/// Implements [`Display`] for an enum by writing the name of every variant in lowercase.
+macro_rules! display {
+    (@impl name = [$name:ident], generics = [$($generics:tt)*], where = [$($where:tt)*], variants = [$({ attrs = [$($attrs:tt)*], name = [$variant:ident], kind = [$kind:ident], fields = [$($fields:tt)*], pattern = [$($pattern:tt)*], $($rest:tt)* }),*],) => {
+        impl<$($generics)*> Display for $name<$($generics)*> where $($where)* {
+            fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+                match self {
+                    $(Self::$variant $($pattern)* => write!(f, "{}", stringify!($variant).to_lowercase()),)*
+                }
+            }
+        }
+    };
+    ($item:item) => {
+        $item
+        variants_of!($item => display!(@impl));
+    };
+}
+
+display! {
+    #[allow(dead_code)]
+    enum Either<L, R> where L: Clone {
+        Left(L),
+        Right { value: R },
+        Neither,
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_variants_of_template() {
+    let names: [&str; 3] = variants_of!(enum Foo { A, B(u8), C { c: u16 } } => { [#( stringify!(#name) ),*] });
+    assert_eq!(names, ["A", "B", "C"]);
+    let kinds: [&str; 3] = variants_of!(enum Foo { A, B(u8), C { c: u16 } } => { [#( stringify!(#kind) ),*] });
+    assert_eq!(kinds, ["unit", "tuple", "named"]);
+    let discs: [u32; 3] = variants_of!(enum Foo { A = 1, B = 1 << 2, C = 42 } => { [#( #discriminant ),*] });
+    assert_eq!(discs, [1, 4, 42]);
+    let indices: [usize; 0] = variants_of!(enum Foo {} => { [#( #index, )*] });
+    assert_eq!(indices, []);
+}
+
+#[test]
+fn test_variants_of_callback() {
+    let (name, variants) = variants_of!(pub enum Foo<T> { #[doc = "A"] A, B(T, Vec<T>) = 2, C { c: u16 } } => variants_str!());
+    assert_eq!(name, "Foo");
+    assert_eq!(variants.map(strip), [
+        strip(["#[doc = \"A\"]", "A", "unit", "", "", "", "0"]),
+        strip(["", "B", "tuple", "(T, Vec<T>)", "(..)", "2", "1"]),
+        strip(["", "C", "named", "{ c: u16 }", "{ .. }", "", "2"]),
+    ]);
+}
+
+#[test]
+fn test_variants_of_dispatch() {
+    assert_eq!(Either::<u8, u8>::Left(1).to_string(), "left");
+    assert_eq!(Either::<u8, u8>::Right { value: 2 }.to_string(), "right");
+    assert_eq!(Either::<u8, u8>::Neither.to_string(), "neither");
+}
+
+#[test]
+fn test_variants_of_macro_rules() {
+    macro_rules! variant_count {
+        ($item:item) => {
+            variants_of!($item => { 0 #( + { let _ = #index; 1 } )* })
+        };
+    }
+
+    assert_eq!(variant_count!(enum Foo { A, B, C }), 3);
+    assert_eq!(variant_count!(enum Bar {}), 0);
+}
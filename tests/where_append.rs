@@ -0,0 +1,93 @@
+//  WHERE APPEND.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases & tests the `where_append!()`-macro.
+//
+
+use std::fmt::Debug;
+
+use macro_toolkit::where_append;
+
+
+/***** HELPERS *****/
+/// Stringifies the parts given to it, without whitespace.
+macro_rules! parts_str {
+    (generics = [$($generics:tt)*], where_clause = [$($where:tt)*],) => {
+        (strip(stringify!($($generics)*)), strip(stringify!($($where)*)))
+    };
+}
+
+/// Removes all whitespace from a string.
+fn strip(s: &str) -> String { s.chars().filter(|c| !c.is_whitespace()).collect() }
+
+where_append! {
+    [T: Clone]
+    /// A function with generics.
+    pub fn duplicate<T>(value: &T) -> (T, T) where T: Debug {
+        (value.clone(), value.clone())
+    }
+}
+
+where_append! {
+    [T: Default]
+    #[derive(Debug, PartialEq)]
+    struct Tuple<T>(T);
+}
+
+where_append! {
+    [T: Default,]
+    #[derive(Debug, PartialEq)]
+    struct Named<T> where T: Clone, {
+        value: T,
+    }
+}
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+where_append! {
+    [T: Debug]
+    impl<T> Describe for Named<T> where T: Clone + Default {
+        fn describe(&self) -> String { format!("{:?}", self.value) }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_where_append_items() {
+    assert_eq!(duplicate(&1), (1, 1));
+    assert_eq!(Tuple(1), Tuple(1));
+    assert_eq!(Named { value: 1 }.describe(), "1");
+}
+
+#[test]
+fn test_where_append_callback() {
+    assert_eq!(where_append!([T: Clone] => parts_str!), (strip(""), strip("where T: Clone")));
+    assert_eq!(where_append!([] <T> => parts_str!), (strip("<T>"), strip("")));
+    assert_eq!(where_append!([U: Debug] <T, U> where T: Clone => parts_str!), (strip("<T, U>"), strip("where T: Clone, U: Debug")));
+    assert_eq!(where_append!([U: Debug, V: Copy] <T = u8> where T: Clone, => parts_str!), (strip("<T = u8>"), strip("where T: Clone, U: Debug, V: Copy")));
+}
+
+#[test]
+fn test_where_append_macro_rules() {
+    macro_rules! impl_describe {
+        ($name:ident [$($params:tt)*] $(where $($preds:tt)*)?) => {
+            where_append! {
+                [T: Debug]
+                impl<$($params)*> Describe for $name<$($params)*> $(where $($preds)*)? {
+                    fn describe(&self) -> String { format!("{:?}", self.0) }
+                }
+            }
+        };
+    }
+
+    struct Local<T>(T);
+    impl_describe!(Local[T] where T: Default);
+    assert_eq!(Local(42).describe(), "42");
+}